@@ -0,0 +1,767 @@
+# DevHealth message catalog: German
+
+## Progress and status messages
+
+check-start = Gesundheitsprüfung läuft für: { $path }
+scan-start = Starte umfassenden Scan für: { $path }
+scan-git = Git-Repositorys werden gescannt...
+scan-deps = Abhängigkeiten werden geprüft...
+scan-audit = Abhängigkeiten werden auf bekannte Schwachstellen geprüft...
+scan-system = Systemressourcen werden überwacht...
+scan-containers = Vergleiche Dev-Container-Toolchains...
+scan-monorepo = Analysiere Monorepo-Workspaces...
+scan-release = Prüfe Release-Versionen...
+scan-cargo = Prüfe Rust-Editionen und MSRV...
+scan-runtimes = Prüfe Node-Versionsmanager und festgelegte Laufzeiten...
+scan-lost-work = Suche verlorene Commits in den Reflogs...
+scan-sizes = Messe Repository-Größen...
+scan-deep = Führe Linter und Builds aus...
+scan-nothing = Keine Scanner aktiviert. Verwende --git, --deps, --system, --audit, --containers, --monorepo, --release, --cargo oder --all, oder liste Scanner unter [scan] default in devhealth.toml.
+scan-auto = Automatisch gewählte Scanner: { $flags }
+scan-auto-nothing = Keine Repositorys oder Projekte gefunden, die Standard-Scanner laufen
+scan-auto-cargo-only = Nur Cargo-Projekte gefunden, die tiefen Rust-Prüfungen kommen hinzu
+scan-auto-no-advisories = Offline und ohne lokale Advisory-Datenbank, das Audit wird übersprungen; zuerst devhealth advisory-db sync ausführen
+scan-auto-network-share = { $path } liegt auf einer Netzwerkfreigabe, der System-Scan wird übersprungen
+scan-auto-incomplete = Suche nach { $seconds } Sekunden beendet; Scanner für tiefer liegende Projekte fehlen eventuell
+tag-filter = Gefiltert nach Tag: { $tags }
+offline-header = Offline-Modus: Netzwerkzugriff war für diesen Lauf deaktiviert
+offline-nothing-skipped = Es wurden keine netzwerkabhängigen Prüfungen angefordert
+offline-skipped = Übersprungen: { $check }
+
+## Advisory database
+
+advisory-importing = Importiere Advisory-Datenbank aus: { $path }
+advisory-imported = Lokale Advisory-Datenbank enthält jetzt { $count } Advisories ({ $path })
+advisory-fetching = Lade Advisories für Abhängigkeiten unter: { $path }
+advisory-fetching-details = Lade { $advisories } Advisory(s) für { $packages } Paket(e)...
+advisory-saved = { $count } Advisories in { $path } gespeichert
+
+## Tags
+
+tag-added = { $repo } getaggt: { $tags }
+tag-removed = Tags von { $repo } entfernt
+tag-not-found = { $repo } hat keinen der angegebenen Tags
+tag-none-defined = Keine Tags definiert. Verwende `devhealth tag add <pfad> <tag>...` oder einen [tags]-Abschnitt in devhealth.toml.
+tag-from-config = { $tag } (Konfiguration): { $patterns }
+
+## Shared report elements
+
+summary = Übersicht
+tip = Tipp:
+tips = Tipps:
+errors = Fehler
+age-today = heute
+age-one-day = vor 1 Tag
+age-days = vor { $days } Tagen
+
+## Quick check
+
+check-manifests = Abhängigkeiten: { $manifests } Manifeste in { $projects } Projekten
+check-no-manifests = Abhängigkeiten: keine Manifestdateien gefunden
+check-partial = (unvollständig: Zeitbudget überschritten)
+system-snapshot = System: Speicher { $memory }, Festplatte { $disk }
+system-free-of = { $free } frei von { $total }
+system-unknown = unbekannt
+wsl-windows-drive = { $path } liegt auf dem Windows-Laufwerk { $drive }:, das WSL um ein Vielfaches langsamer liest als das eigene Dateisystem. Verschiebe Projekte nach WSL (z. B. ~/dev), um schneller zu scannen.
+
+## Git scanner
+
+git-none = Keine Git-Repositorys gefunden
+git-health = Zustand der Git-Repositorys ({ $percent }%)
+git-total = Repositorys gesamt
+git-clean = Sauber
+git-dirty = Geändert
+git-error = Fehler
+git-details = Repository-Details
+git-column-name = Repository
+git-column-branch = Branch
+git-column-status = Status
+git-column-ahead-behind = Voraus/Zurück
+git-column-last-commit = Letzter Commit
+git-column-size = Größe
+git-column-score = Punkte
+git-on-branch = auf
+git-last-commit = (letzter Commit { $age })
+git-dirty-since = (geändert { $age })
+git-lost-commit = verlorener Commit { $hash } „{ $subject }“ ({ $age })
+git-lost-commit-recover = (wiederherstellen: { $command })
+git-ci-failing = CI fehlgeschlagen
+git-ci-passed = CI ✓
+git-ci-failed = CI ✗
+git-ci-running = CI …
+git-ci-no-runs = CI –
+git-ci-unknown = CI ?
+git-stale-forks = Veraltete Forks
+git-fork-behind = { $count } hinter { $upstream }
+git-archived-upstream = Upstream archiviert
+git-archived = archiviert
+git-archived-local-work = auf der Forge archiviert, enthält aber lokale Arbeit: woanders sichern oder den Klon behalten
+git-archived-removable = auf der Forge archiviert, nichts Lokales ginge verloren: kann entfernt werden
+git-tip-forks = Führe { $command } aus, um Forks auf ihren Upstream vorzuspulen
+git-review-issues = Review-Probleme
+git-review-no-pr = { $branch }: kein offener Pull Request
+git-review-unresolved = { $pr } { $branch }: { $count } ungelöste Review-Diskussion(en)
+git-review-merged = { $pr } { $branch }: gemergt, lokaler Branch existiert noch
+git-tip-merged-branches = Lösche lokale Branches gemergter Pull Requests mit { $command }
+git-credential-issues = Zugangsdaten
+git-exposures = Offengelegte Dateien
+git-extra-clones = Zusätzliche Klone
+git-total-size = Gesamtgröße
+git-growing-artifacts = Wachsende Artefakte
+git-stale-artifacts = Veraltete Artefakte
+git-stale-branches = Veraltete Branches
+git-old-stashes = Alte Stashes
+git-credential-no-helper = { $remote }: HTTPS-Remote ohne Credential-Helper
+git-credential-store = { $remote }: Passwort im Klartext vom store-Helper gespeichert
+git-credential-embedded = { $remote }: Passwort oder Token in der Remote-URL
+git-credential-ssh = (SSH: { $url })
+git-exposure-git-directory = .git ist für andere Benutzer lesbar
+git-exposure-committed = { $kind } { $path } ist committet
+git-exposure-readable = { $kind } { $path } ist für andere Benutzer lesbar
+git-exposure-fix = Behebung: { $command }
+git-secret-env-file = .env-Datei
+git-secret-private-key = Privater Schlüssel
+git-nested-untracked = in { $outer }, das es weder verfolgt noch ignoriert
+git-nested-gitlink = in { $outer } als bloßer Commit-Verweis, nicht als Submodul
+git-nested-fix = dort ignorieren: { $command }
+git-duplicate-freshest = aktuellster von { $total } Klonen von { $identity }
+git-duplicate-stale = veralteter Klon von { $identity }, aktuellster in { $freshest }
+git-duplicate-copy = weiterer Klon von { $identity }, auch in { $freshest }
+git-duplicate-unique-work = übertrage die Arbeit vor dem Löschen dorthin
+git-duplicate-removable = nichts ungepusht, kann gelöscht werden
+git-size-breakdown = Arbeitsverzeichnis { $working_tree }, .git { $git }, Build-Artefakte { $artifacts }
+git-artifacts-growing = Build-Artefakte seit dem Scan { $age } um { $growth } gewachsen, jetzt { $size }
+git-stale-artifact = { $dir }/ belegt { $size }, zuletzt geschrieben { $age }
+git-stale-artifact-outdated = (Quellen seitdem geändert)
+git-stale-branch-merged = Branch { $branch } ist in den Standard-Branch gemergt (letzter Commit { $age })
+git-stale-branch-inactive = Branch { $branch } ist inaktiv (letzter Commit { $age })
+git-stale-branch-delete = (löschen: { $command })
+git-stale-repos = Veraltete Repos
+git-stale-repos-title = { $count } Repositories ohne Commits seit Monaten
+git-stale-repo-local-work = (enthält lokale Arbeit)
+git-stale-repos-hint = Verlassene Klone archivieren oder löschen; die Schwelle ist stale_repo_months unter [scan]
+git-stashes = { $count } Stash(es), der älteste von { $age }
+}, ältester { $age }
+git-tip-credentials = Stelle Remotes mit { $ssh } auf SSH um oder nutze einen Credential-Manager mit { $helper }
+git-tip-dirty = Verwende { $commit } oder { $stash }, um geänderte Repositorys zu bereinigen
+git-tip-duplicates = Behalte einen Klon pro Projekt: übertrage ungepushte Arbeit in den aktuellsten Klon und lösche die anderen
+git-tip-artifacts = Gib Platz wachsender Build-Artefakte mit { $cargo } oder { $node } frei
+git-tip-stale-artifacts = Prüfe veraltete Build-Artefakte mit { $command } und entferne sie dann ohne --dry-run
+git-tip-stale-branches = Lösche gemergte Branches mit { $merged }; prüfe vor { $stale }, dass verwaiste Branches nichts Wichtiges enthalten
+git-tip-old-stashes = Prüfe Stashes, die älter als eine Woche sind, mit { $list } und stelle einen mit { $apply } wieder her oder verwirf ihn mit { $drop }
+repo-title = Repository: { $name }
+repo-path = Pfad
+repo-status = Status
+repo-branch = Branch
+repo-uncommitted-since = Nicht committete Änderungen
+repo-unpushed = Ungepushte Commits
+repo-last-commit = Letzter Commit
+repo-score = Gesundheitswert
+repo-no-commits = keine Commits
+yes = ja
+no = nein
+
+## Dependency scanner
+
+deps-none = Keine Abhängigkeitsdateien gefunden
+deps-title = Abhängigkeitsanalyse ({ $count } Ökosysteme)
+deps-projects = Projekte gesamt
+deps-total = Abhängigkeiten ges.
+deps-ecosystems = Ökosysteme
+deps-breakdown = Aufteilung nach Ökosystem
+deps-details = Projektdetails
+deps-count = { $count } Abhängigkeiten
+deps-count-short = ({ $count } Abh.)
+deps-more = { $count } weitere Abhängigkeiten
+deps-lockfiles = Lockfile-Probleme
+deps-lockfile-missing = { $manifest }: kein Lockfile, { $action } ausführen
+deps-lockfile-out-of-sync = { $manifest }: Lockfile nicht synchron ({ $dependencies }), { $action } ausführen
+deps-lockfile-stale = { $manifest }: Lockfile älter als das Manifest, { $action } ausführen
+deps-build-system-detected = Build-System erkannt, externe Abhängigkeiten nicht aufgelistet
+deps-go-version = { $manifest }: go { $version }
+deps-go-toolchain = Toolchain { $toolchain }
+deps-go-replace = { $manifest }: ersetzt { $replace }
+deps-go-replace-local = { $manifest }: ersetzt { $replace } (lokales Verzeichnis)
+deps-go-exclude = { $manifest }: schließt { $module } { $version } aus
+deps-tip-updates = Nach Updates suchen
+deps-tip-updates-how = Update-Befehle des Paketmanagers ausführen
+deps-tip-security = Sicherheitsscan
+deps-tip-security-how = Werkzeuge wie cargo audit, npm audit oder safety verwenden
+deps-tip-unused = Ungenutzte Abhängigkeiten
+deps-tip-unused-how = Nicht verwendete Abhängigkeiten entfernen
+
+## Vulnerability audit
+
+audit-title = Schwachstellenprüfung ({ $count } gefunden)
+audit-checked = Geprüft
+audit-vulnerable-runtime = Verwundbar (Laufzeit)
+audit-vulnerable-development = Verwundbar (Entwicklung/Build)
+audit-unresolved = Ungelöste Versionen
+audit-ignored = Ignoriert
+audit-database = Advisory-Datenbank
+audit-synced = synchronisiert { $age }
+audit-unavailable = nicht verfügbar
+audit-details-runtime = Schwachstellen zur Laufzeit
+audit-details-development = Schwachstellen in Entwicklung und Build
+audit-fixed-in = behoben in { $version }
+audit-no-fix = keine Korrektur verfügbar
+audit-refresh-failed = Advisories konnten nicht aktualisiert werden, die lokale Datenbank wird verwendet: { $error }
+
+## Dev container scanner
+containers-none = Keine Dev-Container gefunden
+containers-title = Dev-Container ({ $count } gefunden)
+containers-environments = Umgebungen
+containers-running = Laufend
+containers-mismatches = Abweichende Versionen
+containers-details = Umgebungen
+containers-state-running = läuft ({ $id })
+containers-state-stopped = läuft nicht
+containers-state-no-docker = Docker nicht verfügbar
+containers-tool = Host { $host } / Container { $container }
+
+## Monorepo workspaces
+workspace-none = Keine Monorepo-Workspaces gefunden
+workspace-title = Monorepo-Workspaces ({ $count } gefunden)
+workspace-roots = Workspaces
+workspace-packages = Pakete
+workspace-conflicts = Versionskonflikte
+workspace-cycles = Abhängigkeitszyklen
+workspace-details = Workspaces
+workspace-package-count = { $count } Pakete
+workspace-package-deps = { $count } Abh., { $internal } intern
+workspace-conflict = { $name }:
+workspace-cycle = Abhängigkeitszyklus zwischen { $packages }
+workspace-fan-in = Am häufigsten benötigt:
+workspace-fan-out = Meiste Abhängigkeiten:
+workspace-graph-saved = Workspace-Abhängigkeitsgraph nach { $path } geschrieben
+
+## Release-Versionen
+release-none = Keine versionierten Repositories gefunden
+release-title = Release-Versionen ({ $count } gefunden)
+release-repos = Repositories
+release-released = Veröffentlicht
+release-bumps-missing = Fehlende Erhöhungen
+release-mismatches = Tag-Abweichungen
+release-details = Versionen
+release-status-released = { $version } entspricht Tag { $tag }
+release-status-bump-missing = { $version } entspricht Tag { $tag }, aber HEAD liegt { $commits } Commit(s) voraus, ohne Versionserhöhung
+release-status-unreleased = { $version } ist neuer als Tag { $tag }, noch nicht veröffentlicht
+release-status-mismatch = { $version } passt nicht zum neuesten Tag { $tag }
+release-status-untagged = { $version }, noch keine Tags
+release-stale-changelogs = Veraltete Changelogs
+release-status-unversioned = keine Version in einem Stamm-Manifest
+release-changelog-missing = kein Changelog
+release-changelog-keep = (Keep a Changelog)
+release-changelog-current = { $file } ist aktuell
+release-changelog-stale = { $file } seit { $commits } Commit(s) und { $tags } Tag(s) nicht aktualisiert
+release-changelog-no-entry = { $file } hat keinen Eintrag für Tag { $tag }
+
+## Cargo-Projekte
+cargo-none = Keine Cargo-Pakete gefunden
+cargo-title = Cargo-Projekte ({ $count } gefunden)
+cargo-projects = Pakete
+cargo-missing-msrv = Fehlende MSRV
+cargo-old-editions = Alte Editionen
+cargo-msrv-too-low = MSRV zu niedrig
+cargo-details = Pakete
+cargo-versions = Edition { $edition }, MSRV { $msrv }
+cargo-requirement = Abhängigkeiten benötigen Rust { $required } ({ $dependency } { $version })
+cargo-finding-no-msrv = keine rust-version angegeben
+cargo-finding-old-edition = Edition { $edition } ist älter als 2021
+cargo-finding-msrv-too-low = rust-version { $declared } ist niedriger als die von { $dependency } benötigte { $required }
+cargo-feature-issues = Feature-Probleme
+cargo-features = Features: { $features }
+cargo-feature-deps = { $feature } aktiviert { $dependencies }
+cargo-finding-unused-feature = Feature { $feature } aktiviert keine Abhängigkeit und wird nirgends verwendet
+cargo-finding-heavy-default = Standard-Feature zieht { $dependency } mit { $crates } Crates herein
+
+## Tiefenprüfung
+deep-none = Keine Projekte für die Tiefenprüfung gefunden
+deep-title = Tiefenprüfung (durchschnittliche Gesundheit { $score }%)
+deep-projects = Projekte
+deep-failing = Fehlgeschlagen
+deep-warnings = Warnungen
+deep-details = Projekte
+deep-check-passed = bestanden
+deep-check-warnings = bestanden mit { $warnings } Warnung(en)
+deep-check-failed = fehlgeschlagen mit { $errors } Fehler(n), { $warnings } Warnung(en)
+deep-check-unformatted = { $files } unformatierte Datei(en)
+deep-check-timed-out = Zeitlimit überschritten
+deep-check-unavailable = nicht installiert
+
+## Korrekturen
+fix-no-forks = Keine Forks gefunden
+fix-fork-up-to-date = { $repo } ist auf dem Stand von { $upstream }
+fix-fork-would-sync = { $repo } liegt { $count } Commit(s) hinter { $upstream }
+fix-fork-synced = { $repo } um { $count } Commit(s) von { $upstream } vorgespult
+fix-fork-synced-forge = { $repo } auf der Forge synchronisiert: { $message } (pull aktualisiert den lokalen Klon)
+fix-fork-dirty = { $repo } hat nicht committete Änderungen auf { $branch }; übersprungen
+fix-fork-no-branch = { $repo } hat keinen lokalen Branch { $branch }; übersprungen
+fix-fork-failed = { $repo } konnte nicht synchronisiert werden: { $error }
+clean-none = Keine veralteten Build-Artefakte gefunden
+clean-would-remove = Würde { $path } löschen ({ $size }, zuletzt geschrieben { $age })
+clean-removed = { $path } gelöscht ({ $size })
+clean-failed = { $path } konnte nicht gelöscht werden: { $error }
+clean-would-free = { $size } würden frei
+clean-freed = { $size } freigegeben
+clean-skipped-tracked = { $path } übersprungen: git ignoriert es nicht oder es enthält versionierte Dateien
+clean-confirm = { $count } Verzeichnis(se) löschen und { $size } freigeben?
+clean-cancelled = Nichts gelöscht
+clean-needs-yes = Build-Artefakte werden nicht ohne Bestätigung gelöscht: außerhalb eines Terminals --yes angeben
+
+## Pin suggestions
+pins-title = Vorschläge zum Festlegen von Versionen ({ $count })
+pins-none = Keine offenen Versionsangaben gefunden
+pins-not-locked = nicht in der Lock-Datei
+pins-tip = Festlegen mit: devhealth deps pin-suggestions --patch | git apply
+
+## Dependency exposure
+exposure-title = Abhängigkeits-Exposition ({ $count } Projekte)
+exposure-packages = Pakete
+exposure-drifting = Mehrere Versionen
+exposure-vulnerable = Verwundbare Pakete
+exposure-most-exposed = { $package } ist das verwundbare Paket mit den meisten betroffenen Projekten ({ $count })
+exposure-most-used = Meistgenutzte Pakete (Top { $count })
+exposure-projects = ({ $count } Projekt(e))
+exposure-more = ... und { $count } weitere Pakete (--top 0 listet alle)
+exposure-fixed-in = behoben in { $version }
+exposure-no-fix = noch keine Korrektur
+exposure-no-vulnerable = Keine verwundbaren Pakete gefunden
+exposure-tip-audit = Mit --audit wird angezeigt, welche verwundbaren Pakete die meisten Projekte betreffen
+
+find-title = { $package }: { $count } Abhängigkeit(en) in { $projects } Projekt(en)
+find-none = Kein Projekt verwendet { $package }
+find-locked = (gesperrt auf { $version })
+
+impact-title = Auswirkung des Upgrades: { $package } → { $version }
+impact-none = Kein Projekt und kein gesperrtes Paket hängt von { $package } ab
+impact-constraints = Einschränkungen
+impact-blocking = Blockierend
+impact-unknown = Unbekannt
+impact-compatible = Kompatibel
+impact-ready = Alle Einschränkungen erlauben { $version }
+impact-blocked-projects = { $count } Projekt(e) können noch nicht auf { $version } aktualisieren
+impact-direct = deklariert
+impact-via = über { $package }
+impact-tip-unknown = Unbekannte Einschränkungen stammen aus Git- oder Pfadquellen, nie heruntergeladenen Paketen oder nicht prüfbaren Bereichen
+
+align-title = Versionsangleichung
+align-none = Kein Paket wird in weit auseinanderliegenden Versionen verwendet
+align-misaligned = Abweichende Pakete
+align-changes = Zu ändernde Einschränkungen
+align-spread-major = Hauptversionen auseinander
+align-spread-minor = Nebenversionen auseinander
+align-spread-patch = Patchversionen auseinander
+align-target = auf { $version } angleichen ({ $satisfied } von { $total } Einschränkung(en) erlauben sie)
+align-tip = Bei gelben Versionen muss die Einschränkung geändert werden, um die vorgeschlagene Version zu erreichen
+
+## License inventory
+licenses-title = Lizenzinventar ({ $count } Lizenzen)
+licenses-unknown = Unbekannte Lizenzen
+licenses-packages = ({ $count } Paket(e))
+licenses-tip-unknown = Unbekannte Lizenzen gehören zu nie heruntergeladenen Paketen; Projekte zuerst bauen oder installieren
+
+## Advisory-Überwachung
+watch-watching = Überwache { $dependencies } Abhängigkeiten in { $projects } Projekt(en)
+watch-baseline = Erste Prüfung: { $count } bekannte Advisory(s) erfasst; ab jetzt werden nur neue gemeldet
+watch-no-new = Keine neuen Advisories seit der letzten Prüfung
+watch-new-title = Neue Advisories ({ $count })
+watch-notification-title = DevHealth: { $count } neue(s) Advisory(s) für deine Abhängigkeiten
+watch-next-check = Nächste Prüfung in { $minutes } Minute(n)
+watch-inventory-failed = Abhängigkeitsinventar konnte nicht gespeichert werden: { $error }
+
+## Organisationsrichtlinie
+policy-applied = Wende Organisationsrichtlinie von { $url } an
+policy-syncing = Synchronisiere Organisationsrichtlinie von { $url }...
+policy-updated = Richtlinie aktualisiert und Signatur geprüft
+policy-not-modified = Richtlinie ist aktuell
+policy-not-synced = Noch keine Richtlinie synchronisiert; führe devhealth policy sync aus
+policy-source = Richtlinie von { $url } (synchronisiert { $synced })
+policy-required-scanners = Erforderliche Scanner: { $scanners }
+policy-fail-on = Immer fehlschlagen bei: { $kinds }
+policy-deep = Tiefenprüfung: { $timeout }s Zeitlimit, Tests { $tests }
+policy-none = keine
+policy-refresh-failed = Organisationsrichtlinie konnte nicht aktualisiert werden: { $error }
+
+## Team reports
+merge-loading = Führe { $count } Ergebnisdatei(en) zusammen
+merge-title = Team-Gesundheit ({ $count } Maschinen)
+merge-machines = Maschinen
+merge-repositories = Repositories
+merge-dirty = Geänderte Kopien
+merge-unpushed = Ungepushte Kopien
+merge-exposed = Betroffene Maschinen
+merge-advisories = Sicherheitshinweise
+merge-vulnerabilities = Betroffenheit durch Schwachstellen
+merge-dependencies = Verwendete Abhängigkeiten
+merge-repo = { $name }: { $machines } Maschine(n), { $dirty } geändert
+merge-copy = { $status } auf { $branch }, Bewertung { $score }
+merge-vulnerability = { $package } { $versions }: { $machines }/{ $total } Maschinen ({ $names }), { $projects } Projekt(e)
+merge-fixed-in = behoben in { $version }
+merge-dependency = { $name }: { $machines }/{ $total } Maschinen, { $projects } Projekt(e)
+merge-versions = Versionen { $versions }
+merge-more = … und { $count } weitere
+
+## Snapshots
+snapshot-written = Snapshot gespeichert unter { $path }
+snapshot-title = Snapshot
+snapshot-version = DevHealth-Version
+snapshot-created = Erstellt
+snapshot-platform = Plattform
+snapshot-path = Geprüfter Pfad
+snapshot-config = Konfiguration
+snapshot-config-included = enthalten
+snapshot-config-none = keine
+snapshot-tools = Werkzeuge
+snapshot-tool-missing = nicht installiert
+snapshot-configuration = Konfiguration
+snapshot-diff-title = Unterschiede der Snapshots
+snapshot-diff-range = Vergleich eines Snapshots von { $before } mit einem von { $after }
+snapshot-diff-none = Die Snapshots unterscheiden sich nicht
+snapshot-diff-config-changed = Die Konfiguration wurde geändert
+snapshot-diff-repositories = Repositories
+snapshot-diff-vulnerabilities = Schwachstellen
+snapshot-diff-dependencies = Abhängigkeiten
+snapshot-diff-absent = fehlt
+
+## Acknowledgements
+ack-added = Bestätigt: { $finding }
+ack-removed = { $finding } wird wieder gemeldet
+ack-not-found = { $finding } ist nicht bestätigt
+ack-none = Keine Befunde bestätigt
+ack-expired = abgelaufen
+ack-until = bis { $date }
+ack-indefinitely = unbefristet
+ack-hidden = { $count } bestätigte(r) Befund(e) ausgeblendet (devhealth ack list)
+
+## Findings
+findings-title = Befunde nach Schweregrad ({ $count })
+severity-info = Info
+severity-warn = Warnung
+severity-error = Fehler
+severity-critical = Kritisch
+check-uncommitted-changes = Nicht committete Änderungen
+check-unpushed-commits = Nicht gepushte Commits
+check-repository-error = Repository konnte nicht untersucht werden
+check-ci-failed = CI schlägt auf dem Standard-Branch fehl
+check-fork-behind = Fork liegt hinter seinem Upstream zurück
+check-unresolved-reviews = Offene Review-Threads
+check-merged-branches = Branches gemergter Pull Requests existieren noch
+check-credential-helper-missing = HTTPS-Remote ohne Credential-Helper
+check-plaintext-credentials = Zugangsdaten im Klartext gespeichert
+check-embedded-credentials = Zugangsdaten in der Remote-URL
+check-git-config-recommendation = Eine empfohlene globale git-Einstellung fehlt
+check-stale-uncommitted-changes = Nicht committete Änderungen liegen zu lange herum
+check-lost-work = Neue Commits liegen auf keinem Branch mehr
+check-readable-git-directory = Andere Benutzer können das .git-Verzeichnis lesen
+check-committed-secret = Eine .env-Datei oder ein privater Schlüssel ist committet
+check-readable-secret = Andere Benutzer können eine .env-Datei oder einen privaten Schlüssel lesen
+check-nested-repository = Repository in einem anderen, weder Submodul noch ignoriert
+check-duplicate-clone = Es gibt einen weiteren Klon desselben Repositorys
+check-growing-artifacts = Build-Artefakte sind seit dem letzten Scan um ein Gigabyte oder mehr gewachsen
+check-stale-artifacts = Gigabytes an Build-Artefakten seit Monaten unverändert
+check-stale-branch = Lokaler Branch in den Standard-Branch gemergt oder seit Monaten ohne Commits
+check-old-stash = Stash-Einträge, die älter als eine Woche sind
+check-archived-upstream = Repository auf der Forge archiviert
+check-stale-repository = Repository seit Monaten ohne Commits
+check-manifest-error = Manifest konnte nicht gelesen werden
+check-vulnerable-dependency = Verwundbare Abhängigkeit
+check-vulnerable-dev-dependency = Verwundbare Entwicklungs- oder Build-Abhängigkeit
+check-missing-lockfile = Lockfile fehlt
+check-lockfile-out-of-sync = Lockfile erfüllt das Manifest nicht
+check-stale-lockfile = Manifest nach dem Lockfile geändert
+check-go-replace = Go-Modul ersetzt
+check-toolchain-mismatch = Toolchains von Host und Container weichen ab
+check-container-config-error = Dev-Container-Konfiguration nicht lesbar
+check-runtime-version-mismatch = Aktive Laufzeitversion entspricht nicht der Vorgabe des Projekts
+check-shadowed-runtime = Ein node im PATH verdeckt einen aus einem anderen Versionsmanager
+check-workspace-manifest-error = Manifest eines Workspace-Pakets nicht lesbar
+check-dependency-cycle = Abhängigkeitszyklus im Workspace
+check-version-conflict = Abhängigkeit im Workspace uneinheitlich festgelegt
+check-version-mismatch = Manifest-Version weicht vom letzten Tag ab
+check-version-bump-missing = Version seit dem letzten Tag nicht erhöht
+check-stale-changelog = Changelog ist veraltet
+check-duplicate-binary = Entwicklerwerkzeug liegt mehrfach mit verschiedenen Versionen im PATH
+check-clock-skew = Systemuhr weicht um eine Minute oder mehr ab
+check-broken-ca-bundle = CA-Zertifikatsbündel fehlt oder ist defekt
+check-proxy-mismatch = Entwicklerwerkzeuge nutzen verschiedene Proxys
+check-tls-interception = Ein Proxy bricht TLS-Verbindungen zu Registries auf
+check-unexcluded-build-directory = Ein Build-Verzeichnis wird vom Virenscanner geprüft oder von Spotlight indiziert
+check-low-inodes = Dem gescannten Laufwerk gehen die Inodes aus
+check-low-file-limit = Das Limit offener Dateien ist zu niedrig für Datei-Watcher und Builds
+check-non-utf8-locale = Locale oder Konsolen-Codepage ist nicht UTF-8
+check-missing-rust-version = Keine rust-version angegeben
+check-outdated-edition = Veraltete Rust-Edition
+check-rust-version-too-low = Angegebene rust-version ist zu niedrig
+check-unused-feature = Ungenutztes Cargo-Feature
+check-heavy-default-dependency = Schwere optionale Abhängigkeit standardmäßig aktiv
+check-check-failed = Prüfung fehlgeschlagen
+check-check-timed-out = Zeitüberschreitung bei Prüfung
+rules-title = Regelkatalog ({ $count })
+
+## Run metadata
+run-header = DevHealth { $version } · { $time } · { $host }
+run-details = Pfade: { $paths } · Scanner: { $scanners } · Konfiguration: { $config }
+run-unknown-host = unbekannter Host
+run-no-scanners = keine
+run-no-config = Standard
+
+## Overview
+overview-title = Übersicht ({ $repos } Repo(s), { $projects } Projekt(e))
+overview-empty = Nichts anzuzeigen; mit --git oder --deps scannen
+overview-healthy = gesund
+overview-dirty = geändert
+overview-error = Fehler
+overview-unpushed = nicht gepusht
+overview-ci-failed = CI
+overview-behind = { $count } zurück
+overview-vulnerabilities = { $count } Schwachstelle(n)
+overview-lockfiles = { $count } Lockfile-Problem(e)
+overview-errors = { $count } Fehler
+
+## Changes since the last run
+changes-title = Änderungen seit dem letzten Scan ({ $age }):
+changes-none = Keine Änderungen seit dem letzten Scan ({ $age })
+changes-dirty = { $delta } Repository(s) mit Änderungen
+changes-unpushed = { $delta } Repository(s) mit nicht gepushten Commits
+changes-vulnerabilities = { $delta } verwundbare Abhängigkeit(en)
+changes-findings = { $delta } Befund(e) der Stufe { $severity }
+
+## Editor integration
+init-created = { $path } erstellt
+init-updated = { $entries } zu { $path } hinzugefügt
+init-unchanged = { $path } ist bereits eingerichtet
+init-skipped = { $path } nicht geändert: kein reines JSON (Kommentare entfernen oder DevHealth-Einträge von Hand ergänzen)
+
+## First-run setup
+setup-detected = Auf diesem Rechner gefunden: { $tools }
+setup-ask-roots = Wo liegt Ihr Code? Verzeichnisse, durch Kommas getrennt
+setup-no-directory = { $path } ist kein Verzeichnis
+setup-invalid = Keine gültige Antwort, bitte erneut versuchen
+setup-ask-scanners = Scanner, die `devhealth scan` standardmäßig ausführt ({ $available })
+setup-ask-history = Verlauf führen, um Änderungen seit dem letzten Lauf zu zeigen?
+setup-ask-fail-level = Ab welchem Schweregrad fehlschlagen (info, warn, error, critical oder none)?
+setup-ask-stale-days = Tage, nach denen nicht committete Änderungen als veraltet gelten
+setup-ask-schedule = Abhängigkeiten täglich scannen und auf neue Advisories prüfen (cron)?
+setup-ask-hooks = Einen pre-push-Hook mit `devhealth check` zu den Repositories dort hinzufügen?
+setup-scheduled = Täglichen cron-Eintrag für Abhängigkeitsscans und Advisory-Prüfungen hinzugefügt
+setup-already-scheduled = Die crontab enthält den DevHealth-Eintrag bereits
+setup-hooks-installed = pre-push-Hook zu { $count } Repo(s) hinzugefügt
+setup-hooks-skipped = Vorhandenen pre-push-Hook von { $count } Repo(s) beibehalten
+setup-next = Als Nächstes: `devhealth check` für einen ersten Bericht ausführen oder { $path } bearbeiten
+
+## Self-update
+update-up-to-date = DevHealth { $version } ist die neueste Version
+update-available = DevHealth { $version } ist verfügbar (installiert: { $current }); `devhealth self-update` ausführen
+update-done = DevHealth von { $from } auf { $to } aktualisiert
+update-notice = DevHealth { $version } ist verfügbar: `devhealth self-update` ausführen (Hinweise mit `check = false` in [update] abschalten)
+
+## Crash reports
+crash-saved = DevHealth ist unerwartet fehlgeschlagen. Ein geschwärztes Diagnosepaket wurde unter { $path } gespeichert; bitte hängen Sie es an einen Fehlerbericht unter https://github.com/Art-Jashari/DevHealth/issues an
+debug-bundle-written = Diagnosepaket unter { $path } geschrieben
+debug-bundle-crash = Es enthält den letzten Absturz: { $message }
+debug-bundle-no-crash = Kein Absturz aufgezeichnet; das Paket beschreibt die aktuelle Umgebung
+
+## State lock
+lock-waiting = Warte, bis ein anderer DevHealth-Lauf seinen Zustand aktualisiert hat…
+lock-waiting-pid = Warte, bis ein anderer DevHealth-Lauf (Prozess { $pid }) seinen Zustand aktualisiert hat…
+
+## History
+history-pruned = { $runs } Scan(s) und { $sizes } gespeicherte Repository-Größe(n) entfernt
+history-prune-dry-run = Würde { $runs } Scan(s) und { $sizes } gespeicherte Repository-Größe(n) entfernen
+history-empty = Keine gespeicherten Scans passen; mit history = true unter [scan] zeichnen check und scan ihre Läufe auf
+history-list-title = Scan-Verlauf ({ $count } Läufe)
+history-run-findings = { $count } Befund(e)
+history-no-findings = Keine passenden Befunde in den gespeicherten Scans
+history-timelines-title = Verlauf der Befunde ({ $count })
+history-period-fixed = aufgetreten { $appeared }, behoben { $fixed } nach { $days } Tag(en)
+history-period-open = aufgetreten { $appeared } ({ $age }), besteht noch
+
+## Team statistics
+stats-written = Anonyme Statistik unter { $path } geschrieben; sie enthält nur Anzahlen, keine Repository-Namen oder Pfade
+
+## Email digest
+
+digest-subject = DevHealth-Übersicht { $date }: { $count } Befund(e)
+digest-title = DevHealth-Übersicht vom { $date }
+digest-scope = Geprüft: { $paths }
+digest-summary-title = Zusammenfassung
+digest-repositories = { $count } Repository(s) geprüft
+digest-dirty = { $count } Repository(s) mit nicht committeten Änderungen
+digest-unpushed = { $count } Repository(s) mit nicht gepushten Commits
+digest-vulnerabilities = { $count } verwundbare Abhängigkeit(en)
+digest-findings = { $count } Befund(e) der Stufe { $severity }
+digest-changes-title = Änderungen seit { $date }
+digest-no-changes = Nichts hat sich geändert.
+digest-attention-title = Handlungsbedarf ({ $count })
+digest-all-clear = Kein Handlungsbedarf.
+digest-more = …und { $count } weitere(r) Befund(e); devhealth check zeigt alle.
+digest-footer = Gesendet von DevHealth { $version }
+digest-sent = Übersicht an { $recipients } gesendet
+digest-scheduled = Übersicht in deiner crontab eingeplant: { $schedule }
+digest-schedule-exists = Deine crontab enthält bereits einen DevHealth-Übersichtseintrag; bearbeite ihn mit crontab -e
+
+## Desktop notifications
+
+notify-title = DevHealth: { $count } neue(r) Befund(e)
+notify-line = { $severity }: { $check } in { $count } Projekt(en)
+notify-more = …und { $count } weitere Art(en) von Befunden
+
+## Zugangsdaten
+auth-github = GitHub-Token
+auth-gitlab = GitLab-Token
+auth-smtp = SMTP-Passwort
+auth-prompt = { $secret }:
+auth-stored = { $secret } im Schlüsselbund des Systems gespeichert
+auth-removed = { $secret } aus dem Schlüsselbund des Systems entfernt
+auth-not-stored = Kein { $secret } im Schlüsselbund des Systems gespeichert
+
+## Konfiguration
+config-check-valid = { $path } ist gültig
+config-check-missing = { $path } existiert nicht; es gelten die Standardwerte
+config-check-problems = { $count } Problem(e) in { $path }:
+config-show-header = Wirksame Konfiguration: Standardwerte, dann die Konfigurationsdatei, dann Umgebung und Optionen
+config-file = Konfigurationsdatei: { $path }
+config-file-missing = Konfigurationsdatei: { $path } (nicht gefunden, es gelten die Standardwerte)
+config-override = { $key } aus { $source }
+config-source-locale = der Locale
+config-source-keyring = dem Schlüsselbund des Systems
+config-offline = --offline: netzwerkabhängige Prüfungen werden übersprungen
+config-policy = Die Richtlinie der Organisation ersetzt [deep] und ergänzt Pflichtprüfungen bei check und scan
+
+## Lokal fehlende Forge-Repositorys
+
+repos-listing = Repositorys auf GitHub und GitLab werden aufgelistet...
+repos-missing-title = { $count } von { $total } Forge-Repositorys sind nicht lokal geklont
+repos-none-missing = Alle { $total } Forge-Repositorys sind lokal geklont
+repos-fork = (Fork)
+repos-archived = (archiviert)
+repos-tip-clone = Klone eines mit { $command }
+repos-cloning = { $repo } wird geklont...
+repos-cloned = { $repo } nach { $path } geklont
+repos-clone-failed = { $repo } konnte nicht geklont werden: { $error }
+
+## Outdated developer tools
+system-outdated-tools = { $manager }: { $count } veraltete(s) Entwicklerwerkzeug(e)
+system-outdated-tool = { $name } { $installed } → { $available }
+system-update-with = Aktualisieren: { $command }
+
+## Runtime versions
+runtime-title = Laufzeitversionen ({ $count } Projekte mit Vorgaben)
+runtime-managers = Versionsmanager
+runtime-active-node = Aktives node
+runtime-shadowed = Verdeckte nodes
+runtime-mismatched = Abweichungen
+runtime-details = Laufzeitdetails
+runtime-system = System
+runtime-unknown = unbekannt
+runtime-none = keine
+runtime-not-installed = nicht installiert
+runtime-binary = { $source } node { $version } ({ $path })
+runtime-shadows = { $active } verdeckt { $shadowed }
+runtime-on-path-active = Im PATH (aktiv):
+runtime-on-path = Im PATH:
+runtime-pin = { $file }: { $runtime } { $requested }, aktiv { $active }
+
+## Duplicate binaries
+system-duplicate-binary = { $name }: { $count } Versionen im PATH, { $path } wird ausgeführt
+system-binary-wins = { $path } { $version } (wird ausgeführt)
+system-binary-shadowed = { $path } { $version } (verdeckt)
+
+## Clock and certificates
+system-clock = Uhr: { $state }
+system-clock-synced = synchronisiert
+system-clock-unsynced = nicht synchronisiert
+system-clock-offset = Abweichung { $offset } s
+system-ca-bundle = CA-Bündel { $path } ({ $source }): { $count } Zertifikate
+system-ca-system = System
+system-ca-missing = existiert nicht
+system-ca-empty = enthält kein Zertifikat
+system-ca-truncated = endet mitten in einem Zertifikat
+system-ca-incomplete = ersetzt den Zertifikatsspeicher, aber ohne die öffentlichen Stammzertifikate
+
+## Proxy settings
+system-proxy = Proxy: { $variables }
+system-proxy-no-variables = keine Proxy-Variablen gesetzt
+system-proxy-tool = { $tool }: { $proxy } ({ $source })
+system-proxy-direct = { $tool }: direkt
+system-tls-genuine = { $endpoint }: Zertifikat von { $issuer }
+system-tls-intercepted = { $endpoint }: aufgebrochen von { $issuer }
+system-tls-untrusted = { $endpoint }: Zertifikat nicht vertrauenswürdig, vermutlich ein aufbrechender Proxy
+system-tls-unreachable = { $endpoint }: nicht erreichbar
+
+## Benchmarks
+bench-net-title = Netzwerk-Benchmark
+bench-net-title-label = Netzwerk-Benchmark ({ $label })
+bench-net-running = Messe { $count } Endpunkte, je { $rounds } Durchläufe...
+bench-net-timing = DNS { $dns } · Verbindung { $connect } · TLS { $tls }
+bench-net-failed = fehlgeschlagen: { $error }
+bench-net-no-address = Hostname hat keine Adresse
+bench-net-usual = üblich { $timing } ({ $runs } Läufe)
+bench-net-slower = langsamer als üblich
+bench-power-warning = Im Akkubetrieb oder gedrosselt: Ergebnisse fallen langsamer aus als sonst und sind nicht mit anderen Läufen vergleichbar
+bench-saved = Gespeichert in { $path }
+bench-history-title = Verlauf des Netzwerk-Benchmarks ({ $count } Läufe)
+bench-history-empty = Noch kein Netzwerk-Benchmark aufgezeichnet; führe devhealth bench net aus
+bench-history-label = { $label }: { $runs } Läufe, zuletzt { $date }
+bench-unlabeled = ohne Label
+bench-fs-title = Dateisystem-Benchmark: { $path }
+bench-fs-title-type = Dateisystem-Benchmark: { $path } ({ $filesystem })
+bench-fs-running = Erzeuge, lese und entferne { $files } Dateien in { $path }...
+bench-fs-create = erzeugen
+bench-fs-stat = stat
+bench-fs-read = lesen
+bench-fs-rate = { $rate } Dateien/s
+bench-fs-rates = erzeugen { $create } · stat { $stat } · lesen { $read } Dateien/s
+bench-fs-usual = üblich { $rate }
+bench-fs-fast = Schnell genug für git und Compiler
+bench-fs-network = Auf einem Netzlaufwerk: jeder Dateizugriff ist ein Netzwerk-Roundtrip. Repositories und Build-Ausgaben gehören auf eine lokale Platte.
+bench-fs-wsl-drive = Ein Windows-Laufwerk unter WSL: jeder Dateizugriff geht nach Windows. Repositories gehören ins WSL-Dateisystem (~/).
+bench-fs-slow-local = Zugriffe auf kleine Dateien sind auf einer lokalen Platte langsam, meist wegen Virenscanner-Echtzeitprüfung oder Indizierung. Nimm dieses Verzeichnis von beiden aus.
+bench-fs-history-title = Verlauf des Dateisystem-Benchmarks ({ $count } Läufe)
+bench-fs-history-empty = Noch kein Dateisystem-Benchmark aufgezeichnet; führe devhealth bench fs aus
+
+## Antivirus and indexer exclusions
+system-exclusions = { $scanner } prüft { $count } von { $total } Build-Verzeichnissen.
+system-exclusions-defender-impact = Die Echtzeitprüfung macht Builds meist 20–50 % langsamer; nimm sie in einer Administrator-PowerShell aus:
+system-exclusions-spotlight-impact = Das Indizieren der Build-Ausgabe konkurriert mit dem Build um Platte und CPU, meist 5–15 % der Build-Zeit; nimm sie aus mit:
+system-exclusions-ok = { $scanner } lässt alle { $count } Build-Verzeichnisse aus
+system-exclusions-unverified = { $count } weitere ließen sich ohne Administratorrechte nicht prüfen
+
+## Power
+system-power = Stromversorgung: { $state }
+system-power-ac = am Netz
+system-power-battery = im Akkubetrieb
+system-power-level = { $percent } %
+system-power-charging = { $percent } %, lädt
+system-power-low-power = Stromsparmodus
+system-power-balanced = ausbalancierter Modus
+system-power-performance = Höchstleistungsmodus
+system-power-cpu-limit = CPU auf { $percent } % begrenzt
+system-power-slow = Builds und Benchmarks laufen langsamer als sonst; für vergleichbare Zeiten Netzteil anschließen und Stromsparmodus verlassen
+system-power-drain = Akku fast leer: ein langer Scan oder Build kann ihn entladen, vorher Netzteil anschließen
+
+## Limits
+system-limits = Limits: { $limits }
+system-limits-inodes = { $percent } % der Inodes frei
+system-limits-files = offene Dateien { $soft } (hart { $hard })
+system-limits-unlimited = unbegrenzt
+system-limits-inodes-hint = Fast keine Inodes mehr frei: finde die Verzeichnisse mit den meisten Dateien mit { $command } und leere Build-Caches wie target und node_modules
+system-limits-files-hint = Datei-Watcher und Builds können mit "Too many open files" scheitern; erhöhe das Limit in deinem Shell-Profil (~/.bashrc, ~/.zshrc) mit { $command }
+system-limits-hard-hint-command = Das harte Limit ist zu niedrig dafür; erhöhe das Systemlimit mit { $command }
+system-limits-hard-hint-linux = Das harte Limit ist zu niedrig dafür; trage "* hard nofile 65536" in /etc/security/limits.conf ein (oder setze DefaultLimitNOFILE=65536 in /etc/systemd/system.conf) und melde dich neu an
+
+## Locale
+system-locale = Locale: { $locale } ({ $encoding })
+system-locale-no-encoding = Locale: { $locale }
+system-locale-unset = nicht gesetzt
+system-locale-problem-unset = Programme fallen auf die ASCII-Locale C zurück
+system-locale-problem-not-utf8 = nicht UTF-8, Compiler und git verstümmeln Nicht-ASCII-Text
+system-locale-problem-not-installed = nennt UTF-8, ist aber nicht installiert
+system-locale-problem-code-page = die Konsole nutzt kein UTF-8 (Codepage 65001)
+system-locale-fix = Beheben mit { $command }
+
+## Git configuration
+system-git-config = Git-Konfiguration { $path }: { $count } empfohlene Einstellungen fehlen
+system-git-config-setting = { $key } ist { $current }: { $command }
+system-git-config-unset = nicht gesetzt
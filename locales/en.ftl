@@ -0,0 +1,771 @@
+# DevHealth message catalog: English (reference locale)
+#
+# Every message used by DevHealth must be defined here. Other locales may
+# omit messages; missing ones fall back to English. Placeholders are written
+# as { $name } and must match the names used in this file.
+
+## Progress and status messages
+
+check-start = Running health check on: { $path }
+scan-start = Starting comprehensive scan on: { $path }
+scan-git = Scanning Git repositories...
+scan-deps = Checking dependencies...
+scan-audit = Auditing dependencies for known vulnerabilities...
+scan-system = Monitoring system resources...
+scan-containers = Comparing dev container toolchains...
+scan-monorepo = Analyzing monorepo workspaces...
+scan-release = Checking release versions...
+scan-cargo = Auditing Rust editions and MSRV...
+scan-runtimes = Checking Node version managers and pinned runtimes...
+scan-lost-work = Looking for lost commits in reflogs...
+scan-sizes = Measuring repository sizes...
+scan-deep = Running linters and builds...
+scan-nothing = No scanners enabled. Use --git, --deps, --system, --audit, --containers, --monorepo, --release, --cargo or --all, or list scanners under [scan] default in devhealth.toml.
+scan-auto = Auto-selected scanners: { $flags }
+scan-auto-nothing = Found no repositories or projects, running the default scanners
+scan-auto-cargo-only = Only Cargo projects found, adding the deep Rust checks
+scan-auto-no-advisories = Offline without a local advisory database, skipping the audit; run devhealth advisory-db sync first
+scan-auto-network-share = { $path } is on a network share, skipping the system scan
+scan-auto-incomplete = Stopped looking after { $seconds } seconds; scanners for projects further down may be missing
+tag-filter = Filtering by tag: { $tags }
+offline-header = Offline mode: network access was disabled for this run
+offline-nothing-skipped = No network-dependent checks were requested
+offline-skipped = Skipped: { $check }
+
+## Advisory database
+
+advisory-importing = Importing advisory database from: { $path }
+advisory-imported = Local advisory database now holds { $count } advisories ({ $path })
+advisory-fetching = Fetching advisories for dependencies under: { $path }
+advisory-fetching-details = Fetching { $advisories } advisory(s) for { $packages } package(s)...
+advisory-saved = Saved { $count } advisories to { $path }
+
+## Tags
+
+tag-added = Tagged { $repo }: { $tags }
+tag-removed = Removed tags from { $repo }
+tag-not-found = { $repo } has none of the given tags
+tag-none-defined = No tags defined. Use `devhealth tag add <path> <tag>...` or a [tags] section in devhealth.toml.
+tag-from-config = { $tag } (config): { $patterns }
+
+## Shared report elements
+
+summary = Summary
+tip = Tip:
+tips = Tips:
+errors = Errors
+age-today = today
+age-one-day = 1 day ago
+age-days = { $days } days ago
+
+## Quick check
+
+check-manifests = Dependencies: { $manifests } manifests in { $projects } projects
+check-no-manifests = Dependencies: no manifest files found
+check-partial = (partial: time budget exceeded)
+system-snapshot = System: memory { $memory }, disk { $disk }
+system-free-of = { $free } free of { $total }
+system-unknown = unknown
+wsl-windows-drive = { $path } is on Windows drive { $drive }:, which WSL reads many times slower than its own file system. Move projects into WSL (e.g. ~/dev) for faster scans.
+
+## Git scanner
+
+git-none = No git repositories found
+git-health = Git Repository Health ({ $percent }%)
+git-total = Total Repositories
+git-clean = Clean
+git-dirty = Dirty
+git-error = Error
+git-details = Repository Details
+git-column-name = Repository
+git-column-branch = Branch
+git-column-status = Status
+git-column-ahead-behind = Ahead/Behind
+git-column-last-commit = Last Commit
+git-column-size = Size
+git-column-score = Score
+git-on-branch = on
+git-last-commit = (last commit { $age })
+git-dirty-since = (changed { $age })
+git-lost-commit = lost commit { $hash } “{ $subject }” ({ $age })
+git-lost-commit-recover = (recover: { $command })
+git-ci-failing = CI Failing
+git-ci-passed = CI ✓
+git-ci-failed = CI ✗
+git-ci-running = CI …
+git-ci-no-runs = CI –
+git-ci-unknown = CI ?
+git-stale-forks = Stale Forks
+git-fork-behind = { $count } behind { $upstream }
+git-archived-upstream = Archived Upstream
+git-archived = archived
+git-archived-local-work = archived on the forge, but holds local work: push it elsewhere or keep the clone
+git-archived-removable = archived on the forge, nothing local would be lost: safe to remove
+git-tip-forks = Run { $command } to fast-forward forks to their upstream
+git-review-issues = Review Issues
+git-review-no-pr = { $branch }: no open pull request
+git-review-unresolved = { $pr } { $branch }: { $count } unresolved review thread(s)
+git-review-merged = { $pr } { $branch }: merged, local branch still exists
+git-tip-merged-branches = Delete local branches of merged pull requests with { $command }
+git-credential-issues = Credential Issues
+git-exposures = Exposed Files
+git-extra-clones = Extra Clones
+git-total-size = Total Size
+git-growing-artifacts = Growing Artifacts
+git-stale-artifacts = Stale Artifacts
+git-stale-branches = Stale Branches
+git-old-stashes = Old Stashes
+git-credential-no-helper = { $remote }: HTTPS remote without a credential helper
+git-credential-store = { $remote }: password stored in plain text by the store helper
+git-credential-embedded = { $remote }: password or token embedded in the remote URL
+git-credential-ssh = (SSH: { $url })
+git-exposure-git-directory = .git is readable by other users
+git-exposure-committed = { $kind } { $path } is committed
+git-exposure-readable = { $kind } { $path } is readable by other users
+git-exposure-fix = fix: { $command }
+git-secret-env-file = .env file
+git-secret-private-key = Private key
+git-nested-untracked = inside { $outer }, which neither tracks nor ignores it
+git-nested-gitlink = inside { $outer } as a bare commit reference, not a submodule
+git-nested-fix = ignore it there: { $command }
+git-duplicate-freshest = freshest of { $total } clones of { $identity }
+git-duplicate-stale = stale clone of { $identity }, newest in { $freshest }
+git-duplicate-copy = another clone of { $identity }, also in { $freshest }
+git-duplicate-unique-work = push or commit its work there before removing it
+git-duplicate-removable = nothing unpushed, safe to remove
+git-size-breakdown = working tree { $working_tree }, .git { $git }, build artifacts { $artifacts }
+git-artifacts-growing = build artifacts grew { $growth } since the scan { $age }, now { $size }
+git-stale-artifact = { $dir }/ holds { $size }, last written { $age }
+git-stale-artifact-outdated = (sources changed since)
+git-stale-branch-merged = branch { $branch } is merged into the default branch (last commit { $age })
+git-stale-branch-inactive = branch { $branch } is inactive (last commit { $age })
+git-stale-branch-delete = (delete: { $command })
+git-stale-repos = Stale Repos
+git-stale-repos-title = { $count } repositories without commits for months
+git-stale-repo-local-work = (holds local work)
+git-stale-repos-hint = Archive or delete abandoned clones; the threshold is stale_repo_months under [scan]
+git-stashes = { $count } stash(es), oldest stashed { $age }
+}, oldest { $age }
+git-tip-credentials = Switch remotes to SSH with { $ssh }, or use a credential manager with { $helper }
+git-tip-dirty = Use { $commit } or { $stash } to clean dirty repositories
+git-tip-duplicates = Keep one clone per project: move unpushed work to the freshest clone and remove the others
+git-tip-artifacts = Reclaim space from growing build artifacts with { $cargo } or { $node }
+git-tip-stale-artifacts = Review stale build artifacts with { $command }, then run it without --dry-run to remove them
+git-tip-stale-branches = Delete merged branches with { $merged }; check that abandoned branches hold nothing worth keeping before { $stale }
+git-tip-old-stashes = Review stashes older than a week with { $list }, then restore one with { $apply } or discard it with { $drop }
+repo-title = Repository: { $name }
+repo-path = Path
+repo-status = Status
+repo-branch = Branch
+repo-uncommitted-since = Uncommitted Changes
+repo-unpushed = Unpushed Commits
+repo-last-commit = Last Commit
+repo-score = Health Score
+repo-no-commits = no commits
+yes = yes
+no = no
+
+## Dependency scanner
+
+deps-none = No dependency files found
+deps-title = Dependency Analysis ({ $count } ecosystems)
+deps-projects = Total Projects
+deps-total = Total Dependencies
+deps-ecosystems = Ecosystems
+deps-breakdown = Ecosystem Breakdown
+deps-details = Project Details
+deps-count = { $count } dependencies
+deps-count-short = ({ $count } deps)
+deps-more = { $count } more dependencies
+deps-lockfiles = Lockfile Issues
+deps-lockfile-missing = { $manifest }: no lockfile, run { $action }
+deps-lockfile-out-of-sync = { $manifest }: lockfile out of sync ({ $dependencies }), run { $action }
+deps-lockfile-stale = { $manifest }: lockfile older than the manifest, run { $action }
+deps-build-system-detected = build system detected, external dependencies not listed
+deps-go-version = { $manifest }: go { $version }
+deps-go-toolchain = toolchain { $toolchain }
+deps-go-replace = { $manifest }: replaces { $replace }
+deps-go-replace-local = { $manifest }: replaces { $replace } (local directory)
+deps-go-exclude = { $manifest }: excludes { $module } { $version }
+deps-tip-updates = Check for updates
+deps-tip-updates-how = Run package manager update commands
+deps-tip-security = Security scan
+deps-tip-security-how = Use tools like cargo audit, npm audit, or safety
+deps-tip-unused = Clean unused deps
+deps-tip-unused-how = Remove dependencies you're not using
+
+## Vulnerability audit
+
+audit-title = Vulnerability Audit ({ $count } found)
+audit-checked = Checked
+audit-vulnerable-runtime = Vulnerable (runtime)
+audit-vulnerable-development = Vulnerable (dev/build)
+audit-unresolved = Unresolved Versions
+audit-ignored = Ignored
+audit-database = Advisory Database
+audit-synced = synced { $age }
+audit-unavailable = unavailable
+audit-details-runtime = Runtime vulnerabilities
+audit-details-development = Development and build vulnerabilities
+audit-fixed-in = fixed in { $version }
+audit-no-fix = no fix available
+audit-refresh-failed = Could not refresh advisories, using the local database: { $error }
+
+## Dev container scanner
+containers-none = No dev containers found
+containers-title = Dev Containers ({ $count } found)
+containers-environments = Environments
+containers-running = Running
+containers-mismatches = Version Mismatches
+containers-details = Environments
+containers-state-running = running ({ $id })
+containers-state-stopped = not running
+containers-state-no-docker = docker unavailable
+containers-tool = host { $host } / container { $container }
+
+## Monorepo workspaces
+workspace-none = No monorepo workspaces found
+workspace-title = Monorepo Workspaces ({ $count } found)
+workspace-roots = Workspaces
+workspace-packages = Packages
+workspace-conflicts = Version Conflicts
+workspace-cycles = Dependency Cycles
+workspace-details = Workspaces
+workspace-package-count = { $count } packages
+workspace-package-deps = { $count } deps, { $internal } internal
+workspace-conflict = { $name }:
+workspace-cycle = dependency cycle between { $packages }
+workspace-fan-in = Most depended on:
+workspace-fan-out = Most dependencies:
+workspace-graph-saved = Wrote the workspace dependency graph to { $path }
+
+## Release versions
+release-none = No versioned repositories found
+release-title = Release Versions ({ $count } found)
+release-repos = Repositories
+release-released = Released
+release-bumps-missing = Missing Bumps
+release-mismatches = Tag Mismatches
+release-details = Versions
+release-status-released = { $version } matches tag { $tag }
+release-status-bump-missing = { $version } matches tag { $tag }, but HEAD is { $commits } commit(s) ahead without a version bump
+release-status-unreleased = { $version } is ahead of tag { $tag }, not yet released
+release-status-mismatch = { $version } does not match the latest tag { $tag }
+release-status-untagged = { $version }, no tags yet
+release-stale-changelogs = Stale Changelogs
+release-status-unversioned = no version in a root manifest
+release-changelog-missing = no changelog
+release-changelog-keep = (Keep a Changelog)
+release-changelog-current = { $file } is up to date
+release-changelog-stale = { $file } not updated for { $commits } commit(s) and { $tags } tag(s)
+release-changelog-no-entry = { $file } has no entry for tag { $tag }
+
+## Cargo projects
+cargo-none = No Cargo packages found
+cargo-title = Cargo Projects ({ $count } found)
+cargo-projects = Packages
+cargo-missing-msrv = Missing MSRV
+cargo-old-editions = Old Editions
+cargo-msrv-too-low = MSRV Too Low
+cargo-details = Packages
+cargo-versions = edition { $edition }, MSRV { $msrv }
+cargo-requirement = dependencies need Rust { $required } ({ $dependency } { $version })
+cargo-finding-no-msrv = no rust-version declared
+cargo-finding-old-edition = edition { $edition } is older than 2021
+cargo-finding-msrv-too-low = rust-version { $declared } is lower than { $required } required by { $dependency }
+cargo-feature-issues = Feature Issues
+cargo-features = features: { $features }
+cargo-feature-deps = { $feature } enables { $dependencies }
+cargo-finding-unused-feature = feature { $feature } enables no dependency and is never referenced
+cargo-finding-heavy-default = default feature pulls in { $dependency } with { $crates } crates
+
+## Deep checks
+deep-none = No projects to check in depth
+deep-title = Deep Checks (average health { $score }%)
+deep-projects = Projects
+deep-failing = Failing
+deep-warnings = Warnings
+deep-details = Projects
+deep-check-passed = passed
+deep-check-warnings = passed with { $warnings } warning(s)
+deep-check-failed = failed with { $errors } error(s), { $warnings } warning(s)
+deep-check-unformatted = { $files } unformatted file(s)
+deep-check-timed-out = timed out
+deep-check-unavailable = not installed
+
+## Fixes
+fix-no-forks = No forks found
+fix-fork-up-to-date = { $repo } is up to date with { $upstream }
+fix-fork-would-sync = { $repo } is { $count } commit(s) behind { $upstream }
+fix-fork-synced = { $repo } fast-forwarded by { $count } commit(s) from { $upstream }
+fix-fork-synced-forge = { $repo } synced on the forge: { $message } (pull to update the local clone)
+fix-fork-dirty = { $repo } has uncommitted changes on { $branch }; skipped
+fix-fork-no-branch = { $repo } has no local { $branch } branch; skipped
+fix-fork-failed = Could not sync { $repo }: { $error }
+clean-none = No stale build artifacts found
+clean-would-remove = Would remove { $path } ({ $size }, last written { $age })
+clean-removed = Removed { $path } ({ $size })
+clean-failed = Could not remove { $path }: { $error }
+clean-would-free = { $size } would be freed
+clean-freed = Freed { $size }
+clean-skipped-tracked = Skipping { $path }: git does not ignore it or it holds tracked files
+clean-confirm = Remove { $count } directory(s), freeing { $size }?
+clean-cancelled = Nothing removed
+clean-needs-yes = Refusing to remove build output without confirmation: pass --yes when not running in a terminal
+
+## Pin suggestions
+pins-title = Pin Suggestions ({ $count })
+pins-none = No loose dependency constraints found
+pins-not-locked = not in the lockfile
+pins-tip = Apply the pins with: devhealth deps pin-suggestions --patch | git apply
+
+## Dependency exposure
+exposure-title = Dependency Exposure ({ $count } projects)
+exposure-packages = Packages
+exposure-drifting = Multiple versions
+exposure-vulnerable = Vulnerable packages
+exposure-most-exposed = { $package } is the vulnerable package affecting the most projects ({ $count })
+exposure-most-used = Most used packages (top { $count })
+exposure-projects = ({ $count } project(s))
+exposure-more = ... and { $count } more packages (use --top 0 to list all)
+exposure-fixed-in = fixed in { $version }
+exposure-no-fix = no fix yet
+exposure-no-vulnerable = No vulnerable packages found
+exposure-tip-audit = Add --audit to see which vulnerable packages affect the most projects
+
+find-title = { $package }: { $count } dependency(ies) in { $projects } project(s)
+find-none = No project uses { $package }
+find-locked = (locked { $version })
+
+impact-title = Upgrade impact: { $package } → { $version }
+impact-none = No project or locked package depends on { $package }
+impact-constraints = Constraints
+impact-blocking = Blocking
+impact-unknown = Unknown
+impact-compatible = Compatible
+impact-ready = Every constraint admits { $version }
+impact-blocked-projects = { $count } project(s) cannot upgrade to { $version } yet
+impact-direct = declared
+impact-via = via { $package }
+impact-tip-unknown = Unknown constraints come from git or path sources, packages that were never downloaded or ranges that cannot be checked
+
+align-title = Version Alignment
+align-none = No package is used at versions far apart
+align-misaligned = Misaligned packages
+align-changes = Constraints to change
+align-spread-major = major versions apart
+align-spread-minor = minor versions apart
+align-spread-patch = patch versions apart
+align-target = converge on { $version } ({ $satisfied } of { $total } constraint(s) admit it)
+align-tip = Versions in yellow need their constraint changed to reach the suggested version
+
+## License inventory
+licenses-title = License Inventory ({ $count } licenses)
+licenses-unknown = Unknown licenses
+licenses-packages = ({ $count } package(s))
+licenses-tip-unknown = Unknown licenses belong to packages that were never downloaded; build or install the projects first
+
+## Advisory watch
+watch-watching = Watching { $dependencies } dependencies in { $projects } project(s)
+watch-baseline = First check: recorded { $count } known advisory(ies); only new ones will be reported from now on
+watch-no-new = No new advisories since the last check
+watch-new-title = New Advisories ({ $count })
+watch-notification-title = DevHealth: { $count } new advisory(ies) for your dependencies
+watch-next-check = Next check in { $minutes } minute(s)
+watch-inventory-failed = Could not store the dependency inventory: { $error }
+
+## Organization policy
+policy-applied = Applying organization policy from { $url }
+policy-syncing = Syncing organization policy from { $url }...
+policy-updated = Policy updated and signature verified
+policy-not-modified = Policy is up to date
+policy-not-synced = No policy has been synced yet; run devhealth policy sync
+policy-source = Policy from { $url } (synced { $synced })
+policy-required-scanners = Required scanners: { $scanners }
+policy-fail-on = Always fail on: { $kinds }
+policy-deep = Deep checks: { $timeout }s timeout, tests { $tests }
+policy-none = none
+policy-refresh-failed = Could not refresh the organization policy: { $error }
+
+## Team reports
+merge-loading = Merging { $count } result file(s)
+merge-title = Team Health ({ $count } machines)
+merge-machines = Machines
+merge-repositories = Repositories
+merge-dirty = Dirty Copies
+merge-unpushed = Unpushed Copies
+merge-exposed = Exposed Machines
+merge-advisories = Advisories
+merge-vulnerabilities = Vulnerability Exposure
+merge-dependencies = Dependency Exposure
+merge-repo = { $name }: { $machines } machine(s), { $dirty } dirty
+merge-copy = { $status } on { $branch }, score { $score }
+merge-vulnerability = { $package } { $versions }: { $machines }/{ $total } machines ({ $names }), { $projects } project(s)
+merge-fixed-in = fixed in { $version }
+merge-dependency = { $name }: { $machines }/{ $total } machines, { $projects } project(s)
+merge-versions = versions { $versions }
+merge-more = … and { $count } more
+
+## Snapshots
+snapshot-written = Snapshot written to { $path }
+snapshot-title = Snapshot
+snapshot-version = DevHealth Version
+snapshot-created = Created
+snapshot-platform = Platform
+snapshot-path = Scanned Path
+snapshot-config = Configuration
+snapshot-config-included = included
+snapshot-config-none = none
+snapshot-tools = Tools
+snapshot-tool-missing = not installed
+snapshot-configuration = Configuration
+snapshot-diff-title = Snapshot Differences
+snapshot-diff-range = Comparing a snapshot from { $before } with one from { $after }
+snapshot-diff-none = The snapshots do not differ
+snapshot-diff-config-changed = The configuration changed
+snapshot-diff-repositories = Repositories
+snapshot-diff-vulnerabilities = Vulnerabilities
+snapshot-diff-dependencies = Dependencies
+snapshot-diff-absent = absent
+
+## Acknowledgements
+ack-added = Acknowledged { $finding }
+ack-removed = { $finding } will be reported again
+ack-not-found = { $finding } is not acknowledged
+ack-none = No findings are acknowledged
+ack-expired = expired
+ack-until = until { $date }
+ack-indefinitely = indefinitely
+ack-hidden = { $count } acknowledged finding(s) left out (devhealth ack list)
+
+## Findings
+findings-title = Findings by Severity ({ $count })
+severity-info = Info
+severity-warn = Warning
+severity-error = Error
+severity-critical = Critical
+check-uncommitted-changes = Uncommitted changes
+check-unpushed-commits = Unpushed commits
+check-repository-error = Repository could not be inspected
+check-ci-failed = CI fails on the default branch
+check-fork-behind = Fork is behind its upstream
+check-unresolved-reviews = Unresolved review threads
+check-merged-branches = Branches of merged pull requests still exist
+check-credential-helper-missing = HTTPS remote without a credential helper
+check-plaintext-credentials = Credentials stored in plain text
+check-embedded-credentials = Credentials embedded in the remote URL
+check-git-config-recommendation = A recommended global git setting is missing
+check-stale-uncommitted-changes = Uncommitted changes have sat for too long
+check-lost-work = Recent commits are no longer on any branch
+check-readable-git-directory = Other users can read the .git directory
+check-committed-secret = A .env file or private key is committed
+check-readable-secret = Other users can read a .env file or private key
+check-nested-repository = Repository nested in another without being a submodule or ignored
+check-duplicate-clone = Another clone of the same repository exists
+check-growing-artifacts = Build artifacts grew by a gigabyte or more since the previous scan
+check-stale-artifacts = Gigabytes of build artifacts untouched for months
+check-stale-branch = Local branch merged into the default branch or without commits for months
+check-old-stash = Stash entries older than a week
+check-archived-upstream = Repository archived on the forge
+check-stale-repository = Repository without commits for months
+check-manifest-error = Manifest could not be parsed
+check-vulnerable-dependency = Vulnerable dependency
+check-vulnerable-dev-dependency = Vulnerable development or build dependency
+check-missing-lockfile = Lockfile missing
+check-lockfile-out-of-sync = Lockfile does not satisfy the manifest
+check-stale-lockfile = Manifest changed after the lockfile
+check-go-replace = Go module replaced
+check-toolchain-mismatch = Host and container toolchains differ
+check-container-config-error = Dev container configuration could not be read
+check-runtime-version-mismatch = Active runtime version does not match the project's pin
+check-shadowed-runtime = A node on PATH hides one from another version manager
+check-workspace-manifest-error = Workspace package manifest could not be read
+check-dependency-cycle = Workspace dependency cycle
+check-version-conflict = Dependency pinned inconsistently across the workspace
+check-version-mismatch = Manifest version differs from the latest tag
+check-version-bump-missing = Version not bumped since the latest tag
+check-stale-changelog = Changelog is out of date
+check-duplicate-binary = Developer tool is on PATH more than once with different versions
+check-clock-skew = System clock is off by a minute or more
+check-broken-ca-bundle = CA certificate bundle is missing or broken
+check-proxy-mismatch = Developer tools use different proxies
+check-tls-interception = A proxy intercepts TLS connections to registries
+check-unexcluded-build-directory = A build directory is scanned by antivirus or indexed by Spotlight
+check-low-inodes = The scanned volume is running out of inodes
+check-low-file-limit = The open-file limit is too low for file watchers and builds
+check-non-utf8-locale = The locale or console code page is not UTF-8
+check-missing-rust-version = No rust-version declared
+check-outdated-edition = Outdated Rust edition
+check-rust-version-too-low = Declared rust-version is too low
+check-unused-feature = Unused Cargo feature
+check-heavy-default-dependency = Heavy optional dependency enabled by default
+check-check-failed = Check failed
+check-check-timed-out = Check timed out
+rules-title = Rule Catalog ({ $count })
+
+## Run metadata
+run-header = DevHealth { $version } · { $time } · { $host }
+run-details = Paths: { $paths } · Scanners: { $scanners } · Config: { $config }
+run-unknown-host = unknown host
+run-no-scanners = none
+run-no-config = defaults
+
+## Overview
+overview-title = Overview ({ $repos } repo(s), { $projects } project(s))
+overview-empty = Nothing to show; scan with --git or --deps
+overview-healthy = healthy
+overview-dirty = dirty
+overview-error = error
+overview-unpushed = unpushed
+overview-ci-failed = CI
+overview-behind = { $count } behind
+overview-vulnerabilities = { $count } vuln(s)
+overview-lockfiles = { $count } lockfile issue(s)
+overview-errors = { $count } error(s)
+
+## Changes since the last run
+changes-title = Changes since the last scan ({ $age }):
+changes-none = No changes since the last scan ({ $age })
+changes-dirty = { $delta } dirty repo(s)
+changes-unpushed = { $delta } repo(s) with unpushed commits
+changes-vulnerabilities = { $delta } vulnerable dependenc(ies)
+changes-findings = { $delta } { $severity } finding(s)
+
+## Editor integration
+init-created = Created { $path }
+init-updated = Added { $entries } to { $path }
+init-unchanged = { $path } is already set up
+init-skipped = Left { $path } alone: it is not plain JSON (remove its comments or add the DevHealth entries by hand)
+
+## First-run setup
+setup-detected = Detected on this machine: { $tools }
+setup-ask-roots = Where is your code? Directories, comma-separated
+setup-no-directory = { $path } is not a directory
+setup-invalid = Not a valid answer, please try again
+setup-ask-scanners = Scanners `devhealth scan` runs by default ({ $available })
+setup-ask-history = Keep a history to show what changed since the last run?
+setup-ask-fail-level = Fail from which severity (info, warn, error, critical or none)?
+setup-ask-stale-days = Days before uncommitted changes count as stale
+setup-ask-schedule = Scan dependencies daily and watch for new advisories (cron)?
+setup-ask-hooks = Add a pre-push hook running `devhealth check` to the repositories there?
+setup-scheduled = Added a daily cron entry for dependency scans and advisory checks
+setup-already-scheduled = The crontab already has the DevHealth entry
+setup-hooks-installed = Added a pre-push hook to { $count } repo(s)
+setup-hooks-skipped = Kept the existing pre-push hook of { $count } repo(s)
+setup-next = Next: run `devhealth check` for a first report, or edit { $path }
+
+## Self-update
+update-up-to-date = DevHealth { $version } is the latest release
+update-available = DevHealth { $version } is available (installed: { $current }); run `devhealth self-update`
+update-done = Updated DevHealth from { $from } to { $to }
+update-notice = DevHealth { $version } is available: run `devhealth self-update` (turn these notices off with `check = false` in [update])
+
+## Crash reports
+crash-saved = DevHealth failed unexpectedly. A redacted diagnostic bundle was saved to { $path }; please attach it to a bug report at https://github.com/Art-Jashari/DevHealth/issues
+debug-bundle-written = Diagnostic bundle written to { $path }
+debug-bundle-crash = It contains the last crash: { $message }
+debug-bundle-no-crash = No crash is recorded; the bundle describes the current environment
+
+## State lock
+lock-waiting = Waiting for another DevHealth run to finish updating its state…
+lock-waiting-pid = Waiting for another DevHealth run (process { $pid }) to finish updating its state…
+
+## History
+history-pruned = Dropped { $runs } scan run(s) and { $sizes } repository size record(s)
+history-prune-dry-run = Would drop { $runs } scan run(s) and { $sizes } repository size record(s)
+history-empty = No stored scans match; with history = true under [scan], check and scan record their runs
+history-list-title = Scan history ({ $count } runs)
+history-run-findings = { $count } finding(s)
+history-no-findings = No matching findings in the stored scans
+history-timelines-title = Finding timelines ({ $count })
+history-period-fixed = appeared { $appeared }, fixed { $fixed } after { $days } day(s)
+history-period-open = appeared { $appeared } ({ $age }), still present
+
+## Team statistics
+stats-written = Anonymous statistics written to { $path }; they hold counts only, no repository names or paths
+
+## Email digest
+
+digest-subject = DevHealth digest { $date }: { $count } finding(s)
+digest-title = DevHealth digest for { $date }
+digest-scope = Checked { $paths }
+digest-summary-title = Summary
+digest-repositories = { $count } repo(s) checked
+digest-dirty = { $count } repo(s) with uncommitted changes
+digest-unpushed = { $count } repo(s) with unpushed commits
+digest-vulnerabilities = { $count } vulnerable dependenc(ies)
+digest-findings = { $count } { $severity } finding(s)
+digest-changes-title = Changes since { $date }
+digest-no-changes = Nothing changed.
+digest-attention-title = Needs attention ({ $count })
+digest-all-clear = Nothing needs attention.
+digest-more = …and { $count } more finding(s); run devhealth check for all of them.
+digest-footer = Sent by DevHealth { $version }
+digest-sent = Digest sent to { $recipients }
+digest-scheduled = Digest scheduled in your crontab: { $schedule }
+digest-schedule-exists = Your crontab already has a DevHealth digest entry; edit it with crontab -e
+
+## Desktop notifications
+
+notify-title = DevHealth: { $count } new finding(s)
+notify-line = { $severity }: { $check } in { $count } project(s)
+notify-more = …and { $count } more kind(s) of finding
+
+## Credentials
+auth-github = GitHub token
+auth-gitlab = GitLab token
+auth-smtp = SMTP password
+auth-prompt = { $secret }:
+auth-stored = { $secret } stored in the system keyring
+auth-removed = { $secret } removed from the system keyring
+auth-not-stored = No { $secret } stored in the system keyring
+
+## Configuration
+config-check-valid = { $path } is valid
+config-check-missing = { $path } does not exist; the defaults apply
+config-check-problems = { $count } problem(s) in { $path }:
+config-show-header = Configuration in effect: defaults, then the config file, then the environment and flags
+config-file = Config file: { $path }
+config-file-missing = Config file: { $path } (not found, defaults apply)
+config-override = { $key } from { $source }
+config-source-locale = the locale
+config-source-keyring = the system keyring
+config-offline = --offline: network-dependent checks are skipped
+config-policy = The organization policy replaces [deep] and adds required checks during check and scan
+
+## Forge repositories missing locally
+
+repos-listing = Listing repositories on GitHub and GitLab...
+repos-missing-title = { $count } of { $total } forge repositories not cloned locally
+repos-none-missing = All { $total } forge repositories are cloned locally
+repos-fork = (fork)
+repos-archived = (archived)
+repos-tip-clone = Clone one with { $command }
+repos-cloning = Cloning { $repo }...
+repos-cloned = Cloned { $repo } into { $path }
+repos-clone-failed = Could not clone { $repo }: { $error }
+
+## Outdated developer tools
+system-outdated-tools = { $manager }: { $count } outdated developer tool(s)
+system-outdated-tool = { $name } { $installed } → { $available }
+system-update-with = Update: { $command }
+
+## Runtime versions
+runtime-title = Runtime Versions ({ $count } projects with pins)
+runtime-managers = Version managers
+runtime-active-node = Active node
+runtime-shadowed = Shadowed nodes
+runtime-mismatched = Pin mismatches
+runtime-details = Runtime Details
+runtime-system = system
+runtime-unknown = unknown
+runtime-none = none
+runtime-not-installed = not installed
+runtime-binary = { $source } node { $version } ({ $path })
+runtime-shadows = { $active } shadows { $shadowed }
+runtime-on-path-active = On PATH (active):
+runtime-on-path = On PATH:
+runtime-pin = { $file }: { $runtime } { $requested }, active { $active }
+
+## Duplicate binaries
+system-duplicate-binary = { $name }: { $count } versions on PATH, { $path } runs first
+system-binary-wins = { $path } { $version } (runs)
+system-binary-shadowed = { $path } { $version } (shadowed)
+
+## Clock and certificates
+system-clock = Clock: { $state }
+system-clock-synced = synchronized
+system-clock-unsynced = not synchronized
+system-clock-offset = offset { $offset } s
+system-ca-bundle = CA bundle { $path } ({ $source }): { $count } certificates
+system-ca-system = system
+system-ca-missing = does not exist
+system-ca-empty = holds no certificate
+system-ca-truncated = ends in the middle of a certificate
+system-ca-incomplete = replaces the trust store but lacks the public roots
+
+## Proxy settings
+system-proxy = Proxy: { $variables }
+system-proxy-no-variables = no proxy variables set
+system-proxy-tool = { $tool }: { $proxy } ({ $source })
+system-proxy-direct = { $tool }: direct
+system-tls-genuine = { $endpoint }: certificate from { $issuer }
+system-tls-intercepted = { $endpoint }: intercepted by { $issuer }
+system-tls-untrusted = { $endpoint }: certificate not trusted, likely an intercepting proxy
+system-tls-unreachable = { $endpoint }: unreachable
+
+## Benchmarks
+bench-net-title = Network benchmark
+bench-net-title-label = Network benchmark ({ $label })
+bench-net-running = Measuring { $count } endpoints, { $rounds } rounds each...
+bench-net-timing = DNS { $dns } · connect { $connect } · TLS { $tls }
+bench-net-failed = failed: { $error }
+bench-net-no-address = host name has no address
+bench-net-usual = usually { $timing } ({ $runs } runs)
+bench-net-slower = slower than usual
+bench-power-warning = On battery or throttled: results will be slower than usual and not comparable with other runs
+bench-saved = Recorded in { $path }
+bench-history-title = Network benchmark history ({ $count } runs)
+bench-history-empty = No network benchmark recorded yet; run devhealth bench net
+bench-history-label = { $label }: { $runs } runs, last { $date }
+bench-unlabeled = unlabeled
+bench-fs-title = Filesystem benchmark: { $path }
+bench-fs-title-type = Filesystem benchmark: { $path } ({ $filesystem })
+bench-fs-running = Creating, reading and removing { $files } files in { $path }...
+bench-fs-create = create
+bench-fs-stat = stat
+bench-fs-read = read
+bench-fs-rate = { $rate } files/s
+bench-fs-rates = create { $create } · stat { $stat } · read { $read } files/s
+bench-fs-usual = usually { $rate }
+bench-fs-fast = Fast enough for git and compilers
+bench-fs-network = On a network drive: every file access is a round trip. Keep repositories and build output on a local disk.
+bench-fs-wsl-drive = A Windows drive accessed from WSL: every file access crosses into Windows. Keep repositories in the WSL filesystem (~/).
+bench-fs-slow-local = Small-file I/O is slow on a local disk, usually because of on-access antivirus scanning or indexing. Exclude this directory from both.
+bench-fs-history-title = Filesystem benchmark history ({ $count } runs)
+bench-fs-history-empty = No filesystem benchmark recorded yet; run devhealth bench fs
+
+## Antivirus and indexer exclusions
+system-exclusions = { $scanner } scans { $count } of { $total } build directories.
+system-exclusions-defender-impact = Real-time scanning typically makes builds 20–50% slower; exclude them from an administrator PowerShell:
+system-exclusions-spotlight-impact = Indexing build output competes with the build for disk and CPU, typically 5–15% of build time; exclude them with:
+system-exclusions-ok = { $scanner } skips all { $count } build directories
+system-exclusions-unverified = { $count } more could not be checked without administrator rights
+
+## Power
+system-power = Power: { $state }
+system-power-ac = plugged in
+system-power-battery = on battery
+system-power-level = { $percent }%
+system-power-charging = { $percent }%, charging
+system-power-low-power = low-power mode
+system-power-balanced = balanced mode
+system-power-performance = performance mode
+system-power-cpu-limit = CPU limited to { $percent }%
+system-power-slow = Builds and benchmarks will be slower than usual; plug in and leave low-power mode for timings you can compare
+system-power-drain = Battery is low: a long scan or build may drain it, plug in first
+
+## Limits
+system-limits = Limits: { $limits }
+system-limits-inodes = { $percent }% of inodes free
+system-limits-files = open files { $soft } (hard { $hard })
+system-limits-unlimited = unlimited
+system-limits-inodes-hint = Almost out of inodes: find the directories holding the most files with { $command } and clear build caches such as target and node_modules
+system-limits-files-hint = File watchers and builds may fail with "Too many open files"; raise the limit in your shell profile (~/.bashrc, ~/.zshrc) with { $command }
+system-limits-hard-hint-command = The hard limit is too low to raise it that far; raise the system limit with { $command }
+system-limits-hard-hint-linux = The hard limit is too low to raise it that far; add "* hard nofile 65536" to /etc/security/limits.conf (or set DefaultLimitNOFILE=65536 in /etc/systemd/system.conf) and log in again
+
+## Locale
+system-locale = Locale: { $locale } ({ $encoding })
+system-locale-no-encoding = Locale: { $locale }
+system-locale-unset = not set
+system-locale-problem-unset = programs fall back to the ASCII C locale
+system-locale-problem-not-utf8 = not UTF-8, compilers and git mangle non-ASCII text
+system-locale-problem-not-installed = names UTF-8 but is not installed
+system-locale-problem-code-page = the console does not use UTF-8 (code page 65001)
+system-locale-fix = Fix with { $command }
+
+## Git configuration
+system-git-config = Git configuration { $path }: { $count } recommended settings missing
+system-git-config-setting = { $key } is { $current }: { $command }
+system-git-config-unset = unset
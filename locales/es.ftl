@@ -0,0 +1,766 @@
+# DevHealth message catalog: Spanish
+
+## Progress and status messages
+
+check-start = Ejecutando comprobación de salud en: { $path }
+scan-start = Iniciando análisis completo en: { $path }
+scan-git = Analizando repositorios Git...
+scan-deps = Comprobando dependencias...
+scan-audit = Auditando dependencias en busca de vulnerabilidades conocidas...
+scan-system = Supervisando recursos del sistema...
+scan-containers = Comparando toolchains de contenedores de desarrollo...
+scan-monorepo = Analizando workspaces del monorepo...
+scan-release = Comprobando versiones de publicación...
+scan-cargo = Auditando ediciones de Rust y MSRV...
+scan-runtimes = Comprobando gestores de versiones de Node y entornos fijados...
+scan-lost-work = Buscando commits perdidos en los reflogs...
+scan-sizes = Midiendo el tamaño de los repositorios...
+scan-deep = Ejecutando linters y compilaciones...
+scan-nothing = No hay analizadores activados. Usa --git, --deps, --system, --audit, --containers, --monorepo, --release, --cargo o --all, o indica analizadores en [scan] default de devhealth.toml.
+scan-auto = Escáneres elegidos automáticamente: { $flags }
+scan-auto-nothing = No se encontraron repositorios ni proyectos, se ejecutan los escáneres predeterminados
+scan-auto-cargo-only = Solo hay proyectos de Cargo, se añaden las comprobaciones profundas de Rust
+scan-auto-no-advisories = Sin conexión y sin base de datos de avisos local, se omite la auditoría; ejecuta primero devhealth advisory-db sync
+scan-auto-network-share = { $path } está en un recurso compartido de red, se omite el análisis del sistema
+scan-auto-incomplete = Se dejó de buscar tras { $seconds } segundos; pueden faltar escáneres para proyectos más profundos
+tag-filter = Filtrando por etiqueta: { $tags }
+offline-header = Modo sin conexión: el acceso a la red se desactivó en esta ejecución
+offline-nothing-skipped = No se solicitaron comprobaciones que requieran red
+offline-skipped = Omitido: { $check }
+
+## Advisory database
+
+advisory-importing = Importando base de datos de avisos desde: { $path }
+advisory-imported = La base de datos local de avisos contiene ahora { $count } avisos ({ $path })
+advisory-fetching = Obteniendo avisos para las dependencias en: { $path }
+advisory-fetching-details = Descargando { $advisories } aviso(s) para { $packages } paquete(s)...
+advisory-saved = { $count } avisos guardados en { $path }
+
+## Tags
+
+tag-added = Etiquetado { $repo }: { $tags }
+tag-removed = Etiquetas eliminadas de { $repo }
+tag-not-found = { $repo } no tiene ninguna de las etiquetas indicadas
+tag-none-defined = No hay etiquetas definidas. Usa `devhealth tag add <ruta> <etiqueta>...` o una sección [tags] en devhealth.toml.
+tag-from-config = { $tag } (configuración): { $patterns }
+
+## Shared report elements
+
+summary = Resumen
+tip = Consejo:
+tips = Consejos:
+errors = Errores
+age-today = hoy
+age-one-day = hace 1 día
+age-days = hace { $days } días
+
+## Quick check
+
+check-manifests = Dependencias: { $manifests } manifiestos en { $projects } proyectos
+check-no-manifests = Dependencias: no se encontraron archivos de manifiesto
+check-partial = (parcial: se agotó el tiempo)
+system-snapshot = Sistema: memoria { $memory }, disco { $disk }
+system-free-of = { $free } libres de { $total }
+system-unknown = desconocido
+wsl-windows-drive = { $path } está en la unidad de Windows { $drive }:, que WSL lee mucho más despacio que su propio sistema de archivos. Mueve los proyectos a WSL (p. ej. ~/dev) para escanear más rápido.
+
+## Git scanner
+
+git-none = No se encontraron repositorios Git
+git-health = Salud de repositorios Git ({ $percent }%)
+git-total = Total repositorios
+git-clean = Limpio
+git-dirty = Modificado
+git-error = Error
+git-details = Detalles de repositorios
+git-column-name = Repositorio
+git-column-branch = Rama
+git-column-status = Estado
+git-column-ahead-behind = Adelante/Atrás
+git-column-last-commit = Último commit
+git-column-size = Tamaño
+git-column-score = Puntuación
+git-on-branch = en
+git-last-commit = (último commit { $age })
+git-dirty-since = (modificado { $age })
+git-lost-commit = commit perdido { $hash } «{ $subject }» ({ $age })
+git-lost-commit-recover = (recuperar: { $command })
+git-ci-failing = CI con fallos
+git-ci-passed = CI ✓
+git-ci-failed = CI ✗
+git-ci-running = CI …
+git-ci-no-runs = CI –
+git-ci-unknown = CI ?
+git-stale-forks = Forks desfasados
+git-fork-behind = { $count } por detrás de { $upstream }
+git-archived-upstream = Archivados en origen
+git-archived = archivado
+git-archived-local-work = archivado en la forja, pero contiene trabajo local: guárdalo en otro sitio o conserva el clon
+git-archived-removable = archivado en la forja, no se perdería nada local: se puede eliminar
+git-tip-forks = Ejecuta { $command } para avanzar los forks hasta su upstream
+git-review-issues = Revisiones
+git-review-no-pr = { $branch }: sin pull request abierto
+git-review-unresolved = { $pr } { $branch }: { $count } hilo(s) de revisión sin resolver
+git-review-merged = { $pr } { $branch }: fusionado, la rama local sigue existiendo
+git-tip-merged-branches = Elimina las ramas locales de pull requests fusionados con { $command }
+git-credential-issues = Credenciales
+git-exposures = Archivos expuestos
+git-extra-clones = Clones adicionales
+git-total-size = Tamaño total
+git-growing-artifacts = Artefactos en aumento
+git-stale-artifacts = Artefactos obsoletos
+git-stale-branches = Ramas obsoletas
+git-old-stashes = Stashes antiguos
+git-credential-no-helper = { $remote }: remoto HTTPS sin credential helper
+git-credential-store = { $remote }: contraseña guardada en texto plano por el helper store
+git-credential-embedded = { $remote }: contraseña o token incluido en la URL del remoto
+git-credential-ssh = (SSH: { $url })
+git-exposure-git-directory = .git es legible por otros usuarios
+git-exposure-committed = { $kind } { $path } está confirmado en el repositorio
+git-exposure-readable = { $kind } { $path } es legible por otros usuarios
+git-exposure-fix = solución: { $command }
+git-secret-env-file = Archivo .env
+git-secret-private-key = Clave privada
+git-nested-untracked = dentro de { $outer }, que ni lo rastrea ni lo ignora
+git-nested-gitlink = dentro de { $outer } como simple referencia a un commit, no como submódulo
+git-nested-fix = ignorarlo allí: { $command }
+git-duplicate-freshest = el más reciente de { $total } clones de { $identity }
+git-duplicate-stale = clon obsoleto de { $identity }, el más reciente está en { $freshest }
+git-duplicate-copy = otro clon de { $identity }, también en { $freshest }
+git-duplicate-unique-work = lleva su trabajo allí antes de eliminarlo
+git-duplicate-removable = nada sin enviar, se puede eliminar
+git-size-breakdown = árbol de trabajo { $working_tree }, .git { $git }, artefactos de compilación { $artifacts }
+git-artifacts-growing = los artefactos de compilación crecieron { $growth } desde el análisis de { $age }, ahora { $size }
+git-stale-artifact = { $dir }/ ocupa { $size }, escrito por última vez { $age }
+git-stale-artifact-outdated = (las fuentes cambiaron desde entonces)
+git-stale-branch-merged = la rama { $branch } ya está fusionada en la rama por defecto (último commit { $age })
+git-stale-branch-inactive = la rama { $branch } está inactiva (último commit { $age })
+git-stale-branch-delete = (eliminar: { $command })
+git-stale-repos = Repos inactivos
+git-stale-repos-title = { $count } repositorios sin commits desde hace meses
+git-stale-repo-local-work = (contiene trabajo local)
+git-stale-repos-hint = Archiva o borra los clones abandonados; el umbral es stale_repo_months en [scan]
+git-stashes = { $count } stash(es), el más antiguo de { $age }
+git-tip-credentials = Cambia los remotos a SSH con { $ssh }, o usa un gestor de credenciales con { $helper }
+git-tip-dirty = Usa { $commit } o { $stash } para limpiar los repositorios modificados
+git-tip-duplicates = Conserva un clon por proyecto: lleva el trabajo sin enviar al clon más reciente y elimina los demás
+git-tip-artifacts = Libera el espacio de los artefactos de compilación con { $cargo } o { $node }
+git-tip-stale-artifacts = Revisa los artefactos obsoletos con { $command } y ejecútalo sin --dry-run para eliminarlos
+git-tip-stale-branches = Elimina las ramas fusionadas con { $merged }; comprueba que las ramas abandonadas no contengan nada importante antes de { $stale }
+git-tip-old-stashes = Revisa los stashes de más de una semana con { $list } y recupera uno con { $apply } o descártalo con { $drop }
+repo-title = Repositorio: { $name }
+repo-path = Ruta
+repo-status = Estado
+repo-branch = Rama
+repo-uncommitted-since = Cambios sin confirmar
+repo-unpushed = Commits sin enviar
+repo-last-commit = Último commit
+repo-score = Puntuación de salud
+repo-no-commits = sin commits
+yes = sí
+no = no
+
+## Dependency scanner
+
+deps-none = No se encontraron archivos de dependencias
+deps-title = Análisis de dependencias ({ $count } ecosistemas)
+deps-projects = Total de proyectos
+deps-total = Total dependencias
+deps-ecosystems = Ecosistemas
+deps-breakdown = Desglose por ecosistema
+deps-details = Detalles del proyecto
+deps-count = { $count } dependencias
+deps-count-short = ({ $count } deps)
+deps-more = { $count } dependencias más
+deps-lockfiles = Problemas de lockfile
+deps-lockfile-missing = { $manifest }: sin lockfile, ejecuta { $action }
+deps-lockfile-out-of-sync = { $manifest }: lockfile desincronizado ({ $dependencies }), ejecuta { $action }
+deps-lockfile-stale = { $manifest }: lockfile más antiguo que el manifiesto, ejecuta { $action }
+deps-build-system-detected = sistema de compilación detectado, dependencias externas no listadas
+deps-go-version = { $manifest }: go { $version }
+deps-go-toolchain = toolchain { $toolchain }
+deps-go-replace = { $manifest }: reemplaza { $replace }
+deps-go-replace-local = { $manifest }: reemplaza { $replace } (directorio local)
+deps-go-exclude = { $manifest }: excluye { $module } { $version }
+deps-tip-updates = Buscar actualizaciones
+deps-tip-updates-how = Ejecuta los comandos de actualización del gestor de paquetes
+deps-tip-security = Análisis de seguridad
+deps-tip-security-how = Usa herramientas como cargo audit, npm audit o safety
+deps-tip-unused = Limpiar dependencias
+deps-tip-unused-how = Elimina las dependencias que no uses
+
+## Vulnerability audit
+
+audit-title = Auditoría de vulnerabilidades ({ $count } encontradas)
+audit-checked = Comprobadas
+audit-vulnerable-runtime = Vulnerables (ejecución)
+audit-vulnerable-development = Vulnerables (desarrollo/compilación)
+audit-unresolved = Sin resolver
+audit-ignored = Ignoradas
+audit-database = Base de avisos
+audit-synced = sincronizada { $age }
+audit-unavailable = no disponible
+audit-details-runtime = Vulnerabilidades en ejecución
+audit-details-development = Vulnerabilidades de desarrollo y compilación
+audit-fixed-in = corregido en { $version }
+audit-no-fix = sin corrección disponible
+audit-refresh-failed = No se pudieron actualizar los avisos, se usa la base de datos local: { $error }
+
+## Dev container scanner
+containers-none = No se encontraron contenedores de desarrollo
+containers-title = Contenedores de desarrollo ({ $count } encontrados)
+containers-environments = Entornos
+containers-running = En ejecución
+containers-mismatches = Versiones distintas
+containers-details = Entornos
+containers-state-running = en ejecución ({ $id })
+containers-state-stopped = detenido
+containers-state-no-docker = Docker no disponible
+containers-tool = host { $host } / contenedor { $container }
+
+## Monorepo workspaces
+workspace-none = No se encontraron workspaces de monorepo
+workspace-title = Workspaces de monorepo ({ $count } encontrados)
+workspace-roots = Workspaces
+workspace-packages = Paquetes
+workspace-conflicts = Conflictos de versión
+workspace-cycles = Ciclos de dependencias
+workspace-details = Workspaces
+workspace-package-count = { $count } paquetes
+workspace-package-deps = { $count } deps, { $internal } internas
+workspace-conflict = { $name }:
+workspace-cycle = ciclo de dependencias entre { $packages }
+workspace-fan-in = Más requeridos:
+workspace-fan-out = Más dependencias:
+workspace-graph-saved = Grafo de dependencias del workspace escrito en { $path }
+
+## Versiones publicadas
+release-none = No se encontraron repositorios versionados
+release-title = Versiones publicadas ({ $count } encontrados)
+release-repos = Repositorios
+release-released = Publicados
+release-bumps-missing = Sin incremento
+release-mismatches = Etiqueta distinta
+release-details = Versiones
+release-status-released = { $version } coincide con la etiqueta { $tag }
+release-status-bump-missing = { $version } coincide con la etiqueta { $tag }, pero HEAD va { $commits } commit(s) por delante sin incrementar la versión
+release-status-unreleased = { $version } es más reciente que la etiqueta { $tag }, aún sin publicar
+release-status-mismatch = { $version } no coincide con la última etiqueta { $tag }
+release-status-untagged = { $version }, todavía sin etiquetas
+release-stale-changelogs = Changelogs antiguos
+release-status-unversioned = sin versión en un manifiesto raíz
+release-changelog-missing = sin changelog
+release-changelog-keep = (Keep a Changelog)
+release-changelog-current = { $file } está al día
+release-changelog-stale = { $file } sin actualizar desde hace { $commits } commit(s) y { $tags } etiqueta(s)
+release-changelog-no-entry = { $file } no tiene entrada para la etiqueta { $tag }
+
+## Proyectos Cargo
+cargo-none = No se encontraron paquetes Cargo
+cargo-title = Proyectos Cargo ({ $count } encontrados)
+cargo-projects = Paquetes
+cargo-missing-msrv = Sin MSRV
+cargo-old-editions = Ediciones antiguas
+cargo-msrv-too-low = MSRV demasiado baja
+cargo-details = Paquetes
+cargo-versions = edición { $edition }, MSRV { $msrv }
+cargo-requirement = las dependencias necesitan Rust { $required } ({ $dependency } { $version })
+cargo-finding-no-msrv = sin rust-version declarada
+cargo-finding-old-edition = la edición { $edition } es anterior a 2021
+cargo-finding-msrv-too-low = rust-version { $declared } es menor que { $required }, requerida por { $dependency }
+cargo-feature-issues = Features con fallos
+cargo-features = features: { $features }
+cargo-feature-deps = { $feature } activa { $dependencies }
+cargo-finding-unused-feature = la feature { $feature } no activa dependencias y nunca se usa
+cargo-finding-heavy-default = la feature por defecto incluye { $dependency } con { $crates } crates
+
+## Comprobaciones profundas
+deep-none = No hay proyectos para comprobar en profundidad
+deep-title = Comprobaciones profundas (salud media { $score }%)
+deep-projects = Proyectos
+deep-failing = Con fallos
+deep-warnings = Advertencias
+deep-details = Proyectos
+deep-check-passed = correcto
+deep-check-warnings = correcto con { $warnings } advertencia(s)
+deep-check-failed = falló con { $errors } error(es), { $warnings } advertencia(s)
+deep-check-unformatted = { $files } archivo(s) sin formato
+deep-check-timed-out = tiempo agotado
+deep-check-unavailable = no instalado
+
+## Correcciones
+fix-no-forks = No se encontraron forks
+fix-fork-up-to-date = { $repo } está al día con { $upstream }
+fix-fork-would-sync = { $repo } está { $count } commit(s) por detrás de { $upstream }
+fix-fork-synced = { $repo } avanzado { $count } commit(s) desde { $upstream }
+fix-fork-synced-forge = { $repo } sincronizado en la forja: { $message } (haz pull para actualizar el clon local)
+fix-fork-dirty = { $repo } tiene cambios sin confirmar en { $branch }; omitido
+fix-fork-no-branch = { $repo } no tiene una rama local { $branch }; omitido
+fix-fork-failed = No se pudo sincronizar { $repo }: { $error }
+clean-none = No se encontraron artefactos de compilación obsoletos
+clean-would-remove = Se eliminaría { $path } ({ $size }, escrito por última vez { $age })
+clean-removed = Eliminado { $path } ({ $size })
+clean-failed = No se pudo eliminar { $path }: { $error }
+clean-would-free = Se liberarían { $size }
+clean-freed = Liberados { $size }
+clean-skipped-tracked = Se omite { $path }: git no lo ignora o contiene archivos versionados
+clean-confirm = ¿Eliminar { $count } directorio(s) y liberar { $size }?
+clean-cancelled = No se eliminó nada
+clean-needs-yes = No se elimina la salida de compilación sin confirmación: use --yes fuera de una terminal
+
+## Pin suggestions
+pins-title = Sugerencias de versiones fijas ({ $count })
+pins-none = No se encontraron restricciones de versión abiertas
+pins-not-locked = no está en el archivo de bloqueo
+pins-tip = Aplique las versiones con: devhealth deps pin-suggestions --patch | git apply
+
+## Dependency exposure
+exposure-title = Exposición de dependencias ({ $count } proyectos)
+exposure-packages = Paquetes
+exposure-drifting = Varias versiones
+exposure-vulnerable = Paquetes vulnerables
+exposure-most-exposed = { $package } es el paquete vulnerable que afecta a más proyectos ({ $count })
+exposure-most-used = Paquetes más usados (top { $count })
+exposure-projects = ({ $count } proyecto(s))
+exposure-more = ... y { $count } paquetes más (usa --top 0 para verlos todos)
+exposure-fixed-in = corregido en { $version }
+exposure-no-fix = sin corrección todavía
+exposure-no-vulnerable = No se encontraron paquetes vulnerables
+exposure-tip-audit = Añade --audit para ver qué paquetes vulnerables afectan a más proyectos
+
+find-title = { $package }: { $count } dependencia(s) en { $projects } proyecto(s)
+find-none = Ningún proyecto usa { $package }
+find-locked = (bloqueada en { $version })
+
+impact-title = Impacto de la actualización: { $package } → { $version }
+impact-none = Ningún proyecto ni paquete bloqueado depende de { $package }
+impact-constraints = Restricciones
+impact-blocking = Bloqueantes
+impact-unknown = Desconocidas
+impact-compatible = Compatibles
+impact-ready = Todas las restricciones admiten { $version }
+impact-blocked-projects = { $count } proyecto(s) aún no pueden actualizar a { $version }
+impact-direct = declarada
+impact-via = vía { $package }
+impact-tip-unknown = Las restricciones desconocidas provienen de fuentes git o de ruta, paquetes nunca descargados o rangos que no se pueden comprobar
+
+align-title = Alineación de versiones
+align-none = Ningún paquete se usa en versiones muy distintas
+align-misaligned = Paquetes desalineados
+align-changes = Restricciones a cambiar
+align-spread-major = versiones mayores de diferencia
+align-spread-minor = versiones menores de diferencia
+align-spread-patch = versiones de parche de diferencia
+align-target = converger en { $version } ({ $satisfied } de { $total } restricción(es) la admiten)
+align-tip = Las versiones en amarillo necesitan cambiar su restricción para alcanzar la versión sugerida
+
+## License inventory
+licenses-title = Inventario de licencias ({ $count } licencias)
+licenses-unknown = Licencias desconocidas
+licenses-packages = ({ $count } paquete(s))
+licenses-tip-unknown = Las licencias desconocidas son de paquetes nunca descargados; compila o instala los proyectos primero
+
+## Vigilancia de avisos
+watch-watching = Vigilando { $dependencies } dependencias en { $projects } proyecto(s)
+watch-baseline = Primera comprobación: { $count } aviso(s) conocido(s) registrado(s); a partir de ahora solo se informará de los nuevos
+watch-no-new = No hay avisos nuevos desde la última comprobación
+watch-new-title = Avisos nuevos ({ $count })
+watch-notification-title = DevHealth: { $count } aviso(s) nuevo(s) para tus dependencias
+watch-next-check = Próxima comprobación en { $minutes } minuto(s)
+watch-inventory-failed = No se pudo guardar el inventario de dependencias: { $error }
+
+## Política de la organización
+policy-applied = Aplicando la política de la organización de { $url }
+policy-syncing = Sincronizando la política de la organización de { $url }...
+policy-updated = Política actualizada y firma verificada
+policy-not-modified = La política está al día
+policy-not-synced = Aún no se ha sincronizado ninguna política; ejecuta devhealth policy sync
+policy-source = Política de { $url } (sincronizada { $synced })
+policy-required-scanners = Escáneres obligatorios: { $scanners }
+policy-fail-on = Fallar siempre con: { $kinds }
+policy-deep = Comprobaciones profundas: { $timeout }s de límite, tests { $tests }
+policy-none = ninguno
+policy-refresh-failed = No se pudo actualizar la política de la organización: { $error }
+
+## Team reports
+merge-loading = Combinando { $count } archivo(s) de resultados
+merge-title = Salud del equipo ({ $count } máquinas)
+merge-machines = Máquinas
+merge-repositories = Repositorios
+merge-dirty = Copias modificadas
+merge-unpushed = Copias sin push
+merge-exposed = Máquinas expuestas
+merge-advisories = Avisos
+merge-vulnerabilities = Exposición a vulnerabilidades
+merge-dependencies = Exposición de dependencias
+merge-repo = { $name }: { $machines } máquina(s), { $dirty } modificada(s)
+merge-copy = { $status } en { $branch }, puntuación { $score }
+merge-vulnerability = { $package } { $versions }: { $machines }/{ $total } máquinas ({ $names }), { $projects } proyecto(s)
+merge-fixed-in = corregido en { $version }
+merge-dependency = { $name }: { $machines }/{ $total } máquinas, { $projects } proyecto(s)
+merge-versions = versiones { $versions }
+merge-more = … y { $count } más
+
+## Snapshots
+snapshot-written = Instantánea guardada en { $path }
+snapshot-title = Instantánea
+snapshot-version = Versión de DevHealth
+snapshot-created = Creada
+snapshot-platform = Plataforma
+snapshot-path = Ruta analizada
+snapshot-config = Configuración
+snapshot-config-included = incluida
+snapshot-config-none = ninguna
+snapshot-tools = Herramientas
+snapshot-tool-missing = no instalado
+snapshot-configuration = Configuración
+snapshot-diff-title = Diferencias entre instantáneas
+snapshot-diff-range = Comparando una instantánea de { $before } con una de { $after }
+snapshot-diff-none = Las instantáneas no difieren
+snapshot-diff-config-changed = La configuración cambió
+snapshot-diff-repositories = Repositorios
+snapshot-diff-vulnerabilities = Vulnerabilidades
+snapshot-diff-dependencies = Dependencias
+snapshot-diff-absent = ausente
+
+## Acknowledgements
+ack-added = Reconocido: { $finding }
+ack-removed = { $finding } se volverá a informar
+ack-not-found = { $finding } no está reconocido
+ack-none = No hay hallazgos reconocidos
+ack-expired = caducado
+ack-until = hasta { $date }
+ack-indefinitely = indefinidamente
+ack-hidden = { $count } hallazgo(s) reconocido(s) omitido(s) (devhealth ack list)
+
+## Findings
+findings-title = Hallazgos por gravedad ({ $count })
+severity-info = Info
+severity-warn = Aviso
+severity-error = Error
+severity-critical = Crítico
+check-uncommitted-changes = Cambios sin confirmar
+check-unpushed-commits = Commits sin enviar
+check-repository-error = No se pudo inspeccionar el repositorio
+check-ci-failed = La CI falla en la rama principal
+check-fork-behind = El fork va por detrás de su upstream
+check-unresolved-reviews = Hilos de revisión sin resolver
+check-merged-branches = Siguen existiendo ramas de pull requests fusionados
+check-credential-helper-missing = Remoto HTTPS sin asistente de credenciales
+check-plaintext-credentials = Credenciales guardadas en texto plano
+check-embedded-credentials = Credenciales incluidas en la URL del remoto
+check-git-config-recommendation = Falta un ajuste global de git recomendado
+check-stale-uncommitted-changes = Cambios sin confirmar desde hace demasiado tiempo
+check-lost-work = Commits recientes ya no están en ninguna rama
+check-readable-git-directory = Otros usuarios pueden leer el directorio .git
+check-committed-secret = Hay un archivo .env o una clave privada confirmados
+check-readable-secret = Otros usuarios pueden leer un archivo .env o una clave privada
+check-nested-repository = Repositorio dentro de otro sin ser submódulo ni estar ignorado
+check-duplicate-clone = Existe otro clon del mismo repositorio
+check-growing-artifacts = Los artefactos de compilación crecieron un gigabyte o más desde el análisis anterior
+check-stale-artifacts = Gigabytes de artefactos de compilación sin tocar desde hace meses
+check-stale-branch = Rama local fusionada en la rama por defecto o sin commits desde hace meses
+check-old-stash = Entradas del stash de más de una semana
+check-archived-upstream = Repositorio archivado en la forja
+check-stale-repository = Repositorio sin commits desde hace meses
+check-manifest-error = No se pudo analizar el manifiesto
+check-vulnerable-dependency = Dependencia vulnerable
+check-vulnerable-dev-dependency = Dependencia de desarrollo o compilación vulnerable
+check-missing-lockfile = Falta el lockfile
+check-lockfile-out-of-sync = El lockfile no satisface el manifiesto
+check-stale-lockfile = Manifiesto modificado después del lockfile
+check-go-replace = Módulo Go reemplazado
+check-toolchain-mismatch = Las herramientas del host y del contenedor difieren
+check-container-config-error = No se pudo leer la configuración del dev container
+check-runtime-version-mismatch = La versión activa del entorno no coincide con la fijada por el proyecto
+check-shadowed-runtime = Un node del PATH oculta otro de un gestor de versiones distinto
+check-workspace-manifest-error = No se pudo leer el manifiesto de un paquete del workspace
+check-dependency-cycle = Ciclo de dependencias en el workspace
+check-version-conflict = Dependencia fijada de forma inconsistente en el workspace
+check-version-mismatch = La versión del manifiesto difiere de la última etiqueta
+check-version-bump-missing = Versión sin incrementar desde la última etiqueta
+check-stale-changelog = El changelog está desactualizado
+check-duplicate-binary = Una herramienta de desarrollo está varias veces en el PATH con versiones distintas
+check-clock-skew = El reloj del sistema se desvía un minuto o más
+check-broken-ca-bundle = El paquete de certificados CA falta o está dañado
+check-proxy-mismatch = Las herramientas de desarrollo usan proxies distintos
+check-tls-interception = Un proxy intercepta las conexiones TLS a los registros
+check-unexcluded-build-directory = Un directorio de compilación es analizado por el antivirus o indexado por Spotlight
+check-low-inodes = Al volumen escaneado se le acaban los inodos
+check-low-file-limit = El límite de archivos abiertos es demasiado bajo para los observadores de archivos y las compilaciones
+check-non-utf8-locale = La configuración regional o la página de códigos de la consola no es UTF-8
+check-missing-rust-version = No se declara rust-version
+check-outdated-edition = Edición de Rust obsoleta
+check-rust-version-too-low = La rust-version declarada es demasiado baja
+check-unused-feature = Feature de Cargo sin usar
+check-heavy-default-dependency = Dependencia opcional pesada activada por defecto
+check-check-failed = La comprobación falló
+check-check-timed-out = La comprobación superó el tiempo límite
+rules-title = Catálogo de reglas ({ $count })
+
+## Run metadata
+run-header = DevHealth { $version } · { $time } · { $host }
+run-details = Rutas: { $paths } · Escáneres: { $scanners } · Configuración: { $config }
+run-unknown-host = host desconocido
+run-no-scanners = ninguno
+run-no-config = predeterminada
+
+## Overview
+overview-title = Resumen ({ $repos } repo(s), { $projects } proyecto(s))
+overview-empty = Nada que mostrar; escanea con --git o --deps
+overview-healthy = saludable
+overview-dirty = con cambios
+overview-error = error
+overview-unpushed = sin enviar
+overview-ci-failed = CI
+overview-behind = { $count } por detrás
+overview-vulnerabilities = { $count } vuln(s)
+overview-lockfiles = { $count } problema(s) de lockfile
+overview-errors = { $count } error(es)
+
+## Changes since the last run
+changes-title = Cambios desde el último análisis ({ $age }):
+changes-none = Sin cambios desde el último análisis ({ $age })
+changes-dirty = { $delta } repositorio(s) con cambios
+changes-unpushed = { $delta } repositorio(s) con commits sin enviar
+changes-vulnerabilities = { $delta } dependencia(s) vulnerable(s)
+changes-findings = { $delta } hallazgo(s) de nivel { $severity }
+
+## Editor integration
+init-created = Se creó { $path }
+init-updated = Se añadió { $entries } a { $path }
+init-unchanged = { $path } ya está configurado
+init-skipped = No se modificó { $path }: no es JSON simple (quite los comentarios o añada las entradas de DevHealth a mano)
+
+## First-run setup
+setup-detected = Detectado en esta máquina: { $tools }
+setup-ask-roots = ¿Dónde está su código? Directorios separados por comas
+setup-no-directory = { $path } no es un directorio
+setup-invalid = Respuesta no válida, inténtelo de nuevo
+setup-ask-scanners = Escáneres que `devhealth scan` ejecuta por defecto ({ $available })
+setup-ask-history = ¿Guardar un historial para mostrar los cambios desde la última ejecución?
+setup-ask-fail-level = ¿A partir de qué gravedad fallar (info, warn, error, critical o none)?
+setup-ask-stale-days = Días tras los que los cambios sin confirmar se consideran antiguos
+setup-ask-schedule = ¿Analizar las dependencias a diario y vigilar nuevos avisos (cron)?
+setup-ask-hooks = ¿Añadir un hook pre-push que ejecute `devhealth check` a los repositorios de ahí?
+setup-scheduled = Se añadió una entrada diaria de cron para analizar dependencias y avisos
+setup-already-scheduled = La crontab ya tiene la entrada de DevHealth
+setup-hooks-installed = Se añadió un hook pre-push a { $count } repo(s)
+setup-hooks-skipped = Se mantuvo el hook pre-push existente de { $count } repo(s)
+setup-next = Siguiente: ejecute `devhealth check` para un primer informe o edite { $path }
+
+## Self-update
+update-up-to-date = DevHealth { $version } es la última versión
+update-available = DevHealth { $version } está disponible (instalada: { $current }); ejecute `devhealth self-update`
+update-done = DevHealth se actualizó de { $from } a { $to }
+update-notice = DevHealth { $version } está disponible: ejecute `devhealth self-update` (desactive estos avisos con `check = false` en [update])
+
+## Crash reports
+crash-saved = DevHealth falló inesperadamente. Se guardó un paquete de diagnóstico censurado en { $path }; adjúntelo a un informe de error en https://github.com/Art-Jashari/DevHealth/issues
+debug-bundle-written = Paquete de diagnóstico escrito en { $path }
+debug-bundle-crash = Contiene el último fallo: { $message }
+debug-bundle-no-crash = No hay ningún fallo registrado; el paquete describe el entorno actual
+
+## State lock
+lock-waiting = Esperando a que otra ejecución de DevHealth termine de actualizar su estado…
+lock-waiting-pid = Esperando a que otra ejecución de DevHealth (proceso { $pid }) termine de actualizar su estado…
+
+## History
+history-pruned = Se eliminaron { $runs } ejecución(es) de escaneo y { $sizes } registro(s) de tamaño de repositorio
+history-prune-dry-run = Se eliminarían { $runs } ejecución(es) de escaneo y { $sizes } registro(s) de tamaño de repositorio
+history-empty = Ningún escaneo guardado coincide; con history = true en [scan], check y scan registran sus ejecuciones
+history-list-title = Historial de escaneos ({ $count } ejecuciones)
+history-run-findings = { $count } hallazgo(s)
+history-no-findings = No hay hallazgos que coincidan en los escaneos guardados
+history-timelines-title = Cronología de hallazgos ({ $count })
+history-period-fixed = apareció el { $appeared }, corregido el { $fixed } tras { $days } día(s)
+history-period-open = apareció el { $appeared } ({ $age }), sigue presente
+
+## Team statistics
+stats-written = Estadísticas anónimas escritas en { $path }; solo contienen recuentos, sin nombres de repositorios ni rutas
+
+## Email digest
+
+digest-subject = Resumen de DevHealth { $date }: { $count } hallazgo(s)
+digest-title = Resumen de DevHealth del { $date }
+digest-scope = Revisado: { $paths }
+digest-summary-title = Resumen
+digest-repositories = { $count } repositorio(s) revisado(s)
+digest-dirty = { $count } repositorio(s) con cambios sin confirmar
+digest-unpushed = { $count } repositorio(s) con commits sin enviar
+digest-vulnerabilities = { $count } dependencia(s) vulnerable(s)
+digest-findings = { $count } hallazgo(s) de nivel { $severity }
+digest-changes-title = Cambios desde el { $date }
+digest-no-changes = No ha cambiado nada.
+digest-attention-title = Requiere atención ({ $count })
+digest-all-clear = Nada requiere atención.
+digest-more = …y { $count } hallazgo(s) más; devhealth check los muestra todos.
+digest-footer = Enviado por DevHealth { $version }
+digest-sent = Resumen enviado a { $recipients }
+digest-scheduled = Resumen programado en tu crontab: { $schedule }
+digest-schedule-exists = Tu crontab ya tiene una entrada de resumen de DevHealth; edítala con crontab -e
+
+## Desktop notifications
+
+notify-title = DevHealth: { $count } hallazgo(s) nuevo(s)
+notify-line = { $severity }: { $check } en { $count } proyecto(s)
+notify-more = …y { $count } tipo(s) de hallazgo más
+
+## Credenciales
+auth-github = token de GitHub
+auth-gitlab = token de GitLab
+auth-smtp = contraseña SMTP
+auth-prompt = { $secret }:
+auth-stored = { $secret } guardado en el llavero del sistema
+auth-removed = { $secret } eliminado del llavero del sistema
+auth-not-stored = No hay { $secret } guardado en el llavero del sistema
+
+## Configuración
+config-check-valid = { $path } es válido
+config-check-missing = { $path } no existe; se aplican los valores predeterminados
+config-check-problems = { $count } problema(s) en { $path }:
+config-show-header = Configuración vigente: valores predeterminados, luego el archivo de configuración, luego el entorno y las opciones
+config-file = Archivo de configuración: { $path }
+config-file-missing = Archivo de configuración: { $path } (no encontrado, se aplican los valores predeterminados)
+config-override = { $key } desde { $source }
+config-source-locale = la configuración regional
+config-source-keyring = el llavero del sistema
+config-offline = --offline: se omiten las comprobaciones que requieren red
+config-policy = La política de la organización reemplaza [deep] y añade comprobaciones obligatorias en check y scan
+
+## Repositorios de la forja que faltan localmente
+
+repos-listing = Listando repositorios en GitHub y GitLab...
+repos-missing-title = { $count } de { $total } repositorios de la forja no están clonados localmente
+repos-none-missing = Los { $total } repositorios de la forja están clonados localmente
+repos-fork = (fork)
+repos-archived = (archivado)
+repos-tip-clone = Clona uno con { $command }
+repos-cloning = Clonando { $repo }...
+repos-cloned = { $repo } clonado en { $path }
+repos-clone-failed = No se pudo clonar { $repo }: { $error }
+
+## Outdated developer tools
+system-outdated-tools = { $manager }: { $count } herramienta(s) de desarrollo desactualizada(s)
+system-outdated-tool = { $name } { $installed } → { $available }
+system-update-with = Actualizar: { $command }
+
+## Runtime versions
+runtime-title = Versiones de entornos ({ $count } proyectos con versiones fijadas)
+runtime-managers = Gestores de versiones
+runtime-active-node = node activo
+runtime-shadowed = nodes ocultos
+runtime-mismatched = Discrepancias
+runtime-details = Detalles de entornos
+runtime-system = sistema
+runtime-unknown = desconocida
+runtime-none = ninguno
+runtime-not-installed = no instalado
+runtime-binary = node de { $source } { $version } ({ $path })
+runtime-shadows = { $active } oculta { $shadowed }
+runtime-on-path-active = En PATH (activo):
+runtime-on-path = En PATH:
+runtime-pin = { $file }: { $runtime } { $requested }, activo { $active }
+
+## Duplicate binaries
+system-duplicate-binary = { $name }: { $count } versiones en el PATH, se ejecuta { $path }
+system-binary-wins = { $path } { $version } (se ejecuta)
+system-binary-shadowed = { $path } { $version } (oculto)
+
+## Clock and certificates
+system-clock = Reloj: { $state }
+system-clock-synced = sincronizado
+system-clock-unsynced = no sincronizado
+system-clock-offset = desfase { $offset } s
+system-ca-bundle = Paquete CA { $path } ({ $source }): { $count } certificados
+system-ca-system = sistema
+system-ca-missing = no existe
+system-ca-empty = no contiene ningún certificado
+system-ca-truncated = termina a mitad de un certificado
+system-ca-incomplete = sustituye el almacén de confianza pero le faltan las raíces públicas
+
+## Proxy settings
+system-proxy = Proxy: { $variables }
+system-proxy-no-variables = sin variables de proxy
+system-proxy-tool = { $tool }: { $proxy } ({ $source })
+system-proxy-direct = { $tool }: directo
+system-tls-genuine = { $endpoint }: certificado de { $issuer }
+system-tls-intercepted = { $endpoint }: interceptado por { $issuer }
+system-tls-untrusted = { $endpoint }: certificado no confiable, probablemente un proxy que intercepta
+system-tls-unreachable = { $endpoint }: inaccesible
+
+## Benchmarks
+bench-net-title = Benchmark de red
+bench-net-title-label = Benchmark de red ({ $label })
+bench-net-running = Midiendo { $count } endpoints, { $rounds } rondas cada uno...
+bench-net-timing = DNS { $dns } · conexión { $connect } · TLS { $tls }
+bench-net-failed = falló: { $error }
+bench-net-no-address = el nombre de host no tiene dirección
+bench-net-usual = normalmente { $timing } ({ $runs } ejecuciones)
+bench-net-slower = más lento de lo normal
+bench-power-warning = Con batería o limitado: los resultados serán más lentos de lo normal y no comparables con otras ejecuciones
+bench-saved = Registrado en { $path }
+bench-history-title = Historial del benchmark de red ({ $count } ejecuciones)
+bench-history-empty = Aún no hay benchmarks de red registrados; ejecuta devhealth bench net
+bench-history-label = { $label }: { $runs } ejecuciones, última { $date }
+bench-unlabeled = sin etiqueta
+bench-fs-title = Benchmark del sistema de archivos: { $path }
+bench-fs-title-type = Benchmark del sistema de archivos: { $path } ({ $filesystem })
+bench-fs-running = Creando, leyendo y eliminando { $files } archivos en { $path }...
+bench-fs-create = crear
+bench-fs-stat = stat
+bench-fs-read = leer
+bench-fs-rate = { $rate } archivos/s
+bench-fs-rates = crear { $create } · stat { $stat } · leer { $read } archivos/s
+bench-fs-usual = normalmente { $rate }
+bench-fs-fast = Suficientemente rápido para git y compiladores
+bench-fs-network = En una unidad de red: cada acceso a un archivo es un viaje de ida y vuelta. Mantén los repositorios y la salida de compilación en un disco local.
+bench-fs-wsl-drive = Una unidad de Windows accedida desde WSL: cada acceso cruza a Windows. Mantén los repositorios en el sistema de archivos de WSL (~/).
+bench-fs-slow-local = La E/S de archivos pequeños es lenta en un disco local, normalmente por el análisis antivirus en tiempo real o la indexación. Excluye este directorio de ambos.
+bench-fs-history-title = Historial del benchmark del sistema de archivos ({ $count } ejecuciones)
+bench-fs-history-empty = Aún no hay benchmarks del sistema de archivos registrados; ejecuta devhealth bench fs
+
+## Antivirus and indexer exclusions
+system-exclusions = { $scanner } analiza { $count } de { $total } directorios de compilación.
+system-exclusions-defender-impact = El análisis en tiempo real suele hacer las compilaciones un 20–50 % más lentas; exclúyelos desde un PowerShell de administrador:
+system-exclusions-spotlight-impact = Indexar la salida de compilación compite con la compilación por disco y CPU, normalmente un 5–15 % del tiempo; exclúyelos con:
+system-exclusions-ok = { $scanner } omite los { $count } directorios de compilación
+system-exclusions-unverified = { $count } más no se pudieron comprobar sin permisos de administrador
+
+## Power
+system-power = Alimentación: { $state }
+system-power-ac = conectado a la corriente
+system-power-battery = con batería
+system-power-level = { $percent } %
+system-power-charging = { $percent } %, cargando
+system-power-low-power = modo de bajo consumo
+system-power-balanced = modo equilibrado
+system-power-performance = modo de alto rendimiento
+system-power-cpu-limit = CPU limitada al { $percent } %
+system-power-slow = Las compilaciones y benchmarks serán más lentos de lo normal; conecta el cargador y sal del modo de bajo consumo para obtener tiempos comparables
+system-power-drain = Batería baja: un escaneo o compilación largo puede agotarla, conecta el cargador antes
+
+## Limits
+system-limits = Límites: { $limits }
+system-limits-inodes = { $percent } % de inodos libres
+system-limits-files = archivos abiertos { $soft } (máximo { $hard })
+system-limits-unlimited = ilimitado
+system-limits-inodes-hint = Casi sin inodos: encuentra los directorios con más archivos con { $command } y vacía cachés de compilación como target y node_modules
+system-limits-files-hint = Los observadores de archivos y las compilaciones pueden fallar con "Too many open files"; sube el límite en el perfil de tu shell (~/.bashrc, ~/.zshrc) con { $command }
+system-limits-hard-hint-command = El límite máximo es demasiado bajo para subirlo tanto; sube el límite del sistema con { $command }
+system-limits-hard-hint-linux = El límite máximo es demasiado bajo para subirlo tanto; añade "* hard nofile 65536" a /etc/security/limits.conf (o define DefaultLimitNOFILE=65536 en /etc/systemd/system.conf) y vuelve a iniciar sesión
+
+## Locale
+system-locale = Configuración regional: { $locale } ({ $encoding })
+system-locale-no-encoding = Configuración regional: { $locale }
+system-locale-unset = sin definir
+system-locale-problem-unset = los programas usan la configuración regional ASCII C
+system-locale-problem-not-utf8 = no es UTF-8, los compiladores y git estropean el texto no ASCII
+system-locale-problem-not-installed = indica UTF-8 pero no está instalada
+system-locale-problem-code-page = la consola no usa UTF-8 (página de códigos 65001)
+system-locale-fix = Corrígelo con { $command }
+
+## Git configuration
+system-git-config = Configuración de git { $path }: faltan { $count } ajustes recomendados
+system-git-config-setting = { $key } es { $current }: { $command }
+system-git-config-unset = sin definir
@@ -0,0 +1,412 @@
+//! Acknowledged findings
+//!
+//! `devhealth ack <finding-id>` records that a finding is known and accepted,
+//! optionally until a date and with a reason. Acknowledgements are stored in
+//! `acks.json` inside the DevHealth data directory. While active, the finding
+//! is left out of reports, `--only` and `--fail-on`; once its date passes it
+//! resurfaces on its own.
+//!
+//! A finding ID names a kind of attention (as accepted by `--only`) and what
+//! it applies to:
+//!
+//! - `dirty:<repo>`, `unpushed:<repo>`: a repository's uncommitted changes or
+//!   unpushed commits, also covering the projects inside it
+//! - `errors:<path>`: the errors of a repository or project
+//! - `vulnerable:<advisory>`: an advisory, by ID or alias, in every project
+
+use crate::report::Attention;
+use crate::tags;
+use crate::tr;
+use crate::utils::fs as dh_fs;
+use crate::utils::paths;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// File name of the acknowledgement store inside the state directory
+pub const ACKS_FILE: &str = "acks.json";
+
+/// Seconds in a day
+const DAY: u64 = 86_400;
+
+/// Errors that can occur while reading or writing acknowledgements
+#[derive(Error, Debug)]
+pub enum AckError {
+    #[error("Failed to access acknowledgement store: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse acknowledgement store: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error("Invalid finding ID {0:?}: expected <kind>:<subject>, e.g. dirty:~/src/api or vulnerable:GHSA-xxxx-xxxx-xxxx")]
+    InvalidId(String),
+    #[error("Invalid date {0:?}: expected YYYY-MM-DD")]
+    InvalidDate(String),
+    #[error("Could not determine the DevHealth state directory")]
+    NoStateDirectory,
+}
+
+/// What a finding ID refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FindingId {
+    /// The kind of attention acknowledged
+    pub kind: Attention,
+    /// The repository or project path, or the advisory ID
+    pub subject: String,
+}
+
+impl FromStr for FindingId {
+    type Err = AckError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        let invalid = || AckError::InvalidId(id.to_string());
+        let (kind, subject) = id.split_once(':').ok_or_else(invalid)?;
+        let kind = Attention::from_str(kind, true).map_err(|_| invalid())?;
+        if subject.trim().is_empty() {
+            return Err(invalid());
+        }
+        Ok(FindingId {
+            kind,
+            subject: subject.trim().to_string(),
+        })
+    }
+}
+
+impl fmt::Display for FindingId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = self.kind.to_possible_value().expect("no skipped kinds");
+        write!(f, "{}:{}", kind.get_name(), self.subject)
+    }
+}
+
+impl FindingId {
+    /// Resolves the subject of path-based IDs to a canonical path
+    ///
+    /// Lets `dirty:.` or `errors:~/src/api` match the absolute paths scans
+    /// report. Advisory IDs are returned unchanged.
+    pub fn canonicalized(self) -> FindingId {
+        match self.kind {
+            Attention::Vulnerable => self,
+            _ => FindingId {
+                subject: tags::canonical(Path::new(&dh_fs::expand_home(&self.subject)))
+                    .display()
+                    .to_string(),
+                ..self
+            },
+        }
+    }
+
+    /// Returns `true` if this ID acknowledges `kind` at `path`
+    pub fn covers_path(&self, kind: Attention, path: &Path) -> bool {
+        self.kind == kind
+            && kind != Attention::Vulnerable
+            && (Path::new(&self.subject) == path
+                || Path::new(&self.subject) == tags::canonical(path))
+    }
+
+    /// Returns `true` if this ID acknowledges an advisory known by `ids`
+    pub fn covers_advisory<'a>(&self, mut ids: impl Iterator<Item = &'a str>) -> bool {
+        self.kind == Attention::Vulnerable && ids.any(|id| id.eq_ignore_ascii_case(&self.subject))
+    }
+}
+
+/// A finding acknowledged with `devhealth ack`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Acknowledgement {
+    /// The finding ID, see [`FindingId`]
+    pub id: String,
+    /// When the finding resurfaces, as a Unix timestamp; `None` means never
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<u64>,
+    /// Why the finding was accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// When the finding was acknowledged, as a Unix timestamp
+    pub created_at: u64,
+}
+
+impl Acknowledgement {
+    /// Returns `true` if the acknowledgement has not expired at `now`
+    pub fn is_active(&self, now: u64) -> bool {
+        self.until.is_none_or(|until| now < until)
+    }
+
+    /// Parses the acknowledgement's finding ID
+    ///
+    /// Returns `None` for IDs the store was edited to contain but that are
+    /// not valid.
+    pub fn finding(&self) -> Option<FindingId> {
+        self.id.parse().ok()
+    }
+}
+
+/// Findings acknowledged with the `devhealth ack` command
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AckStore {
+    /// Acknowledgements, sorted by finding ID
+    pub acknowledgements: Vec<Acknowledgement>,
+}
+
+impl AckStore {
+    /// Returns the default location of the acknowledgement store
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AckError::NoStateDirectory`] if no home directory is known.
+    pub fn default_path() -> Result<PathBuf, AckError> {
+        paths::data_dir()
+            .map(|dir| dir.join(ACKS_FILE))
+            .ok_or(AckError::NoStateDirectory)
+    }
+
+    /// Loads the store from `path`, returning an empty store if missing
+    pub fn load(path: &Path) -> Result<AckStore, AckError> {
+        if !path.exists() {
+            return Ok(AckStore::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the store to `path`, creating parent directories if needed
+    pub fn save(&self, path: &Path) -> Result<(), AckError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Records an acknowledgement, replacing any earlier one for the same ID
+    ///
+    /// Acknowledgements that expired before `acknowledgement.created_at` are
+    /// dropped at the same time.
+    pub fn add(&mut self, acknowledgement: Acknowledgement) {
+        let now = acknowledgement.created_at;
+        self.acknowledgements
+            .retain(|existing| existing.id != acknowledgement.id && existing.is_active(now));
+        self.acknowledgements.push(acknowledgement);
+        self.acknowledgements.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+
+    /// Removes the acknowledgement of a finding
+    ///
+    /// Returns `true` if the finding was acknowledged.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.acknowledgements.len();
+        self.acknowledgements.retain(|existing| existing.id != id);
+        self.acknowledgements.len() != before
+    }
+
+    /// Returns the acknowledgements in effect at `now`
+    pub fn active(&self, now: u64) -> Vec<Acknowledgement> {
+        self.acknowledgements
+            .iter()
+            .filter(|ack| ack.is_active(now))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Describes an acknowledgement for listings, e.g.
+/// `dirty:/src/api until 2025-02-01: migration in progress`
+pub fn describe(ack: &Acknowledgement) -> String {
+    let mut line = match ack.until {
+        Some(until) => format!("{} {}", ack.id, tr!("ack-until", date = format_date(until))),
+        None => format!("{} {}", ack.id, tr!("ack-indefinitely")),
+    };
+    if let Some(reason) = &ack.reason {
+        line.push_str(&format!(": {}", reason));
+    }
+    line
+}
+
+/// Parses a `YYYY-MM-DD` date into the Unix timestamp of its start (UTC)
+///
+/// # Errors
+///
+/// Returns [`AckError::InvalidDate`] for anything else.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::acks::parse_date;
+///
+/// assert_eq!(parse_date("2025-02-01").unwrap(), 1_738_368_000);
+/// assert!(parse_date("01/02/2025").is_err());
+/// ```
+pub fn parse_date(date: &str) -> Result<u64, AckError> {
+    let invalid = || AckError::InvalidDate(date.to_string());
+    let mut parts = date.trim().splitn(3, '-');
+    let mut next = |digits: usize| -> Result<i64, AckError> {
+        let part = parts
+            .next()
+            .filter(|part| part.len() == digits)
+            .ok_or_else(invalid)?;
+        part.parse().map_err(|_| invalid())
+    };
+    let (year, month, day) = (next(4)?, next(2)?, next(2)?);
+
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return Err(invalid()),
+    };
+    if !(1..=days_in_month).contains(&day) || year < 1970 {
+        return Err(invalid());
+    }
+
+    // Days since the epoch, following Howard Hinnant's days_from_civil
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    Ok(days as u64 * DAY)
+}
+
+/// Formats a Unix timestamp as its `YYYY-MM-DD` date (UTC)
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::acks::format_date;
+///
+/// assert_eq!(format_date(1_738_368_000), "2025-02-01");
+/// ```
+pub fn format_date(timestamp: u64) -> String {
+    // Howard Hinnant's civil_from_days
+    let days = (timestamp / DAY) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn ack(id: &str, until: Option<u64>) -> Acknowledgement {
+        Acknowledgement {
+            id: id.to_string(),
+            until,
+            reason: None,
+            created_at: 1_000,
+        }
+    }
+
+    mod finding_ids {
+        use super::*;
+
+        #[test]
+        fn parses_kind_and_subject() {
+            let id: FindingId = "vulnerable:GHSA-1234".parse().unwrap();
+            assert_eq!(id.kind, Attention::Vulnerable);
+            assert_eq!(id.subject, "GHSA-1234");
+            assert_eq!(id.to_string(), "vulnerable:GHSA-1234");
+
+            assert!("GHSA-1234".parse::<FindingId>().is_err());
+            assert!("stale:/src/api".parse::<FindingId>().is_err());
+            assert!("dirty:".parse::<FindingId>().is_err());
+        }
+
+        #[test]
+        fn matches_paths_and_advisory_aliases() {
+            let temp_dir = TempDir::new().unwrap();
+            let id = FindingId {
+                kind: Attention::Dirty,
+                subject: temp_dir.path().display().to_string(),
+            }
+            .canonicalized();
+
+            assert!(id.covers_path(Attention::Dirty, temp_dir.path()));
+            assert!(!id.covers_path(Attention::Unpushed, temp_dir.path()));
+
+            let advisory: FindingId = "vulnerable:cve-2024-1".parse().unwrap();
+            assert!(advisory.covers_advisory(["GHSA-1234", "CVE-2024-1"].into_iter()));
+            assert!(!advisory.covers_advisory(["GHSA-1234"].into_iter()));
+        }
+    }
+
+    mod store {
+        use super::*;
+
+        #[test]
+        fn replaces_and_expires_acknowledgements() {
+            let mut store = AckStore::default();
+            store.add(ack("dirty:/src/api", Some(500)));
+            store.add(ack("vulnerable:GHSA-1", None));
+            store.add(Acknowledgement {
+                reason: Some("vendored".to_string()),
+                ..ack("vulnerable:GHSA-1", Some(5_000))
+            });
+
+            let ids: Vec<&str> = store
+                .acknowledgements
+                .iter()
+                .map(|a| a.id.as_str())
+                .collect();
+            assert_eq!(
+                ids,
+                vec!["vulnerable:GHSA-1"],
+                "expired entries are dropped on add"
+            );
+            assert_eq!(store.active(4_999).len(), 1);
+            assert!(
+                store.active(5_000).is_empty(),
+                "resurfaces once the date is reached"
+            );
+
+            assert!(store.remove("vulnerable:GHSA-1"));
+            assert!(!store.remove("vulnerable:GHSA-1"));
+        }
+
+        #[test]
+        fn round_trips_through_disk() {
+            let temp_dir = TempDir::new().unwrap();
+            let path = temp_dir.path().join("state").join(ACKS_FILE);
+            let mut store = AckStore::load(&path).unwrap();
+            store.add(ack("errors:/src/api", None));
+
+            store.save(&path).unwrap();
+            assert_eq!(AckStore::load(&path).unwrap(), store);
+        }
+    }
+
+    mod dates {
+        use super::*;
+
+        #[test]
+        fn converts_between_dates_and_timestamps() {
+            assert_eq!(parse_date("1970-01-01").unwrap(), 0);
+            assert_eq!(parse_date("2024-02-29").unwrap(), 1_709_164_800);
+            assert_eq!(format_date(1_709_164_800), "2024-02-29");
+            assert_eq!(format_date(1_709_164_800 + DAY - 1), "2024-02-29");
+
+            for invalid in ["2023-02-29", "2025-13-01", "2025-1-01", "tomorrow"] {
+                assert!(
+                    parse_date(invalid).is_err(),
+                    "{} should be rejected",
+                    invalid
+                );
+            }
+        }
+    }
+}
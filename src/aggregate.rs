@@ -0,0 +1,725 @@
+//! Team aggregation of scan results from several machines
+//!
+//! Every developer (or CI runner) saves a JSON report with
+//! `devhealth scan --format json > results/<machine>.json`, and
+//! `devhealth merge results/*.json` combines them into one team report:
+//!
+//! - **Repositories**: each repository next to its copies on every machine,
+//!   matched by directory name since checkout paths differ between machines
+//! - **Vulnerability exposure**: each advisory with the machines and projects
+//!   affected by it
+//! - **Dependency exposure**: each dependency with the versions in use and
+//!   the machines using them, so version drift across the team stands out
+//!
+//! Each input is labeled with its file name without extension, so naming the
+//! files after their machines gives a readable report.
+
+use crate::i18n::t;
+use crate::report::ScanResults;
+use crate::scanner::deps::Ecosystem;
+use crate::scanner::git::GitStatus;
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that can occur while loading results to merge
+#[derive(Error, Debug)]
+pub enum MergeError {
+    #[error("Failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{} is not a DevHealth JSON report: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("No result files matched {0}")]
+    NoInputs(String),
+}
+
+/// The results of one machine
+#[derive(Debug, Clone)]
+pub struct MachineResults {
+    /// Label of the machine, taken from the file name
+    pub machine: String,
+    /// The machine's scan results
+    pub results: ScanResults,
+}
+
+/// Scan results of several machines, combined
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TeamReport {
+    /// Labels of the merged machines, in input order
+    pub machines: Vec<String>,
+    /// Repositories with their copies on every machine, by name
+    pub repositories: Vec<SharedRepository>,
+    /// Advisories with the machines and projects they affect
+    pub vulnerabilities: Vec<VulnerabilityExposure>,
+    /// Dependencies with the versions in use across machines
+    pub dependencies: Vec<DependencyExposure>,
+}
+
+/// A repository checked out on one or more machines
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SharedRepository {
+    /// Directory name of the repository
+    pub name: String,
+    /// The repository's copy on each machine that has it
+    pub copies: Vec<RepositoryCopy>,
+}
+
+/// One machine's copy of a repository
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RepositoryCopy {
+    /// Label of the machine
+    pub machine: String,
+    /// Path of the copy on that machine
+    pub path: PathBuf,
+    /// Checked-out branch
+    pub branch: String,
+    /// Whether the copy has uncommitted changes
+    pub dirty: bool,
+    /// Whether the copy has unpushed commits
+    pub unpushed: bool,
+    /// Whether the copy could not be analyzed
+    pub error: bool,
+    /// Health score of the copy (see `GitRepo::health_score`)
+    pub health_score: u8,
+}
+
+/// An advisory and everywhere it applies
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VulnerabilityExposure {
+    /// Identifier of the advisory
+    pub advisory_id: String,
+    /// Summary of the advisory
+    pub summary: String,
+    /// Affected package
+    pub package: String,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+    /// Affected versions in use
+    pub versions: Vec<String>,
+    /// First version containing a fix, if known
+    pub fixed_version: Option<String>,
+    /// Machines with an affected project
+    pub machines: Vec<String>,
+    /// Number of affected manifests across all machines
+    pub projects: usize,
+}
+
+/// A dependency and the versions of it in use across machines
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DependencyExposure {
+    /// Package name
+    pub name: String,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+    /// Version requirement mapped to the machines using it
+    pub versions: BTreeMap<String, Vec<String>>,
+    /// Machines using the dependency in any version
+    pub machines: Vec<String>,
+    /// Number of manifests declaring it across all machines
+    pub projects: usize,
+}
+
+impl DependencyExposure {
+    /// Returns `true` if machines use more than one version
+    pub fn has_drift(&self) -> bool {
+        self.versions.len() > 1
+    }
+}
+
+/// Expands the result file arguments of `merge`
+///
+/// Arguments containing `*` are matched against the files of their
+/// directory (see [`dh_fs::glob_matches`]), so patterns work even where the
+/// shell does not expand them; other arguments are used as given.
+///
+/// # Errors
+///
+/// Returns [`MergeError::NoInputs`] if a pattern matches no file.
+pub fn expand_inputs(arguments: &[PathBuf]) -> Result<Vec<PathBuf>, MergeError> {
+    let mut files = Vec::new();
+    for argument in arguments {
+        let pattern = argument.to_string_lossy();
+        if !pattern.contains('*') {
+            files.push(argument.clone());
+            continue;
+        }
+
+        let dir = argument
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let mut matches: Vec<PathBuf> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && dh_fs::glob_matches(&pattern, path))
+            .collect();
+        if matches.is_empty() {
+            return Err(MergeError::NoInputs(pattern.to_string()));
+        }
+        matches.sort();
+        files.extend(matches);
+    }
+    Ok(files)
+}
+
+/// Loads one machine's JSON report
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a DevHealth JSON
+/// report.
+pub fn load_results(path: &Path) -> Result<MachineResults, MergeError> {
+    let content = fs::read_to_string(path).map_err(|source| MergeError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let results = serde_json::from_str(&content).map_err(|source| MergeError::Json {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let machine = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+    Ok(MachineResults { machine, results })
+}
+
+impl TeamReport {
+    /// Combines the results of several machines
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::aggregate::{MachineResults, TeamReport};
+    /// use devhealth::report::ScanResults;
+    ///
+    /// let report = TeamReport::merge(&[
+    ///     MachineResults { machine: "alice".to_string(), results: ScanResults::default() },
+    ///     MachineResults { machine: "bob".to_string(), results: ScanResults::default() },
+    /// ]);
+    /// assert_eq!(report.machines, vec!["alice", "bob"]);
+    /// ```
+    pub fn merge(inputs: &[MachineResults]) -> TeamReport {
+        let mut repositories: BTreeMap<String, Vec<RepositoryCopy>> = BTreeMap::new();
+        let mut vulnerabilities: BTreeMap<(String, String), VulnerabilityExposure> =
+            BTreeMap::new();
+        let mut dependencies: BTreeMap<(String, Ecosystem), DependencyExposure> = BTreeMap::new();
+
+        for input in inputs {
+            let machine = &input.machine;
+
+            for repo in input.results.git.iter().flatten() {
+                let name = repository_name(&repo.path);
+                repositories.entry(name).or_default().push(RepositoryCopy {
+                    machine: machine.clone(),
+                    path: repo.path.clone(),
+                    branch: repo.branch.clone(),
+                    dirty: repo.uncommitted_changes,
+                    unpushed: repo.unpushed_commits,
+                    error: matches!(repo.status, GitStatus::Error(_)),
+                    health_score: repo.health_score(),
+                });
+            }
+
+            let vulnerable = input
+                .results
+                .audit
+                .iter()
+                .flat_map(|audit| &audit.vulnerabilities);
+            for vuln in vulnerable {
+                let key = (vuln.advisory_id.clone(), vuln.dependency.name.clone());
+                let exposure =
+                    vulnerabilities
+                        .entry(key)
+                        .or_insert_with(|| VulnerabilityExposure {
+                            advisory_id: vuln.advisory_id.clone(),
+                            summary: vuln.summary.clone(),
+                            package: vuln.dependency.name.clone(),
+                            ecosystem: vuln.dependency.ecosystem.clone(),
+                            versions: Vec::new(),
+                            fixed_version: vuln.fixed_version.clone(),
+                            machines: Vec::new(),
+                            projects: 0,
+                        });
+                insert_sorted(&mut exposure.versions, &vuln.version);
+                insert_once(&mut exposure.machines, machine);
+                exposure.projects += 1;
+            }
+
+            let declared = input
+                .results
+                .dependencies
+                .iter()
+                .flatten()
+                .flat_map(|report| &report.dependencies);
+            for dependency in declared {
+                let key = (dependency.name.clone(), dependency.ecosystem.clone());
+                let exposure = dependencies
+                    .entry(key)
+                    .or_insert_with(|| DependencyExposure {
+                        name: dependency.name.clone(),
+                        ecosystem: dependency.ecosystem.clone(),
+                        versions: BTreeMap::new(),
+                        machines: Vec::new(),
+                        projects: 0,
+                    });
+                insert_once(
+                    exposure
+                        .versions
+                        .entry(dependency.version.clone())
+                        .or_default(),
+                    machine,
+                );
+                insert_once(&mut exposure.machines, machine);
+                exposure.projects += 1;
+            }
+        }
+
+        let mut vulnerabilities: Vec<_> = vulnerabilities.into_values().collect();
+        vulnerabilities.sort_by(|a, b| {
+            b.machines
+                .len()
+                .cmp(&a.machines.len())
+                .then_with(|| a.advisory_id.cmp(&b.advisory_id))
+        });
+        let mut dependencies: Vec<_> = dependencies.into_values().collect();
+        dependencies.sort_by(|a, b| {
+            b.machines
+                .len()
+                .cmp(&a.machines.len())
+                .then_with(|| b.has_drift().cmp(&a.has_drift()))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        TeamReport {
+            machines: inputs.iter().map(|input| input.machine.clone()).collect(),
+            repositories: repositories
+                .into_iter()
+                .map(|(name, copies)| SharedRepository { name, copies })
+                .collect(),
+            vulnerabilities,
+            dependencies,
+        }
+    }
+
+    /// Serializes the report as pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Displays the team report at the given level of detail
+    ///
+    /// - [`Detail::Summary`]: header and summary box only
+    /// - [`Detail::Minimal`]: one line per repository, advisory and dependency
+    /// - [`Detail::Normal`]: adds each machine's copy of every repository and
+    ///   caps the dependencies listed
+    /// - [`Detail::Full`]: lists every dependency
+    pub fn display(&self, detail: Detail) {
+        let total = self.machines.len();
+        println!(
+            "{}",
+            display::header(&tr!("merge-title", count = total), "👥", Role::Accent)
+        );
+
+        let copies = || self.repositories.iter().flat_map(|repo| &repo.copies);
+        let dirty = copies().filter(|copy| copy.dirty).count();
+        let unpushed = copies().filter(|copy| copy.unpushed).count();
+        let exposed: BTreeSet<&String> = self
+            .vulnerabilities
+            .iter()
+            .flat_map(|vuln| &vuln.machines)
+            .collect();
+        let warn = |count: usize| {
+            if count > 0 {
+                format!("{} {}", count, "⚠️".paint(Role::Warning))
+            } else {
+                count.to_string()
+            }
+        };
+        let summary_items = vec![
+            (t("merge-machines"), total.to_string()),
+            (t("merge-repositories"), self.repositories.len().to_string()),
+            (t("merge-dirty"), warn(dirty)),
+            (t("merge-unpushed"), warn(unpushed)),
+            (
+                t("merge-exposed"),
+                format!(
+                    "{} {}",
+                    exposed.len(),
+                    display::progress_bar(exposed.len(), total, 10)
+                ),
+            ),
+            (t("merge-advisories"), warn(self.vulnerabilities.len())),
+        ];
+        print!("{}", display::summary_box(&summary_items));
+
+        if detail == Detail::Summary {
+            return;
+        }
+
+        if !self.repositories.is_empty() {
+            println!("{}", display::section_divider(t("merge-repositories")));
+            for (index, repo) in self.repositories.iter().enumerate() {
+                let is_last = index == self.repositories.len() - 1;
+                let dirty = repo.copies.iter().filter(|copy| copy.dirty).count();
+                let line = tr!(
+                    "merge-repo",
+                    name = repo.name,
+                    machines = repo.copies.len(),
+                    dirty = dirty
+                );
+                let role = if dirty > 0 {
+                    Role::Warning
+                } else {
+                    Role::Emphasis
+                };
+                println!(
+                    "{}",
+                    display::tree_item(&line.paint(role).to_string(), is_last, 0)
+                );
+                if detail < Detail::Normal {
+                    continue;
+                }
+                for copy in &repo.copies {
+                    println!(
+                        "{}",
+                        display::tree_continuation(&copy_line(copy), is_last, 0)
+                    );
+                }
+            }
+        }
+
+        if !self.vulnerabilities.is_empty() {
+            println!("{}", display::section_divider(t("merge-vulnerabilities")));
+            for (index, vuln) in self.vulnerabilities.iter().enumerate() {
+                let is_last = index == self.vulnerabilities.len() - 1;
+                let mut line = format!(
+                    "{} {}",
+                    vuln.advisory_id.paint(Role::Error).bold(),
+                    tr!(
+                        "merge-vulnerability",
+                        package = vuln.package,
+                        versions = vuln.versions.join(", "),
+                        machines = vuln.machines.len(),
+                        total = total,
+                        names = vuln.machines.join(", "),
+                        projects = vuln.projects
+                    )
+                );
+                if let Some(fixed) = &vuln.fixed_version {
+                    line.push_str(&format!(
+                        " {}",
+                        tr!("merge-fixed-in", version = fixed).paint(Role::Success)
+                    ));
+                }
+                println!("{}", display::tree_item(&line, is_last, 0));
+            }
+        }
+
+        if !self.dependencies.is_empty() {
+            println!("{}", display::section_divider(t("merge-dependencies")));
+            let limit = detail.dependency_limit().unwrap_or(self.dependencies.len());
+            let shown = &self.dependencies[..limit.min(self.dependencies.len())];
+            for (index, dependency) in shown.iter().enumerate() {
+                let is_last = index == shown.len() - 1 && shown.len() == self.dependencies.len();
+                let versions = dependency
+                    .versions
+                    .iter()
+                    .map(|(version, machines)| format!("{} ({})", version, machines.len()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let line = format!(
+                    "{} {} {}",
+                    display::ecosystem_icon(&dependency.ecosystem.to_string()),
+                    tr!(
+                        "merge-dependency",
+                        name = dependency.name,
+                        machines = dependency.machines.len(),
+                        total = total,
+                        projects = dependency.projects
+                    ),
+                    tr!("merge-versions", versions = versions).paint(if dependency.has_drift() {
+                        Role::Warning
+                    } else {
+                        Role::Muted
+                    })
+                );
+                println!("{}", display::tree_item(&line, is_last, 0));
+            }
+            if shown.len() < self.dependencies.len() {
+                let more = tr!("merge-more", count = self.dependencies.len() - shown.len());
+                println!(
+                    "{}",
+                    display::tree_item(&more.paint(Role::Muted).to_string(), true, 0)
+                );
+            }
+        }
+    }
+}
+
+/// Returns the directory name of a repository
+///
+/// Splits on both `/` and `\`, since reports from Windows machines are
+/// often merged elsewhere.
+fn repository_name(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let path = path.trim_end_matches(['/', '\\']);
+    path.rsplit(['/', '\\']).next().unwrap_or(path).to_string()
+}
+
+/// Formats one machine's copy of a repository
+fn copy_line(copy: &RepositoryCopy) -> String {
+    let status = if copy.error {
+        t("git-error").paint(Role::Error)
+    } else if copy.dirty {
+        t("git-dirty").paint(Role::Warning)
+    } else {
+        t("git-clean").paint(Role::Success)
+    };
+    let mut line = format!(
+        "{} {}",
+        format!("{}:", copy.machine).paint(Role::Highlight).bold(),
+        tr!(
+            "merge-copy",
+            status = status,
+            branch = copy.branch,
+            score = copy.health_score
+        )
+    );
+    if copy.unpushed {
+        line.push_str(&format!(" {}", "↑".paint(Role::Accent).bold()));
+    }
+    line
+}
+
+/// Adds `value` to a list unless it is already there
+fn insert_once(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|existing| existing == value) {
+        list.push(value.to_string());
+    }
+}
+
+/// Adds `value` to a sorted list unless it is already there
+fn insert_sorted(list: &mut Vec<String>, value: &str) {
+    if let Err(position) = list.binary_search_by(|existing| existing.as_str().cmp(value)) {
+        list.insert(position, value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::audit::{AuditReport, Vulnerability};
+    use crate::scanner::deps::{Dependency, DependencyReport, DependencyType};
+    use crate::scanner::git::GitRepo;
+    use tempfile::TempDir;
+
+    fn repo(path: &str, dirty: bool) -> GitRepo {
+        GitRepo {
+            path: PathBuf::from(path),
+            status: if dirty {
+                GitStatus::Dirty
+            } else {
+                GitStatus::Clean
+            },
+            uncommitted_changes: dirty,
+            ..GitRepo::test(path)
+        }
+    }
+
+    fn dependency(name: &str, version: &str, project: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependency_type: DependencyType::Runtime,
+            ecosystem: Ecosystem::Rust,
+            source_file: PathBuf::from(project).join("Cargo.toml"),
+            extras: Vec::new(),
+            marker: None,
+        }
+    }
+
+    fn machine(name: &str, results: ScanResults) -> MachineResults {
+        MachineResults {
+            machine: name.to_string(),
+            results,
+        }
+    }
+
+    mod merging {
+        use super::*;
+
+        #[test]
+        fn groups_repositories_by_name_across_machines() {
+            let report = TeamReport::merge(&[
+                machine(
+                    "alice",
+                    ScanResults {
+                        git: Some(vec![
+                            repo("/home/alice/src/api", true),
+                            repo("/home/alice/src/web", false),
+                        ]),
+                        ..Default::default()
+                    },
+                ),
+                machine(
+                    "bob",
+                    ScanResults {
+                        git: Some(vec![repo("C:\\work\\api", false)]),
+                        ..Default::default()
+                    },
+                ),
+            ]);
+
+            assert_eq!(report.machines, vec!["alice", "bob"]);
+            let names: Vec<&str> = report
+                .repositories
+                .iter()
+                .map(|repo| repo.name.as_str())
+                .collect();
+            assert_eq!(names, vec!["api", "web"]);
+
+            let api = &report.repositories[0];
+            let machines: Vec<&str> = api
+                .copies
+                .iter()
+                .map(|copy| copy.machine.as_str())
+                .collect();
+            assert_eq!(machines, vec!["alice", "bob"]);
+            assert!(api.copies[0].dirty);
+            assert_eq!(api.copies[0].health_score, 60);
+        }
+
+        #[test]
+        fn reports_vulnerability_exposure_per_machine() {
+            let vulnerable = |project: &str| Vulnerability {
+                advisory_id: "GHSA-1234".to_string(),
+                summary: "Bad parsing".to_string(),
+                aliases: Vec::new(),
+                dependency: dependency("serde", "1.0.0", project),
+                version: "1.0.0".to_string(),
+                fixed_version: Some("1.0.1".to_string()),
+            };
+            let audit = |projects: &[&str]| ScanResults {
+                audit: Some(AuditReport {
+                    vulnerabilities: projects.iter().map(|p| vulnerable(p)).collect(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+
+            let report = TeamReport::merge(&[
+                machine("alice", audit(&["/a/api", "/a/web"])),
+                machine("bob", audit(&["/b/api"])),
+                machine("carol", ScanResults::default()),
+            ]);
+
+            assert_eq!(report.vulnerabilities.len(), 1);
+            let exposure = &report.vulnerabilities[0];
+            assert_eq!(exposure.machines, vec!["alice", "bob"]);
+            assert_eq!(exposure.projects, 3);
+            assert_eq!(exposure.versions, vec!["1.0.0"]);
+        }
+
+        #[test]
+        fn tracks_dependency_versions_across_machines() {
+            let deps = |version: &str| ScanResults {
+                dependencies: Some(vec![DependencyReport {
+                    project_path: PathBuf::from("/src/api"),
+                    dependencies: vec![
+                        dependency("serde", version, "/src/api"),
+                        dependency("log", "0.4", "/src/api"),
+                    ],
+                    ecosystems: vec![Ecosystem::Rust],
+                    errors: Vec::new(),
+                    lockfiles: Vec::new(),
+                    go_modules: Vec::new(),
+                }]),
+                ..Default::default()
+            };
+
+            let report = TeamReport::merge(&[
+                machine("alice", deps("1.0")),
+                machine("bob", deps("1.0")),
+                machine("carol", deps("0.9")),
+            ]);
+
+            let serde = &report.dependencies[0];
+            assert_eq!(
+                serde.name, "serde",
+                "drifting dependencies sort first among equals"
+            );
+            assert!(serde.has_drift());
+            assert_eq!(serde.versions["1.0"], vec!["alice", "bob"]);
+            assert_eq!(serde.versions["0.9"], vec!["carol"]);
+            assert!(!report.dependencies[1].has_drift());
+        }
+    }
+
+    mod inputs {
+        use super::*;
+
+        #[test]
+        fn labels_machines_by_file_name() {
+            let temp_dir = TempDir::new().unwrap();
+            let path = temp_dir.path().join("alice-laptop.json");
+            fs::write(&path, "{\"git\": []}").unwrap();
+
+            let loaded = load_results(&path).unwrap();
+            assert_eq!(loaded.machine, "alice-laptop");
+            assert_eq!(loaded.results.git.map(|repos| repos.len()), Some(0));
+        }
+
+        #[test]
+        fn rejects_files_that_are_not_reports() {
+            let temp_dir = TempDir::new().unwrap();
+            let path = temp_dir.path().join("notes.json");
+            fs::write(&path, "not json").unwrap();
+
+            assert!(matches!(load_results(&path), Err(MergeError::Json { .. })));
+        }
+
+        #[test]
+        fn expands_patterns_the_shell_left_alone() {
+            let temp_dir = TempDir::new().unwrap();
+            for name in ["b.json", "a.json", "notes.txt"] {
+                fs::write(temp_dir.path().join(name), "{}").unwrap();
+            }
+            let explicit = temp_dir.path().join("notes.txt");
+
+            let files = expand_inputs(&[temp_dir.path().join("*.json"), explicit.clone()]).unwrap();
+            assert_eq!(
+                files,
+                vec![
+                    temp_dir.path().join("a.json"),
+                    temp_dir.path().join("b.json"),
+                    explicit
+                ]
+            );
+
+            let missing = expand_inputs(&[temp_dir.path().join("*.xml")]);
+            assert!(matches!(missing, Err(MergeError::NoInputs(_))));
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! Credentials kept in the system keyring
+//!
+//! `devhealth auth login <provider>` stores a forge token or the SMTP
+//! password of the email digest in the system keyring (the Keychain on
+//! macOS, the Credential Manager on Windows, the Secret Service on Linux)
+//! under the service `devhealth`, and `devhealth auth logout <provider>`
+//! removes it, so no secret has to sit in plain text in `devhealth.toml`.
+//!
+//! Every network integration resolves its credential with [`resolve`]: the
+//! keyring first, then the provider's environment variables, then the
+//! config. A keyring that cannot be reached, such as on a headless machine
+//! without a Secret Service, counts as empty. Every credential resolved is
+//! masked in reports and logs (see [`crate::utils::redact`]).
+
+use crate::config::Config;
+use crate::i18n::t;
+use crate::utils::redact;
+use clap::ValueEnum;
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal, Write};
+use thiserror::Error;
+
+/// Keyring service every credential is stored under
+pub const KEYRING_SERVICE: &str = "devhealth";
+
+/// Errors that can occur while storing or removing a credential
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("System keyring unavailable: {0}")]
+    Keyring(#[from] keyring::Error),
+    #[error("Failed to read the credential: {0}")]
+    Io(#[from] io::Error),
+    #[error("No {0} given")]
+    Empty(Provider),
+}
+
+/// A service DevHealth holds a credential for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Provider {
+    /// GitHub access token, for CI status, pull requests and fork sync
+    #[value(name = "github")]
+    GitHub,
+    /// GitLab access token, for pipelines and merge requests
+    #[value(name = "gitlab")]
+    GitLab,
+    /// Password of the SMTP server sending the email digest
+    Smtp,
+}
+
+impl Provider {
+    /// Returns the keyring account the credential is stored under
+    pub fn account(self) -> &'static str {
+        match self {
+            Provider::GitHub => "github",
+            Provider::GitLab => "gitlab",
+            Provider::Smtp => "smtp",
+        }
+    }
+
+    /// Returns the environment variables checked after the keyring, in order
+    ///
+    /// The SMTP password has none here: its variable is named by
+    /// `password_env` under `[digest]`.
+    pub fn env_vars(self) -> &'static [&'static str] {
+        match self {
+            Provider::GitHub => &["GITHUB_TOKEN", "GH_TOKEN"],
+            Provider::GitLab => &["GITLAB_TOKEN"],
+            Provider::Smtp => &[],
+        }
+    }
+
+    /// Returns a localized name of the credential, such as "GitHub token"
+    pub fn describe(self) -> &'static str {
+        match self {
+            Provider::GitHub => t("auth-github"),
+            Provider::GitLab => t("auth-gitlab"),
+            Provider::Smtp => t("auth-smtp"),
+        }
+    }
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Provider::GitHub => "GitHub token",
+            Provider::GitLab => "GitLab token",
+            Provider::Smtp => "SMTP password",
+        })
+    }
+}
+
+/// Opens the keyring entry of `provider`
+fn entry(provider: Provider) -> Result<keyring::Entry, keyring::Error> {
+    keyring::Entry::new(KEYRING_SERVICE, provider.account())
+}
+
+/// Returns the credential of `provider` stored in the keyring, if any
+///
+/// # Errors
+///
+/// Returns [`AuthError::Keyring`] if the keyring cannot be reached.
+pub fn stored(provider: Provider) -> Result<Option<String>, AuthError> {
+    match entry(provider)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Stores `secret` as the credential of `provider`, replacing any earlier one
+///
+/// # Errors
+///
+/// Returns [`AuthError::Empty`] for a blank secret and
+/// [`AuthError::Keyring`] if the keyring cannot be reached.
+pub fn store(provider: Provider, secret: &str) -> Result<(), AuthError> {
+    let secret = secret.trim();
+    if secret.is_empty() {
+        return Err(AuthError::Empty(provider));
+    }
+    entry(provider)?.set_password(secret)?;
+    Ok(())
+}
+
+/// Removes the credential of `provider` from the keyring
+///
+/// Returns `false` if none was stored.
+///
+/// # Errors
+///
+/// Returns [`AuthError::Keyring`] if the keyring cannot be reached.
+pub fn remove(provider: Provider) -> Result<bool, AuthError> {
+    match entry(provider)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Resolves the credential of `provider`: the keyring first, then the
+/// environment variables `env_vars` in order, then `config`
+///
+/// Blank values are skipped, and an unreachable keyring counts as empty.
+pub fn resolve(provider: Provider, env_vars: &[&str], config: Option<&str>) -> Option<String> {
+    resolve_with(
+        provider,
+        env_vars,
+        config,
+        |provider| stored(provider).ok().flatten(),
+        |name| std::env::var(name).ok(),
+    )
+}
+
+/// Works like [`resolve`], with the keyring and environment lookups given
+///
+/// The credential found is registered with [`redact::register`], so it is
+/// masked in every report and log of the run.
+pub fn resolve_with(
+    provider: Provider,
+    env_vars: &[&str],
+    config: Option<&str>,
+    keyring: impl Fn(Provider) -> Option<String>,
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    let non_empty = |secret: Option<String>| secret.filter(|s| !s.trim().is_empty());
+    let secret = non_empty(keyring(provider))
+        .or_else(|| env_vars.iter().find_map(|name| non_empty(env(name))))
+        .or_else(|| non_empty(config.map(str::to_string)));
+    if let Some(secret) = &secret {
+        redact::register(secret);
+    }
+    secret
+}
+
+/// Registers the credentials set in the environment and `config` with
+/// [`redact::register`], without reading the keyring
+///
+/// Called at startup, so these are masked in reports and logs even when no
+/// network integration resolves them.
+pub fn register_secrets(config: &Config) {
+    let env_vars = [Provider::GitHub, Provider::GitLab]
+        .iter()
+        .flat_map(|provider| provider.env_vars().iter().copied())
+        .chain([config.digest.password_env.as_str()]);
+    for name in env_vars {
+        if let Ok(secret) = std::env::var(name) {
+            redact::register(&secret);
+        }
+    }
+    for secret in [&config.forge.github_token, &config.forge.gitlab_token]
+        .into_iter()
+        .flatten()
+    {
+        redact::register(secret);
+    }
+}
+
+/// Reads a credential from standard input, prompting on stderr with echo
+/// turned off when it is a terminal
+///
+/// # Errors
+///
+/// Returns [`AuthError::Io`] if reading fails and [`AuthError::Empty`] if
+/// nothing was entered.
+pub fn read_secret(provider: Provider, prompt: &str) -> Result<String, AuthError> {
+    let stdin = io::stdin();
+    let mut line = String::new();
+    if stdin.is_terminal() {
+        eprint!("{} ", prompt);
+        io::stderr().flush()?;
+        let _echo = EchoOff::new();
+        stdin.lock().read_line(&mut line)?;
+        eprintln!();
+    } else {
+        stdin.lock().read_line(&mut line)?;
+    }
+    let secret = line.trim();
+    if secret.is_empty() {
+        return Err(AuthError::Empty(provider));
+    }
+    Ok(secret.to_string())
+}
+
+/// Turns terminal echo off on stdin until dropped
+struct EchoOff {
+    #[cfg(unix)]
+    saved: Option<libc::termios>,
+}
+
+impl EchoOff {
+    #[cfg(unix)]
+    fn new() -> EchoOff {
+        // SAFETY: tcgetattr and tcsetattr only read and write the termios
+        // struct passed to them
+        unsafe {
+            let mut termios = std::mem::zeroed::<libc::termios>();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                return EchoOff { saved: None };
+            }
+            let saved = termios;
+            termios.c_lflag &= !libc::ECHO;
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+            EchoOff { saved: Some(saved) }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn new() -> EchoOff {
+        EchoOff {}
+    }
+}
+
+impl Drop for EchoOff {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(saved) = &self.saved {
+            // SAFETY: restores the settings read in `EchoOff::new`
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, saved);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_keyring_then_environment_then_config() {
+        let env = |name: &str| match name {
+            "GH_TOKEN" => Some("from-gh".to_string()),
+            "GITLAB_TOKEN" => Some(" ".to_string()),
+            _ => None,
+        };
+        let keyring = |provider| (provider == Provider::GitHub).then(|| "from-keyring".to_string());
+        let github = Provider::GitHub;
+
+        assert_eq!(
+            resolve_with(github, github.env_vars(), Some("from-config"), keyring, env).as_deref(),
+            Some("from-keyring")
+        );
+        assert_eq!(
+            resolve_with(
+                github,
+                github.env_vars(),
+                Some("from-config"),
+                |_| None,
+                env
+            )
+            .as_deref(),
+            Some("from-gh")
+        );
+        let gitlab = Provider::GitLab;
+        assert_eq!(
+            resolve_with(gitlab, gitlab.env_vars(), Some("from-config"), keyring, env).as_deref(),
+            Some("from-config"),
+            "Blank variables are skipped"
+        );
+        assert_eq!(
+            resolve_with(gitlab, gitlab.env_vars(), Some(""), |_| None, env),
+            None
+        );
+    }
+
+    #[test]
+    fn refuses_blank_secrets() {
+        assert!(matches!(
+            store(Provider::GitHub, "  \n"),
+            Err(AuthError::Empty(Provider::GitHub))
+        ));
+    }
+}
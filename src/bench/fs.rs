@@ -0,0 +1,614 @@
+//! Filesystem benchmark
+//!
+//! `devhealth bench fs` measures the small-file workload git and compilers
+//! spend most of their I/O on: creating, `stat`-ing and reading many files
+//! of one kilobyte in a scratch directory inside the benchmarked path, which
+//! is removed afterwards.
+//!
+//! A slow result is explained when the cause is visible:
+//!
+//! - the path is on a network filesystem (NFS, SMB, sshfs, …), detected from
+//!   the mount table on Linux, `statfs` on macOS, and UNC paths or mapped
+//!   drives on Windows
+//! - the path is a Windows drive mounted into WSL, where every file access
+//!   crosses into Windows
+//! - otherwise, on a local disk, on-access antivirus scanning or indexing is
+//!   the usual cause of small-file I/O this slow
+
+use crate::acks;
+use crate::bench::{median, BenchError};
+use crate::i18n::t;
+use crate::tr;
+use crate::utils::display;
+use crate::utils::theme::{Paint, Role};
+use crate::utils::wsl;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Size of each file written, in bytes
+const FILE_SIZE: usize = 1024;
+
+/// Filesystem types reached over the network
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb",
+    "smb2",
+    "smb3",
+    "smbfs",
+    "afpfs",
+    "webdav",
+    "davfs",
+    "fuse.sshfs",
+    "sshfs",
+    "osxfuse",
+    "macfuse",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "fuse.glusterfs",
+    "fuse.rclone",
+    "9p",
+];
+
+/// Throughput below which a step counts as slow, in operations per second
+///
+/// Local SSDs manage several times these rates; real-time antivirus scanning
+/// and network round trips fall well below them.
+pub const SLOW_CREATE_PER_SEC: f64 = 2_500.0;
+/// See [`SLOW_CREATE_PER_SEC`]
+pub const SLOW_STAT_PER_SEC: f64 = 25_000.0;
+/// See [`SLOW_CREATE_PER_SEC`]
+pub const SLOW_READ_PER_SEC: f64 = 10_000.0;
+
+/// Why a benchmarked path is slow for builds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowCause {
+    /// The path is on a network filesystem
+    NetworkDrive,
+    /// The path is a Windows drive accessed from WSL
+    WindowsDriveFromWsl,
+    /// Small-file I/O is slow on a local disk
+    SlowLocalDisk,
+}
+
+/// One run of the filesystem benchmark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FsRun {
+    /// When the run started, as a Unix timestamp
+    pub at: u64,
+    /// Where the run was made, e.g. `office`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The benchmarked directory
+    pub path: PathBuf,
+    /// Type of the filesystem holding it, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filesystem: Option<String>,
+    /// Number of files created, stat-ed and read
+    pub files: usize,
+    /// Files created and written per second
+    pub create_per_sec: f64,
+    /// Files stat-ed per second
+    pub stat_per_sec: f64,
+    /// Files read per second
+    pub read_per_sec: f64,
+    /// Why the path is slow for builds, if it is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slow: Option<SlowCause>,
+}
+
+/// Typical throughput of a path across the runs with one label
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FsSummary {
+    /// The benchmarked directory
+    pub path: PathBuf,
+    /// The runs' label
+    pub label: Option<String>,
+    /// Number of runs
+    pub runs: usize,
+    /// Median files created per second
+    pub create_per_sec: f64,
+    /// Median files stat-ed per second
+    pub stat_per_sec: f64,
+    /// Median files read per second
+    pub read_per_sec: f64,
+    /// When the latest of the runs started
+    pub last: u64,
+}
+
+/// Removes the scratch directory, also when the benchmark fails halfway
+struct Scratch(PathBuf);
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Creates, stats and reads `files` small files in a scratch directory inside `path`
+///
+/// # Errors
+///
+/// Returns [`BenchError::Workload`] if the scratch directory or a file
+/// cannot be written or read.
+pub fn run(path: &Path, files: usize, label: Option<String>) -> Result<FsRun, BenchError> {
+    let at = display::unix_now();
+    let workload = |source| BenchError::Workload {
+        path: path.to_path_buf(),
+        source,
+    };
+    let scratch = Scratch(path.join(format!(".devhealth-bench-{}", std::process::id())));
+    fs::create_dir(&scratch.0).map_err(workload)?;
+    let names: Vec<PathBuf> = (0..files)
+        .map(|i| scratch.0.join(format!("file-{:05}.txt", i)))
+        .collect();
+    let content = vec![b'x'; FILE_SIZE];
+    let files = names.len().max(1) as f64;
+
+    let start = Instant::now();
+    for name in &names {
+        fs::File::create(name)
+            .and_then(|mut file| file.write_all(&content))
+            .map_err(workload)?;
+    }
+    let create_per_sec = files / start.elapsed().as_secs_f64();
+
+    let start = Instant::now();
+    for name in &names {
+        fs::metadata(name).map_err(workload)?;
+    }
+    let stat_per_sec = files / start.elapsed().as_secs_f64();
+
+    let start = Instant::now();
+    for name in &names {
+        fs::read(name).map_err(workload)?;
+    }
+    let read_per_sec = files / start.elapsed().as_secs_f64();
+    drop(scratch);
+
+    let filesystem = filesystem_type(path);
+    let mut run = FsRun {
+        at,
+        label,
+        path: path.to_path_buf(),
+        filesystem,
+        files: names.len(),
+        create_per_sec,
+        stat_per_sec,
+        read_per_sec,
+        slow: None,
+    };
+    run.slow = slow_cause(
+        &run,
+        is_network_path(path),
+        wsl::detect().is_some() && wsl::windows_drive(path).is_some(),
+    );
+    Ok(run)
+}
+
+/// Explains why a run is slow for builds, if it is
+///
+/// Network drives and Windows drives under WSL are flagged whatever the
+/// numbers say, since their latency varies; local disks only when a step
+/// falls below its threshold.
+fn slow_cause(run: &FsRun, network: bool, windows_drive: bool) -> Option<SlowCause> {
+    let network = network || run.filesystem.as_deref().is_some_and(is_network_filesystem);
+    if windows_drive {
+        Some(SlowCause::WindowsDriveFromWsl)
+    } else if network {
+        Some(SlowCause::NetworkDrive)
+    } else if run.create_per_sec < SLOW_CREATE_PER_SEC
+        || run.stat_per_sec < SLOW_STAT_PER_SEC
+        || run.read_per_sec < SLOW_READ_PER_SEC
+    {
+        Some(SlowCause::SlowLocalDisk)
+    } else {
+        None
+    }
+}
+
+/// Returns `true` for filesystem types reached over the network
+fn is_network_filesystem(filesystem: &str) -> bool {
+    let filesystem = filesystem.to_lowercase();
+    NETWORK_FILESYSTEMS.contains(&filesystem.as_str())
+}
+
+/// Returns `true` if `path` is on a network filesystem, a UNC share or a
+/// mapped network drive
+pub fn is_network_drive(path: &Path) -> bool {
+    is_network_path(path)
+        || filesystem_type(path)
+            .as_deref()
+            .is_some_and(is_network_filesystem)
+}
+
+/// Returns `true` if a Windows path is a UNC share or a mapped network drive
+fn is_network_path(path: &Path) -> bool {
+    if !cfg!(windows) {
+        return false;
+    }
+    let text = crate::utils::fs::strip_extended_length(path)
+        .to_string_lossy()
+        .into_owned();
+    if text.starts_with(r"\\") {
+        return true;
+    }
+    let Some(drive) = text.get(..2).filter(|drive| drive.ends_with(':')) else {
+        return false;
+    };
+    crate::scanner::container::run_command("net", &["use"]).is_some_and(|output| {
+        output.lines().any(|line| {
+            line.split_whitespace()
+                .any(|word| word.eq_ignore_ascii_case(drive))
+        })
+    })
+}
+
+/// Returns the type of the filesystem holding `path`
+#[cfg(target_os = "linux")]
+fn filesystem_type(path: &Path) -> Option<String> {
+    let path = crate::utils::fs::canonicalize(path).ok()?;
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo").ok()?;
+    mount_type(&mountinfo, &path)
+}
+
+/// Returns the type of the filesystem holding `path`
+#[cfg(target_os = "macos")]
+fn filesystem_type(path: &Path) -> Option<String> {
+    use std::ffi::{CStr, CString};
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `statfs` is plain data, so all-zero is a valid value.
+    let mut stats: libc::statfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stats` points to
+    // a valid, properly aligned local that statfs fills in.
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+    // SAFETY: the kernel NUL-terminates `f_fstypename`.
+    let name = unsafe { CStr::from_ptr(stats.f_fstypename.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+/// Returns the type of the filesystem holding `path`
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn filesystem_type(_path: &Path) -> Option<String> {
+    None
+}
+
+/// Finds the filesystem type of the deepest mount holding `path` in `/proc/self/mountinfo`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn mount_type(mountinfo: &str, path: &Path) -> Option<String> {
+    mountinfo
+        .lines()
+        .filter_map(|line| {
+            let (mount, filesystem) = line.split_once(" - ")?;
+            let mount_point = unescape(mount.split(' ').nth(4)?);
+            let filesystem = filesystem.split(' ').next()?;
+            path.starts_with(&mount_point)
+                .then(|| (mount_point.components().count(), filesystem.to_string()))
+        })
+        .max_by_key(|(depth, _)| *depth)
+        .map(|(_, filesystem)| filesystem)
+}
+
+/// Decodes the octal escapes (`\040` for a space) of a mount point
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn unescape(field: &str) -> PathBuf {
+    let mut decoded = String::new();
+    let mut rest = field;
+    while let Some(index) = rest.find('\\') {
+        decoded.push_str(&rest[..index]);
+        let code = rest
+            .get(index + 1..index + 4)
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match code {
+            Some(code) => {
+                decoded.push(code as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                decoded.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    PathBuf::from(decoded)
+}
+
+/// Summarizes the runs per path and label
+pub fn summarize(runs: &[FsRun]) -> Vec<FsSummary> {
+    let mut grouped = BTreeMap::<(PathBuf, Option<String>), Vec<&FsRun>>::new();
+    for run in runs {
+        grouped
+            .entry((run.path.clone(), run.label.clone()))
+            .or_default()
+            .push(run);
+    }
+    grouped
+        .into_iter()
+        .map(|((path, label), runs)| {
+            let step = |pick: fn(&FsRun) -> f64| {
+                median(&runs.iter().map(|run| pick(run)).collect::<Vec<_>>()).unwrap_or_default()
+            };
+            FsSummary {
+                path,
+                label,
+                runs: runs.len(),
+                create_per_sec: step(|run| run.create_per_sec),
+                stat_per_sec: step(|run| run.stat_per_sec),
+                read_per_sec: step(|run| run.read_per_sec),
+                last: runs.iter().map(|run| run.at).max().unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Displays a run next to the usual throughput of earlier runs on its path with its label
+pub fn display_run(run: &FsRun, earlier: &[FsRun]) {
+    let path = run.path.display().to_string();
+    let title = match &run.filesystem {
+        Some(filesystem) => tr!(
+            "bench-fs-title-type",
+            path = path.as_str(),
+            filesystem = filesystem.as_str()
+        ),
+        None => tr!("bench-fs-title", path = path.as_str()),
+    };
+    println!("{}", display::header(&title, "🗂️", Role::Accent));
+
+    let same: Vec<FsRun> = earlier
+        .iter()
+        .filter(|earlier| earlier.path == run.path && earlier.label == run.label)
+        .cloned()
+        .collect();
+    let usual = summarize(&same).into_iter().next();
+    let steps = [
+        (
+            t("bench-fs-create"),
+            run.create_per_sec,
+            SLOW_CREATE_PER_SEC,
+            usual.as_ref().map(|usual| usual.create_per_sec),
+        ),
+        (
+            t("bench-fs-stat"),
+            run.stat_per_sec,
+            SLOW_STAT_PER_SEC,
+            usual.as_ref().map(|usual| usual.stat_per_sec),
+        ),
+        (
+            t("bench-fs-read"),
+            run.read_per_sec,
+            SLOW_READ_PER_SEC,
+            usual.as_ref().map(|usual| usual.read_per_sec),
+        ),
+    ];
+    let width = steps
+        .iter()
+        .map(|(name, ..)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (name, rate, threshold, usual) in steps {
+        let value = tr!("bench-fs-rate", rate = per_sec(rate));
+        let value = if rate < threshold {
+            value.paint(Role::Warning).to_string()
+        } else {
+            value
+        };
+        let line = match usual {
+            Some(usual) => format!(
+                "{}  {}",
+                value,
+                tr!("bench-fs-usual", rate = per_sec(usual)).paint(Role::Muted)
+            ),
+            None => value,
+        };
+        println!(
+            "{:width$}  {}",
+            name.paint(Role::Emphasis).bold(),
+            line,
+            width = width
+        );
+    }
+
+    let verdict = match run.slow {
+        None => format!("✅ {}", t("bench-fs-fast").paint(Role::Success)),
+        Some(SlowCause::NetworkDrive) => {
+            format!("⚠️  {}", t("bench-fs-network").paint(Role::Warning))
+        }
+        Some(SlowCause::WindowsDriveFromWsl) => {
+            format!("⚠️  {}", t("bench-fs-wsl-drive").paint(Role::Warning))
+        }
+        Some(SlowCause::SlowLocalDisk) => {
+            format!("⚠️  {}", t("bench-fs-slow-local").paint(Role::Warning))
+        }
+    };
+    println!("\n{}", display::symbols(&verdict));
+}
+
+/// Displays the typical throughput of every path, per label
+pub fn display_history(runs: &[FsRun]) {
+    if runs.is_empty() {
+        println!(
+            "{}",
+            display::symbols(&format!("ℹ️  {}", t("bench-fs-history-empty")))
+        );
+        return;
+    }
+    println!(
+        "{}",
+        display::header(
+            &tr!("bench-fs-history-title", count = runs.len()),
+            "🗂️",
+            Role::Accent
+        )
+    );
+
+    let summaries = summarize(runs);
+    let mut paths: Vec<&PathBuf> = summaries.iter().map(|summary| &summary.path).collect();
+    paths.dedup();
+    for path in paths {
+        println!(
+            "\n{}",
+            path.display().to_string().paint(Role::Emphasis).bold()
+        );
+        let labels: Vec<&FsSummary> = summaries
+            .iter()
+            .filter(|summary| &summary.path == path)
+            .collect();
+        for (i, summary) in labels.iter().enumerate() {
+            let line = format!(
+                "{}: {}",
+                tr!(
+                    "bench-history-label",
+                    label = summary.label.as_deref().unwrap_or(t("bench-unlabeled")),
+                    runs = summary.runs,
+                    date = acks::format_date(summary.last)
+                ),
+                tr!(
+                    "bench-fs-rates",
+                    create = per_sec(summary.create_per_sec),
+                    stat = per_sec(summary.stat_per_sec),
+                    read = per_sec(summary.read_per_sec)
+                )
+            );
+            println!("{}", display::tree_item(&line, i + 1 == labels.len(), 0));
+        }
+    }
+}
+
+/// Formats a rate with thousands separators, e.g. `12,345`
+fn per_sec(rate: f64) -> String {
+    let digits = format!("{:.0}", rate);
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fs_run(path: &str, label: Option<&str>, create: f64) -> FsRun {
+        FsRun {
+            at: create as u64,
+            label: label.map(str::to_string),
+            path: PathBuf::from(path),
+            filesystem: Some("ext4".to_string()),
+            files: 1000,
+            create_per_sec: create,
+            stat_per_sec: 200_000.0,
+            read_per_sec: 60_000.0,
+            slow: None,
+        }
+    }
+
+    #[test]
+    fn runs_the_workload_and_cleans_up() {
+        let dir = TempDir::new().unwrap();
+        let run = run(dir.path(), 50, Some("laptop".to_string())).unwrap();
+        assert_eq!(run.files, 50);
+        assert!(run.create_per_sec > 0.0 && run.stat_per_sec > 0.0 && run.read_per_sec > 0.0);
+        assert_eq!(
+            fs::read_dir(dir.path()).unwrap().count(),
+            0,
+            "the scratch directory is removed"
+        );
+
+        let missing = dir.path().join("missing");
+        assert!(matches!(
+            super::run(&missing, 10, None),
+            Err(BenchError::Workload { .. })
+        ));
+    }
+
+    #[test]
+    fn explains_slow_paths() {
+        let fast = fs_run("/src", None, 30_000.0);
+        assert_eq!(slow_cause(&fast, false, false), None);
+        assert_eq!(
+            slow_cause(&fs_run("/src", None, 900.0), false, false),
+            Some(SlowCause::SlowLocalDisk)
+        );
+        assert_eq!(
+            slow_cause(&fast, false, true),
+            Some(SlowCause::WindowsDriveFromWsl)
+        );
+        assert_eq!(
+            slow_cause(&fast, true, false),
+            Some(SlowCause::NetworkDrive)
+        );
+
+        let nfs = FsRun {
+            filesystem: Some("nfs4".to_string()),
+            ..fast
+        };
+        assert_eq!(
+            slow_cause(&nfs, false, false),
+            Some(SlowCause::NetworkDrive)
+        );
+    }
+
+    #[test]
+    fn finds_the_deepest_mount() {
+        let mountinfo = "\
+22 1 8:1 / / rw,relatime - ext4 /dev/sda1 rw
+40 22 0:50 / /home/ada/shared rw,relatime - nfs4 fileserver:/export rw
+41 22 0:51 / /mnt/my\\040drive rw,relatime - cifs //nas/share rw
+";
+        assert_eq!(
+            mount_type(mountinfo, Path::new("/home/ada/src")).as_deref(),
+            Some("ext4")
+        );
+        assert_eq!(
+            mount_type(mountinfo, Path::new("/home/ada/shared/api")).as_deref(),
+            Some("nfs4")
+        );
+        assert_eq!(
+            mount_type(mountinfo, Path::new("/mnt/my drive/x")).as_deref(),
+            Some("cifs")
+        );
+        assert_eq!(
+            mount_type(mountinfo, Path::new("/home/ada/sharedx")).as_deref(),
+            Some("ext4")
+        );
+    }
+
+    #[test]
+    fn summarizes_runs_per_path_and_label() {
+        let runs = [
+            fs_run("/src", Some("office"), 3_000.0),
+            fs_run("/src", Some("office"), 5_000.0),
+            fs_run("/src", None, 40_000.0),
+            fs_run("/mnt/c/src", None, 800.0),
+        ];
+        let summaries = summarize(&runs);
+        assert_eq!(summaries.len(), 3);
+        let office = summaries
+            .iter()
+            .find(|summary| summary.label.as_deref() == Some("office"))
+            .unwrap();
+        assert_eq!(
+            (office.runs, office.create_per_sec, office.last),
+            (2, 4_000.0, 5_000)
+        );
+        assert_eq!(per_sec(1_234_567.4), "1,234,567");
+        assert_eq!(per_sec(999.0), "999");
+    }
+}
@@ -0,0 +1,146 @@
+//! Benchmarks of the environment development depends on
+//!
+//! Benchmarks are opt-in: they only run through `devhealth bench`, never as
+//! part of `check` or `scan`.
+//!
+//! - [`net`]: DNS resolution, connection and TLS handshake times to
+//!   registries, forges and internal servers
+//! - [`fs`]: small-file create, stat and read throughput of a directory
+//!
+//! Every run is appended to `bench-history.json` in the DevHealth data
+//! directory, optionally labeled (`office`, `home`, `vpn`), so results from
+//! different places and times can be compared.
+
+pub mod fs;
+pub mod net;
+
+use crate::utils::paths;
+use fs::FsRun;
+use net::NetRun;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the benchmark history inside the state directory
+pub const HISTORY_FILE: &str = "bench-history.json";
+
+/// Number of runs of each benchmark kept in the history
+pub const MAX_RUNS: usize = 500;
+
+/// Errors that can occur while running benchmarks or keeping their history
+#[derive(Error, Debug)]
+pub enum BenchError {
+    #[error("Failed to access benchmark history: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse benchmark history: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error("Invalid endpoint {0:?}: expected a host, host:port or https:// URL")]
+    InvalidEndpoint(String),
+    #[error("Failed to benchmark {}: {source}", path.display())]
+    Workload {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Could not determine the DevHealth state directory")]
+    NoStateDirectory,
+}
+
+/// Results of earlier benchmark runs, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenchHistory {
+    /// Network benchmark runs
+    #[serde(default)]
+    pub network: Vec<NetRun>,
+    /// Filesystem benchmark runs
+    #[serde(default)]
+    pub filesystem: Vec<FsRun>,
+}
+
+impl BenchHistory {
+    /// Returns the default location of the benchmark history
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BenchError::NoStateDirectory`] if no home directory is known.
+    pub fn default_path() -> Result<PathBuf, BenchError> {
+        paths::data_dir()
+            .map(|dir| dir.join(HISTORY_FILE))
+            .ok_or(BenchError::NoStateDirectory)
+    }
+
+    /// Loads the history from `path`, returning an empty history if missing
+    pub fn load(path: &Path) -> Result<BenchHistory, BenchError> {
+        if !path.exists() {
+            return Ok(BenchHistory::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the history to `path`, creating parent directories if needed
+    pub fn save(&self, path: &Path) -> Result<(), BenchError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Appends a network run, dropping the oldest beyond [`MAX_RUNS`]
+    pub fn add_network(&mut self, run: NetRun) {
+        self.network.push(run);
+        let excess = self.network.len().saturating_sub(MAX_RUNS);
+        self.network.drain(..excess);
+    }
+
+    /// Appends a filesystem run, dropping the oldest beyond [`MAX_RUNS`]
+    pub fn add_filesystem(&mut self, run: FsRun) {
+        self.filesystem.push(run);
+        let excess = self.filesystem.len().saturating_sub(MAX_RUNS);
+        self.filesystem.drain(..excess);
+    }
+}
+
+/// Returns the median of some measurements, `None` if there are none
+pub fn median(values: &[f64]) -> Option<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let middle = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        len if len % 2 == 0 => Some((sorted[middle - 1] + sorted[middle]) / 2.0),
+        _ => Some(sorted[middle]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn takes_the_median() {
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&[30.0, 10.0, 20.0]), Some(20.0));
+        assert_eq!(median(&[40.0, 10.0, 20.0, 30.0]), Some(25.0));
+    }
+
+    #[test]
+    fn keeps_a_bounded_history() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state").join(HISTORY_FILE);
+        let mut history = BenchHistory::load(&path).unwrap();
+        for at in 0..MAX_RUNS as u64 + 3 {
+            history.add_network(NetRun {
+                at,
+                label: None,
+                results: Vec::new(),
+            });
+        }
+        history.save(&path).unwrap();
+
+        let loaded = BenchHistory::load(&path).unwrap();
+        assert_eq!(loaded.network.len(), MAX_RUNS);
+        assert_eq!(loaded.network[0].at, 3, "oldest runs are dropped");
+    }
+}
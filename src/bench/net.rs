@@ -0,0 +1,542 @@
+//! Network benchmark
+//!
+//! `devhealth bench net` connects to each endpoint a few times and times the
+//! three steps every registry request goes through:
+//!
+//! - **DNS**: resolving the host name
+//! - **connect**: the TCP handshake with the first address
+//! - **TLS**: the TLS handshake, verified against the system trust store
+//!
+//! The median of the rounds is reported. Endpoints are the public registries
+//! and forges (crates.io, npm, PyPI, GitHub), the self-managed GitLab hosts
+//! from `[forge]` and the `endpoints` of the `[bench]` section, for internal
+//! servers such as an Artifactory mirror.
+//!
+//! Connections are made directly, not through a proxy; behind a proxy that
+//! blocks direct connections every endpoint reports an error.
+
+use crate::acks;
+use crate::bench::{median, BenchError};
+use crate::i18n::t;
+use crate::tr;
+use crate::utils::display;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Name of the network benchmark as recorded by `--offline`
+pub const NET_BENCH_CHECK: &str = "network benchmark";
+
+/// Endpoints measured unless others are given
+pub const DEFAULT_ENDPOINTS: [&str; 4] = [
+    "index.crates.io",
+    "registry.npmjs.org",
+    "pypi.org",
+    "github.com",
+];
+
+/// How long connecting or a handshake may take before the round fails
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A run counts as slow when it takes this many times the usual time
+const SLOW_FACTOR: f64 = 2.0;
+
+/// A host and port to measure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// Host name
+    pub host: String,
+    /// TCP port, 443 unless given
+    pub port: u16,
+}
+
+impl FromStr for Endpoint {
+    type Err = BenchError;
+
+    /// Parses a host (`pypi.org`), a host and port (`artifactory:8443`) or
+    /// an `https://` URL, whose path is ignored
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || BenchError::InvalidEndpoint(value.to_string());
+        let rest = match value.trim().split_once("://") {
+            Some(("https", rest)) => rest,
+            Some(_) => return Err(invalid()),
+            None => value.trim(),
+        };
+        let authority = rest.split('/').next().unwrap_or_default();
+        let authority = authority
+            .rsplit_once('@')
+            .map_or(authority, |(_, host)| host);
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid())?),
+            None => (authority, 443),
+        };
+        if host.is_empty() || host.contains(char::is_whitespace) {
+            return Err(invalid());
+        }
+        Ok(Endpoint {
+            host: host.to_lowercase(),
+            port,
+        })
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.port == 443 {
+            write!(f, "{}", self.host)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// Median timings of one endpoint, in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointResult {
+    /// The endpoint, as `host` or `host:port`
+    pub endpoint: String,
+    /// Host name resolution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_ms: Option<f64>,
+    /// TCP handshake
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_ms: Option<f64>,
+    /// TLS handshake
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_ms: Option<f64>,
+    /// Why every round failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl EndpointResult {
+    /// Returns the time until the connection was ready, if every step succeeded
+    pub fn total_ms(&self) -> Option<f64> {
+        Some(self.dns_ms? + self.connect_ms? + self.tls_ms?)
+    }
+}
+
+/// One run of the network benchmark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetRun {
+    /// When the run started, as a Unix timestamp
+    pub at: u64,
+    /// Where the run was made, e.g. `office`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Timings of each endpoint
+    pub results: Vec<EndpointResult>,
+}
+
+/// Typical timings of an endpoint across the runs with one label
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LabelSummary {
+    /// The runs' label
+    pub label: Option<String>,
+    /// Number of runs that reached the endpoint
+    pub runs: usize,
+    /// Median host name resolution
+    pub dns_ms: Option<f64>,
+    /// Median TCP handshake
+    pub connect_ms: Option<f64>,
+    /// Median TLS handshake
+    pub tls_ms: Option<f64>,
+    /// When the latest of the runs started
+    pub last: u64,
+}
+
+impl LabelSummary {
+    /// Returns the usual time until the connection was ready
+    pub fn total_ms(&self) -> Option<f64> {
+        Some(self.dns_ms? + self.connect_ms? + self.tls_ms?)
+    }
+}
+
+/// Typical timings of an endpoint, per label
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EndpointSummary {
+    /// The endpoint, as `host` or `host:port`
+    pub endpoint: String,
+    /// Summaries by label, unlabeled runs first
+    pub labels: Vec<LabelSummary>,
+}
+
+/// Builds the endpoint list: the defaults, the GitLab hosts and the configured endpoints
+///
+/// Duplicates are dropped.
+///
+/// # Errors
+///
+/// Returns [`BenchError::InvalidEndpoint`] for a configured endpoint that
+/// cannot be parsed.
+pub fn endpoints(
+    gitlab_hosts: &[String],
+    configured: &[String],
+) -> Result<Vec<Endpoint>, BenchError> {
+    let mut endpoints: Vec<Endpoint> = Vec::new();
+    let names = DEFAULT_ENDPOINTS
+        .iter()
+        .map(|name| name.to_string())
+        .chain(gitlab_hosts.iter().cloned());
+    for name in names.chain(configured.iter().cloned()) {
+        let endpoint: Endpoint = name.parse()?;
+        if !endpoints.contains(&endpoint) {
+            endpoints.push(endpoint);
+        }
+    }
+    Ok(endpoints)
+}
+
+/// Measures every endpoint `rounds` times
+pub fn run(endpoints: &[Endpoint], rounds: usize, label: Option<String>) -> NetRun {
+    let at = display::unix_now();
+    let results = endpoints
+        .iter()
+        .map(|endpoint| {
+            let mut timings = Vec::new();
+            let mut error = None;
+            for _ in 0..rounds.max(1) {
+                match measure(endpoint) {
+                    Ok(timing) => timings.push(timing),
+                    Err(message) => error = Some(message),
+                }
+            }
+            let step =
+                |pick: fn(&Timing) -> f64| median(&timings.iter().map(pick).collect::<Vec<_>>());
+            EndpointResult {
+                endpoint: endpoint.to_string(),
+                dns_ms: step(|timing| timing.dns_ms),
+                connect_ms: step(|timing| timing.connect_ms),
+                tls_ms: step(|timing| timing.tls_ms),
+                error: if timings.is_empty() { error } else { None },
+            }
+        })
+        .collect();
+    NetRun { at, label, results }
+}
+
+/// Timings of one round, in milliseconds
+struct Timing {
+    dns_ms: f64,
+    connect_ms: f64,
+    tls_ms: f64,
+}
+
+/// Resolves, connects to and shakes hands with an endpoint once
+fn measure(endpoint: &Endpoint) -> Result<Timing, String> {
+    let ms = |start: Instant| start.elapsed().as_secs_f64() * 1000.0;
+
+    let start = Instant::now();
+    let address = (endpoint.host.as_str(), endpoint.port)
+        .to_socket_addrs()
+        .map_err(|error| error.to_string())?
+        .next()
+        .ok_or_else(|| t("bench-net-no-address").to_string())?;
+    let dns_ms = ms(start);
+
+    let start = Instant::now();
+    let stream =
+        TcpStream::connect_timeout(&address, TIMEOUT).map_err(|error| error.to_string())?;
+    let connect_ms = ms(start);
+    stream
+        .set_read_timeout(Some(TIMEOUT))
+        .map_err(|error| error.to_string())?;
+    stream
+        .set_write_timeout(Some(TIMEOUT))
+        .map_err(|error| error.to_string())?;
+
+    let connector = native_tls::TlsConnector::new().map_err(|error| error.to_string())?;
+    let start = Instant::now();
+    connector
+        .connect(&endpoint.host, stream)
+        .map_err(|error| error.to_string())?;
+    let tls_ms = ms(start);
+
+    Ok(Timing {
+        dns_ms,
+        connect_ms,
+        tls_ms,
+    })
+}
+
+/// Summarizes the runs per endpoint and label
+///
+/// Endpoints are listed in the order they first appear. Runs in which an
+/// endpoint failed do not count towards its medians.
+pub fn summarize(runs: &[NetRun]) -> Vec<EndpointSummary> {
+    let mut order: Vec<String> = Vec::new();
+    let mut grouped = BTreeMap::<(String, Option<String>), Vec<(u64, &EndpointResult)>>::new();
+    for run in runs {
+        for result in run.results.iter().filter(|result| result.error.is_none()) {
+            if !order.contains(&result.endpoint) {
+                order.push(result.endpoint.clone());
+            }
+            grouped
+                .entry((result.endpoint.clone(), run.label.clone()))
+                .or_default()
+                .push((run.at, result));
+        }
+    }
+
+    order
+        .into_iter()
+        .map(|endpoint| {
+            let labels = grouped
+                .iter()
+                .filter(|((name, _), _)| *name == endpoint)
+                .map(|((_, label), results)| {
+                    let step = |pick: fn(&EndpointResult) -> Option<f64>| {
+                        median(
+                            &results
+                                .iter()
+                                .filter_map(|(_, result)| pick(result))
+                                .collect::<Vec<_>>(),
+                        )
+                    };
+                    LabelSummary {
+                        label: label.clone(),
+                        runs: results.len(),
+                        dns_ms: step(|result| result.dns_ms),
+                        connect_ms: step(|result| result.connect_ms),
+                        tls_ms: step(|result| result.tls_ms),
+                        last: results.iter().map(|(at, _)| *at).max().unwrap_or_default(),
+                    }
+                })
+                .collect();
+            EndpointSummary { endpoint, labels }
+        })
+        .collect()
+}
+
+/// Displays a run next to the usual timings of earlier runs with its label
+pub fn display_run(run: &NetRun, earlier: &[NetRun]) {
+    let title = match &run.label {
+        Some(label) => tr!("bench-net-title-label", label = label.as_str()),
+        None => t("bench-net-title").to_string(),
+    };
+    println!("{}", display::header(&title, "🌐", Role::Accent));
+
+    let same_label: Vec<NetRun> = earlier
+        .iter()
+        .filter(|earlier| earlier.label == run.label)
+        .cloned()
+        .collect();
+    let usual = summarize(&same_label);
+    let width = run
+        .results
+        .iter()
+        .map(|result| result.endpoint.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for result in &run.results {
+        let name = format!("{:width$}", result.endpoint, width = width)
+            .paint(Role::Emphasis)
+            .bold();
+        let Some(total) = result.total_ms() else {
+            let error = result.error.as_deref().unwrap_or(t("system-unknown"));
+            println!(
+                "{}  {}",
+                name,
+                tr!("bench-net-failed", error = error).paint(Role::Error)
+            );
+            continue;
+        };
+        let timing = timings(result.dns_ms, result.connect_ms, result.tls_ms);
+        let previous = usual
+            .iter()
+            .find(|summary| summary.endpoint == result.endpoint)
+            .and_then(|summary| summary.labels.first());
+        match previous {
+            Some(previous) => {
+                let slow = previous
+                    .total_ms()
+                    .is_some_and(|usual| total > usual * SLOW_FACTOR);
+                let timing = if slow {
+                    format!("{} ({})", timing, t("bench-net-slower"))
+                        .paint(Role::Warning)
+                        .to_string()
+                } else {
+                    timing
+                };
+                let usually = tr!(
+                    "bench-net-usual",
+                    timing = timings(previous.dns_ms, previous.connect_ms, previous.tls_ms),
+                    runs = previous.runs
+                );
+                println!("{}  {}", name, timing);
+                println!(
+                    "{}",
+                    display::tree_item(&usually.paint(Role::Muted).to_string(), true, 0)
+                );
+            }
+            None => println!("{}  {}", name, timing),
+        }
+    }
+}
+
+/// Displays the typical timings of every endpoint, per label
+pub fn display_history(runs: &[NetRun]) {
+    if runs.is_empty() {
+        println!(
+            "{}",
+            display::symbols(&format!("ℹ️  {}", t("bench-history-empty")))
+        );
+        return;
+    }
+    println!(
+        "{}",
+        display::header(
+            &tr!("bench-history-title", count = runs.len()),
+            "🌐",
+            Role::Accent
+        )
+    );
+
+    for summary in summarize(runs) {
+        println!("\n{}", summary.endpoint.paint(Role::Emphasis).bold());
+        for (i, label) in summary.labels.iter().enumerate() {
+            let line = format!(
+                "{}: {}",
+                tr!(
+                    "bench-history-label",
+                    label = label.label.as_deref().unwrap_or(t("bench-unlabeled")),
+                    runs = label.runs,
+                    date = acks::format_date(label.last)
+                ),
+                timings(label.dns_ms, label.connect_ms, label.tls_ms)
+            );
+            println!(
+                "{}",
+                display::tree_item(&line, i + 1 == summary.labels.len(), 0)
+            );
+        }
+    }
+}
+
+/// Formats the three steps, e.g. `DNS 12 ms · connect 25 ms · TLS 48 ms`
+fn timings(dns: Option<f64>, connect: Option<f64>, tls: Option<f64>) -> String {
+    let ms = |value: Option<f64>| match value {
+        Some(value) if value < 10.0 => format!("{:.1} ms", value),
+        Some(value) => format!("{:.0} ms", value),
+        None => "–".to_string(),
+    };
+    tr!(
+        "bench-net-timing",
+        dns = ms(dns),
+        connect = ms(connect),
+        tls = ms(tls)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(endpoint: &str, dns: f64, connect: f64, tls: f64) -> EndpointResult {
+        EndpointResult {
+            endpoint: endpoint.to_string(),
+            dns_ms: Some(dns),
+            connect_ms: Some(connect),
+            tls_ms: Some(tls),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn parses_endpoints() {
+        let parse = |value: &str| {
+            value
+                .parse::<Endpoint>()
+                .map(|endpoint| endpoint.to_string())
+        };
+        assert_eq!(parse("pypi.org").unwrap(), "pypi.org");
+        assert_eq!(
+            parse("https://artifactory.corp.example:8443/api/npm/").unwrap(),
+            "artifactory.corp.example:8443"
+        );
+        assert_eq!(
+            parse("GitLab.Corp.Example:443").unwrap(),
+            "gitlab.corp.example"
+        );
+        assert!(
+            parse("http://registry.local").is_err(),
+            "only TLS endpoints are measured"
+        );
+        assert!(parse("host:port").is_err());
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn lists_each_endpoint_once() {
+        let endpoints = endpoints(
+            &["gitlab.corp.example".to_string()],
+            &[
+                "https://github.com/".to_string(),
+                "artifactory.corp.example".to_string(),
+            ],
+        )
+        .unwrap();
+        let names: Vec<String> = endpoints.iter().map(Endpoint::to_string).collect();
+        assert_eq!(
+            names,
+            [
+                "index.crates.io",
+                "registry.npmjs.org",
+                "pypi.org",
+                "github.com",
+                "gitlab.corp.example",
+                "artifactory.corp.example"
+            ]
+        );
+        assert!(super::endpoints(&[], &["ftp://mirror".to_string()]).is_err());
+    }
+
+    #[test]
+    fn summarizes_runs_per_label() {
+        let run = |at, label: Option<&str>, results| NetRun {
+            at,
+            label: label.map(str::to_string),
+            results,
+        };
+        let failed = EndpointResult {
+            error: Some("timed out".to_string()),
+            ..result("pypi.org", 0.0, 0.0, 0.0)
+        };
+        let runs = [
+            run(
+                1,
+                Some("office"),
+                vec![result("pypi.org", 200.0, 30.0, 90.0), failed],
+            ),
+            run(
+                2,
+                Some("office"),
+                vec![result("pypi.org", 180.0, 40.0, 80.0)],
+            ),
+            run(
+                3,
+                Some("office"),
+                vec![result("pypi.org", 220.0, 35.0, 100.0)],
+            ),
+            run(4, Some("home"), vec![result("pypi.org", 8.0, 20.0, 45.0)]),
+        ];
+
+        let summaries = summarize(&runs);
+        assert_eq!(summaries.len(), 1);
+        let labels = &summaries[0].labels;
+        assert_eq!(labels[0].label.as_deref(), Some("home"));
+        let office = &labels[1];
+        assert_eq!((office.runs, office.last), (3, 3));
+        assert_eq!(
+            (office.dns_ms, office.connect_ms, office.tls_ms),
+            (Some(200.0), Some(35.0), Some(90.0))
+        );
+        assert_eq!(office.total_ms(), Some(325.0));
+    }
+}
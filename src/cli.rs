@@ -4,7 +4,15 @@
 //! It provides two main commands: `check` for quick health checks and `scan`
 //! for comprehensive analysis with configurable options.
 
-use clap::{Parser, Subcommand};
+use crate::auth::Provider;
+use crate::findings::Severity;
+use crate::i18n::Lang;
+use crate::prompt;
+use crate::report::{Attention, OutputFormat, ReportOptions, SortKey};
+use crate::scanner::licenses::LicenseFormat;
+use crate::utils::display::{Detail, View};
+use crate::utils::lock::LockMode;
+use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 
 /// DevHealth CLI application
@@ -19,6 +27,48 @@ pub struct Cli {
     /// The subcommand to execute
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Disable every network-dependent check
+    ///
+    /// Registry freshness, vulnerability audits, and forge enrichment are
+    /// skipped and listed at the end of the report, so scans are
+    /// deterministic on airgapped machines.
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Use plain-text labels instead of emoji, boxes and tree lines
+    ///
+    /// Produces output suited to screen readers and terminals without UTF-8
+    /// support. Enabled automatically when the locale is not UTF-8.
+    #[arg(long, global = true)]
+    pub accessible: bool,
+
+    /// Language of the report (defaults to the `lang` config setting or the locale)
+    #[arg(long, global = true, value_enum)]
+    pub lang: Option<Lang>,
+
+    /// Wait for another DevHealth run updating the same state to finish (default)
+    #[arg(long, global = true, overrides_with = "no_wait")]
+    pub wait: bool,
+
+    /// Fail with exit code 5 instead of waiting for another DevHealth run
+    /// updating the same state
+    ///
+    /// Scheduled scans can use this to skip a run rather than queue behind
+    /// a long manual scan.
+    #[arg(long, global = true, overrides_with = "wait")]
+    pub no_wait: bool,
+}
+
+impl Cli {
+    /// Returns what to do when another run holds the state lock
+    pub fn lock_mode(&self) -> LockMode {
+        if self.no_wait {
+            LockMode::NoWait
+        } else {
+            LockMode::Wait
+        }
+    }
 }
 
 /// Available CLI commands
@@ -26,6 +76,10 @@ pub struct Cli {
 /// DevHealth supports two main operation modes:
 /// - `Check`: Quick health assessment of a directory
 /// - `Scan`: Comprehensive analysis with configurable scanning options
+///
+/// `Show` drills down into a single repository, `Tag` groups repositories for
+/// filtered scans, and `AdvisoryDb` manages the local vulnerability database
+/// used by `scan --audit`.
 #[derive(Subcommand)]
 pub enum Commands {
     /// Quick health check of a directory
@@ -39,6 +93,14 @@ pub enum Commands {
         /// working directory.
         #[arg(short, long, default_value = ".")]
         path: PathBuf,
+
+        /// Only include repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Output format, filtering and sorting options
+        #[command(flatten)]
+        report: ReportArgs,
     },
     /// Comprehensive scan with specific options
     ///
@@ -74,157 +136,2584 @@ pub enum Commands {
         /// Note: This feature is currently under development.
         #[arg(long)]
         system: bool,
+
+        /// Audit dependencies for known vulnerabilities
+        ///
+        /// Checks every discovered dependency against the local advisory
+        /// database, refreshing it from OSV first unless `--offline` is set.
+        #[arg(long)]
+        audit: bool,
+
+        /// Compare host and dev container toolchains
+        ///
+        /// Finds `.devcontainer/devcontainer.json` and Compose dev files
+        /// (`docker-compose.dev.yml`), and compares tool versions on the host
+        /// with those inside running containers via `docker exec`.
+        #[arg(long)]
+        containers: bool,
+
+        /// Report monorepo workspaces per internal package
+        ///
+        /// Detects Cargo, pnpm, npm/Yarn, Nx, Turborepo and `go.work`
+        /// workspaces, reports the health of each internal package, and
+        /// flags external dependencies pinned to different versions by
+        /// different packages.
+        #[arg(long)]
+        monorepo: bool,
+
+        /// Write the internal package dependency graph as Graphviz DOT
+        ///
+        /// Implies `--monorepo`. Render it with e.g.
+        /// `dot -Tsvg graph.dot -o graph.svg`.
+        #[arg(long, value_name = "FILE")]
+        graph: Option<PathBuf>,
+
+        /// Compare manifest versions and changelogs with the latest git tag
+        ///
+        /// Reads the version from `Cargo.toml`, `package.json` or
+        /// `pyproject.toml` and flags repositories whose version differs
+        /// from the latest tag, or that have commits since the tag without
+        /// a version bump.
+        /// Also flags root changelogs (`CHANGELOG.md`) with no entry for the
+        /// latest tag or left untouched for many commits.
+        #[arg(long)]
+        release: bool,
+
+        /// Audit the edition, MSRV and features of Rust packages
+        ///
+        /// Reports `edition` and `rust-version` of every Cargo package and
+        /// flags missing MSRVs, editions older than 2021, and MSRVs lower
+        /// than the locked dependencies require. Also lists features and
+        /// flags unused ones and heavy optional dependencies in `default`.
+        #[arg(long)]
+        cargo: bool,
+
+        /// Check Node version managers and pinned runtime versions
+        ///
+        /// Detects nvm, fnm, Volta and asdf, lists every `node` on `PATH`
+        /// and flags one shadowing another from a different source. Compares
+        /// the versions pinned in `.nvmrc`, `.node-version`, `.tool-versions`
+        /// and `package.json` (`volta.node`) with the versions active in each
+        /// project directory.
+        #[arg(long)]
+        runtimes: bool,
+
+        /// Look for recent commits lost by a reset, rebase or dropped stash
+        ///
+        /// Walks each repository's `HEAD` reflog and the dangling commits
+        /// `git fsck` finds, and reports commits from the last 30 days that
+        /// no branch, tag or remote-tracking ref reaches, with the command
+        /// that recovers them. Implies `--git` and is not enabled by `--all`,
+        /// since `git fsck` reads every object.
+        #[arg(long)]
+        lost_work: bool,
+
+        /// Measure each repository's working tree, `.git` and build artifacts
+        ///
+        /// Build artifacts are `node_modules`, `__pycache__`, `.venv` and the
+        /// `target`, `build` and `dist` directories of Cargo, Maven, Gradle,
+        /// CMake and npm projects. With `history = true` under `[scan]` in
+        /// `devhealth.toml`, sizes are recorded and compared with the
+        /// previous scan. Implies `--git` and is not enabled by `--all`,
+        /// since it reads the size of every file.
+        #[arg(long)]
+        sizes: bool,
+
+        /// Also lint, format-check and build every Rust and Node project
+        ///
+        /// Runs `cargo clippy`, `cargo fmt --check` and, with `tests = true`
+        /// under `[deep]` in `devhealth.toml`, `cargo test --no-run` in each
+        /// Cargo package or workspace. Node projects run their `lint` and
+        /// `typecheck` scripts, or ESLint, Prettier and `tsc` when configured.
+        /// Reports warnings, errors and a health score per project. Each tool is killed after
+        /// `[deep] timeout_secs` (300 by default). Adds to the scanners that
+        /// run and is not enabled by `--all`, since it builds every project.
+        #[arg(long)]
+        deep: bool,
+
+        /// Run every scanner
+        ///
+        /// Equivalent to `--git --deps --system --audit --containers
+        /// --monorepo --release --cargo --runtimes`.
+        /// Without any scanner flag, the scanners listed under `[scan]
+        /// default` in `devhealth.toml` run (git and deps unless configured).
+        #[arg(long)]
+        all: bool,
+
+        /// Pick the scanners from what the path holds
+        ///
+        /// Runs git for repositories, deps and audit for manifests, cargo
+        /// for Cargo packages (and `--deep` when every project is one),
+        /// monorepo for workspaces, runtimes for Node projects and
+        /// containers for dev container files, and skips the system scan
+        /// on network shares. Prints the flags it picked; scanner flags
+        /// given as well are added to them.
+        #[arg(long, conflicts_with = "all")]
+        auto: bool,
+
+        /// Only include repositories carrying this tag (repeatable)
+        ///
+        /// Tags come from the `[tags]` section of `devhealth.toml` and from
+        /// `devhealth tag add`. Applies to every enabled scanner.
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Output format, filtering and sorting options
+        #[command(flatten)]
+        report: ReportArgs,
+    },
+    /// Show everything known about a single repository
+    ///
+    /// Prints the repository's git state and health score, its dependencies
+    /// grouped by ecosystem, and any known vulnerabilities according to the
+    /// local advisory database. Never uses the network.
+    Show {
+        /// Path of the repository to inspect
+        repo: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Assign tags to repositories
+    ///
+    /// Tagged repositories can be selected with `--tag` on `check` and `scan`.
+    Tag {
+        /// The tag operation to run
+        #[command(subcommand)]
+        action: TagCommands,
+    },
+    /// Acknowledge a finding so reports leave it out
+    ///
+    /// Finding IDs name a kind of attention and what it applies to:
+    /// `dirty:<repo>`, `unpushed:<repo>`, `errors:<path>` or
+    /// `vulnerable:<advisory>`. Acknowledged findings resurface once the
+    /// `--until` date is reached.
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Ack {
+        /// Listing and removing acknowledgements
+        #[command(subcommand)]
+        action: Option<AckCommands>,
+
+        /// The finding to acknowledge, e.g. `vulnerable:GHSA-xxxx-xxxx-xxxx`
+        #[arg(required = true, value_name = "FINDING_ID")]
+        id: Option<String>,
+
+        /// Date the finding resurfaces on (YYYY-MM-DD); never if omitted
+        #[arg(long, value_name = "DATE")]
+        until: Option<String>,
+
+        /// Why the finding is accepted
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// List the rule catalog
+    ///
+    /// Shows every check with its stable rule ID (such as `GIT001` for
+    /// unpushed commits), its severity after `[severity.checks]` and a
+    /// description. Findings carry the same IDs in every output format.
+    Rules {
+        /// Only show the rule with this ID
+        #[arg(value_name = "RULE_ID")]
+        rule: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Manage the local vulnerability advisory database
+    ///
+    /// The database can be synced on a connected machine, exported to a
+    /// directory, and imported on an airgapped machine so `scan --audit`
+    /// keeps working without internet access.
+    AdvisoryDb {
+        /// The advisory database operation to run
+        #[command(subcommand)]
+        action: AdvisoryDbCommands,
+    },
+    /// Manage the organization policy
+    ///
+    /// With `[policy]` configured, `check` and `scan` apply the required
+    /// checks and thresholds published by the platform team. The policy is
+    /// refreshed automatically when stale; `sync` forces a refresh.
+    Policy {
+        /// The policy operation to run
+        #[command(subcommand)]
+        action: PolicyCommands,
+    },
+    /// Combine JSON reports from several machines into a team report
+    ///
+    /// Each file is a `scan --format json` report, labeled with its file name
+    /// without extension. Shows every repository's copies across machines,
+    /// which machines are exposed to each advisory, and the versions of each
+    /// dependency in use across the team. Arguments containing `*` are
+    /// expanded even where the shell does not.
+    Merge {
+        /// JSON reports to combine
+        #[arg(required = true, value_name = "FILE")]
+        files: Vec<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// How much of each section to print in text output
+        #[arg(long, value_enum, default_value_t = Detail::Normal)]
+        detail: Detail,
+    },
+    /// Bundle results, configuration and tool versions into a snapshot
+    ///
+    /// Writes a `tar` archive with the results of a quick check (or of a
+    /// saved JSON report), the configuration with tokens redacted, and the
+    /// versions of DevHealth and common toolchain commands, ready to attach
+    /// to a bug report. The extension selects the compression: `.tar.zst`,
+    /// `.tar.gz` or `.tar`.
+    Export {
+        /// Snapshot archive to write
+        #[arg(long, value_name = "FILE")]
+        out: PathBuf,
+
+        /// Path to check (defaults to current directory)
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Bundle a saved `--format json` report instead of running a check
+        #[arg(long, value_name = "FILE")]
+        results: Option<PathBuf>,
+    },
+    /// Write a diagnostic bundle of the last crash for a bug report
+    ///
+    /// Bundles the latest crash recorded in the data directory (arguments,
+    /// error or panic with backtrace, partial results) with the redacted
+    /// configuration and the versions of common toolchain commands. Without
+    /// a recorded crash, the bundle describes the current environment.
+    DebugBundle {
+        /// File to write (defaults to the crash directory)
+        #[arg(long, value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
+    /// View a snapshot written by `export`, or compare two
+    Import {
+        /// Snapshot archive to read
+        snapshot: PathBuf,
+
+        /// Compare with this newer snapshot instead of printing it
+        #[arg(long, value_name = "SNAPSHOT")]
+        diff: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// How much of the snapshot to print in text output
+        #[arg(long, value_enum, default_value_t = Detail::Normal)]
+        detail: Detail,
+    },
+    /// Set up DevHealth: without an editor, write devhealth.toml interactively
+    ///
+    /// Asks for the code roots, the scanners `scan` runs and the failure
+    /// thresholds, suggesting defaults found on this machine, then writes a
+    /// commented devhealth.toml. Optionally installs a daily cron entry
+    /// watching for new advisories and `pre-push` hooks running `check`.
+    #[command(args_conflicts_with_subcommands = true)]
+    Init {
+        /// The editor to set up
+        #[command(subcommand)]
+        action: Option<InitCommands>,
+
+        /// Accept every suggested answer without asking
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Replace an existing devhealth.toml
+        #[arg(long)]
+        force: bool,
+    },
+    /// Dependency maintenance
+    Deps {
+        /// The dependency task to run
+        #[command(subcommand)]
+        action: DepsCommands,
+    },
+    /// Inventory of dependency licenses across projects
+    ///
+    /// Lists the license of every dependency of every project under the
+    /// directory, grouped by license with the packages and the projects
+    /// using them. Licenses are read from the packages Cargo, npm, pip and
+    /// Go already downloaded; packages that were never downloaded are listed
+    /// as unknown.
+    Licenses {
+        /// Directory to search for projects
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include projects in repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = LicenseFormat::Text)]
+        format: LicenseFormat,
+    },
+    /// Alert when a new advisory affects a previously scanned dependency
+    ///
+    /// Audits the dependencies stored by earlier `scan --deps` and
+    /// `scan --audit` runs, without scanning the filesystem, and reports
+    /// advisories published since the previous check, also as a desktop
+    /// notification. The first check only records the advisories already
+    /// known.
+    WatchAdvisories {
+        /// Keep checking every this many minutes instead of checking once
+        #[arg(long, value_name = "MINUTES", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: Option<u64>,
+
+        /// Do not send desktop notifications
+        #[arg(long)]
+        no_notify: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Print a short status of the current repository for shell prompts
+    ///
+    /// Prints `✎` for uncommitted changes, `↑N`/`↓N` for commits ahead of
+    /// or behind the upstream, and `🛡N` for vulnerable dependencies found by
+    /// the last audit, or nothing if all is well. Reads a cache refreshed in
+    /// the background, so it returns fast enough to run before every prompt.
+    Prompt {
+        /// Directory inside the repository (defaults to current directory)
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Refresh the cached status in the background once it is older
+        /// than this many seconds
+        #[arg(long, value_name = "SECONDS", default_value_t = prompt::DEFAULT_MAX_AGE)]
+        max_age: u64,
+
+        /// Read the repository status and update the cache without printing
+        #[arg(long, hide = true)]
+        refresh: bool,
+    },
+    /// Download and install the latest release
+    ///
+    /// Downloads the release binary for this platform, verifies its checksum
+    /// and, when a release key is known, the checksums' signature, then
+    /// replaces the running executable. With --check, only reports whether a
+    /// newer release exists.
+    SelfUpdate {
+        /// Only check for a newer release, without installing it
+        #[arg(long)]
+        check: bool,
+    },
+    /// Apply fixes for problems found by `check` and `scan`
+    Fix {
+        /// The fix to apply
+        #[command(subcommand)]
+        action: FixCommands,
+    },
+    /// Remove build output nothing was written to for months
+    ///
+    /// Deletes the `target`, `build` and `dist` directories of Cargo, Maven,
+    /// Gradle, CMake and npm projects in git repositories that hold at least
+    /// 1 GiB and were not written to for 90 days, as reported by
+    /// `scan --sizes`. A build recreates them when needed. Directories git
+    /// does not ignore or that hold tracked files are left alone, and the
+    /// removal is confirmed on the terminal unless `--yes` is given.
+    Clean {
+        /// Directory to search for repositories
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// List what would be removed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Remove without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Anonymous aggregate statistics to share with a platform team
+    Stats {
+        /// The statistics operation to run
+        #[command(subcommand)]
+        action: StatsCommands,
+    },
+    /// Email digest sent over SMTP as configured under `[digest]`
+    Digest {
+        /// The digest operation to run
+        #[command(subcommand)]
+        action: DigestCommands,
+    },
+    /// Store forge tokens and the SMTP password in the system keyring
+    ///
+    /// Network integrations look credentials up in the keyring first, then
+    /// in the environment, then in `devhealth.toml`.
+    Auth {
+        /// The credential operation to run
+        #[command(subcommand)]
+        action: AuthCommands,
+    },
+    /// Compare the repositories on GitHub and GitLab with the local clones
+    Repos {
+        /// The repository operation to run
+        #[command(subcommand)]
+        action: ReposCommands,
+    },
+    /// Validate `devhealth.toml` and show the configuration in effect
+    Config {
+        /// The configuration operation to run
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+    /// Manage the scan history kept with `history = true` under `[scan]`
+    History {
+        /// The history operation to run
+        #[command(subcommand)]
+        action: HistoryCommands,
+    },
+    /// Measure how fast the development environment is
+    ///
+    /// Benchmarks only run through this command. Each run is recorded in a
+    /// history in the DevHealth data directory, so runs made at different
+    /// places and times can be compared.
+    Bench {
+        /// The benchmark to run
+        #[command(subcommand)]
+        action: BenchCommands,
     },
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use clap::Parser;
+/// Options controlling how `check` and `scan` present their results
+///
+/// Filtering, sorting and limiting apply to every section of the report and
+/// to every output format alike.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ReportArgs {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
 
-    mod check_command {
-        use super::*;
+    /// Only show entries needing this kind of attention (repeatable)
+    ///
+    /// Entries matching any of the given kinds are kept.
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "KIND")]
+    pub only: Vec<Attention>,
 
-        #[test]
-        fn parses_with_default_path() {
-            let cli = Cli::parse_from(["devhealth", "check"]);
+    /// Order entries within each section
+    #[arg(long, value_enum, value_name = "KEY")]
+    pub sort: Option<SortKey>,
 
-            match cli.command {
-                Commands::Check { path } => {
-                    assert_eq!(
-                        path.to_str().unwrap(),
-                        ".",
-                        "Default path should be current directory"
-                    );
-                }
-                _ => panic!("Expected Check command"),
-            }
-        }
+    /// Show at most N entries per section
+    #[arg(long, value_name = "N")]
+    pub limit: Option<usize>,
 
-        #[test]
-        fn parses_with_custom_path() {
-            let test_path = "/custom/test/path";
-            let cli = Cli::parse_from(["devhealth", "check", "--path", test_path]);
+    /// Show only the N worst entries of each section
+    ///
+    /// Keeps entries needing any kind of attention, lowest health score
+    /// first: a short list of what to look at today.
+    #[arg(long, value_name = "N", conflicts_with_all = ["sort", "limit"])]
+    pub top: Option<usize>,
 
-            match cli.command {
-                Commands::Check { path } => {
-                    assert_eq!(
-                        path.to_str().unwrap(),
-                        test_path,
-                        "Should use provided path"
-                    );
-                }
-                _ => panic!("Expected Check command"),
-            }
-        }
+    /// Exit with status 1 if any entry needs this kind of attention (repeatable)
+    ///
+    /// Checked against the full results, before `--only` and `--limit`.
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "KIND")]
+    pub fail_on: Vec<Attention>,
 
-        #[test]
-        fn supports_short_path_flag() {
-            let test_path = "/short/flag/path";
-            let cli = Cli::parse_from(["devhealth", "check", "-p", test_path]);
+    /// Exit with status 1 if any finding is at least this severe
+    ///
+    /// Defaults to `fail_level` in the `[severity]` section of the config
+    /// file. Combines with `--fail-on`: either one failing fails the run.
+    #[arg(long, value_enum, value_name = "SEVERITY")]
+    pub fail_level: Option<Severity>,
 
-            match cli.command {
-                Commands::Check { path } => {
-                    assert_eq!(path.to_str().unwrap(), test_path, "Short flag should work");
-                }
-                _ => panic!("Expected Check command"),
-            }
-        }
-    }
+    /// Only print the summary boxes of each section
+    #[arg(long, conflicts_with = "detail")]
+    pub summary: bool,
 
-    mod scan_command {
-        use super::*;
+    /// Print one line per repository and project with health badges
+    ///
+    /// Projects are nested under the repository that holds them, and the
+    /// findings summary follows.
+    #[arg(long, conflicts_with_all = ["summary", "detail"])]
+    pub overview: bool,
 
-        #[test]
-        fn parses_with_default_values() {
-            let cli = Cli::parse_from(["devhealth", "scan"]);
+    /// How to lay out repositories in text output
+    ///
+    /// `table` prints one row per repository with its branch, status,
+    /// ahead/behind, last commit, size and score, fitted to the terminal.
+    #[arg(long, value_enum, default_value_t = View::Tree, conflicts_with = "overview")]
+    pub view: View,
 
-            match cli.command {
-                Commands::Scan {
-                    path,
-                    git,
-                    deps,
-                    system,
-                } => {
-                    assert_eq!(
-                        path.to_str().unwrap(),
-                        ".",
-                        "Default path should be current directory"
-                    );
-                    assert!(!git, "Git flag should default to false");
-                    assert!(!deps, "Deps flag should default to false");
-                    assert!(!system, "System flag should default to false");
-                }
-                _ => panic!("Expected Scan command"),
-            }
-        }
+    /// Print one stable, tab-separated line per repository and finding
+    ///
+    /// Meant for scripts and shell prompts: the format does not change
+    /// between minor versions. Status messages go to stderr.
+    #[arg(long, conflicts_with_all = ["format", "overview", "view", "summary", "detail"])]
+    pub porcelain: bool,
 
-        #[test]
-        fn parses_all_flags_correctly() {
-            let test_path = "/test/scan/path";
-            let cli = Cli::parse_from([
-                "devhealth",
-                "scan",
-                "--git",
-                "--deps",
-                "--system",
-                "--path",
-                test_path,
-            ]);
+    /// How much of each section to print in text output
+    ///
+    /// `minimal` lists one line per entry, `normal` caps the dependencies
+    /// listed per ecosystem, and `full` lists everything.
+    #[arg(long, value_enum, default_value_t = Detail::Normal)]
+    pub detail: Detail,
 
-            match cli.command {
-                Commands::Scan {
-                    path,
-                    git,
-                    deps,
-                    system,
-                } => {
-                    assert_eq!(
-                        path.to_str().unwrap(),
-                        test_path,
-                        "Should use provided path"
-                    );
-                    assert!(git, "Git flag should be true");
-                    assert!(deps, "Deps flag should be true");
-                    assert!(system, "System flag should be true");
-                }
-                _ => panic!("Expected Scan command"),
+    /// Raise a desktop notification when the run finds new critical issues
+    ///
+    /// New means missing from the previous run over the same paths and
+    /// scanners. `level` under `[notify]` lowers the threshold.
+    #[arg(long)]
+    pub notify_desktop: bool,
+}
+
+impl Commands {
+    /// Returns `true` if the command updates state in the data directory
+    /// and must hold the state lock while it runs
+    ///
+    /// Listing commands only read, `prompt` must never block a shell
+    /// prompt, and `watch-advisories` takes the lock for each check so a
+    /// long-running watch does not hold it while sleeping.
+    pub fn writes_state(&self) -> bool {
+        match self {
+            Commands::Check { .. } | Commands::Scan { .. } | Commands::Ack { action: None, .. } => {
+                true
+            }
+            Commands::Tag { action } => !matches!(action, TagCommands::List),
+            Commands::Ack {
+                action: Some(action),
+                ..
+            } => !matches!(action, AckCommands::List),
+            Commands::AdvisoryDb { action } => {
+                !matches!(action, AdvisoryDbCommands::Sync { to: Some(_), .. })
             }
+            Commands::Policy { action } => matches!(action, PolicyCommands::Sync),
+            Commands::Deps { action } => {
+                matches!(action, DepsCommands::Exposure { audit: true, .. })
+            }
+            Commands::History {
+                action: HistoryCommands::Prune { dry_run, .. },
+            } => !dry_run,
+            Commands::History { .. } => false,
+            Commands::Digest {
+                action:
+                    DigestCommands::Send {
+                        results, dry_run, ..
+                    },
+            } => results.is_none() && !dry_run,
+            Commands::Bench { action } => !matches!(
+                action,
+                BenchCommands::Net { history: true, .. } | BenchCommands::Fs { history: true, .. }
+            ),
+            _ => false,
         }
+    }
+}
 
-        #[test]
-        fn parses_individual_flags() {
-            // Test each flag individually
-            let test_cases = [
-                (vec!["devhealth", "scan", "--git"], (true, false, false)),
-                (vec!["devhealth", "scan", "--deps"], (false, true, false)),
-                (vec!["devhealth", "scan", "--system"], (false, false, true)),
-            ];
+impl ReportArgs {
+    /// Returns the filtering and sorting options selected on the command line
+    pub fn options(&self) -> ReportOptions {
+        ReportOptions {
+            only: self.only.clone(),
+            sort: self.sort,
+            limit: self.limit,
+            top: self.top,
+        }
+    }
 
-            for (args, (expected_git, expected_deps, expected_system)) in test_cases {
+    /// Returns the format status messages are printed for
+    ///
+    /// Porcelain output keeps stdout to itself, so its messages go to stderr
+    /// like those of JSON output.
+    pub fn message_format(&self) -> OutputFormat {
+        if self.porcelain {
+            OutputFormat::Json
+        } else {
+            self.format
+        }
+    }
+
+    /// Returns the level of detail for text output, honoring `--summary`
+    pub fn detail(&self) -> Detail {
+        if self.summary {
+            Detail::Summary
+        } else {
+            self.detail
+        }
+    }
+}
+
+/// Repository tag operations
+#[derive(Subcommand)]
+pub enum TagCommands {
+    /// Add tags to a repository
+    Add {
+        /// Path of the repository to tag
+        path: PathBuf,
+
+        /// Tags to add
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Remove tags from a repository
+    Remove {
+        /// Path of the repository to untag
+        path: PathBuf,
+
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// List tagged repositories and configured tag patterns
+    List,
+}
+
+/// Acknowledgement operations
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum AckCommands {
+    /// List acknowledged findings, including expired ones
+    List,
+    /// Remove the acknowledgement of a finding so it is reported again
+    Remove {
+        /// The acknowledged finding ID, as shown by `ack list`
+        id: String,
+    },
+}
+
+/// Advisory database operations
+#[derive(Subcommand)]
+pub enum AdvisoryDbCommands {
+    /// Update the advisory database
+    ///
+    /// Without options, fetches advisories from OSV for every dependency
+    /// found under `--path` into the local database. Use `--to` on a
+    /// connected machine to write the database to a directory instead, and
+    /// `--from` on an airgapped machine to import it.
+    Sync {
+        /// Projects whose dependencies to fetch advisories for
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Write the fetched database to this directory
+        #[arg(long, conflicts_with = "from")]
+        to: Option<PathBuf>,
+
+        /// Import a database previously exported with `--to`
+        #[arg(long)]
+        from: Option<PathBuf>,
+    },
+}
+
+/// Organization policy operations
+#[derive(Subcommand)]
+pub enum PolicyCommands {
+    /// Download and verify the policy now
+    Sync,
+    /// Show the cached policy and when it was last synced
+    Show,
+}
+
+/// Editor integrations `init` can set up
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum InitCommands {
+    /// Add DevHealth tasks and recommended settings to `.vscode`
+    ///
+    /// Writes `tasks.json` with `devhealth: check` and `devhealth: scan`
+    /// tasks whose findings appear in the Problems panel, plus recommended
+    /// settings and extensions. Existing files are merged: entries already
+    /// present are kept, and files with comments are left untouched.
+    Vscode {
+        /// Workspace folder to set up (defaults to current directory)
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+    },
+}
+
+/// Statistics operations
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum StatsCommands {
+    /// Write aggregate statistics without repository names or paths
+    ///
+    /// Runs a quick check (or reads a saved `--format json` report) and
+    /// writes only counts: repositories by state and health score,
+    /// manifests per ecosystem, dependencies and findings per rule and
+    /// severity. Nothing is sent anywhere.
+    Export {
+        /// File to write the statistics to (defaults to stdout)
+        #[arg(long, value_name = "FILE")]
+        out: Option<PathBuf>,
+
+        /// Path to check (defaults to current directory)
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Aggregate a saved `--format json` report instead of running a check
+        #[arg(long, value_name = "FILE")]
+        results: Option<PathBuf>,
+    },
+}
+
+/// Email digest operations
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum DigestCommands {
+    /// Run a quick check and email the digest to the configured recipients
+    ///
+    /// The digest summarizes the check, lists what changed since the
+    /// previous run and the repositories that need attention, as HTML with
+    /// a Markdown alternative.
+    Send {
+        /// Path to check (defaults to `path` under `[digest]`, then the
+        /// current directory)
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+
+        /// Only include repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Send a saved `--format json` report instead of running a check
+        #[arg(long, value_name = "FILE")]
+        results: Option<PathBuf>,
+
+        /// Print the Markdown digest instead of sending it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Add a crontab entry sending the digest on the `[digest]` schedule
+    Schedule {
+        /// Path the scheduled digest checks (defaults to `path` under
+        /// `[digest]`, then the current directory)
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+    },
+}
+
+/// Credential operations
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum AuthCommands {
+    /// Store a token or password in the system keyring
+    ///
+    /// The secret is read from standard input, without echo when it is a
+    /// terminal, and replaces any earlier one.
+    Login {
+        /// Service the secret is for
+        #[arg(value_enum)]
+        provider: Provider,
+    },
+    /// Remove a stored token or password from the system keyring
+    Logout {
+        /// Service the secret is for
+        #[arg(value_enum)]
+        provider: Provider,
+    },
+}
+
+/// Forge repository operations
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum ReposCommands {
+    /// List forge repositories not cloned under the given paths
+    ///
+    /// Lists the repositories the GitHub and GitLab tokens give access to
+    /// (owned, collaborated on or shared with an organization or group)
+    /// that no git repository under the paths has as a remote. Any remote
+    /// counts, not only `origin`. Archived repositories are left out
+    /// unless `--archived` is given.
+    Missing {
+        /// Directory to search for local clones (repeatable)
+        #[arg(short, long = "path", value_name = "PATH", default_value = ".")]
+        paths: Vec<PathBuf>,
+
+        /// Only list repositories of this user, organization or GitLab
+        /// group (repeatable)
+        #[arg(long = "owner", value_name = "OWNER")]
+        owners: Vec<String>,
+
+        /// Also list archived repositories
+        #[arg(long)]
+        archived: bool,
+
+        /// Clone this missing repository, given as `owner/repo` (repeatable)
+        #[arg(long = "clone", value_name = "REPO")]
+        clone: Vec<String>,
+
+        /// Directory to clone into (defaults to the first `--path`)
+        #[arg(long, value_name = "DIR", requires = "clone")]
+        into: Option<PathBuf>,
+
+        /// Clone over SSH instead of HTTPS
+        #[arg(long, requires = "clone")]
+        ssh: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+}
+
+/// Configuration operations
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum ConfigCommands {
+    /// Report unknown keys, invalid path patterns and out-of-range values
+    ///
+    /// Lists every problem with its key and exits with code 2 if there is
+    /// any. Checks the DevHealth config file unless `--file` is given.
+    Check {
+        /// Config file to check instead of the DevHealth one
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Print the configuration in effect as TOML
+    ///
+    /// Defaults overlaid with `devhealth.toml`, then the environment and
+    /// the global flags, noting where each override came from. Tokens are
+    /// redacted.
+    Show,
+}
+
+/// Which stored runs and findings `history list` and `history show` cover
+#[derive(Args, Debug, Clone, PartialEq)]
+pub struct HistoryFilterArgs {
+    /// Only runs on or after this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<String>,
+
+    /// Only findings in this repository, by name (`api`, `org/api`) or absolute path
+    #[arg(long, value_name = "REPO")]
+    pub repo: Option<String>,
+
+    /// Only findings of this rule, e.g. `DEP014`
+    #[arg(long, value_name = "RULE_ID")]
+    pub finding: Option<String>,
+}
+
+/// Scan history operations
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum HistoryCommands {
+    /// List stored runs with their findings counts
+    ///
+    /// With `--repo` or `--finding`, lists the matching findings of each
+    /// run, and only runs of the paths and scanners that ever had one.
+    List {
+        #[command(flatten)]
+        filter: HistoryFilterArgs,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Show when each finding appeared and when it was fixed
+    ///
+    /// Follows every finding through the stored runs of the same paths and
+    /// scanners: the first run that had it, the first later run without it,
+    /// and any reappearance after that.
+    Show {
+        #[command(flatten)]
+        filter: HistoryFilterArgs,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Drop old runs from the scan history and old repository sizes
+    ///
+    /// Applies `history_keep_runs` and `history_keep_days` from `[scan]`,
+    /// which scans also apply whenever they record a run. The options
+    /// override them, e.g. to shrink the history once.
+    Prune {
+        /// Runs to keep per combination of paths and scanners (0 keeps every run)
+        #[arg(long, value_name = "RUNS")]
+        keep: Option<usize>,
+
+        /// Drop runs and sizes recorded more than this many days ago (0 keeps them)
+        #[arg(long, value_name = "DAYS")]
+        keep_days: Option<u64>,
+
+        /// Only report what would be dropped
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Benchmarks run by `bench`
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum BenchCommands {
+    /// Time DNS resolution, connection and TLS handshake to developer endpoints
+    ///
+    /// Measures crates.io, npm, PyPI, GitHub, the GitLab hosts from
+    /// `[forge]` and the endpoints from `[bench]`, and compares each with
+    /// earlier runs carrying the same label.
+    Net {
+        /// Endpoint to measure instead (repeatable): a host, host:port or https:// URL
+        #[arg(long = "endpoint", value_name = "HOST")]
+        endpoints: Vec<String>,
+
+        /// Measurements per endpoint; the median is reported
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..=100))]
+        rounds: u32,
+
+        /// Label the run with where it was made, e.g. `office` or `home`
+        #[arg(long)]
+        label: Option<String>,
+
+        /// Show the recorded runs instead of running the benchmark
+        #[arg(long)]
+        history: bool,
+
+        /// Do not record the run in the history
+        #[arg(long)]
+        no_save: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Measure small-file create, stat and read throughput of a directory
+    ///
+    /// Writes, stats and reads many 1 KiB files in a scratch directory that
+    /// is removed afterwards, and flags network drives, Windows drives under
+    /// WSL and local disks slowed down by antivirus scanning or indexing.
+    Fs {
+        /// Directory to benchmark (defaults to current directory)
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Number of files to create, stat and read
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(10..=100_000))]
+        files: u32,
+
+        /// Label the run with where it was made, e.g. `office` or `home`
+        #[arg(long)]
+        label: Option<String>,
+
+        /// Show the recorded runs instead of running the benchmark
+        #[arg(long)]
+        history: bool,
+
+        /// Do not record the run in the history
+        #[arg(long)]
+        no_save: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+}
+
+/// Dependency tasks run by `deps`
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum DepsCommands {
+    /// Suggest exact pins for loose version constraints
+    ///
+    /// Lists every `Cargo.toml` and `package.json` constraint without an
+    /// upper bound or with only a major version (`*`, `>=1`, `^1`, `latest`)
+    /// with the version its lockfile resolved it to. With `--patch`, prints
+    /// a unified diff pinning them instead, which `git apply` applies from
+    /// the scanned directory.
+    PinSuggestions {
+        /// Directory to search for projects
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include projects in repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Print a patch applying the pins instead of the list
+        #[arg(long, conflicts_with = "format")]
+        patch: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Show which packages and versions the projects share
+    ///
+    /// Lists the packages the most projects depend on, with the versions in
+    /// use (from the lockfile where there is one) and the projects using
+    /// each. With `--audit`, also lists the vulnerable packages, the one
+    /// affecting the most projects first.
+    Exposure {
+        /// Directory to search for projects
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include projects in repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Audit the dependencies for known vulnerabilities
+        #[arg(long)]
+        audit: bool,
+
+        /// Number of packages to list in text output, 0 for all
+        #[arg(long, default_value_t = crate::scanner::exposure::DEFAULT_TOP)]
+        top: usize,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// List every project using a package
+    ///
+    /// Shows each dependency on the package with its declared version, the
+    /// version its lockfile resolved, its type and the manifest declaring
+    /// it. The name matches case-insensitively and may be part of the
+    /// package name (`log4j` finds `log4j-core`). With `--stored`, searches
+    /// the dependencies recorded by previous `scan --deps` runs instead of
+    /// scanning.
+    Find {
+        /// Package name, or part of it
+        package: String,
+
+        /// Directory to search for projects
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include projects in repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Search the dependencies recorded by previous scans instead of scanning
+        #[arg(long, conflicts_with = "path")]
+        stored: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Show which constraints block upgrading a package
+    ///
+    /// Checks every constraint on the package against the new version: the
+    /// ones the projects declare, and the ones of other packages in their
+    /// `Cargo.lock` and `package-lock.json`. Lists the blocking constraints
+    /// by project, and the ones that could not be checked. Exits with 1 if
+    /// any constraint blocks the upgrade.
+    UpgradeImpact {
+        /// Package to upgrade
+        package: String,
+
+        /// Version to upgrade to
+        version: String,
+
+        /// Directory to search for projects
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include projects in repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Suggest versions for the projects to converge on
+    ///
+    /// Lists the packages more than one project uses at versions far apart,
+    /// with the version each project uses and its constraint, and suggests
+    /// the version in use that the most constraints admit. By default only
+    /// major differences are listed.
+    Align {
+        /// Directory to search for projects
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include projects in repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Smallest difference between versions to list
+        #[arg(long, value_enum, default_value_t = crate::scanner::alignment::Spread::Major)]
+        min_spread: crate::scanner::alignment::Spread,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+}
+
+/// Fixes that change repositories
+#[derive(Subcommand)]
+pub enum FixCommands {
+    /// Bring forks up to date with their upstream default branch
+    ///
+    /// Forks with an `upstream` remote are fetched and their local default
+    /// branch is fast-forwarded; branches with commits of their own are left
+    /// alone. GitHub forks without an `upstream` remote are synced on GitHub
+    /// when a GitHub token is configured.
+    SyncForks {
+        /// Directory to search for forks
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Only include repositories carrying this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Show which forks are behind without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Push fast-forwarded branches to `origin`
+        #[arg(long)]
+        push: bool,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    mod check_command {
+        use super::*;
+
+        #[test]
+        fn parses_with_default_path() {
+            let cli = Cli::parse_from(["devhealth", "check"]);
+
+            match cli.command {
+                Commands::Check { path, .. } => {
+                    assert_eq!(
+                        path.to_str().unwrap(),
+                        ".",
+                        "Default path should be current directory"
+                    );
+                }
+                _ => panic!("Expected Check command"),
+            }
+        }
+
+        #[test]
+        fn parses_with_custom_path() {
+            let test_path = "/custom/test/path";
+            let cli = Cli::parse_from(["devhealth", "check", "--path", test_path]);
+
+            match cli.command {
+                Commands::Check { path, .. } => {
+                    assert_eq!(
+                        path.to_str().unwrap(),
+                        test_path,
+                        "Should use provided path"
+                    );
+                }
+                _ => panic!("Expected Check command"),
+            }
+        }
+
+        #[test]
+        fn supports_short_path_flag() {
+            let test_path = "/short/flag/path";
+            let cli = Cli::parse_from(["devhealth", "check", "-p", test_path]);
+
+            match cli.command {
+                Commands::Check { path, .. } => {
+                    assert_eq!(path.to_str().unwrap(), test_path, "Short flag should work");
+                }
+                _ => panic!("Expected Check command"),
+            }
+        }
+    }
+
+    mod scan_command {
+        use super::*;
+
+        #[test]
+        fn parses_with_default_values() {
+            let cli = Cli::parse_from(["devhealth", "scan"]);
+
+            match cli.command {
+                Commands::Scan {
+                    path,
+                    git,
+                    deps,
+                    system,
+                    audit,
+                    containers,
+                    monorepo,
+                    graph,
+                    release,
+                    cargo,
+                    runtimes,
+                    lost_work,
+                    sizes,
+                    deep,
+                    all,
+                    auto,
+                    tags,
+                    report,
+                } => {
+                    assert_eq!(
+                        path.to_str().unwrap(),
+                        ".",
+                        "Default path should be current directory"
+                    );
+                    assert!(!git, "Git flag should default to false");
+                    assert!(!deps, "Deps flag should default to false");
+                    assert!(!system, "System flag should default to false");
+                    assert!(!audit, "Audit flag should default to false");
+                    assert!(!containers, "Containers flag should default to false");
+                    assert!(!monorepo, "Monorepo flag should default to false");
+                    assert!(graph.is_none(), "No graph export by default");
+                    assert!(!release, "Release flag should default to false");
+                    assert!(!cargo, "Cargo flag should default to false");
+                    assert!(!runtimes, "Runtimes flag should default to false");
+                    assert!(!lost_work, "Lost work flag should default to false");
+                    assert!(!sizes, "Sizes flag should default to false");
+                    assert!(!deep, "Deep flag should default to false");
+                    assert!(!all, "All flag should default to false");
+                    assert!(!auto, "Auto flag should default to false");
+                    assert!(tags.is_empty(), "No tag filter by default");
+                    assert_eq!(report.format, OutputFormat::Text);
+                    assert!(
+                        !report.options().is_active(),
+                        "No report shaping by default"
+                    );
+                    assert_eq!(report.detail(), Detail::Normal);
+                }
+                _ => panic!("Expected Scan command"),
+            }
+        }
+
+        #[test]
+        fn parses_all_flags_correctly() {
+            let test_path = "/test/scan/path";
+            let cli = Cli::parse_from([
+                "devhealth",
+                "scan",
+                "--git",
+                "--deps",
+                "--system",
+                "--path",
+                test_path,
+            ]);
+
+            match cli.command {
+                Commands::Scan {
+                    path,
+                    git,
+                    deps,
+                    system,
+                    ..
+                } => {
+                    assert_eq!(
+                        path.to_str().unwrap(),
+                        test_path,
+                        "Should use provided path"
+                    );
+                    assert!(git, "Git flag should be true");
+                    assert!(deps, "Deps flag should be true");
+                    assert!(system, "System flag should be true");
+                }
+                _ => panic!("Expected Scan command"),
+            }
+        }
+
+        #[test]
+        fn parses_all_scanners_flag() {
+            let cli = Cli::parse_from(["devhealth", "scan", "--all"]);
+
+            match cli.command {
+                Commands::Scan { all, git, .. } => {
+                    assert!(all, "All flag should be true");
+                    assert!(!git, "--all does not set the individual flags");
+                }
+                _ => panic!("Expected Scan command"),
+            }
+        }
+
+        #[test]
+        fn parses_auto_flag() {
+            let cli = Cli::parse_from(["devhealth", "scan", "--auto", "--release"]);
+
+            match cli.command {
+                Commands::Scan { auto, release, .. } => {
+                    assert!(auto, "Auto flag should be true");
+                    assert!(release, "Scanner flags combine with --auto");
+                }
+                _ => panic!("Expected Scan command"),
+            }
+            assert!(Cli::try_parse_from(["devhealth", "scan", "--auto", "--all"]).is_err());
+        }
+
+        #[test]
+        fn parses_individual_flags() {
+            // Test each flag individually
+            let test_cases = [
+                (vec!["devhealth", "scan", "--git"], (true, false, false)),
+                (vec!["devhealth", "scan", "--deps"], (false, true, false)),
+                (vec!["devhealth", "scan", "--system"], (false, false, true)),
+            ];
+
+            for (args, (expected_git, expected_deps, expected_system)) in test_cases {
                 let args_clone = args.clone(); // Clone for error messages
                 let cli = Cli::parse_from(args);
 
-                match cli.command {
-                    Commands::Scan {
-                        git, deps, system, ..
+                match cli.command {
+                    Commands::Scan {
+                        git, deps, system, ..
+                    } => {
+                        assert_eq!(git, expected_git, "Git flag mismatch for {:?}", args_clone);
+                        assert_eq!(
+                            deps, expected_deps,
+                            "Deps flag mismatch for {:?}",
+                            args_clone
+                        );
+                        assert_eq!(
+                            system, expected_system,
+                            "System flag mismatch for {:?}",
+                            args_clone
+                        );
+                    }
+                    _ => panic!("Expected Scan command for args {:?}", args_clone),
+                }
+            }
+        }
+    }
+
+    mod show_command {
+        use super::*;
+
+        #[test]
+        fn parses_repository_and_format() {
+            let cli = Cli::parse_from(["devhealth", "show", "/src/api", "--format", "json"]);
+
+            match cli.command {
+                Commands::Show { repo, format } => {
+                    assert_eq!(repo, PathBuf::from("/src/api"));
+                    assert_eq!(format, OutputFormat::Json);
+                }
+                _ => panic!("Expected Show command"),
+            }
+        }
+
+        #[test]
+        fn requires_a_repository() {
+            assert!(Cli::try_parse_from(["devhealth", "show"]).is_err());
+        }
+    }
+
+    mod tag_filtering {
+        use super::*;
+
+        #[test]
+        fn accepts_repeated_tag_flags() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "scan",
+                "--git",
+                "--tag",
+                "work",
+                "--tag",
+                "oss",
+            ]);
+
+            match cli.command {
+                Commands::Scan { tags, .. } => assert_eq!(tags, vec!["work", "oss"]),
+                _ => panic!("Expected Scan command"),
+            }
+        }
+
+        #[test]
+        fn parses_tag_add_command() {
+            let cli = Cli::parse_from(["devhealth", "tag", "add", "/src/api", "work", "backend"]);
+
+            match cli.command {
+                Commands::Tag {
+                    action: TagCommands::Add { path, tags },
+                } => {
+                    assert_eq!(path, PathBuf::from("/src/api"));
+                    assert_eq!(tags, vec!["work", "backend"]);
+                }
+                _ => panic!("Expected Tag add command"),
+            }
+        }
+
+        #[test]
+        fn tag_add_requires_at_least_one_tag() {
+            assert!(Cli::try_parse_from(["devhealth", "tag", "add", "/src/api"]).is_err());
+        }
+    }
+
+    mod lang_flag {
+        use super::*;
+
+        #[test]
+        fn parses_supported_languages() {
+            assert_eq!(Cli::parse_from(["devhealth", "check"]).lang, None);
+            assert_eq!(
+                Cli::parse_from(["devhealth", "check", "--lang", "de"]).lang,
+                Some(Lang::De)
+            );
+            assert_eq!(
+                Cli::parse_from(["devhealth", "--lang", "es", "scan"]).lang,
+                Some(Lang::Es)
+            );
+        }
+
+        #[test]
+        fn rejects_unsupported_languages() {
+            assert!(Cli::try_parse_from(["devhealth", "check", "--lang", "fr"]).is_err());
+        }
+    }
+
+    mod accessible_flag {
+        use super::*;
+
+        #[test]
+        fn is_off_by_default_and_global() {
+            assert!(!Cli::parse_from(["devhealth", "check"]).accessible);
+            assert!(Cli::parse_from(["devhealth", "scan", "--git", "--accessible"]).accessible);
+            assert!(Cli::parse_from(["devhealth", "--accessible", "tag", "list"]).accessible);
+        }
+    }
+
+    mod report_options {
+        use super::*;
+
+        #[test]
+        fn parses_filtering_sorting_and_limit() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "scan",
+                "--git",
+                "--only",
+                "dirty,unpushed",
+                "--only",
+                "errors",
+                "--sort",
+                "last-commit",
+                "--limit",
+                "5",
+                "--format",
+                "json",
+            ]);
+
+            match cli.command {
+                Commands::Scan { report, .. } => {
+                    assert_eq!(
+                        report.only,
+                        vec![Attention::Dirty, Attention::Unpushed, Attention::Errors]
+                    );
+                    assert_eq!(report.sort, Some(SortKey::LastCommit));
+                    assert_eq!(report.limit, Some(5));
+                    assert_eq!(report.format, OutputFormat::Json);
+                }
+                _ => panic!("Expected Scan command"),
+            }
+        }
+
+        #[test]
+        fn check_accepts_report_options() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "check",
+                "--only",
+                "vulnerable",
+                "--sort",
+                "score",
+            ]);
+
+            match cli.command {
+                Commands::Check { report, .. } => {
+                    assert_eq!(report.only, vec![Attention::Vulnerable]);
+                    assert_eq!(report.sort, Some(SortKey::Score));
+                }
+                _ => panic!("Expected Check command"),
+            }
+        }
+
+        #[test]
+        fn parses_detail_levels() {
+            let cli = Cli::parse_from(["devhealth", "scan", "--deps", "--detail", "full"]);
+            match cli.command {
+                Commands::Scan { report, .. } => assert_eq!(report.detail(), Detail::Full),
+                _ => panic!("Expected Scan command"),
+            }
+
+            let cli = Cli::parse_from(["devhealth", "check", "--summary"]);
+            match cli.command {
+                Commands::Check { report, .. } => assert_eq!(report.detail(), Detail::Summary),
+                _ => panic!("Expected Check command"),
+            }
+        }
+
+        #[test]
+        fn summary_conflicts_with_explicit_detail() {
+            let result =
+                Cli::try_parse_from(["devhealth", "scan", "--summary", "--detail", "full"]);
+            assert!(result.is_err());
+            assert!(Cli::try_parse_from(["devhealth", "scan", "--detail", "summary"]).is_err());
+        }
+
+        #[test]
+        fn parses_overview() {
+            let cli = Cli::parse_from(["devhealth", "scan", "--git", "--deps", "--overview"]);
+            match cli.command {
+                Commands::Scan { report, .. } => assert!(report.overview),
+                _ => panic!("Expected Scan command"),
+            }
+            assert!(Cli::try_parse_from(["devhealth", "scan", "--overview", "--summary"]).is_err());
+            assert!(
+                Cli::try_parse_from(["devhealth", "scan", "--overview", "--detail", "full"])
+                    .is_err()
+            );
+        }
+
+        #[test]
+        fn parses_table_view_and_new_sort_keys() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "scan",
+                "--git",
+                "--view",
+                "table",
+                "--sort",
+                "size",
+            ]);
+            match cli.command {
+                Commands::Scan { report, .. } => {
+                    assert_eq!(report.view, View::Table);
+                    assert_eq!(report.sort, Some(SortKey::Size));
+                }
+                _ => panic!("Expected Scan command"),
+            }
+            let cli = Cli::parse_from(["devhealth", "check", "--sort", "branch"]);
+            match cli.command {
+                Commands::Check { report, .. } => assert_eq!(report.view, View::Tree),
+                _ => panic!("Expected Check command"),
+            }
+            assert!(
+                Cli::try_parse_from(["devhealth", "scan", "--view", "table", "--overview"])
+                    .is_err()
+            );
+        }
+
+        #[test]
+        fn porcelain_sends_messages_to_stderr_and_excludes_other_layouts() {
+            let cli = Cli::parse_from(["devhealth", "scan", "--git", "--porcelain"]);
+            match cli.command {
+                Commands::Scan { report, .. } => {
+                    assert!(report.porcelain);
+                    assert_eq!(report.message_format(), OutputFormat::Json);
+                }
+                _ => panic!("Expected Scan command"),
+            }
+            assert!(
+                Cli::try_parse_from(["devhealth", "check", "--porcelain", "--format", "json"])
+                    .is_err()
+            );
+            assert!(
+                Cli::try_parse_from(["devhealth", "check", "--porcelain", "--overview"]).is_err()
+            );
+        }
+
+        #[test]
+        fn notify_desktop_is_off_unless_given() {
+            match Cli::parse_from(["devhealth", "scan", "--git", "--notify-desktop"]).command {
+                Commands::Scan { report, .. } => assert!(report.notify_desktop),
+                _ => panic!("Expected Scan command"),
+            }
+            match Cli::parse_from(["devhealth", "check"]).command {
+                Commands::Check { report, .. } => assert!(!report.notify_desktop),
+                _ => panic!("Expected Check command"),
+            }
+        }
+
+        #[test]
+        fn top_implies_worst_first_and_conflicts_with_sort_and_limit() {
+            let cli = Cli::parse_from(["devhealth", "check", "--top", "5"]);
+            match cli.command {
+                Commands::Check { report, .. } => {
+                    let options = report.options();
+                    assert_eq!(options.top, Some(5));
+                    assert!(options.is_active());
+                }
+                _ => panic!("Expected Check command"),
+            }
+            assert!(
+                Cli::try_parse_from(["devhealth", "check", "--top", "5", "--limit", "3"]).is_err()
+            );
+            assert!(
+                Cli::try_parse_from(["devhealth", "check", "--top", "5", "--sort", "name"])
+                    .is_err()
+            );
+        }
+
+        #[test]
+        fn parses_fail_on_kinds() {
+            let cli = Cli::parse_from(["devhealth", "scan", "--fail-on", "dirty,vulnerable"]);
+            match cli.command {
+                Commands::Scan { report, .. } => {
+                    assert_eq!(
+                        report.fail_on,
+                        vec![Attention::Dirty, Attention::Vulnerable]
+                    );
+                }
+                _ => panic!("Expected Scan command"),
+            }
+        }
+
+        #[test]
+        fn parses_fail_level() {
+            let cli = Cli::parse_from(["devhealth", "check", "--fail-level", "warn"]);
+            match cli.command {
+                Commands::Check { report, .. } => {
+                    assert_eq!(report.fail_level, Some(Severity::Warn))
+                }
+                _ => panic!("Expected Check command"),
+            }
+            assert!(Cli::try_parse_from(["devhealth", "check", "--fail-level", "fatal"]).is_err());
+        }
+
+        #[test]
+        fn rejects_unknown_filter() {
+            assert!(Cli::try_parse_from(["devhealth", "scan", "--only", "stale"]).is_err());
+        }
+    }
+
+    mod rules_command {
+        use super::*;
+
+        #[test]
+        fn parses_optional_rule_and_format() {
+            let cli = Cli::parse_from(["devhealth", "rules", "GIT001", "--format", "json"]);
+            match cli.command {
+                Commands::Rules { rule, format } => {
+                    assert_eq!(rule.as_deref(), Some("GIT001"));
+                    assert_eq!(format, OutputFormat::Json);
+                }
+                _ => panic!("Expected Rules command"),
+            }
+            assert!(matches!(
+                Cli::parse_from(["devhealth", "rules"]).command,
+                Commands::Rules { rule: None, .. }
+            ));
+        }
+    }
+
+    mod init_command {
+        use super::*;
+
+        #[test]
+        fn parses_vscode_with_default_path() {
+            let cli = Cli::parse_from(["devhealth", "init", "vscode"]);
+            match cli.command {
+                Commands::Init { action, .. } => {
+                    assert_eq!(
+                        action,
+                        Some(InitCommands::Vscode {
+                            path: PathBuf::from(".")
+                        })
+                    );
+                }
+                _ => panic!("Expected Init command"),
+            }
+        }
+
+        #[test]
+        fn runs_the_wizard_without_an_editor() {
+            let cli = Cli::parse_from(["devhealth", "init", "--yes", "--force"]);
+            match cli.command {
+                Commands::Init { action, yes, force } => {
+                    assert_eq!(action, None);
+                    assert!(yes && force);
+                }
+                _ => panic!("Expected Init command"),
+            }
+            assert!(Cli::try_parse_from(["devhealth", "init", "--yes", "vscode"]).is_err());
+        }
+    }
+
+    mod debug_bundle_command {
+        use super::*;
+
+        #[test]
+        fn parses_out() {
+            match Cli::parse_from(["devhealth", "debug-bundle", "--out", "bundle.json"]).command {
+                Commands::DebugBundle { out } => {
+                    assert_eq!(out, Some(PathBuf::from("bundle.json")))
+                }
+                _ => panic!("Expected DebugBundle command"),
+            }
+        }
+    }
+
+    mod self_update_command {
+        use super::*;
+
+        #[test]
+        fn parses_check() {
+            assert!(matches!(
+                Cli::parse_from(["devhealth", "self-update", "--check"]).command,
+                Commands::SelfUpdate { check: true }
+            ));
+            assert!(matches!(
+                Cli::parse_from(["devhealth", "self-update"]).command,
+                Commands::SelfUpdate { check: false }
+            ));
+        }
+    }
+
+    mod stats_command {
+        use super::*;
+
+        #[test]
+        fn parses_export_of_a_saved_report() {
+            match Cli::parse_from(["devhealth", "stats", "export", "--results", "report.json"])
+                .command
+            {
+                Commands::Stats {
+                    action:
+                        StatsCommands::Export {
+                            out, path, results, ..
+                        },
+                } => {
+                    assert_eq!(out, None);
+                    assert_eq!(path, PathBuf::from("."));
+                    assert_eq!(results, Some(PathBuf::from("report.json")));
+                }
+                _ => panic!("Expected stats command"),
+            }
+        }
+    }
+
+    mod digest_command {
+        use super::*;
+
+        #[test]
+        fn parses_send_and_schedule() {
+            match Cli::parse_from(["devhealth", "digest", "send", "--dry-run", "--tag", "work"])
+                .command
+            {
+                Commands::Digest {
+                    action:
+                        DigestCommands::Send {
+                            path,
+                            tags,
+                            dry_run,
+                            ..
+                        },
+                } => {
+                    assert_eq!(path, None);
+                    assert_eq!(tags, vec!["work".to_string()]);
+                    assert!(dry_run);
+                }
+                _ => panic!("Expected digest send command"),
+            }
+
+            let schedule = Cli::parse_from(["devhealth", "digest", "schedule", "--path", "~/src"]);
+            assert!(matches!(
+                schedule.command,
+                Commands::Digest {
+                    action: DigestCommands::Schedule { path: Some(_) }
+                }
+            ));
+            assert!(!schedule.command.writes_state());
+        }
+    }
+
+    mod auth_command {
+        use super::*;
+
+        #[test]
+        fn parses_login_and_logout() {
+            let login = Cli::parse_from(["devhealth", "auth", "login", "github"]);
+            assert!(matches!(
+                login.command,
+                Commands::Auth {
+                    action: AuthCommands::Login {
+                        provider: Provider::GitHub
+                    }
+                }
+            ));
+            assert!(!login.command.writes_state());
+
+            let logout = Cli::parse_from(["devhealth", "auth", "logout", "smtp"]);
+            assert!(matches!(
+                logout.command,
+                Commands::Auth {
+                    action: AuthCommands::Logout {
+                        provider: Provider::Smtp
+                    }
+                }
+            ));
+            assert!(Cli::try_parse_from(["devhealth", "auth", "login", "slack"]).is_err());
+        }
+    }
+
+    mod repos_command {
+        use super::*;
+
+        #[test]
+        fn parses_missing_with_paths_owners_and_clones() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "repos",
+                "missing",
+                "-p",
+                "~/src",
+                "--path",
+                "~/work",
+                "--owner",
+                "ada",
+                "--clone",
+                "ada/api",
+                "--clone",
+                "ada/web",
+                "--ssh",
+            ]);
+            match &cli.command {
+                Commands::Repos {
+                    action:
+                        ReposCommands::Missing {
+                            paths,
+                            owners,
+                            clone,
+                            into,
+                            ssh,
+                            archived,
+                            ..
+                        },
+                } => {
+                    assert_eq!(paths, &[PathBuf::from("~/src"), PathBuf::from("~/work")]);
+                    assert_eq!(owners, &["ada"]);
+                    assert_eq!(clone, &["ada/api", "ada/web"]);
+                    assert!(into.is_none() && *ssh && !*archived);
+                }
+                _ => panic!("Expected repos missing"),
+            }
+            assert!(!cli.command.writes_state());
+
+            match Cli::parse_from(["devhealth", "repos", "missing"]).command {
+                Commands::Repos {
+                    action: ReposCommands::Missing { paths, .. },
+                } => assert_eq!(paths, [PathBuf::from(".")]),
+                _ => panic!("Expected repos missing"),
+            }
+            assert!(
+                Cli::try_parse_from(["devhealth", "repos", "missing", "--into", "/tmp"]).is_err(),
+                "--into needs --clone"
+            );
+        }
+    }
+
+    mod config_command {
+        use super::*;
+
+        #[test]
+        fn parses_check_and_show() {
+            let check = Cli::parse_from(["devhealth", "config", "check", "--file", "team.toml"]);
+            match &check.command {
+                Commands::Config {
+                    action: ConfigCommands::Check { file },
+                } => assert_eq!(file.as_deref(), Some(std::path::Path::new("team.toml"))),
+                _ => panic!("Expected config check"),
+            }
+            assert!(!check.command.writes_state());
+
+            let show = Cli::parse_from(["devhealth", "config", "show", "--lang", "de"]);
+            assert!(matches!(
+                show.command,
+                Commands::Config {
+                    action: ConfigCommands::Show
+                }
+            ));
+            assert_eq!(show.lang, Some(Lang::De));
+        }
+    }
+
+    mod history_command {
+        use super::*;
+
+        #[test]
+        fn parses_prune_overrides() {
+            match Cli::parse_from([
+                "devhealth",
+                "history",
+                "prune",
+                "--keep",
+                "5",
+                "--keep-days",
+                "30",
+            ])
+            .command
+            {
+                Commands::History { action } => assert_eq!(
+                    action,
+                    HistoryCommands::Prune {
+                        keep: Some(5),
+                        keep_days: Some(30),
+                        dry_run: false,
+                    }
+                ),
+                _ => panic!("Expected history command"),
+            }
+        }
+
+        #[test]
+        fn parses_list_and_show_filters() {
+            let filter = HistoryFilterArgs {
+                since: Some("2024-01-01".to_string()),
+                repo: Some("api".to_string()),
+                finding: Some("DEP014".to_string()),
+            };
+            let args = [
+                "--since",
+                "2024-01-01",
+                "--repo",
+                "api",
+                "--finding",
+                "DEP014",
+            ];
+            for command in ["list", "show"] {
+                let argv = ["devhealth", "history", command].into_iter().chain(args);
+                match Cli::parse_from(argv).command {
+                    Commands::History {
+                        action:
+                            HistoryCommands::List {
+                                filter: parsed,
+                                format,
+                            }
+                            | HistoryCommands::Show {
+                                filter: parsed,
+                                format,
+                            },
                     } => {
-                        assert_eq!(git, expected_git, "Git flag mismatch for {:?}", args_clone);
-                        assert_eq!(
-                            deps, expected_deps,
-                            "Deps flag mismatch for {:?}",
-                            args_clone
-                        );
-                        assert_eq!(
-                            system, expected_system,
-                            "System flag mismatch for {:?}",
-                            args_clone
-                        );
+                        assert_eq!(parsed, filter);
+                        assert_eq!(format, OutputFormat::Text);
                     }
-                    _ => panic!("Expected Scan command for args {:?}", args_clone),
+                    _ => panic!("Expected history command"),
+                }
+            }
+        }
+    }
+
+    mod state_lock {
+        use super::*;
+
+        #[test]
+        fn waits_unless_told_otherwise() {
+            assert_eq!(
+                Cli::parse_from(["devhealth", "check"]).lock_mode(),
+                LockMode::Wait
+            );
+            assert_eq!(
+                Cli::parse_from(["devhealth", "check", "--no-wait"]).lock_mode(),
+                LockMode::NoWait
+            );
+            assert_eq!(
+                Cli::parse_from(["devhealth", "scan", "--no-wait", "--wait"]).lock_mode(),
+                LockMode::Wait
+            );
+        }
+
+        #[test]
+        fn only_commands_writing_state_lock_it() {
+            let writes = |args: &[&str]| {
+                let args = std::iter::once("devhealth").chain(args.iter().copied());
+                Cli::parse_from(args).command.writes_state()
+            };
+            assert!(writes(&["scan"]));
+            assert!(writes(&["tag", "add", ".", "work"]));
+            assert!(writes(&["bench", "fs"]));
+            assert!(!writes(&["tag", "list"]));
+            assert!(!writes(&["bench", "fs", "--history"]));
+            assert!(!writes(&["prompt"]));
+            assert!(!writes(&["watch-advisories"]));
+            assert!(writes(&["digest", "send"]));
+            assert!(!writes(&["digest", "send", "--dry-run"]));
+            assert!(
+                writes(&["deps", "exposure", "--audit"]),
+                "The audit refreshes the advisory mirror"
+            );
+            assert!(!writes(&["deps", "exposure"]));
+        }
+    }
+
+    mod advisory_db_command {
+        use super::*;
+
+        #[test]
+        fn parses_sync_export_and_import() {
+            let export = Cli::parse_from(["devhealth", "advisory-db", "sync", "--to", "/mnt/usb"]);
+            match export.command {
+                Commands::AdvisoryDb {
+                    action: AdvisoryDbCommands::Sync { to, from, .. },
+                } => {
+                    assert_eq!(to, Some(PathBuf::from("/mnt/usb")));
+                    assert_eq!(from, None);
+                }
+                _ => panic!("Expected AdvisoryDb sync command"),
+            }
+
+            let import =
+                Cli::parse_from(["devhealth", "advisory-db", "sync", "--from", "/mnt/usb"]);
+            match import.command {
+                Commands::AdvisoryDb {
+                    action: AdvisoryDbCommands::Sync { to, from, .. },
+                } => {
+                    assert_eq!(to, None);
+                    assert_eq!(from, Some(PathBuf::from("/mnt/usb")));
+                }
+                _ => panic!("Expected AdvisoryDb sync command"),
+            }
+        }
+
+        #[test]
+        fn rejects_sync_with_both_directions() {
+            let result = Cli::try_parse_from([
+                "devhealth",
+                "advisory-db",
+                "sync",
+                "--to",
+                "a",
+                "--from",
+                "b",
+            ]);
+            assert!(result.is_err(), "--to and --from should conflict");
+        }
+    }
+
+    mod policy_command {
+        use super::*;
+
+        #[test]
+        fn parses_sync_and_show() {
+            let sync = Cli::parse_from(["devhealth", "policy", "sync"]);
+            assert!(matches!(
+                sync.command,
+                Commands::Policy {
+                    action: PolicyCommands::Sync
+                }
+            ));
+
+            let show = Cli::parse_from(["devhealth", "policy", "show"]);
+            assert!(matches!(
+                show.command,
+                Commands::Policy {
+                    action: PolicyCommands::Show
+                }
+            ));
+        }
+    }
+
+    mod merge_command {
+        use super::*;
+
+        #[test]
+        fn parses_result_files() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "merge",
+                "alice.json",
+                "bob.json",
+                "--detail",
+                "full",
+            ]);
+            match cli.command {
+                Commands::Merge {
+                    files,
+                    format,
+                    detail,
+                } => {
+                    assert_eq!(
+                        files,
+                        vec![PathBuf::from("alice.json"), PathBuf::from("bob.json")]
+                    );
+                    assert_eq!(format, OutputFormat::Text);
+                    assert_eq!(detail, Detail::Full);
+                }
+                _ => panic!("Expected Merge command"),
+            }
+        }
+
+        #[test]
+        fn requires_result_files() {
+            assert!(Cli::try_parse_from(["devhealth", "merge"]).is_err());
+        }
+    }
+
+    mod ack_command {
+        use super::*;
+
+        #[test]
+        fn parses_acknowledgements() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "ack",
+                "vulnerable:GHSA-1234",
+                "--until",
+                "2025-02-01",
+                "--reason",
+                "not reachable",
+            ]);
+            match cli.command {
+                Commands::Ack {
+                    action,
+                    id,
+                    until,
+                    reason,
+                } => {
+                    assert_eq!(action, None);
+                    assert_eq!(id.as_deref(), Some("vulnerable:GHSA-1234"));
+                    assert_eq!(until.as_deref(), Some("2025-02-01"));
+                    assert_eq!(reason.as_deref(), Some("not reachable"));
+                }
+                _ => panic!("Expected Ack command"),
+            }
+        }
+
+        #[test]
+        fn parses_list_and_remove() {
+            let list = Cli::parse_from(["devhealth", "ack", "list"]);
+            assert!(matches!(
+                list.command,
+                Commands::Ack {
+                    action: Some(AckCommands::List),
+                    ..
+                }
+            ));
+
+            let remove = Cli::parse_from(["devhealth", "ack", "remove", "dirty:/src/api"]);
+            assert!(matches!(
+                remove.command,
+                Commands::Ack { action: Some(AckCommands::Remove { ref id }), .. } if id == "dirty:/src/api"
+            ));
+
+            assert!(
+                Cli::try_parse_from(["devhealth", "ack"]).is_err(),
+                "a finding ID is required"
+            );
+        }
+    }
+
+    mod snapshot_commands {
+        use super::*;
+
+        #[test]
+        fn parses_export() {
+            let cli = Cli::parse_from(["devhealth", "export", "--out", "snapshot.tar.zst"]);
+            match cli.command {
+                Commands::Export {
+                    out, path, results, ..
+                } => {
+                    assert_eq!(out, PathBuf::from("snapshot.tar.zst"));
+                    assert_eq!(path, PathBuf::from("."));
+                    assert_eq!(results, None);
+                }
+                _ => panic!("Expected Export command"),
+            }
+            assert!(
+                Cli::try_parse_from(["devhealth", "export"]).is_err(),
+                "--out is required"
+            );
+        }
+
+        #[test]
+        fn parses_import_with_diff() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "import",
+                "before.tar.zst",
+                "--diff",
+                "after.tar.zst",
+            ]);
+            match cli.command {
+                Commands::Import { snapshot, diff, .. } => {
+                    assert_eq!(snapshot, PathBuf::from("before.tar.zst"));
+                    assert_eq!(diff, Some(PathBuf::from("after.tar.zst")));
+                }
+                _ => panic!("Expected Import command"),
+            }
+        }
+    }
+
+    mod fix_command {
+        use super::*;
+
+        #[test]
+        fn parses_sync_forks_options() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "fix",
+                "sync-forks",
+                "--path",
+                "/src",
+                "--tag",
+                "work",
+                "--dry-run",
+            ]);
+            match cli.command {
+                Commands::Fix {
+                    action:
+                        FixCommands::SyncForks {
+                            path,
+                            tags,
+                            dry_run,
+                            push,
+                        },
+                } => {
+                    assert_eq!(path, PathBuf::from("/src"));
+                    assert_eq!(tags, vec!["work".to_string()]);
+                    assert!(dry_run);
+                    assert!(!push);
+                }
+                _ => panic!("Expected Fix sync-forks command"),
+            }
+        }
+    }
+
+    mod clean_command {
+        use super::*;
+
+        #[test]
+        fn parses_clean_options() {
+            match Cli::parse_from(["devhealth", "clean", "--path", "/src", "--dry-run"]).command {
+                Commands::Clean {
+                    path,
+                    tags,
+                    dry_run,
+                    yes,
+                } => {
+                    assert_eq!(path, PathBuf::from("/src"));
+                    assert!(tags.is_empty());
+                    assert!(dry_run && !yes);
+                }
+                _ => panic!("Expected Clean command"),
+            }
+            assert!(matches!(
+                Cli::parse_from(["devhealth", "clean", "-y"]).command,
+                Commands::Clean { yes: true, .. }
+            ));
+        }
+    }
+
+    mod licenses_command {
+        use super::*;
+
+        #[test]
+        fn parses_csv_format() {
+            match Cli::parse_from(["devhealth", "licenses", "--path", "/src", "--format", "csv"])
+                .command
+            {
+                Commands::Licenses { path, tags, format } => {
+                    assert_eq!(path, PathBuf::from("/src"));
+                    assert!(tags.is_empty());
+                    assert_eq!(format, LicenseFormat::Csv);
+                }
+                _ => panic!("Expected Licenses command"),
+            }
+        }
+    }
+
+    mod prompt_command {
+        use super::*;
+
+        #[test]
+        fn parses_path_and_max_age() {
+            match Cli::parse_from([
+                "devhealth",
+                "prompt",
+                "--path",
+                "/src/api",
+                "--max-age",
+                "30",
+            ])
+            .command
+            {
+                Commands::Prompt {
+                    path,
+                    max_age,
+                    refresh,
+                } => {
+                    assert_eq!(path, PathBuf::from("/src/api"));
+                    assert_eq!(max_age, 30);
+                    assert!(!refresh);
+                }
+                _ => panic!("Expected Prompt command"),
+            }
+            match Cli::parse_from(["devhealth", "prompt"]).command {
+                Commands::Prompt { max_age, .. } => assert_eq!(max_age, prompt::DEFAULT_MAX_AGE),
+                _ => panic!("Expected Prompt command"),
+            }
+        }
+    }
+
+    mod watch_advisories_command {
+        use super::*;
+
+        #[test]
+        fn parses_interval() {
+            match Cli::parse_from([
+                "devhealth",
+                "watch-advisories",
+                "--interval",
+                "60",
+                "--no-notify",
+            ])
+            .command
+            {
+                Commands::WatchAdvisories {
+                    interval,
+                    no_notify,
+                    format,
+                } => {
+                    assert_eq!(interval, Some(60));
+                    assert!(no_notify);
+                    assert_eq!(format, OutputFormat::Text);
+                }
+                _ => panic!("Expected WatchAdvisories command"),
+            }
+            assert!(
+                Cli::try_parse_from(["devhealth", "watch-advisories", "--interval", "0"]).is_err()
+            );
+        }
+    }
+
+    mod deps_command {
+        use super::*;
+
+        #[test]
+        fn parses_pin_suggestions() {
+            match Cli::parse_from([
+                "devhealth",
+                "deps",
+                "pin-suggestions",
+                "--path",
+                "/src",
+                "--patch",
+            ])
+            .command
+            {
+                Commands::Deps { action } => assert_eq!(
+                    action,
+                    DepsCommands::PinSuggestions {
+                        path: PathBuf::from("/src"),
+                        tags: Vec::new(),
+                        patch: true,
+                        format: OutputFormat::Text,
+                    }
+                ),
+                _ => panic!("Expected Deps command"),
+            }
+            assert!(Cli::try_parse_from([
+                "devhealth",
+                "deps",
+                "pin-suggestions",
+                "--patch",
+                "--format",
+                "json"
+            ])
+            .is_err());
+        }
+
+        #[test]
+        fn parses_exposure() {
+            match Cli::parse_from(["devhealth", "deps", "exposure", "--audit", "--top", "5"])
+                .command
+            {
+                Commands::Deps { action } => assert_eq!(
+                    action,
+                    DepsCommands::Exposure {
+                        path: PathBuf::from("."),
+                        tags: Vec::new(),
+                        audit: true,
+                        top: 5,
+                        format: OutputFormat::Text,
+                    }
+                ),
+                _ => panic!("Expected Deps command"),
+            }
+        }
+
+        #[test]
+        fn parses_find() {
+            match Cli::parse_from(["devhealth", "deps", "find", "openssl", "--stored"]).command {
+                Commands::Deps { action } => assert_eq!(
+                    action,
+                    DepsCommands::Find {
+                        package: "openssl".to_string(),
+                        path: PathBuf::from("."),
+                        tags: Vec::new(),
+                        stored: true,
+                        format: OutputFormat::Text,
+                    }
+                ),
+                _ => panic!("Expected Deps command"),
+            }
+            assert!(Cli::try_parse_from(["devhealth", "deps", "find"]).is_err());
+            assert!(Cli::try_parse_from([
+                "devhealth",
+                "deps",
+                "find",
+                "xz",
+                "--stored",
+                "--path",
+                "/src"
+            ])
+            .is_err());
+        }
+
+        #[test]
+        fn parses_upgrade_impact() {
+            match Cli::parse_from([
+                "devhealth",
+                "deps",
+                "upgrade-impact",
+                "tokio",
+                "2.0.0",
+                "--tag",
+                "work",
+            ])
+            .command
+            {
+                Commands::Deps { action } => assert_eq!(
+                    action,
+                    DepsCommands::UpgradeImpact {
+                        package: "tokio".to_string(),
+                        version: "2.0.0".to_string(),
+                        path: PathBuf::from("."),
+                        tags: vec!["work".to_string()],
+                        format: OutputFormat::Text,
+                    }
+                ),
+                _ => panic!("Expected Deps command"),
+            }
+            assert!(Cli::try_parse_from(["devhealth", "deps", "upgrade-impact", "tokio"]).is_err());
+        }
+
+        #[test]
+        fn parses_align() {
+            match Cli::parse_from(["devhealth", "deps", "align", "--min-spread", "minor"]).command {
+                Commands::Deps { action } => assert_eq!(
+                    action,
+                    DepsCommands::Align {
+                        path: PathBuf::from("."),
+                        tags: Vec::new(),
+                        min_spread: crate::scanner::alignment::Spread::Minor,
+                        format: OutputFormat::Text,
+                    }
+                ),
+                _ => panic!("Expected Deps command"),
+            }
+        }
+    }
+
+    mod bench_command {
+        use super::*;
+
+        #[test]
+        fn parses_net_with_defaults() {
+            match Cli::parse_from(["devhealth", "bench", "net"]).command {
+                Commands::Bench { action } => assert_eq!(
+                    action,
+                    BenchCommands::Net {
+                        endpoints: Vec::new(),
+                        rounds: 3,
+                        label: None,
+                        history: false,
+                        no_save: false,
+                        format: OutputFormat::Text,
+                    }
+                ),
+                _ => panic!("Expected Bench command"),
+            }
+        }
+
+        #[test]
+        fn parses_net_endpoints_and_label() {
+            let cli = Cli::parse_from([
+                "devhealth",
+                "bench",
+                "net",
+                "--endpoint",
+                "pypi.org",
+                "--endpoint",
+                "artifactory:8443",
+                "--label",
+                "office",
+                "--rounds",
+                "5",
+            ]);
+            match cli.command {
+                Commands::Bench {
+                    action:
+                        BenchCommands::Net {
+                            endpoints,
+                            rounds,
+                            label,
+                            ..
+                        },
+                } => {
+                    assert_eq!(
+                        endpoints,
+                        vec!["pypi.org".to_string(), "artifactory:8443".to_string()]
+                    );
+                    assert_eq!(rounds, 5);
+                    assert_eq!(label.as_deref(), Some("office"));
                 }
+                _ => panic!("Expected Bench net command"),
             }
         }
+
+        #[test]
+        fn parses_fs_with_defaults() {
+            match Cli::parse_from(["devhealth", "bench", "fs"]).command {
+                Commands::Bench { action } => assert_eq!(
+                    action,
+                    BenchCommands::Fs {
+                        path: PathBuf::from("."),
+                        files: 1000,
+                        label: None,
+                        history: false,
+                        no_save: false,
+                        format: OutputFormat::Text,
+                    }
+                ),
+                _ => panic!("Expected Bench command"),
+            }
+            assert!(Cli::try_parse_from(["devhealth", "bench", "fs", "--files", "5"]).is_err());
+        }
+
+        #[test]
+        fn rejects_zero_rounds() {
+            assert!(Cli::try_parse_from(["devhealth", "bench", "net", "--rounds", "0"]).is_err());
+        }
+    }
+
+    #[test]
+    fn offline_flag_defaults_to_false() {
+        let cli = Cli::parse_from(["devhealth", "check"]);
+        assert!(!cli.offline, "Offline mode should be opt-in");
+    }
+
+    #[test]
+    fn offline_flag_is_accepted_before_and_after_subcommand() {
+        let before = Cli::parse_from(["devhealth", "--offline", "scan", "--deps"]);
+        let after = Cli::parse_from(["devhealth", "scan", "--deps", "--offline"]);
+
+        assert!(
+            before.offline,
+            "Global flag should parse before the subcommand"
+        );
+        assert!(
+            after.offline,
+            "Global flag should parse after the subcommand"
+        );
     }
 
     #[test]
@@ -0,0 +1,1088 @@
+//! Configuration file support for DevHealth
+//!
+//! This module loads the user's `devhealth.toml` from the DevHealth config
+//! directory (see [`crate::utils::paths::config_dir`]). Every section is
+//! optional; a missing file yields the default configuration.
+//!
+//! Loading validates the file (see [`Config::validate`]): unknown keys, path
+//! patterns that can never match and out-of-range thresholds are errors
+//! listing every problem with its key, as `devhealth config check` does.
+//!
+//! ## Example
+//!
+//! ```toml
+//! # Plain-text output without emoji or box drawing
+//! accessible = true
+//!
+//! # Report language: "en", "de" or "es" (defaults to the locale)
+//! lang = "de"
+//!
+//! # Scanners `devhealth scan` runs when no scanner flag is given, and
+//! # whether reports and `scan --sizes` compare with the last scan, keeping
+//! # at most 30 runs per scanned directory and nothing older than 180 days;
+//! # local branches without commits for 60 days and repositories without
+//! # commits for a year are reported as stale
+//! [scan]
+//! default = ["git", "deps", "audit"]
+//! history = true
+//! history_keep_runs = 30
+//! history_keep_days = 180
+//! stale_branch_days = 60
+//! stale_repo_months = 12
+//!
+//! # Vulnerabilities left out of audits, optionally until a date (see
+//! # `scanner::audit`)
+//! [audit]
+//! ignore_dev = true
+//! ignore = [{ advisory = "RUSTSEC-2023-0071", until = 2026-06-30, reason = "no fix yet" }]
+//!
+//! # Internal servers `bench net` measures besides the public registries
+//! [bench]
+//! endpoints = ["https://artifactory.example.com"]
+//!
+//! # Limits for `scan --deep`
+//! [deep]
+//! timeout_secs = 600
+//! tests = true
+//!
+//! # Weekly email digest sent by `digest send` (see `digest`); the password
+//! # comes from the system keyring (`auth login smtp`) or the variable named
+//! # by `password_env`
+//! [digest]
+//! server = "smtp.example.com"
+//! username = "me@example.com"
+//! from = "DevHealth <me@example.com>"
+//! to = ["me@example.com"]
+//! path = "~/src"
+//! schedule = "0 8 * * 1"
+//!
+//! # Checks switched off everywhere or for some repositories (see `findings`)
+//! [checks]
+//! disabled = ["stale-changelog"]
+//!
+//! # Forge access for CI status (tokens stored with `auth login` or set in
+//! # the environment take precedence)
+//! [forge]
+//! github_token = "ghp_..."
+//! gitlab_hosts = ["gitlab.example.com"]
+//!
+//! # Desktop notification when a check or scan finds new critical issues,
+//! # as `--notify-desktop` does (see `notify`)
+//! [notify]
+//! desktop = true
+//! level = "critical"
+//!
+//! # Organization policy with required checks (see `policy`)
+//! [policy]
+//! url = "https://platform.example.com/devhealth/policy.toml"
+//! public_key = "ssh-ed25519 AAAA... platform-team"
+//!
+//! # Severity overrides and exit threshold (see `findings`)
+//! [severity]
+//! fail_level = "error"
+//! checks = { stale-changelog = "info" }
+//!
+//! # Tag repositories by path pattern
+//! [tags]
+//! work = ["~/work/**"]
+//! personal = ["~/personal/*", "~/dotfiles"]
+//!
+//! # Recolor output (see `utils::theme`)
+//! [theme]
+//! palette = "colorblind"
+//!
+//! # Daily new-version check and release verification (see `update`)
+//! [update]
+//! check = false
+//! ```
+
+use crate::findings::{ChecksConfig, Severity, SeverityConfig};
+use crate::history;
+use crate::i18n::Lang;
+use crate::scanner::audit::AuditConfig;
+use crate::scanner::git::{self, GitScanOptions};
+use crate::utils::fs as dh_fs;
+use crate::utils::paths;
+use crate::utils::redact;
+use crate::utils::smtp::Security;
+use crate::utils::theme::{Theme, ThemeConfig};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the configuration file inside the state directory
+pub const CONFIG_FILE: &str = "devhealth.toml";
+
+/// Errors that can occur while loading the configuration
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read config file: {0}")]
+    FileRead(#[from] std::io::Error),
+    #[error("Failed to parse config file: {0}")]
+    TomlParse(#[from] toml::de::Error),
+    #[error("Invalid config file {}:{}", .path.display(), list_issues(.issues))]
+    Invalid {
+        path: PathBuf,
+        issues: Vec<ConfigIssue>,
+    },
+}
+
+/// Formats issues one per line, each on a new indented line
+fn list_issues(issues: &[ConfigIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| format!("\n  {}", issue))
+        .collect()
+}
+
+/// A problem in `devhealth.toml`, at a dotted key such as
+/// `severity.overrides[0].paths[1]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Key the problem is at
+    pub key: String,
+    /// What is wrong
+    pub problem: ConfigProblem,
+}
+
+/// What is wrong with a key of `devhealth.toml`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigProblem {
+    /// Key DevHealth does not know, such as a misspelled setting
+    UnknownKey,
+    /// Path pattern that can never match, and why
+    InvalidGlob {
+        pattern: String,
+        reason: &'static str,
+    },
+    /// Number outside the range the setting accepts
+    InvalidThreshold { value: u64, minimum: u64 },
+    /// Cron schedule without five fields
+    InvalidSchedule(String),
+    /// Theme role or color that does not exist
+    InvalidTheme(String),
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.problem {
+            ConfigProblem::UnknownKey => write!(f, "{}: unknown key", self.key),
+            ConfigProblem::InvalidGlob { pattern, reason } => {
+                write!(f, "{}: invalid pattern {:?}: {}", self.key, pattern, reason)
+            }
+            ConfigProblem::InvalidThreshold { value, minimum } => {
+                write!(
+                    f,
+                    "{}: invalid value {}: must be at least {}",
+                    self.key, value, minimum
+                )
+            }
+            ConfigProblem::InvalidSchedule(schedule) => write!(
+                f,
+                "{}: invalid schedule {:?}: expected five cron fields such as \"0 8 * * 1\"",
+                self.key, schedule
+            ),
+            ConfigProblem::InvalidTheme(message) => write!(f, "{}: {}", self.key, message),
+        }
+    }
+}
+
+/// User configuration loaded from `devhealth.toml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Always use accessible, emoji-free output
+    pub accessible: bool,
+    /// Advisories, packages and dev dependencies left out of audits
+    pub audit: AuditConfig,
+    /// Extra endpoints for `devhealth bench`
+    pub bench: BenchConfig,
+    /// Checks disabled globally or per path or tag
+    pub checks: ChecksConfig,
+    /// Timeout and optional checks for `scan --deep`
+    pub deep: DeepConfig,
+    /// SMTP server, recipients and schedule of the email digest
+    pub digest: DigestConfig,
+    /// Forge tokens and hosts used to enrich git results
+    pub forge: ForgeConfig,
+    /// Report language, overriding the locale
+    pub lang: Option<Lang>,
+    /// Desktop notifications about new findings
+    pub notify: NotifyConfig,
+    /// Central endpoint the organization policy is pulled from
+    pub policy: PolicyConfig,
+    /// Defaults for `devhealth scan`
+    pub scan: ScanConfig,
+    /// Check severities and the level `--fail-level` defaults to
+    pub severity: SeverityConfig,
+    /// Tag name mapped to the path patterns of repositories carrying it
+    pub tags: BTreeMap<String, Vec<String>>,
+    /// Color palette and per-role color overrides
+    pub theme: ThemeConfig,
+    /// New-version check and the key releases are verified with
+    pub update: UpdateConfig,
+}
+
+/// Scanners that `devhealth scan` can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scanner {
+    /// Git repository status (`--git`)
+    Git,
+    /// Dependency analysis (`--deps`)
+    Deps,
+    /// System resources (`--system`)
+    System,
+    /// Vulnerability audit (`--audit`)
+    Audit,
+    /// Dev container toolchain comparison (`--containers`)
+    Containers,
+    /// Monorepo workspace packages (`--monorepo`)
+    Monorepo,
+    /// Manifest version versus latest git tag (`--release`)
+    Release,
+    /// Rust edition and MSRV audit (`--cargo`)
+    Cargo,
+    /// Node version managers and pinned runtimes (`--runtimes`)
+    Runtimes,
+}
+
+impl Scanner {
+    /// Returns the scanner's name as written in `devhealth.toml`
+    pub fn name(self) -> &'static str {
+        match self {
+            Scanner::Git => "git",
+            Scanner::Deps => "deps",
+            Scanner::System => "system",
+            Scanner::Audit => "audit",
+            Scanner::Containers => "containers",
+            Scanner::Monorepo => "monorepo",
+            Scanner::Release => "release",
+            Scanner::Cargo => "cargo",
+            Scanner::Runtimes => "runtimes",
+        }
+    }
+
+    /// Every scanner, as enabled by `scan --all`
+    pub const ALL: [Scanner; 9] = [
+        Scanner::Git,
+        Scanner::Deps,
+        Scanner::System,
+        Scanner::Audit,
+        Scanner::Containers,
+        Scanner::Monorepo,
+        Scanner::Release,
+        Scanner::Cargo,
+        Scanner::Runtimes,
+    ];
+}
+
+/// The `[scan]` section of `devhealth.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanConfig {
+    /// Scanners run when `scan` is given no scanner flags
+    pub default: Vec<Scanner>,
+    /// Record each report and the repository sizes measured by
+    /// `scan --sizes`, and show what changed since the previous run
+    pub history: bool,
+    /// Runs kept in the history per combination of paths and scanners
+    /// (0 keeps every run)
+    pub history_keep_runs: usize,
+    /// Days after which recorded runs and sizes are dropped (0 keeps them
+    /// forever)
+    pub history_keep_days: u64,
+    /// Days without commits after which a local branch is stale
+    pub stale_branch_days: u64,
+    /// Months without commits after which a repository is stale
+    pub stale_repo_months: u64,
+}
+
+impl ScanConfig {
+    /// Returns the options the git scanner analyzes repositories with
+    pub fn git_options(&self) -> GitScanOptions {
+        GitScanOptions {
+            stale_branch_days: self.stale_branch_days,
+            stale_repo_months: self.stale_repo_months,
+        }
+    }
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        ScanConfig {
+            default: vec![Scanner::Git, Scanner::Deps],
+            history: false,
+            history_keep_runs: history::DEFAULT_KEEP_RUNS,
+            history_keep_days: history::DEFAULT_KEEP_DAYS,
+            stale_branch_days: git::STALE_BRANCH_DAYS,
+            stale_repo_months: git::STALE_REPO_MONTHS,
+        }
+    }
+}
+
+/// The `[bench]` section of `devhealth.toml`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BenchConfig {
+    /// Hosts, `host:port`s or `https://` URLs `bench net` measures in
+    /// addition to the public registries and forges
+    pub endpoints: Vec<String>,
+}
+
+/// The `[deep]` section of `devhealth.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeepConfig {
+    /// Seconds each tool may run before it is killed
+    pub timeout_secs: u64,
+    /// Also check that the tests compile (`cargo test --no-run`)
+    pub tests: bool,
+}
+
+impl Default for DeepConfig {
+    fn default() -> Self {
+        DeepConfig {
+            timeout_secs: 300,
+            tests: false,
+        }
+    }
+}
+
+/// The `[digest]` section of `devhealth.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestConfig {
+    /// Host name of the SMTP server; no digest is sent without it
+    pub server: Option<String>,
+    /// Port of the SMTP server (defaults to 587, 465 or 25 by `security`)
+    pub port: Option<u16>,
+    /// `starttls`, `tls` or `none`
+    pub security: Security,
+    /// User name to log in with; the server is used without login if unset
+    pub username: Option<String>,
+    /// Environment variable holding the password, checked before the
+    /// system keyring
+    pub password_env: String,
+    /// Sender address, e.g. `DevHealth <me@example.com>`
+    pub from: Option<String>,
+    /// Recipient addresses
+    pub to: Vec<String>,
+    /// Directory the digest checks (defaults to the current directory)
+    pub path: Option<String>,
+    /// Cron schedule `digest schedule` installs
+    pub schedule: String,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        DigestConfig {
+            server: None,
+            port: None,
+            security: Security::StartTls,
+            username: None,
+            password_env: "DEVHEALTH_SMTP_PASSWORD".to_string(),
+            from: None,
+            to: Vec::new(),
+            path: None,
+            schedule: "0 8 * * 1".to_string(),
+        }
+    }
+}
+
+/// The `[forge]` section of `devhealth.toml`
+///
+/// The tokens are never printed: the `Debug` and serialized output only say
+/// whether each is set.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForgeConfig {
+    /// GitHub token, used when neither the keyring nor `GITHUB_TOKEN` or
+    /// `GH_TOKEN` has one
+    #[serde(serialize_with = "serialize_secret")]
+    pub github_token: Option<String>,
+    /// GitLab token, used when neither the keyring nor `GITLAB_TOKEN` has one
+    #[serde(serialize_with = "serialize_secret")]
+    pub gitlab_token: Option<String>,
+    /// Hosts of self-managed GitLab instances, in addition to gitlab.com
+    pub gitlab_hosts: Vec<String>,
+}
+
+/// The `[notify]` section of `devhealth.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotifyConfig {
+    /// Notify after every `check` and `scan`, as `--notify-desktop` does
+    pub desktop: bool,
+    /// Lowest severity of new findings that raises a notification
+    pub level: Severity,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        NotifyConfig {
+            desktop: false,
+            level: Severity::Critical,
+        }
+    }
+}
+
+/// The `[policy]` section of `devhealth.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    /// HTTPS URL of the policy document; no policy applies without it
+    pub url: Option<String>,
+    /// SSH public key the policy must be signed with
+    pub public_key: Option<String>,
+    /// URL of the signature (defaults to `url` with `.sig` appended)
+    pub signature_url: Option<String>,
+    /// Hours after which the cached policy is refreshed
+    pub refresh_hours: u64,
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        PolicyConfig {
+            url: None,
+            public_key: None,
+            signature_url: None,
+            refresh_hours: 24,
+        }
+    }
+}
+
+/// The `[update]` section of `devhealth.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateConfig {
+    /// Check once a day whether a newer release exists
+    pub check: bool,
+    /// SSH public key release checksums must be signed with, replacing the
+    /// key built into official releases
+    pub public_key: Option<String>,
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        UpdateConfig {
+            check: true,
+            public_key: None,
+        }
+    }
+}
+
+/// Writes a set secret as `<redacted>`
+fn serialize_secret<S: Serializer>(
+    secret: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    secret
+        .as_ref()
+        .map(|_| redact::REDACTED)
+        .serialize(serializer)
+}
+
+impl fmt::Debug for ForgeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = |token: &Option<String>| token.as_ref().map(|_| redact::REDACTED);
+        f.debug_struct("ForgeConfig")
+            .field("github_token", &redacted(&self.github_token))
+            .field("gitlab_token", &redacted(&self.gitlab_token))
+            .field("gitlab_hosts", &self.gitlab_hosts)
+            .finish()
+    }
+}
+
+impl Config {
+    /// Returns the default location of the configuration file
+    pub fn default_path() -> Option<PathBuf> {
+        paths::config_dir().map(|dir| dir.join(CONFIG_FILE))
+    }
+
+    /// Loads the configuration from its default location
+    ///
+    /// A missing file (or unknown home directory) yields the default
+    /// configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load() -> Result<Config, ConfigError> {
+        match Config::default_path() {
+            Some(path) if path.exists() => Config::load_from(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    /// Loads the configuration from a specific file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, and
+    /// [`ConfigError::Invalid`] listing every problem [`Config::check`]
+    /// finds.
+    pub fn load_from(path: &Path) -> Result<Config, ConfigError> {
+        let (config, issues) = Config::check(&fs::read_to_string(path)?)?;
+        if !issues.is_empty() {
+            return Err(ConfigError::Invalid {
+                path: path.to_path_buf(),
+                issues,
+            });
+        }
+        Ok(config)
+    }
+
+    /// Parses `content` as `devhealth.toml` and returns the configuration
+    /// with its unknown keys and the problems [`Config::validate`] finds
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::TomlParse`] if the content is not valid TOML or
+    /// a value has the wrong type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::config::{Config, ConfigProblem};
+    ///
+    /// let (config, issues) = Config::check("[scan]\nhistroy = true\n").unwrap();
+    /// assert!(!config.scan.history);
+    /// assert_eq!(issues[0].key, "scan.histroy");
+    /// assert_eq!(issues[0].problem, ConfigProblem::UnknownKey);
+    /// ```
+    pub fn check(content: &str) -> Result<(Config, Vec<ConfigIssue>), ConfigError> {
+        let mut issues = Vec::new();
+        let config: Config =
+            serde_ignored::deserialize(toml::Deserializer::new(content), |path| {
+                issues.push(ConfigIssue {
+                    key: dotted_key(&path),
+                    problem: ConfigProblem::UnknownKey,
+                })
+            })?;
+        issues.extend(config.validate());
+        Ok((config, issues))
+    }
+
+    /// Returns the values that parse but cannot work: path patterns that
+    /// never match (see [`dh_fs::check_glob`]), thresholds of zero days or
+    /// seconds, an incomplete digest schedule and unknown theme roles or
+    /// colors
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        let mut globs = |key: String, patterns: &[String]| {
+            for (index, pattern) in patterns.iter().enumerate() {
+                if let Err(reason) = dh_fs::check_glob(pattern) {
+                    issues.push(ConfigIssue {
+                        key: format!("{}[{}]", key, index),
+                        problem: ConfigProblem::InvalidGlob {
+                            pattern: pattern.clone(),
+                            reason,
+                        },
+                    });
+                }
+            }
+        };
+        for (tag, patterns) in &self.tags {
+            globs(format!("tags.{}", tag), patterns);
+        }
+        for (index, rule) in self.severity.overrides.iter().enumerate() {
+            globs(format!("severity.overrides[{}].paths", index), &rule.paths);
+        }
+        for (index, rule) in self.checks.overrides.iter().enumerate() {
+            globs(format!("checks.overrides[{}].paths", index), &rule.paths);
+        }
+
+        let thresholds = [
+            ("scan.stale_branch_days", self.scan.stale_branch_days),
+            ("scan.stale_repo_months", self.scan.stale_repo_months),
+            (
+                "severity.stale_uncommitted_days",
+                self.severity.stale_uncommitted_days,
+            ),
+            ("deep.timeout_secs", self.deep.timeout_secs),
+            ("digest.port", self.digest.port.map_or(1, u64::from)),
+        ];
+        for (key, value) in thresholds {
+            if value < 1 {
+                issues.push(ConfigIssue {
+                    key: key.to_string(),
+                    problem: ConfigProblem::InvalidThreshold { value, minimum: 1 },
+                });
+            }
+        }
+        if self.digest.schedule.split_whitespace().count() != 5 {
+            issues.push(ConfigIssue {
+                key: "digest.schedule".to_string(),
+                problem: ConfigProblem::InvalidSchedule(self.digest.schedule.clone()),
+            });
+        }
+        for (role, color) in &self.theme.colors {
+            let theme = ThemeConfig {
+                palette: self.theme.palette,
+                colors: BTreeMap::from([(role.clone(), color.clone())]),
+            };
+            if let Err(e) = Theme::from_config(&theme) {
+                issues.push(ConfigIssue {
+                    key: format!("theme.{}", role),
+                    problem: ConfigProblem::InvalidTheme(e.to_string()),
+                });
+            }
+        }
+        issues
+    }
+
+    /// Returns the configuration as `devhealth.toml` content, with tokens
+    /// redacted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value cannot be written as TOML.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+}
+
+/// Formats the path of an ignored key as `section.list[0].key`
+fn dotted_key(path: &serde_ignored::Path) -> String {
+    match path {
+        serde_ignored::Path::Root => String::new(),
+        serde_ignored::Path::Seq { parent, index } => format!("{}[{}]", dotted_key(parent), index),
+        serde_ignored::Path::Map { parent, key } => match dotted_key(parent) {
+            parent if parent.is_empty() => key.clone(),
+            parent => format!("{}.{}", parent, key),
+        },
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => dotted_key(parent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::findings::{Check, Severity};
+    use crate::utils::theme::Palette;
+    use tempfile::TempDir;
+
+    #[test]
+    fn empty_file_yields_default_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(&path, "").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert!(config.tags.is_empty());
+        assert!(!config.accessible);
+        assert_eq!(config.lang, None);
+        assert_eq!(config.scan.default, vec![Scanner::Git, Scanner::Deps]);
+        assert_eq!(config.deep, DeepConfig::default());
+    }
+
+    #[test]
+    fn parses_default_scanners() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(&path, "[scan]\ndefault = [\"deps\", \"audit\"]\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.scan.default, vec![Scanner::Deps, Scanner::Audit]);
+        assert!(!config.scan.history, "Size history is opt-in");
+        assert_eq!(config.scan.history_keep_runs, history::DEFAULT_KEEP_RUNS);
+
+        fs::write(
+            &path,
+            "[scan]\nhistory_keep_runs = 5\nhistory_keep_days = 0\n",
+        )
+        .unwrap();
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(
+            (config.scan.history_keep_runs, config.scan.history_keep_days),
+            (5, 0)
+        );
+        assert_eq!(
+            config.scan.git_options().stale_branch_days,
+            git::STALE_BRANCH_DAYS
+        );
+
+        fs::write(
+            &path,
+            "[scan]\nstale_branch_days = 30\nstale_repo_months = 12\n",
+        )
+        .unwrap();
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(
+            config.scan.git_options(),
+            GitScanOptions {
+                stale_branch_days: 30,
+                stale_repo_months: 12
+            }
+        );
+
+        fs::write(&path, "[scan]\ndefault = [\"lint\"]\n").unwrap();
+        assert!(matches!(
+            Config::load_from(&path),
+            Err(ConfigError::TomlParse(_))
+        ));
+    }
+
+    #[test]
+    fn parses_deep_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(&path, "[deep]\ntests = true\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(
+            config.deep,
+            DeepConfig {
+                timeout_secs: 300,
+                tests: true
+            }
+        );
+    }
+
+    #[test]
+    fn parses_audit_ignores() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            "[audit]
+ignore_dev = true
+
+[[audit.ignore]]
+advisory = \"RUSTSEC-2023-0071\"
+until = 2026-06-30
+
+[[audit.ignore]]
+package = \"lodash\"
+until = \"2026-07-01\"
+reason = \"internal tool\"
+",
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert!(config.audit.ignore_dev);
+        assert_eq!(config.audit.ignore.len(), 2);
+        assert_eq!(
+            config.audit.ignore[0].until,
+            Some(crate::acks::parse_date("2026-06-30").unwrap())
+        );
+        assert_eq!(config.audit.ignore[1].package.as_deref(), Some("lodash"));
+
+        for invalid in [
+            "[[audit.ignore]]\nreason = \"too broad\"\n",
+            "[[audit.ignore]]\npackage = \"x\"\nuntil = \"soon\"\n",
+        ] {
+            fs::write(&path, invalid).unwrap();
+            assert!(
+                matches!(Config::load_from(&path), Err(ConfigError::TomlParse(_))),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn parses_forge_section_without_leaking_tokens() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            "[forge]\ngithub_token = \"ghp_secret\"\ngitlab_hosts = [\"git.example.com\"]\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.forge.github_token.as_deref(), Some("ghp_secret"));
+        assert_eq!(config.forge.gitlab_token, None);
+        assert_eq!(config.forge.gitlab_hosts, vec!["git.example.com"]);
+        assert!(!format!("{:?}", config).contains("ghp_secret"));
+    }
+
+    #[test]
+    fn parses_policy_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            "[policy]
+url = \"https://platform.example.com/policy.toml\"
+public_key = \"ssh-ed25519 AAAA\"
+",
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(
+            config.policy.url.as_deref(),
+            Some("https://platform.example.com/policy.toml")
+        );
+        assert_eq!(
+            config.policy.public_key.as_deref(),
+            Some("ssh-ed25519 AAAA")
+        );
+        assert_eq!(config.policy.signature_url, None);
+        assert_eq!(config.policy.refresh_hours, 24);
+    }
+
+    #[test]
+    fn parses_severity_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            r#"
+[severity]
+fail_level = "warn"
+checks = { unpushed-commits = "error" }
+
+[[severity.overrides]]
+tags = ["personal"]
+checks = { uncommitted-changes = "info" }
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.severity.fail_level, Some(Severity::Warn));
+        assert_eq!(
+            config.severity.checks[&Check::UnpushedCommits],
+            Severity::Error
+        );
+        assert_eq!(config.severity.overrides[0].tags, vec!["personal"]);
+        assert_eq!(
+            config.severity.overrides[0].checks[&Check::UncommittedChanges],
+            Severity::Info
+        );
+    }
+
+    #[test]
+    fn parses_checks_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            r#"
+[checks]
+disabled = ["stale-changelog", "git002"]
+
+[[checks.overrides]]
+paths = ["~/personal/**"]
+enabled = ["GIT002"]
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(
+            config.checks.disabled,
+            vec![
+                Check::StaleChangelog,
+                Check::from_rule_id("GIT002").unwrap()
+            ]
+        );
+        assert_eq!(config.checks.overrides[0].paths, vec!["~/personal/**"]);
+        assert_eq!(
+            config.checks.overrides[0].enabled,
+            vec![Check::from_rule_id("GIT002").unwrap()]
+        );
+
+        fs::write(&path, "[checks]\ndisabled = [\"no-such-check\"]\n").unwrap();
+        assert!(matches!(
+            Config::load_from(&path),
+            Err(ConfigError::TomlParse(_))
+        ));
+    }
+
+    #[test]
+    fn parses_tag_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            r#"
+[tags]
+work = ["~/work/**", "/srv/api"]
+personal = ["~/personal/*"]
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.tags["work"], vec!["~/work/**", "/srv/api"]);
+        assert_eq!(config.tags["personal"], vec!["~/personal/*"]);
+    }
+
+    #[test]
+    fn parses_theme_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            r#"
+[theme]
+palette = "light"
+error = "bright magenta"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.theme.palette, Palette::Light);
+        assert_eq!(config.theme.colors["error"], "bright magenta");
+    }
+
+    #[test]
+    fn parses_update_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(&path, "[update]\ncheck = false\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert!(!config.update.check);
+        assert_eq!(config.update.public_key, None);
+        assert!(Config::default().update.check);
+    }
+
+    #[test]
+    fn parses_digest_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            "[digest]\nserver = \"smtp.example.com\"\nsecurity = \"tls\"\nto = [\"me@example.com\"]\n",
+        )
+        .unwrap();
+
+        let digest = Config::load_from(&path).unwrap().digest;
+        assert_eq!(digest.server.as_deref(), Some("smtp.example.com"));
+        assert_eq!(digest.security, Security::Tls);
+        assert_eq!(digest.to, vec!["me@example.com".to_string()]);
+        assert_eq!(digest.password_env, "DEVHEALTH_SMTP_PASSWORD");
+        assert_eq!(digest.schedule, "0 8 * * 1");
+    }
+
+    #[test]
+    fn parses_notify_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(&path, "[notify]\ndesktop = true\nlevel = \"error\"\n").unwrap();
+
+        let notify = Config::load_from(&path).unwrap().notify;
+        assert!(notify.desktop);
+        assert_eq!(notify.level, Severity::Error);
+        assert_eq!(Config::default().notify.level, Severity::Critical);
+    }
+
+    #[test]
+    fn lists_unknown_keys_and_invalid_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(
+            &path,
+            r#"
+[scan]
+histroy = true
+stale_branch_days = 0
+
+[tags]
+work = ["~/work/**", "work/*"]
+
+[[checks.overrides]]
+paths = ["/src/{api,cli}"]
+disabeld = ["stale-changelog"]
+"#,
+        )
+        .unwrap();
+
+        let Err(ConfigError::Invalid { issues, .. }) = Config::load_from(&path) else {
+            panic!("Expected an invalid config");
+        };
+        let keys: Vec<&str> = issues.iter().map(|issue| issue.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "scan.histroy",
+                "checks.overrides[0].disabeld",
+                "tags.work[1]",
+                "checks.overrides[0].paths[0]",
+                "scan.stale_branch_days"
+            ]
+        );
+        assert_eq!(
+            issues[4].problem,
+            ConfigProblem::InvalidThreshold {
+                value: 0,
+                minimum: 1
+            }
+        );
+        assert!(
+            issues[2]
+                .to_string()
+                .starts_with("tags.work[1]: invalid pattern \"work/*\""),
+            "{}",
+            issues[2]
+        );
+
+        let (_, issues) =
+            Config::check("[digest]\nport = 0\nschedule = \"daily\"\n[theme]\nsparkle = \"red\"\n")
+                .unwrap();
+        let keys: Vec<&str> = issues.iter().map(|issue| issue.key.as_str()).collect();
+        assert_eq!(keys, ["digest.port", "digest.schedule", "theme.sparkle"]);
+    }
+
+    #[test]
+    fn writes_the_configuration_back_without_tokens() {
+        let content = r#"
+lang = "de"
+
+[forge]
+github_token = "ghp_secret"
+
+[[audit.ignore]]
+package = "lodash"
+until = 2026-07-01
+
+[severity]
+checks = { stale-changelog = "info" }
+
+[theme]
+error = "bright magenta"
+"#;
+        let (config, issues) = Config::check(content).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+        let written = config.to_toml().unwrap();
+        assert!(!written.contains("ghp_secret"));
+        assert!(
+            written.contains("github_token = \"<redacted>\""),
+            "{}",
+            written
+        );
+
+        let (reread, issues) = Config::check(&written).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert_eq!(reread.lang, Some(Lang::De));
+        assert_eq!(reread.audit, config.audit);
+        assert_eq!(reread.severity, config.severity);
+        assert_eq!(reread.theme.colors, config.theme.colors);
+        assert_eq!(reread.scan, ScanConfig::default());
+    }
+
+    #[test]
+    fn reports_invalid_toml() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE);
+        fs::write(&path, "[tags\nwork = ").unwrap();
+
+        assert!(matches!(
+            Config::load_from(&path),
+            Err(ConfigError::TomlParse(_))
+        ));
+    }
+}
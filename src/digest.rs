@@ -0,0 +1,615 @@
+//! Email digest of the development environment's health
+//!
+//! `devhealth digest send` runs a quick check and mails the result: a
+//! summary, what changed since the previous run and the repositories that
+//! need attention. The message is HTML with a Markdown alternative for
+//! plain-text mail clients. `devhealth digest schedule` adds a cron entry
+//! sending it on the schedule in `[digest]`, every Monday at 8:00 by default.
+//!
+//! The SMTP password never goes into `devhealth.toml`. It is read from the
+//! system keyring, where `devhealth auth login smtp` stores it, then from
+//! the environment variable named by `password_env`.
+
+use crate::acks;
+use crate::auth::{self, Provider};
+use crate::config::DigestConfig;
+use crate::findings::{Finding, Severity};
+use crate::report::ScanResults;
+use crate::setup::shell_quote;
+use crate::tr;
+use crate::utils::fs::expand_home;
+use crate::utils::smtp::{self, Envelope, Server, SmtpError};
+use base64::Engine;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Name of the network check sending the digest, skipped with `--offline`
+pub const DIGEST_DELIVERY: &str = "email digest";
+
+/// First line of the crontab entry written by `digest schedule`
+pub const SCHEDULE_MARKER: &str = "# devhealth digest: scheduled email digest";
+
+/// Findings listed before the rest are only counted
+const MAX_FINDINGS: usize = 50;
+
+/// Errors that can occur while sending the digest
+#[derive(Error, Debug)]
+pub enum DigestError {
+    #[error("The [digest] section of devhealth.toml needs {0}")]
+    NotConfigured(&'static str),
+    #[error("No SMTP password for {user}: run `devhealth auth login smtp` or set {env}")]
+    NoPassword { user: String, env: String },
+    #[error("Invalid email address: {0}")]
+    InvalidAddress(String),
+    #[error("Invalid digest schedule {0:?}: expected five cron fields such as \"0 8 * * 1\"")]
+    InvalidSchedule(String),
+    #[error("Failed to send the digest: {0}")]
+    Smtp(#[from] SmtpError),
+}
+
+/// A rendered digest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digest {
+    /// Subject line
+    pub subject: String,
+    /// Plain-text body in Markdown
+    pub markdown: String,
+    /// HTML body
+    pub html: String,
+}
+
+/// One element of the digest, rendered as Markdown and as HTML
+enum Block {
+    Title(String),
+    Heading(String),
+    Path(String),
+    Text(String),
+    Item { code: Option<String>, text: String },
+}
+
+impl Digest {
+    /// Renders `results` as a digest sent at `now`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::digest::Digest;
+    /// use devhealth::report::ScanResults;
+    ///
+    /// let digest = Digest::render(&ScanResults::default(), 0);
+    /// assert!(digest.subject.contains("1970-01-01"));
+    /// assert!(digest.html.starts_with("<!DOCTYPE html>"));
+    /// ```
+    pub fn render(results: &ScanResults, now: u64) -> Digest {
+        let date = acks::format_date(now);
+        let blocks = blocks(results, &date);
+        Digest {
+            subject: tr!(
+                "digest-subject",
+                date = date,
+                count = results.findings.len()
+            ),
+            markdown: markdown(&blocks),
+            html: html(&blocks),
+        }
+    }
+
+    /// Builds the MIME message from `from` to `to`, with the Markdown and
+    /// HTML bodies as alternatives
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DigestError::InvalidAddress`] if an address holds a line
+    /// break, which would inject headers.
+    pub fn message(&self, from: &str, to: &[String], now: u64) -> Result<String, DigestError> {
+        for address in std::iter::once(from).chain(to.iter().map(String::as_str)) {
+            if address.contains(['\r', '\n']) {
+                return Err(DigestError::InvalidAddress(
+                    address.escape_debug().to_string(),
+                ));
+            }
+        }
+        let boundary = format!("devhealth-digest-{}", now);
+        let mut message = String::new();
+        message.push_str(&format!("From: {}\r\n", from));
+        message.push_str(&format!("To: {}\r\n", to.join(", ")));
+        message.push_str(&format!("Subject: {}\r\n", encode_header(&self.subject)));
+        message.push_str(&format!("Date: {}\r\n", rfc2822_date(now)));
+        message.push_str(&format!(
+            "Message-ID: <{}.{}@devhealth>\r\n",
+            now,
+            std::process::id()
+        ));
+        message.push_str("MIME-Version: 1.0\r\n");
+        // Base64 never contains `-`, so the boundary cannot occur in a part
+        message.push_str(&format!(
+            "Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
+            boundary
+        ));
+        for (kind, body) in [("text/plain", &self.markdown), ("text/html", &self.html)] {
+            message.push_str(&format!("--{}\r\n", boundary));
+            message.push_str(&format!("Content-Type: {}; charset=utf-8\r\n", kind));
+            message.push_str("Content-Transfer-Encoding: base64\r\n\r\n");
+            let encoded = base64::engine::general_purpose::STANDARD.encode(body);
+            for line in encoded.as_bytes().chunks(76) {
+                message.push_str(&String::from_utf8_lossy(line));
+                message.push_str("\r\n");
+            }
+        }
+        message.push_str(&format!("--{}--\r\n", boundary));
+        Ok(message)
+    }
+}
+
+/// Lays out the digest of `results`
+fn blocks(results: &ScanResults, date: &str) -> Vec<Block> {
+    let mut blocks = vec![Block::Title(tr!("digest-title", date = date))];
+    if let Some(run) = &results.run {
+        let paths: Vec<String> = run
+            .paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        let scope = tr!("digest-scope", paths = paths.join(", "));
+        blocks.push(Block::Text(match &run.hostname {
+            Some(host) => format!("{} ({})", scope, host),
+            None => scope,
+        }));
+    }
+
+    blocks.push(Block::Heading(tr!("digest-summary-title")));
+    let summary = results.summary();
+    if let Some(repos) = &results.git {
+        blocks.push(item(tr!("digest-repositories", count = repos.len())));
+    }
+    if let Some(dirty) = summary.dirty {
+        blocks.push(item(tr!("digest-dirty", count = dirty)));
+    }
+    if let Some(unpushed) = summary.unpushed {
+        blocks.push(item(tr!("digest-unpushed", count = unpushed)));
+    }
+    if let Some(vulnerabilities) = summary.vulnerabilities {
+        blocks.push(item(tr!("digest-vulnerabilities", count = vulnerabilities)));
+    }
+    for severity in Severity::ALL {
+        if let Some(&count) = summary.findings.get(&severity) {
+            blocks.push(item(tr!(
+                "digest-findings",
+                count = count,
+                severity = severity.label()
+            )));
+        }
+    }
+
+    if let Some(changes) = &results.changes {
+        blocks.push(Block::Heading(tr!(
+            "digest-changes-title",
+            date = acks::format_date(changes.since)
+        )));
+        if changes.deltas.is_empty() {
+            blocks.push(Block::Text(tr!("digest-no-changes")));
+        }
+        blocks.extend(changes.deltas.iter().map(|delta| item(delta.describe())));
+    }
+
+    blocks.push(Block::Heading(tr!(
+        "digest-attention-title",
+        count = results.findings.len()
+    )));
+    if results.findings.is_empty() {
+        blocks.push(Block::Text(tr!("digest-all-clear")));
+    }
+    // Findings come most severe first; each path is listed where its most
+    // severe finding would be
+    let shown = &results.findings[..results.findings.len().min(MAX_FINDINGS)];
+    let mut paths: Vec<&Path> = Vec::new();
+    for finding in shown {
+        if !paths.contains(&finding.path.as_path()) {
+            paths.push(&finding.path);
+        }
+    }
+    for path in paths {
+        blocks.push(Block::Path(path.display().to_string()));
+        blocks.extend(
+            shown
+                .iter()
+                .filter(|finding| finding.path == path)
+                .map(finding_item),
+        );
+    }
+    if results.findings.len() > shown.len() {
+        blocks.push(Block::Text(tr!(
+            "digest-more",
+            count = results.findings.len() - shown.len()
+        )));
+    }
+
+    blocks.push(Block::Text(tr!(
+        "digest-footer",
+        version = env!("CARGO_PKG_VERSION")
+    )));
+    blocks
+}
+
+fn item(text: String) -> Block {
+    Block::Item { code: None, text }
+}
+
+fn finding_item(finding: &Finding) -> Block {
+    let mut text = format!("{}: {}", finding.severity.label(), finding.check.describe());
+    if let Some(detail) = &finding.detail {
+        text.push_str(&format!(" ({})", detail));
+    }
+    Block::Item {
+        code: Some(finding.rule.clone()),
+        text,
+    }
+}
+
+/// Renders the blocks as Markdown
+fn markdown(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for (index, block) in blocks.iter().enumerate() {
+        match block {
+            Block::Title(text) => out.push_str(&format!("# {}\n\n", text)),
+            Block::Heading(text) => out.push_str(&format!("## {}\n\n", text)),
+            Block::Path(path) => out.push_str(&format!("### `{}`\n\n", path)),
+            Block::Text(text) => out.push_str(&format!("{}\n\n", text)),
+            Block::Item { code, text } => {
+                match code {
+                    Some(code) => out.push_str(&format!("- `{}` {}\n", code, text)),
+                    None => out.push_str(&format!("- {}\n", text)),
+                }
+                if !matches!(blocks.get(index + 1), Some(Block::Item { .. })) {
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+/// Renders the blocks as an HTML document with inline styles, which mail
+/// clients keep more reliably than style sheets
+fn html(blocks: &[Block]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"></head>\n\
+         <body style=\"font-family: -apple-system, 'Segoe UI', sans-serif; line-height: 1.5\">\n",
+    );
+    let mut in_list = false;
+    for block in blocks {
+        let is_item = matches!(block, Block::Item { .. });
+        if is_item && !in_list {
+            out.push_str("<ul>\n");
+        } else if !is_item && in_list {
+            out.push_str("</ul>\n");
+        }
+        in_list = is_item;
+        match block {
+            Block::Title(text) => out.push_str(&format!("<h1>{}</h1>\n", escape(text))),
+            Block::Heading(text) => out.push_str(&format!("<h2>{}</h2>\n", escape(text))),
+            Block::Path(path) => out.push_str(&format!("<h3><code>{}</code></h3>\n", escape(path))),
+            Block::Text(text) => out.push_str(&format!("<p>{}</p>\n", escape(text))),
+            Block::Item { code, text } => match code {
+                Some(code) => out.push_str(&format!(
+                    "<li><code>{}</code> {}</li>\n",
+                    escape(code),
+                    escape(text)
+                )),
+                None => out.push_str(&format!("<li>{}</li>\n", escape(text))),
+            },
+        }
+    }
+    if in_list {
+        out.push_str("</ul>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Escapes text for HTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Encodes a header value as an RFC 2047 encoded word if it is not ASCII
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!(
+            "=?UTF-8?B?{}?=",
+            base64::engine::general_purpose::STANDARD.encode(value)
+        )
+    }
+}
+
+/// Formats a Unix timestamp as an RFC 2822 date in UTC
+fn rfc2822_date(timestamp: u64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let date = acks::format_date(timestamp);
+    let mut parts = date
+        .split('-')
+        .map(|part| part.parse::<usize>().unwrap_or(1));
+    let (year, month, day) = (
+        parts.next().unwrap_or(1970),
+        parts.next().unwrap_or(1),
+        parts.next().unwrap_or(1),
+    );
+    let seconds = timestamp % 86_400;
+    format!(
+        "{}, {} {} {} {:02}:{:02}:{:02} +0000",
+        WEEKDAYS[(timestamp / 86_400 % 7) as usize],
+        day,
+        MONTHS[month.clamp(1, 12) - 1],
+        year,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Returns the bare address of a mailbox such as `DevHealth <me@example.com>`
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::digest::address;
+///
+/// assert_eq!(address("DevHealth <me@example.com>"), Some("me@example.com"));
+/// assert_eq!(address("me@example.com"), Some("me@example.com"));
+/// assert_eq!(address("not an address"), None);
+/// ```
+pub fn address(mailbox: &str) -> Option<&str> {
+    let mailbox = mailbox.trim();
+    let address = match (mailbox.rfind('<'), mailbox.ends_with('>')) {
+        (Some(start), true) => &mailbox[start + 1..mailbox.len() - 1],
+        _ => mailbox,
+    };
+    let valid = address.contains('@')
+        && !address.contains(|c: char| c.is_whitespace() || "<>\"".contains(c));
+    valid.then_some(address)
+}
+
+/// Returns the directory the digest checks: `path`, else the configured
+/// one, else the current directory
+pub fn root(path: Option<&Path>, config: &DigestConfig) -> PathBuf {
+    path.map(Path::to_path_buf)
+        .or_else(|| {
+            config
+                .path
+                .as_deref()
+                .map(|path| PathBuf::from(expand_home(path)))
+        })
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Checks that `[digest]` names a server, a sender and recipients,
+/// returning the server and sender
+///
+/// # Errors
+///
+/// Returns [`DigestError::NotConfigured`] naming the first missing setting.
+pub fn require_config(config: &DigestConfig) -> Result<(String, &str), DigestError> {
+    let host = config
+        .server
+        .clone()
+        .ok_or(DigestError::NotConfigured("a server"))?;
+    let from = config
+        .from
+        .as_deref()
+        .ok_or(DigestError::NotConfigured("a from address"))?;
+    if config.to.is_empty() {
+        return Err(DigestError::NotConfigured("at least one to address"));
+    }
+    Ok((host, from))
+}
+
+/// Sends `digest` as configured in `[digest]`
+///
+/// # Errors
+///
+/// Returns an error if the server, sender or recipients are missing or
+/// invalid, no password is found for the configured user, or the SMTP
+/// server cannot be reached or refuses the message.
+pub fn send(config: &DigestConfig, digest: &Digest, now: u64) -> Result<(), DigestError> {
+    let (host, from) = require_config(config)?;
+    let bare = |mailbox: &str| {
+        address(mailbox)
+            .map(str::to_string)
+            .ok_or_else(|| DigestError::InvalidAddress(mailbox.to_string()))
+    };
+    let envelope = Envelope {
+        from: bare(from)?,
+        to: config
+            .to
+            .iter()
+            .map(|mailbox| bare(mailbox))
+            .collect::<Result<_, _>>()?,
+        data: digest.message(from, &config.to, now)?,
+    };
+    let credentials = match &config.username {
+        Some(user) => {
+            let password = auth::resolve(Provider::Smtp, &[&config.password_env], None)
+                .ok_or_else(|| DigestError::NoPassword {
+                    user: user.clone(),
+                    env: config.password_env.clone(),
+                })?;
+            Some((user.clone(), password))
+        }
+        None => None,
+    };
+    let server = Server {
+        port: config.port.unwrap_or(config.security.default_port()),
+        host,
+        security: config.security,
+        credentials,
+    };
+    smtp::send(&server, &envelope)?;
+    Ok(())
+}
+
+/// Returns the crontab lines sending the digest of `path` on `schedule`
+///
+/// # Errors
+///
+/// Returns [`DigestError::InvalidSchedule`] unless `schedule` has the five
+/// fields of a cron schedule.
+pub fn schedule_entry(exe: &Path, path: &Path, schedule: &str) -> Result<String, DigestError> {
+    if schedule.split_whitespace().count() != 5 {
+        return Err(DigestError::InvalidSchedule(schedule.to_string()));
+    }
+    Ok(format!(
+        "{}\n{} {} digest send --path {} >/dev/null 2>&1\n",
+        SCHEDULE_MARKER,
+        schedule.split_whitespace().collect::<Vec<_>>().join(" "),
+        shell_quote(&exe.to_string_lossy()),
+        shell_quote(&path.to_string_lossy())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::findings::Check;
+    use crate::report::{Delta, Measure, ReportChanges};
+
+    fn results() -> ScanResults {
+        ScanResults {
+            git: Some(Vec::new()),
+            changes: Some(ReportChanges {
+                since: 86_400,
+                deltas: vec![Delta {
+                    measure: Measure::Dirty,
+                    severity: None,
+                    before: 0,
+                    after: 1,
+                }],
+            }),
+            findings: vec![
+                Finding {
+                    rule: "GIT002".to_string(),
+                    check: Check::UncommittedChanges,
+                    severity: Severity::Warn,
+                    path: PathBuf::from("/src/a<b>"),
+                    detail: None,
+                },
+                Finding {
+                    rule: "GIT001".to_string(),
+                    check: Check::UnpushedCommits,
+                    severity: Severity::Info,
+                    path: PathBuf::from("/src/a<b>"),
+                    detail: Some("main".to_string()),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renders_changes_and_findings_by_path() {
+        let digest = Digest::render(&results(), 7 * 86_400);
+
+        assert!(digest.subject.contains("1970-01-08"));
+        assert!(digest.markdown.starts_with("# "));
+        assert!(digest.markdown.contains("1970-01-02"));
+        assert!(digest.markdown.contains("+1"));
+        assert_eq!(digest.markdown.matches("### `/src/a<b>`").count(), 1);
+        assert!(digest.markdown.contains("- `GIT001` "));
+        assert!(digest.markdown.contains("(main)"));
+
+        assert!(digest
+            .html
+            .contains("<h3><code>/src/a&lt;b&gt;</code></h3>"));
+        assert_eq!(
+            digest.html.matches("<ul>").count(),
+            digest.html.matches("</ul>").count()
+        );
+        assert!(!digest.html.contains("a<b>"));
+    }
+
+    #[test]
+    fn message_has_both_alternatives() {
+        let digest = Digest::render(&results(), 0);
+        let to = vec!["me@example.com".to_string()];
+        let message = digest
+            .message("DevHealth <dh@example.com>", &to, 0)
+            .unwrap();
+
+        assert!(message.contains("From: DevHealth <dh@example.com>\r\n"));
+        assert!(message.contains("To: me@example.com\r\n"));
+        assert!(message.contains("Date: Thu, 1 Jan 1970 00:00:00 +0000\r\n"));
+        assert!(message.contains("Content-Type: text/plain; charset=utf-8"));
+        assert!(message.contains("Content-Type: text/html; charset=utf-8"));
+        assert!(message.ends_with("--devhealth-digest-0--\r\n"));
+        assert!(message.lines().all(|line| line.len() <= 998));
+
+        let bad = digest.message("dh@example.com\r\nBcc: x@example.com", &to, 0);
+        assert!(matches!(bad, Err(DigestError::InvalidAddress(_))));
+    }
+
+    #[test]
+    fn encodes_non_ascii_subjects() {
+        assert_eq!(encode_header("Digest"), "Digest");
+        assert_eq!(encode_header("Übersicht"), "=?UTF-8?B?w5xiZXJzaWNodA==?=");
+    }
+
+    #[test]
+    fn formats_dates_for_headers() {
+        assert_eq!(
+            rfc2822_date(1_700_000_000),
+            "Tue, 14 Nov 2023 22:13:20 +0000"
+        );
+    }
+
+    #[test]
+    fn requires_server_and_recipients() {
+        let digest = Digest::render(&ScanResults::default(), 0);
+        let config = DigestConfig::default();
+        assert!(matches!(
+            send(&config, &digest, 0),
+            Err(DigestError::NotConfigured(_))
+        ));
+
+        let config = DigestConfig {
+            server: Some("smtp.example.com".to_string()),
+            from: Some("dh@example.com".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            send(&config, &digest, 0),
+            Err(DigestError::NotConfigured(_))
+        ));
+    }
+
+    #[test]
+    fn schedule_entry_sends_the_digest_of_a_path() {
+        let entry = schedule_entry(
+            Path::new("/usr/bin/devhealth"),
+            Path::new("/home/me/src"),
+            "0  8 * * 1",
+        )
+        .unwrap();
+        let mut lines = entry.lines();
+        assert_eq!(lines.next(), Some(SCHEDULE_MARKER));
+        assert_eq!(
+            lines.next(),
+            Some(
+                "0 8 * * 1 '/usr/bin/devhealth' digest send --path '/home/me/src' >/dev/null 2>&1"
+            )
+        );
+
+        assert!(matches!(
+            schedule_entry(Path::new("devhealth"), Path::new("."), "weekly"),
+            Err(DigestError::InvalidSchedule(_))
+        ));
+    }
+}
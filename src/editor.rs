@@ -0,0 +1,319 @@
+//! Editor integration files
+//!
+//! `devhealth init vscode` sets up a workspace to run DevHealth from VS Code:
+//!
+//! - `.vscode/tasks.json`: `devhealth: check` and `devhealth: scan` tasks
+//!   printing `--format diagnostics`, with a problem matcher that turns each
+//!   line into an entry of the Problems panel
+//! - `.vscode/settings.json`: shows the problem under the cursor in the
+//!   status bar
+//! - `.vscode/extensions.json`: recommends a TOML extension for editing
+//!   `devhealth.toml`
+//!
+//! Existing files are merged, never overwritten: tasks, settings and
+//! recommendations already present are kept as they are, and only missing
+//! entries are added, so running the command again changes nothing. Files
+//! that are not plain JSON (VS Code also accepts comments) are left alone.
+
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Directory holding VS Code workspace files
+const VSCODE_DIR: &str = ".vscode";
+
+/// Owner and source of the diagnostics the problem matcher reports
+const MATCHER_OWNER: &str = "devhealth";
+
+/// Adds missing entries to a parsed file, returning their names
+type Merge = fn(&mut Map<String, Value>) -> Vec<String>;
+
+/// Errors that can occur while writing editor files
+#[derive(Error, Debug)]
+pub enum EditorError {
+    #[error("Failed to write {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to serialize {}: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// What happened to one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileOutcome {
+    /// The file did not exist and was written
+    Created,
+    /// Missing entries were added; the names of the added entries
+    Updated(Vec<String>),
+    /// Everything was already in place
+    Unchanged,
+    /// The file could not be parsed as JSON and was not touched
+    Skipped,
+}
+
+/// The outcome for one file written by [`init_vscode`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrittenFile {
+    /// Path of the file
+    pub path: PathBuf,
+    /// What happened to it
+    pub outcome: FileOutcome,
+}
+
+/// Returns the problem matcher for `--format diagnostics` output
+///
+/// Matches `file:line:column: level: RULE message` lines with absolute file
+/// paths, reporting the rule ID as the diagnostic code.
+pub fn problem_matcher() -> Value {
+    json!({
+        "owner": MATCHER_OWNER,
+        "source": MATCHER_OWNER,
+        "fileLocation": "absolute",
+        "pattern": {
+            "regexp": r"^(.+):(\d+):(\d+): (error|warning|info): (\S+) (.+)$",
+            "file": 1,
+            "line": 2,
+            "column": 3,
+            "severity": 4,
+            "code": 5,
+            "message": 6
+        }
+    })
+}
+
+/// Returns the tasks `init vscode` adds, by label
+fn tasks() -> Vec<Value> {
+    let task = |label: &str, args: &[&str]| {
+        let args: Vec<&str> = args
+            .iter()
+            .copied()
+            .chain(["--format", "diagnostics", "--path", "${workspaceFolder}"])
+            .collect();
+        json!({
+            "label": label,
+            "type": "process",
+            "command": "devhealth",
+            "args": args,
+            "problemMatcher": problem_matcher(),
+            "presentation": { "reveal": "silent" }
+        })
+    };
+    vec![
+        task("devhealth: check", &["check"]),
+        task("devhealth: scan", &["scan", "--all"]),
+    ]
+}
+
+/// Returns the settings `init vscode` recommends
+fn settings() -> Map<String, Value> {
+    let mut settings = Map::new();
+    settings.insert(
+        "problems.showCurrentInStatus".to_string(),
+        Value::Bool(true),
+    );
+    settings
+}
+
+/// Returns the extensions `init vscode` recommends
+fn recommendations() -> Vec<&'static str> {
+    vec!["tamasfe.even-better-toml"]
+}
+
+/// Writes the VS Code task, settings and recommendation files
+///
+/// # Arguments
+///
+/// * `workspace` - The workspace folder to set up
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be written.
+pub fn init_vscode(workspace: &Path) -> Result<Vec<WrittenFile>, EditorError> {
+    let dir = workspace.join(VSCODE_DIR);
+    let files: [(&str, Merge); 3] = [
+        ("tasks.json", merge_tasks),
+        ("settings.json", merge_settings),
+        ("extensions.json", merge_recommendations),
+    ];
+
+    let mut written = Vec::new();
+    for (name, merge) in files {
+        let path = dir.join(name);
+        let outcome = update_json(&path, merge)?;
+        written.push(WrittenFile { path, outcome });
+    }
+    Ok(written)
+}
+
+/// Applies `merge` to the JSON object in `path` and writes it back if it changed
+fn update_json(path: &Path, merge: Merge) -> Result<FileOutcome, EditorError> {
+    let io = |source| EditorError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let (mut object, existed) = match fs::read_to_string(path) {
+        Ok(content) => match serde_json::from_str::<Value>(&content) {
+            Ok(Value::Object(object)) => (object, true),
+            _ => return Ok(FileOutcome::Skipped),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => (Map::new(), false),
+        Err(e) => return Err(io(e)),
+    };
+
+    let added = merge(&mut object);
+    if existed && added.is_empty() {
+        return Ok(FileOutcome::Unchanged);
+    }
+    let content = serde_json::to_string_pretty(&Value::Object(object)).map_err(|source| {
+        EditorError::Json {
+            path: path.to_path_buf(),
+            source,
+        }
+    })?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io)?;
+    }
+    fs::write(path, content + "\n").map_err(io)?;
+    Ok(if existed {
+        FileOutcome::Updated(added)
+    } else {
+        FileOutcome::Created
+    })
+}
+
+/// Adds the DevHealth tasks whose labels are not taken yet
+fn merge_tasks(object: &mut Map<String, Value>) -> Vec<String> {
+    object
+        .entry("version")
+        .or_insert_with(|| Value::String("2.0.0".to_string()));
+    let Some(existing) = object
+        .entry("tasks")
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+    else {
+        return Vec::new();
+    };
+
+    let mut added = Vec::new();
+    for task in tasks() {
+        let label = task["label"].clone();
+        if !existing.iter().any(|other| other["label"] == label) {
+            added.push(label.as_str().unwrap_or_default().to_string());
+            existing.push(task);
+        }
+    }
+    added
+}
+
+/// Adds the recommended settings the workspace does not set yet
+fn merge_settings(object: &mut Map<String, Value>) -> Vec<String> {
+    let mut added = Vec::new();
+    for (key, value) in settings() {
+        if !object.contains_key(&key) {
+            added.push(key.clone());
+            object.insert(key, value);
+        }
+    }
+    added
+}
+
+/// Adds the recommended extensions that are not listed yet
+fn merge_recommendations(object: &mut Map<String, Value>) -> Vec<String> {
+    let Some(listed) = object
+        .entry("recommendations")
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+    else {
+        return Vec::new();
+    };
+
+    let mut added = Vec::new();
+    for extension in recommendations() {
+        if !listed.iter().any(|other| other == extension) {
+            added.push(extension.to_string());
+            listed.push(Value::String(extension.to_string()));
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn outcomes(written: &[WrittenFile]) -> Vec<FileOutcome> {
+        written.iter().map(|file| file.outcome.clone()).collect()
+    }
+
+    #[test]
+    fn creates_files_once() {
+        let dir = TempDir::new().unwrap();
+
+        let first = init_vscode(dir.path()).unwrap();
+        assert_eq!(outcomes(&first), vec![FileOutcome::Created; 3]);
+        let tasks: Value = serde_json::from_str(
+            &fs::read_to_string(dir.path().join(".vscode/tasks.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(tasks["tasks"][0]["label"], "devhealth: check");
+        assert_eq!(tasks["tasks"][0]["problemMatcher"]["owner"], "devhealth");
+
+        let second = init_vscode(dir.path()).unwrap();
+        assert_eq!(outcomes(&second), vec![FileOutcome::Unchanged; 3]);
+    }
+
+    #[test]
+    fn keeps_existing_content() {
+        let dir = TempDir::new().unwrap();
+        let vscode = dir.path().join(".vscode");
+        fs::create_dir(&vscode).unwrap();
+        fs::write(
+            vscode.join("tasks.json"),
+            r#"{"version": "2.0.0", "tasks": [{"label": "build", "command": "make"}, {"label": "devhealth: scan", "command": "custom"}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            vscode.join("settings.json"),
+            r#"{"problems.showCurrentInStatus": false}"#,
+        )
+        .unwrap();
+        fs::write(vscode.join("extensions.json"), "// comments\n{}").unwrap();
+
+        let written = init_vscode(dir.path()).unwrap();
+        assert_eq!(
+            outcomes(&written),
+            vec![
+                FileOutcome::Updated(vec!["devhealth: check".to_string()]),
+                FileOutcome::Unchanged,
+                FileOutcome::Skipped,
+            ]
+        );
+
+        let tasks: Value =
+            serde_json::from_str(&fs::read_to_string(vscode.join("tasks.json")).unwrap()).unwrap();
+        let labels: Vec<&str> = tasks["tasks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|task| task["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, vec!["build", "devhealth: scan", "devhealth: check"]);
+        assert_eq!(
+            tasks["tasks"][1]["command"], "custom",
+            "Existing tasks are not replaced"
+        );
+        assert_eq!(
+            fs::read_to_string(vscode.join("extensions.json")).unwrap(),
+            "// comments\n{}"
+        );
+    }
+}
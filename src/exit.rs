@@ -0,0 +1,231 @@
+//! Process exit codes
+//!
+//! DevHealth exits with a code describing the outcome, so CI scripts can tell
+//! an unhealthy environment apart from a failure of DevHealth itself:
+//!
+//! | Code | Meaning                                                        |
+//! |------|----------------------------------------------------------------|
+//! | 0    | Success; no findings matched `--fail-on` or `--fail-level`     |
+//! | 1    | Findings matched `--fail-on` or reached `--fail-level`         |
+//! | 2    | Usage error: invalid arguments or configuration                |
+//! | 3    | Internal or scan error                                         |
+//! | 4    | Network failure                                                |
+//! | 5    | Another run holds the state lock and `--no-wait` was given     |
+//!
+//! Command-line parsing errors are reported by clap, which also exits with 2.
+
+use crate::acks::AckError;
+use crate::aggregate::MergeError;
+use crate::auth::AuthError;
+use crate::bench::BenchError;
+use crate::config::ConfigError;
+use crate::digest::DigestError;
+use crate::policy::PolicyError;
+use crate::repos::ReposError;
+use crate::scanner::audit::AuditError;
+use crate::scanner::forge::ForgeError;
+use crate::snapshot::SnapshotError;
+use crate::tags::TagError;
+use crate::utils::lock::LockError;
+use crate::utils::smtp::SmtpError;
+use crate::utils::theme::ThemeError;
+use crate::watch::WatchError;
+use std::error::Error;
+use thiserror::Error;
+
+/// Outcome of a DevHealth run, mapped to a process exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// Everything ran and nothing matched `--fail-on` or `--fail-level`
+    Ok,
+    /// Findings matched `--fail-on` or reached `--fail-level`
+    Findings,
+    /// Invalid arguments or configuration
+    Usage,
+    /// A scan or DevHealth itself failed
+    Internal,
+    /// A network request failed
+    Network,
+    /// Another DevHealth run held the state lock under `--no-wait`
+    Busy,
+}
+
+/// An error caused by how DevHealth was invoked rather than by a failure
+///
+/// Classified as [`ExitStatus::Usage`].
+#[derive(Error, Debug)]
+#[error("{0}")]
+pub struct UsageError(pub String);
+
+impl ExitStatus {
+    /// Returns the process exit code
+    pub fn code(self) -> i32 {
+        match self {
+            ExitStatus::Ok => 0,
+            ExitStatus::Findings => 1,
+            ExitStatus::Usage => 2,
+            ExitStatus::Internal => 3,
+            ExitStatus::Network => 4,
+            ExitStatus::Busy => 5,
+        }
+    }
+
+    /// Classifies an error by walking its chain of sources
+    ///
+    /// Network errors anywhere in the chain win, then a busy state lock;
+    /// configuration and [`UsageError`]s are usage errors; everything else
+    /// is internal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::exit::{ExitStatus, UsageError};
+    ///
+    /// let error = UsageError("not a git repository".to_string());
+    /// assert_eq!(ExitStatus::of_error(&error), ExitStatus::Usage);
+    /// ```
+    pub fn of_error(error: &(dyn Error + 'static)) -> ExitStatus {
+        let chain = std::iter::successors(Some(error), |e| (*e).source());
+        let mut status = ExitStatus::Internal;
+
+        for cause in chain {
+            if cause.is::<reqwest::Error>()
+                || matches!(
+                    cause.downcast_ref::<AuditError>(),
+                    Some(AuditError::Http(_))
+                )
+                || cause
+                    .downcast_ref::<SmtpError>()
+                    .is_some_and(|e| !matches!(e, SmtpError::InsecureAuth))
+            {
+                return ExitStatus::Network;
+            }
+            if matches!(cause.downcast_ref::<LockError>(), Some(LockError::Busy(_))) {
+                return ExitStatus::Busy;
+            }
+            if cause.is::<UsageError>()
+                || cause.is::<ConfigError>()
+                || cause.is::<ThemeError>()
+                || cause.is::<MergeError>()
+                || matches!(
+                    cause.downcast_ref::<AckError>(),
+                    Some(AckError::InvalidId(_) | AckError::InvalidDate(_))
+                )
+                || matches!(
+                    cause.downcast_ref::<BenchError>(),
+                    Some(BenchError::InvalidEndpoint(_))
+                )
+                || matches!(cause.downcast_ref::<TagError>(), Some(TagError::Config(_)))
+                || matches!(
+                    cause.downcast_ref::<AuditError>(),
+                    Some(AuditError::MissingDatabase(_))
+                )
+                || matches!(
+                    cause.downcast_ref::<WatchError>(),
+                    Some(WatchError::NoInventory)
+                )
+                || matches!(
+                    cause.downcast_ref::<SmtpError>(),
+                    Some(SmtpError::InsecureAuth)
+                )
+                || matches!(
+                    cause.downcast_ref::<AuthError>(),
+                    Some(AuthError::Keyring(_) | AuthError::Empty(_))
+                )
+                || matches!(
+                    cause.downcast_ref::<ForgeError>(),
+                    Some(ForgeError::NoToken)
+                )
+                || matches!(
+                    cause.downcast_ref::<ReposError>(),
+                    Some(ReposError::NotMissing(_) | ReposError::Exists(_))
+                )
+                || matches!(
+                    cause.downcast_ref::<DigestError>(),
+                    Some(
+                        DigestError::NotConfigured(_)
+                            | DigestError::NoPassword { .. }
+                            | DigestError::InvalidAddress(_)
+                            | DigestError::InvalidSchedule(_)
+                    )
+                )
+                || matches!(
+                    cause.downcast_ref::<PolicyError>(),
+                    Some(
+                        PolicyError::NoPublicKey
+                            | PolicyError::InsecureUrl(_)
+                            | PolicyError::TomlParse(_)
+                    )
+                )
+                || matches!(
+                    cause.downcast_ref::<SnapshotError>(),
+                    Some(SnapshotError::UnsupportedArchive(_) | SnapshotError::NotASnapshot(_))
+                )
+            {
+                status = ExitStatus::Usage;
+            }
+        }
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn exit_codes_are_stable() {
+        let codes: Vec<i32> = [
+            ExitStatus::Ok,
+            ExitStatus::Findings,
+            ExitStatus::Usage,
+            ExitStatus::Internal,
+            ExitStatus::Network,
+            ExitStatus::Busy,
+        ]
+        .into_iter()
+        .map(ExitStatus::code)
+        .collect();
+        assert_eq!(codes, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn classifies_errors_by_category() {
+        let theme = ThemeError::UnknownRole("sparkle".to_string());
+        assert_eq!(ExitStatus::of_error(&theme), ExitStatus::Usage);
+
+        let missing = AuditError::MissingDatabase(PathBuf::from("/db"));
+        assert_eq!(ExitStatus::of_error(&missing), ExitStatus::Usage);
+
+        let merge = MergeError::NoInputs("results/*.json".to_string());
+        assert_eq!(ExitStatus::of_error(&merge), ExitStatus::Usage);
+
+        let busy = LockError::Busy(Some(42));
+        assert_eq!(ExitStatus::of_error(&busy), ExitStatus::Busy);
+
+        let no_token = AuthError::Empty(crate::auth::Provider::GitHub);
+        assert_eq!(ExitStatus::of_error(&no_token), ExitStatus::Usage);
+
+        let unconfigured = DigestError::NotConfigured("a server");
+        assert_eq!(ExitStatus::of_error(&unconfigured), ExitStatus::Usage);
+
+        let refused = DigestError::Smtp(SmtpError::Rejected {
+            command: "RCPT TO".to_string(),
+            reply: "550 no such user".to_string(),
+        });
+        assert_eq!(ExitStatus::of_error(&refused), ExitStatus::Network);
+
+        let io = AuditError::Io(std::io::Error::other("disk full"));
+        assert_eq!(ExitStatus::of_error(&io), ExitStatus::Internal);
+    }
+
+    #[test]
+    fn classifies_boxed_errors() {
+        let boxed: Box<dyn Error> = Box::new(UsageError("bad flag".to_string()));
+        assert_eq!(ExitStatus::of_error(boxed.as_ref()), ExitStatus::Usage);
+
+        let boxed: Box<dyn Error> = "something broke".into();
+        assert_eq!(ExitStatus::of_error(boxed.as_ref()), ExitStatus::Internal);
+    }
+}
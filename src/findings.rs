@@ -0,0 +1,2207 @@
+//! Findings and their severities
+//!
+//! Every problem a scanner reports becomes a [`Finding`] of one [`Check`],
+//! rated `info`, `warn`, `error` or `critical`. Each check has a default
+//! severity that the `[severity]` section of `devhealth.toml` can override,
+//! globally or for repositories selected by path or tag:
+//!
+//! ```toml
+//! [severity]
+//! # Exit with status 1 on findings at or above this level
+//! fail_level = "error"
+//!
+//! [severity.checks]
+//! stale-changelog = "info"
+//!
+//! # Personal repositories get a laxer rating; the last matching override wins
+//! [[severity.overrides]]
+//! tags = ["personal"]
+//! checks = { uncommitted-changes = "info", missing-rust-version = "info" }
+//! ```
+//!
+//! `--fail-level` on the command line takes precedence over `fail_level`.
+//!
+//! The `[checks]` section switches checks off, by name or rule ID, the same
+//! way for every scanner: a disabled check reports no findings, so it never
+//! fails a run either.
+//!
+//! ```toml
+//! [checks]
+//! disabled = ["stale-changelog", "GIT013"]
+//!
+//! # Overrides disable or re-enable checks; the last matching override wins
+//! [[checks.overrides]]
+//! paths = ["~/personal/**"]
+//! disabled = ["unpushed-commits"]
+//! ```
+//!
+//! Every check also has a stable rule ID, such as `GIT001` for unpushed
+//! commits or `DEP014` for a vulnerable dependency, carried by each finding
+//! in every output format. IDs are never renumbered or reused; `devhealth
+//! rules` lists the catalog.
+//!
+//! `--format diagnostics` prints each finding as an editor diagnostic,
+//! pointing at the manifest line of a vulnerable package or Cargo setting
+//! where possible:
+//!
+//! ```text
+//! /src/api/Cargo.toml:12:1: error: DEP014 Vulnerable dependency: time@0.1.45 (RUSTSEC-2020-0071)
+//! ```
+
+use crate::acks::FindingId;
+use crate::i18n::t;
+use crate::report::{Attention, ScanResults};
+use crate::scanner::analytics::CheckOutcome;
+use crate::scanner::cargo::CargoFinding;
+use crate::scanner::forge::CiStatus;
+use crate::scanner::git::{CredentialProblem, GitStatus, StashEntry};
+use crate::scanner::lockfiles::LockfileProblem;
+use crate::scanner::permissions::ExposureProblem;
+use crate::scanner::release::{Changelog, VersionStatus};
+use crate::scanner::sizes::DiskUsage;
+use crate::tags::{self, TagFilter};
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// How serious a finding is, from least to most severe
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth knowing, no action needed
+    Info,
+    /// Should be looked at soon
+    Warn,
+    /// Broken or risky, should be fixed
+    Error,
+    /// Leaks secrets or otherwise needs immediate action
+    Critical,
+}
+
+impl Severity {
+    /// Every severity, from most to least severe
+    pub const ALL: [Severity; 4] = [
+        Severity::Critical,
+        Severity::Error,
+        Severity::Warn,
+        Severity::Info,
+    ];
+
+    /// Returns the severity's name as written in `devhealth.toml`
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warn => "warn",
+            Severity::Error => "error",
+            Severity::Critical => "critical",
+        }
+    }
+
+    /// Returns the localized label of the severity
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => t("severity-info"),
+            Severity::Warn => t("severity-warn"),
+            Severity::Error => t("severity-error"),
+            Severity::Critical => t("severity-critical"),
+        }
+    }
+
+    /// Returns the level of the severity in editor diagnostics
+    ///
+    /// Editors know `error`, `warning` and `info`; critical findings are
+    /// errors.
+    pub fn diagnostic_level(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warn => "warning",
+            Severity::Error | Severity::Critical => "error",
+        }
+    }
+
+    /// Returns the color role findings of this severity are printed in
+    pub fn role(self) -> Role {
+        match self {
+            Severity::Info => Role::Muted,
+            Severity::Warn => Role::Warning,
+            Severity::Error | Severity::Critical => Role::Error,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Everything DevHealth checks for, named as in `[severity.checks]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Check {
+    /// A repository has uncommitted changes
+    UncommittedChanges,
+    /// A repository has commits not pushed to its upstream
+    UnpushedCommits,
+    /// A repository could not be inspected
+    RepositoryError,
+    /// CI fails on the default branch
+    CiFailed,
+    /// A fork trails its upstream default branch
+    ForkBehind,
+    /// Open pull requests have unresolved review threads
+    UnresolvedReviews,
+    /// Local branches of merged pull requests were not deleted
+    MergedBranches,
+    /// An HTTPS remote has no credential helper
+    CredentialHelperMissing,
+    /// Git stores passwords in plain text
+    PlaintextCredentials,
+    /// A remote URL contains a password or token
+    EmbeddedCredentials,
+    /// A recommended global git setting is missing
+    GitConfigRecommendation,
+    /// Uncommitted changes have sat for longer than
+    /// `stale_uncommitted_days`
+    StaleUncommittedChanges,
+    /// Recent commits are reachable only from the reflog or not at all
+    LostWork,
+    /// Other users can read a repository's `.git` directory
+    ReadableGitDirectory,
+    /// A `.env` file or private key is committed
+    CommittedSecret,
+    /// Other users can read an untracked `.env` file or private key
+    ReadableSecret,
+    /// A repository sits in another's working tree without being a
+    /// submodule or ignored
+    NestedRepository,
+    /// Another clone of the same repository under the scan root is the
+    /// freshest one
+    DuplicateClone,
+    /// A repository's build artifacts grew a lot since the previous scan
+    GrowingArtifacts,
+    /// Gigabytes of build output nothing was written to for months
+    StaleArtifacts,
+    /// A local branch is merged into the default branch or had no commits
+    /// for `stale_branch_days`
+    StaleBranch,
+    /// A repository has stash entries older than a week
+    OldStash,
+    /// A repository is archived on the forge
+    ArchivedUpstream,
+    /// A repository had no commits for `stale_repo_months`
+    StaleRepository,
+    /// A dependency manifest could not be parsed
+    ManifestError,
+    /// A runtime dependency has a known vulnerability
+    VulnerableDependency,
+    /// A development or build dependency has a known vulnerability
+    VulnerableDevDependency,
+    /// An application or `package.json` with dependencies has no lockfile
+    MissingLockfile,
+    /// The lockfile does not satisfy the manifest's dependencies
+    LockfileOutOfSync,
+    /// The manifest changed after the lockfile was last written
+    StaleLockfile,
+    /// A Go module or workspace replaces a dependency
+    GoReplace,
+    /// Host and container toolchain versions differ
+    ToolchainMismatch,
+    /// A dev container configuration could not be read
+    ContainerConfigError,
+    /// The active runtime version does not satisfy a project's pin
+    RuntimeVersionMismatch,
+    /// A `node` on `PATH` hides one from another source
+    ShadowedRuntime,
+    /// A developer tool is on `PATH` more than once with different versions
+    DuplicateBinary,
+    /// The system clock is off by a minute or more
+    ClockSkew,
+    /// A CA bundle is missing, empty, truncated or incomplete
+    BrokenCaBundle,
+    /// Developer tools are configured with different proxies
+    ProxyMismatch,
+    /// A proxy intercepts TLS connections to registries
+    TlsInterception,
+    /// A build directory is scanned by antivirus or indexed by Spotlight
+    UnexcludedBuildDirectory,
+    /// The scanned volume is running out of inodes
+    LowInodes,
+    /// The open-file limit is too low for file watchers and builds
+    LowFileLimit,
+    /// The locale or console code page is not UTF-8
+    NonUtf8Locale,
+    /// A workspace package manifest could not be read
+    WorkspaceManifestError,
+    /// Workspace packages depend on each other in a cycle
+    DependencyCycle,
+    /// Workspace packages pin an external dependency differently
+    VersionConflict,
+    /// The manifest version differs from the latest tag
+    VersionMismatch,
+    /// Commits follow the latest tag without a version bump
+    VersionBumpMissing,
+    /// The changelog fell behind the history
+    StaleChangelog,
+    /// A Rust package declares no `rust-version`
+    MissingRustVersion,
+    /// A Rust package uses an edition older than 2021
+    OutdatedEdition,
+    /// Locked dependencies need a newer Rust than `rust-version`
+    RustVersionTooLow,
+    /// A Cargo feature is never used
+    UnusedFeature,
+    /// The `default` feature pulls in a heavy optional dependency
+    HeavyDefaultDependency,
+    /// A lint, format or build check failed under `--deep`
+    CheckFailed,
+    /// A check under `--deep` ran past its timeout
+    CheckTimedOut,
+}
+
+impl Check {
+    /// Every check, in rule ID order
+    pub const ALL: [Check; 57] = [
+        Check::UnpushedCommits,
+        Check::UncommittedChanges,
+        Check::RepositoryError,
+        Check::CiFailed,
+        Check::ForkBehind,
+        Check::UnresolvedReviews,
+        Check::MergedBranches,
+        Check::CredentialHelperMissing,
+        Check::PlaintextCredentials,
+        Check::EmbeddedCredentials,
+        Check::GitConfigRecommendation,
+        Check::StaleUncommittedChanges,
+        Check::LostWork,
+        Check::ReadableGitDirectory,
+        Check::CommittedSecret,
+        Check::ReadableSecret,
+        Check::NestedRepository,
+        Check::DuplicateClone,
+        Check::GrowingArtifacts,
+        Check::StaleArtifacts,
+        Check::StaleBranch,
+        Check::OldStash,
+        Check::ArchivedUpstream,
+        Check::StaleRepository,
+        Check::ManifestError,
+        Check::WorkspaceManifestError,
+        Check::DependencyCycle,
+        Check::VersionConflict,
+        Check::VulnerableDependency,
+        Check::VulnerableDevDependency,
+        Check::MissingLockfile,
+        Check::LockfileOutOfSync,
+        Check::StaleLockfile,
+        Check::GoReplace,
+        Check::ToolchainMismatch,
+        Check::ContainerConfigError,
+        Check::RuntimeVersionMismatch,
+        Check::ShadowedRuntime,
+        Check::VersionMismatch,
+        Check::VersionBumpMissing,
+        Check::StaleChangelog,
+        Check::DuplicateBinary,
+        Check::ClockSkew,
+        Check::BrokenCaBundle,
+        Check::ProxyMismatch,
+        Check::TlsInterception,
+        Check::UnexcludedBuildDirectory,
+        Check::LowInodes,
+        Check::LowFileLimit,
+        Check::NonUtf8Locale,
+        Check::MissingRustVersion,
+        Check::OutdatedEdition,
+        Check::RustVersionTooLow,
+        Check::UnusedFeature,
+        Check::HeavyDefaultDependency,
+        Check::CheckFailed,
+        Check::CheckTimedOut,
+    ];
+
+    /// Returns the check's stable rule ID, such as `GIT001`
+    ///
+    /// The prefix names the area: `GIT` repositories, `DEP` dependencies and
+    /// workspaces, `ENV` dev containers and runtimes, `REL` releases, `SYS`
+    /// the system, `RST` Rust packages
+    /// and `QLT` the `--deep` checks.
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            Check::UnpushedCommits => "GIT001",
+            Check::UncommittedChanges => "GIT002",
+            Check::RepositoryError => "GIT003",
+            Check::CiFailed => "GIT004",
+            Check::ForkBehind => "GIT005",
+            Check::UnresolvedReviews => "GIT006",
+            Check::MergedBranches => "GIT007",
+            Check::CredentialHelperMissing => "GIT008",
+            Check::PlaintextCredentials => "GIT009",
+            Check::EmbeddedCredentials => "GIT010",
+            Check::GitConfigRecommendation => "GIT011",
+            Check::StaleUncommittedChanges => "GIT012",
+            Check::LostWork => "GIT013",
+            Check::ReadableGitDirectory => "GIT014",
+            Check::CommittedSecret => "GIT015",
+            Check::ReadableSecret => "GIT016",
+            Check::NestedRepository => "GIT017",
+            Check::DuplicateClone => "GIT018",
+            Check::GrowingArtifacts => "GIT019",
+            Check::StaleArtifacts => "GIT020",
+            Check::StaleBranch => "GIT021",
+            Check::OldStash => "GIT022",
+            Check::ArchivedUpstream => "GIT023",
+            Check::StaleRepository => "GIT024",
+            Check::ManifestError => "DEP001",
+            Check::WorkspaceManifestError => "DEP002",
+            Check::DependencyCycle => "DEP003",
+            Check::VersionConflict => "DEP004",
+            Check::VulnerableDependency => "DEP014",
+            Check::VulnerableDevDependency => "DEP009",
+            Check::MissingLockfile => "DEP005",
+            Check::LockfileOutOfSync => "DEP006",
+            Check::StaleLockfile => "DEP007",
+            Check::GoReplace => "DEP008",
+            Check::ToolchainMismatch => "ENV001",
+            Check::ContainerConfigError => "ENV002",
+            Check::RuntimeVersionMismatch => "ENV003",
+            Check::ShadowedRuntime => "ENV004",
+            Check::VersionMismatch => "REL001",
+            Check::VersionBumpMissing => "REL002",
+            Check::StaleChangelog => "REL003",
+            Check::DuplicateBinary => "SYS001",
+            Check::ClockSkew => "SYS002",
+            Check::BrokenCaBundle => "SYS003",
+            Check::ProxyMismatch => "SYS004",
+            Check::TlsInterception => "SYS005",
+            Check::UnexcludedBuildDirectory => "SYS006",
+            Check::LowInodes => "SYS007",
+            Check::LowFileLimit => "SYS008",
+            Check::NonUtf8Locale => "SYS009",
+            Check::MissingRustVersion => "RST001",
+            Check::OutdatedEdition => "RST002",
+            Check::RustVersionTooLow => "RST003",
+            Check::UnusedFeature => "RST004",
+            Check::HeavyDefaultDependency => "RST005",
+            Check::CheckFailed => "QLT001",
+            Check::CheckTimedOut => "QLT002",
+        }
+    }
+
+    /// Finds the check with rule ID `id`, ignoring case
+    pub fn from_rule_id(id: &str) -> Option<Check> {
+        Check::ALL
+            .into_iter()
+            .find(|check| check.rule_id().eq_ignore_ascii_case(id))
+    }
+
+    /// Returns the check's name as written in `[severity.checks]`
+    pub fn name(self) -> &'static str {
+        match self {
+            Check::UnpushedCommits => "unpushed-commits",
+            Check::UncommittedChanges => "uncommitted-changes",
+            Check::RepositoryError => "repository-error",
+            Check::CiFailed => "ci-failed",
+            Check::ForkBehind => "fork-behind",
+            Check::UnresolvedReviews => "unresolved-reviews",
+            Check::MergedBranches => "merged-branches",
+            Check::CredentialHelperMissing => "credential-helper-missing",
+            Check::PlaintextCredentials => "plaintext-credentials",
+            Check::EmbeddedCredentials => "embedded-credentials",
+            Check::GitConfigRecommendation => "git-config-recommendation",
+            Check::StaleUncommittedChanges => "stale-uncommitted-changes",
+            Check::LostWork => "lost-work",
+            Check::ReadableGitDirectory => "readable-git-directory",
+            Check::CommittedSecret => "committed-secret",
+            Check::ReadableSecret => "readable-secret",
+            Check::NestedRepository => "nested-repository",
+            Check::DuplicateClone => "duplicate-clone",
+            Check::GrowingArtifacts => "growing-artifacts",
+            Check::StaleArtifacts => "stale-artifacts",
+            Check::StaleBranch => "stale-branch",
+            Check::OldStash => "old-stash",
+            Check::ArchivedUpstream => "archived-upstream",
+            Check::StaleRepository => "stale-repository",
+            Check::ManifestError => "manifest-error",
+            Check::WorkspaceManifestError => "workspace-manifest-error",
+            Check::DependencyCycle => "dependency-cycle",
+            Check::VersionConflict => "version-conflict",
+            Check::VulnerableDependency => "vulnerable-dependency",
+            Check::VulnerableDevDependency => "vulnerable-dev-dependency",
+            Check::MissingLockfile => "missing-lockfile",
+            Check::LockfileOutOfSync => "lockfile-out-of-sync",
+            Check::StaleLockfile => "stale-lockfile",
+            Check::GoReplace => "go-replace",
+            Check::ToolchainMismatch => "toolchain-mismatch",
+            Check::ContainerConfigError => "container-config-error",
+            Check::RuntimeVersionMismatch => "runtime-version-mismatch",
+            Check::ShadowedRuntime => "shadowed-runtime",
+            Check::VersionMismatch => "version-mismatch",
+            Check::VersionBumpMissing => "version-bump-missing",
+            Check::StaleChangelog => "stale-changelog",
+            Check::DuplicateBinary => "duplicate-binary",
+            Check::ClockSkew => "clock-skew",
+            Check::BrokenCaBundle => "broken-ca-bundle",
+            Check::ProxyMismatch => "proxy-mismatch",
+            Check::TlsInterception => "tls-interception",
+            Check::UnexcludedBuildDirectory => "unexcluded-build-directory",
+            Check::LowInodes => "low-inodes",
+            Check::LowFileLimit => "low-file-limit",
+            Check::NonUtf8Locale => "non-utf8-locale",
+            Check::MissingRustVersion => "missing-rust-version",
+            Check::OutdatedEdition => "outdated-edition",
+            Check::RustVersionTooLow => "rust-version-too-low",
+            Check::UnusedFeature => "unused-feature",
+            Check::HeavyDefaultDependency => "heavy-default-dependency",
+            Check::CheckFailed => "check-failed",
+            Check::CheckTimedOut => "check-timed-out",
+        }
+    }
+
+    /// Returns the severity of the check when the configuration sets none
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Check::ForkBehind
+            | Check::UnresolvedReviews
+            | Check::MergedBranches
+            | Check::MissingRustVersion
+            | Check::OutdatedEdition
+            | Check::UnusedFeature
+            | Check::GitConfigRecommendation
+            | Check::StaleArtifacts
+            | Check::StaleBranch
+            | Check::ArchivedUpstream
+            | Check::StaleRepository
+            | Check::StaleLockfile
+            | Check::GoReplace => Severity::Info,
+            Check::UncommittedChanges
+            | Check::UnpushedCommits
+            | Check::CredentialHelperMissing
+            | Check::LostWork
+            | Check::ReadableGitDirectory
+            | Check::NestedRepository
+            | Check::DuplicateClone
+            | Check::GrowingArtifacts
+            | Check::OldStash
+            | Check::ToolchainMismatch
+            | Check::RuntimeVersionMismatch
+            | Check::ShadowedRuntime
+            | Check::DuplicateBinary
+            | Check::ClockSkew
+            | Check::ProxyMismatch
+            | Check::TlsInterception
+            | Check::UnexcludedBuildDirectory
+            | Check::LowFileLimit
+            | Check::NonUtf8Locale
+            | Check::VersionConflict
+            | Check::MissingLockfile
+            | Check::LockfileOutOfSync
+            | Check::VersionBumpMissing
+            | Check::StaleChangelog
+            | Check::HeavyDefaultDependency
+            | Check::VulnerableDevDependency
+            | Check::CheckTimedOut => Severity::Warn,
+            Check::RepositoryError
+            | Check::CiFailed
+            | Check::PlaintextCredentials
+            | Check::StaleUncommittedChanges
+            | Check::CommittedSecret
+            | Check::ReadableSecret
+            | Check::BrokenCaBundle
+            | Check::LowInodes
+            | Check::ManifestError
+            | Check::VulnerableDependency
+            | Check::ContainerConfigError
+            | Check::WorkspaceManifestError
+            | Check::DependencyCycle
+            | Check::VersionMismatch
+            | Check::RustVersionTooLow
+            | Check::CheckFailed => Severity::Error,
+            Check::EmbeddedCredentials => Severity::Critical,
+        }
+    }
+
+    /// Returns the kind of attention (as used by `--only` and `ack`) the
+    /// check belongs to, if any
+    pub fn attention(self) -> Option<Attention> {
+        match self {
+            Check::UncommittedChanges | Check::StaleUncommittedChanges => Some(Attention::Dirty),
+            Check::UnpushedCommits => Some(Attention::Unpushed),
+            Check::VulnerableDependency | Check::VulnerableDevDependency => {
+                Some(Attention::Vulnerable)
+            }
+            Check::ArchivedUpstream => Some(Attention::Archived),
+            Check::ForkBehind
+            | Check::UnresolvedReviews
+            | Check::MergedBranches
+            | Check::CredentialHelperMissing
+            | Check::PlaintextCredentials
+            | Check::EmbeddedCredentials
+            | Check::ToolchainMismatch
+            | Check::RuntimeVersionMismatch
+            | Check::ShadowedRuntime
+            | Check::DuplicateBinary
+            | Check::ClockSkew
+            | Check::BrokenCaBundle
+            | Check::ProxyMismatch
+            | Check::TlsInterception
+            | Check::UnexcludedBuildDirectory
+            | Check::LowInodes
+            | Check::LowFileLimit
+            | Check::NonUtf8Locale
+            | Check::GitConfigRecommendation
+            | Check::LostWork
+            | Check::ReadableGitDirectory
+            | Check::CommittedSecret
+            | Check::ReadableSecret
+            | Check::NestedRepository
+            | Check::DuplicateClone
+            | Check::GrowingArtifacts
+            | Check::StaleArtifacts
+            | Check::StaleBranch
+            | Check::OldStash
+            | Check::StaleRepository
+            | Check::VersionConflict
+            | Check::MissingLockfile
+            | Check::LockfileOutOfSync
+            | Check::StaleLockfile
+            | Check::GoReplace => None,
+            _ => Some(Attention::Errors),
+        }
+    }
+
+    /// Returns `true` if the check is about exposed credentials or secrets,
+    /// which matter for as long as the clone exists, archived or not
+    fn concerns_secrets(self) -> bool {
+        matches!(
+            self,
+            Check::PlaintextCredentials
+                | Check::EmbeddedCredentials
+                | Check::ReadableGitDirectory
+                | Check::CommittedSecret
+                | Check::ReadableSecret
+        )
+    }
+
+    /// Returns a localized, one-line description of the check
+    pub fn describe(self) -> &'static str {
+        match self {
+            Check::UncommittedChanges => t("check-uncommitted-changes"),
+            Check::UnpushedCommits => t("check-unpushed-commits"),
+            Check::RepositoryError => t("check-repository-error"),
+            Check::CiFailed => t("check-ci-failed"),
+            Check::ForkBehind => t("check-fork-behind"),
+            Check::UnresolvedReviews => t("check-unresolved-reviews"),
+            Check::MergedBranches => t("check-merged-branches"),
+            Check::CredentialHelperMissing => t("check-credential-helper-missing"),
+            Check::PlaintextCredentials => t("check-plaintext-credentials"),
+            Check::EmbeddedCredentials => t("check-embedded-credentials"),
+            Check::GitConfigRecommendation => t("check-git-config-recommendation"),
+            Check::StaleUncommittedChanges => t("check-stale-uncommitted-changes"),
+            Check::LostWork => t("check-lost-work"),
+            Check::ReadableGitDirectory => t("check-readable-git-directory"),
+            Check::CommittedSecret => t("check-committed-secret"),
+            Check::ReadableSecret => t("check-readable-secret"),
+            Check::NestedRepository => t("check-nested-repository"),
+            Check::DuplicateClone => t("check-duplicate-clone"),
+            Check::GrowingArtifacts => t("check-growing-artifacts"),
+            Check::StaleArtifacts => t("check-stale-artifacts"),
+            Check::StaleBranch => t("check-stale-branch"),
+            Check::OldStash => t("check-old-stash"),
+            Check::ArchivedUpstream => t("check-archived-upstream"),
+            Check::StaleRepository => t("check-stale-repository"),
+            Check::ManifestError => t("check-manifest-error"),
+            Check::VulnerableDependency => t("check-vulnerable-dependency"),
+            Check::VulnerableDevDependency => t("check-vulnerable-dev-dependency"),
+            Check::MissingLockfile => t("check-missing-lockfile"),
+            Check::LockfileOutOfSync => t("check-lockfile-out-of-sync"),
+            Check::StaleLockfile => t("check-stale-lockfile"),
+            Check::GoReplace => t("check-go-replace"),
+            Check::ToolchainMismatch => t("check-toolchain-mismatch"),
+            Check::ContainerConfigError => t("check-container-config-error"),
+            Check::RuntimeVersionMismatch => t("check-runtime-version-mismatch"),
+            Check::ShadowedRuntime => t("check-shadowed-runtime"),
+            Check::WorkspaceManifestError => t("check-workspace-manifest-error"),
+            Check::DependencyCycle => t("check-dependency-cycle"),
+            Check::VersionConflict => t("check-version-conflict"),
+            Check::VersionMismatch => t("check-version-mismatch"),
+            Check::VersionBumpMissing => t("check-version-bump-missing"),
+            Check::StaleChangelog => t("check-stale-changelog"),
+            Check::DuplicateBinary => t("check-duplicate-binary"),
+            Check::ClockSkew => t("check-clock-skew"),
+            Check::BrokenCaBundle => t("check-broken-ca-bundle"),
+            Check::ProxyMismatch => t("check-proxy-mismatch"),
+            Check::TlsInterception => t("check-tls-interception"),
+            Check::UnexcludedBuildDirectory => t("check-unexcluded-build-directory"),
+            Check::LowInodes => t("check-low-inodes"),
+            Check::LowFileLimit => t("check-low-file-limit"),
+            Check::NonUtf8Locale => t("check-non-utf8-locale"),
+            Check::MissingRustVersion => t("check-missing-rust-version"),
+            Check::OutdatedEdition => t("check-outdated-edition"),
+            Check::RustVersionTooLow => t("check-rust-version-too-low"),
+            Check::UnusedFeature => t("check-unused-feature"),
+            Check::HeavyDefaultDependency => t("check-heavy-default-dependency"),
+            Check::CheckFailed => t("check-check-failed"),
+            Check::CheckTimedOut => t("check-check-timed-out"),
+        }
+    }
+}
+
+/// One problem found by a scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    /// Stable rule ID of the check, such as `GIT001`
+    pub rule: String,
+    /// What was checked
+    pub check: Check,
+    /// How serious it is, after configured overrides
+    pub severity: Severity,
+    /// Repository, project or file the finding applies to
+    pub path: PathBuf,
+    /// What exactly is affected, such as a package, remote or feature name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Days after which uncommitted changes are reported as stale by default
+pub const STALE_UNCOMMITTED_DAYS: u64 = 14;
+
+/// The `[severity]` section of `devhealth.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeverityConfig {
+    /// Exit with status 1 if a finding reaches this severity
+    pub fail_level: Option<Severity>,
+    /// Severity of each check for every repository
+    pub checks: BTreeMap<Check, Severity>,
+    /// Severities for repositories selected by path or tag
+    pub overrides: Vec<SeverityOverride>,
+    /// Days after which uncommitted changes escalate from
+    /// `uncommitted-changes` to `stale-uncommitted-changes`
+    pub stale_uncommitted_days: u64,
+    /// Highest severity of findings in repositories archived on the forge,
+    /// other than exposed credentials and secrets
+    pub archived_level: Severity,
+}
+
+impl Default for SeverityConfig {
+    fn default() -> Self {
+        SeverityConfig {
+            fail_level: None,
+            checks: BTreeMap::new(),
+            overrides: Vec::new(),
+            stale_uncommitted_days: STALE_UNCOMMITTED_DAYS,
+            archived_level: Severity::Info,
+        }
+    }
+}
+
+/// Check severities for the repositories matching a path pattern or tag
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeverityOverride {
+    /// Path patterns, as in `[tags]`
+    pub paths: Vec<String>,
+    /// Tags assigned in `[tags]` or with the `tag` command
+    pub tags: Vec<String>,
+    /// Severity of each overridden check
+    pub checks: BTreeMap<Check, Severity>,
+}
+
+impl SeverityConfig {
+    /// Returns `true` if an override selects repositories by tag
+    pub fn uses_tags(&self) -> bool {
+        self.overrides.iter().any(|rule| !rule.tags.is_empty())
+    }
+
+    /// Returns the severity of `check` for the entry at `path`
+    ///
+    /// The last override matching `path` or one of its ancestors wins, then
+    /// `[severity.checks]`, then the check's default.
+    ///
+    /// # Arguments
+    ///
+    /// * `check` - The check that found something
+    /// * `path` - Where it was found
+    /// * `tags` - Tags applying to `path`, see [`TagFilter::tags_for`]
+    pub fn severity_of(&self, check: Check, path: &Path, tags: &BTreeSet<String>) -> Severity {
+        self.overrides
+            .iter()
+            .rev()
+            .filter(|rule| rule.matches(path, tags))
+            .find_map(|rule| rule.checks.get(&check))
+            .or_else(|| self.checks.get(&check))
+            .copied()
+            .unwrap_or_else(|| check.default_severity())
+    }
+}
+
+impl SeverityOverride {
+    fn matches(&self, path: &Path, tags: &BTreeSet<String>) -> bool {
+        selects(&self.paths, &self.tags, path, tags)
+    }
+}
+
+/// Returns `true` if an override with these path patterns and tags applies
+/// to `path` or one of its ancestors
+fn selects(patterns: &[String], selected: &[String], path: &Path, tags: &BTreeSet<String>) -> bool {
+    selected.iter().any(|tag| tags.contains(tag))
+        || path.ancestors().any(|ancestor| {
+            patterns
+                .iter()
+                .any(|pattern| dh_fs::glob_matches(pattern, ancestor))
+        })
+}
+
+/// The `[checks]` section of `devhealth.toml`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChecksConfig {
+    /// Checks that report nothing, by name or rule ID
+    #[serde(deserialize_with = "check_list")]
+    pub disabled: Vec<Check>,
+    /// Checks switched off or back on for repositories selected by path or tag
+    pub overrides: Vec<CheckOverride>,
+}
+
+/// Checks switched off or on for the repositories matching a path pattern
+/// or tag
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckOverride {
+    /// Path patterns, as in `[tags]`
+    pub paths: Vec<String>,
+    /// Tags assigned in `[tags]` or with the `tag` command
+    pub tags: Vec<String>,
+    /// Checks that report nothing there, by name or rule ID
+    #[serde(deserialize_with = "check_list")]
+    pub disabled: Vec<Check>,
+    /// Checks that report again there although disabled globally or by an
+    /// earlier override
+    #[serde(deserialize_with = "check_list")]
+    pub enabled: Vec<Check>,
+}
+
+impl ChecksConfig {
+    /// Returns `true` if an override selects repositories by tag
+    pub fn uses_tags(&self) -> bool {
+        self.overrides.iter().any(|rule| !rule.tags.is_empty())
+    }
+
+    /// Returns `true` if `check` reports findings for the entry at `path`
+    ///
+    /// The last override matching `path` or one of its ancestors that names
+    /// the check wins, then `disabled`. Checks are enabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::findings::{Check, CheckOverride, ChecksConfig};
+    /// use std::collections::BTreeSet;
+    /// use std::path::Path;
+    ///
+    /// let config = ChecksConfig {
+    ///     disabled: vec![Check::StaleChangelog],
+    ///     overrides: vec![CheckOverride {
+    ///         paths: vec!["/home/dev/personal/**".to_string()],
+    ///         disabled: vec![Check::UnpushedCommits],
+    ///         ..Default::default()
+    ///     }],
+    /// };
+    /// let none = BTreeSet::new();
+    /// assert!(!config.is_enabled(Check::StaleChangelog, Path::new("/src/api"), &none));
+    /// assert!(config.is_enabled(Check::UnpushedCommits, Path::new("/src/api"), &none));
+    /// assert!(!config.is_enabled(Check::UnpushedCommits, Path::new("/home/dev/personal/blog"), &none));
+    /// ```
+    pub fn is_enabled(&self, check: Check, path: &Path, tags: &BTreeSet<String>) -> bool {
+        self.overrides
+            .iter()
+            .rev()
+            .filter(|rule| selects(&rule.paths, &rule.tags, path, tags))
+            .find_map(|rule| {
+                if rule.enabled.contains(&check) {
+                    Some(true)
+                } else if rule.disabled.contains(&check) {
+                    Some(false)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_else(|| !self.disabled.contains(&check))
+    }
+
+    /// Clears what disabled repository checks would report from `results`,
+    /// so the text report shows neither their status nor their tips
+    ///
+    /// A repository whose `uncommitted-changes` check is disabled is shown
+    /// as clean, one whose `unpushed-commits` check is disabled loses its
+    /// unpushed marker, and so on. Repository errors and artifact growth
+    /// stay listed, since the status line and sizes need them.
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - The scan results, before [`collect`]
+    /// * `tags` - Tag assignments used by overrides selecting tags
+    pub fn hide_disabled(&self, results: &mut ScanResults, tags: &TagFilter) {
+        if self.disabled.is_empty() && self.overrides.is_empty() {
+            return;
+        }
+        let now = results
+            .run
+            .as_ref()
+            .map_or_else(display::unix_now, |run| run.generated_at);
+        for repo in results.git.iter_mut().flatten() {
+            let applying = if self.uses_tags() {
+                tags.tags_for(&repo.path)
+            } else {
+                BTreeSet::new()
+            };
+            let canonical = tags::canonical(&repo.path);
+            let enabled = |check| self.is_enabled(check, &canonical, &applying);
+
+            if !enabled(Check::UncommittedChanges) {
+                repo.uncommitted_changes = false;
+                repo.uncommitted_since = None;
+                if matches!(repo.status, GitStatus::Dirty) {
+                    repo.status = GitStatus::Clean;
+                }
+            }
+            if !enabled(Check::StaleUncommittedChanges) && !enabled(Check::UncommittedChanges) {
+                repo.uncommitted_since = None;
+            }
+            if !enabled(Check::UnpushedCommits) {
+                repo.unpushed_commits = false;
+            }
+            if !enabled(Check::CiFailed) && repo.ci == Some(CiStatus::Failed) {
+                repo.ci = None;
+            }
+            if !enabled(Check::ForkBehind) {
+                repo.fork = None;
+            }
+            if let Some(reviews) = &mut repo.reviews {
+                if !enabled(Check::UnresolvedReviews) {
+                    reviews.unresolved.clear();
+                }
+                if !enabled(Check::MergedBranches) {
+                    reviews.merged.clear();
+                }
+            }
+            repo.credentials.retain(|issue| {
+                enabled(match issue.problem {
+                    CredentialProblem::NoHelper => Check::CredentialHelperMissing,
+                    CredentialProblem::PlaintextStore => Check::PlaintextCredentials,
+                    CredentialProblem::EmbeddedCredentials => Check::EmbeddedCredentials,
+                })
+            });
+            if !enabled(Check::NestedRepository) {
+                repo.nested = None;
+            }
+            if !enabled(Check::DuplicateClone) {
+                repo.duplicate = None;
+            }
+            if !enabled(Check::StaleArtifacts) {
+                repo.stale_artifacts.clear();
+            }
+            if !enabled(Check::StaleBranch) {
+                repo.stale_branches.clear();
+            }
+            if !enabled(Check::OldStash) {
+                repo.stashes.retain(|stash| !stash.is_old(now));
+            }
+            repo.exposures.retain(|exposure| {
+                enabled(match exposure.problem {
+                    ExposureProblem::ReadableGitDirectory => Check::ReadableGitDirectory,
+                    ExposureProblem::CommittedSecret(_) => Check::CommittedSecret,
+                    ExposureProblem::ReadableSecret(_) => Check::ReadableSecret,
+                })
+            });
+            if !enabled(Check::LostWork) {
+                repo.lost_commits.clear();
+            }
+            if !enabled(Check::StaleRepository) {
+                repo.stale = false;
+            }
+            if !enabled(Check::ArchivedUpstream) {
+                repo.archived = false;
+            }
+        }
+    }
+}
+
+/// Reads a list of checks given by name, such as `unpushed-commits`, or by
+/// rule ID, such as `GIT001`
+fn check_list<'de, D>(deserializer: D) -> Result<Vec<Check>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|key| {
+            Check::ALL
+                .into_iter()
+                .find(|check| check.name() == key)
+                .or_else(|| Check::from_rule_id(&key))
+                .ok_or_else(|| {
+                    serde::de::Error::custom(format!("unknown check or rule ID `{}`", key))
+                })
+        })
+        .collect()
+}
+
+/// Gathers the findings of every scanned section, most severe first
+///
+/// Acknowledged findings and findings of disabled checks are left out.
+///
+/// # Arguments
+///
+/// * `results` - The scan results, after [`ScanResults::acknowledge`]
+/// * `config` - Severity overrides from `devhealth.toml`
+/// * `checks` - Checks disabled in `devhealth.toml`, whose findings are left out
+/// * `tags` - Tag assignments used by overrides selecting tags
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::findings::{self, ChecksConfig, SeverityConfig};
+/// use devhealth::report::ScanResults;
+/// use devhealth::tags::TagFilter;
+///
+/// let results = ScanResults::default();
+/// let found = findings::collect(&results, &SeverityConfig::default(), &ChecksConfig::default(), &TagFilter::default());
+/// assert!(found.is_empty());
+/// ```
+pub fn collect(
+    results: &ScanResults,
+    config: &SeverityConfig,
+    checks: &ChecksConfig,
+    tags: &TagFilter,
+) -> Vec<Finding> {
+    let mut raw: Vec<(Check, &Path, Option<String>)> = Vec::new();
+    let now = results
+        .run
+        .as_ref()
+        .map_or_else(display::unix_now, |run| run.generated_at);
+
+    for repo in results.git.iter().flatten() {
+        let path = repo.path.as_path();
+        match repo.uncommitted_days(now) {
+            Some(days) if days >= config.stale_uncommitted_days => {
+                raw.push((
+                    Check::StaleUncommittedChanges,
+                    path,
+                    Some(format!("{} days", days)),
+                ));
+            }
+            _ if repo.uncommitted_changes => raw.push((Check::UncommittedChanges, path, None)),
+            _ => {}
+        }
+        if repo.unpushed_commits {
+            raw.push((Check::UnpushedCommits, path, None));
+        }
+        if let GitStatus::Error(message) = &repo.status {
+            raw.push((Check::RepositoryError, path, Some(message.clone())));
+        }
+        if repo.ci == Some(CiStatus::Failed) {
+            raw.push((Check::CiFailed, path, Some(repo.branch.clone())));
+        }
+        if let Some(fork) = repo.fork.as_ref().filter(|fork| fork.behind > 0) {
+            raw.push((Check::ForkBehind, path, Some(fork.upstream_branch())));
+        }
+        if let Some(reviews) = &repo.reviews {
+            let references = |pull_requests: &[_]| {
+                pull_requests
+                    .iter()
+                    .map(|pull_request| reviews.reference(pull_request))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            if !reviews.unresolved.is_empty() {
+                raw.push((
+                    Check::UnresolvedReviews,
+                    path,
+                    Some(references(&reviews.unresolved)),
+                ));
+            }
+            if !reviews.merged.is_empty() {
+                raw.push((
+                    Check::MergedBranches,
+                    path,
+                    Some(references(&reviews.merged)),
+                ));
+            }
+        }
+        for issue in &repo.credentials {
+            let check = match issue.problem {
+                CredentialProblem::NoHelper => Check::CredentialHelperMissing,
+                CredentialProblem::PlaintextStore => Check::PlaintextCredentials,
+                CredentialProblem::EmbeddedCredentials => Check::EmbeddedCredentials,
+            };
+            raw.push((check, path, Some(issue.remote.clone())));
+        }
+        if let Some(nested) = &repo.nested {
+            raw.push((
+                Check::NestedRepository,
+                path,
+                Some(nested.outer.display().to_string()),
+            ));
+        }
+        if let Some(duplicate) = &repo.duplicate {
+            if let Some(freshest) = &duplicate.freshest {
+                let detail = if duplicate.stale {
+                    "stale, freshest clone"
+                } else {
+                    "freshest clone"
+                };
+                raw.push((
+                    Check::DuplicateClone,
+                    path,
+                    Some(format!("{}: {}", detail, freshest.display())),
+                ));
+            }
+        }
+        if let Some(disk) = repo.disk.filter(DiskUsage::artifacts_ballooning) {
+            let growth = disk.artifact_growth().unwrap_or_default().unsigned_abs();
+            raw.push((
+                Check::GrowingArtifacts,
+                path,
+                Some(format!(
+                    "+{} since the previous scan",
+                    display::bytes(growth)
+                )),
+            ));
+        }
+        for artifacts in &repo.stale_artifacts {
+            let dir = artifacts
+                .path
+                .strip_prefix(path)
+                .unwrap_or(&artifacts.path)
+                .display();
+            raw.push((
+                Check::StaleArtifacts,
+                path,
+                Some(format!("{}: {}", dir, display::bytes(artifacts.bytes))),
+            ));
+        }
+        for branch in &repo.stale_branches {
+            let detail = if branch.merged {
+                format!("{}: merged", branch.name)
+            } else {
+                format!(
+                    "{}: {} days without commits",
+                    branch.name,
+                    now.saturating_sub(branch.last_commit) / 86_400
+                )
+            };
+            raw.push((Check::StaleBranch, path, Some(detail)));
+        }
+        let old_stashes: Vec<&StashEntry> = repo
+            .stashes
+            .iter()
+            .filter(|stash| stash.is_old(now))
+            .collect();
+        if let Some(oldest) = old_stashes.iter().min_by_key(|stash| stash.created) {
+            let days = now.saturating_sub(oldest.created) / 86_400;
+            raw.push((
+                Check::OldStash,
+                path,
+                Some(format!(
+                    "{} older than a week, oldest {} days",
+                    old_stashes.len(),
+                    days
+                )),
+            ));
+        }
+        for exposure in &repo.exposures {
+            let check = match exposure.problem {
+                ExposureProblem::ReadableGitDirectory => Check::ReadableGitDirectory,
+                ExposureProblem::CommittedSecret(_) => Check::CommittedSecret,
+                ExposureProblem::ReadableSecret(_) => Check::ReadableSecret,
+            };
+            raw.push((check, path, Some(exposure.path.display().to_string())));
+        }
+        for commit in &repo.lost_commits {
+            raw.push((
+                Check::LostWork,
+                path,
+                Some(format!("{} {}", commit.short_hash(), commit.subject)),
+            ));
+        }
+        if let Some(last_commit) = repo.last_commit.filter(|_| repo.stale) {
+            let days = now.saturating_sub(last_commit) / 86_400;
+            raw.push((
+                Check::StaleRepository,
+                path,
+                Some(format!("last commit {} days ago", days)),
+            ));
+        }
+        if repo.archived {
+            let detail = if repo.has_local_work() {
+                "holds local work"
+            } else {
+                "nothing local, safe to remove"
+            };
+            raw.push((Check::ArchivedUpstream, path, Some(detail.to_string())));
+        }
+    }
+
+    for report in results.dependencies.iter().flatten() {
+        for error in &report.errors {
+            raw.push((
+                Check::ManifestError,
+                &report.project_path,
+                Some(error.clone()),
+            ));
+        }
+        for issue in &report.lockfiles {
+            let (check, detail) = match issue.problem {
+                LockfileProblem::Missing => {
+                    (Check::MissingLockfile, format!("run {}", issue.action))
+                }
+                LockfileProblem::OutOfSync => (
+                    Check::LockfileOutOfSync,
+                    format!("{}; run {}", issue.dependencies.join(", "), issue.action),
+                ),
+                LockfileProblem::Stale => (Check::StaleLockfile, format!("run {}", issue.action)),
+            };
+            raw.push((check, &issue.manifest, Some(detail)));
+        }
+        for module in &report.go_modules {
+            for replace in &module.replaces {
+                raw.push((
+                    Check::GoReplace,
+                    &module.manifest,
+                    Some(replace.to_string()),
+                ));
+            }
+        }
+    }
+
+    for vuln in results
+        .audit
+        .iter()
+        .flat_map(|report| &report.vulnerabilities)
+    {
+        let detail = format!(
+            "{}@{} ({})",
+            vuln.dependency.name, vuln.version, vuln.advisory_id
+        );
+        let check = if vuln.dependency.dependency_type.is_runtime() {
+            Check::VulnerableDependency
+        } else {
+            Check::VulnerableDevDependency
+        };
+        raw.push((check, &vuln.dependency.source_file, Some(detail)));
+    }
+
+    for report in results.containers.iter().flatten() {
+        let path = report.environment.project_path.as_path();
+        for tool in report.tools.iter().filter(|tool| tool.differs()) {
+            raw.push((Check::ToolchainMismatch, path, Some(tool.tool.clone())));
+        }
+        for error in &report.errors {
+            raw.push((Check::ContainerConfigError, path, Some(error.clone())));
+        }
+    }
+
+    for duplicate in results
+        .system
+        .iter()
+        .flat_map(|snapshot| &snapshot.duplicates)
+    {
+        let shadowed: Vec<String> = duplicate
+            .shadowed()
+            .iter()
+            .map(|installation| installation.path.display().to_string())
+            .collect();
+        let detail = format!("{} ({})", duplicate.name, shadowed.join(", "));
+        raw.push((
+            Check::DuplicateBinary,
+            &duplicate.winner().path,
+            Some(detail),
+        ));
+    }
+
+    if let Some(snapshot) = &results.system {
+        if let Some(offset) = snapshot
+            .clock
+            .as_ref()
+            .filter(|clock| clock.is_skewed())
+            .and_then(|clock| clock.offset_secs)
+        {
+            raw.push((
+                Check::ClockSkew,
+                &snapshot.disk_path,
+                Some(format!("{:+} s", offset)),
+            ));
+        }
+        for bundle in &snapshot.certificates {
+            if let Some(problem) = bundle.problem {
+                let source = bundle.variable.as_deref().unwrap_or("system");
+                raw.push((
+                    Check::BrokenCaBundle,
+                    &bundle.path,
+                    Some(format!("{} ({})", problem.name(), source)),
+                ));
+            }
+        }
+        if let Some(proxy) = &snapshot.proxy {
+            if proxy.is_mismatched() {
+                let tools: Vec<String> = proxy
+                    .tools
+                    .iter()
+                    .map(|tool| {
+                        format!(
+                            "{} {}",
+                            tool.tool,
+                            tool.proxy.as_deref().unwrap_or("direct")
+                        )
+                    })
+                    .collect();
+                raw.push((
+                    Check::ProxyMismatch,
+                    &snapshot.disk_path,
+                    Some(tools.join(", ")),
+                ));
+            }
+            for endpoint in proxy.intercepted() {
+                let detail = match &endpoint.issuer {
+                    Some(issuer) => format!("{}: {}", endpoint.name, issuer),
+                    None => format!("{}: untrusted certificate", endpoint.name),
+                };
+                raw.push((Check::TlsInterception, &snapshot.disk_path, Some(detail)));
+            }
+        }
+        if let Some(limits) = &snapshot.limits {
+            if let Some(inodes) = limits.inodes.as_ref().filter(|inodes| inodes.is_low()) {
+                raw.push((
+                    Check::LowInodes,
+                    &inodes.path,
+                    Some(format!("{}% free", inodes.available_percent())),
+                ));
+            }
+            if let Some(open_files) = limits.open_files.filter(|open_files| open_files.is_low()) {
+                raw.push((
+                    Check::LowFileLimit,
+                    &snapshot.disk_path,
+                    Some(format!("ulimit -n {}", open_files.soft)),
+                ));
+            }
+        }
+        if let Some(git_config) = &snapshot.git_config {
+            for setting in &git_config.deviations {
+                let current = setting.current.as_deref().unwrap_or("unset");
+                let detail = format!(
+                    "{}={} (recommended {})",
+                    setting.key, current, setting.recommended
+                );
+                raw.push((
+                    Check::GitConfigRecommendation,
+                    &git_config.path,
+                    Some(detail),
+                ));
+            }
+        }
+        if let Some(locale) = &snapshot.locale {
+            if let Some(problem) = locale.problem {
+                let detail = match (&locale.variable, &locale.locale, &locale.encoding) {
+                    (Some(variable), Some(value), _) => {
+                        format!("{}={} ({})", variable, value, problem.name())
+                    }
+                    (_, _, Some(encoding)) => format!("{} ({})", encoding, problem.name()),
+                    _ => problem.name().to_string(),
+                };
+                raw.push((Check::NonUtf8Locale, &snapshot.disk_path, Some(detail)));
+            }
+        }
+        if let Some(exclusions) = &snapshot.exclusions {
+            for dir in &exclusions.unexcluded {
+                raw.push((
+                    Check::UnexcludedBuildDirectory,
+                    &dir.path,
+                    Some(exclusions.scanner.name().to_string()),
+                ));
+            }
+        }
+    }
+
+    for report in results.runtimes.iter() {
+        for shadowing in &report.shadowing {
+            let shadowed = &shadowing.shadowed;
+            let detail = format!("{} {}", shadowed.source(), shadowed.path.display());
+            raw.push((Check::ShadowedRuntime, &shadowing.active.path, Some(detail)));
+        }
+        for pin in report
+            .projects
+            .iter()
+            .flat_map(|project| project.mismatches())
+        {
+            let requested = format!("{} {}", pin.runtime, pin.requested.join(" "));
+            let detail = match &pin.active {
+                Some(active) => format!("{} ≠ {}", requested, active),
+                None => requested,
+            };
+            raw.push((Check::RuntimeVersionMismatch, &pin.file, Some(detail)));
+        }
+    }
+
+    for report in results.workspaces.iter().flatten() {
+        for package in report
+            .packages
+            .iter()
+            .filter(|package| !package.errors.is_empty())
+        {
+            raw.push((
+                Check::WorkspaceManifestError,
+                &package.path,
+                Some(package.name.clone()),
+            ));
+        }
+        for cycle in &report.cycles {
+            raw.push((
+                Check::DependencyCycle,
+                &report.root,
+                Some(cycle.join(" → ")),
+            ));
+        }
+        for conflict in &report.conflicts {
+            raw.push((
+                Check::VersionConflict,
+                &report.root,
+                Some(conflict.name.clone()),
+            ));
+        }
+    }
+
+    for report in results.releases.iter().flatten() {
+        let path = report.repo_path.as_path();
+        let version = report.version.clone().unwrap_or_default();
+        match report.status {
+            VersionStatus::Mismatch => raw.push((Check::VersionMismatch, path, Some(version))),
+            VersionStatus::BumpMissing => {
+                raw.push((Check::VersionBumpMissing, path, Some(version)))
+            }
+            _ => {}
+        }
+        if let Some(changelog) = report.changelog.as_ref().filter(|c| Changelog::is_stale(c)) {
+            raw.push((Check::StaleChangelog, &changelog.path, None));
+        }
+    }
+
+    for project in results.cargo.iter().flatten() {
+        for finding in &project.findings {
+            let (check, detail) = match finding {
+                CargoFinding::MissingRustVersion => (Check::MissingRustVersion, None),
+                CargoFinding::OutdatedEdition { edition } => {
+                    (Check::OutdatedEdition, Some(edition.clone()))
+                }
+                CargoFinding::RustVersionTooLow { dependency, .. } => {
+                    (Check::RustVersionTooLow, Some(dependency.clone()))
+                }
+                CargoFinding::UnusedFeature { feature } => {
+                    (Check::UnusedFeature, Some(feature.clone()))
+                }
+                CargoFinding::HeavyDefaultDependency { dependency, .. } => {
+                    (Check::HeavyDefaultDependency, Some(dependency.clone()))
+                }
+            };
+            raw.push((check, &project.path, detail));
+        }
+    }
+
+    for analysis in results.analysis.iter().flatten() {
+        for check in &analysis.checks {
+            let kind = match check.outcome {
+                CheckOutcome::Failed => Check::CheckFailed,
+                CheckOutcome::TimedOut => Check::CheckTimedOut,
+                CheckOutcome::Passed | CheckOutcome::Unavailable => continue,
+            };
+            raw.push((kind, &analysis.path, Some(check.command.clone())));
+        }
+    }
+
+    // Nothing can be pushed to or fixed in an archived repository, so its
+    // findings and those of projects inside it are capped
+    let archived: Vec<&Path> = results
+        .git
+        .iter()
+        .flatten()
+        .filter(|repo| repo.archived)
+        .map(|repo| repo.path.as_path())
+        .collect();
+    let acknowledged: Vec<FindingId> = results
+        .acknowledged
+        .iter()
+        .filter_map(|ack| ack.finding().map(FindingId::canonicalized))
+        .collect();
+    let mut findings: Vec<Finding> = raw
+        .into_iter()
+        .filter(|(check, path, _)| {
+            check.attention().is_none_or(|kind| {
+                !acknowledged
+                    .iter()
+                    .any(|finding| finding.covers_path(kind, path))
+            })
+        })
+        .filter_map(|(check, path, detail)| {
+            let applying = if config.uses_tags() || checks.uses_tags() {
+                tags.tags_for(path)
+            } else {
+                BTreeSet::new()
+            };
+            let canonical = tags::canonical(path);
+            let mut severity = config.severity_of(check, &canonical, &applying);
+            if !check.concerns_secrets() && archived.iter().any(|repo| path.starts_with(repo)) {
+                severity = severity.min(config.archived_level);
+            }
+            checks
+                .is_enabled(check, &canonical, &applying)
+                .then(|| Finding {
+                    rule: check.rule_id().to_string(),
+                    check,
+                    severity,
+                    path: path.to_path_buf(),
+                    detail,
+                })
+        })
+        .collect();
+    findings.sort_by_key(|finding| Reverse(finding.severity));
+    findings
+}
+
+/// An entry of the rule catalog listed by `devhealth rules`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Rule {
+    /// Stable rule ID, such as `GIT001`
+    pub id: &'static str,
+    /// Name of the check in `[severity.checks]`
+    pub check: Check,
+    /// Severity for repositories no override applies to
+    pub severity: Severity,
+    /// Whether the check reports findings for repositories no override
+    /// applies to
+    pub enabled: bool,
+    /// Localized description of the check
+    pub description: &'static str,
+}
+
+/// Returns the rule catalog in rule ID order
+///
+/// Severities include `[severity.checks]` and enablement `[checks]
+/// disabled` from the configuration, but not the path or tag overrides.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::findings::{self, ChecksConfig, SeverityConfig};
+///
+/// let rules = findings::catalog(&SeverityConfig::default(), &ChecksConfig::default());
+/// assert_eq!(rules[0].id, "GIT001");
+/// ```
+pub fn catalog(config: &SeverityConfig, checks: &ChecksConfig) -> Vec<Rule> {
+    Check::ALL
+        .into_iter()
+        .map(|check| Rule {
+            id: check.rule_id(),
+            check,
+            severity: config
+                .checks
+                .get(&check)
+                .copied()
+                .unwrap_or_else(|| check.default_severity()),
+            enabled: !checks.disabled.contains(&check),
+            description: check.describe(),
+        })
+        .collect()
+}
+
+/// Prints the rule catalog, one rule per line
+pub fn display_rules(rules: &[Rule]) {
+    println!(
+        "{}",
+        display::header(&tr!("rules-title", count = rules.len()), "📖", Role::Accent)
+    );
+    let width = rules
+        .iter()
+        .map(|rule| rule.check.name().len())
+        .max()
+        .unwrap_or(0);
+    for (index, rule) in rules.iter().enumerate() {
+        let line = format!(
+            "{} {:<8} {:<width$} {}",
+            rule.id.paint(Role::Accent).bold(),
+            if rule.enabled {
+                rule.severity.name().paint(rule.severity.role())
+            } else {
+                "off".paint(Role::Muted)
+            },
+            rule.check.name().paint(Role::Highlight),
+            rule.description,
+            width = width
+        );
+        println!("{}", display::tree_item(&line, index == rules.len() - 1, 0));
+    }
+}
+
+/// Returns `true` if any finding is at least as severe as `level`
+pub fn reaches(findings: &[Finding], level: Severity) -> bool {
+    findings.iter().any(|finding| finding.severity >= level)
+}
+
+/// Prints the number of findings per severity and lists the findings
+///
+/// `Summary` detail prints only the counts, `Full` lists every finding and
+/// the others list findings rated `error` or worse.
+pub fn display_findings(findings: &[Finding], detail: Detail) {
+    if findings.is_empty() {
+        return;
+    }
+
+    println!();
+    let worst = findings
+        .iter()
+        .map(|finding| finding.severity)
+        .max()
+        .unwrap_or(Severity::Info);
+    println!(
+        "{}",
+        display::header(
+            &tr!("findings-title", count = findings.len()),
+            "🚦",
+            worst.role()
+        )
+    );
+    let summary_items: Vec<(&str, String)> = Severity::ALL
+        .into_iter()
+        .map(|severity| {
+            let count = findings
+                .iter()
+                .filter(|finding| finding.severity == severity)
+                .count();
+            (severity.label(), count.to_string())
+        })
+        .collect();
+    print!("{}", display::summary_box(&summary_items));
+
+    if detail == Detail::Summary {
+        return;
+    }
+    let shown: Vec<&Finding> = findings
+        .iter()
+        .filter(|finding| detail == Detail::Full || finding.severity >= Severity::Error)
+        .collect();
+    for (index, finding) in shown.iter().enumerate() {
+        let role = finding.severity.role();
+        let mut line = format!(
+            "{} {} {}",
+            format!("[{}]", finding.severity.label()).paint(role).bold(),
+            finding.rule.paint(Role::Accent),
+            finding.check.describe().paint(role)
+        );
+        if let Some(detail) = &finding.detail {
+            line.push_str(&format!(" {}", detail.paint(Role::Emphasis)));
+        }
+        line.push_str(&format!(
+            " {}",
+            finding.path.display().to_string().paint(Role::Muted)
+        ));
+        println!("{}", display::tree_item(&line, index == shown.len() - 1, 0));
+    }
+}
+
+/// Where in a file a finding points, for editor diagnostics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// The file, or the finding's directory if no file applies
+    pub file: PathBuf,
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column number, starting at 1
+    pub column: usize,
+}
+
+/// Finds the file and line a finding is about
+///
+/// Cargo findings point into the project's `Cargo.toml` and credential
+/// findings into the repository's `.git/config`, at the line naming the
+/// feature, dependency, setting or remote. A vulnerable dependency points at
+/// its line in the manifest. Everything else points at the start of the
+/// finding's path.
+pub fn locate(finding: &Finding) -> Location {
+    let detail = finding.detail.as_deref().unwrap_or_default();
+    let (file, needle) = match finding.check {
+        Check::VulnerableDependency | Check::VulnerableDevDependency => {
+            let package = detail.split('@').next().unwrap_or_default();
+            (finding.path.clone(), Some(package.to_string()))
+        }
+        Check::MissingRustVersion => (
+            finding.path.join("Cargo.toml"),
+            Some("[package]".to_string()),
+        ),
+        Check::OutdatedEdition => (finding.path.join("Cargo.toml"), Some("edition".to_string())),
+        Check::RustVersionTooLow => (
+            finding.path.join("Cargo.toml"),
+            Some("rust-version".to_string()),
+        ),
+        Check::UnusedFeature | Check::HeavyDefaultDependency => {
+            (finding.path.join("Cargo.toml"), Some(detail.to_string()))
+        }
+        Check::RuntimeVersionMismatch if finding.path.ends_with("package.json") => {
+            (finding.path.clone(), Some("volta".to_string()))
+        }
+        Check::RuntimeVersionMismatch => {
+            let runtime = detail.split(' ').next().unwrap_or_default();
+            (finding.path.clone(), Some(runtime.to_string()))
+        }
+        Check::CredentialHelperMissing
+        | Check::PlaintextCredentials
+        | Check::EmbeddedCredentials => (
+            finding.path.join(".git").join("config"),
+            Some(format!("[remote \"{}\"]", detail)),
+        ),
+        _ => (finding.path.clone(), None),
+    };
+    let file = if file.is_file() {
+        file
+    } else {
+        finding.path.clone()
+    };
+
+    let (line, column) = needle
+        .filter(|needle| !needle.is_empty())
+        .and_then(|needle| {
+            let content = std::fs::read_to_string(&file).ok()?;
+            find_word(&content, &needle)
+        })
+        .unwrap_or((1, 1));
+    Location {
+        file: std::path::absolute(&file).unwrap_or(file),
+        line,
+        column,
+    }
+}
+
+/// Returns the line and column of the first whole-word match of `needle`
+fn find_word(content: &str, needle: &str) -> Option<(usize, usize)> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    content.lines().enumerate().find_map(|(index, line)| {
+        line.match_indices(needle).find_map(|(start, _)| {
+            let before = line[..start].chars().next_back();
+            let after = line[start + needle.len()..].chars().next();
+            let bounded = !before.is_some_and(is_word) && !after.is_some_and(is_word);
+            bounded.then(|| (index + 1, line[..start].chars().count() + 1))
+        })
+    })
+}
+
+/// Formats a finding as a one-line editor diagnostic
+///
+/// The line reads `file:line:column: level: RULE description: detail`, the
+/// layout of compiler diagnostics that editor problem matchers parse.
+pub fn diagnostic(finding: &Finding) -> String {
+    let location = locate(finding);
+    let mut message = format!("{} {}", finding.rule, finding.check.describe());
+    if let Some(detail) = &finding.detail {
+        message.push_str(&format!(": {}", detail));
+    }
+    format!(
+        "{}:{}:{}: {}: {}",
+        location.file.display(),
+        location.line,
+        location.column,
+        finding.severity.diagnostic_level(),
+        message
+    )
+}
+
+/// Prints every finding as an editor diagnostic, one per line
+pub fn display_diagnostics(findings: &[Finding]) {
+    for finding in findings {
+        println!("{}", diagnostic(finding));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::acks::Acknowledgement;
+    use crate::scanner::git::GitRepo;
+
+    fn repo(path: &str, dirty: bool, unpushed: bool) -> GitRepo {
+        GitRepo {
+            uncommitted_changes: dirty,
+            unpushed_commits: unpushed,
+            ..GitRepo::test(path)
+        }
+    }
+
+    fn results(repos: Vec<GitRepo>) -> ScanResults {
+        ScanResults {
+            git: Some(repos),
+            ..Default::default()
+        }
+    }
+
+    mod severity_config {
+        use super::*;
+
+        #[test]
+        fn falls_back_to_default_severity() {
+            let config = SeverityConfig::default();
+            let path = Path::new("/src/api");
+            assert_eq!(
+                config.severity_of(Check::UnpushedCommits, path, &BTreeSet::new()),
+                Severity::Warn
+            );
+            assert_eq!(
+                config.severity_of(Check::EmbeddedCredentials, path, &BTreeSet::new()),
+                Severity::Critical
+            );
+        }
+
+        #[test]
+        fn last_matching_override_wins() {
+            let config: SeverityConfig = toml::from_str(
+                r#"
+checks = { unpushed-commits = "error" }
+
+[[overrides]]
+paths = ["/home/me/personal/**"]
+checks = { unpushed-commits = "info" }
+
+[[overrides]]
+tags = ["important"]
+checks = { unpushed-commits = "critical" }
+"#,
+            )
+            .unwrap();
+            let personal = Path::new("/home/me/personal/notes");
+            let important = BTreeSet::from(["important".to_string()]);
+            let untagged = BTreeSet::new();
+
+            let severity = |path, tags| config.severity_of(Check::UnpushedCommits, path, tags);
+            assert_eq!(severity(Path::new("/srv/api"), &untagged), Severity::Error);
+            assert_eq!(severity(personal, &untagged), Severity::Info);
+            assert_eq!(severity(personal, &important), Severity::Critical);
+            assert_eq!(
+                config.severity_of(Check::UncommittedChanges, personal, &important),
+                Severity::Warn
+            );
+        }
+
+        #[test]
+        fn rule_ids_are_unique_and_round_trip() {
+            let ids: BTreeSet<&str> = Check::ALL.iter().map(|check| check.rule_id()).collect();
+            assert_eq!(ids.len(), Check::ALL.len());
+            assert_eq!(Check::UnpushedCommits.rule_id(), "GIT001");
+            assert_eq!(Check::VulnerableDependency.rule_id(), "DEP014");
+            for check in Check::ALL {
+                assert_eq!(
+                    Check::from_rule_id(&check.rule_id().to_lowercase()),
+                    Some(check)
+                );
+                let name = serde_json::to_value(check).unwrap();
+                assert_eq!(
+                    name,
+                    check.name(),
+                    "name() should match the config spelling"
+                );
+            }
+            assert_eq!(Check::from_rule_id("GIT999"), None);
+        }
+
+        #[test]
+        fn catalog_uses_configured_severities() {
+            let config = SeverityConfig {
+                checks: BTreeMap::from([(Check::UnpushedCommits, Severity::Error)]),
+                ..Default::default()
+            };
+            let rules = catalog(&config, &ChecksConfig::default());
+            assert_eq!(rules.len(), Check::ALL.len());
+            assert_eq!(
+                (rules[0].check, rules[0].severity),
+                (Check::UnpushedCommits, Severity::Error)
+            );
+            assert_eq!(
+                rules[1].severity,
+                Check::UncommittedChanges.default_severity()
+            );
+        }
+
+        #[test]
+        fn rejects_unknown_checks_and_levels() {
+            assert!(
+                toml::from_str::<SeverityConfig>("checks = { no-such-check = \"info\" }").is_err()
+            );
+            assert!(toml::from_str::<SeverityConfig>("fail_level = \"fatal\"").is_err());
+        }
+    }
+
+    mod collect {
+        use super::*;
+
+        #[test]
+        fn rates_repository_findings_most_severe_first() {
+            let mut leaky = repo("/src/leaky", false, true);
+            leaky
+                .credentials
+                .push(crate::scanner::git::CredentialIssue {
+                    remote: "origin".to_string(),
+                    url: "https://example.com/repo.git".to_string(),
+                    problem: CredentialProblem::EmbeddedCredentials,
+                });
+            let results = results(vec![repo("/src/clean", false, false), leaky]);
+
+            let found = collect(
+                &results,
+                &SeverityConfig::default(),
+                &ChecksConfig::default(),
+                &TagFilter::default(),
+            );
+            let checks: Vec<(Check, Severity)> =
+                found.iter().map(|f| (f.check, f.severity)).collect();
+            assert_eq!(
+                checks,
+                vec![
+                    (Check::EmbeddedCredentials, Severity::Critical),
+                    (Check::UnpushedCommits, Severity::Warn)
+                ]
+            );
+            assert_eq!(found[0].detail.as_deref(), Some("origin"));
+            assert!(reaches(&found, Severity::Critical));
+        }
+
+        #[test]
+        fn applies_configured_severities() {
+            let results = results(vec![repo("/src/api", true, false)]);
+            let config = SeverityConfig {
+                checks: BTreeMap::from([(Check::UncommittedChanges, Severity::Info)]),
+                ..Default::default()
+            };
+
+            let found = collect(
+                &results,
+                &config,
+                &ChecksConfig::default(),
+                &TagFilter::default(),
+            );
+            assert_eq!(found[0].severity, Severity::Info);
+            assert!(!reaches(&found, Severity::Warn));
+        }
+
+        #[test]
+        fn escalates_stale_uncommitted_changes() {
+            let mut stale = repo("/src/api", true, false);
+            stale.uncommitted_since = Some(0);
+            let mut fresh = repo("/src/web", true, false);
+            fresh.uncommitted_since = Some(20 * 86_400);
+            let mut results = results(vec![stale, fresh]);
+            results.run = Some(crate::report::RunMetadata {
+                generated_at: 21 * 86_400,
+                ..crate::report::RunMetadata::capture(&[], &[])
+            });
+
+            let found = collect(
+                &results,
+                &SeverityConfig::default(),
+                &ChecksConfig::default(),
+                &TagFilter::default(),
+            );
+            let checks: Vec<(Check, Severity)> =
+                found.iter().map(|f| (f.check, f.severity)).collect();
+            assert_eq!(
+                checks,
+                vec![
+                    (Check::StaleUncommittedChanges, Severity::Error),
+                    (Check::UncommittedChanges, Severity::Warn)
+                ]
+            );
+            assert_eq!(found[0].detail.as_deref(), Some("21 days"));
+
+            let config = SeverityConfig {
+                stale_uncommitted_days: 30,
+                ..Default::default()
+            };
+            let found = collect(
+                &results,
+                &config,
+                &ChecksConfig::default(),
+                &TagFilter::default(),
+            );
+            assert!(found.iter().all(|f| f.check == Check::UncommittedChanges));
+        }
+
+        #[test]
+        fn caps_findings_of_archived_repositories() {
+            let mut archived = repo("/src/old", true, true);
+            archived.archived = true;
+            archived
+                .credentials
+                .push(crate::scanner::git::CredentialIssue {
+                    remote: "origin".to_string(),
+                    url: "https://example.com/old.git".to_string(),
+                    problem: CredentialProblem::EmbeddedCredentials,
+                });
+            let mut done = repo("/src/done", false, false);
+            done.archived = true;
+            let results = results(vec![archived, done]);
+
+            let found = collect(
+                &results,
+                &SeverityConfig::default(),
+                &ChecksConfig::default(),
+                &TagFilter::default(),
+            );
+            let checks: Vec<(Check, Severity, Option<&str>)> = found
+                .iter()
+                .map(|f| (f.check, f.severity, f.detail.as_deref()))
+                .collect();
+            assert_eq!(
+                checks,
+                vec![
+                    (
+                        Check::EmbeddedCredentials,
+                        Severity::Critical,
+                        Some("origin")
+                    ),
+                    (Check::UncommittedChanges, Severity::Info, None),
+                    (Check::UnpushedCommits, Severity::Info, None),
+                    (
+                        Check::ArchivedUpstream,
+                        Severity::Info,
+                        Some("holds local work")
+                    ),
+                    (
+                        Check::ArchivedUpstream,
+                        Severity::Info,
+                        Some("nothing local, safe to remove")
+                    ),
+                ]
+            );
+
+            let config = SeverityConfig {
+                archived_level: Severity::Critical,
+                ..Default::default()
+            };
+            let found = collect(
+                &results,
+                &config,
+                &ChecksConfig::default(),
+                &TagFilter::default(),
+            );
+            assert!(found
+                .iter()
+                .any(|f| f.check == Check::UncommittedChanges && f.severity == Severity::Warn));
+        }
+
+        #[test]
+        fn skips_acknowledged_findings() {
+            let mut results = results(vec![repo("/src/api", true, true)]);
+            results.acknowledged.push(Acknowledgement {
+                id: "dirty:/src/api".to_string(),
+                until: None,
+                reason: None,
+                created_at: 0,
+            });
+
+            let found = collect(
+                &results,
+                &SeverityConfig::default(),
+                &ChecksConfig::default(),
+                &TagFilter::default(),
+            );
+            let checks: Vec<Check> = found.iter().map(|f| f.check).collect();
+            assert_eq!(checks, vec![Check::UnpushedCommits]);
+        }
+
+        #[test]
+        fn skips_disabled_checks() {
+            let results = results(vec![
+                repo("/src/api", true, true),
+                repo("/home/dev/personal/blog", true, true),
+            ]);
+            let checks = ChecksConfig {
+                disabled: vec![Check::UncommittedChanges],
+                overrides: vec![
+                    CheckOverride {
+                        paths: vec!["/home/dev/personal/**".to_string()],
+                        disabled: vec![Check::UnpushedCommits],
+                        ..Default::default()
+                    },
+                    CheckOverride {
+                        paths: vec!["/src/api".to_string()],
+                        enabled: vec![Check::UncommittedChanges],
+                        ..Default::default()
+                    },
+                ],
+            };
+
+            let found = collect(
+                &results,
+                &SeverityConfig::default(),
+                &checks,
+                &TagFilter::default(),
+            );
+            let found: Vec<(&str, Check)> = found
+                .iter()
+                .map(|f| (f.path.to_str().unwrap(), f.check))
+                .collect();
+            assert_eq!(
+                found,
+                vec![
+                    ("/src/api", Check::UncommittedChanges),
+                    ("/src/api", Check::UnpushedCommits)
+                ]
+            );
+            assert!(!catalog(&SeverityConfig::default(), &checks)[1].enabled);
+        }
+
+        #[test]
+        fn separates_runtime_from_development_vulnerabilities() {
+            use crate::scanner::audit::{AuditReport, Vulnerability};
+            use crate::scanner::deps::{Dependency, DependencyType, Ecosystem};
+
+            let vulnerability = |name: &str, dependency_type: DependencyType| Vulnerability {
+                advisory_id: format!("GHSA-{}", name),
+                summary: String::new(),
+                aliases: Vec::new(),
+                dependency: Dependency {
+                    name: name.to_string(),
+                    version: "1.0.0".to_string(),
+                    dependency_type,
+                    ecosystem: Ecosystem::NodeJs,
+                    source_file: PathBuf::from("/src/api/package.json"),
+                    extras: Vec::new(),
+                    marker: None,
+                },
+                version: "1.0.0".to_string(),
+                fixed_version: None,
+            };
+            let results = ScanResults {
+                audit: Some(AuditReport {
+                    vulnerabilities: vec![
+                        vulnerability("jest", DependencyType::Development),
+                        vulnerability("axios", DependencyType::Runtime),
+                    ],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+
+            let found = collect(
+                &results,
+                &SeverityConfig::default(),
+                &ChecksConfig::default(),
+                &TagFilter::default(),
+            );
+            let checks: Vec<(Check, Severity)> =
+                found.iter().map(|f| (f.check, f.severity)).collect();
+            assert_eq!(
+                checks,
+                vec![
+                    (Check::VulnerableDependency, Severity::Error),
+                    (Check::VulnerableDevDependency, Severity::Warn)
+                ]
+            );
+
+            let config = SeverityConfig {
+                checks: BTreeMap::from([(Check::VulnerableDevDependency, Severity::Info)]),
+                ..Default::default()
+            };
+            let found = collect(
+                &results,
+                &config,
+                &ChecksConfig::default(),
+                &TagFilter::default(),
+            );
+            assert_eq!(
+                found[1].severity,
+                Severity::Info,
+                "Thresholds are set per check"
+            );
+        }
+    }
+    mod diagnostics {
+        use super::*;
+        use tempfile::TempDir;
+
+        fn finding(check: Check, path: &Path, detail: Option<&str>) -> Finding {
+            Finding {
+                rule: check.rule_id().to_string(),
+                check,
+                severity: check.default_severity(),
+                path: path.to_path_buf(),
+                detail: detail.map(str::to_string),
+            }
+        }
+
+        #[test]
+        fn points_at_the_vulnerable_dependency() {
+            let dir = TempDir::new().unwrap();
+            let manifest = dir.path().join("Cargo.toml");
+            std::fs::write(
+                &manifest,
+                "[dependencies]\nserde_json = \"1\"\nserde = \"1.0.150\"\n",
+            )
+            .unwrap();
+
+            let found = finding(
+                Check::VulnerableDependency,
+                &manifest,
+                Some("serde@1.0.150 (RUSTSEC-1)"),
+            );
+            assert_eq!(
+                locate(&found),
+                Location {
+                    file: manifest.clone(),
+                    line: 3,
+                    column: 1
+                }
+            );
+            assert_eq!(
+                diagnostic(&found),
+                format!(
+                    "{}:3:1: error: DEP014 {}: serde@1.0.150 (RUSTSEC-1)",
+                    manifest.display(),
+                    Check::VulnerableDependency.describe()
+                )
+            );
+        }
+
+        #[test]
+        fn points_into_cargo_manifests_and_falls_back_to_the_path() {
+            let dir = TempDir::new().unwrap();
+            std::fs::write(
+                dir.path().join("Cargo.toml"),
+                "[package]\nname = \"api\"\n  edition = \"2018\"\n",
+            )
+            .unwrap();
+
+            let edition = locate(&finding(Check::OutdatedEdition, dir.path(), Some("2018")));
+            assert_eq!(
+                (
+                    edition.file.file_name().unwrap(),
+                    edition.line,
+                    edition.column
+                ),
+                ("Cargo.toml".as_ref(), 3, 3)
+            );
+
+            let dirty = locate(&finding(Check::UncommittedChanges, dir.path(), None));
+            assert_eq!(
+                (dirty.file, dirty.line, dirty.column),
+                (dir.path().to_path_buf(), 1, 1)
+            );
+        }
+    }
+}
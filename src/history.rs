@@ -0,0 +1,829 @@
+//! Scan history
+//!
+//! With `history = true` under `[scan]` in `devhealth.toml`, every `check`
+//! and `scan` records a [`ReportSummary`] in `scan-history.json` inside the
+//! DevHealth data directory: how many repositories are dirty or have
+//! unpushed commits, how many dependencies are vulnerable and how many
+//! findings there are per severity.
+//!
+//! Runs are recorded per combination of scanned paths and scanners, since a
+//! quick `check` and a full `scan --audit` of the same directory count
+//! different things. The next run with the same paths and scanners reports
+//! what changed since, in text and JSON output alike (see
+//! [`ReportChanges`]).
+//!
+//! Earlier runs are kept too, within the [`Retention`] configured by
+//! `history_keep_runs` and `history_keep_days` under `[scan]`, so scheduled
+//! scans do not grow the history without bound. `devhealth history prune`
+//! applies the retention on demand, optionally with tighter limits.
+//!
+//! Each run also keeps its findings, so `devhealth history list` can show
+//! stored runs and `devhealth history show` when each finding first appeared
+//! and when it was fixed (see [`timelines`]), both narrowed down by a
+//! [`HistoryQuery`].
+
+use crate::acks;
+use crate::config::ScanConfig;
+use crate::findings::{Finding, Severity};
+use crate::i18n::t;
+use crate::report::{ReportChanges, ReportSummary, RunMetadata, ScanResults};
+use crate::tr;
+use crate::utils::display;
+use crate::utils::paths;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the scan history inside the data directory
+pub const HISTORY_FILE: &str = "scan-history.json";
+
+/// Runs kept per combination of paths and scanners unless configured
+pub const DEFAULT_KEEP_RUNS: usize = 30;
+
+/// Days runs are kept unless configured
+pub const DEFAULT_KEEP_DAYS: u64 = 180;
+
+/// Seconds in a day
+const DAY: u64 = 24 * 60 * 60;
+
+/// Errors that can occur while reading or writing the scan history
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("Failed to access scan history: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse scan history: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error("Could not determine the DevHealth data directory")]
+    NoStateDirectory,
+}
+
+/// A run recorded for a combination of paths and scanners
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanRecord {
+    /// When the run happened, as a Unix timestamp
+    pub at: u64,
+    /// What needed attention
+    pub summary: ReportSummary,
+    /// The findings of the run; empty for runs recorded before findings
+    /// were kept
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<RecordedFinding>,
+}
+
+/// A finding as kept in the history
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct RecordedFinding {
+    /// Stable rule ID, such as `DEP014`
+    pub rule: String,
+    /// Repository, project or file the finding applied to
+    pub path: PathBuf,
+    /// What exactly was affected, such as a package name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// How serious it was
+    pub severity: Severity,
+}
+
+impl From<&Finding> for RecordedFinding {
+    fn from(finding: &Finding) -> Self {
+        RecordedFinding {
+            rule: finding.rule.clone(),
+            path: finding.path.clone(),
+            detail: finding.detail.clone(),
+            severity: finding.severity,
+        }
+    }
+}
+
+impl RecordedFinding {
+    /// Returns `true` for the same finding in another run, whatever its
+    /// severity then
+    fn same_as(&self, other: &RecordedFinding) -> bool {
+        self.rule == other.rule && self.path == other.path && self.detail == other.detail
+    }
+}
+
+/// Which stored runs and findings `history list` and `history show` cover
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryQuery {
+    /// Only runs at or after this Unix timestamp
+    pub since: Option<u64>,
+    /// Only findings in this repository: a name matching whole path
+    /// components, such as `api` or `org/api`, or an absolute path
+    pub repo: Option<String>,
+    /// Only findings of this rule ID, such as `DEP014` (case-insensitive)
+    pub finding: Option<String>,
+}
+
+impl HistoryQuery {
+    /// Returns `true` if the query narrows down findings, not only runs
+    pub fn filters_findings(&self) -> bool {
+        self.repo.is_some() || self.finding.is_some()
+    }
+
+    /// Returns `true` if a run at `at` is covered
+    pub fn covers_run(&self, at: u64) -> bool {
+        self.since.is_none_or(|since| at >= since)
+    }
+
+    /// Returns `true` if `finding` is covered
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::findings::Severity;
+    /// use devhealth::history::{HistoryQuery, RecordedFinding};
+    ///
+    /// let finding = RecordedFinding {
+    ///     rule: "DEP014".to_string(),
+    ///     path: "/src/org/api".into(),
+    ///     detail: None,
+    ///     severity: Severity::Error,
+    /// };
+    /// let query = |repo: &str| HistoryQuery { repo: Some(repo.to_string()), ..Default::default() };
+    /// assert!(query("api").covers(&finding));
+    /// assert!(query("org/api").covers(&finding));
+    /// assert!(!query("ap").covers(&finding));
+    /// ```
+    pub fn covers(&self, finding: &RecordedFinding) -> bool {
+        let rule = self
+            .finding
+            .as_ref()
+            .is_none_or(|rule| rule.eq_ignore_ascii_case(&finding.rule));
+        rule && self
+            .repo
+            .as_ref()
+            .is_none_or(|repo| in_repository(&finding.path, repo))
+    }
+}
+
+/// Returns `true` if `path` lies in the repository named by `repo`
+fn in_repository(path: &Path, repo: &str) -> bool {
+    let repo = Path::new(repo);
+    if repo.is_absolute() {
+        return path.starts_with(repo);
+    }
+    let names = |path: &Path| -> Vec<String> {
+        path.components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect()
+    };
+    let (path, repo) = (names(path), names(repo));
+    !repo.is_empty()
+        && path
+            .windows(repo.len())
+            .any(|window| window == repo.as_slice())
+}
+
+/// A stored run, as listed by `history list`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RunEntry {
+    /// The scanned paths and scanners, see [`ScanHistory::key`]
+    pub key: String,
+    /// When the run happened, as a Unix timestamp
+    pub at: u64,
+    /// What needed attention
+    pub summary: ReportSummary,
+    /// The run's findings covered by the query
+    pub findings: Vec<RecordedFinding>,
+}
+
+/// A stretch of runs in which a finding was present
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Period {
+    /// The first run with the finding, as a Unix timestamp
+    pub appeared: u64,
+    /// The first later run without it, `None` while it is still present
+    pub fixed: Option<u64>,
+}
+
+/// When a finding appeared and was fixed across the runs of one key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Timeline {
+    /// The scanned paths and scanners whose runs saw the finding
+    pub key: String,
+    /// The finding, with its severity in the latest run that had it
+    pub finding: RecordedFinding,
+    /// Stretches in which the finding was present, oldest first
+    pub periods: Vec<Period>,
+}
+
+/// How much of the history is kept
+///
+/// A limit of zero means no limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    /// Runs kept per combination of paths and scanners
+    pub runs: usize,
+    /// Days after which runs and recorded sizes are dropped
+    pub days: u64,
+}
+
+impl Retention {
+    /// Returns the retention configured under `[scan]`
+    pub fn from_config(config: &ScanConfig) -> Retention {
+        Retention {
+            runs: config.history_keep_runs,
+            days: config.history_keep_days,
+        }
+    }
+
+    /// Returns the oldest timestamp still kept at `now`, `0` without an age limit
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::history::Retention;
+    ///
+    /// let retention = Retention { runs: 10, days: 1 };
+    /// assert_eq!(retention.oldest(100_000), 100_000 - 86_400);
+    /// assert_eq!(Retention { runs: 10, days: 0 }.oldest(100_000), 0);
+    /// ```
+    pub fn oldest(&self, now: u64) -> u64 {
+        if self.days == 0 {
+            0
+        } else {
+            now.saturating_sub(self.days.saturating_mul(DAY))
+        }
+    }
+}
+
+/// Summaries of past runs, by scanned paths and scanners
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanHistory {
+    /// Runs per key, see [`ScanHistory::key`], oldest first
+    ///
+    /// Histories written before earlier runs were kept hold a single run
+    /// per key, which is read as a list of one.
+    #[serde(deserialize_with = "runs_of_any_version")]
+    pub runs: BTreeMap<String, Vec<ScanRecord>>,
+}
+
+/// The runs stored under a key, in the current or the single-run format
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredRuns {
+    Many(Vec<ScanRecord>),
+    One(ScanRecord),
+}
+
+fn runs_of_any_version<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, Vec<ScanRecord>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let runs = BTreeMap::<String, StoredRuns>::deserialize(deserializer)?;
+    Ok(runs
+        .into_iter()
+        .map(|(key, runs)| match runs {
+            StoredRuns::Many(runs) => (key, runs),
+            StoredRuns::One(run) => (key, vec![run]),
+        })
+        .collect())
+}
+
+impl ScanHistory {
+    /// Returns the location of the history inside the data directory
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HistoryError::NoStateDirectory`] if no data directory can be
+    /// determined.
+    pub fn default_path() -> Result<PathBuf, HistoryError> {
+        paths::data_dir()
+            .map(|dir| dir.join(HISTORY_FILE))
+            .ok_or(HistoryError::NoStateDirectory)
+    }
+
+    /// Loads the history from `path`, returning an empty history if missing
+    pub fn load(path: &Path) -> Result<ScanHistory, HistoryError> {
+        if !path.exists() {
+            return Ok(ScanHistory::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the history to `path`, creating parent directories if needed
+    pub fn save(&self, path: &Path) -> Result<(), HistoryError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Returns the key runs are recorded under: the scanned paths and the
+    /// scanners that ran
+    pub fn key(run: &RunMetadata) -> String {
+        let paths: Vec<String> = run
+            .paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        format!("{} [{}]", paths.join(", "), run.scanners.join(", "))
+    }
+
+    /// Records the summary of `results` after the previous runs with the
+    /// same paths and scanners, and returns what changed since the latest
+    ///
+    /// Returns `None` for the first run, or for results without
+    /// [`RunMetadata`], which are not recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::history::ScanHistory;
+    /// use devhealth::report::{RunMetadata, ScanResults};
+    ///
+    /// let mut history = ScanHistory::default();
+    /// let results = ScanResults {
+    ///     run: Some(RunMetadata::capture(&[".".into()], &["git"])),
+    ///     git: Some(Vec::new()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(history.record(&results, 1_000), None);
+    /// let changes = history.record(&results, 2_000).unwrap();
+    /// assert_eq!(changes.since, 1_000);
+    /// assert!(changes.deltas.is_empty());
+    /// ```
+    pub fn record(&mut self, results: &ScanResults, now: u64) -> Option<ReportChanges> {
+        let key = ScanHistory::key(results.run.as_ref()?);
+        let summary = results.summary();
+        let runs = self.runs.entry(key).or_default();
+        let changes = runs.last().map(|previous| ReportChanges {
+            since: previous.at,
+            deltas: summary.changes_since(&previous.summary),
+        });
+        runs.push(ScanRecord {
+            at: now,
+            summary,
+            findings: results.findings.iter().map(RecordedFinding::from).collect(),
+        });
+        changes
+    }
+
+    /// Returns the stored runs covered by `query`, oldest first
+    ///
+    /// When the query names a repository or rule, only runs of keys that
+    /// ever had a matching finding are listed, so the runs in which it was
+    /// fixed show too.
+    pub fn list(&self, query: &HistoryQuery) -> Vec<RunEntry> {
+        let mut entries: Vec<RunEntry> = self
+            .runs
+            .iter()
+            .filter(|(_, runs)| {
+                !query.filters_findings()
+                    || runs
+                        .iter()
+                        .any(|run| run.findings.iter().any(|f| query.covers(f)))
+            })
+            .flat_map(|(key, runs)| {
+                runs.iter()
+                    .filter(|run| query.covers_run(run.at))
+                    .map(move |run| RunEntry {
+                        key: key.clone(),
+                        at: run.at,
+                        summary: run.summary.clone(),
+                        findings: run
+                            .findings
+                            .iter()
+                            .filter(|f| query.covers(f))
+                            .cloned()
+                            .collect(),
+                    })
+            })
+            .collect();
+        entries.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.key.cmp(&b.key)));
+        entries
+    }
+
+    /// Drops runs beyond `retention` and returns how many were dropped
+    ///
+    /// The latest run of each key is always kept, so the next run still
+    /// reports what changed, unless it is older than the age limit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::history::{Retention, ScanHistory};
+    /// use devhealth::report::{RunMetadata, ScanResults};
+    ///
+    /// let mut history = ScanHistory::default();
+    /// let results = ScanResults {
+    ///     run: Some(RunMetadata::capture(&[".".into()], &["git"])),
+    ///     ..Default::default()
+    /// };
+    /// for at in [1_000, 2_000, 3_000] {
+    ///     history.record(&results, at);
+    /// }
+    /// assert_eq!(history.prune(Retention { runs: 2, days: 0 }, 3_000), 1);
+    /// assert_eq!(history.runs.values().next().unwrap().len(), 2);
+    /// ```
+    pub fn prune(&mut self, retention: Retention, now: u64) -> usize {
+        let oldest = retention.oldest(now);
+        let mut dropped = 0;
+        for runs in self.runs.values_mut() {
+            let before = runs.len();
+            runs.retain(|run| run.at >= oldest);
+            if retention.runs > 0 {
+                let excess = runs.len().saturating_sub(retention.runs);
+                runs.drain(..excess);
+            }
+            dropped += before - runs.len();
+        }
+        self.runs.retain(|_, runs| !runs.is_empty());
+        dropped
+    }
+}
+
+/// Returns when each finding covered by `query` appeared and was fixed
+///
+/// Findings are followed through the runs of each key separately: a
+/// finding is fixed by the first later run of the same paths and scanners
+/// without it, and appears again if a run after that has it. Timelines are
+/// ordered by when the finding first appeared.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::findings::{Check, Finding, Severity};
+/// use devhealth::history::{timelines, HistoryQuery, ScanHistory};
+/// use devhealth::report::{RunMetadata, ScanResults};
+///
+/// let finding = Finding {
+///     rule: "GIT002".to_string(),
+///     check: Check::UncommittedChanges,
+///     severity: Severity::Warn,
+///     path: "/src/api".into(),
+///     detail: None,
+/// };
+/// let mut results = ScanResults {
+///     run: Some(RunMetadata::capture(&["/src".into()], &["git"])),
+///     findings: vec![finding],
+///     ..Default::default()
+/// };
+/// let mut history = ScanHistory::default();
+/// history.record(&results, 1_000);
+/// results.findings.clear();
+/// history.record(&results, 2_000);
+///
+/// let timelines = timelines(&history, &HistoryQuery::default());
+/// assert_eq!(timelines[0].periods[0].appeared, 1_000);
+/// assert_eq!(timelines[0].periods[0].fixed, Some(2_000));
+/// ```
+pub fn timelines(history: &ScanHistory, query: &HistoryQuery) -> Vec<Timeline> {
+    let mut timelines: Vec<Timeline> = Vec::new();
+    for (key, runs) in &history.runs {
+        let mut open: Vec<Timeline> = Vec::new();
+        for run in runs.iter().filter(|run| query.covers_run(run.at)) {
+            for timeline in open.iter_mut() {
+                let period = timeline
+                    .periods
+                    .last_mut()
+                    .expect("timelines have a period");
+                match run.findings.iter().find(|f| f.same_as(&timeline.finding)) {
+                    Some(finding) if period.fixed.is_some() => {
+                        timeline.finding.severity = finding.severity;
+                        timeline.periods.push(Period {
+                            appeared: run.at,
+                            fixed: None,
+                        });
+                    }
+                    Some(finding) => timeline.finding.severity = finding.severity,
+                    None if period.fixed.is_none() => period.fixed = Some(run.at),
+                    None => {}
+                }
+            }
+            for finding in run.findings.iter().filter(|f| query.covers(f)) {
+                if !open
+                    .iter()
+                    .any(|timeline| timeline.finding.same_as(finding))
+                {
+                    open.push(Timeline {
+                        key: key.clone(),
+                        finding: finding.clone(),
+                        periods: vec![Period {
+                            appeared: run.at,
+                            fixed: None,
+                        }],
+                    });
+                }
+            }
+        }
+        timelines.extend(open);
+    }
+    timelines.sort_by(|a, b| {
+        a.periods[0]
+            .appeared
+            .cmp(&b.periods[0].appeared)
+            .then_with(|| a.finding.cmp(&b.finding))
+    });
+    timelines
+}
+
+/// Displays stored runs for `history list`
+pub fn display_runs(entries: &[RunEntry], query: &HistoryQuery) {
+    if entries.is_empty() {
+        println!(
+            "{}",
+            display::symbols(&format!("ℹ️  {}", t("history-empty")))
+        );
+        return;
+    }
+    println!(
+        "{}",
+        display::header(
+            &tr!("history-list-title", count = entries.len()),
+            "📜",
+            Role::Accent
+        )
+    );
+
+    let mut keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+    keys.sort_unstable();
+    keys.dedup();
+    for key in keys {
+        println!(
+            "
+{}",
+            key.paint(Role::Emphasis).bold()
+        );
+        let runs: Vec<&RunEntry> = entries.iter().filter(|entry| entry.key == key).collect();
+        for (i, entry) in runs.iter().enumerate() {
+            let last = i + 1 == runs.len();
+            let count = if query.filters_findings() {
+                entry.findings.len()
+            } else {
+                entry.summary.findings.values().sum()
+            };
+            let mut parts = vec![tr!("history-run-findings", count = count)];
+            if let Some(dirty) = entry.summary.dirty {
+                parts.push(tr!("changes-dirty", delta = dirty));
+            }
+            if let Some(vulnerabilities) = entry.summary.vulnerabilities {
+                parts.push(tr!("changes-vulnerabilities", delta = vulnerabilities));
+            }
+            let line = format!("{}  {}", acks::format_date(entry.at), parts.join(", "));
+            println!("{}", display::tree_item(&line, last, 0));
+            if query.filters_findings() {
+                for (j, finding) in entry.findings.iter().enumerate() {
+                    let line = describe(finding);
+                    println!(
+                        "{}",
+                        display::tree_item(&line, j + 1 == entry.findings.len(), 1)
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Displays finding timelines for `history show`
+pub fn display_timelines(timelines: &[Timeline], now: u64) {
+    if timelines.is_empty() {
+        println!(
+            "{}",
+            display::symbols(&format!("ℹ️  {}", t("history-no-findings")))
+        );
+        return;
+    }
+    println!(
+        "{}",
+        display::header(
+            &tr!("history-timelines-title", count = timelines.len()),
+            "🕒",
+            Role::Accent
+        )
+    );
+
+    for timeline in timelines {
+        println!(
+            "
+{}",
+            describe(&timeline.finding)
+                .paint(timeline.finding.severity.role())
+                .bold()
+        );
+        for (i, period) in timeline.periods.iter().enumerate() {
+            let line = match period.fixed {
+                Some(fixed) => tr!(
+                    "history-period-fixed",
+                    appeared = acks::format_date(period.appeared),
+                    fixed = acks::format_date(fixed),
+                    days = fixed.saturating_sub(period.appeared) / DAY
+                ),
+                None => tr!(
+                    "history-period-open",
+                    appeared = acks::format_date(period.appeared),
+                    age = display::age(period.appeared, now)
+                )
+                .paint(Role::Warning)
+                .to_string(),
+            };
+            println!(
+                "{}",
+                display::tree_item(&line, i + 1 == timeline.periods.len(), 0)
+            );
+        }
+    }
+}
+
+/// Formats a finding as `DEP014 /src/api (serde)`
+fn describe(finding: &RecordedFinding) -> String {
+    match &finding.detail {
+        Some(detail) => format!("{} {} ({})", finding.rule, finding.path.display(), detail),
+        None => format!("{} {}", finding.rule, finding.path.display()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::findings::Severity;
+    use crate::report::Measure;
+    use tempfile::TempDir;
+
+    fn results(paths: &[&str], scanners: &[&str]) -> ScanResults {
+        let run = RunMetadata::capture(
+            &paths.iter().map(PathBuf::from).collect::<Vec<_>>(),
+            scanners,
+        );
+        ScanResults {
+            run: Some(run),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn compares_runs_with_the_same_paths_and_scanners() {
+        let mut history = ScanHistory::default();
+        let mut check = results(&["/src"], &["git"]);
+        check.git = Some(Vec::new());
+        assert!(history.record(&check, 100).is_none());
+        assert!(history
+            .record(&results(&["/src"], &["git", "audit"]), 150)
+            .is_none());
+        assert!(history
+            .record(&results(&["/work"], &["git"]), 150)
+            .is_none());
+
+        check.findings.push(crate::findings::Finding {
+            rule: crate::findings::Check::EmbeddedCredentials
+                .rule_id()
+                .to_string(),
+            check: crate::findings::Check::EmbeddedCredentials,
+            severity: Severity::Critical,
+            path: PathBuf::from("/src/api"),
+            detail: None,
+        });
+        let changes = history.record(&check, 200).unwrap();
+        assert_eq!(changes.since, 100);
+        assert_eq!(changes.deltas.len(), 1);
+        assert_eq!(changes.deltas[0].measure, Measure::Findings);
+        assert_eq!(changes.deltas[0].severity, Some(Severity::Critical));
+        assert_eq!(changes.deltas[0].difference(), 1);
+        assert_eq!(history.runs.len(), 3);
+    }
+
+    #[test]
+    fn keeps_earlier_runs_within_the_retention() {
+        let mut history = ScanHistory::default();
+        let check = results(&["/src"], &["git"]);
+        for at in [DAY, 2 * DAY, 3 * DAY, 4 * DAY] {
+            history.record(&check, at);
+        }
+        history.record(&results(&["/work"], &["git"]), DAY);
+        assert_eq!(history.runs.values().map(Vec::len).sum::<usize>(), 5);
+
+        assert_eq!(history.prune(Retention { runs: 0, days: 2 }, 4 * DAY), 2);
+        let kept: Vec<u64> = history.runs.values().flatten().map(|run| run.at).collect();
+        assert_eq!(kept, vec![2 * DAY, 3 * DAY, 4 * DAY]);
+
+        assert_eq!(history.prune(Retention { runs: 1, days: 0 }, 4 * DAY), 2);
+        assert_eq!(history.record(&check, 5 * DAY).unwrap().since, 4 * DAY);
+    }
+
+    fn finding(rule: &str, path: &str, severity: Severity) -> Finding {
+        Finding {
+            rule: rule.to_string(),
+            check: crate::findings::Check::UncommittedChanges,
+            severity,
+            path: PathBuf::from(path),
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn follows_findings_until_fixed_and_back() {
+        let mut history = ScanHistory::default();
+        let mut check = results(&["/src"], &["git"]);
+        let runs = [
+            vec![finding("GIT002", "/src/api", Severity::Warn)],
+            vec![
+                finding("GIT002", "/src/api", Severity::Error),
+                finding("DEP014", "/src/web", Severity::Error),
+            ],
+            vec![finding("DEP014", "/src/web", Severity::Error)],
+            vec![finding("GIT002", "/src/api", Severity::Warn)],
+        ];
+        for (day, findings) in runs.into_iter().enumerate() {
+            check.findings = findings;
+            history.record(&check, (day as u64 + 1) * DAY);
+        }
+
+        let all = timelines(&history, &HistoryQuery::default());
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].finding.rule, "GIT002");
+        assert_eq!(
+            all[0].periods,
+            vec![
+                Period {
+                    appeared: DAY,
+                    fixed: Some(3 * DAY)
+                },
+                Period {
+                    appeared: 4 * DAY,
+                    fixed: None
+                },
+            ]
+        );
+        assert_eq!(
+            all[1].periods,
+            vec![Period {
+                appeared: 2 * DAY,
+                fixed: Some(4 * DAY)
+            }]
+        );
+
+        let query = HistoryQuery {
+            since: Some(3 * DAY),
+            finding: Some("git002".to_string()),
+            ..Default::default()
+        };
+        let recent = timelines(&history, &query);
+        assert_eq!(recent.len(), 1);
+        assert_eq!(
+            recent[0].periods,
+            vec![Period {
+                appeared: 4 * DAY,
+                fixed: None
+            }]
+        );
+    }
+
+    #[test]
+    fn lists_runs_of_keys_with_matching_findings() {
+        let mut history = ScanHistory::default();
+        let mut check = results(&["/src"], &["git"]);
+        check.findings = vec![finding("GIT002", "/src/api", Severity::Warn)];
+        history.record(&check, DAY);
+        check.findings.clear();
+        history.record(&check, 2 * DAY);
+        history.record(&results(&["/work"], &["git"]), DAY);
+
+        assert_eq!(history.list(&HistoryQuery::default()).len(), 3);
+
+        let query = HistoryQuery {
+            repo: Some("api".to_string()),
+            ..Default::default()
+        };
+        let entries = history.list(&query);
+        assert_eq!(
+            entries.iter().map(|entry| entry.at).collect::<Vec<_>>(),
+            vec![DAY, 2 * DAY]
+        );
+        assert_eq!(entries[0].findings.len(), 1);
+        assert!(
+            entries[1].findings.is_empty(),
+            "The run that fixed it is listed too"
+        );
+    }
+
+    #[test]
+    fn reads_histories_with_a_single_run_per_key() {
+        let json = r#"{"runs": {"/src [git]": {"at": 100, "summary": {}}}}"#;
+        let history: ScanHistory = serde_json::from_str(json).unwrap();
+        assert_eq!(history.runs["/src [git]"].len(), 1);
+        assert_eq!(history.runs["/src [git]"][0].at, 100);
+    }
+
+    #[test]
+    fn round_trips_through_a_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state").join(HISTORY_FILE);
+        assert_eq!(ScanHistory::load(&path).unwrap(), ScanHistory::default());
+
+        let mut history = ScanHistory::default();
+        history.record(&results(&["/src"], &["git"]), 100);
+        history.save(&path).unwrap();
+        assert_eq!(ScanHistory::load(&path).unwrap(), history);
+    }
+}
@@ -0,0 +1,272 @@
+//! Localization of user-facing report strings
+//!
+//! Messages live in per-language catalogs under `locales/` and are compiled
+//! into the binary. The catalogs use a subset of the
+//! [Fluent](https://projectfluent.org) syntax: one `key = value` message per
+//! line, `#` comments, and `{ $name }` placeholders.
+//!
+//! English (`locales/en.ftl`) is the reference catalog. A message missing from
+//! another catalog falls back to English, so partial translations are safe.
+//!
+//! The language is chosen once at startup with [`set_language`], from the
+//! `--lang` flag, the `lang` setting in `devhealth.toml`, or the locale
+//! environment (see [`Lang::detect`]). Until then every message is English.
+//!
+//! ## Adding a language
+//!
+//! 1. Copy `locales/en.ftl` to `locales/<code>.ftl` and translate the values
+//! 2. Add a [`Lang`] variant and its catalog in [`Lang::source`]
+//! 3. Map the locale code in [`Lang::from_locale`]
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// The language selected for this process
+static ACTIVE_LANGUAGE: OnceLock<Lang> = OnceLock::new();
+
+/// Parsed catalogs, built on first use
+static CATALOGS: OnceLock<HashMap<Lang, Catalog>> = OnceLock::new();
+
+/// Messages of one language, keyed by message ID
+type Catalog = HashMap<&'static str, &'static str>;
+
+/// Languages DevHealth has message catalogs for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    /// English
+    #[default]
+    En,
+    /// German
+    De,
+    /// Spanish
+    Es,
+}
+
+impl Lang {
+    /// Every supported language
+    pub const ALL: [Lang; 3] = [Lang::En, Lang::De, Lang::Es];
+
+    /// Returns the ISO 639-1 code of the language
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::De => "de",
+            Lang::Es => "es",
+        }
+    }
+
+    /// Returns the language for a POSIX locale name such as `de_DE.UTF-8`
+    ///
+    /// Returns `None` for locales without a catalog, including `C` and `POSIX`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::i18n::Lang;
+    ///
+    /// assert_eq!(Lang::from_locale("de_AT.UTF-8"), Some(Lang::De));
+    /// assert_eq!(Lang::from_locale("es"), Some(Lang::Es));
+    /// assert_eq!(Lang::from_locale("C.UTF-8"), None);
+    /// ```
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let code = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        Lang::ALL.into_iter().find(|lang| lang.code() == code)
+    }
+
+    /// Detects the language from the locale environment
+    ///
+    /// Checks `LC_ALL`, `LC_MESSAGES` and `LANG` in that order and uses the
+    /// first one that is set. Falls back to English.
+    pub fn detect() -> Lang {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+            .and_then(|locale| Lang::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// Returns the catalog source compiled into the binary
+    fn source(self) -> &'static str {
+        match self {
+            Lang::En => include_str!("../locales/en.ftl"),
+            Lang::De => include_str!("../locales/de.ftl"),
+            Lang::Es => include_str!("../locales/es.ftl"),
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Selects the language used for the rest of the process
+///
+/// Returns `false` if a language was already selected (the first one wins).
+pub fn set_language(lang: Lang) -> bool {
+    ACTIVE_LANGUAGE.set(lang).is_ok()
+}
+
+/// Returns the selected language, or English if none was selected
+pub fn language() -> Lang {
+    ACTIVE_LANGUAGE.get().copied().unwrap_or_default()
+}
+
+/// Looks up a message without placeholders in the selected language
+///
+/// Falls back to English, and to the message ID itself if even the English
+/// catalog lacks it.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::i18n;
+///
+/// assert_eq!(i18n::t("summary"), "Summary");
+/// ```
+pub fn t(id: &'static str) -> &'static str {
+    lookup(language(), id)
+}
+
+/// Looks up a message and fills in its `{ $name }` placeholders
+///
+/// Usually called through the [`tr!`](crate::tr) macro.
+pub fn format(id: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    args.iter()
+        .fold(t(id).to_string(), |message, (name, value)| {
+            message.replace(&format!("{{ ${} }}", name), &value.to_string())
+        })
+}
+
+/// Translates a message, filling in named placeholders
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::tr;
+///
+/// assert_eq!(tr!("git-health", percent = 50), "Git Repository Health (50%)");
+/// assert_eq!(tr!("git-none"), "No git repositories found");
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::t($id).to_string()
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format($id, &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+])
+    };
+}
+
+/// Finds `id` in the catalog of `lang`, falling back to English
+fn lookup(lang: Lang, id: &'static str) -> &'static str {
+    let catalogs = CATALOGS.get_or_init(|| {
+        Lang::ALL
+            .into_iter()
+            .map(|lang| (lang, parse_catalog(lang.source())))
+            .collect()
+    });
+
+    catalogs[&lang]
+        .get(id)
+        .or_else(|| catalogs[&Lang::En].get(id))
+        .copied()
+        .unwrap_or(id)
+}
+
+/// Parses `key = value` lines, skipping blank lines and `#` comments
+fn parse_catalog(source: &'static str) -> Catalog {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(message: &str) -> BTreeSet<&str> {
+        message
+            .split("{ $")
+            .skip(1)
+            .filter_map(|rest| rest.split_once(" }").map(|(name, _)| name))
+            .collect()
+    }
+
+    mod catalogs {
+        use super::*;
+
+        #[test]
+        fn translations_only_use_known_messages() {
+            let english = parse_catalog(Lang::En.source());
+            for lang in [Lang::De, Lang::Es] {
+                for key in parse_catalog(lang.source()).keys() {
+                    assert!(
+                        english.contains_key(key),
+                        "{} defines unknown message '{}'",
+                        lang,
+                        key
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn translations_keep_placeholders() {
+            let english = parse_catalog(Lang::En.source());
+            for lang in [Lang::De, Lang::Es] {
+                for (key, message) in parse_catalog(lang.source()) {
+                    assert_eq!(
+                        placeholders(message),
+                        placeholders(english[key]),
+                        "{} changes the placeholders of '{}'",
+                        lang,
+                        key
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn falls_back_to_english_and_then_the_id() {
+            assert_eq!(lookup(Lang::De, "summary"), "Übersicht");
+            assert_eq!(lookup(Lang::Es, "summary"), "Resumen");
+            assert_eq!(lookup(Lang::De, "no-such-message"), "no-such-message");
+        }
+    }
+
+    mod formatting {
+        use super::*;
+
+        #[test]
+        fn fills_in_named_placeholders() {
+            assert_eq!(
+                crate::tr!("advisory-saved", count = 3, path = "/tmp/db"),
+                "Saved 3 advisories to /tmp/db"
+            );
+        }
+
+        #[test]
+        fn parses_locale_names() {
+            assert_eq!(Lang::from_locale("de_DE.UTF-8"), Some(Lang::De));
+            assert_eq!(Lang::from_locale("es_MX"), Some(Lang::Es));
+            assert_eq!(Lang::from_locale("en_US.UTF-8"), Some(Lang::En));
+            assert_eq!(Lang::from_locale("fr_FR.UTF-8"), None);
+            assert_eq!(Lang::from_locale("POSIX"), None);
+        }
+    }
+}
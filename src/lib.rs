@@ -8,6 +8,27 @@
 //!
 //! - **Git Repository Health**: Scan directories for git repositories and check their status
 //! - **Dependency Analysis**: Monitor project dependencies across multiple ecosystems
+//! - **Vulnerability Audit**: Check dependencies against OSV advisories, online or airgapped
+//! - **Repository Tags**: Group repositories and filter scans by tag
+//! - **Organization Policy**: Pull signed required checks and thresholds from a central endpoint
+//! - **Team Reports**: Merge JSON reports from several machines into one team view
+//! - **Crash Reports**: Redacted diagnostic bundles written on panics and internal errors
+//! - **Snapshots**: Export results, configuration and tool versions for bug reports
+//! - **Acknowledgements**: Snooze known findings until a date, with a reason
+//! - **Severities**: Rate every finding from info to critical, with per-check overrides
+//! - **Report Shaping**: Filter, sort and limit reports in text or JSON output
+//! - **Porcelain Output**: One stable, tab-separated line per repository and finding for scripts
+//! - **Shell Prompt**: Cached repository status for `PS1` and starship prompts
+//! - **Guided Setup**: `devhealth init` writes a commented configuration, schedule and hooks
+//! - **Editor Integration**: Findings as editor diagnostics and generated VS Code tasks
+//! - **Advisory Watch**: Alert when a new advisory affects a previously scanned dependency
+//! - **Team Statistics**: Anonymous aggregate counts to share with a platform team
+//! - **Email Digest**: A weekly HTML and Markdown health summary sent over SMTP
+//! - **Desktop Notifications**: Native notifications when a run finds new critical issues
+//! - **Credentials**: Forge tokens and the SMTP password stored in the system keyring
+//! - **Missing Repositories**: Forge repositories not cloned under the scanned paths, cloned on request
+//! - **Self-Update**: Verified release updates and a daily background check for new versions
+//! - **Localization**: Report strings in English, German and Spanish
 //! - **System Monitoring**: Track system resource usage (planned feature)
 //! - **Project Analytics**: Analyze code quality metrics (planned feature)
 //!
@@ -41,9 +62,33 @@
 //! scanner::deps::display_results(&dep_reports);
 //! ```
 
+pub mod acks;
+pub mod aggregate;
+pub mod auth;
+pub mod bench;
 pub mod cli;
+pub mod config;
+pub mod crash;
+pub mod digest;
+pub mod editor;
+pub mod exit;
+pub mod findings;
+pub mod history;
+pub mod i18n;
+pub mod notify;
+pub mod policy;
+pub mod porcelain;
+pub mod prompt;
+pub mod report;
+pub mod repos;
 pub mod scanner;
+pub mod setup;
+pub mod snapshot;
+pub mod stats;
+pub mod tags;
+pub mod update;
 pub mod utils;
+pub mod watch;
 
 pub use cli::Cli;
 
@@ -76,8 +121,8 @@ mod tests {
 
         // Test that we can call functions from all scanner modules
         let _deps_result = scanner::deps::scan_dependencies(std::path::Path::new("."));
-        scanner::system::monitor_system();
-        scanner::analytics::analyze_projects();
+        let _snapshot = scanner::system::snapshot(std::path::Path::new("."));
+        let _command = scanner::analytics::CheckKind::Clippy.command();
     }
 
     #[test]
@@ -4,21 +4,90 @@
 //! This binary provides command-line interface for monitoring development
 //! environment health including git repositories, dependencies, and system resources.
 
-use clap::Parser;
-use devhealth::cli::Cli;
+use clap::{Parser, ValueEnum};
+use devhealth::cli::{
+    AckCommands, AdvisoryDbCommands, AuthCommands, BenchCommands, Cli, ConfigCommands,
+    DepsCommands, DigestCommands, FixCommands, HistoryCommands, HistoryFilterArgs, InitCommands,
+    PolicyCommands, ReportArgs, ReposCommands, StatsCommands, TagCommands,
+};
+use devhealth::config::Scanner;
+use devhealth::crash;
+use devhealth::exit::{ExitStatus, UsageError};
+use devhealth::findings;
+use devhealth::history::Retention;
+use devhealth::i18n::{self, Lang};
+use devhealth::porcelain;
+use devhealth::prompt;
+use devhealth::report::{OutputFormat, RunMetadata, ScanResults};
+use devhealth::repos;
 use devhealth::scanner;
+use devhealth::scanner::licenses::LicenseFormat;
+use devhealth::tags::{self, TagFilter, TagStore};
+use devhealth::tr;
+use devhealth::update;
+use devhealth::utils::display::{self, Detail};
+use devhealth::utils::lock::{LockMode, StateLock};
+use devhealth::utils::net::NetworkGate;
+use devhealth::utils::redact;
+use devhealth::utils::theme::{self, Theme};
+use devhealth::utils::wsl;
+use devhealth::watch;
 use std::process;
 
 /// Application entry point
 ///
 /// Parses command line arguments and executes the appropriate command.
-/// Handles errors gracefully and exits with appropriate status codes.
+/// Handles errors gracefully and exits with the status codes documented in
+/// [`devhealth::exit`].
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    crash::install_panic_hook(args.clone());
     let cli = Cli::parse();
+    let announce = announces_updates(&cli);
 
-    if let Err(e) = run(cli) {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+    let status = match run(cli) {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("Error: {}", redact::text(&e.to_string()));
+            let status = ExitStatus::of_error(e.as_ref());
+            if status == ExitStatus::Internal {
+                if let Some(message) = crash::save_error(&args, e.as_ref()) {
+                    eprintln!("{}", message);
+                }
+            }
+            status
+        }
+    };
+    if announce {
+        announce_update();
+    }
+    process::exit(status.code());
+}
+
+/// Returns `true` if the run should mention a newer release afterwards
+///
+/// Only interactive runs are told: not offline ones, not those whose
+/// stderr is redirected, and not `prompt`, which runs before every shell
+/// prompt.
+fn announces_updates(cli: &Cli) -> bool {
+    use std::io::IsTerminal;
+
+    !cli.offline
+        && std::io::stderr().is_terminal()
+        && !matches!(
+            cli.command,
+            devhealth::cli::Commands::Prompt { .. } | devhealth::cli::Commands::SelfUpdate { .. }
+        )
+}
+
+/// Mentions a newer release found by the background version check
+fn announce_update() {
+    let config = devhealth::config::Config::load().unwrap_or_default();
+    if let Some(version) = update::pending_update(&config.update, display::unix_now()) {
+        eprintln!(
+            "\n{}",
+            display::symbols(&format!("💡 {}", tr!("update-notice", version = version)))
+        );
     }
 }
 
@@ -33,55 +102,2649 @@ fn main() {
 ///
 /// # Returns
 ///
-/// A `Result` indicating success or failure of the operation.
+/// The exit status of a completed run: [`ExitStatus::Findings`] if
+/// `--fail-on` matched, or the category of a scanner that failed without
+/// aborting the report.
 ///
 /// # Errors
 ///
 /// Returns an error if any scanner operation fails or if invalid
 /// arguments are provided.
-fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
-    match cli.command {
-        devhealth::cli::Commands::Check { path } => {
-            println!("🔍 Running health check on: {}", path.display());
+fn run(cli: Cli) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    let mut network = NetworkGate::new(cli.offline);
+
+    let mut config = match (&cli.command, devhealth::config::Config::load()) {
+        // `config check` lists the problems itself
+        (
+            devhealth::cli::Commands::Config {
+                action: ConfigCommands::Check { .. },
+            },
+            Err(_),
+        ) => Default::default(),
+        (_, config) => config?,
+    };
+    devhealth::auth::register_secrets(&config);
+    theme::set_theme(Theme::from_config(&config.theme)?);
+    display::set_accessible(cli.accessible || config.accessible || !display::locale_is_utf8());
+    i18n::set_language(cli.lang.or(config.lang).unwrap_or_else(Lang::detect));
+    let lock_mode = cli.lock_mode();
+    let flags = GlobalFlags {
+        lang: cli.lang,
+        accessible: cli.accessible,
+        offline: cli.offline,
+    };
+    let _lock = if cli.command.writes_state() {
+        Some(lock_state(lock_mode)?)
+    } else {
+        None
+    };
 
-            // Run git scanner
-            let git_results = scanner::git::scan_directory(&path)?;
-            scanner::git::display_results(&git_results);
+    match cli.command {
+        devhealth::cli::Commands::Check {
+            path,
+            tags,
+            mut report,
+        } => {
+            let path = wsl::resolve_path(&path);
+            progress(
+                report.message_format(),
+                &format!("🔍 {}", tr!("check-start", path = path.display())),
+            );
+            if let Some(policy) = apply_policy(&mut config, &mut network, report.message_format())?
+            {
+                policy.require_fail_on(&mut report.fail_on);
+            }
+            warn_if_windows_drive(&path, report.message_format());
+            let filter = TagFilter::load(&tags)?;
+            report_tag_filter(&tags, report.message_format());
 
-            Ok(())
+            let results = quick_check(&path, &filter, &config, &mut network)?;
+            emit_report(results, &report, &config, &network)
         }
         devhealth::cli::Commands::Scan {
             path,
             git,
             deps,
             system,
+            audit,
+            containers,
+            monorepo,
+            graph,
+            release,
+            cargo,
+            runtimes,
+            lost_work,
+            sizes,
+            deep,
+            all,
+            auto,
+            tags,
+            mut report,
         } => {
-            println!("🚀 Starting comprehensive scan on: {}", path.display());
+            let format = report.message_format();
+            let policy = apply_policy(&mut config, &mut network, format)?;
+            let path = wsl::resolve_path(&path);
+            let flagged: Vec<Scanner> = [
+                (git || lost_work || sizes, Scanner::Git),
+                (deps, Scanner::Deps),
+                (system, Scanner::System),
+                (audit, Scanner::Audit),
+                (containers, Scanner::Containers),
+                (monorepo || graph.is_some(), Scanner::Monorepo),
+                (release, Scanner::Release),
+                (cargo, Scanner::Cargo),
+                (runtimes, Scanner::Runtimes),
+            ]
+            .into_iter()
+            .filter_map(|(enabled, scanner)| enabled.then_some(scanner))
+            .collect();
+            let detected = auto.then(|| scanner::auto::Detected {
+                advisories: !network.is_offline() || scanner::audit::has_local_database(),
+                ..scanner::auto::detect(&path)
+            });
+            let preset = detected
+                .as_ref()
+                .filter(|detected| !detected.is_empty())
+                .map(scanner::auto::preset);
+            let deep = deep || preset.as_ref().is_some_and(|preset| preset.deep);
+            let mut scanners = if all {
+                Scanner::ALL.to_vec()
+            } else if let Some(preset) = &preset {
+                let mut scanners = preset.scanners.clone();
+                scanners.extend(
+                    flagged
+                        .into_iter()
+                        .filter(|scanner| !preset.scanners.contains(scanner)),
+                );
+                scanners
+            } else if flagged.is_empty() {
+                config.scan.default.clone()
+            } else {
+                flagged
+            };
+            if let Some(policy) = &policy {
+                policy.require_scanners(&mut scanners);
+                policy.require_fail_on(&mut report.fail_on);
+            }
+            let enabled = |scanner| scanners.contains(&scanner);
+            let (git, deps, system, audit, containers, monorepo, release, cargo, runtimes) = (
+                enabled(Scanner::Git),
+                enabled(Scanner::Deps),
+                enabled(Scanner::System),
+                enabled(Scanner::Audit),
+                enabled(Scanner::Containers),
+                enabled(Scanner::Monorepo),
+                enabled(Scanner::Release),
+                enabled(Scanner::Cargo),
+                enabled(Scanner::Runtimes),
+            );
+
+            progress(
+                format,
+                &format!("🚀 {}", tr!("scan-start", path = path.display())),
+            );
+            if let Some(detected) = &detected {
+                for line in scanner::auto::describe(detected, preset.as_ref(), &path) {
+                    progress(format, &format!("🧭 {}", line));
+                }
+            }
+            warn_if_windows_drive(&path, format);
+            let filter = TagFilter::load(&tags)?;
+            report_tag_filter(&tags, format);
+            let mut names: Vec<&str> = scanners.iter().map(|scanner| scanner.name()).collect();
+            if lost_work {
+                names.push("lost-work");
+            }
+            if sizes {
+                names.push("sizes");
+            }
+            if deep {
+                names.push("deep");
+            }
+            let mut results = ScanResults {
+                run: Some(RunMetadata::capture(std::slice::from_ref(&path), &names)),
+                ..Default::default()
+            };
+            let mut failure = None;
 
             if git {
-                println!("\n📁 Scanning Git repositories...");
-                let git_results = scanner::git::scan_directory(&path)?;
-                scanner::git::display_results(&git_results);
+                crash::checkpoint(&results);
+                progress(format, &format!("\n📁 {}", tr!("scan-git")));
+                let mut repos = scanner::git::scan_directory_with(
+                    &path,
+                    |p| filter.matches(p),
+                    &config.scan.git_options(),
+                )?;
+                scanner::forge::enrich_repositories(&mut repos, &config.forge, &mut network);
+                if lost_work {
+                    progress(format, &format!("🔎 {}", tr!("scan-lost-work")));
+                    scanner::lostwork::enrich_repositories(&mut repos, display::unix_now());
+                }
+                if sizes {
+                    progress(format, &format!("📏 {}", tr!("scan-sizes")));
+                    scanner::sizes::enrich_repositories(&mut repos, display::unix_now());
+                    if config.scan.history {
+                        let path = scanner::sizes::SizeHistory::default_path()?;
+                        let mut history = scanner::sizes::SizeHistory::load(&path)?;
+                        history.record(&mut repos, display::unix_now());
+                        history.prune(
+                            Retention::from_config(&config.scan).oldest(display::unix_now()),
+                        );
+                        history.save(&path)?;
+                    }
+                }
+                results.git = Some(repos);
+            }
+
+            if deps || audit {
+                crash::checkpoint(&results);
+                if deps {
+                    progress(format, &format!("\n📦 {}", tr!("scan-deps")));
+                }
+                match scanner::deps::scan_dependencies_matching(&path, |p| filter.matches(p)) {
+                    Ok(dep_reports) => {
+                        if let Err(e) = watch::record_scan(
+                            &path,
+                            &dep_reports,
+                            !tags.is_empty(),
+                            display::unix_now(),
+                        ) {
+                            eprintln!(
+                                "⚠️  {}",
+                                tr!("watch-inventory-failed", error = e.to_string())
+                            );
+                        }
+                        if audit {
+                            progress(format, &format!("\n🛡️  {}", tr!("scan-audit")));
+                            match scanner::audit::run_audit(&dep_reports, &mut network) {
+                                Ok(mut audit_report) => {
+                                    audit_report.apply_ignores(&config.audit, display::unix_now());
+                                    prompt::record_audit(
+                                        &dep_reports,
+                                        &audit_report.vulnerabilities,
+                                    );
+                                    results.audit = Some(audit_report);
+                                }
+                                Err(e) => {
+                                    eprintln!(
+                                        "Error auditing dependencies: {}",
+                                        redact::text(&e.to_string())
+                                    );
+                                    failure = Some(ExitStatus::of_error(&e));
+                                }
+                            }
+                        }
+                        if deps {
+                            results.dependencies = Some(dep_reports);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "Error scanning dependencies: {}",
+                            redact::text(&e.to_string())
+                        );
+                        failure = Some(ExitStatus::Internal);
+                    }
+                }
+            }
+
+            if containers {
+                crash::checkpoint(&results);
+                progress(format, &format!("\n🐳 {}", tr!("scan-containers")));
+                let environments =
+                    scanner::container::find_environments_matching(&path, |p| filter.matches(p));
+                results.containers = Some(scanner::container::check_environments(environments));
             }
 
-            if deps {
-                println!("\n📦 Checking dependencies...");
-                match scanner::deps::scan_dependencies(&path) {
-                    Ok(dep_reports) => scanner::deps::display_results(&dep_reports),
-                    Err(e) => eprintln!("Error scanning dependencies: {}", e),
+            if monorepo {
+                crash::checkpoint(&results);
+                progress(format, &format!("\n🗂️  {}", tr!("scan-monorepo")));
+                let workspaces =
+                    scanner::workspace::scan_workspaces_matching(&path, |p| filter.matches(p));
+                if let Some(graph) = &graph {
+                    std::fs::write(graph, scanner::workspace::to_dot(&workspaces))?;
+                    progress(
+                        format,
+                        &format!(
+                            "🕸️  {}",
+                            tr!("workspace-graph-saved", path = graph.display())
+                        ),
+                    );
                 }
+                results.workspaces = Some(workspaces);
+            }
+
+            if release {
+                crash::checkpoint(&results);
+                progress(format, &format!("\n🏷️  {}", tr!("scan-release")));
+                results.releases =
+                    Some(scanner::release::scan_repositories_matching(&path, |p| {
+                        filter.matches(p)
+                    })?);
+            }
+
+            if cargo {
+                crash::checkpoint(&results);
+                progress(format, &format!("\n🦀 {}", tr!("scan-cargo")));
+                results.cargo = Some(scanner::cargo::scan_projects_matching(&path, |p| {
+                    filter.matches(p)
+                }));
+            }
+
+            if runtimes {
+                crash::checkpoint(&results);
+                progress(format, &format!("\n🧰 {}", tr!("scan-runtimes")));
+                results.runtimes = Some(scanner::runtime::scan_runtimes_matching(&path, |p| {
+                    filter.matches(p)
+                }));
+            }
+
+            if deep {
+                crash::checkpoint(&results);
+                progress(format, &format!("\n🔬 {}", tr!("scan-deep")));
+                results.analysis = Some(scanner::analytics::analyze_projects_matching(
+                    &path,
+                    |p| filter.matches(p),
+                    &config.deep,
+                ));
             }
 
             if system {
-                println!("\n💻 Monitoring system resources...");
-                scanner::system::monitor_system();
+                progress(format, &format!("\n💻 {}", tr!("scan-system")));
+                crash::checkpoint(&results);
+                let mut snapshot = scanner::system::snapshot(&path);
+                snapshot.packages = scanner::packages::check_outdated(&mut network);
+                snapshot.duplicates = scanner::binaries::find_duplicates();
+                snapshot.clock = Some(scanner::clock::check_clock(&mut network));
+                snapshot.certificates = scanner::certs::check_bundles();
+                snapshot.proxy = Some(scanner::proxy::check_proxies(&mut network));
+                snapshot.exclusions = scanner::exclusions::check_exclusions(&path);
+                snapshot.power = scanner::power::check_power();
+                snapshot.limits = Some(scanner::limits::check_limits(&path));
+                snapshot.locale = Some(scanner::locale::check_locale());
+                snapshot.git_config = scanner::gitconfig::audit_global_config();
+                results.system = Some(snapshot);
+            }
+
+            if scanners.is_empty() && !deep {
+                progress(format, &format!("ℹ️  {}", tr!("scan-nothing")));
+            }
+
+            let status = emit_report(results, &report, &config, &network)?;
+            Ok(failure.unwrap_or(status))
+        }
+        devhealth::cli::Commands::Show { repo, format } => {
+            show_repository(&repo, &config.audit, format).map(|_| ExitStatus::Ok)
+        }
+        devhealth::cli::Commands::Tag { action } => manage_tags(action).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Ack {
+            action,
+            id,
+            until,
+            reason,
+        } => manage_acks(action, id, until, reason).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Rules { rule, format } => {
+            list_rules(rule.as_deref(), format, &config).map(|_| ExitStatus::Ok)
+        }
+        devhealth::cli::Commands::AdvisoryDb {
+            action: AdvisoryDbCommands::Sync { path, to, from },
+        } => sync_advisory_database(&path, to, from, &mut network).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Policy { action } => {
+            manage_policy(action, &config, &mut network).map(|_| ExitStatus::Ok)
+        }
+        devhealth::cli::Commands::Merge {
+            files,
+            format,
+            detail,
+        } => merge_results(&files, format, detail).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Export {
+            out,
+            path,
+            tags,
+            results,
+        } => export_snapshot(
+            &out,
+            &path,
+            &tags,
+            results.as_deref(),
+            &config,
+            &mut network,
+        )
+        .map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Import {
+            snapshot,
+            diff,
+            format,
+            detail,
+        } => import_snapshot(&snapshot, diff.as_deref(), format, detail).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Init {
+            action: Some(InitCommands::Vscode { path }),
+            ..
+        } => init_vscode(&path).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Init {
+            action: None,
+            yes,
+            force,
+        } => init_config(yes, force).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Deps {
+            action:
+                DepsCommands::PinSuggestions {
+                    path,
+                    tags,
+                    patch,
+                    format,
+                },
+        } => suggest_pins(&path, &tags, patch, format),
+        devhealth::cli::Commands::Deps {
+            action:
+                DepsCommands::Exposure {
+                    path,
+                    tags,
+                    audit,
+                    top,
+                    format,
+                },
+        } => show_exposure(
+            &path,
+            &tags,
+            audit,
+            top,
+            format,
+            &config.audit,
+            &mut network,
+        ),
+        devhealth::cli::Commands::Deps {
+            action:
+                DepsCommands::Find {
+                    package,
+                    path,
+                    tags,
+                    stored,
+                    format,
+                },
+        } => find_dependency(&package, &path, &tags, stored, format),
+        devhealth::cli::Commands::Deps {
+            action:
+                DepsCommands::UpgradeImpact {
+                    package,
+                    version,
+                    path,
+                    tags,
+                    format,
+                },
+        } => upgrade_impact(&package, &version, &path, &tags, format),
+        devhealth::cli::Commands::Deps {
+            action:
+                DepsCommands::Align {
+                    path,
+                    tags,
+                    min_spread,
+                    format,
+                },
+        } => align_versions(&path, &tags, min_spread, format),
+        devhealth::cli::Commands::Licenses { path, tags, format } => {
+            list_licenses(&path, &tags, format)
+        }
+        devhealth::cli::Commands::WatchAdvisories {
+            interval,
+            no_notify,
+            format,
+        } => watch_advisories(
+            interval,
+            !no_notify,
+            format,
+            lock_mode,
+            &config.audit,
+            &mut network,
+        ),
+        devhealth::cli::Commands::Prompt {
+            path,
+            max_age,
+            refresh,
+        } => {
+            if refresh {
+                prompt::refresh(&path, display::unix_now())?;
+            } else {
+                let status = prompt::status(&path, max_age, display::unix_now());
+                if !status.is_empty() {
+                    println!("{}", status);
+                }
+            }
+            Ok(ExitStatus::Ok)
+        }
+        devhealth::cli::Commands::DebugBundle { out } => debug_bundle(out).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::SelfUpdate { check } => {
+            if !network.allow(update::UPDATE_CHECK) {
+                return Err(UsageError(
+                    "self-update needs network access; run it without --offline".to_string(),
+                )
+                .into());
+            }
+            match update::self_update(&config.update, check, display::unix_now())? {
+                update::UpdateOutcome::UpToDate(version) => say(&format!(
+                    "✅ {}",
+                    tr!("update-up-to-date", version = version)
+                )),
+                update::UpdateOutcome::Available(version) => say(&format!(
+                    "💡 {}",
+                    tr!(
+                        "update-available",
+                        version = version,
+                        current = update::current_version()
+                    )
+                )),
+                update::UpdateOutcome::Updated { from, to } => {
+                    say(&format!("✅ {}", tr!("update-done", from = from, to = to)))
+                }
+            }
+            Ok(ExitStatus::Ok)
+        }
+        devhealth::cli::Commands::Fix {
+            action:
+                FixCommands::SyncForks {
+                    path,
+                    tags,
+                    dry_run,
+                    push,
+                },
+        } => sync_forks(&path, &tags, dry_run, push, &config, &mut network),
+        devhealth::cli::Commands::Clean {
+            path,
+            tags,
+            dry_run,
+            yes,
+        } => clean_artifacts(&path, &tags, dry_run, yes),
+        devhealth::cli::Commands::Stats {
+            action:
+                StatsCommands::Export {
+                    out,
+                    path,
+                    tags,
+                    results,
+                },
+        } => export_stats(
+            out.as_deref(),
+            &path,
+            &tags,
+            results.as_deref(),
+            &config,
+            &mut network,
+        )
+        .map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Digest {
+            action:
+                DigestCommands::Send {
+                    path,
+                    tags,
+                    results,
+                    dry_run,
+                },
+        } => send_digest(
+            path.as_deref(),
+            &tags,
+            results.as_deref(),
+            dry_run,
+            &config,
+            &mut network,
+        )
+        .map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Digest {
+            action: DigestCommands::Schedule { path },
+        } => schedule_digest(path.as_deref(), &config).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::Auth { action } => {
+            manage_credentials(action).map(|_| ExitStatus::Ok)
+        }
+        devhealth::cli::Commands::Repos { action } => {
+            find_missing_repos(action, &config, &mut network)
+        }
+        devhealth::cli::Commands::Config {
+            action: ConfigCommands::Check { file },
+        } => check_config(file.as_deref()),
+        devhealth::cli::Commands::Config {
+            action: ConfigCommands::Show,
+        } => show_config(config, flags).map(|_| ExitStatus::Ok),
+        devhealth::cli::Commands::History { action } => {
+            manage_history(action, &config).map(|_| ExitStatus::Ok)
+        }
+        devhealth::cli::Commands::Bench { action } => {
+            run_bench(action, &config, &mut network).map(|_| ExitStatus::Ok)
+        }
+    }
+}
+
+/// Runs the quick check behind `check` and `export`
+///
+/// Scans git repositories, then adds the fast offline dependency and system
+/// summaries, and records the run in the results.
+///
+/// # Errors
+///
+/// Returns an error if the git scan fails.
+fn quick_check(
+    path: &std::path::Path,
+    filter: &TagFilter,
+    config: &devhealth::config::Config,
+    network: &mut NetworkGate,
+) -> Result<ScanResults, Box<dyn std::error::Error>> {
+    let run = RunMetadata::capture(
+        &[path.to_path_buf()],
+        &[
+            Scanner::Git.name(),
+            Scanner::Deps.name(),
+            Scanner::System.name(),
+        ],
+    );
+    let mut repos =
+        scanner::git::scan_directory_with(path, |p| filter.matches(p), &config.scan.git_options())?;
+    scanner::forge::enrich_repositories(&mut repos, &config.forge, network);
+    Ok(ScanResults {
+        run: Some(run),
+        git: Some(repos),
+        manifests: Some(scanner::deps::summarize_manifests_matching(
+            path,
+            |p| filter.matches(p),
+            scanner::deps::MANIFEST_SCAN_BUDGET,
+        )),
+        system: Some(scanner::system::snapshot(path)),
+        ..Default::default()
+    })
+}
+
+/// Writes a snapshot of the results, configuration and tool versions
+///
+/// # Errors
+///
+/// Returns an error if the check fails, the saved report or configuration
+/// cannot be read, or the archive cannot be written.
+fn export_snapshot(
+    out: &std::path::Path,
+    path: &std::path::Path,
+    tags: &[String],
+    saved: Option<&std::path::Path>,
+    config: &devhealth::config::Config,
+    network: &mut NetworkGate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::snapshot::{self, Manifest, Snapshot};
+
+    snapshot::ensure_supported(out)?;
+    let path = wsl::resolve_path(path);
+    let mut results = match saved {
+        Some(file) => devhealth::aggregate::load_results(file)?.results,
+        None => {
+            say(&format!("🔍 {}", tr!("check-start", path = path.display())));
+            let filter = TagFilter::load(tags)?;
+            report_tag_filter(tags, OutputFormat::Text);
+            let mut results = quick_check(&path, &filter, config, network)?;
+            results.skipped_checks = network.skipped().to_vec();
+            results
+        }
+    };
+    results.redact();
+    let config = match devhealth::config::Config::default_path() {
+        Some(file) if file.exists() => {
+            Some(snapshot::redact_config(&std::fs::read_to_string(file)?)?)
+        }
+        _ => None,
+    };
+
+    let snapshot = Snapshot {
+        manifest: Manifest::capture(&std::path::absolute(&path)?),
+        results,
+        config,
+    };
+    snapshot.write(out)?;
+    say(&format!(
+        "📸 {}",
+        tr!("snapshot-written", path = out.display())
+    ));
+    Ok(())
+}
+
+/// Writes anonymous aggregate statistics of a check or a saved report
+///
+/// Status messages go to stderr, so the statistics can be piped when no
+/// `out` file is given.
+///
+/// # Errors
+///
+/// Returns an error if the check fails, the saved report cannot be read,
+/// or the statistics cannot be written.
+fn export_stats(
+    out: Option<&std::path::Path>,
+    path: &std::path::Path,
+    tags: &[String],
+    saved: Option<&std::path::Path>,
+    config: &devhealth::config::Config,
+    network: &mut NetworkGate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let results = match saved {
+        Some(file) => devhealth::aggregate::load_results(file)?.results,
+        None => {
+            let path = wsl::resolve_path(path);
+            progress(
+                OutputFormat::Json,
+                &format!("🔍 {}", tr!("check-start", path = path.display())),
+            );
+            let filter = TagFilter::load(tags)?;
+            report_tag_filter(tags, OutputFormat::Json);
+            let mut results = quick_check(&path, &filter, config, network)?;
+            let tag_filter =
+                TagFilter::new(&[], config, TagStore::load(&TagStore::default_path()?)?);
+            config.checks.hide_disabled(&mut results, &tag_filter);
+            results.findings =
+                findings::collect(&results, &config.severity, &config.checks, &tag_filter);
+            results
+        }
+    };
+
+    let stats = devhealth::stats::TeamStats::from_results(&results, display::unix_now());
+    let json = serde_json::to_string_pretty(&stats)?;
+    match out {
+        Some(out) => {
+            std::fs::write(out, json + "\n")?;
+            progress(
+                OutputFormat::Json,
+                &format!("📊 {}", tr!("stats-written", path = out.display())),
+            );
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// Executes `digest send`, mailing the digest of a quick check or a saved
+/// report
+///
+/// A check is recorded in the scan history like `check`, so the digest
+/// lists what changed since the previous run. `dry_run` prints the Markdown
+/// digest instead and leaves the history alone.
+///
+/// # Errors
+///
+/// Returns an error if the check fails, the saved report cannot be read,
+/// `[digest]` is incomplete, or the message cannot be sent.
+fn send_digest(
+    path: Option<&std::path::Path>,
+    tags: &[String],
+    saved: Option<&std::path::Path>,
+    dry_run: bool,
+    config: &devhealth::config::Config,
+    network: &mut NetworkGate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::digest::{self, Digest};
+    use devhealth::history::ScanHistory;
+
+    if !dry_run {
+        if !network.allow(digest::DIGEST_DELIVERY) {
+            return Err(UsageError(
+                "digest send needs the network; use --dry-run or run without --offline".to_string(),
+            )
+            .into());
+        }
+        digest::require_config(&config.digest)?;
+    }
+    let now = display::unix_now();
+    let results = match saved {
+        Some(file) => {
+            let mut results = devhealth::aggregate::load_results(file)?.results;
+            results.redact();
+            results
+        }
+        None => {
+            let path = wsl::resolve_path(&digest::root(path, &config.digest));
+            progress(
+                OutputFormat::Json,
+                &format!("🔍 {}", tr!("check-start", path = path.display())),
+            );
+            let filter = TagFilter::load(tags)?;
+            report_tag_filter(tags, OutputFormat::Json);
+            let mut results = quick_check(&path, &filter, config, network)?;
+            if let Ok(path) = devhealth::acks::AckStore::default_path() {
+                let store = devhealth::acks::AckStore::load(&path)?;
+                results.acknowledge(&store.active(now));
+            }
+            let tag_filter =
+                TagFilter::new(&[], config, TagStore::load(&TagStore::default_path()?)?);
+            config.checks.hide_disabled(&mut results, &tag_filter);
+            results.findings =
+                findings::collect(&results, &config.severity, &config.checks, &tag_filter);
+            results.redact();
+            if config.scan.history {
+                let path = ScanHistory::default_path()?;
+                let mut history = ScanHistory::load(&path)?;
+                results.changes = history.record(&results, now);
+                if !dry_run {
+                    history.prune(Retention::from_config(&config.scan), now);
+                    history.save(&path)?;
+                }
+            }
+            results
+        }
+    };
+
+    let digest = Digest::render(&results, now);
+    if dry_run {
+        print!("{}", digest.markdown);
+        return Ok(());
+    }
+    digest::send(&config.digest, &digest, now)?;
+    progress(
+        OutputFormat::Json,
+        &format!(
+            "📧 {}",
+            tr!("digest-sent", recipients = config.digest.to.join(", "))
+        ),
+    );
+    Ok(())
+}
+
+/// Executes `digest schedule`, adding a crontab entry that sends the digest
+///
+/// # Errors
+///
+/// Returns an error if `[digest]` is incomplete, the schedule or path is
+/// invalid, or the crontab cannot be updated.
+fn schedule_digest(
+    path: Option<&std::path::Path>,
+    config: &devhealth::config::Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::digest;
+    use devhealth::setup;
+
+    digest::require_config(&config.digest)?;
+    // Cron starts in the home directory, so the entry needs an absolute path
+    let root = std::fs::canonicalize(digest::root(path, &config.digest))?;
+    let entry = digest::schedule_entry(&std::env::current_exe()?, &root, &config.digest.schedule)?;
+    if setup::install_schedule(&entry)? {
+        say(&format!(
+            "✅ {}",
+            tr!(
+                "digest-scheduled",
+                schedule = config.digest.schedule.as_str()
+            )
+        ));
+    } else {
+        say(&format!("ℹ️  {}", tr!("digest-schedule-exists")));
+    }
+    Ok(())
+}
+
+/// Executes `auth login` and `auth logout`
+///
+/// # Errors
+///
+/// Returns an error if the secret cannot be read or the system keyring
+/// cannot be reached.
+fn manage_credentials(action: AuthCommands) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::auth;
+
+    match action {
+        AuthCommands::Login { provider } => {
+            let secret =
+                auth::read_secret(provider, &tr!("auth-prompt", secret = provider.describe()))?;
+            auth::store(provider, &secret)?;
+            say(&format!(
+                "🔑 {}",
+                tr!("auth-stored", secret = provider.describe())
+            ));
+        }
+        AuthCommands::Logout { provider } => {
+            if auth::remove(provider)? {
+                say(&format!(
+                    "✅ {}",
+                    tr!("auth-removed", secret = provider.describe())
+                ));
+            } else {
+                say(&format!(
+                    "ℹ️  {}",
+                    tr!("auth-not-stored", secret = provider.describe())
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Global flags that override configuration settings
+#[derive(Clone, Copy)]
+struct GlobalFlags {
+    lang: Option<Lang>,
+    accessible: bool,
+    offline: bool,
+}
+
+/// Executes `config check`, listing every problem in the config file
+///
+/// Returns [`ExitStatus::Usage`] if there is any.
+///
+/// # Errors
+///
+/// Returns an error if a given file cannot be read or the file is not valid
+/// TOML.
+fn check_config(file: Option<&std::path::Path>) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    use devhealth::config::Config;
+
+    let path = match file {
+        Some(file) => file.to_path_buf(),
+        None => match Config::default_path() {
+            Some(path) if path.exists() => path,
+            path => {
+                let path = path
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_default();
+                say(&format!("ℹ️  {}", tr!("config-check-missing", path = path)));
+                return Ok(ExitStatus::Ok);
+            }
+        },
+    };
+    let content = std::fs::read_to_string(&path).map_err(devhealth::config::ConfigError::from)?;
+    let (_, issues) = Config::check(&content)?;
+    if issues.is_empty() {
+        say(&format!(
+            "✅ {}",
+            tr!("config-check-valid", path = path.display())
+        ));
+        return Ok(ExitStatus::Ok);
+    }
+    say(&format!(
+        "❌ {}",
+        tr!(
+            "config-check-problems",
+            count = issues.len(),
+            path = path.display()
+        )
+    ));
+    for issue in &issues {
+        say(&format!("  - {}", issue));
+    }
+    Ok(ExitStatus::Usage)
+}
+
+/// Executes `config show`, printing the configuration in effect as TOML
+///
+/// Comments above it name the config file and every setting the
+/// environment or a flag overrides.
+///
+/// # Errors
+///
+/// Returns an error if the configuration cannot be written as TOML.
+fn show_config(
+    mut config: devhealth::config::Config,
+    flags: GlobalFlags,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::auth::{self, Provider};
+    use devhealth::config::Config;
+
+    let mut notes = Vec::new();
+    match Config::default_path() {
+        Some(path) if path.exists() => notes.push(tr!("config-file", path = path.display())),
+        Some(path) => notes.push(tr!("config-file-missing", path = path.display())),
+        None => {}
+    }
+
+    let lang_source = match (flags.lang, config.lang) {
+        (Some(_), _) => Some("--lang".to_string()),
+        (None, Some(_)) => None,
+        (None, None) => Some(tr!("config-source-locale")),
+    };
+    config.lang = Some(i18n::language());
+    if let Some(source) = lang_source {
+        notes.push(tr!("config-override", key = "lang", source = source));
+    }
+    if !config.accessible && display::is_accessible() {
+        config.accessible = true;
+        let source = if flags.accessible {
+            "--accessible".to_string()
+        } else {
+            tr!("config-source-locale")
+        };
+        notes.push(tr!("config-override", key = "accessible", source = source));
+    }
+    if flags.offline {
+        notes.push(tr!("config-offline"));
+    }
+
+    let forge = [
+        (
+            Provider::GitHub,
+            "forge.github_token",
+            &mut config.forge.github_token,
+        ),
+        (
+            Provider::GitLab,
+            "forge.gitlab_token",
+            &mut config.forge.gitlab_token,
+        ),
+    ];
+    for (provider, key, token) in forge {
+        let source = if auth::stored(provider).ok().flatten().is_some() {
+            Some(tr!("config-source-keyring"))
+        } else {
+            provider
+                .env_vars()
+                .iter()
+                .find(|name| std::env::var(name).is_ok_and(|value| !value.trim().is_empty()))
+                .map(|name| name.to_string())
+        };
+        if let Some(source) = source {
+            *token = Some(String::new());
+            notes.push(tr!("config-override", key = key, source = source));
+        }
+    }
+    if config.policy.url.is_some() {
+        notes.push(tr!("config-policy"));
+    }
+
+    println!("# {}", tr!("config-show-header"));
+    for note in notes {
+        println!("# {}", note);
+    }
+    println!();
+    print!("{}", config.to_toml()?);
+    Ok(())
+}
+
+/// Executes `debug-bundle`, writing the latest crash again for a bug report
+///
+/// # Errors
+///
+/// Returns an error if the latest crash cannot be read or the bundle cannot
+/// be written.
+fn debug_bundle(out: Option<std::path::PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = crash::crash_dir()?;
+    let out = out.unwrap_or_else(|| dir.join(format!("debug-bundle-{}.json", display::unix_now())));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let bundle = crash::regenerate(&dir, &args, &out)?;
+    say(&format!(
+        "📋 {}",
+        tr!("debug-bundle-written", path = out.display())
+    ));
+    match &bundle.failure {
+        Some(failure) => say(&format!(
+            "ℹ️  {}",
+            tr!("debug-bundle-crash", message = failure.message)
+        )),
+        None => say(&format!("ℹ️  {}", tr!("debug-bundle-no-crash"))),
+    }
+    Ok(())
+}
+
+/// Prints a snapshot, or the differences between two snapshots
+///
+/// # Errors
+///
+/// Returns an error if a snapshot cannot be read.
+fn import_snapshot(
+    path: &std::path::Path,
+    newer: Option<&std::path::Path>,
+    format: OutputFormat,
+    detail: Detail,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::snapshot::Snapshot;
+
+    let snapshot = Snapshot::read(path)?;
+    match newer.map(Snapshot::read).transpose()? {
+        Some(newer) => {
+            let diff = snapshot.diff(&newer);
+            match format {
+                OutputFormat::Text => diff.display(&snapshot.manifest, &newer.manifest),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+                OutputFormat::Diagnostics => return Err(diagnostics_unsupported("import --diff")),
+            }
+        }
+        None => match format {
+            OutputFormat::Text => snapshot.display(detail),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+            OutputFormat::Diagnostics => findings::display_diagnostics(&snapshot.results.findings),
+        },
+    }
+    Ok(())
+}
+
+/// Combines the JSON reports of several machines into a team report
+///
+/// # Errors
+///
+/// Returns an error if a pattern matches no file or a file is not a
+/// DevHealth JSON report.
+fn merge_results(
+    files: &[std::path::PathBuf],
+    format: OutputFormat,
+    detail: Detail,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::aggregate::{self, TeamReport};
+
+    let files = aggregate::expand_inputs(files)?;
+    progress(
+        format,
+        &format!("👥 {}", tr!("merge-loading", count = files.len())),
+    );
+    let inputs = files
+        .iter()
+        .map(|file| {
+            aggregate::load_results(file).map(|mut input| {
+                input.results.redact();
+                input
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let report = TeamReport::merge(&inputs);
+
+    match format {
+        OutputFormat::Text => report.display(detail),
+        OutputFormat::Json => println!("{}", report.to_json()?),
+        OutputFormat::Diagnostics => return Err(diagnostics_unsupported("merge")),
+    }
+    Ok(())
+}
+
+/// Loads the organization policy for `check` and `scan`
+///
+/// Replaces the `[deep]` limits with the policy's and announces the policy;
+/// the caller applies its required scanners and `fail_on` kinds. Returns
+/// `None` when no policy is configured.
+///
+/// # Errors
+///
+/// Returns an error if a policy is configured but none could be synced or
+/// loaded.
+fn apply_policy(
+    config: &mut devhealth::config::Config,
+    network: &mut NetworkGate,
+    format: OutputFormat,
+) -> Result<Option<devhealth::policy::Policy>, Box<dyn std::error::Error>> {
+    let Some(policy) = devhealth::policy::load(&config.policy, network)? else {
+        return Ok(None);
+    };
+    if let Some(deep) = &policy.deep {
+        config.deep = deep.clone();
+    }
+    let url = config.policy.url.as_deref().unwrap_or_default();
+    progress(format, &format!("📜 {}", tr!("policy-applied", url = url)));
+    Ok(Some(policy))
+}
+
+/// Executes the `policy` subcommands
+///
+/// # Errors
+///
+/// Returns an error if no policy is configured, syncing is requested in
+/// offline mode, or the policy cannot be synced or read.
+fn manage_policy(
+    action: PolicyCommands,
+    config: &devhealth::config::Config,
+    network: &mut NetworkGate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::policy::{self, PolicyCache, SyncOutcome};
+
+    let Some(url) = config.policy.url.as_deref() else {
+        return Err(UsageError("no organization policy configured; set `url` in the [policy] section of devhealth.toml".to_string()).into());
+    };
+    let cache = PolicyCache::default_location()?;
+
+    match action {
+        PolicyCommands::Sync => {
+            if !network.allow(policy::POLICY_CHECK) {
+                return Err(UsageError("policy sync needs network access".to_string()).into());
+            }
+            say(&format!("🔄 {}", tr!("policy-syncing", url = url)));
+            match policy::sync(&config.policy, &cache)? {
+                SyncOutcome::Updated => say(&format!("✅ {}", tr!("policy-updated"))),
+                SyncOutcome::NotModified => say(&format!("✅ {}", tr!("policy-not-modified"))),
+            }
+        }
+        PolicyCommands::Show => {
+            let Some(policy) = cache.load()? else {
+                say(&format!("ℹ️  {}", tr!("policy-not-synced")));
+                return Ok(());
+            };
+            let synced = cache
+                .last_synced()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|since| display::age(since.as_secs(), display::unix_now()))
+                .unwrap_or_default();
+            let join = |names: Vec<String>| {
+                if names.is_empty() {
+                    i18n::t("policy-none").to_string()
+                } else {
+                    names.join(", ")
+                }
+            };
+
+            say(&format!(
+                "📜 {}",
+                tr!("policy-source", url = url, synced = synced)
+            ));
+            say(&format!(
+                "  {}",
+                tr!(
+                    "policy-required-scanners",
+                    scanners = join(
+                        policy
+                            .required_scanners
+                            .iter()
+                            .map(|s| s.name().to_string())
+                            .collect()
+                    )
+                )
+            ));
+            say(&format!(
+                "  {}",
+                tr!(
+                    "policy-fail-on",
+                    kinds = join(
+                        policy
+                            .fail_on
+                            .iter()
+                            .filter_map(|k| k.to_possible_value())
+                            .map(|v| v.get_name().to_string())
+                            .collect()
+                    )
+                )
+            ));
+            if let Some(deep) = &policy.deep {
+                say(&format!(
+                    "  {}",
+                    tr!(
+                        "policy-deep",
+                        timeout = deep.timeout_secs,
+                        tests = deep.tests.to_string()
+                    )
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Executes `init vscode`, reporting what happened to each file
+///
+/// # Errors
+///
+/// Returns an error if `path` is not a directory or a file cannot be
+/// written.
+fn init_vscode(path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::editor::{self, FileOutcome};
+
+    if !path.is_dir() {
+        return Err(UsageError(format!("{} is not a directory", path.display())).into());
+    }
+    for file in editor::init_vscode(path)? {
+        let path = file.path.display();
+        match file.outcome {
+            FileOutcome::Created => say(&format!("✅ {}", tr!("init-created", path = path))),
+            FileOutcome::Updated(added) => say(&format!(
+                "✅ {}",
+                tr!("init-updated", path = path, entries = added.join(", "))
+            )),
+            FileOutcome::Unchanged => say(&format!("ℹ️  {}", tr!("init-unchanged", path = path))),
+            FileOutcome::Skipped => say(&format!("⚠️  {}", tr!("init-skipped", path = path))),
+        }
+    }
+    Ok(())
+}
+
+/// Executes `init` without an editor, writing `devhealth.toml`
+///
+/// Asks on the terminal unless `--yes` is given or stdin is not a
+/// terminal, in which case every suggested answer is taken. The schedule
+/// and hooks are only installed when asked for.
+///
+/// # Errors
+///
+/// Returns an error if the configuration exists and `force` is not set, or
+/// if the file, crontab or hooks cannot be written.
+fn init_config(yes: bool, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::setup::{self, Answers, Environment, SetupError};
+    use std::io::IsTerminal;
+
+    let path = devhealth::config::Config::default_path().ok_or(SetupError::NoConfigDirectory)?;
+    if path.exists() && !force {
+        return Err(UsageError(SetupError::ConfigExists(path).to_string()).into());
+    }
+
+    let env = Environment::detect();
+    let tools: Vec<&str> = env
+        .git
+        .then_some("git")
+        .into_iter()
+        .chain(env.tools.iter().copied())
+        .collect();
+    say(&format!(
+        "🔎 {}",
+        tr!(
+            "setup-detected",
+            tools = if tools.is_empty() {
+                "-".to_string()
+            } else {
+                tools.join(", ")
+            }
+        )
+    ));
+    let current_dir = std::env::current_dir()?;
+    let answers = if yes || !std::io::stdin().is_terminal() {
+        Answers::defaults(&env, &current_dir)
+    } else {
+        setup::ask(
+            &env,
+            &current_dir,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stdout(),
+        )?
+    };
+
+    setup::write_config(&path, &setup::render(&answers, &env), force)?;
+    say(&format!(
+        "✅ {}",
+        tr!("init-created", path = path.display())
+    ));
+
+    let exe = std::env::current_exe()?;
+    if answers.schedule {
+        if setup::install_schedule(&setup::schedule_entry(&exe, &answers.roots))? {
+            say(&format!("✅ {}", tr!("setup-scheduled")));
+        } else {
+            say(&format!("ℹ️  {}", tr!("setup-already-scheduled")));
+        }
+    }
+    if answers.hooks {
+        let outcome = setup::install_hooks(&answers.roots, &exe)?;
+        say(&format!(
+            "✅ {}",
+            tr!("setup-hooks-installed", count = outcome.installed.len())
+        ));
+        if !outcome.skipped.is_empty() {
+            say(&format!(
+                "ℹ️  {}",
+                tr!("setup-hooks-skipped", count = outcome.skipped.len())
+            ));
+        }
+    }
+    say(&format!("💡 {}", tr!("setup-next", path = path.display())));
+    Ok(())
+}
+
+/// Executes `fix sync-forks`, bringing stale forks up to date
+///
+/// Forks found through an `upstream` remote are fast-forwarded locally (and
+/// pushed with `push`); GitHub forks found through the API are synced on
+/// GitHub. With `dry_run`, only reports how far behind each fork is.
+///
+/// Returns [`ExitStatus::Internal`] if any fork could not be synced.
+///
+/// # Errors
+///
+/// Returns an error if syncing is requested in offline mode or the
+/// directory cannot be scanned.
+fn sync_forks(
+    path: &std::path::Path,
+    tags: &[String],
+    dry_run: bool,
+    push: bool,
+    config: &devhealth::config::Config,
+    network: &mut NetworkGate,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    use scanner::git::{ForkSource, ForkSync};
+
+    if !dry_run && !network.allow("fork sync") {
+        return Err(UsageError(
+            "syncing forks needs network access; use `--dry-run` to list stale forks offline"
+                .to_string(),
+        )
+        .into());
+    }
+
+    let path = wsl::resolve_path(path);
+    let filter = TagFilter::load(tags)?;
+    report_tag_filter(tags, OutputFormat::Text);
+    let mut repos = scanner::git::scan_directory_matching(&path, |p| filter.matches(p))?;
+    scanner::forge::enrich_repositories(&mut repos, &config.forge, network);
+
+    let forks: Vec<_> = repos.iter().filter(|repo| repo.fork.is_some()).collect();
+    if forks.is_empty() {
+        say(&format!("ℹ️  {}", tr!("fix-no-forks")));
+        return Ok(ExitStatus::Ok);
+    }
+
+    let mut failed = false;
+    for repo in forks {
+        let Some(fork) = &repo.fork else { continue };
+        let name = repo.path.display();
+        let upstream = fork.upstream_branch();
+
+        if dry_run || fork.behind == 0 {
+            if fork.behind == 0 {
+                say(&format!(
+                    "✅ {}",
+                    tr!("fix-fork-up-to-date", repo = name, upstream = upstream)
+                ));
+            } else {
+                say(&format!(
+                    "ℹ️  {}",
+                    tr!(
+                        "fix-fork-would-sync",
+                        repo = name,
+                        count = fork.behind,
+                        upstream = upstream
+                    )
+                ));
+            }
+            continue;
+        }
+
+        let outcome = match fork.source {
+            ForkSource::Remote => {
+                scanner::git::fast_forward_fork(repo, push).map(|sync| match sync {
+                    ForkSync::UpToDate => format!(
+                        "✅ {}",
+                        tr!("fix-fork-up-to-date", repo = name, upstream = upstream)
+                    ),
+                    ForkSync::FastForwarded(count) => format!(
+                        "✅ {}",
+                        tr!(
+                            "fix-fork-synced",
+                            repo = name,
+                            count = count,
+                            upstream = upstream
+                        )
+                    ),
+                    ForkSync::Dirty => format!(
+                        "⚠️  {}",
+                        tr!("fix-fork-dirty", repo = name, branch = fork.branch)
+                    ),
+                    ForkSync::NoLocalBranch => format!(
+                        "⚠️  {}",
+                        tr!("fix-fork-no-branch", repo = name, branch = fork.branch)
+                    ),
+                })
             }
+            ForkSource::Forge => scanner::forge::remote_for(&repo.path, &config.forge)
+                .ok_or_else(|| "origin is not a forge repository".into())
+                .and_then(|remote| {
+                    scanner::forge::sync_fork(&remote, &fork.branch, &config.forge)
+                        .map_err(Into::into)
+                })
+                .map(|message| {
+                    format!(
+                        "✅ {}",
+                        tr!("fix-fork-synced-forge", repo = name, message = message)
+                    )
+                }),
+        };
+        match outcome {
+            Ok(message) => say(&message),
+            Err(e) => {
+                failed = true;
+                say(&format!(
+                    "❌ {}",
+                    tr!("fix-fork-failed", repo = name, error = e)
+                ));
+            }
+        }
+    }
+
+    Ok(if failed {
+        ExitStatus::Internal
+    } else {
+        ExitStatus::Ok
+    })
+}
+
+/// Executes `repos missing`, listing the forge repositories not cloned
+/// under the given paths, or cloning the ones named with `--clone`
+///
+/// Returns [`ExitStatus::Internal`] if any clone failed.
+///
+/// # Errors
+///
+/// Returns an error in offline mode, without a forge token, if no forge can
+/// be queried, or if a repository to clone is not missing.
+fn find_missing_repos(
+    action: ReposCommands,
+    config: &devhealth::config::Config,
+    network: &mut NetworkGate,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    let ReposCommands::Missing {
+        paths,
+        owners,
+        archived,
+        clone,
+        into,
+        ssh,
+        format,
+    } = action;
+    if format == OutputFormat::Diagnostics {
+        return Err(diagnostics_unsupported("repos missing"));
+    }
+    if !network.allow("forge repository listing") {
+        return Err(
+            UsageError("listing forge repositories needs network access".to_string()).into(),
+        );
+    }
 
-            if !git && !deps && !system {
-                println!("ℹ️  No scan options specified. Use --git, --deps, or --system flags to enable specific scans.");
+    let paths: Vec<std::path::PathBuf> = paths.iter().map(|path| wsl::resolve_path(path)).collect();
+    progress(format, &format!("📡 {}", tr!("repos-listing")));
+    let listed = scanner::forge::list_repositories(&config.forge)?;
+    let total = listed.len();
+    let local = repos::local_remotes(&paths, &config.forge.gitlab_hosts)?;
+    let missing = repos::missing(listed, &local, &owners, archived);
+    let selected = repos::select(&missing, &clone)?;
+    if selected.is_empty() {
+        match format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&missing)?),
+            _ => repos::display_results(&missing, total),
+        }
+        return Ok(ExitStatus::Ok);
+    }
+
+    let into = into
+        .as_deref()
+        .map(wsl::resolve_path)
+        .unwrap_or_else(|| paths[0].clone());
+    let mut failed = false;
+    for repository in selected {
+        progress(
+            format,
+            &format!(
+                "⬇️  {}",
+                tr!("repos-cloning", repo = repository.project.as_str())
+            ),
+        );
+        match repos::clone(repository, &into, ssh) {
+            Ok(target) => progress(
+                format,
+                &format!(
+                    "✅ {}",
+                    tr!(
+                        "repos-cloned",
+                        repo = repository.project.as_str(),
+                        path = target.display()
+                    )
+                ),
+            ),
+            Err(e) => {
+                failed = true;
+                progress(
+                    format,
+                    &format!(
+                        "❌ {}",
+                        tr!(
+                            "repos-clone-failed",
+                            repo = repository.project.as_str(),
+                            error = e
+                        )
+                    ),
+                );
             }
+        }
+    }
+    Ok(if failed {
+        ExitStatus::Internal
+    } else {
+        ExitStatus::Ok
+    })
+}
+
+/// Executes `deps pin-suggestions`, listing exact pins for loose constraints
+///
+/// With `patch`, prints a unified diff applying the pins instead.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be scanned, a manifest cannot be
+/// read for the patch, or the output format is `diagnostics`.
+fn suggest_pins(
+    path: &std::path::Path,
+    tags: &[String],
+    patch: bool,
+    format: OutputFormat,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    if format == OutputFormat::Diagnostics {
+        return Err(diagnostics_unsupported("deps pin-suggestions"));
+    }
+    let path = wsl::resolve_path(path);
+    let filter = TagFilter::load(tags)?;
+    if !patch {
+        report_tag_filter(tags, format);
+    }
+    let reports = scanner::deps::scan_dependencies_matching(&path, |p| filter.matches(p))?;
+    let suggestions = scanner::pins::suggest_pins(&reports);
+
+    if patch {
+        print!("{}", scanner::pins::patch(&suggestions, &path)?);
+        return Ok(ExitStatus::Ok);
+    }
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&suggestions)?),
+        _ => scanner::pins::display_suggestions(&suggestions),
+    }
+    Ok(ExitStatus::Ok)
+}
+
+/// Executes `deps exposure`, showing the packages and versions the projects
+/// share and, with `audit`, the vulnerable packages affecting the most
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be scanned or the audit has no
+/// advisory database to use.
+fn show_exposure(
+    path: &std::path::Path,
+    tags: &[String],
+    audit: bool,
+    top: usize,
+    format: OutputFormat,
+    ignores: &scanner::audit::AuditConfig,
+    network: &mut NetworkGate,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    if format == OutputFormat::Diagnostics {
+        return Err(diagnostics_unsupported("deps exposure"));
+    }
+    let path = wsl::resolve_path(path);
+    let filter = TagFilter::load(tags)?;
+    report_tag_filter(tags, format);
+    let reports = scanner::deps::scan_dependencies_matching(&path, |p| filter.matches(p))?;
+    let audit = if audit {
+        progress(format, &format!("🛡️  {}", tr!("scan-audit")));
+        let mut audit = scanner::audit::run_audit(&reports, network)?;
+        audit.apply_ignores(ignores, display::unix_now());
+        Some(audit)
+    } else {
+        None
+    };
+    let report = scanner::exposure::ExposureReport::build(&reports, audit.as_ref());
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => scanner::exposure::display_report(&report, (top > 0).then_some(top)),
+    }
+    Ok(ExitStatus::Ok)
+}
+
+/// Executes `deps find`, listing every project using a package
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be scanned or, with `stored`,
+/// if no scan has recorded dependencies yet.
+fn find_dependency(
+    package: &str,
+    path: &std::path::Path,
+    tags: &[String],
+    stored: bool,
+    format: OutputFormat,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    if format == OutputFormat::Diagnostics {
+        return Err(diagnostics_unsupported("deps find"));
+    }
+    let filter = TagFilter::load(tags)?;
+    report_tag_filter(tags, format);
+    let reports = if stored {
+        let inventory =
+            watch::DependencyInventory::load(&watch::DependencyInventory::default_path()?)?;
+        if inventory.projects.is_empty() {
+            return Err(watch::WatchError::NoInventory.into());
+        }
+        let mut reports = inventory.reports();
+        reports.retain(|report| filter.matches(&report.project_path));
+        reports
+    } else {
+        let path = wsl::resolve_path(path);
+        scanner::deps::scan_dependencies_matching(&path, |p| filter.matches(p))?
+    };
+    let matches = scanner::exposure::find_package(&reports, package);
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&matches)?),
+        _ => scanner::exposure::display_matches(package, &matches),
+    }
+    Ok(ExitStatus::Ok)
+}
+
+/// Executes `deps upgrade-impact`, listing the constraints that block
+/// upgrading a package
+///
+/// Returns [`ExitStatus::Findings`] if any constraint blocks the upgrade.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be scanned.
+fn upgrade_impact(
+    package: &str,
+    version: &str,
+    path: &std::path::Path,
+    tags: &[String],
+    format: OutputFormat,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    if format == OutputFormat::Diagnostics {
+        return Err(diagnostics_unsupported("deps upgrade-impact"));
+    }
+    let filter = TagFilter::load(tags)?;
+    report_tag_filter(tags, format);
+    let path = wsl::resolve_path(path);
+    let reports = scanner::deps::scan_dependencies_matching(&path, |p| filter.matches(p))?;
+    let impact = scanner::impact::UpgradeImpact::analyze(&reports, package, version);
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&impact)?),
+        _ => scanner::impact::display_impact(&impact),
+    }
+    Ok(if impact.count(scanner::impact::Verdict::Blocking) > 0 {
+        ExitStatus::Findings
+    } else {
+        ExitStatus::Ok
+    })
+}
+
+/// Executes `deps align`, suggesting versions for the projects to
+/// converge on
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be scanned.
+fn align_versions(
+    path: &std::path::Path,
+    tags: &[String],
+    min_spread: scanner::alignment::Spread,
+    format: OutputFormat,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    if format == OutputFormat::Diagnostics {
+        return Err(diagnostics_unsupported("deps align"));
+    }
+    let filter = TagFilter::load(tags)?;
+    report_tag_filter(tags, format);
+    let path = wsl::resolve_path(path);
+    let reports = scanner::deps::scan_dependencies_matching(&path, |p| filter.matches(p))?;
+    let report = scanner::alignment::AlignmentReport::build(&reports, min_spread);
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => scanner::alignment::display_report(&report),
+    }
+    Ok(ExitStatus::Ok)
+}
+
+/// Executes `licenses`, listing the license of every dependency across
+/// projects
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be scanned.
+fn list_licenses(
+    path: &std::path::Path,
+    tags: &[String],
+    format: LicenseFormat,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    let path = wsl::resolve_path(path);
+    let filter = TagFilter::load(tags)?;
+    // Only text output goes to stdout with the progress messages
+    let progress_format = match format {
+        LicenseFormat::Text => OutputFormat::Text,
+        LicenseFormat::Json | LicenseFormat::Csv => OutputFormat::Json,
+    };
+    report_tag_filter(tags, progress_format);
+    let reports = scanner::deps::scan_dependencies_matching(&path, |p| filter.matches(p))?;
+    let inventory = scanner::licenses::LicenseInventory::build(&reports);
+
+    match format {
+        LicenseFormat::Text => scanner::licenses::display_inventory(&inventory),
+        LicenseFormat::Json => println!("{}", serde_json::to_string_pretty(&inventory)?),
+        LicenseFormat::Csv => print!("{}", inventory.to_csv()),
+    }
+    Ok(ExitStatus::Ok)
+}
+
+/// Executes `watch-advisories`, alerting about advisories published since
+/// the previous check for the stored dependency inventory
+///
+/// With `interval`, checks every `interval` minutes until interrupted.
+/// Returns [`ExitStatus::Findings`] if a single check found new advisories.
+///
+/// # Errors
+///
+/// Returns an error if there is no inventory yet, or if the advisories or
+/// the watch state cannot be read.
+fn watch_advisories(
+    interval: Option<u64>,
+    notify: bool,
+    format: OutputFormat,
+    lock_mode: LockMode,
+    ignores: &scanner::audit::AuditConfig,
+    network: &mut NetworkGate,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    if format == OutputFormat::Diagnostics {
+        return Err(diagnostics_unsupported("watch-advisories"));
+    }
+    loop {
+        let lock = lock_state(lock_mode)?;
+        let inventory =
+            watch::DependencyInventory::load(&watch::DependencyInventory::default_path()?)?;
+        if inventory.projects.is_empty() {
+            return Err(watch::WatchError::NoInventory.into());
+        }
+        let state_path = watch::WatchState::default_path()?;
+        let mut state = watch::WatchState::load(&state_path)?;
+        let first_check = state.checked_at.is_none();
+
+        let reports = inventory.reports();
+        let mut audit = scanner::audit::run_audit(&reports, network)?;
+        audit.apply_ignores(ignores, display::unix_now());
+        prompt::record_audit(&reports, &audit.vulnerabilities);
+        let alerts = state.check(&audit.vulnerabilities, display::unix_now());
+        state.save(&state_path)?;
+
+        match format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&alerts)?),
+            _ => watch::display_check(&inventory, &alerts, first_check, state.known.len()),
+        }
+        if notify && !alerts.is_empty() {
+            let (title, body) = watch::notification(&alerts);
+            watch::notify(&title, &body);
+        }
+
+        let Some(minutes) = interval else {
+            return Ok(if alerts.is_empty() {
+                ExitStatus::Ok
+            } else {
+                ExitStatus::Findings
+            });
+        };
+        drop(lock);
+        progress(format, &tr!("watch-next-check", minutes = minutes));
+        std::thread::sleep(std::time::Duration::from_secs(minutes * 60));
+    }
+}
+
+/// Executes `clean`, removing build output untouched for months
+///
+/// The candidates are the stale artifact directories `scan --sizes` reports.
+/// With `dry_run`, only lists them and the space they take.
+///
+/// Returns [`ExitStatus::Internal`] if any directory could not be removed.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be scanned.
+fn clean_artifacts(
+    path: &std::path::Path,
+    tags: &[String],
+    dry_run: bool,
+    yes: bool,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    let path = wsl::resolve_path(path);
+    let filter = TagFilter::load(tags)?;
+    report_tag_filter(tags, OutputFormat::Text);
+    let mut repos = scanner::git::scan_directory_matching(&path, |p| filter.matches(p))?;
+    let now = display::unix_now();
+    scanner::sizes::enrich_repositories(&mut repos, now);
+
+    // Output git does not ignore, or that holds committed files, is not ours to delete
+    let (disposable, kept): (Vec<_>, Vec<_>) = repos
+        .iter()
+        .flat_map(|repo| {
+            repo.stale_artifacts
+                .iter()
+                .map(move |artifacts| (repo, artifacts))
+        })
+        .partition(|(repo, artifacts)| scanner::sizes::is_disposable(&repo.path, &artifacts.path));
+    for (_, artifacts) in kept {
+        say(&format!(
+            "⚠️  {}",
+            tr!("clean-skipped-tracked", path = artifacts.path.display())
+        ));
+    }
+    let candidates: Vec<_> = disposable
+        .into_iter()
+        .map(|(_, artifacts)| artifacts)
+        .collect();
+    if candidates.is_empty() {
+        say(&format!("ℹ️  {}", tr!("clean-none")));
+        return Ok(ExitStatus::Ok);
+    }
+
+    if !dry_run && !yes {
+        for artifacts in &candidates {
+            let age = display::age(artifacts.last_modified, now);
+            say(&format!(
+                "ℹ️  {}",
+                tr!(
+                    "clean-would-remove",
+                    path = artifacts.path.display(),
+                    size = display::bytes(artifacts.bytes),
+                    age = age
+                )
+            ));
+        }
+        let size = display::bytes(candidates.iter().map(|artifacts| artifacts.bytes).sum());
+        if !confirm(&tr!("clean-confirm", count = candidates.len(), size = size))? {
+            say(&format!("ℹ️  {}", tr!("clean-cancelled")));
+            return Ok(ExitStatus::Ok);
+        }
+    }
+
+    let mut failed = false;
+    let mut freed = 0;
+    for artifacts in candidates {
+        let dir = artifacts.path.display();
+        let size = display::bytes(artifacts.bytes);
+        if dry_run {
+            say(&format!(
+                "ℹ️  {}",
+                tr!(
+                    "clean-would-remove",
+                    path = dir,
+                    size = size,
+                    age = display::age(artifacts.last_modified, now)
+                )
+            ));
+            freed += artifacts.bytes;
+            continue;
+        }
+        match std::fs::remove_dir_all(&artifacts.path) {
+            Ok(()) => {
+                say(&format!(
+                    "✅ {}",
+                    tr!("clean-removed", path = dir, size = size)
+                ));
+                freed += artifacts.bytes;
+            }
+            Err(e) => {
+                failed = true;
+                say(&format!(
+                    "❌ {}",
+                    tr!("clean-failed", path = dir, error = e)
+                ));
+            }
+        }
+    }
+    let total = display::bytes(freed);
+    say(&format!(
+        "\n💾 {}",
+        if dry_run {
+            tr!("clean-would-free", size = total)
+        } else {
+            tr!("clean-freed", size = total)
+        }
+    ));
+
+    Ok(if failed {
+        ExitStatus::Internal
+    } else {
+        ExitStatus::Ok
+    })
+}
+
+/// Asks a yes/no question on the terminal, defaulting to no
+///
+/// # Errors
+///
+/// Returns a usage error if stdin is not a terminal, since nobody can
+/// answer, and an I/O error if the answer cannot be read.
+fn confirm(question: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    if !std::io::stdin().is_terminal() {
+        return Err(UsageError(tr!("clean-needs-yes")).into());
+    }
+    print!("{} [y/N]: ", question);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Executes `advisory-db sync` in one of its three modes
+///
+/// - `--from <dir>`: import an exported database into the local mirror
+/// - `--to <dir>`: fetch advisories and write them to `dir`
+/// - neither: fetch advisories into the local mirror
+///
+/// # Errors
+///
+/// Returns an error if fetching is requested in offline mode, if the network
+/// request fails, or if a database cannot be read or written.
+fn sync_advisory_database(
+    path: &std::path::Path,
+    to: Option<std::path::PathBuf>,
+    from: Option<std::path::PathBuf>,
+    network: &mut NetworkGate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use scanner::audit::{self, AdvisoryDatabase};
+
+    let mirror_dir = audit::default_database_dir()?;
+
+    if let Some(from) = from {
+        say(&format!(
+            "📥 {}",
+            tr!("advisory-importing", path = from.display())
+        ));
+        let database = audit::import_database(&from, &mirror_dir)?;
+        say(&format!(
+            "✅ {}",
+            tr!(
+                "advisory-imported",
+                count = database.advisories.len(),
+                path = mirror_dir.display()
+            )
+        ));
+        return Ok(());
+    }
+
+    if !network.allow("advisory database sync") {
+        return Err(UsageError("advisory database sync needs network access; use `--from <dir>` to import an exported database offline".to_string()).into());
+    }
+
+    say(&format!(
+        "🔄 {}",
+        tr!("advisory-fetching", path = path.display())
+    ));
+    let dep_reports = scanner::deps::scan_dependencies(path)?;
+    let fresh = audit::fetch_advisories(&dep_reports)?;
+
+    let target = to.unwrap_or(mirror_dir);
+    let mut database = match AdvisoryDatabase::load(&target) {
+        Ok(existing) => existing,
+        Err(_) => AdvisoryDatabase::new(Vec::new()),
+    };
+    database.merge(fresh);
+    database.save(&target)?;
+
+    say(&format!(
+        "✅ {}",
+        tr!(
+            "advisory-saved",
+            count = database.advisories.len(),
+            path = target.display()
+        )
+    ));
+    Ok(())
+}
+
+/// Executes `show`, printing everything known about one repository
+///
+/// Vulnerabilities are checked against the local advisory database only; the
+/// section is omitted when no database has been synced yet.
+///
+/// # Errors
+///
+/// Returns an error if `repo` is not a git repository, if its dependencies
+/// cannot be scanned, or if the advisory database is unreadable.
+fn show_repository(
+    repo: &std::path::Path,
+    ignores: &scanner::audit::AuditConfig,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use scanner::audit::{self, AdvisoryDatabase, AuditError};
+
+    let repo = devhealth::utils::fs::canonicalize(repo)
+        .map_err(|e| UsageError(format!("Cannot show {}: {}", repo.display(), e)))?;
+    if !repo.join(".git").exists() {
+        return Err(UsageError(format!("{} is not a git repository", repo.display())).into());
+    }
+
+    let git_repo = scanner::git::analyze_repository(&repo);
+    let dep_reports = scanner::deps::scan_dependencies(&repo)?;
+    let audit_report = match AdvisoryDatabase::load(&audit::default_database_dir()?) {
+        Ok(database) => {
+            let mut report = audit::audit_dependencies(&dep_reports, &database);
+            report.apply_ignores(ignores, display::unix_now());
+            Some(report)
+        }
+        Err(AuditError::MissingDatabase(_)) => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    match format {
+        OutputFormat::Text => {
+            scanner::git::display_repository(&git_repo);
+            println!();
+            scanner::deps::display_results_with(&dep_reports, Detail::Full);
+            if let Some(report) = &audit_report {
+                println!();
+                audit::display_results_with(report, Detail::Full);
+            }
+        }
+        OutputFormat::Json => {
+            let results = ScanResults {
+                git: Some(vec![git_repo]),
+                dependencies: Some(dep_reports),
+                audit: audit_report,
+                ..Default::default()
+            };
+            println!("{}", results.to_json()?);
+        }
+        OutputFormat::Diagnostics => return Err(diagnostics_unsupported("show")),
+    }
+
+    Ok(())
+}
+
+/// Prints which tags a filtered run is restricted to
+fn report_tag_filter(tags: &[String], format: OutputFormat) {
+    if !tags.is_empty() {
+        progress(
+            format,
+            &format!("🏷️  {}", tr!("tag-filter", tags = tags.join(", "))),
+        );
+    }
+}
+
+/// Warns that scanning a Windows drive from inside WSL is slow
+fn warn_if_windows_drive(path: &std::path::Path, format: OutputFormat) {
+    if wsl::detect().is_none() {
+        return;
+    }
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    if let Some(drive) = wsl::windows_drive(&absolute) {
+        progress(
+            format,
+            &format!(
+                "⚠️  {}",
+                tr!("wsl-windows-drive", path = path.display(), drive = drive)
+            ),
+        );
+    }
+}
+
+/// Prints a progress message without corrupting machine-readable output
+///
+/// Messages go to stdout for text reports and to stderr for every other
+/// format, so stdout only ever contains the report itself.
+fn progress(format: OutputFormat, message: &str) {
+    match format {
+        OutputFormat::Text => say(message),
+        _ => eprintln!("{}", display::symbols(message)),
+    }
+}
+
+/// Takes the state lock, saying so on stderr if another run holds it
+fn lock_state(mode: LockMode) -> Result<StateLock, Box<dyn std::error::Error>> {
+    let path = StateLock::default_path()?;
+    Ok(StateLock::acquire_with(&path, mode, |pid| {
+        let message = match pid {
+            Some(pid) => tr!("lock-waiting-pid", pid = pid),
+            None => tr!("lock-waiting"),
+        };
+        eprintln!("{}", display::symbols(&format!("⏳ {}", message)));
+    })?)
+}
+
+/// Returns the error for `--format diagnostics` on a command without findings
+fn diagnostics_unsupported(command: &str) -> Box<dyn std::error::Error> {
+    UsageError(format!(
+        "`{}` has no findings to print as diagnostics; use --format text or json",
+        command
+    ))
+    .into()
+}
+
+/// Prints a status message, replacing emoji with labels in accessible mode
+fn say(message: &str) {
+    println!("{}", display::symbols(message));
+}
+
+/// Shapes the collected results and prints them in the requested format
+///
+/// Applies `--only`, `--sort` and `--limit` before rendering, so text,
+/// JSON and porcelain output always show the same entries. `--summary` and `--detail` only
+/// affect text output.
+///
+/// With `history = true` under `[scan]`, records the run in the scan history
+/// and reports what changed since the previous one. With `--notify-desktop`
+/// or `desktop = true` under `[notify]`, new findings at the `[notify]` level
+/// raise a desktop notification.
+///
+/// Rates every finding with the severities from `config` and returns
+/// [`ExitStatus::Findings`] if the full, unfiltered results match
+/// `--fail-on` or reach `--fail-level`.
+///
+/// # Errors
+///
+/// Returns an error if the results cannot be serialized, the tag store
+/// cannot be read for severity overrides selecting tags, or the scan history
+/// or notification state cannot be read or written.
+fn emit_report(
+    mut results: ScanResults,
+    args: &ReportArgs,
+    config: &devhealth::config::Config,
+    network: &NetworkGate,
+) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+    crash::checkpoint(&results);
+    if let Ok(path) = devhealth::acks::AckStore::default_path() {
+        let store = devhealth::acks::AckStore::load(&path)?;
+        results.acknowledge(&store.active(display::unix_now()));
+    }
+    let tag_filter = if config.severity.uses_tags() || config.checks.uses_tags() {
+        TagFilter::new(&[], config, TagStore::load(&TagStore::default_path()?)?)
+    } else {
+        TagFilter::default()
+    };
+    config.checks.hide_disabled(&mut results, &tag_filter);
+    results.findings = findings::collect(&results, &config.severity, &config.checks, &tag_filter);
+    results.redact();
+    if config.scan.history {
+        let path = devhealth::history::ScanHistory::default_path()?;
+        let mut history = devhealth::history::ScanHistory::load(&path)?;
+        results.changes = history.record(&results, display::unix_now());
+        history.prune(Retention::from_config(&config.scan), display::unix_now());
+        history.save(&path)?;
+    }
+    if args.notify_desktop || config.notify.desktop {
+        let path = devhealth::notify::NotifyState::default_path()?;
+        let mut state = devhealth::notify::NotifyState::load(&path)?;
+        let new_findings = state.update(&results, config.notify.level);
+        state.save(&path)?;
+        if !new_findings.is_empty() {
+            let (title, body) = devhealth::notify::notification(&new_findings);
+            watch::notify(&title, &body);
+        }
+    }
+    let fail_level = args.fail_level.or(config.severity.fail_level);
+    let status = if results.has_findings(&args.fail_on)
+        || fail_level.is_some_and(|level| findings::reaches(&results.findings, level))
+    {
+        ExitStatus::Findings
+    } else {
+        ExitStatus::Ok
+    };
+
+    results.skipped_checks = network.skipped().to_vec();
+    args.options().apply(&mut results);
+
+    match args.format {
+        _ if args.porcelain => porcelain::display(&results),
+        OutputFormat::Text => {
+            if args.overview {
+                results.display_overview();
+            } else {
+                results.display_as(args.detail(), args.view);
+            }
+            report_offline_mode(network);
+        }
+        OutputFormat::Json => println!("{}", results.to_json()?),
+        OutputFormat::Diagnostics => findings::display_diagnostics(&results.findings),
+    }
+
+    Ok(status)
+}
+
+/// Executes the `tag` subcommands against the persisted tag store
+///
+/// # Errors
+///
+/// Returns an error if the repository path does not exist or the tag store
+/// or configuration cannot be read or written.
+fn manage_tags(action: TagCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let store_path = TagStore::default_path()?;
+    let mut store = TagStore::load(&store_path)?;
+
+    match action {
+        TagCommands::Add { path, tags } => {
+            let repo = devhealth::utils::fs::canonicalize(&path)
+                .map_err(|e| UsageError(format!("Cannot tag {}: {}", path.display(), e)))?;
+            store.add(&repo, &tags);
+            store.save(&store_path)?;
+            say(&format!(
+                "🏷️  {}",
+                tr!("tag-added", repo = repo.display(), tags = tags.join(", "))
+            ));
+        }
+        TagCommands::Remove { path, tags } => {
+            let repo = tags::canonical(&path);
+            if store.remove(&repo, &tags) {
+                store.save(&store_path)?;
+                say(&format!(
+                    "🏷️  {}",
+                    tr!("tag-removed", repo = repo.display())
+                ));
+            } else {
+                say(&format!(
+                    "ℹ️  {}",
+                    tr!("tag-not-found", repo = repo.display())
+                ));
+            }
+        }
+        TagCommands::List => {
+            let config = devhealth::config::Config::load()?;
+            if store.repositories.is_empty() && config.tags.is_empty() {
+                say(&format!("ℹ️  {}", tr!("tag-none-defined")));
+                return Ok(());
+            }
+
+            for (repo, repo_tags) in &store.repositories {
+                let repo_tags: Vec<&str> = repo_tags.iter().map(String::as_str).collect();
+                say(&format!("🏷️  {}: {}", repo.display(), repo_tags.join(", ")));
+            }
+            for (tag, patterns) in &config.tags {
+                say(&format!(
+                    "⚙️  {}",
+                    tr!("tag-from-config", tag = tag, patterns = patterns.join(", "))
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Executes `ack` and its subcommands against the acknowledgement store
+///
+/// # Errors
+///
+/// Returns an error if the finding ID or date is invalid, or the store
+/// cannot be read or written.
+fn manage_acks(
+    action: Option<AckCommands>,
+    id: Option<String>,
+    until: Option<String>,
+    reason: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::acks::{self, AckStore, Acknowledgement, FindingId};
+
+    let store_path = AckStore::default_path()?;
+    let mut store = AckStore::load(&store_path)?;
+    let now = display::unix_now();
+
+    match action {
+        None => {
+            let finding = id.unwrap_or_default().parse::<FindingId>()?.canonicalized();
+            let until = until.as_deref().map(acks::parse_date).transpose()?;
+            if until.is_some_and(|until| until <= now) {
+                return Err(UsageError("--until must be a date in the future".to_string()).into());
+            }
+            let ack = Acknowledgement {
+                id: finding.to_string(),
+                until,
+                reason,
+                created_at: now,
+            };
+            say(&format!(
+                "🔕 {}",
+                tr!("ack-added", finding = acks::describe(&ack))
+            ));
+            store.add(ack);
+            store.save(&store_path)?;
+        }
+        Some(AckCommands::Remove { id }) => {
+            let canonical = id
+                .parse::<FindingId>()
+                .map(|finding| finding.canonicalized().to_string());
+            if store.remove(&id) || canonical.is_ok_and(|canonical| store.remove(&canonical)) {
+                store.save(&store_path)?;
+                say(&format!("🔔 {}", tr!("ack-removed", finding = id)));
+            } else {
+                say(&format!("ℹ️  {}", tr!("ack-not-found", finding = id)));
+            }
+        }
+        Some(AckCommands::List) => {
+            if store.acknowledgements.is_empty() {
+                say(&format!("ℹ️  {}", tr!("ack-none")));
+            }
+            for ack in &store.acknowledgements {
+                if ack.is_active(now) {
+                    say(&format!("🔕 {}", acks::describe(ack)));
+                } else {
+                    say(&format!(
+                        "🔔 {} ({})",
+                        acks::describe(ack),
+                        tr!("ack-expired")
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the rule catalog, or a single rule
+///
+/// # Errors
+///
+/// Returns a usage error if `rule` is not a known rule ID, or an error if
+/// the catalog cannot be serialized.
+fn list_rules(
+    rule: Option<&str>,
+    format: OutputFormat,
+    config: &devhealth::config::Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut rules = findings::catalog(&config.severity, &config.checks);
+    if let Some(id) = rule {
+        let check = findings::Check::from_rule_id(id)
+            .ok_or_else(|| UsageError(format!("Unknown rule ID: {} (see devhealth rules)", id)))?;
+        rules.retain(|rule| rule.check == check);
+    }
+
+    match format {
+        OutputFormat::Text => findings::display_rules(&rules),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&rules)?),
+        OutputFormat::Diagnostics => return Err(diagnostics_unsupported("rules")),
+    }
+    Ok(())
+}
+
+/// Executes the `history` subcommands
+///
+/// # Errors
+///
+/// Returns an error if the scan or size history cannot be read or written.
+fn manage_history(
+    action: HistoryCommands,
+    config: &devhealth::config::Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (keep, keep_days, dry_run) = match action {
+        HistoryCommands::List { filter, format } => return query_history(filter, format, false),
+        HistoryCommands::Show { filter, format } => return query_history(filter, format, true),
+        HistoryCommands::Prune {
+            keep,
+            keep_days,
+            dry_run,
+        } => (keep, keep_days, dry_run),
+    };
+    let configured = Retention::from_config(&config.scan);
+    let retention = Retention {
+        runs: keep.unwrap_or(configured.runs),
+        days: keep_days.unwrap_or(configured.days),
+    };
+    let now = display::unix_now();
+
+    let history_path = devhealth::history::ScanHistory::default_path()?;
+    let mut history = devhealth::history::ScanHistory::load(&history_path)?;
+    let runs = history.prune(retention, now);
+    let sizes_path = scanner::sizes::SizeHistory::default_path()?;
+    let mut sizes = scanner::sizes::SizeHistory::load(&sizes_path)?;
+    let sizes_dropped = sizes.prune(retention.oldest(now));
+
+    if !dry_run {
+        if runs > 0 {
+            history.save(&history_path)?;
+        }
+        if sizes_dropped > 0 {
+            sizes.save(&sizes_path)?;
+        }
+    }
+    let message = if dry_run {
+        tr!("history-prune-dry-run", runs = runs, sizes = sizes_dropped)
+    } else {
+        tr!("history-pruned", runs = runs, sizes = sizes_dropped)
+    };
+    say(&format!("🧹 {}", message));
+    Ok(())
+}
+
+/// Executes `history list`, or `history show` with `timelines`
+///
+/// # Errors
+///
+/// Returns an error if `--since` is not a date or the scan history cannot
+/// be read.
+fn query_history(
+    filter: HistoryFilterArgs,
+    format: OutputFormat,
+    timelines: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::history::{self, HistoryQuery, ScanHistory};
+
+    let query = HistoryQuery {
+        since: filter
+            .since
+            .as_deref()
+            .map(devhealth::acks::parse_date)
+            .transpose()?,
+        repo: filter.repo,
+        finding: filter.finding,
+    };
+    let stored = ScanHistory::load(&ScanHistory::default_path()?)?;
+    match (format, timelines) {
+        (OutputFormat::Diagnostics, _) => return Err(diagnostics_unsupported("history")),
+        (OutputFormat::Json, false) => {
+            println!("{}", serde_json::to_string_pretty(&stored.list(&query))?)
+        }
+        (OutputFormat::Json, true) => println!(
+            "{}",
+            serde_json::to_string_pretty(&history::timelines(&stored, &query))?
+        ),
+        (OutputFormat::Text, false) => history::display_runs(&stored.list(&query), &query),
+        (OutputFormat::Text, true) => {
+            history::display_timelines(&history::timelines(&stored, &query), display::unix_now())
+        }
+    }
+    Ok(())
+}
+
+/// Executes the `bench` subcommands and records their runs
+///
+/// # Errors
+///
+/// Returns an error if an endpoint or directory is invalid, the network is
+/// disabled, the workload fails, or the benchmark history cannot be read or
+/// written.
+fn run_bench(
+    action: BenchCommands,
+    config: &devhealth::config::Config,
+    network: &mut NetworkGate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use devhealth::bench::{fs, net, BenchHistory};
+
+    match action {
+        BenchCommands::Net {
+            endpoints,
+            rounds,
+            label,
+            history,
+            no_save,
+            format,
+        } => {
+            if format == OutputFormat::Diagnostics {
+                return Err(diagnostics_unsupported("bench"));
+            }
+            let path = BenchHistory::default_path()?;
+            let mut store = BenchHistory::load(&path)?;
+
+            if history {
+                match format {
+                    OutputFormat::Json => println!(
+                        "{}",
+                        serde_json::to_string_pretty(&net::summarize(&store.network))?
+                    ),
+                    _ => net::display_history(&store.network),
+                }
+                return Ok(());
+            }
+
+            if !network.allow(net::NET_BENCH_CHECK) {
+                return Err(UsageError("bench net needs network access".to_string()).into());
+            }
+            let endpoints = if endpoints.is_empty() {
+                net::endpoints(&config.forge.gitlab_hosts, &config.bench.endpoints)?
+            } else {
+                endpoints
+                    .iter()
+                    .map(|endpoint| endpoint.parse())
+                    .collect::<Result<Vec<_>, _>>()?
+            };
+            let label = label
+                .map(|label| label.trim().to_string())
+                .filter(|label| !label.is_empty());
+
+            warn_power(format);
+            progress(
+                format,
+                &format!(
+                    "⏱️  {}",
+                    tr!(
+                        "bench-net-running",
+                        count = endpoints.len(),
+                        rounds = rounds
+                    )
+                ),
+            );
+            let run = net::run(&endpoints, rounds as usize, label);
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&run)?),
+                _ => net::display_run(&run, &store.network),
+            }
+
+            if !no_save {
+                store.add_network(run);
+                store.save(&path)?;
+                progress(
+                    format,
+                    &format!(
+                        "\n💾 {}",
+                        tr!("bench-saved", path = path.display().to_string())
+                    ),
+                );
+            }
+        }
+        BenchCommands::Fs {
+            path: dir,
+            files,
+            label,
+            history,
+            no_save,
+            format,
+        } => {
+            if format == OutputFormat::Diagnostics {
+                return Err(diagnostics_unsupported("bench"));
+            }
+            let path = BenchHistory::default_path()?;
+            let mut store = BenchHistory::load(&path)?;
+
+            if history {
+                match format {
+                    OutputFormat::Json => println!(
+                        "{}",
+                        serde_json::to_string_pretty(&fs::summarize(&store.filesystem))?
+                    ),
+                    _ => fs::display_history(&store.filesystem),
+                }
+                return Ok(());
+            }
+
+            let dir = devhealth::utils::fs::canonicalize(&wsl::resolve_path(&dir))
+                .ok()
+                .filter(|dir| dir.is_dir())
+                .ok_or_else(|| UsageError(format!("{} is not a directory", dir.display())))?;
+            let label = label
+                .map(|label| label.trim().to_string())
+                .filter(|label| !label.is_empty());
+
+            warn_power(format);
+            progress(
+                format,
+                &format!(
+                    "⏱️  {}",
+                    tr!(
+                        "bench-fs-running",
+                        files = files,
+                        path = dir.display().to_string()
+                    )
+                ),
+            );
+            let run = fs::run(&dir, files as usize, label)?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&run)?),
+                _ => fs::display_run(&run, &store.filesystem),
+            }
+
+            if !no_save {
+                store.add_filesystem(run);
+                store.save(&path)?;
+                progress(
+                    format,
+                    &format!(
+                        "\n💾 {}",
+                        tr!("bench-saved", path = path.display().to_string())
+                    ),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Warns that a benchmark on battery or a throttled CPU is not comparable
+fn warn_power(format: OutputFormat) {
+    if scanner::power::check_power().is_some_and(|power| power.slows_builds()) {
+        progress(format, &format!("🔋 {}", i18n::t("bench-power-warning")));
+    }
+}
+
+/// Annotates the report with the checks skipped because of `--offline`
+///
+/// Prints nothing when the run was online.
+fn report_offline_mode(network: &NetworkGate) {
+    if !network.is_offline() {
+        return;
+    }
 
-            Ok(())
+    say(&format!("\n📴 {}", tr!("offline-header")));
+    if network.skipped().is_empty() {
+        say(&format!("  • {}", tr!("offline-nothing-skipped")));
+    } else {
+        for check in network.skipped() {
+            say(&format!("  • {}", tr!("offline-skipped", check = check)));
         }
     }
 }
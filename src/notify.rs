@@ -0,0 +1,238 @@
+//! Desktop notifications about new findings
+//!
+//! With `--notify-desktop`, or `desktop = true` under `[notify]` for
+//! scheduled runs, `check` and `scan` raise a native desktop notification
+//! when they find issues at `level` (critical by default) that were not
+//! there at the previous run over the same paths and scanners, such as
+//! "Critical: Vulnerable dependency in 3 project(s)".
+//!
+//! The findings each run saw at the level are kept in
+//! `notified-findings.json` inside the DevHealth data directory, whether or
+//! not the scan history is kept, so an issue left unfixed is announced once
+//! and again only if it returns after being fixed. Notifications go through
+//! the same system tools as `watch-advisories` alerts (see
+//! [`watch::notify`](crate::watch::notify)).
+
+use crate::findings::{Check, Finding, Severity};
+use crate::history::{RecordedFinding, ScanHistory};
+use crate::report::ScanResults;
+use crate::tr;
+use crate::utils::paths;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the findings already notified about
+pub const NOTIFY_FILE: &str = "notified-findings.json";
+
+/// Errors that can occur while reading or writing the notification state
+#[derive(Error, Debug)]
+pub enum NotifyError {
+    #[error("Failed to access the notification state: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse the notification state: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error("Could not determine the DevHealth data directory")]
+    NoStateDirectory,
+}
+
+/// Findings seen by the latest run over each combination of paths and
+/// scanners, keyed like the scan history
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifyState {
+    /// Findings at the notification level per run key
+    pub seen: BTreeMap<String, Vec<RecordedFinding>>,
+}
+
+impl NotifyState {
+    /// Returns the location of the notification state inside the data
+    /// directory
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotifyError::NoStateDirectory`] if no data directory can be
+    /// determined.
+    pub fn default_path() -> Result<PathBuf, NotifyError> {
+        paths::data_dir()
+            .map(|dir| dir.join(NOTIFY_FILE))
+            .ok_or(NotifyError::NoStateDirectory)
+    }
+
+    /// Loads the state from `path`, returning an empty state if missing
+    pub fn load(path: &Path) -> Result<NotifyState, NotifyError> {
+        if !path.exists() {
+            return Ok(NotifyState::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the state to `path`, creating parent directories if needed
+    pub fn save(&self, path: &Path) -> Result<(), NotifyError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Records the findings of `results` at `level` or above and returns
+    /// those the previous run with the same paths and scanners did not have
+    ///
+    /// Every finding is new on the first run. Results without
+    /// [`RunMetadata`](crate::report::RunMetadata) are not recorded, so all
+    /// their findings at the level are new.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::findings::Severity;
+    /// use devhealth::notify::NotifyState;
+    /// use devhealth::report::{RunMetadata, ScanResults};
+    ///
+    /// let mut state = NotifyState::default();
+    /// let results = ScanResults {
+    ///     run: Some(RunMetadata::capture(&[".".into()], &["git"])),
+    ///     ..Default::default()
+    /// };
+    /// assert!(state.update(&results, Severity::Critical).is_empty());
+    /// assert_eq!(state.seen.len(), 1);
+    /// ```
+    pub fn update(&mut self, results: &ScanResults, level: Severity) -> Vec<Finding> {
+        let current: Vec<&Finding> = results
+            .findings
+            .iter()
+            .filter(|finding| finding.severity >= level)
+            .collect();
+        let Some(run) = &results.run else {
+            return current.into_iter().cloned().collect();
+        };
+        let seen = self.seen.entry(ScanHistory::key(run)).or_default();
+        let new = current
+            .iter()
+            .filter(|finding| {
+                !seen.iter().any(|earlier| {
+                    earlier.rule == finding.rule
+                        && earlier.path == finding.path
+                        && earlier.detail == finding.detail
+                })
+            })
+            .map(|finding| (*finding).clone())
+            .collect();
+        *seen = current.into_iter().map(RecordedFinding::from).collect();
+        new
+    }
+}
+
+/// Kinds of findings listed in the body before the rest are only counted
+const MAX_LINES: usize = 5;
+
+/// Returns the title and body of the notification about `findings`
+///
+/// The body has one line per severity and check, most severe first, with
+/// the number of repositories or projects affected.
+pub fn notification(findings: &[Finding]) -> (String, String) {
+    let mut groups: BTreeMap<(Severity, Check), BTreeSet<&Path>> = BTreeMap::new();
+    for finding in findings {
+        groups
+            .entry((finding.severity, finding.check))
+            .or_default()
+            .insert(&finding.path);
+    }
+    let mut lines: Vec<String> = groups
+        .iter()
+        .rev()
+        .take(MAX_LINES)
+        .map(|((severity, check), paths)| {
+            tr!(
+                "notify-line",
+                severity = severity.label(),
+                check = check.describe(),
+                count = paths.len()
+            )
+        })
+        .collect();
+    if groups.len() > MAX_LINES {
+        lines.push(tr!("notify-more", count = groups.len() - MAX_LINES));
+    }
+    (
+        tr!("notify-title", count = findings.len()),
+        lines.join("\n"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::RunMetadata;
+
+    fn finding(check: Check, severity: Severity, path: &str) -> Finding {
+        Finding {
+            rule: check.rule_id().to_string(),
+            check,
+            severity,
+            path: PathBuf::from(path),
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn announces_findings_once_until_they_return() {
+        let mut state = NotifyState::default();
+        let mut results = ScanResults {
+            run: Some(RunMetadata::capture(&[PathBuf::from("/src")], &["git"])),
+            findings: vec![
+                finding(Check::EmbeddedCredentials, Severity::Critical, "/src/api"),
+                finding(Check::UncommittedChanges, Severity::Warn, "/src/api"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(state.update(&results, Severity::Critical).len(), 1);
+        assert!(state.update(&results, Severity::Critical).is_empty());
+
+        results.findings.push(finding(
+            Check::EmbeddedCredentials,
+            Severity::Critical,
+            "/src/cli",
+        ));
+        let new = state.update(&results, Severity::Critical);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].path, PathBuf::from("/src/cli"));
+
+        let fixed = results.findings.remove(0);
+        state.update(&results, Severity::Critical);
+        results.findings.push(fixed);
+        assert_eq!(state.update(&results, Severity::Critical).len(), 1);
+    }
+
+    #[test]
+    fn state_round_trips_through_disk() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state").join(NOTIFY_FILE);
+        assert_eq!(NotifyState::load(&path).unwrap(), NotifyState::default());
+
+        let mut state = NotifyState::default();
+        state.seen.insert("/src [git]".to_string(), Vec::new());
+        state.save(&path).unwrap();
+        assert_eq!(NotifyState::load(&path).unwrap(), state);
+    }
+
+    #[test]
+    fn counts_projects_per_check_most_severe_first() {
+        let findings = [
+            finding(Check::UncommittedChanges, Severity::Error, "/src/web"),
+            finding(Check::EmbeddedCredentials, Severity::Critical, "/src/api"),
+            finding(Check::EmbeddedCredentials, Severity::Critical, "/src/cli"),
+            finding(Check::EmbeddedCredentials, Severity::Critical, "/src/cli"),
+        ];
+        let (title, body) = notification(&findings);
+
+        assert!(title.contains('4'), "{}", title);
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(Check::EmbeddedCredentials.describe()));
+        assert!(lines[0].contains('2'), "{}", lines[0]);
+        assert!(lines[1].contains(Check::UncommittedChanges.describe()));
+    }
+}
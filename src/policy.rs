@@ -0,0 +1,497 @@
+//! Organization policy pulled from a central HTTPS endpoint
+//!
+//! A platform team can publish a policy document and its signature, and point
+//! every developer's `devhealth.toml` at it:
+//!
+//! ```toml
+//! [policy]
+//! url = "https://platform.example.com/devhealth/policy.toml"
+//! public_key = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAI... platform-team"
+//! ```
+//!
+//! The policy itself is a small TOML document of required checks and
+//! thresholds:
+//!
+//! ```toml
+//! # Scanners `scan` always runs, in addition to the selected ones
+//! required_scanners = ["git", "audit"]
+//!
+//! # Findings that always fail `check` and `scan` (exit status 1)
+//! fail_on = ["vulnerable"]
+//!
+//! # Limits for `scan --deep`, replacing the local `[deep]` section
+//! [deep]
+//! timeout_secs = 600
+//! tests = true
+//! ```
+//!
+//! Policies are signed with an SSH key, the same way git signs commits:
+//!
+//! ```bash
+//! ssh-keygen -Y sign -f platform_key -n devhealth-policy policy.toml
+//! ```
+//!
+//! which writes `policy.toml.sig` next to the policy. DevHealth downloads
+//! both, verifies the signature with `ssh-keygen -Y verify` against the
+//! configured public key (see [`crate::utils::signature`]), and only then
+//! replaces its cached copy. Downloads are conditional on the previous
+//! `ETag`, so an unchanged policy costs one `304 Not Modified` response. The cached policy is refreshed when it is
+//! older than `refresh_hours` and keeps applying offline.
+
+use crate::config::{DeepConfig, PolicyConfig, Scanner};
+use crate::report::Attention;
+use crate::tr;
+use crate::utils::net::NetworkGate;
+use crate::utils::paths;
+use crate::utils::redact;
+use crate::utils::signature::{self, SignatureError};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+
+/// Namespace policy signatures are made for (`ssh-keygen -Y sign -n`)
+pub const SIGNATURE_NAMESPACE: &str = "devhealth-policy";
+
+/// Name of the policy sync in the list of checks skipped offline
+pub const POLICY_CHECK: &str = "organization policy sync";
+
+/// Name of the policy directory inside the data directory
+const POLICY_DIR: &str = "policy";
+
+/// File names of the cached policy, its signature and its `ETag`
+const POLICY_FILE: &str = "policy.toml";
+const SIGNATURE_FILE: &str = "policy.toml.sig";
+const ETAG_FILE: &str = "policy.etag";
+
+/// Errors that can occur while syncing or loading the policy
+#[derive(Error, Debug)]
+pub enum PolicyError {
+    #[error("Failed to download policy: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Failed to access policy cache: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse policy: {0}")]
+    TomlParse(#[from] toml::de::Error),
+    #[error("[policy] needs a public_key to verify the policy signature")]
+    NoPublicKey,
+    #[error("Policy URL must use HTTPS: {0}")]
+    InsecureUrl(String),
+    #[error("Policy signature is not valid: {0}")]
+    BadSignature(String),
+    #[error("Cannot verify the policy signature: ssh-keygen is not available ({0})")]
+    VerifierUnavailable(std::io::Error),
+    #[error("No policy has been synced yet; run `devhealth policy sync`")]
+    NotSynced,
+    #[error("Could not determine the DevHealth data directory")]
+    NoStateDirectory,
+}
+
+/// Required checks and thresholds rolled out by an organization
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    /// Scanners `scan` always runs, in addition to the selected ones
+    pub required_scanners: Vec<Scanner>,
+    /// Findings that always fail `check` and `scan`
+    pub fail_on: Vec<Attention>,
+    /// Limits for `scan --deep`, replacing the local `[deep]` section
+    pub deep: Option<DeepConfig>,
+}
+
+impl Policy {
+    /// Adds the policy's required scanners to a selection, keeping its order
+    pub fn require_scanners(&self, scanners: &mut Vec<Scanner>) {
+        for scanner in &self.required_scanners {
+            if !scanners.contains(scanner) {
+                scanners.push(*scanner);
+            }
+        }
+    }
+
+    /// Adds the policy's `fail_on` kinds to those given on the command line
+    pub fn require_fail_on(&self, fail_on: &mut Vec<Attention>) {
+        for kind in &self.fail_on {
+            if !fail_on.contains(kind) {
+                fail_on.push(*kind);
+            }
+        }
+    }
+}
+
+/// What [`sync`] did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// A new, verified policy replaced the cached one
+    Updated,
+    /// The server reported the cached policy as current
+    NotModified,
+}
+
+/// The locally cached policy
+#[derive(Debug, Clone)]
+pub struct PolicyCache {
+    dir: PathBuf,
+}
+
+impl PolicyCache {
+    /// Creates a cache in an explicit directory
+    pub fn new(dir: &Path) -> PolicyCache {
+        PolicyCache {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    /// Returns the cache in the DevHealth data directory
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PolicyError::NoStateDirectory`] if no home directory is known.
+    pub fn default_location() -> Result<PolicyCache, PolicyError> {
+        paths::data_dir()
+            .map(|dir| PolicyCache::new(&dir.join(POLICY_DIR)))
+            .ok_or(PolicyError::NoStateDirectory)
+    }
+
+    /// Loads the cached policy, or `None` if none has been synced
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cached policy cannot be read or parsed.
+    pub fn load(&self) -> Result<Option<Policy>, PolicyError> {
+        let path = self.dir.join(POLICY_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(toml::from_str(&fs::read_to_string(path)?)?))
+    }
+
+    /// Returns when the cached policy was last confirmed current
+    pub fn last_synced(&self) -> Option<SystemTime> {
+        fs::metadata(self.dir.join(POLICY_FILE))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    /// Returns `true` if the cached policy is missing or older than `max_age`
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.last_synced()
+            .and_then(|synced| synced.elapsed().ok())
+            .is_none_or(|age| age >= max_age)
+    }
+
+    fn etag(&self) -> Option<String> {
+        fs::read_to_string(self.dir.join(ETAG_FILE))
+            .ok()
+            .map(|etag| etag.trim().to_string())
+            .filter(|etag| !etag.is_empty())
+    }
+
+    /// Marks the cached policy as current without changing it
+    fn touch(&self) -> Result<(), PolicyError> {
+        fs::File::options()
+            .append(true)
+            .open(self.dir.join(POLICY_FILE))?
+            .set_modified(SystemTime::now())?;
+        Ok(())
+    }
+
+    fn store(
+        &self,
+        policy: &[u8],
+        signature: &[u8],
+        etag: Option<&str>,
+    ) -> Result<(), PolicyError> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(SIGNATURE_FILE), signature)?;
+        match etag {
+            Some(etag) => fs::write(self.dir.join(ETAG_FILE), etag)?,
+            None => {
+                let _ = fs::remove_file(self.dir.join(ETAG_FILE));
+            }
+        }
+        // Written last, so a policy never sits next to another one's signature
+        fs::write(self.dir.join(POLICY_FILE), policy)?;
+        Ok(())
+    }
+}
+
+/// Downloads, verifies and caches the organization policy
+///
+/// Sends the cached `ETag` so an unchanged policy is not downloaded again.
+/// A changed policy only replaces the cached one after its signature has been
+/// verified and it has been parsed successfully.
+///
+/// # Arguments
+///
+/// * `config` - The `[policy]` section naming the endpoint and public key
+/// * `cache` - Where the verified policy is kept
+///
+/// # Errors
+///
+/// Returns an error if no URL or public key is configured, the URL is not
+/// HTTPS, a download fails, or the signature or policy is invalid.
+pub fn sync(config: &PolicyConfig, cache: &PolicyCache) -> Result<SyncOutcome, PolicyError> {
+    let Some(url) = config.url.as_deref() else {
+        return Err(PolicyError::NotSynced);
+    };
+    let public_key = config
+        .public_key
+        .as_deref()
+        .filter(|key| !key.trim().is_empty())
+        .ok_or(PolicyError::NoPublicKey)?;
+    let signature_url = config
+        .signature_url
+        .clone()
+        .unwrap_or_else(|| format!("{}.sig", url));
+    for url in [url, signature_url.as_str()] {
+        if !is_secure_url(url) {
+            return Err(PolicyError::InsecureUrl(url.to_string()));
+        }
+    }
+
+    let http = reqwest::blocking::Client::builder()
+        .user_agent(concat!("devhealth/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(15))
+        .build()?;
+
+    let mut request = http.get(url);
+    if let Some(etag) = cache.etag().filter(|_| cache.last_synced().is_some()) {
+        request = request.header("If-None-Match", etag);
+    }
+    let response = request.send()?;
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        cache.touch()?;
+        return Ok(SyncOutcome::NotModified);
+    }
+    let response = response.error_for_status()?;
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let policy = response.bytes()?;
+    let signature = http
+        .get(&signature_url)
+        .send()?
+        .error_for_status()?
+        .bytes()?;
+
+    verify_signature(&policy, &signature, public_key, &cache.dir)?;
+    toml::from_str::<Policy>(&String::from_utf8_lossy(&policy))?;
+    cache.store(&policy, &signature, etag.as_deref())?;
+    Ok(SyncOutcome::Updated)
+}
+
+/// Returns the policy that applies to this run, refreshing it when stale
+///
+/// Returns `None` when no policy URL is configured. A stale cache is
+/// refreshed if the network may be used; when the refresh fails, the cached
+/// policy keeps applying and a warning is printed.
+///
+/// # Errors
+///
+/// Returns an error if a policy is configured but none could be synced or
+/// loaded.
+pub fn load(
+    config: &PolicyConfig,
+    network: &mut NetworkGate,
+) -> Result<Option<Policy>, PolicyError> {
+    if config.url.is_none() {
+        return Ok(None);
+    }
+    let cache = PolicyCache::default_location()?;
+
+    let max_age = Duration::from_secs(config.refresh_hours.saturating_mul(3600));
+    if cache.is_stale(max_age) && network.allow(POLICY_CHECK) {
+        if let Err(e) = sync(config, &cache) {
+            if cache.last_synced().is_none() {
+                return Err(e);
+            }
+            eprintln!(
+                "⚠️  {}",
+                tr!(
+                    "policy-refresh-failed",
+                    error = redact::text(&e.to_string())
+                )
+            );
+        }
+    }
+    cache.load()?.map(Some).ok_or(PolicyError::NotSynced)
+}
+
+/// Returns `true` for HTTPS URLs and for plain HTTP on the loopback interface
+fn is_secure_url(url: &str) -> bool {
+    if url.starts_with("https://") {
+        return true;
+    }
+    let Some(rest) = url.strip_prefix("http://") else {
+        return false;
+    };
+    let host = rest.split(['/', '?']).next().unwrap_or_default();
+    let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+}
+
+/// Verifies an SSH signature of `data` made by `public_key` for policies
+fn verify_signature(
+    data: &[u8],
+    signature: &[u8],
+    public_key: &str,
+    work_dir: &Path,
+) -> Result<(), PolicyError> {
+    signature::verify(data, signature, public_key, SIGNATURE_NAMESPACE, work_dir).map_err(|e| {
+        match e {
+            SignatureError::Io(e) => PolicyError::Io(e),
+            SignatureError::BadSignature(message) => PolicyError::BadSignature(message),
+            SignatureError::VerifierUnavailable(e) => PolicyError::VerifierUnavailable(e),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    /// Generates a key pair and signs `data` with it, returning the public key
+    /// and the signature
+    fn sign(dir: &Path, data: &[u8]) -> (String, Vec<u8>) {
+        let key = dir.join("key");
+        if !key.exists() {
+            let status = Command::new("ssh-keygen")
+                .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+                .arg(&key)
+                .status()
+                .expect("ssh-keygen is needed for policy tests");
+            assert!(status.success());
+        }
+        let file = dir.join("policy.toml");
+        fs::write(&file, data).unwrap();
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-Y", "sign", "-n", SIGNATURE_NAMESPACE, "-f"])
+            .arg(&key)
+            .arg(&file)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        (
+            fs::read_to_string(dir.join("key.pub")).unwrap(),
+            fs::read(dir.join("policy.toml.sig")).unwrap(),
+        )
+    }
+
+    mod signatures {
+        use super::*;
+
+        #[test]
+        fn accepts_policies_signed_by_the_configured_key() {
+            let temp_dir = TempDir::new().unwrap();
+            let data = b"fail_on = [\"vulnerable\"]\n";
+            let (public_key, signature) = sign(temp_dir.path(), data);
+
+            let work_dir = temp_dir.path().join("work");
+            verify_signature(data, &signature, &public_key, &work_dir).unwrap();
+            assert_eq!(
+                fs::read_dir(&work_dir).unwrap().count(),
+                0,
+                "temporary files are removed"
+            );
+        }
+
+        #[test]
+        fn rejects_tampered_policies() {
+            let temp_dir = TempDir::new().unwrap();
+            let (public_key, signature) = sign(temp_dir.path(), b"fail_on = [\"vulnerable\"]\n");
+
+            let result =
+                verify_signature(b"fail_on = []\n", &signature, &public_key, temp_dir.path());
+            assert!(matches!(result, Err(PolicyError::BadSignature(_))));
+        }
+
+        #[test]
+        fn rejects_signatures_by_other_keys() {
+            let signer = TempDir::new().unwrap();
+            let other = TempDir::new().unwrap();
+            let data = b"required_scanners = [\"audit\"]\n";
+            let (_, signature) = sign(signer.path(), data);
+            let (other_key, _) = sign(other.path(), data);
+
+            let result = verify_signature(data, &signature, &other_key, signer.path());
+            assert!(matches!(result, Err(PolicyError::BadSignature(_))));
+        }
+    }
+
+    mod policies {
+        use super::*;
+
+        #[test]
+        fn parses_required_checks_and_thresholds() {
+            let policy: Policy = toml::from_str(
+                "required_scanners = [\"git\", \"audit\"]\nfail_on = [\"vulnerable\"]\n\n[deep]\ntimeout_secs = 600\n",
+            )
+            .unwrap();
+
+            assert_eq!(policy.required_scanners, vec![Scanner::Git, Scanner::Audit]);
+            assert_eq!(policy.fail_on, vec![Attention::Vulnerable]);
+            assert_eq!(policy.deep.map(|deep| deep.timeout_secs), Some(600));
+        }
+
+        #[test]
+        fn rejects_unknown_keys() {
+            assert!(toml::from_str::<Policy>("fail_on_everything = true\n").is_err());
+        }
+
+        #[test]
+        fn adds_requirements_without_duplicates() {
+            let policy = Policy {
+                required_scanners: vec![Scanner::Git, Scanner::Audit],
+                fail_on: vec![Attention::Vulnerable],
+                deep: None,
+            };
+
+            let mut scanners = vec![Scanner::Deps, Scanner::Git];
+            policy.require_scanners(&mut scanners);
+            assert_eq!(scanners, vec![Scanner::Deps, Scanner::Git, Scanner::Audit]);
+
+            let mut fail_on = vec![Attention::Vulnerable];
+            policy.require_fail_on(&mut fail_on);
+            assert_eq!(fail_on, vec![Attention::Vulnerable]);
+        }
+
+        #[test]
+        fn only_allows_https_or_loopback_http() {
+            assert!(is_secure_url("https://platform.example.com/policy.toml"));
+            assert!(is_secure_url("http://127.0.0.1:8080/policy.toml"));
+            assert!(is_secure_url("http://localhost/policy.toml"));
+            assert!(!is_secure_url("http://platform.example.com/policy.toml"));
+            assert!(!is_secure_url("http://localhost.example.com/policy.toml"));
+            assert!(!is_secure_url("ftp://platform.example.com/policy.toml"));
+        }
+    }
+
+    mod cache {
+        use super::*;
+
+        #[test]
+        fn stores_policies_and_tracks_staleness() {
+            let temp_dir = TempDir::new().unwrap();
+            let cache = PolicyCache::new(&temp_dir.path().join("policy"));
+            assert!(cache.load().unwrap().is_none());
+            assert!(cache.is_stale(Duration::from_secs(3600)));
+
+            cache
+                .store(b"fail_on = [\"errors\"]\n", b"sig", Some("\"v1\""))
+                .unwrap();
+            assert_eq!(
+                cache.load().unwrap().unwrap().fail_on,
+                vec![Attention::Errors]
+            );
+            assert_eq!(cache.etag().as_deref(), Some("\"v1\""));
+            assert!(!cache.is_stale(Duration::from_secs(3600)));
+            assert!(cache.is_stale(Duration::ZERO));
+        }
+    }
+}
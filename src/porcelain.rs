@@ -0,0 +1,169 @@
+//! Porcelain output for scripts and shell prompts
+//!
+//! `--porcelain` prints one line per repository and one per finding, with
+//! tab-separated fields and nothing else: no colors, emoji, headers or
+//! translations. Like `git status --porcelain`, the format is stable: fields
+//! keep their order and meaning between minor versions, and new kinds of
+//! records may only be added as lines with a new first field, which scripts
+//! should skip. Fields are separated by single tabs:
+//!
+//! ```text
+//! repo    <status>    <score> <flags> <branch> <path>
+//! finding <severity>  <rule>  <check> <path>   <detail>
+//! ```
+//!
+//! - `status` is `clean`, `dirty` or `error`; `score` is the health score
+//!   from 0 to 100.
+//! - `flags` is a comma-separated list of `unpushed`, `ci-failed`,
+//!   `behind` and `archived`, or `-` if there are none.
+//! - `severity` is `info`, `warn`, `error` or `critical`; `rule` and `check`
+//!   are the rule ID and name listed by `devhealth rules`.
+//! - `detail` names what is affected, such as a package, or is `-`.
+//!
+//! Repositories come first, in report order, followed by the findings, most
+//! severe first. Tabs and line breaks inside values are replaced by spaces.
+
+use crate::findings::Finding;
+use crate::report::ScanResults;
+use crate::scanner::forge::CiStatus;
+use crate::scanner::git::{GitRepo, GitStatus};
+
+/// Returns the porcelain lines for `results`, repositories first
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::porcelain;
+/// use devhealth::report::ScanResults;
+///
+/// assert!(porcelain::lines(&ScanResults::default()).is_empty());
+/// ```
+pub fn lines(results: &ScanResults) -> Vec<String> {
+    let repos = results.git.iter().flatten().map(repo_line);
+    let findings = results.findings.iter().map(finding_line);
+    repos.chain(findings).collect()
+}
+
+/// Prints the porcelain lines for `results`
+pub fn display(results: &ScanResults) {
+    for line in lines(results) {
+        println!("{}", line);
+    }
+}
+
+/// Formats the `repo` record of a repository
+fn repo_line(repo: &GitRepo) -> String {
+    let status = match repo.status {
+        GitStatus::Clean => "clean",
+        GitStatus::Dirty => "dirty",
+        GitStatus::Error(_) => "error",
+    };
+    let flags: Vec<&str> = [
+        (repo.unpushed_commits, "unpushed"),
+        (repo.ci == Some(CiStatus::Failed), "ci-failed"),
+        (
+            repo.fork.as_ref().is_some_and(|fork| fork.behind > 0),
+            "behind",
+        ),
+        (repo.archived, "archived"),
+    ]
+    .into_iter()
+    .filter_map(|(set, flag)| set.then_some(flag))
+    .collect();
+    record(&[
+        "repo",
+        status,
+        &repo.health_score().to_string(),
+        &or_dash(&flags.join(",")),
+        &repo.branch,
+        &repo.path.to_string_lossy(),
+    ])
+}
+
+/// Formats the `finding` record of a finding
+fn finding_line(finding: &Finding) -> String {
+    record(&[
+        "finding",
+        finding.severity.name(),
+        &finding.rule,
+        finding.check.name(),
+        &finding.path.to_string_lossy(),
+        &or_dash(finding.detail.as_deref().unwrap_or_default()),
+    ])
+}
+
+/// Returns `value`, or `-` if it is empty
+fn or_dash(value: &str) -> String {
+    if value.is_empty() {
+        "-".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Joins fields with tabs, replacing tabs and line breaks inside them
+fn record(fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|field| field.replace(['\t', '\n', '\r'], " "))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::findings::{Check, Severity};
+    use std::path::PathBuf;
+
+    fn repo(name: &str, status: GitStatus, unpushed: bool) -> GitRepo {
+        GitRepo {
+            uncommitted_changes: matches!(status, GitStatus::Dirty),
+            status,
+            unpushed_commits: unpushed,
+            ..GitRepo::test(name)
+        }
+    }
+
+    #[test]
+    fn prints_repositories_then_findings() {
+        let mut dirty = repo("my api", GitStatus::Dirty, true);
+        dirty.ci = Some(CiStatus::Failed);
+        let results = ScanResults {
+            git: Some(vec![dirty, repo("cli", GitStatus::Clean, false)]),
+            findings: vec![Finding {
+                rule: Check::UncommittedChanges.rule_id().to_string(),
+                check: Check::UncommittedChanges,
+                severity: Severity::Warn,
+                path: PathBuf::from("/src/my api"),
+                detail: None,
+            }],
+            ..Default::default()
+        };
+
+        let lines = lines(&results);
+        assert_eq!(
+            lines[0],
+            "repo\tdirty\t40\tunpushed,ci-failed\tmain\t/src/my api"
+        );
+        assert_eq!(lines[1], "repo\tclean\t100\t-\tmain\t/src/cli");
+        assert_eq!(
+            lines[2],
+            format!(
+                "finding\twarn\t{}\tuncommitted-changes\t/src/my api\t-",
+                Check::UncommittedChanges.rule_id()
+            )
+        );
+    }
+
+    #[test]
+    fn replaces_separators_inside_values() {
+        let mut repo = repo(
+            "odd",
+            GitStatus::Error("bad\tindex\nfile".to_string()),
+            false,
+        );
+        repo.branch = "feature\tx".to_string();
+        assert_eq!(repo_line(&repo), "repo\terror\t0\t-\tfeature x\t/src/odd");
+    }
+}
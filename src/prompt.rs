@@ -0,0 +1,476 @@
+//! Shell prompt status (`devhealth prompt`)
+//!
+//! Prints a short status for the repository containing the current
+//! directory, meant for `PS1` or a starship `custom` module:
+//!
+//! ```text
+//! ✎ ↑2 ↓1 🛡3
+//! ```
+//!
+//! `✎` marks uncommitted changes, `↑`/`↓` count commits ahead of and behind
+//! the upstream branch, and `🛡` counts vulnerable dependencies in the
+//! repository's projects. Nothing is printed outside a repository or when
+//! everything is fine. Accessible output uses `*`, `+2`, `-1` and `!3`.
+//!
+//! Prompts run before every command, so the status comes from
+//! `prompt-cache.json` in the cache directory. A cached status younger than
+//! `--max-age` is printed as is; an older one is printed too, while a
+//! detached `devhealth prompt --refresh` updates it for the next prompt. Only
+//! a repository seen for the first time runs git before printing.
+//!
+//! Vulnerable dependencies are not looked up by the prompt: every
+//! `scan --audit` and `watch-advisories` records the count per project.
+
+use crate::scanner::audit::Vulnerability;
+use crate::scanner::deps::DependencyReport;
+use crate::utils::display;
+use crate::utils::paths;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+/// File name of the prompt cache inside the cache directory
+pub const CACHE_FILE: &str = "prompt-cache.json";
+
+/// Seconds a cached status is printed without refreshing it
+pub const DEFAULT_MAX_AGE: u64 = 10;
+
+/// Errors that can occur while reading or writing the prompt cache
+#[derive(Error, Debug)]
+pub enum PromptError {
+    #[error("Failed to access the prompt cache: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse the prompt cache: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error("Could not determine the DevHealth cache directory")]
+    NoCacheDirectory,
+}
+
+/// The git side of a repository's prompt status
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitState {
+    /// Whether the working tree has uncommitted changes
+    pub dirty: bool,
+    /// Commits on `HEAD` missing from the upstream branch
+    pub ahead: usize,
+    /// Commits on the upstream branch missing from `HEAD`
+    pub behind: usize,
+}
+
+impl GitState {
+    /// Reads the state of the repository at `root` with git
+    ///
+    /// A repository without an upstream branch is neither ahead nor behind.
+    pub fn read(root: &Path) -> GitState {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(root)
+                .stderr(Stdio::null())
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        };
+        let dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.trim().is_empty());
+        let counts =
+            git(&["rev-list", "--left-right", "--count", "@{upstream}...HEAD"]).unwrap_or_default();
+        let mut counts = counts
+            .split_whitespace()
+            .map(|count| count.parse().unwrap_or(0));
+        let behind = counts.next().unwrap_or(0);
+        let ahead = counts.next().unwrap_or(0);
+        GitState {
+            dirty,
+            ahead,
+            behind,
+        }
+    }
+}
+
+/// A repository's git state and when it was read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedState {
+    /// When the state was read, as a Unix timestamp
+    pub checked_at: u64,
+    /// The state then
+    pub state: GitState,
+}
+
+/// Cached prompt statuses and vulnerability counts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptCache {
+    /// Git state by repository root
+    #[serde(default)]
+    pub repos: BTreeMap<PathBuf, CachedState>,
+    /// Vulnerable dependencies by project directory, from the latest audit
+    /// covering the project; projects without any are left out
+    #[serde(default)]
+    pub vulnerable: BTreeMap<PathBuf, usize>,
+}
+
+impl PromptCache {
+    /// Returns the location of the prompt cache inside the cache directory
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PromptError::NoCacheDirectory`] if no cache directory can
+    /// be determined.
+    pub fn default_path() -> Result<PathBuf, PromptError> {
+        paths::cache_dir()
+            .map(|dir| dir.join(CACHE_FILE))
+            .ok_or(PromptError::NoCacheDirectory)
+    }
+
+    /// Loads the cache from `path`, returning an empty cache if missing
+    pub fn load(path: &Path) -> Result<PromptCache, PromptError> {
+        if !path.exists() {
+            return Ok(PromptCache::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the cache to `path`, replacing it in one step so a prompt
+    /// reading it concurrently never sees half a file
+    pub fn save(&self, path: &Path) -> Result<(), PromptError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let partial = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&partial, serde_json::to_string(self)?)?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    /// Records the vulnerabilities an audit of `reports` found
+    ///
+    /// Each audited project's count is replaced, so fixed projects drop out;
+    /// projects the audit did not cover are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::prompt::PromptCache;
+    /// use devhealth::scanner::deps::DependencyReport;
+    /// use std::path::PathBuf;
+    ///
+    /// let report = DependencyReport {
+    ///     project_path: PathBuf::from("/src/api"),
+    ///     dependencies: Vec::new(),
+    ///     ecosystems: Vec::new(),
+    ///     errors: Vec::new(),
+    ///     lockfiles: Vec::new(),
+    ///     go_modules: Vec::new(),
+    /// };
+    /// let mut cache = PromptCache::default();
+    /// cache.vulnerable.insert(PathBuf::from("/src/api"), 2);
+    /// cache.record_audit(&[report], &[]);
+    /// assert!(cache.vulnerable.is_empty());
+    /// ```
+    pub fn record_audit(
+        &mut self,
+        reports: &[DependencyReport],
+        vulnerabilities: &[Vulnerability],
+    ) {
+        for report in reports {
+            let project = absolute(&report.project_path);
+            let count = vulnerabilities
+                .iter()
+                .filter(|vuln| {
+                    vuln.dependency.source_file.parent().map(absolute).as_ref() == Some(&project)
+                })
+                .count();
+            if count == 0 {
+                self.vulnerable.remove(&project);
+            } else {
+                self.vulnerable.insert(project, count);
+            }
+        }
+    }
+
+    /// Returns the number of vulnerable dependencies in projects under `root`
+    pub fn vulnerable_under(&self, root: &Path) -> usize {
+        self.vulnerable
+            .iter()
+            .filter(|(project, _)| project.starts_with(root))
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+/// Records an audit in the prompt cache, ignoring failures
+///
+/// The cache only speeds up prompts, so a scan never fails because of it.
+pub fn record_audit(reports: &[DependencyReport], vulnerabilities: &[Vulnerability]) {
+    let Ok(path) = PromptCache::default_path() else {
+        return;
+    };
+    if let Ok(mut cache) = PromptCache::load(&path) {
+        cache.record_audit(reports, vulnerabilities);
+        let _ = cache.save(&path);
+    }
+}
+
+/// Returns the root of the repository containing `path`, if any
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::prompt;
+///
+/// let dir = tempfile::TempDir::new().unwrap();
+/// std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+/// std::fs::create_dir_all(dir.path().join("src/bin")).unwrap();
+/// assert_eq!(prompt::repository_root(&dir.path().join("src/bin")).as_deref(), Some(dir.path()));
+/// ```
+pub fn repository_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Formats a prompt status, empty if there is nothing to report
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::prompt::{self, GitState};
+///
+/// let state = GitState { dirty: true, ahead: 2, behind: 0 };
+/// assert_eq!(prompt::format_status(state, 3), "✎ ↑2 🛡3");
+/// assert_eq!(prompt::format_status(GitState::default(), 0), "");
+/// ```
+pub fn format_status(state: GitState, vulnerable: usize) -> String {
+    let (dirty, ahead, behind, shield) = if display::is_accessible() {
+        ("*", "+", "-", "!")
+    } else {
+        ("✎", "↑", "↓", "🛡")
+    };
+    let mut parts = Vec::new();
+    if state.dirty {
+        parts.push(dirty.to_string());
+    }
+    if state.ahead > 0 {
+        parts.push(format!("{}{}", ahead, state.ahead));
+    }
+    if state.behind > 0 {
+        parts.push(format!("{}{}", behind, state.behind));
+    }
+    if vulnerable > 0 {
+        parts.push(format!("{}{}", shield, vulnerable));
+    }
+    parts.join(" ")
+}
+
+/// Returns the prompt status for the repository containing `path`
+///
+/// Uses the cached state when there is one, starting a background refresh
+/// if it is older than `max_age` seconds; reads and caches the state right
+/// away otherwise. Returns an empty string outside a repository.
+pub fn status(path: &Path, max_age: u64, now: u64) -> String {
+    let Some(root) = repository_root(&absolute(path)) else {
+        return String::new();
+    };
+    let cache_path = PromptCache::default_path().ok();
+    let mut cache = cache_path
+        .as_deref()
+        .and_then(|path| PromptCache::load(path).ok())
+        .unwrap_or_default();
+
+    let state = match cache.repos.get(&root) {
+        Some(cached) => {
+            if now.saturating_sub(cached.checked_at) > max_age {
+                spawn_refresh(&root);
+            }
+            cached.state
+        }
+        None => {
+            let state = GitState::read(&root);
+            cache.repos.insert(
+                root.clone(),
+                CachedState {
+                    checked_at: now,
+                    state,
+                },
+            );
+            if let Some(path) = &cache_path {
+                let _ = cache.save(path);
+            }
+            state
+        }
+    };
+    format_status(state, cache.vulnerable_under(&root))
+}
+
+/// Reads the git state of the repository containing `path` and caches it
+///
+/// # Errors
+///
+/// Returns an error if the cache cannot be read or written.
+pub fn refresh(path: &Path, now: u64) -> Result<(), PromptError> {
+    let Some(root) = repository_root(&absolute(path)) else {
+        return Ok(());
+    };
+    let state = GitState::read(&root);
+    let cache_path = PromptCache::default_path()?;
+    let mut cache = PromptCache::load(&cache_path)?;
+    cache.repos.insert(
+        root,
+        CachedState {
+            checked_at: now,
+            state,
+        },
+    );
+    cache.save(&cache_path)
+}
+
+/// Starts `devhealth prompt --refresh` for `root` without waiting for it
+fn spawn_refresh(root: &Path) {
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = Command::new(exe)
+            .args(["prompt", "--refresh", "--path"])
+            .arg(root)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+    }
+}
+
+/// Makes `path` absolute without touching the filesystem
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::deps::{Dependency, DependencyType, Ecosystem};
+    use tempfile::TempDir;
+
+    fn report(project: &str) -> DependencyReport {
+        DependencyReport {
+            project_path: PathBuf::from(project),
+            dependencies: Vec::new(),
+            ecosystems: Vec::new(),
+            errors: Vec::new(),
+            lockfiles: Vec::new(),
+            go_modules: Vec::new(),
+        }
+    }
+
+    fn vulnerability(project: &str) -> Vulnerability {
+        Vulnerability {
+            advisory_id: "RUSTSEC-2020-0071".to_string(),
+            summary: String::new(),
+            aliases: Vec::new(),
+            dependency: Dependency {
+                name: "time".to_string(),
+                version: "0.1.45".to_string(),
+                dependency_type: DependencyType::Runtime,
+                ecosystem: Ecosystem::Rust,
+                source_file: PathBuf::from(project).join("Cargo.toml"),
+                extras: Vec::new(),
+                marker: None,
+            },
+            version: "0.1.45".to_string(),
+            fixed_version: None,
+        }
+    }
+
+    #[test]
+    fn audits_replace_counts_of_the_projects_they_cover() {
+        let mut cache = PromptCache::default();
+        cache.record_audit(
+            &[
+                report("/src/api"),
+                report("/src/api/web"),
+                report("/src/cli"),
+            ],
+            &[
+                vulnerability("/src/api"),
+                vulnerability("/src/api/web"),
+                vulnerability("/src/cli"),
+            ],
+        );
+        assert_eq!(cache.vulnerable_under(Path::new("/src/api")), 2);
+        assert_eq!(cache.vulnerable_under(Path::new("/src")), 3);
+
+        cache.record_audit(&[report("/src/cli")], &[]);
+        assert_eq!(cache.vulnerable_under(Path::new("/src/cli")), 0);
+        assert_eq!(
+            cache.vulnerable_under(Path::new("/src/api")),
+            2,
+            "other projects are kept"
+        );
+    }
+
+    #[test]
+    fn cache_round_trips_through_disk() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("cache").join(CACHE_FILE);
+        let mut cache = PromptCache::default();
+        cache.repos.insert(
+            PathBuf::from("/src/api"),
+            CachedState {
+                checked_at: 100,
+                state: GitState {
+                    dirty: true,
+                    ahead: 1,
+                    behind: 0,
+                },
+            },
+        );
+        cache.save(&path).unwrap();
+        assert_eq!(PromptCache::load(&path).unwrap(), cache);
+        assert_eq!(
+            fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1,
+            "no temporary file is left"
+        );
+    }
+
+    #[test]
+    fn formats_every_flag_in_order() {
+        let state = GitState {
+            dirty: true,
+            ahead: 2,
+            behind: 5,
+        };
+        assert_eq!(format_status(state, 1), "✎ ↑2 ↓5 🛡1");
+        assert_eq!(
+            format_status(
+                GitState {
+                    behind: 1,
+                    ..GitState::default()
+                },
+                0
+            ),
+            "↓1"
+        );
+    }
+
+    #[test]
+    fn reads_uncommitted_changes_without_an_upstream() {
+        let dir = TempDir::new().unwrap();
+        Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert_eq!(GitState::read(dir.path()), GitState::default());
+
+        fs::write(dir.path().join("notes.txt"), "wip").unwrap();
+        assert_eq!(
+            GitState::read(dir.path()),
+            GitState {
+                dirty: true,
+                ahead: 0,
+                behind: 0
+            }
+        );
+    }
+}
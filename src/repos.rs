@@ -0,0 +1,385 @@
+//! Forge repositories not cloned locally
+//!
+//! `devhealth repos missing` is the inverse of the local scan: it lists the
+//! repositories the GitHub and GitLab tokens give access to (see
+//! [`forge::list_repositories`]) that no git repository under the given
+//! paths has as a remote, and clones the ones picked with `--clone`.
+//!
+//! Every remote of a local repository counts, not only `origin`, so a fork
+//! cloned with its parent as `upstream` accounts for both. Archived
+//! repositories are left out unless asked for.
+
+use crate::i18n::t;
+use crate::scanner::forge::{self, ForgeRepository};
+use crate::tr;
+use crate::utils::display;
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+/// Errors that can occur while picking or cloning missing repositories
+#[derive(Error, Debug)]
+pub enum ReposError {
+    #[error("{0} is not among the missing repositories (see `devhealth repos missing`)")]
+    NotMissing(String),
+    #[error("Cannot clone into {0}: it already exists")]
+    Exists(PathBuf),
+    #[error("Failed to run git: {0}")]
+    Io(#[from] io::Error),
+    #[error("git clone of {0} failed")]
+    CloneFailed(String),
+}
+
+/// A forge repository named by a remote, as host and lowercase project
+pub type RemoteKey = (String, String);
+
+/// Returns the key a forge repository is matched with local remotes by
+///
+/// Forges treat owner and repository names case-insensitively.
+pub fn key(host: &str, project: &str) -> RemoteKey {
+    (host.to_lowercase(), project.to_lowercase())
+}
+
+/// Collects the forge repositories named by a remote of any git repository
+/// under `paths`
+///
+/// # Arguments
+///
+/// * `paths` - Directories searched for git repositories
+/// * `gitlab_hosts` - Hosts of self-managed GitLab instances
+///
+/// # Errors
+///
+/// Returns an error if a directory cannot be searched.
+pub fn local_remotes(
+    paths: &[PathBuf],
+    gitlab_hosts: &[String],
+) -> Result<BTreeSet<RemoteKey>, Box<dyn std::error::Error>> {
+    let mut remotes = BTreeSet::new();
+    for path in paths {
+        for repo in dh_fs::find_git_repositories(path)? {
+            for url in remote_urls(&repo) {
+                if let Some(remote) = forge::parse_remote(&url, gitlab_hosts) {
+                    remotes.insert(key(&remote.host, &remote.project));
+                }
+            }
+        }
+    }
+    Ok(remotes)
+}
+
+/// Returns the URLs of every remote of a repository
+fn remote_urls(repo_path: &Path) -> Vec<String> {
+    let Ok(output) = Command::new("git")
+        .args(["config", "--get-regexp", r"^remote\..*\.url$"])
+        .current_dir(repo_path)
+        .output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once(' ').map(|(_, url)| url.to_string()))
+        .collect()
+}
+
+/// Keeps the repositories no local remote names, sorted by host and project
+///
+/// # Arguments
+///
+/// * `repositories` - Repositories listed by the forges
+/// * `local` - Keys of the local remotes, from [`local_remotes`]
+/// * `owners` - Owners, organizations or GitLab groups to keep; all when empty
+/// * `archived` - Whether to keep archived repositories
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::repos;
+/// use devhealth::scanner::forge::{Forge, ForgeRepository};
+///
+/// let repository = |project: &str| ForgeRepository {
+///     forge: Forge::GitHub,
+///     host: "github.com".to_string(),
+///     project: project.to_string(),
+///     clone_url: format!("https://github.com/{}.git", project),
+///     ssh_url: format!("git@github.com:{}.git", project),
+///     web_url: format!("https://github.com/{}", project),
+///     archived: false,
+///     fork: false,
+/// };
+/// let local = [repos::key("github.com", "ada/api")].into();
+///
+/// let missing = repos::missing(vec![repository("Ada/API"), repository("ada/web")], &local, &[], false);
+/// assert_eq!(missing.len(), 1);
+/// assert_eq!(missing[0].project, "ada/web");
+/// ```
+pub fn missing(
+    repositories: Vec<ForgeRepository>,
+    local: &BTreeSet<RemoteKey>,
+    owners: &[String],
+    archived: bool,
+) -> Vec<ForgeRepository> {
+    let owned = |project: &str| {
+        owners.is_empty()
+            || owners.iter().any(|owner| {
+                let owner = owner.trim_matches('/').to_lowercase();
+                project.to_lowercase().starts_with(&format!("{}/", owner))
+            })
+    };
+    let mut missing: Vec<ForgeRepository> = repositories
+        .into_iter()
+        .filter(|repository| archived || !repository.archived)
+        .filter(|repository| owned(&repository.project))
+        .filter(|repository| !local.contains(&key(&repository.host, &repository.project)))
+        .collect();
+    missing.sort_by_key(|repository| key(&repository.host, &repository.project));
+    missing.dedup_by(|a, b| key(&a.host, &a.project) == key(&b.host, &b.project));
+    missing
+}
+
+/// Picks the missing repositories named `owner/repo` (case-insensitively)
+///
+/// # Errors
+///
+/// Returns [`ReposError::NotMissing`] for a name that matches none of them.
+pub fn select<'a>(
+    missing: &'a [ForgeRepository],
+    names: &[String],
+) -> Result<Vec<&'a ForgeRepository>, ReposError> {
+    names
+        .iter()
+        .map(|name| {
+            let name = name.trim_matches('/');
+            missing
+                .iter()
+                .find(|repository| repository.project.eq_ignore_ascii_case(name))
+                .ok_or_else(|| ReposError::NotMissing(name.to_string()))
+        })
+        .collect()
+}
+
+/// Clones `repository` into a directory named after it inside `into`
+///
+/// Uses the SSH URL with `ssh`, the HTTPS one otherwise. Git's progress and
+/// credential prompts are shown as they are.
+///
+/// # Errors
+///
+/// Returns [`ReposError::Exists`] if the target directory exists, and an
+/// error if git cannot be run or the clone fails.
+pub fn clone(repository: &ForgeRepository, into: &Path, ssh: bool) -> Result<PathBuf, ReposError> {
+    let name = repository
+        .project
+        .rsplit('/')
+        .next()
+        .unwrap_or(&repository.project);
+    let target = into.join(name);
+    if target.exists() {
+        return Err(ReposError::Exists(target));
+    }
+    let url = if ssh {
+        &repository.ssh_url
+    } else {
+        &repository.clone_url
+    };
+    let status = Command::new("git")
+        .arg("clone")
+        .arg("--")
+        .arg(url)
+        .arg(&target)
+        .status()?;
+    if !status.success() {
+        return Err(ReposError::CloneFailed(repository.project.clone()));
+    }
+    Ok(target)
+}
+
+/// Displays the repositories missing locally out of `total` listed
+pub fn display_results(missing: &[ForgeRepository], total: usize) {
+    if missing.is_empty() {
+        println!(
+            "{}",
+            display::header(
+                &tr!("repos-none-missing", total = total),
+                "✅",
+                Role::Success
+            )
+        );
+        return;
+    }
+    println!(
+        "{}",
+        display::header(
+            &tr!("repos-missing-title", count = missing.len(), total = total),
+            "📡",
+            Role::Special
+        )
+    );
+    for (index, repository) in missing.iter().enumerate() {
+        let mut line = format!(
+            "{}/{}",
+            repository.host.paint(Role::Muted),
+            repository.project.paint(Role::Highlight).bold()
+        );
+        if repository.fork {
+            line.push_str(&format!(" {}", t("repos-fork").paint(Role::Muted)));
+        }
+        if repository.archived {
+            line.push_str(&format!(" {}", t("repos-archived").paint(Role::Warning)));
+        }
+        line.push_str(&format!(" {}", repository.web_url.paint(Role::Muted)));
+        println!(
+            "{}",
+            display::tree_item(&line, index == missing.len() - 1, 0)
+        );
+    }
+    let command = format!("devhealth repos missing --clone {}", missing[0].project);
+    println!(
+        "\n{} {}",
+        display::symbols("💡"),
+        tr!("repos-tip-clone", command = command.paint(Role::Accent))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::forge::Forge;
+    use tempfile::TempDir;
+
+    fn repository(host: &str, project: &str) -> ForgeRepository {
+        ForgeRepository {
+            forge: if host == "github.com" {
+                Forge::GitHub
+            } else {
+                Forge::GitLab
+            },
+            host: host.to_string(),
+            project: project.to_string(),
+            clone_url: format!("https://{}/{}.git", host, project),
+            ssh_url: format!("git@{}:{}.git", host, project),
+            web_url: format!("https://{}/{}", host, project),
+            archived: false,
+            fork: false,
+        }
+    }
+
+    #[test]
+    fn matches_every_remote_of_local_repositories() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let repo = temp_dir.path().join("api-fork");
+        std::fs::create_dir(&repo).unwrap();
+        let git = |args: &[&str]| {
+            assert!(
+                Command::new("git")
+                    .args(args)
+                    .current_dir(&repo)
+                    .status()
+                    .unwrap()
+                    .success(),
+                "git {:?} failed",
+                args
+            );
+        };
+        git(&["init", "-q"]);
+        git(&["remote", "add", "origin", "git@github.com:ada/api.git"]);
+        git(&["remote", "add", "upstream", "https://github.com/Team/API"]);
+        git(&[
+            "remote",
+            "add",
+            "mirror",
+            "https://git.example.com/group/sub/api.git",
+        ]);
+
+        let local = local_remotes(
+            &[temp_dir.path().to_path_buf()],
+            &["git.example.com".to_string()],
+        )
+        .unwrap();
+
+        let expected: BTreeSet<RemoteKey> = [
+            key("github.com", "ada/api"),
+            key("github.com", "team/api"),
+            key("git.example.com", "group/sub/api"),
+        ]
+        .into();
+        assert_eq!(local, expected);
+    }
+
+    #[test]
+    fn filters_by_owner_and_archived_state() {
+        let mut archived = repository("github.com", "ada/old");
+        archived.archived = true;
+        let listed = vec![
+            repository("gitlab.com", "group/sub/tool"),
+            repository("github.com", "ada/web"),
+            archived,
+            repository("github.com", "team/api"),
+            repository("github.com", "ada/web"),
+        ];
+        let local: BTreeSet<RemoteKey> = [key("github.com", "team/api")].into();
+
+        let names = |missing: Vec<ForgeRepository>| -> Vec<String> {
+            missing.into_iter().map(|r| r.project).collect()
+        };
+        assert_eq!(
+            names(missing(listed.clone(), &local, &[], false)),
+            ["ada/web", "group/sub/tool"]
+        );
+        assert_eq!(
+            names(missing(listed.clone(), &local, &["ADA".to_string()], true)),
+            ["ada/old", "ada/web"]
+        );
+        assert_eq!(
+            names(missing(listed, &local, &["group/sub".to_string()], false)),
+            ["group/sub/tool"]
+        );
+    }
+
+    #[test]
+    fn selects_missing_repositories_by_name() {
+        let missing = vec![
+            repository("github.com", "ada/web"),
+            repository("gitlab.com", "group/tool"),
+        ];
+
+        let selected = select(&missing, &["Ada/Web".to_string()]).unwrap();
+        assert_eq!(selected[0].project, "ada/web");
+        assert!(matches!(
+            select(&missing, &["ada/api".to_string()]),
+            Err(ReposError::NotMissing(name)) if name == "ada/api"
+        ));
+    }
+
+    #[test]
+    fn clones_into_a_directory_named_after_the_repository() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let source = temp_dir.path().join("source");
+        std::fs::create_dir(&source).unwrap();
+        assert!(Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(&source)
+            .status()
+            .unwrap()
+            .success());
+        let mut listed = repository("github.com", "ada/web");
+        listed.clone_url = source.to_string_lossy().into_owned();
+        let into = temp_dir.path().join("code");
+        std::fs::create_dir(&into).unwrap();
+
+        let target = clone(&listed, &into, false).unwrap();
+
+        assert_eq!(target, into.join("web"));
+        assert!(target.join(".git").is_dir());
+        assert!(matches!(
+            clone(&listed, &into, false),
+            Err(ReposError::Exists(_))
+        ));
+    }
+}
@@ -0,0 +1,450 @@
+//! Version alignment across projects (`deps align`)
+//!
+//! Finds the packages the scanned projects use at versions far apart, and
+//! suggests one version for all of them to converge on.
+//!
+//! The version a project uses is the one its lockfile locked where there is
+//! one, and the lower bound of its declared constraint otherwise. How far
+//! apart the versions are is their [`Spread`]: a difference in the leftmost
+//! non-zero component is a major spread, the one Cargo and npm treat as
+//! breaking.
+//!
+//! The suggested target is the version in use that the most declared
+//! constraints admit, the highest one on a tie, so converging needs the
+//! fewest manifest changes. Constraints are checked with the rules of their
+//! ecosystem, as in [`crate::scanner::impact`].
+
+use crate::i18n::t;
+use crate::scanner::audit::parse_loose_version;
+use crate::scanner::deps::{DependencyReport, Ecosystem};
+use crate::scanner::exposure::{normalize_package_name, project_name, Resolver};
+use crate::scanner::impact::{self, Verdict};
+use crate::tr;
+use crate::utils::display;
+use crate::utils::theme::{Paint, Role};
+use clap::ValueEnum;
+use colored::*;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+/// How far apart the versions of a package in use are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Spread {
+    /// Only the patch version differs
+    Patch,
+    /// The minor version differs
+    Minor,
+    /// The leftmost non-zero component differs
+    Major,
+}
+
+impl Spread {
+    /// Returns the spread between two versions
+    fn between(a: &Version, b: &Version) -> Spread {
+        let breaking = |version: &Version| match (version.major, version.minor) {
+            (0, 0) => (0, 0, version.patch),
+            (0, minor) => (0, minor, 0),
+            (major, _) => (major, 0, 0),
+        };
+        if breaking(a) != breaking(b) {
+            Spread::Major
+        } else if a.minor != b.minor {
+            Spread::Minor
+        } else {
+            Spread::Patch
+        }
+    }
+}
+
+/// A project, its declared constraint and the version in use
+type Use = (PathBuf, String, String);
+
+/// A project's use of a misaligned package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionUse {
+    /// The project
+    pub project: PathBuf,
+    /// The declared constraint
+    pub constraint: String,
+    /// The version in use
+    pub version: String,
+    /// Whether the constraint admits the suggested target
+    pub admits_target: bool,
+}
+
+/// A package used at versions far apart, and the version to converge on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Misalignment {
+    /// Package name
+    pub name: String,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+    /// How far apart the versions in use are
+    pub spread: Spread,
+    /// Each project's use of the package, by version
+    pub uses: Vec<VersionUse>,
+    /// The version in use the most constraints admit
+    pub target: String,
+    /// How many constraints admit the target
+    pub satisfied: usize,
+}
+
+impl Misalignment {
+    /// Returns the projects whose constraint must change to reach the target
+    pub fn to_change(&self) -> Vec<&VersionUse> {
+        self.uses
+            .iter()
+            .filter(|version_use| !version_use.admits_target)
+            .collect()
+    }
+}
+
+/// Packages the scanned projects use at versions far apart
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlignmentReport {
+    /// Number of projects scanned
+    pub projects: usize,
+    /// Misaligned packages, the widest spread and most used first
+    pub packages: Vec<Misalignment>,
+}
+
+impl AlignmentReport {
+    /// Builds the report from the projects' dependency reports, listing the
+    /// packages used by more than one project whose versions are at least
+    /// `min_spread` apart
+    ///
+    /// Package names match case-insensitively, treating `-`, `_` and `.`
+    /// alike. Versions that cannot be parsed, such as git or path
+    /// dependencies, are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::alignment::{AlignmentReport, Spread};
+    /// use devhealth::scanner::deps;
+    /// use std::path::Path;
+    ///
+    /// let reports = deps::scan_dependencies(Path::new(".")).unwrap();
+    /// let report = AlignmentReport::build(&reports, Spread::Major);
+    /// for package in &report.packages {
+    ///     println!("{}: converge on {}", package.name, package.target);
+    /// }
+    /// ```
+    pub fn build(reports: &[DependencyReport], min_spread: Spread) -> AlignmentReport {
+        let mut resolver = Resolver::default();
+        let mut packages: BTreeMap<(String, Ecosystem), (String, BTreeSet<Use>)> = BTreeMap::new();
+        for report in reports {
+            for dependency in &report.dependencies {
+                let resolved = resolver.resolve(dependency);
+                let Some(version) = version_in_use(&resolved) else {
+                    continue;
+                };
+                packages
+                    .entry((
+                        normalize_package_name(&dependency.name),
+                        dependency.ecosystem.clone(),
+                    ))
+                    .or_insert_with(|| (dependency.name.clone(), BTreeSet::new()))
+                    .1
+                    .insert((
+                        report.project_path.clone(),
+                        dependency.version.clone(),
+                        version,
+                    ));
+            }
+        }
+
+        let mut misaligned: Vec<Misalignment> = packages
+            .into_iter()
+            .filter_map(|((_, ecosystem), (name, uses))| misalignment(name, ecosystem, uses))
+            .filter(|package| package.spread >= min_spread)
+            .collect();
+        misaligned.sort_by(|a, b| {
+            b.spread
+                .cmp(&a.spread)
+                .then_with(|| b.uses.len().cmp(&a.uses.len()))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        AlignmentReport {
+            projects: reports.len(),
+            packages: misaligned,
+        }
+    }
+}
+
+/// Returns the version a resolved version or constraint stands for: the
+/// locked version, or the lower bound of a constraint
+fn version_in_use(resolved: &str) -> Option<String> {
+    let first = resolved
+        .split([',', ' ', '|'])
+        .find(|part| !part.is_empty())?;
+    let version = first.trim_start_matches(['^', '~', '=', '>']);
+    if first.starts_with(['<', '!']) || version.contains('*') {
+        return None;
+    }
+    parse_loose_version(version).map(|_| version.to_string())
+}
+
+/// Checks whether a package used by more than one project is misaligned,
+/// and picks its target version
+fn misalignment(name: String, ecosystem: Ecosystem, uses: BTreeSet<Use>) -> Option<Misalignment> {
+    let projects = uses
+        .iter()
+        .map(|(project, _, _)| project)
+        .collect::<BTreeSet<_>>();
+    if projects.len() < 2 {
+        return None;
+    }
+    let mut candidates: Vec<(Version, &String)> = uses
+        .iter()
+        .filter_map(|(_, _, version)| Some((parse_loose_version(version)?, version)))
+        .collect();
+    candidates.sort();
+    candidates.dedup_by(|a, b| a.0 == b.0);
+    let spread = Spread::between(&candidates.first()?.0, &candidates.last()?.0);
+
+    let admits = |constraint: &str, version: &str| {
+        impact::verdict(&ecosystem, constraint, version) == Verdict::Compatible
+    };
+    let (satisfied, target) = candidates
+        .iter()
+        .map(|(_, candidate)| {
+            let satisfied = uses
+                .iter()
+                .filter(|(_, constraint, _)| admits(constraint, candidate))
+                .count();
+            (satisfied, *candidate)
+        })
+        .max_by_key(|(satisfied, _)| *satisfied)?;
+    let target = target.clone();
+
+    let mut uses: Vec<VersionUse> = uses
+        .into_iter()
+        .map(|(project, constraint, version)| VersionUse {
+            admits_target: admits(&constraint, &target),
+            project,
+            constraint,
+            version,
+        })
+        .collect();
+    uses.sort_by(|a, b| {
+        parse_loose_version(&a.version)
+            .cmp(&parse_loose_version(&b.version))
+            .then_with(|| a.project.cmp(&b.project))
+    });
+
+    Some(Misalignment {
+        name,
+        ecosystem,
+        spread,
+        uses,
+        target,
+        satisfied,
+    })
+}
+
+/// Displays the misaligned packages and the version to converge on
+pub fn display_report(report: &AlignmentReport) {
+    if report.packages.is_empty() {
+        println!("{}", display::header(t("align-title"), "🧭", Role::Special));
+        println!(
+            "{} {}",
+            display::symbols("✅"),
+            t("align-none").paint(Role::Success)
+        );
+        return;
+    }
+    println!("{}", display::header(t("align-title"), "🧭", Role::Warning));
+    let changes: usize = report
+        .packages
+        .iter()
+        .map(|package| package.to_change().len())
+        .sum();
+    print!(
+        "{}",
+        display::summary_box(&[
+            (t("deps-projects"), report.projects.to_string()),
+            (t("align-misaligned"), report.packages.len().to_string()),
+            (t("align-changes"), changes.to_string()),
+        ])
+    );
+    println!();
+
+    for (index, package) in report.packages.iter().enumerate() {
+        let spread = match package.spread {
+            Spread::Major => t("align-spread-major"),
+            Spread::Minor => t("align-spread-minor"),
+            Spread::Patch => t("align-spread-patch"),
+        };
+        let header = format!(
+            "{} {} {}",
+            display::ecosystem_icon(&package.ecosystem.to_string()),
+            package.name.paint(Role::Highlight).bold(),
+            format!("({})", spread).paint(Role::Muted)
+        );
+        println!(
+            "{}",
+            display::tree_item(&header, index == report.packages.len() - 1, 0)
+        );
+        for version_use in &package.uses {
+            let role = if version_use.admits_target {
+                Role::Success
+            } else {
+                Role::Warning
+            };
+            let line = format!(
+                "{} {} {}",
+                version_use.version.paint(role),
+                project_name(&version_use.project),
+                format!("({})", version_use.constraint).paint(Role::Muted)
+            );
+            println!("{}", display::tree_item(&line, false, 1));
+        }
+        let suggestion = tr!(
+            "align-target",
+            version = package.target.as_str(),
+            satisfied = package.satisfied,
+            total = package.uses.len()
+        );
+        println!(
+            "{}",
+            display::tree_item(
+                &format!("→ {}", suggestion.paint(Role::Success).bold()),
+                true,
+                1
+            )
+        );
+    }
+    println!(
+        "\n{} {}",
+        display::symbols("💡"),
+        t("align-tip").paint(Role::Muted)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::deps::{Dependency, DependencyType};
+    use std::path::Path;
+
+    fn report(
+        project: &str,
+        ecosystem: Ecosystem,
+        dependencies: &[(&str, &str)],
+    ) -> DependencyReport {
+        let dir = Path::new("/nonexistent").join(project);
+        DependencyReport {
+            project_path: dir.clone(),
+            dependencies: dependencies
+                .iter()
+                .map(|(name, version)| Dependency {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    dependency_type: DependencyType::Runtime,
+                    ecosystem: ecosystem.clone(),
+                    source_file: dir.join("manifest"),
+                    extras: Vec::new(),
+                    marker: None,
+                })
+                .collect(),
+            ecosystems: vec![ecosystem],
+            errors: Vec::new(),
+            lockfiles: Vec::new(),
+            go_modules: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn measures_the_spread_between_versions() {
+        let version = |text: &str| Version::parse(text).unwrap();
+        assert_eq!(
+            Spread::between(&version("1.2.0"), &version("2.0.0")),
+            Spread::Major
+        );
+        assert_eq!(
+            Spread::between(&version("0.3.1"), &version("0.4.0")),
+            Spread::Major
+        );
+        assert_eq!(
+            Spread::between(&version("1.2.0"), &version("1.9.3")),
+            Spread::Minor
+        );
+        assert_eq!(
+            Spread::between(&version("1.2.0"), &version("1.2.7")),
+            Spread::Patch
+        );
+    }
+
+    #[test]
+    fn reads_the_version_a_constraint_stands_for() {
+        assert_eq!(version_in_use("^4.17.21").as_deref(), Some("4.17.21"));
+        assert_eq!(version_in_use(">=2.28,<3").as_deref(), Some("2.28"));
+        assert_eq!(version_in_use("v1.9.0").as_deref(), Some("v1.9.0"));
+        assert_eq!(version_in_use("<3"), None);
+        assert_eq!(version_in_use("*"), None);
+        assert_eq!(version_in_use("git+https://example.com/repo"), None);
+    }
+
+    #[test]
+    fn suggests_the_version_most_constraints_admit() {
+        let reports = [
+            report(
+                "api",
+                Ecosystem::NodeJs,
+                &[("lodash", "^4.17.0"), ("react", "^18.2.0")],
+            ),
+            report(
+                "web",
+                Ecosystem::NodeJs,
+                &[("lodash", "^4.17.21"), ("react", "^18.3.0")],
+            ),
+            report(
+                "legacy",
+                Ecosystem::NodeJs,
+                &[("lodash", "^3.10.1"), ("react", "^18.2.0")],
+            ),
+        ];
+        let report = AlignmentReport::build(&reports, Spread::Major);
+
+        assert_eq!(
+            report.packages.len(),
+            1,
+            "react only drifts by a minor version"
+        );
+        let lodash = &report.packages[0];
+        assert_eq!(lodash.spread, Spread::Major);
+        assert_eq!(lodash.target, "4.17.21");
+        assert_eq!(lodash.satisfied, 2);
+        let to_change: Vec<&Path> = lodash
+            .to_change()
+            .iter()
+            .map(|version_use| version_use.project.as_path())
+            .collect();
+        assert_eq!(to_change, [Path::new("/nonexistent/legacy")]);
+
+        let report = AlignmentReport::build(&reports, Spread::Minor);
+        let react = report
+            .packages
+            .iter()
+            .find(|package| package.name == "react")
+            .unwrap();
+        assert_eq!((react.target.as_str(), react.satisfied), ("18.3.0", 3));
+    }
+
+    #[test]
+    fn ignores_packages_used_by_a_single_project() {
+        let reports = [report(
+            "api",
+            Ecosystem::Rust,
+            &[("serde", "0.9"), ("serde_json", "1.0")],
+        )];
+        assert!(AlignmentReport::build(&reports, Spread::Patch)
+            .packages
+            .is_empty());
+    }
+}
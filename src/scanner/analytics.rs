@@ -1,48 +1,1084 @@
-//! Project analytics and code quality metrics (planned feature)
+//! Deep project checks with each ecosystem's own tooling
 //!
-//! This module will provide functionality for analyzing project health
-//! and code quality metrics, including:
+//! The other scanners read manifests and git metadata, which is fast but
+//! cannot tell whether a project still builds. The `--deep` mode runs the
+//! project's linters and compilers and folds their results into a per-project
+//! health report:
 //!
-//! - Code complexity analysis
-//! - Test coverage metrics
-//! - Documentation coverage
-//! - Technical debt indicators
-//! - Code style and formatting consistency
+//! - **Rust**: `cargo clippy --message-format=json` (warnings, errors and
+//!   whether the project compiles), `cargo fmt --check` (unformatted files)
+//!   and, when enabled with `tests = true` under `[deep]`, `cargo test
+//!   --no-run` (whether the tests compile)
+//! - **Node.js**: the `lint` and `typecheck` scripts from `package.json` when
+//!   present, otherwise the project's own `eslint`, `prettier --check` and
+//!   `tsc --noEmit` from `node_modules/.bin` when their configuration files
+//!   exist. Error and warning counts are read from ESLint's output, unformatted
+//!   files from Prettier's and `error TS` lines from the TypeScript compiler's
+//!
+//! Every command runs with the timeout from `[deep] timeout_secs` and is
+//! killed when it expires. Tools that are not installed are reported as
+//! unavailable instead of failing, as are Node checks in projects whose
+//! dependencies are not installed. Cargo and Node workspaces are checked once
+//! at their root rather than once per member.
+//!
+//! Deep checks build projects, which can take minutes and writes to their
+//! `target` directories, so they only run when asked for.
 
-/// Analyzes projects for code quality and health metrics
-///
-/// This is a placeholder function for future project analytics functionality.
-/// When implemented, it will analyze codebases to provide insights into:
-/// - Code quality metrics and trends
-/// - Test coverage analysis
-/// - Documentation completeness
-/// - Technical debt assessment
-/// - Best practice compliance
+use crate::config::DeepConfig;
+use crate::i18n::t;
+use crate::scanner::deps::Ecosystem;
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+/// How often a running check is polled for completion
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Script names run as the Node typecheck, in order of preference
+const TYPECHECK_SCRIPTS: [&str; 3] = ["typecheck", "type-check", "tsc"];
+
+/// Files configuring ESLint
+const ESLINT_CONFIGS: [&str; 10] = [
+    "eslint.config.js",
+    "eslint.config.mjs",
+    "eslint.config.cjs",
+    "eslint.config.ts",
+    ".eslintrc",
+    ".eslintrc.js",
+    ".eslintrc.cjs",
+    ".eslintrc.json",
+    ".eslintrc.yml",
+    ".eslintrc.yaml",
+];
+
+/// Files configuring Prettier
+const PRETTIER_CONFIGS: [&str; 11] = [
+    ".prettierrc",
+    ".prettierrc.json",
+    ".prettierrc.yml",
+    ".prettierrc.yaml",
+    ".prettierrc.toml",
+    ".prettierrc.js",
+    ".prettierrc.cjs",
+    ".prettierrc.mjs",
+    "prettier.config.js",
+    "prettier.config.cjs",
+    "prettier.config.mjs",
+];
+
+/// Health points deducted per failed check
+const FAILURE_PENALTY: usize = 25;
+
+/// Most health points deducted for warnings
+const MAX_WARNING_PENALTY: usize = 25;
+
+/// A tool run by the deep checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckKind {
+    /// `cargo clippy`
+    Clippy,
+    /// `cargo fmt --check`
+    Rustfmt,
+    /// `cargo test --no-run`
+    TestBuild,
+    /// ESLint, directly or through the `lint` script
+    Eslint,
+    /// `prettier --check`
+    Prettier,
+    /// `tsc --noEmit`, directly or through the `typecheck` script
+    Typecheck,
+}
+
+impl CheckKind {
+    /// Returns the command line shown when the tool is run directly
+    pub fn command(&self) -> &'static str {
+        match self {
+            CheckKind::Clippy => "cargo clippy",
+            CheckKind::Rustfmt => "cargo fmt --check",
+            CheckKind::TestBuild => "cargo test --no-run",
+            CheckKind::Eslint => "eslint",
+            CheckKind::Prettier => "prettier --check",
+            CheckKind::Typecheck => "tsc --noEmit",
+        }
+    }
+}
+
+/// How a check ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckOutcome {
+    /// The tool ran and found no blocking problem
+    Passed,
+    /// The project does not compile, typecheck or pass linting, or is not
+    /// formatted
+    Failed,
+    /// The tool did not finish within the timeout and was killed
+    TimedOut,
+    /// The tool is not installed
+    Unavailable,
+}
+
+/// Result of running one tool on a project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// The tool that ran
+    pub kind: CheckKind,
+    /// Command line shown for the check, such as `npm run lint`
+    pub command: String,
+    /// How it ended
+    pub outcome: CheckOutcome,
+    /// Warnings reported (unformatted files for rustfmt and Prettier)
+    pub warnings: usize,
+    /// Errors reported
+    pub errors: usize,
+    /// Wall-clock time the tool took, in milliseconds
+    pub duration_ms: u64,
+}
+
+/// Deep check results for one project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectAnalysis {
+    /// Project root the tools ran in
+    pub path: PathBuf,
+    /// Ecosystem whose tools ran
+    pub ecosystem: Ecosystem,
+    /// One result per tool
+    pub checks: Vec<CheckResult>,
+}
+
+impl ProjectAnalysis {
+    /// Total warnings across all checks
+    pub fn warnings(&self) -> usize {
+        self.checks.iter().map(|check| check.warnings).sum()
+    }
+
+    /// Number of checks that failed or timed out
+    pub fn failures(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.outcome, CheckOutcome::Failed | CheckOutcome::TimedOut))
+            .count()
+    }
+
+    /// Calculates a health score from 0 to 100
+    ///
+    /// Every failed or timed out check costs 25 points and every warning one
+    /// point, up to 25 points for warnings.
+    pub fn health_score(&self) -> usize {
+        let penalty = FAILURE_PENALTY * self.failures() + self.warnings().min(MAX_WARNING_PENALTY);
+        100usize.saturating_sub(penalty)
+    }
+}
+
+/// Runs the deep checks on projects accepted by a filter
 ///
-/// # Note
+/// # Arguments
 ///
-/// This function is currently not implemented and serves as a placeholder
-/// for future development.
+/// * `path` - The directory to search for projects
+/// * `include` - Predicate deciding whether a project directory is checked
+/// * `config` - Timeout and optional checks from the `[deep]` section
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,no_run
+/// use devhealth::config::DeepConfig;
 /// use devhealth::scanner::analytics;
+/// use std::path::Path;
 ///
-/// // Future usage (not yet implemented)
-/// analytics::analyze_projects();
+/// let analyses = analytics::analyze_projects_matching(Path::new("."), |_| true, &DeepConfig::default());
+/// for analysis in &analyses {
+///     println!("{}: {}%", analysis.path.display(), analysis.health_score());
+/// }
 /// ```
-pub fn analyze_projects() {
-    println!("Project analytics not implemented yet");
+pub fn analyze_projects_matching<F>(
+    path: &Path,
+    include: F,
+    config: &DeepConfig,
+) -> Vec<ProjectAnalysis>
+where
+    F: Fn(&Path) -> bool,
+{
+    find_projects(path)
+        .into_iter()
+        .filter(|(project, _)| include(project))
+        .map(|(project, ecosystem)| {
+            eprintln!("  Analyzing: {}", project.display());
+            let checks = match ecosystem {
+                Ecosystem::Rust => check_rust_project(&project, config),
+                Ecosystem::NodeJs => check_node_project(&project, config),
+                Ecosystem::Python
+                | Ecosystem::Go
+                | Ecosystem::Bazel
+                | Ecosystem::Buck
+                | Ecosystem::Nix => Vec::new(),
+            };
+            ProjectAnalysis {
+                path: project,
+                ecosystem,
+                checks,
+            }
+        })
+        .collect()
+}
+
+/// Displays deep check results at the given level of detail
+///
+/// - [`Detail::Summary`]: header and summary box only
+/// - [`Detail::Minimal`]: only projects with failed checks or warnings
+/// - [`Detail::Normal`]: every project with the outcome of each check
+/// - [`Detail::Full`]: also how long each check took
+///
+/// # Arguments
+///
+/// * `analyses` - Slice of project analyses to display
+/// * `detail` - How much of the report to print
+pub fn display_results_with(analyses: &[ProjectAnalysis], detail: Detail) {
+    if analyses.is_empty() {
+        println!(
+            "{}",
+            display::header(&tr!("deep-none"), "🔬", Role::Warning)
+        );
+        return;
+    }
+
+    let failing = analyses.iter().filter(|a| a.failures() > 0).count();
+    let warnings: usize = analyses.iter().map(ProjectAnalysis::warnings).sum();
+    let average = analyses
+        .iter()
+        .map(ProjectAnalysis::health_score)
+        .sum::<usize>()
+        / analyses.len();
+    let role = if failing > 0 {
+        Role::Warning
+    } else {
+        Role::Special
+    };
+
+    println!(
+        "{}",
+        display::header(&tr!("deep-title", score = average), "🔬", role)
+    );
+
+    let summary_items = vec![
+        (t("deep-projects"), analyses.len().to_string()),
+        (
+            t("deep-failing"),
+            if failing > 0 {
+                format!("{} ⚠️", failing)
+            } else {
+                "0".to_string()
+            },
+        ),
+        (t("deep-warnings"), warnings.to_string()),
+    ];
+    print!("{}", display::summary_box(&summary_items));
+
+    if detail == Detail::Summary {
+        return;
+    }
+
+    let shown: Vec<&ProjectAnalysis> = analyses
+        .iter()
+        .filter(|a| detail >= Detail::Normal || a.failures() > 0 || a.warnings() > 0)
+        .collect();
+    if shown.is_empty() {
+        return;
+    }
+
+    println!("{}", display::section_divider(t("deep-details")));
+    for (index, analysis) in shown.iter().enumerate() {
+        let name = analysis
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "unknown".to_string());
+        let score = analysis.health_score();
+        let score_role = match score {
+            90.. => Role::Success,
+            60..=89 => Role::Warning,
+            _ => Role::Error,
+        };
+        let header = format!(
+            "{} {} {}",
+            name.paint(Role::Emphasis).bold(),
+            format!("[{}]", analysis.ecosystem).paint(Role::Muted),
+            format!("{}%", score).paint(score_role)
+        );
+        println!(
+            "{}",
+            display::tree_item(&header, index == shown.len() - 1, 0)
+        );
+
+        let lines: Vec<String> = analysis
+            .checks
+            .iter()
+            .filter(|check| {
+                detail >= Detail::Normal
+                    || check.outcome != CheckOutcome::Passed
+                    || check.warnings > 0
+            })
+            .map(|check| check_line(check, detail))
+            .collect();
+        for (line_index, line) in lines.iter().enumerate() {
+            println!(
+                "{}",
+                display::tree_item(line, line_index == lines.len() - 1, 1)
+            );
+        }
+    }
+}
+
+/// Formats one check result
+fn check_line(check: &CheckResult, detail: Detail) -> String {
+    let (symbol, role, status) = match check.outcome {
+        CheckOutcome::Passed if check.warnings > 0 => (
+            "⚠",
+            Role::Warning,
+            tr!("deep-check-warnings", warnings = check.warnings),
+        ),
+        CheckOutcome::Passed => ("✓", Role::Success, t("deep-check-passed").to_string()),
+        CheckOutcome::Failed if matches!(check.kind, CheckKind::Rustfmt | CheckKind::Prettier) => (
+            "✗",
+            Role::Error,
+            tr!("deep-check-unformatted", files = check.warnings),
+        ),
+        CheckOutcome::Failed => (
+            "✗",
+            Role::Error,
+            tr!(
+                "deep-check-failed",
+                errors = check.errors,
+                warnings = check.warnings
+            ),
+        ),
+        CheckOutcome::TimedOut => ("⏱", Role::Error, t("deep-check-timed-out").to_string()),
+        CheckOutcome::Unavailable => ("○", Role::Muted, t("deep-check-unavailable").to_string()),
+    };
+    let mut line = format!(
+        "{} {} {}",
+        symbol.paint(role),
+        check.command.paint(Role::Emphasis),
+        status.paint(role)
+    );
+    if detail == Detail::Full {
+        let seconds = format!("({:.1}s)", check.duration_ms as f64 / 1000.0);
+        line.push_str(&format!(" {}", seconds.paint(Role::Muted)));
+    }
+    line
+}
+
+/// Finds project roots, checking each workspace once at its root
+///
+/// Cargo `[workspace]` roots and Node roots declaring `workspaces` (or with a
+/// `pnpm-workspace.yaml`) cover their members, which are not checked again.
+fn find_projects(path: &Path) -> Vec<(PathBuf, Ecosystem)> {
+    let mut roots = Vec::new();
+
+    for entry in dh_fs::walk(path) {
+        if dh_fs::is_skipped(entry.path().strip_prefix(path).unwrap_or(entry.path())) {
+            continue;
+        }
+        let Some(dir) = entry.path().parent() else {
+            continue;
+        };
+        let dir = dh_fs::strip_extended_length(dir);
+        if entry.file_name() == "Cargo.toml" {
+            let Some(manifest) = read_toml(entry.path()) else {
+                continue;
+            };
+            if manifest.get("workspace").is_some() {
+                roots.push((Ecosystem::Rust, dir, true));
+            } else if manifest.get("package").is_some() {
+                roots.push((Ecosystem::Rust, dir, false));
+            }
+        } else if entry.file_name() == "package.json" {
+            let Some(manifest) = read_json(entry.path()) else {
+                continue;
+            };
+            let workspace =
+                manifest.get("workspaces").is_some() || dir.join("pnpm-workspace.yaml").is_file();
+            roots.push((Ecosystem::NodeJs, dir, workspace));
+        }
+    }
+
+    let workspaces: BTreeSet<(Ecosystem, PathBuf)> = roots
+        .iter()
+        .filter(|(_, _, workspace)| *workspace)
+        .map(|(ecosystem, dir, _)| (ecosystem.clone(), dir.clone()))
+        .collect();
+    // Members, including nested workspaces, are checked by their outermost workspace
+    let mut projects: Vec<(PathBuf, Ecosystem)> = roots
+        .into_iter()
+        .filter(|(ecosystem, dir, _)| {
+            !dir.ancestors()
+                .skip(1)
+                .any(|ancestor| workspaces.contains(&(ecosystem.clone(), ancestor.to_path_buf())))
+        })
+        .map(|(ecosystem, dir, _)| (dir, ecosystem))
+        .collect();
+    projects.sort();
+    projects
+}
+
+/// Runs clippy, rustfmt and optionally the test build on a Cargo project
+fn check_rust_project(dir: &Path, config: &DeepConfig) -> Vec<CheckResult> {
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let cargo = |kind: CheckKind, args: &[&str]| {
+        let mut command = Command::new("cargo");
+        command
+            .args(args)
+            .current_dir(dir)
+            .env("CARGO_TERM_COLOR", "never");
+        run_check(kind, kind.command().to_string(), command, timeout)
+    };
+
+    let mut checks = vec![
+        cargo(
+            CheckKind::Clippy,
+            &[
+                "clippy",
+                "--workspace",
+                "--all-targets",
+                "--message-format=json",
+            ],
+        ),
+        cargo(CheckKind::Rustfmt, &["fmt", "--all", "--check"]),
+    ];
+    if config.tests {
+        checks.push(cargo(
+            CheckKind::TestBuild,
+            &["test", "--workspace", "--no-run", "--message-format=json"],
+        ));
+    }
+    checks
+}
+
+/// How a Node check is run
+enum NodeTool<'a> {
+    /// `npm run <script>`
+    Script(&'a str),
+    /// A binary from `node_modules/.bin` with its arguments
+    Bin(&'a str, &'a [&'a str]),
+}
+
+/// Runs the lint, format and typecheck tools a Node project is set up for
+///
+/// Scripts take precedence over detected configuration, since they carry the
+/// project's own file selection and flags.
+fn check_node_project(dir: &Path, config: &DeepConfig) -> Vec<CheckResult> {
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let manifest = read_json(&dir.join("package.json")).unwrap_or_default();
+    let has_script = |name: &str| {
+        manifest
+            .get("scripts")
+            .and_then(|scripts| scripts.get(name))
+            .is_some_and(|script| script.is_string())
+    };
+    let has_config = |files: &[&str], key: &str| {
+        manifest.get(key).is_some() || files.iter().any(|file| dir.join(file).is_file())
+    };
+
+    let mut tools = Vec::new();
+    if has_script("lint") {
+        tools.push((CheckKind::Eslint, NodeTool::Script("lint")));
+    } else if has_config(&ESLINT_CONFIGS, "eslintConfig") {
+        tools.push((
+            CheckKind::Eslint,
+            NodeTool::Bin("eslint", &[".", "--format", "json"]),
+        ));
+    }
+    if has_config(&PRETTIER_CONFIGS, "prettier") {
+        tools.push((
+            CheckKind::Prettier,
+            NodeTool::Bin("prettier", &["--check", "."]),
+        ));
+    }
+    if let Some(script) = TYPECHECK_SCRIPTS.into_iter().find(|name| has_script(name)) {
+        tools.push((CheckKind::Typecheck, NodeTool::Script(script)));
+    } else if dir.join("tsconfig.json").is_file() {
+        tools.push((
+            CheckKind::Typecheck,
+            NodeTool::Bin("tsc", &["--noEmit", "--pretty", "false"]),
+        ));
+    }
+
+    let installed = dir.join("node_modules").is_dir();
+    tools
+        .into_iter()
+        .map(|(kind, tool)| {
+            let (label, mut command) = match tool {
+                NodeTool::Script(script) => {
+                    let mut command = Command::new(platform_program("npm"));
+                    command.args(["run", "--silent", script]);
+                    (format!("npm run {}", script), command)
+                }
+                NodeTool::Bin(name, args) => {
+                    let mut command =
+                        Command::new(dir.join("node_modules/.bin").join(platform_program(name)));
+                    command.args(args);
+                    (kind.command().to_string(), command)
+                }
+            };
+            if !installed {
+                return CheckResult {
+                    kind,
+                    command: label,
+                    outcome: CheckOutcome::Unavailable,
+                    warnings: 0,
+                    errors: 0,
+                    duration_ms: 0,
+                };
+            }
+            command
+                .current_dir(dir)
+                .env("FORCE_COLOR", "0")
+                .env("NO_COLOR", "1");
+            run_check(kind, label, command, timeout)
+        })
+        .collect()
+}
+
+/// Returns the name to spawn for a Node tool (`.cmd` shims on Windows)
+fn platform_program(name: &str) -> String {
+    if cfg!(windows) {
+        format!("{}.cmd", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Runs one tool with a timeout and interprets its output
+fn run_check(kind: CheckKind, label: String, command: Command, timeout: Duration) -> CheckResult {
+    let started = Instant::now();
+    let result = run_with_timeout(command, timeout);
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let (outcome, warnings, errors) = match result {
+        RunResult::Finished(output) => interpret(kind, &output),
+        RunResult::TimedOut => (CheckOutcome::TimedOut, 0, 0),
+        RunResult::NotFound => (CheckOutcome::Unavailable, 0, 0),
+    };
+
+    CheckResult {
+        kind,
+        command: label,
+        outcome,
+        warnings,
+        errors,
+        duration_ms,
+    }
+}
+
+/// Reads the outcome, warning count and error count from a tool's output
+fn interpret(kind: CheckKind, output: &Output) -> (CheckOutcome, usize, usize) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let outcome = |passed: bool| {
+        if passed {
+            CheckOutcome::Passed
+        } else {
+            CheckOutcome::Failed
+        }
+    };
+    let is_cargo = matches!(
+        kind,
+        CheckKind::Clippy | CheckKind::Rustfmt | CheckKind::TestBuild
+    );
+    if is_cargo && is_missing_subcommand(&stderr) {
+        return (CheckOutcome::Unavailable, 0, 0);
+    }
+
+    match kind {
+        CheckKind::Clippy | CheckKind::TestBuild => {
+            let counts = count_diagnostics(&stdout);
+            (
+                outcome(output.status.success() && counts.errors == 0),
+                counts.warnings,
+                counts.errors,
+            )
+        }
+        CheckKind::Rustfmt => (
+            outcome(output.status.success()),
+            count_unformatted_files(&stdout),
+            0,
+        ),
+        CheckKind::Eslint => {
+            let counts = count_eslint_problems(&stdout);
+            (
+                outcome(output.status.success()),
+                counts.warnings,
+                counts.errors,
+            )
+        }
+        CheckKind::Prettier => (
+            outcome(output.status.success()),
+            count_prettier_files(&format!("{}{}", stdout, stderr)),
+            0,
+        ),
+        CheckKind::Typecheck => (
+            outcome(output.status.success()),
+            0,
+            count_type_errors(&format!("{}{}", stdout, stderr)),
+        ),
+    }
+}
+
+/// Warning and error counts from cargo's JSON messages
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DiagnosticCounts {
+    warnings: usize,
+    errors: usize,
+}
+
+/// Counts compiler diagnostics in `--message-format=json` output
+///
+/// Summary lines such as "3 warnings emitted" carry no source location and
+/// are not counted; the same diagnostic reported for several targets is
+/// counted once.
+fn count_diagnostics(json_lines: &str) -> DiagnosticCounts {
+    let mut seen = BTreeSet::new();
+    let mut counts = DiagnosticCounts::default();
+
+    for line in json_lines.lines() {
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if message.get("reason").and_then(|r| r.as_str()) != Some("compiler-message") {
+            continue;
+        }
+        let Some(diagnostic) = message.get("message") else {
+            continue;
+        };
+        let has_location = diagnostic
+            .get("spans")
+            .and_then(|spans| spans.as_array())
+            .is_some_and(|spans| !spans.is_empty());
+        let rendered = diagnostic
+            .get("rendered")
+            .and_then(|r| r.as_str())
+            .unwrap_or_default();
+        if !has_location || !seen.insert(rendered.to_string()) {
+            continue;
+        }
+        match diagnostic.get("level").and_then(|l| l.as_str()) {
+            Some("warning") => counts.warnings += 1,
+            Some("error") | Some("error: internal compiler error") => counts.errors += 1,
+            _ => {}
+        }
+    }
+    counts
+}
+
+/// Counts the files `cargo fmt --check` would reformat
+fn count_unformatted_files(output: &str) -> usize {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("Diff in "))
+        .filter_map(|rest| {
+            rest.rsplit_once(" at line ")
+                .map(|(file, _)| file)
+                .or(Some(rest))
+        })
+        .map(|file| file.trim_end_matches(':'))
+        .collect::<BTreeSet<_>>()
+        .len()
+}
+
+/// Counts ESLint problems from its JSON or default ("stylish") output
+///
+/// The stylish summary reads "✖ 3 problems (2 errors, 1 warning)"; output of
+/// other linters behind a `lint` script counts as no problems.
+fn count_eslint_problems(output: &str) -> DiagnosticCounts {
+    if let Ok(serde_json::Value::Array(files)) = serde_json::from_str(output.trim()) {
+        let total = |key: &str| {
+            files
+                .iter()
+                .filter_map(|file| file.get(key)?.as_u64())
+                .sum::<u64>() as usize
+        };
+        return DiagnosticCounts {
+            warnings: total("warningCount"),
+            errors: total("errorCount"),
+        };
+    }
+
+    output
+        .lines()
+        .filter(|line| line.contains(" problem"))
+        .find_map(|line| {
+            let (_, counts) = line.split_once('(')?;
+            let count = |word: &str| {
+                counts
+                    .split(',')
+                    .find(|part| part.contains(word))
+                    .and_then(|part| part.split_whitespace().next()?.parse().ok())
+                    .unwrap_or(0)
+            };
+            Some(DiagnosticCounts {
+                warnings: count("warning"),
+                errors: count("error"),
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Counts the files `prettier --check` reports as unformatted
+fn count_prettier_files(output: &str) -> usize {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("[warn] "))
+        .filter(|file| !file.starts_with("Code style issues"))
+        .count()
+}
+
+/// Counts TypeScript compiler errors such as `src/a.ts(1,7): error TS2322`
+fn count_type_errors(output: &str) -> usize {
+    output
+        .lines()
+        .filter(|line| line.contains("error TS"))
+        .count()
+}
+
+/// Returns `true` if cargo reports the subcommand as not installed
+fn is_missing_subcommand(stderr: &str) -> bool {
+    stderr.contains("no such command") || stderr.contains("is not installed for the toolchain")
+}
+
+/// How a command run with a timeout ended
+enum RunResult {
+    /// The command exited on its own
+    Finished(Output),
+    /// The command was killed after the timeout
+    TimedOut,
+    /// The program does not exist
+    NotFound,
+}
+
+/// Runs a command, killing it if it does not finish within `timeout`
+///
+/// Output is read on background threads so a chatty command cannot block on
+/// a full pipe while it is being waited for.
+fn run_with_timeout(mut command: Command, timeout: Duration) -> RunResult {
+    let mut child = match command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(_) => return RunResult::NotFound,
+    };
+    let read_all = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buffer);
+            }
+            buffer
+        })
+    };
+    let stdout = read_all(
+        child
+            .stdout
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+    let stderr = read_all(
+        child
+            .stderr
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(POLL_INTERVAL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+        }
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    match status {
+        Some(status) => RunResult::Finished(Output {
+            status,
+            stdout,
+            stderr,
+        }),
+        None => RunResult::TimedOut,
+    }
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    toml::from_str(&std::fs::read_to_string(path).ok()?).ok()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn check(kind: CheckKind, outcome: CheckOutcome, warnings: usize) -> CheckResult {
+        CheckResult {
+            kind,
+            command: kind.command().to_string(),
+            outcome,
+            warnings,
+            errors: 0,
+            duration_ms: 0,
+        }
+    }
+
+    mod discovery {
+        use super::*;
+
+        #[test]
+        fn checks_workspaces_once_at_their_root() {
+            let temp_dir = TempDir::new().unwrap();
+            let root = temp_dir.path();
+            let write = |path: &str, content: &str| {
+                let file = root.join(path);
+                fs::create_dir_all(file.parent().unwrap()).unwrap();
+                fs::write(file, content).unwrap();
+            };
+            write("mono/Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n");
+            write(
+                "mono/crates/core/Cargo.toml",
+                "[package]\nname = \"core\"\n",
+            );
+            write("cli/Cargo.toml", "[package]\nname = \"cli\"\n");
+            write(
+                "cli/target/package/cli-0.1.0/Cargo.toml",
+                "[package]\nname = \"cli\"\n",
+            );
+            write(
+                "web/package.json",
+                r#"{"name": "web", "workspaces": ["apps/*"]}"#,
+            );
+            write("web/apps/site/package.json", r#"{"name": "site"}"#);
+            write(
+                "web/node_modules/react/package.json",
+                r#"{"name": "react"}"#,
+            );
+            write("mono/tools/package.json", r#"{"name": "tools"}"#);
+
+            assert_eq!(
+                find_projects(root),
+                vec![
+                    (root.join("cli"), Ecosystem::Rust),
+                    (root.join("mono"), Ecosystem::Rust),
+                    (root.join("mono/tools"), Ecosystem::NodeJs),
+                    (root.join("web"), Ecosystem::NodeJs),
+                ]
+            );
+        }
+    }
+
+    mod output {
+        use super::*;
+
+        #[test]
+        fn counts_located_diagnostics_once() {
+            let warning = r#"{"reason":"compiler-message","message":{"level":"warning","rendered":"warning: unused variable `x`","spans":[{"file_name":"src/lib.rs"}]}}"#;
+            let error = r#"{"reason":"compiler-message","message":{"level":"error","rendered":"error: mismatched types","spans":[{"file_name":"src/main.rs"}]}}"#;
+            let summary = r#"{"reason":"compiler-message","message":{"level":"warning","rendered":"warning: 1 warning emitted","spans":[]}}"#;
+            let finished = r#"{"reason":"build-finished","success":false}"#;
+            let output = [
+                warning,
+                warning,
+                error,
+                summary,
+                finished,
+                "Compiling api v0.1.0",
+            ]
+            .join("\n");
+
+            assert_eq!(
+                count_diagnostics(&output),
+                DiagnosticCounts {
+                    warnings: 1,
+                    errors: 1
+                }
+            );
+        }
+
+        #[test]
+        fn counts_unformatted_files() {
+            let output = "Diff in /src/api/src/lib.rs at line 3:\n-fn a(){}\n+fn a() {}\n\
+                          Diff in /src/api/src/lib.rs at line 9:\n\
+                          Diff in /src/api/src/main.rs:12:\n";
+            assert_eq!(count_unformatted_files(output), 2);
+            assert_eq!(count_unformatted_files(""), 0);
+        }
+
+        #[test]
+        fn counts_eslint_problems() {
+            let json = r#"[{"filePath":"/web/a.js","errorCount":2,"warningCount":1},
+                           {"filePath":"/web/b.js","errorCount":0,"warningCount":3}]"#;
+            assert_eq!(
+                count_eslint_problems(json),
+                DiagnosticCounts {
+                    warnings: 4,
+                    errors: 2
+                }
+            );
+
+            let stylish = "\n/web/src/a.js\n  1:7  error  'x' is unused  no-unused-vars\n\n\
+                           ✖ 3 problems (1 error, 2 warnings)\n  1 error and 0 warnings potentially fixable\n";
+            assert_eq!(
+                count_eslint_problems(stylish),
+                DiagnosticCounts {
+                    warnings: 2,
+                    errors: 1
+                }
+            );
+            assert_eq!(count_eslint_problems(""), DiagnosticCounts::default());
+        }
+
+        #[test]
+        fn counts_prettier_and_typescript_output() {
+            let prettier = "Checking formatting...\n[warn] src/a.ts\n[warn] src/b.ts\n\
+                            [warn] Code style issues found in 2 files. Run Prettier with --write to fix.\n";
+            assert_eq!(count_prettier_files(prettier), 2);
+
+            let tsc = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                       src/b.ts:4:1 - error TS2304: Cannot find name 'foo'.\n\nFound 2 errors in 2 files.\n";
+            assert_eq!(count_type_errors(tsc), 2);
+        }
+
+        #[test]
+        fn recognizes_missing_tools() {
+            assert!(is_missing_subcommand("error: no such command: `clippy`\n"));
+            assert!(is_missing_subcommand(
+                "error: 'cargo-fmt' is not installed for the toolchain 'stable-x86_64-unknown-linux-gnu'"
+            ));
+            assert!(!is_missing_subcommand("error: could not compile `api`"));
+        }
+    }
+
+    mod scoring {
+        use super::*;
+
+        #[test]
+        fn deducts_failures_and_capped_warnings() {
+            let analysis = |checks| ProjectAnalysis {
+                path: PathBuf::from("/src/api"),
+                ecosystem: Ecosystem::Rust,
+                checks,
+            };
+
+            assert_eq!(
+                analysis(vec![check(CheckKind::Clippy, CheckOutcome::Passed, 0)]).health_score(),
+                100
+            );
+            assert_eq!(
+                analysis(vec![check(CheckKind::Clippy, CheckOutcome::Passed, 7)]).health_score(),
+                93
+            );
+            assert_eq!(
+                analysis(vec![check(CheckKind::Clippy, CheckOutcome::Passed, 400)]).health_score(),
+                75
+            );
+            let failing = analysis(vec![
+                check(CheckKind::Clippy, CheckOutcome::Failed, 2),
+                check(CheckKind::Rustfmt, CheckOutcome::TimedOut, 0),
+                check(CheckKind::TestBuild, CheckOutcome::Unavailable, 0),
+            ]);
+            assert_eq!(failing.failures(), 2);
+            assert_eq!(failing.health_score(), 48);
+        }
+    }
+
+    mod node {
+        use super::*;
+
+        #[test]
+        fn prefers_scripts_and_skips_uninstalled_projects() {
+            let temp_dir = TempDir::new().unwrap();
+            let dir = temp_dir.path();
+            fs::write(
+                dir.join("package.json"),
+                r#"{"scripts": {"lint": "eslint src", "type-check": "tsc -p ."}, "prettier": {}}"#,
+            )
+            .unwrap();
+            fs::write(dir.join("tsconfig.json"), "{}").unwrap();
+
+            let checks = check_node_project(dir, &DeepConfig::default());
+
+            let commands: Vec<&str> = checks.iter().map(|c| c.command.as_str()).collect();
+            assert_eq!(
+                commands,
+                vec!["npm run lint", "prettier --check", "npm run type-check"]
+            );
+            assert!(checks
+                .iter()
+                .all(|c| c.outcome == CheckOutcome::Unavailable));
+        }
+
+        #[test]
+        fn detects_tool_configs() {
+            let temp_dir = TempDir::new().unwrap();
+            let dir = temp_dir.path();
+            fs::write(dir.join("package.json"), r#"{"name": "web"}"#).unwrap();
+            fs::write(dir.join("eslint.config.mjs"), "export default [];").unwrap();
+            fs::write(dir.join("tsconfig.json"), "{}").unwrap();
+
+            let kinds: Vec<CheckKind> = check_node_project(dir, &DeepConfig::default())
+                .into_iter()
+                .map(|c| c.kind)
+                .collect();
+            assert_eq!(kinds, vec![CheckKind::Eslint, CheckKind::Typecheck]);
+        }
+    }
+
+    #[cfg(unix)]
+    mod timeouts {
+        use super::*;
+
+        #[test]
+        fn kills_commands_that_run_too_long() {
+            let mut command = Command::new("sleep");
+            command.arg("5");
+            let started = Instant::now();
+            assert!(matches!(
+                run_with_timeout(command, Duration::from_millis(100)),
+                RunResult::TimedOut
+            ));
+            assert!(started.elapsed() < Duration::from_secs(4));
+        }
 
-    #[test]
-    fn analyze_projects_does_not_panic() {
-        // Ensure the placeholder function can be called without issues
-        analyze_projects();
+        #[test]
+        fn returns_output_of_finished_commands() {
+            let mut command = Command::new("echo");
+            command.arg("ready");
+            match run_with_timeout(command, Duration::from_secs(5)) {
+                RunResult::Finished(output) => assert_eq!(output.stdout, b"ready\n"),
+                _ => panic!("echo should finish"),
+            }
+            let missing = Command::new("devhealth-definitely-missing-tool");
+            assert!(matches!(
+                run_with_timeout(missing, Duration::from_secs(1)),
+                RunResult::NotFound
+            ));
+        }
     }
 }
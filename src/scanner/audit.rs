@@ -0,0 +1,1438 @@
+//! Dependency vulnerability audit
+//!
+//! This module checks the dependencies discovered by [`crate::scanner::deps`]
+//! against a local advisory database stored in the [OSV](https://osv.dev)
+//! format. The database can be:
+//!
+//! - refreshed from the OSV API when network access is allowed
+//! - exported to a directory on a connected machine
+//! - imported from that directory on an airgapped machine
+//!
+//! so that `devhealth scan --audit` keeps working without internet access.
+//!
+//! Each dependency is checked at the version its lockfile (`Cargo.lock`,
+//! `package-lock.json` or `poetry.lock`) installs. Without a lockfile entry
+//! the audit falls back to the lowest version the declared constraint admits
+//! (e.g. `^1.2` is checked as `1.2.0`). Constraints without a concrete
+//! version (`*`) are counted as unresolved rather than guessed.
+//!
+//! The `[audit]` section of `devhealth.toml` ([`AuditConfig`]) leaves
+//! accepted vulnerabilities out of the results: by advisory ID or alias, by
+//! package in any ecosystem, or every vulnerability in a development
+//! dependency. Each ignore may expire on a date, after which the
+//! vulnerability is reported again.
+
+use crate::acks;
+use crate::i18n::t;
+use crate::scanner::deps::{Dependency, DependencyReport, DependencyType, Ecosystem};
+use crate::scanner::exposure::{normalize_package_name, Resolver};
+use crate::scanner::pep440;
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::net;
+use crate::utils::paths;
+use crate::utils::redact;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+/// File name of the advisory database inside a database directory
+pub const DATABASE_FILE: &str = "advisories.json";
+
+/// Current on-disk format version of the advisory database
+pub const DATABASE_FORMAT_VERSION: u32 = 1;
+
+/// Base URL of the OSV API
+const OSV_API_URL: &str = "https://api.osv.dev/v1";
+
+/// Errors that can occur while auditing dependencies or managing the database
+#[derive(Error, Debug)]
+pub enum AuditError {
+    #[error("Failed to access advisory database: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse advisory data: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error("Advisory request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("No advisory database found at {0} (run `devhealth advisory-db sync`, or `--from <dir>` when offline)")]
+    MissingDatabase(PathBuf),
+    #[error("Unsupported advisory database format version {0}")]
+    UnsupportedFormat(u32),
+    #[error("Could not determine the DevHealth state directory")]
+    NoStateDirectory,
+}
+
+/// A set of advisories together with the time they were fetched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvisoryDatabase {
+    /// On-disk format version, see [`DATABASE_FORMAT_VERSION`]
+    pub format_version: u32,
+    /// When the advisories were fetched, in seconds since the Unix epoch
+    pub synced_at: u64,
+    /// The advisories, in OSV format
+    pub advisories: Vec<Advisory>,
+}
+
+/// A single security advisory (subset of the OSV schema)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Advisory {
+    /// Advisory identifier (e.g. `RUSTSEC-2020-0071`, `GHSA-...`)
+    pub id: String,
+    /// One-line description of the issue
+    #[serde(default)]
+    pub summary: String,
+    /// Other identifiers for the same issue (e.g. CVE numbers)
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Last modification timestamp as reported by OSV
+    #[serde(default)]
+    pub modified: String,
+    /// Packages and versions affected by the advisory
+    #[serde(default)]
+    pub affected: Vec<Affected>,
+}
+
+/// A package affected by an advisory and the affected versions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Affected {
+    /// The affected package
+    pub package: AffectedPackage,
+    /// Affected version ranges
+    #[serde(default)]
+    pub ranges: Vec<VersionRange>,
+    /// Explicitly enumerated affected versions
+    #[serde(default)]
+    pub versions: Vec<String>,
+}
+
+/// Package identity in OSV terms
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AffectedPackage {
+    /// OSV ecosystem name (`crates.io`, `npm`, `PyPI`, `Go`)
+    pub ecosystem: String,
+    /// Package name within the ecosystem
+    pub name: String,
+}
+
+/// A range of affected versions described by ordered events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionRange {
+    /// Range type (`SEMVER`, `ECOSYSTEM`, or `GIT`)
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Events marking where the range starts and ends
+    #[serde(default)]
+    pub events: Vec<RangeEvent>,
+}
+
+/// A boundary inside a [`VersionRange`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RangeEvent {
+    /// First affected version (`0` means "all versions before the next event")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introduced: Option<String>,
+    /// First version containing the fix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed: Option<String>,
+    /// Last affected version when no fix exists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_affected: Option<String>,
+}
+
+/// A dependency matched by an advisory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vulnerability {
+    /// Identifier of the matching advisory
+    pub advisory_id: String,
+    /// Summary of the advisory
+    pub summary: String,
+    /// Aliases of the advisory (e.g. CVE numbers)
+    pub aliases: Vec<String>,
+    /// The affected dependency
+    pub dependency: Dependency,
+    /// The concrete version that was checked
+    pub version: String,
+    /// First version containing a fix, if known
+    pub fixed_version: Option<String>,
+}
+
+/// Result of auditing a set of dependency reports
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditReport {
+    /// Dependencies matched by at least one advisory
+    pub vulnerabilities: Vec<Vulnerability>,
+    /// Number of dependencies whose version could be checked
+    pub checked: usize,
+    /// Number of dependencies without a concrete version to check
+    pub unresolved: usize,
+    /// When the database used for the audit was synced
+    pub database_synced_at: Option<u64>,
+    /// Vulnerabilities left out by the `[audit]` configuration
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignored: Vec<Vulnerability>,
+}
+
+impl AuditReport {
+    /// Moves the vulnerabilities `config` ignores at `now` from
+    /// [`AuditReport::vulnerabilities`] to [`AuditReport::ignored`]
+    pub fn apply_ignores(&mut self, config: &AuditConfig, now: u64) {
+        let (ignored, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.vulnerabilities)
+            .into_iter()
+            .partition(|vulnerability| config.ignores(vulnerability, now));
+        self.vulnerabilities = kept;
+        self.ignored.extend(ignored);
+    }
+}
+
+/// The `[audit]` section of `devhealth.toml`
+///
+/// ```toml
+/// [audit]
+/// ignore_dev = true
+///
+/// [[audit.ignore]]
+/// advisory = "RUSTSEC-2023-0071"
+/// until = 2026-06-30
+/// reason = "No fixed rsa release yet"
+///
+/// [[audit.ignore]]
+/// package = "lodash"
+/// advisory = "CVE-2021-23337"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    /// Advisories and packages left out of the results
+    pub ignore: Vec<AuditIgnore>,
+    /// Leave out every vulnerability in a development dependency
+    pub ignore_dev: bool,
+}
+
+impl AuditConfig {
+    /// Returns `true` if `vulnerability` is ignored at `now`
+    pub fn ignores(&self, vulnerability: &Vulnerability, now: u64) -> bool {
+        (self.ignore_dev && vulnerability.dependency.dependency_type == DependencyType::Development)
+            || self
+                .ignore
+                .iter()
+                .any(|rule| rule.covers(vulnerability, now))
+    }
+}
+
+/// An advisory, a package, or an advisory in one package, left out of the
+/// audit results
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawAuditIgnore")]
+pub struct AuditIgnore {
+    /// Advisory ID or alias, e.g. a CVE number
+    pub advisory: Option<String>,
+    /// Package name, in any ecosystem
+    pub package: Option<String>,
+    /// When the ignore expires, as a Unix timestamp; `None` means never
+    #[serde(serialize_with = "serialize_until")]
+    pub until: Option<u64>,
+    /// Why the vulnerability is accepted
+    pub reason: Option<String>,
+}
+
+impl AuditIgnore {
+    /// Returns `true` if the ignore is active at `now` and matches
+    /// `vulnerability`
+    ///
+    /// Advisories match by ID or alias and packages by name, both
+    /// case-insensitively; package names treat `-`, `_` and `.` alike.
+    pub fn covers(&self, vulnerability: &Vulnerability, now: u64) -> bool {
+        let advisory = |advisory: &String| {
+            std::iter::once(&vulnerability.advisory_id)
+                .chain(&vulnerability.aliases)
+                .any(|id| id.eq_ignore_ascii_case(advisory))
+        };
+        let package = |package: &String| {
+            normalize_package_name(package)
+                == normalize_package_name(&vulnerability.dependency.name)
+        };
+        self.until.is_none_or(|until| now < until)
+            && self.advisory.as_ref().is_none_or(advisory)
+            && self.package.as_ref().is_none_or(package)
+    }
+}
+
+/// An `[[audit.ignore]]` entry as written, before its date is parsed
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawAuditIgnore {
+    advisory: Option<String>,
+    package: Option<String>,
+    until: Option<toml::Value>,
+    reason: Option<String>,
+}
+
+/// Writes `until` back as the `YYYY-MM-DD` date it was given as
+fn serialize_until<S: serde::Serializer>(
+    until: &Option<u64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    until.map(acks::format_date).serialize(serializer)
+}
+
+impl TryFrom<RawAuditIgnore> for AuditIgnore {
+    type Error = String;
+
+    fn try_from(raw: RawAuditIgnore) -> Result<Self, Self::Error> {
+        if raw.advisory.is_none() && raw.package.is_none() {
+            return Err("an audit ignore needs an `advisory`, a `package` or both".to_string());
+        }
+        let until = match raw.until {
+            None => None,
+            // Both `until = 2026-06-30` and `until = "2026-06-30"`
+            Some(toml::Value::Datetime(date)) => Some(acks::parse_date(&date.to_string())),
+            Some(toml::Value::String(date)) => Some(acks::parse_date(&date)),
+            Some(other) => return Err(format!("invalid `until` {}: expected YYYY-MM-DD", other)),
+        };
+        Ok(AuditIgnore {
+            advisory: raw.advisory,
+            package: raw.package,
+            until: until.transpose().map_err(|e| e.to_string())?,
+            reason: raw.reason,
+        })
+    }
+}
+
+impl AdvisoryDatabase {
+    /// Creates a database from freshly fetched advisories
+    pub fn new(advisories: Vec<Advisory>) -> Self {
+        AdvisoryDatabase {
+            format_version: DATABASE_FORMAT_VERSION,
+            synced_at: display::unix_now(),
+            advisories,
+        }
+    }
+
+    /// Loads the database stored in `dir`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AuditError::MissingDatabase`] if `dir` holds no database,
+    /// or an error if the file cannot be read, parsed, or uses an
+    /// unsupported format version.
+    pub fn load(dir: &Path) -> Result<Self, AuditError> {
+        let file = dir.join(DATABASE_FILE);
+        if !file.exists() {
+            return Err(AuditError::MissingDatabase(dir.to_path_buf()));
+        }
+
+        let database: AdvisoryDatabase = serde_json::from_str(&fs::read_to_string(file)?)?;
+        if database.format_version != DATABASE_FORMAT_VERSION {
+            return Err(AuditError::UnsupportedFormat(database.format_version));
+        }
+
+        Ok(database)
+    }
+
+    /// Writes the database to `dir`, creating the directory if needed
+    pub fn save(&self, dir: &Path) -> Result<(), AuditError> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(DATABASE_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Merges `other` into this database
+    ///
+    /// Advisories from `other` replace ones with the same ID, and the sync
+    /// time becomes the more recent of the two.
+    pub fn merge(&mut self, other: AdvisoryDatabase) {
+        let mut by_id: HashMap<String, Advisory> = self
+            .advisories
+            .drain(..)
+            .map(|a| (a.id.clone(), a))
+            .collect();
+        for advisory in other.advisories {
+            by_id.insert(advisory.id.clone(), advisory);
+        }
+
+        let mut advisories: Vec<Advisory> = by_id.into_values().collect();
+        advisories.sort_by(|a, b| a.id.cmp(&b.id));
+
+        self.advisories = advisories;
+        self.synced_at = self.synced_at.max(other.synced_at);
+    }
+}
+
+impl Affected {
+    /// Returns `true` if this entry refers to the given package
+    fn matches_package(&self, ecosystem: &str, name: &str) -> bool {
+        self.package.ecosystem == ecosystem
+            && normalize_name(ecosystem, &self.package.name) == normalize_name(ecosystem, name)
+    }
+
+    /// Returns `true` if `version` is affected
+    fn contains(&self, version: &str) -> bool {
+        let ecosystem = self.package.ecosystem.as_str();
+        let listed = |v: &String| {
+            v == version
+                || (ecosystem == "PyPI"
+                    && compare_versions(ecosystem, v, version) == Some(Ordering::Equal))
+        };
+        if self.versions.iter().any(listed) {
+            return true;
+        }
+
+        self.ranges
+            .iter()
+            .any(|range| range.contains(ecosystem, version))
+    }
+
+    /// Returns the first fixed version above `version`, if any
+    fn fixed_version_for(&self, version: &str) -> Option<String> {
+        let ecosystem = self.package.ecosystem.as_str();
+        self.ranges
+            .iter()
+            .flat_map(|range| &range.events)
+            .filter_map(|event| event.fixed.as_deref())
+            .find(|fixed| compare_versions(ecosystem, fixed, version) == Some(Ordering::Greater))
+            .map(str::to_string)
+    }
+}
+
+impl VersionRange {
+    /// Returns `true` if `version` falls inside this range
+    ///
+    /// Events are evaluated in order, as OSV lists them sorted. `GIT` ranges
+    /// refer to commits rather than versions and never match. Versions are
+    /// compared with [`compare_versions`] for the package's `ecosystem`.
+    fn contains(&self, ecosystem: &str, version: &str) -> bool {
+        if self.kind == "GIT" || compare_versions(ecosystem, version, version).is_none() {
+            return false;
+        }
+
+        let mut affected = false;
+        for event in &self.events {
+            if let Some(introduced) = &event.introduced {
+                if introduced == "0"
+                    || compare_versions(ecosystem, version, introduced).is_some_and(Ordering::is_ge)
+                {
+                    affected = true;
+                }
+            }
+            if let Some(fixed) = &event.fixed {
+                if compare_versions(ecosystem, version, fixed).is_some_and(Ordering::is_ge) {
+                    affected = false;
+                }
+            }
+            if let Some(last) = &event.last_affected {
+                if compare_versions(ecosystem, version, last).is_some_and(Ordering::is_gt) {
+                    affected = false;
+                }
+            }
+        }
+
+        affected
+    }
+}
+
+/// Returns the directory of the local advisory database mirror
+///
+/// # Errors
+///
+/// Returns [`AuditError::NoStateDirectory`] if no home directory is known.
+pub fn default_database_dir() -> Result<PathBuf, AuditError> {
+    paths::data_dir()
+        .map(|dir| dir.join("advisory-db"))
+        .ok_or(AuditError::NoStateDirectory)
+}
+
+/// Returns `true` if a local advisory database exists, so an offline audit
+/// has something to check against
+pub fn has_local_database() -> bool {
+    default_database_dir().is_ok_and(|dir| dir.join(DATABASE_FILE).is_file())
+}
+
+/// Returns the OSV ecosystem name for a dependency ecosystem
+///
+/// Build systems have no OSV ecosystem of their own and return `None`; their
+/// dependencies are not audited.
+pub fn osv_ecosystem(ecosystem: &Ecosystem) -> Option<&'static str> {
+    match ecosystem {
+        Ecosystem::Rust => Some("crates.io"),
+        Ecosystem::NodeJs => Some("npm"),
+        Ecosystem::Python => Some("PyPI"),
+        Ecosystem::Go => Some("Go"),
+        Ecosystem::Bazel | Ecosystem::Buck | Ecosystem::Nix => None,
+    }
+}
+
+/// Checks every dependency in `reports` against `database`
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::{audit, deps};
+/// use std::path::Path;
+///
+/// let reports = deps::scan_dependencies(Path::new(".")).unwrap();
+/// let database = audit::AdvisoryDatabase::new(Vec::new());
+/// let report = audit::audit_dependencies(&reports, &database);
+/// assert!(report.vulnerabilities.is_empty());
+/// ```
+pub fn audit_dependencies(
+    reports: &[DependencyReport],
+    database: &AdvisoryDatabase,
+) -> AuditReport {
+    // Index advisories by package so each dependency only checks its own
+    let mut index: HashMap<(String, String), Vec<(&Advisory, &Affected)>> = HashMap::new();
+    for advisory in &database.advisories {
+        for affected in &advisory.affected {
+            let ecosystem = affected.package.ecosystem.clone();
+            let name = normalize_name(&ecosystem, &affected.package.name);
+            index
+                .entry((ecosystem, name))
+                .or_default()
+                .push((advisory, affected));
+        }
+    }
+
+    let mut report = AuditReport {
+        database_synced_at: Some(database.synced_at),
+        ..AuditReport::default()
+    };
+
+    let mut resolver = Resolver::default();
+    for dependency in reports.iter().flat_map(|r| &r.dependencies) {
+        let Some(ecosystem) = osv_ecosystem(&dependency.ecosystem) else {
+            continue;
+        };
+        let resolved = resolver.locked(dependency).or_else(|| {
+            if dependency.ecosystem == Ecosystem::Python {
+                resolve_python_version(&dependency.version)
+            } else {
+                resolve_version(&dependency.version)
+            }
+        });
+        let Some(version) = resolved else {
+            report.unresolved += 1;
+            continue;
+        };
+        report.checked += 1;
+
+        let key = (
+            ecosystem.to_string(),
+            normalize_name(ecosystem, &dependency.name),
+        );
+        let Some(candidates) = index.get(&key) else {
+            continue;
+        };
+
+        let mut reported = HashSet::new();
+        for (advisory, affected) in candidates {
+            if affected.matches_package(ecosystem, &dependency.name)
+                && affected.contains(&version)
+                && reported.insert(advisory.id.as_str())
+            {
+                report.vulnerabilities.push(Vulnerability {
+                    advisory_id: advisory.id.clone(),
+                    summary: advisory.summary.clone(),
+                    aliases: advisory.aliases.clone(),
+                    dependency: dependency.clone(),
+                    version: version.clone(),
+                    fixed_version: affected.fixed_version_for(&version),
+                });
+            }
+        }
+    }
+
+    report
+}
+
+/// A package queried through the OSV `querybatch` endpoint
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct OsvPackage<'a> {
+    name: &'a str,
+    ecosystem: &'a str,
+}
+
+/// One query of a `querybatch` request, continuing from `page_token` when
+/// a previous response was cut short
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct OsvQuery<'a> {
+    package: OsvPackage<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_token: Option<String>,
+}
+
+#[derive(Serialize)]
+struct OsvBatchRequest<'a> {
+    queries: &'a [OsvQuery<'a>],
+}
+
+#[derive(Deserialize)]
+struct OsvBatchResponse {
+    #[serde(default)]
+    results: Vec<OsvBatchResult>,
+}
+
+#[derive(Deserialize)]
+struct OsvBatchResult {
+    #[serde(default)]
+    vulns: Vec<OsvVulnRef>,
+    /// Set when OSV has more advisories for the package than it returned
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OsvVulnRef {
+    id: String,
+}
+
+/// Fetches advisories for every dependency in `reports` from the OSV API
+///
+/// Packages are queried through the OSV `querybatch` endpoint in batches of
+/// [`net::OSV_BATCH_LIMIT`], then each referenced advisory is downloaded.
+/// All requests go through a [`net::RateLimiter`].
+///
+/// # Errors
+///
+/// Returns an error if a request fails or a response cannot be parsed.
+pub fn fetch_advisories(reports: &[DependencyReport]) -> Result<AdvisoryDatabase, AuditError> {
+    // Unique packages in a stable order
+    let mut packages: Vec<(&'static str, &str)> = reports
+        .iter()
+        .flat_map(|r| &r.dependencies)
+        .filter_map(|d| Some((osv_ecosystem(&d.ecosystem)?, d.name.as_str())))
+        .collect();
+    packages.sort();
+    packages.dedup();
+
+    let queries: Vec<OsvQuery> = packages
+        .iter()
+        .map(|(ecosystem, name)| OsvQuery {
+            package: OsvPackage { name, ecosystem },
+            page_token: None,
+        })
+        .collect();
+
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(concat!("devhealth/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let mut limiter = net::RateLimiter::default();
+
+    let advisory_ids = query_advisory_ids(queries, |batch| {
+        limiter.wait();
+        Ok(client
+            .post(format!("{}/querybatch", OSV_API_URL))
+            .json(&OsvBatchRequest { queries: batch })
+            .send()?
+            .error_for_status()?
+            .json()?)
+    })?;
+
+    eprintln!(
+        "  {}",
+        tr!(
+            "advisory-fetching-details",
+            advisories = advisory_ids.len(),
+            packages = packages.len()
+        )
+    );
+
+    let mut advisories = Vec::with_capacity(advisory_ids.len());
+    for id in advisory_ids {
+        limiter.wait();
+        let advisory: Advisory = client
+            .get(format!("{}/vulns/{}", OSV_API_URL, id))
+            .send()?
+            .error_for_status()?
+            .json()?;
+        advisories.push(advisory);
+    }
+
+    Ok(AdvisoryDatabase::new(advisories))
+}
+
+/// Runs `queries` through `send` in batches of [`net::OSV_BATCH_LIMIT`] and
+/// returns the sorted, unique advisory IDs
+///
+/// OSV cuts a package's result short when it has many advisories and
+/// returns a `next_page_token` instead; such packages are queried again
+/// with the token until every page was read.
+fn query_advisory_ids<'a>(
+    mut queries: Vec<OsvQuery<'a>>,
+    mut send: impl FnMut(&[OsvQuery<'a>]) -> Result<OsvBatchResponse, AuditError>,
+) -> Result<Vec<String>, AuditError> {
+    let mut advisory_ids = Vec::new();
+    while !queries.is_empty() {
+        let mut next_pages = Vec::new();
+        for batch in net::batches(&queries, net::OSV_BATCH_LIMIT) {
+            let response = send(batch)?;
+            for (query, result) in batch.iter().zip(response.results) {
+                advisory_ids.extend(result.vulns.into_iter().map(|v| v.id));
+                if let Some(token) = result.next_page_token.filter(|token| !token.is_empty()) {
+                    next_pages.push(OsvQuery {
+                        package: query.package.clone(),
+                        page_token: Some(token),
+                    });
+                }
+            }
+        }
+        queries = next_pages;
+    }
+    advisory_ids.sort();
+    advisory_ids.dedup();
+    Ok(advisory_ids)
+}
+
+/// Imports a database exported with `advisory-db sync --to` into `mirror_dir`
+///
+/// The imported advisories are merged into any existing mirror.
+///
+/// # Errors
+///
+/// Returns an error if `from_dir` holds no valid database or the mirror
+/// cannot be written.
+pub fn import_database(from_dir: &Path, mirror_dir: &Path) -> Result<AdvisoryDatabase, AuditError> {
+    let imported = AdvisoryDatabase::load(from_dir)?;
+    let mut mirror = match AdvisoryDatabase::load(mirror_dir) {
+        Ok(existing) => existing,
+        Err(AuditError::MissingDatabase(_)) => AdvisoryDatabase {
+            synced_at: 0,
+            ..AdvisoryDatabase::new(Vec::new())
+        },
+        Err(e) => return Err(e),
+    };
+
+    mirror.merge(imported);
+    mirror.save(mirror_dir)?;
+    Ok(mirror)
+}
+
+/// Audits `reports` using the local advisory mirror
+///
+/// When `network` allows it, the mirror is first refreshed from OSV for the
+/// scanned packages. A failed refresh is reported and the audit falls back to
+/// the existing mirror; in offline mode the mirror is used as-is.
+///
+/// # Errors
+///
+/// Returns an error if no usable local database exists.
+pub fn run_audit(
+    reports: &[DependencyReport],
+    network: &mut net::NetworkGate,
+) -> Result<AuditReport, AuditError> {
+    let mirror_dir = default_database_dir()?;
+
+    if network.allow("live advisory sync (OSV)") {
+        match fetch_advisories(reports) {
+            Ok(fresh) => {
+                let mut database = AdvisoryDatabase::load(&mirror_dir)
+                    .unwrap_or_else(|_| AdvisoryDatabase::new(Vec::new()));
+                database.merge(fresh);
+                database.save(&mirror_dir)?;
+            }
+            Err(e) => eprintln!(
+                "⚠️  {}",
+                tr!("audit-refresh-failed", error = redact::text(&e.to_string()))
+            ),
+        }
+    }
+
+    let database = AdvisoryDatabase::load(&mirror_dir)?;
+    Ok(audit_dependencies(reports, &database))
+}
+
+/// Displays audit results in a formatted output
+///
+/// # Arguments
+///
+/// * `report` - The audit report to display
+pub fn display_results(report: &AuditReport) {
+    display_results_with(report, Detail::Normal);
+}
+
+/// Displays audit results at the given level of detail
+///
+/// [`Detail::Summary`] prints only the summary box, and [`Detail::Minimal`]
+/// lists vulnerabilities without their summaries and source files.
+///
+/// # Arguments
+///
+/// * `report` - The audit report to display
+/// * `detail` - How much of the report to print
+pub fn display_results_with(report: &AuditReport, detail: Detail) {
+    let vulnerable = report.vulnerabilities.len();
+    let (runtime, development): (Vec<&Vulnerability>, Vec<&Vulnerability>) = report
+        .vulnerabilities
+        .iter()
+        .partition(|vuln| vuln.dependency.dependency_type.is_runtime());
+    let (emoji, color) = if vulnerable == 0 {
+        ("🛡️", Role::Success)
+    } else if runtime.is_empty() {
+        ("⚠️", Role::Warning)
+    } else {
+        ("🚨", Role::Error)
+    };
+
+    println!(
+        "{}",
+        display::header(&tr!("audit-title", count = vulnerable), emoji, color)
+    );
+
+    let database_age = match report.database_synced_at {
+        Some(synced_at) => tr!(
+            "audit-synced",
+            age = display::age(synced_at, display::unix_now())
+        ),
+        None => tr!("audit-unavailable"),
+    };
+    let count = |count: usize, mark: &str| {
+        if count > 0 {
+            format!("{} {}", count, mark)
+        } else {
+            "0".to_string()
+        }
+    };
+
+    let mut summary_items = vec![
+        (t("audit-checked"), report.checked.to_string()),
+        (t("audit-vulnerable-runtime"), count(runtime.len(), "❌")),
+        (
+            t("audit-vulnerable-development"),
+            count(development.len(), "⚠️"),
+        ),
+        (t("audit-unresolved"), report.unresolved.to_string()),
+        (t("audit-database"), database_age),
+    ];
+    if !report.ignored.is_empty() {
+        summary_items.push((t("audit-ignored"), report.ignored.len().to_string()));
+    }
+    print!("{}", display::summary_box(&summary_items));
+
+    if detail == Detail::Summary {
+        return;
+    }
+    if !runtime.is_empty() {
+        println!("{}", display::section_divider(t("audit-details-runtime")));
+        display_vulnerabilities(&runtime, Role::Error, detail);
+    }
+    if !development.is_empty() {
+        println!(
+            "{}",
+            display::section_divider(t("audit-details-development"))
+        );
+        display_vulnerabilities(&development, Role::Warning, detail);
+    }
+}
+
+/// Lists vulnerabilities with their fix and, unless `detail` is
+/// [`Detail::Minimal`], their summary and manifest
+fn display_vulnerabilities(vulnerabilities: &[&Vulnerability], role: Role, detail: Detail) {
+    for (index, vuln) in vulnerabilities.iter().enumerate() {
+        let is_last = index == vulnerabilities.len() - 1;
+        let fix = match &vuln.fixed_version {
+            Some(fixed) => format!("→ {}", tr!("audit-fixed-in", version = fixed))
+                .paint(Role::Success)
+                .to_string(),
+            None => t("audit-no-fix").paint(Role::Error).to_string(),
+        };
+
+        let mut content = format!(
+            "{} {} {} {}",
+            "✗".paint(role).bold(),
+            vuln.advisory_id.paint(role).bold(),
+            display::version_display(&vuln.dependency.name, &vuln.version, None),
+            fix
+        );
+        if detail >= Detail::Normal {
+            let remaining = display::terminal_width().saturating_sub(
+                display::tree_prefix_width(0) + display::display_width(&content) + 1,
+            );
+            let summary = if detail == Detail::Full {
+                vuln.summary.clone()
+            } else {
+                display::truncate(&vuln.summary, remaining)
+            };
+            content = format!("{} {}", content, summary.paint(Role::Emphasis));
+        }
+        println!("{}", display::tree_item(&content, is_last, 0));
+        if detail >= Detail::Normal {
+            let path = vuln.dependency.source_file.to_string_lossy();
+            let width = display::terminal_width().saturating_sub(display::tree_prefix_width(0));
+            for line in display::wrap_path(&path, width) {
+                println!(
+                    "{}",
+                    display::tree_continuation(&display::file_path(&line), is_last, 0)
+                );
+            }
+        }
+    }
+}
+
+/// Extracts the lowest concrete version admitted by a version constraint
+///
+/// Returns `None` for wildcards, strict lower bounds such as `>1.2` (an
+/// excluded Go module version), upper bounds such as `<2.0` and exclusions
+/// such as `!=1.4.2`, which name a version the project cannot install, and
+/// constraints without a version number.
+fn resolve_version(constraint: &str) -> Option<String> {
+    let first = constraint
+        .split([',', ' ', '|'])
+        .find(|part| !part.is_empty())?;
+    // A strict lower bound, an upper bound or an exclusion rules out the
+    // version it names
+    if (first.starts_with('>') && !first.starts_with(">=")) || first.starts_with(['<', '!']) {
+        return None;
+    }
+    let version = first.trim_start_matches(['^', '~', '=', '>', 'v']);
+
+    if version.starts_with(|c: char| c.is_ascii_digit()) && !version.contains('*') {
+        Some(version.to_string())
+    } else {
+        None
+    }
+}
+
+/// Extracts the lowest version a Python requirement admits
+///
+/// Returns `None` for requirements without a lower bound, such as `<3`, and
+/// for specifiers that are not valid PEP 440.
+fn resolve_python_version(constraint: &str) -> Option<String> {
+    let specifiers: pep440::SpecifierSet = constraint.parse().ok()?;
+    specifiers
+        .lowest_version()
+        .map(|version| version.to_string())
+}
+
+/// Compares two versions of a package in an OSV ecosystem
+///
+/// PyPI versions follow PEP 440; versions of other ecosystems are compared
+/// as semver with missing minor and patch components padded. Returns `None`
+/// if either version cannot be parsed.
+fn compare_versions(ecosystem: &str, a: &str, b: &str) -> Option<Ordering> {
+    if ecosystem == "PyPI" {
+        let a: pep440::Version = a.parse().ok()?;
+        let b: pep440::Version = b.parse().ok()?;
+        Some(a.cmp(&b))
+    } else {
+        Some(parse_loose_version(a)?.cmp(&parse_loose_version(b)?))
+    }
+}
+
+/// Parses a version leniently, padding missing minor/patch components
+pub(crate) fn parse_loose_version(version: &str) -> Option<semver::Version> {
+    let version = version.trim().trim_start_matches('v');
+    let split_at = version.find(['-', '+']).unwrap_or(version.len());
+    let (core, suffix) = version.split_at(split_at);
+
+    let mut padded = core.to_string();
+    for _ in core.split('.').count()..3 {
+        padded.push_str(".0");
+    }
+    padded.push_str(suffix);
+
+    semver::Version::parse(&padded).ok()
+}
+
+/// Normalizes a package name for comparison within an ecosystem
+fn normalize_name(ecosystem: &str, name: &str) -> String {
+    if ecosystem == "PyPI" {
+        name.to_lowercase().replace(['_', '.'], "-")
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::deps::DependencyType;
+    use tempfile::TempDir;
+
+    fn advisory(id: &str, ecosystem: &str, name: &str, introduced: &str, fixed: &str) -> Advisory {
+        Advisory {
+            id: id.to_string(),
+            summary: format!("Issue in {}", name),
+            aliases: vec![],
+            modified: String::new(),
+            affected: vec![Affected {
+                package: AffectedPackage {
+                    ecosystem: ecosystem.to_string(),
+                    name: name.to_string(),
+                },
+                ranges: vec![VersionRange {
+                    kind: "SEMVER".to_string(),
+                    events: vec![
+                        RangeEvent {
+                            introduced: Some(introduced.to_string()),
+                            ..RangeEvent::default()
+                        },
+                        RangeEvent {
+                            fixed: Some(fixed.to_string()),
+                            ..RangeEvent::default()
+                        },
+                    ],
+                }],
+                versions: vec![],
+            }],
+        }
+    }
+
+    fn report_with(deps: &[(&str, &str, Ecosystem)]) -> DependencyReport {
+        DependencyReport {
+            project_path: PathBuf::from("/test/project"),
+            dependencies: deps
+                .iter()
+                .map(|(name, version, ecosystem)| Dependency {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    dependency_type: DependencyType::Runtime,
+                    ecosystem: ecosystem.clone(),
+                    source_file: PathBuf::from("/test/project/manifest"),
+                    extras: Vec::new(),
+                    marker: None,
+                })
+                .collect(),
+            ecosystems: vec![],
+            errors: vec![],
+            lockfiles: Vec::new(),
+            go_modules: Vec::new(),
+        }
+    }
+
+    mod version_handling {
+        use super::*;
+
+        #[test]
+        fn resolves_lowest_version_of_constraints() {
+            assert_eq!(resolve_version("^4.18.0").as_deref(), Some("4.18.0"));
+            assert_eq!(resolve_version("1.0").as_deref(), Some("1.0"));
+            assert_eq!(resolve_version(">=2.28,<3").as_deref(), Some("2.28"));
+            assert_eq!(resolve_version("v1.9.1").as_deref(), Some("1.9.1"));
+        }
+
+        #[test]
+        fn leaves_wildcards_unresolved() {
+            assert_eq!(resolve_version("*"), None);
+            assert_eq!(resolve_version(">v0.9.0"), None);
+            assert_eq!(resolve_version("<2.0"), None);
+            assert_eq!(resolve_version("<=2.0"), None);
+            assert_eq!(resolve_version("!=1.4.2"), None);
+            assert_eq!(resolve_version("1.*"), None);
+            assert_eq!(resolve_version("latest"), None);
+            assert_eq!(resolve_version(""), None);
+        }
+
+        #[test]
+        fn parses_partial_versions() {
+            assert_eq!(
+                parse_loose_version("1"),
+                Some(semver::Version::new(1, 0, 0))
+            );
+            assert_eq!(
+                parse_loose_version("1.2"),
+                Some(semver::Version::new(1, 2, 0))
+            );
+            assert!(parse_loose_version("1.2.3-beta.1").is_some());
+            assert_eq!(parse_loose_version("not-a-version"), None);
+        }
+    }
+
+    mod range_matching {
+        use super::*;
+
+        #[test]
+        fn matches_versions_between_introduced_and_fixed() {
+            let adv = advisory("TEST-1", "crates.io", "time", "0.1.0", "0.2.23");
+            let affected = &adv.affected[0];
+
+            assert!(affected.contains("0.1.5"));
+            assert!(affected.contains("0.2.22"));
+            assert!(
+                !affected.contains("0.2.23"),
+                "Fixed version is not affected"
+            );
+            assert!(
+                !affected.contains("0.0.9"),
+                "Versions before introduction are not affected"
+            );
+        }
+
+        #[test]
+        fn zero_introduced_affects_everything_before_fix() {
+            let adv = advisory("TEST-2", "npm", "lodash", "0", "4.17.21");
+            assert!(adv.affected[0].contains("1.0.0"));
+            assert!(!adv.affected[0].contains("4.17.21"));
+        }
+
+        #[test]
+        fn reports_first_fixed_version() {
+            let adv = advisory("TEST-3", "npm", "lodash", "0", "4.17.21");
+            assert_eq!(
+                adv.affected[0].fixed_version_for("4.17.0").as_deref(),
+                Some("4.17.21")
+            );
+        }
+
+        #[test]
+        fn ignores_git_ranges() {
+            let mut adv = advisory("TEST-4", "Go", "example.com/mod", "0", "1.0.0");
+            adv.affected[0].ranges[0].kind = "GIT".to_string();
+            assert!(!adv.affected[0].contains("0.5.0"));
+        }
+    }
+
+    mod auditing {
+        use super::*;
+
+        #[test]
+        fn reports_vulnerable_dependencies() {
+            let database = AdvisoryDatabase::new(vec![
+                advisory("RUSTSEC-0000-0001", "crates.io", "time", "0.1.0", "0.2.23"),
+                advisory("GHSA-xxxx", "npm", "lodash", "0", "4.17.21"),
+            ]);
+            let reports = [report_with(&[
+                ("time", "0.1", Ecosystem::Rust),
+                ("serde", "1.0", Ecosystem::Rust),
+                ("lodash", "^4.17.21", Ecosystem::NodeJs),
+            ])];
+
+            let result = audit_dependencies(&reports, &database);
+
+            assert_eq!(result.checked, 3);
+            assert_eq!(result.vulnerabilities.len(), 1);
+            assert_eq!(result.vulnerabilities[0].advisory_id, "RUSTSEC-0000-0001");
+            assert_eq!(
+                result.vulnerabilities[0].fixed_version.as_deref(),
+                Some("0.2.23")
+            );
+        }
+
+        #[test]
+        fn checks_the_locked_version() {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let project = temp_dir.path();
+            fs::write(
+                project.join("Cargo.lock"),
+                "version = 3\n\n[[package]]\nname = \"time\"\nversion = \"0.1.45\"\n",
+            )
+            .unwrap();
+            fs::write(
+                project.join("package-lock.json"),
+                r#"{"packages": {"node_modules/minimist": {"version": "1.2.3"}}}"#,
+            )
+            .unwrap();
+            let database = AdvisoryDatabase::new(vec![
+                advisory("RUSTSEC-0000-0002", "crates.io", "time", "0", "0.1.44"),
+                advisory("GHSA-minimist", "npm", "minimist", "1.2.0", "1.2.6"),
+            ]);
+            let mut report = report_with(&[
+                ("time", "0.1", Ecosystem::Rust),
+                ("minimist", "^1.0.0", Ecosystem::NodeJs),
+            ]);
+            for dependency in &mut report.dependencies {
+                dependency.source_file = project.join("manifest");
+            }
+
+            let result = audit_dependencies(&[report], &database);
+
+            let found: Vec<_> = result
+                .vulnerabilities
+                .iter()
+                .map(|v| (v.advisory_id.as_str(), v.version.as_str()))
+                .collect();
+            assert_eq!(
+                found,
+                vec![("GHSA-minimist", "1.2.3")],
+                "0.1.45 is fixed, 1.2.3 is affected although ^1.0.0 starts below"
+            );
+        }
+
+        #[test]
+        fn normalizes_python_package_names() {
+            let database =
+                AdvisoryDatabase::new(vec![advisory("PYSEC-1", "PyPI", "Django", "0", "4.2.0")]);
+            let reports = [report_with(&[("django", "4.1.0", Ecosystem::Python)])];
+
+            let result = audit_dependencies(&reports, &database);
+            assert_eq!(result.vulnerabilities.len(), 1);
+        }
+
+        #[test]
+        fn compares_python_versions_with_pep440() {
+            let database = AdvisoryDatabase::new(vec![advisory(
+                "PYSEC-2",
+                "PyPI",
+                "urllib3",
+                "2.0.0rc1",
+                "2.0.0.post1",
+            )]);
+            let reports = [report_with(&[
+                ("urllib3", "==2.0.0", Ecosystem::Python),
+                ("urllib3", ">=2.0.0.post1,<3", Ecosystem::Python),
+                ("urllib3", "~=2.0rc2", Ecosystem::Python),
+                ("urllib3", ">=1.26,!=1.26", Ecosystem::Python),
+            ])];
+
+            let result = audit_dependencies(&reports, &database);
+            let versions: Vec<&str> = result
+                .vulnerabilities
+                .iter()
+                .map(|v| v.version.as_str())
+                .collect();
+            assert_eq!(versions, ["2.0.0", "2.0rc2"]);
+            assert_eq!(
+                result.vulnerabilities[0].fixed_version.as_deref(),
+                Some("2.0.0.post1")
+            );
+            assert_eq!(result.checked, 3);
+            assert_eq!(result.unresolved, 1);
+        }
+
+        #[test]
+        fn counts_unresolved_versions() {
+            let database = AdvisoryDatabase::new(vec![]);
+            let reports = [report_with(&[("anything", "*", Ecosystem::Rust)])];
+
+            let result = audit_dependencies(&reports, &database);
+            assert_eq!(result.checked, 0);
+            assert_eq!(result.unresolved, 1);
+        }
+    }
+
+    mod ignores {
+        use super::*;
+
+        fn audited() -> AuditReport {
+            let mut cve = advisory("GHSA-lodash", "npm", "lodash", "0", "4.17.21");
+            cve.aliases = vec!["CVE-2021-23337".to_string()];
+            let database = AdvisoryDatabase::new(vec![
+                cve,
+                advisory("RUSTSEC-0000-0001", "crates.io", "time", "0.1.0", "0.2.23"),
+                advisory("PYSEC-1", "PyPI", "Django", "0", "4.2.0"),
+            ]);
+            let mut report = report_with(&[
+                ("lodash", "4.17.0", Ecosystem::NodeJs),
+                ("time", "0.1", Ecosystem::Rust),
+                ("django", "4.1.0", Ecosystem::Python),
+            ]);
+            report.dependencies[2].dependency_type = DependencyType::Development;
+            audit_dependencies(&[report], &database)
+        }
+
+        fn ignore(
+            advisory: Option<&str>,
+            package: Option<&str>,
+            until: Option<u64>,
+        ) -> AuditIgnore {
+            AuditIgnore {
+                advisory: advisory.map(str::to_string),
+                package: package.map(str::to_string),
+                until,
+                reason: None,
+            }
+        }
+
+        fn remaining(report: &AuditReport) -> Vec<&str> {
+            report
+                .vulnerabilities
+                .iter()
+                .map(|v| v.advisory_id.as_str())
+                .collect()
+        }
+
+        #[test]
+        fn ignores_by_alias_package_and_dev_dependency() {
+            let config = AuditConfig {
+                ignore: vec![ignore(Some("cve-2021-23337"), None, None)],
+                ignore_dev: true,
+            };
+            let mut report = audited();
+            report.apply_ignores(&config, 0);
+            assert_eq!(remaining(&report), ["RUSTSEC-0000-0001"]);
+            assert_eq!(report.ignored.len(), 2);
+
+            let config = AuditConfig {
+                ignore: vec![
+                    ignore(None, Some("Time"), None),
+                    ignore(Some("GHSA-lodash"), Some("underscore"), None),
+                ],
+                ignore_dev: false,
+            };
+            let mut report = audited();
+            report.apply_ignores(&config, 0);
+            assert_eq!(remaining(&report), ["GHSA-lodash", "PYSEC-1"]);
+        }
+
+        #[test]
+        fn expired_ignores_no_longer_apply() {
+            let config = AuditConfig {
+                ignore: vec![ignore(None, Some("lodash"), Some(1_000))],
+                ignore_dev: false,
+            };
+            let mut report = audited();
+            report.apply_ignores(&config, 999);
+            assert_eq!(report.ignored.len(), 1);
+
+            let mut report = audited();
+            report.apply_ignores(&config, 1_000);
+            assert!(report.ignored.is_empty());
+            assert_eq!(report.vulnerabilities.len(), 3);
+        }
+    }
+
+    mod osv_queries {
+        use super::*;
+
+        #[test]
+        fn splits_batches_and_follows_page_tokens() {
+            let names: Vec<String> = (0..=net::OSV_BATCH_LIMIT)
+                .map(|i| format!("pkg{:04}", i))
+                .collect();
+            let queries = names
+                .iter()
+                .map(|name| OsvQuery {
+                    package: OsvPackage {
+                        name,
+                        ecosystem: "npm",
+                    },
+                    page_token: None,
+                })
+                .collect();
+
+            let mut sizes = Vec::new();
+            let ids = query_advisory_ids(queries, |batch| {
+                sizes.push(batch.len());
+                let results = batch
+                    .iter()
+                    .map(
+                        |query| match (query.package.name, query.page_token.as_deref()) {
+                            ("pkg0000", None) => OsvBatchResult {
+                                vulns: vec![OsvVulnRef {
+                                    id: "GHSA-2".to_string(),
+                                }],
+                                next_page_token: Some("page-2".to_string()),
+                            },
+                            ("pkg0000", Some("page-2")) => OsvBatchResult {
+                                vulns: vec![
+                                    OsvVulnRef {
+                                        id: "GHSA-1".to_string(),
+                                    },
+                                    OsvVulnRef {
+                                        id: "GHSA-2".to_string(),
+                                    },
+                                ],
+                                next_page_token: None,
+                            },
+                            _ => OsvBatchResult {
+                                vulns: Vec::new(),
+                                next_page_token: None,
+                            },
+                        },
+                    )
+                    .collect();
+                Ok(OsvBatchResponse { results })
+            })
+            .unwrap();
+
+            assert_eq!(
+                sizes,
+                vec![net::OSV_BATCH_LIMIT, 1, 1],
+                "Two batches, then the second page"
+            );
+            assert_eq!(ids, vec!["GHSA-1", "GHSA-2"]);
+        }
+    }
+
+    mod database_storage {
+        use super::*;
+
+        #[test]
+        fn round_trips_through_directory() {
+            let dir = TempDir::new().unwrap();
+            let database =
+                AdvisoryDatabase::new(vec![advisory("A-1", "npm", "left-pad", "0", "1.0.0")]);
+
+            database.save(dir.path()).unwrap();
+            let loaded = AdvisoryDatabase::load(dir.path()).unwrap();
+
+            assert_eq!(loaded.advisories, database.advisories);
+            assert_eq!(loaded.synced_at, database.synced_at);
+        }
+
+        #[test]
+        fn missing_database_is_reported() {
+            let dir = TempDir::new().unwrap();
+            assert!(matches!(
+                AdvisoryDatabase::load(dir.path()),
+                Err(AuditError::MissingDatabase(_))
+            ));
+        }
+
+        #[test]
+        fn rejects_unknown_format_versions() {
+            let dir = TempDir::new().unwrap();
+            let mut database = AdvisoryDatabase::new(vec![]);
+            database.format_version = 99;
+            database.save(dir.path()).unwrap();
+
+            assert!(matches!(
+                AdvisoryDatabase::load(dir.path()),
+                Err(AuditError::UnsupportedFormat(99))
+            ));
+        }
+
+        #[test]
+        fn import_merges_into_existing_mirror() {
+            let export_dir = TempDir::new().unwrap();
+            let mirror_dir = TempDir::new().unwrap();
+
+            AdvisoryDatabase::new(vec![advisory("A-1", "npm", "a", "0", "1.0.0")])
+                .save(mirror_dir.path())
+                .unwrap();
+            AdvisoryDatabase::new(vec![advisory("B-1", "npm", "b", "0", "1.0.0")])
+                .save(export_dir.path())
+                .unwrap();
+
+            let merged = import_database(export_dir.path(), mirror_dir.path()).unwrap();
+            let ids: Vec<_> = merged.advisories.iter().map(|a| a.id.as_str()).collect();
+            assert_eq!(ids, vec!["A-1", "B-1"]);
+
+            let reloaded = AdvisoryDatabase::load(mirror_dir.path()).unwrap();
+            assert_eq!(reloaded.advisories.len(), 2);
+        }
+
+        #[test]
+        fn parses_osv_records() {
+            let json = r#"{
+                "id": "RUSTSEC-2020-0071",
+                "summary": "Potential segfault in the time crate",
+                "aliases": ["CVE-2020-26235"],
+                "modified": "2023-06-13T13:10:24Z",
+                "database_specific": {"ignored": true},
+                "affected": [{
+                    "package": {"ecosystem": "crates.io", "name": "time"},
+                    "ranges": [{"type": "SEMVER", "events": [
+                        {"introduced": "0.0.0-0"}, {"fixed": "0.2.23"}
+                    ]}]
+                }]
+            }"#;
+
+            let parsed: Advisory = serde_json::from_str(json).unwrap();
+            assert_eq!(parsed.aliases, vec!["CVE-2020-26235"]);
+            assert!(parsed.affected[0].contains("0.1.0"));
+        }
+    }
+
+    #[test]
+    fn maps_ecosystems_to_osv_names() {
+        assert_eq!(osv_ecosystem(&Ecosystem::Rust), Some("crates.io"));
+        assert_eq!(osv_ecosystem(&Ecosystem::NodeJs), Some("npm"));
+        assert_eq!(osv_ecosystem(&Ecosystem::Python), Some("PyPI"));
+        assert_eq!(osv_ecosystem(&Ecosystem::Go), Some("Go"));
+        assert_eq!(osv_ecosystem(&Ecosystem::Nix), None);
+    }
+}
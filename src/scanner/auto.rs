@@ -0,0 +1,317 @@
+//! Scanner presets picked from what a path holds (`scan --auto`)
+//!
+//! `devhealth scan --auto` walks the scanned path once, within the same time
+//! budget as the manifest summary of `check`, and picks the scanners worth
+//! running there:
+//!
+//! - git when the path holds a repository
+//! - deps when it holds a dependency manifest, and audit as well when the
+//!   network may be used or a local advisory database exists
+//! - cargo when it holds a Cargo package, and the deep lint and build checks
+//!   of `--deep` when every project found is a Cargo package
+//! - monorepo for Cargo, npm, pnpm, Nx, Turborepo and Go workspaces
+//! - runtimes for Node projects and pinned Node versions
+//! - containers for dev container and Compose dev files
+//! - system, unless the path is on a network share, where the memory and
+//!   disks of this machine say little about it
+//!
+//! Release checks, `--lost-work` and `--sizes` are never picked.
+
+use crate::bench;
+use crate::config::Scanner;
+use crate::i18n::t;
+use crate::scanner::deps::{self, Ecosystem, MANIFEST_SCAN_BUDGET};
+use crate::scanner::{container, runtime, workspace};
+use crate::tr;
+use crate::utils::fs as dh_fs;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::time::Instant;
+
+/// What a walk of the scanned path found
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Detected {
+    /// Number of git repositories
+    pub repositories: usize,
+    /// Ecosystems with at least one dependency manifest
+    pub ecosystems: BTreeSet<Ecosystem>,
+    /// Number of workspace definitions
+    pub workspaces: usize,
+    /// Whether a `.nvmrc` or `.node-version` pins a Node version
+    pub node_pins: bool,
+    /// Number of dev container and Compose dev files
+    pub dev_environments: usize,
+    /// Whether the path is on a network share
+    pub network_share: bool,
+    /// Whether the audit has advisories to check against, because the
+    /// network may be used or a local advisory database exists
+    ///
+    /// [`detect`] leaves this `false`; the caller knows whether the run is
+    /// offline.
+    pub advisories: bool,
+    /// Whether the walk finished within [`MANIFEST_SCAN_BUDGET`]
+    pub complete: bool,
+}
+
+impl Detected {
+    /// Returns `true` if neither a repository nor a project was found
+    pub fn is_empty(&self) -> bool {
+        self.repositories == 0 && self.ecosystems.is_empty()
+    }
+
+    /// Returns `true` if projects were found and all of them are Cargo packages
+    pub fn only_cargo(&self) -> bool {
+        self.ecosystems.iter().eq([&Ecosystem::Rust])
+    }
+}
+
+/// Scanners picked for a path
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preset {
+    /// Scanners to run, in the order of [`Scanner::ALL`]
+    pub scanners: Vec<Scanner>,
+    /// Whether to run the deep lint and build checks of `--deep`
+    pub deep: bool,
+}
+
+impl Preset {
+    /// Returns the command-line flags selecting the same scanners, such as
+    /// `--git --deps --audit`
+    pub fn flags(&self) -> String {
+        let mut flags: Vec<String> = self
+            .scanners
+            .iter()
+            .map(|scanner| format!("--{}", scanner.name()))
+            .collect();
+        if self.deep {
+            flags.push("--deep".to_string());
+        }
+        flags.join(" ")
+    }
+}
+
+/// Walks `path` and notes what decides the scanners worth running
+///
+/// Dependency and build output directories (`node_modules`, `target`, ...)
+/// are not looked into. The walk stops once [`MANIFEST_SCAN_BUDGET`] has
+/// elapsed, in which case the result is marked incomplete.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::auto;
+/// use std::path::Path;
+///
+/// let detected = auto::detect(Path::new("."));
+/// println!("{} repositories, {} ecosystems", detected.repositories, detected.ecosystems.len());
+/// ```
+pub fn detect(path: &Path) -> Detected {
+    let started = Instant::now();
+    let mut detected = Detected {
+        network_share: bench::fs::is_network_drive(path),
+        complete: true,
+        ..Default::default()
+    };
+
+    let root = dh_fs::extended_length(path);
+    for entry in dh_fs::walk(path) {
+        if started.elapsed() >= MANIFEST_SCAN_BUDGET {
+            detected.complete = false;
+            break;
+        }
+        let file = entry.path();
+        if entry.file_name() == ".git" {
+            detected.repositories += 1;
+            continue;
+        }
+        if dh_fs::is_skipped(file.strip_prefix(&root).unwrap_or(file))
+            || !entry.file_type().is_file()
+        {
+            continue;
+        }
+
+        if let Some(ecosystem) = deps::detect_dependency_file(file) {
+            detected.ecosystems.insert(ecosystem);
+        }
+        if workspace::detect_workspace_file(file).is_some() {
+            detected.workspaces += 1;
+        }
+        if container::detect_environment_file(file).is_some() {
+            detected.dev_environments += 1;
+        }
+        if entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| runtime::NODE_PIN_FILES.contains(&name))
+        {
+            detected.node_pins = true;
+        }
+    }
+
+    detected
+}
+
+/// Picks the scanners for what [`detect`] found
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::config::Scanner;
+/// use devhealth::scanner::auto::{self, Detected};
+/// use devhealth::scanner::deps::Ecosystem;
+///
+/// let detected = Detected {
+///     repositories: 2,
+///     ecosystems: [Ecosystem::Rust].into(),
+///     advisories: true,
+///     ..Default::default()
+/// };
+/// let preset = auto::preset(&detected);
+/// assert_eq!(preset.flags(), "--git --deps --system --audit --cargo --deep");
+/// assert!(preset.scanners.contains(&Scanner::Cargo));
+/// ```
+pub fn preset(detected: &Detected) -> Preset {
+    let manifests = !detected.ecosystems.is_empty();
+    let scanners = Scanner::ALL
+        .into_iter()
+        .filter(|scanner| match scanner {
+            Scanner::Git => detected.repositories > 0,
+            Scanner::Deps => manifests,
+            Scanner::Audit => manifests && detected.advisories,
+            Scanner::System => !detected.network_share,
+            Scanner::Containers => detected.dev_environments > 0,
+            Scanner::Monorepo => detected.workspaces > 0,
+            Scanner::Release => false,
+            Scanner::Cargo => detected.ecosystems.contains(&Ecosystem::Rust),
+            Scanner::Runtimes => {
+                detected.node_pins || detected.ecosystems.contains(&Ecosystem::NodeJs)
+            }
+        })
+        .collect();
+    Preset {
+        scanners,
+        deep: detected.only_cargo(),
+    }
+}
+
+/// Explains the scanners picked for `path`, one line per reason
+///
+/// `preset` is `None` when nothing was found and the default scanners run.
+pub fn describe(detected: &Detected, preset: Option<&Preset>, path: &Path) -> Vec<String> {
+    let mut lines = vec![match preset {
+        Some(preset) => tr!("scan-auto", flags = preset.flags()),
+        None => t("scan-auto-nothing").to_string(),
+    }];
+    if preset.is_some_and(|preset| preset.deep) {
+        lines.push(t("scan-auto-cargo-only").to_string());
+    }
+    if preset.is_some() && !detected.ecosystems.is_empty() && !detected.advisories {
+        lines.push(t("scan-auto-no-advisories").to_string());
+    }
+    if detected.network_share {
+        lines.push(tr!("scan-auto-network-share", path = path.display()));
+    }
+    if !detected.complete {
+        lines.push(tr!(
+            "scan-auto-incomplete",
+            seconds = MANIFEST_SCAN_BUDGET.as_secs()
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn detects_repositories_projects_and_markers() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let root = temp_dir.path();
+        for dir in [
+            "api/.git",
+            "api/node_modules/left-pad",
+            "web/.devcontainer",
+            "tool",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("api/Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+        fs::write(root.join("api/node_modules/left-pad/package.json"), "{}").unwrap();
+        fs::write(root.join("web/package.json"), "{}").unwrap();
+        fs::write(root.join("web/.nvmrc"), "20\n").unwrap();
+        fs::write(root.join("web/.devcontainer/devcontainer.json"), "{}").unwrap();
+
+        let mut detected = detect(root);
+        detected.advisories = true;
+
+        assert_eq!(detected.repositories, 1);
+        assert_eq!(
+            detected.ecosystems,
+            [Ecosystem::Rust, Ecosystem::NodeJs].into()
+        );
+        assert_eq!(detected.workspaces, 1);
+        assert!(detected.node_pins);
+        assert_eq!(detected.dev_environments, 1);
+        assert!(detected.complete);
+        assert_eq!(
+            preset(&detected).flags(),
+            "--git --deps --system --audit --containers --monorepo --cargo --runtimes"
+        );
+    }
+
+    #[test]
+    fn skips_node_projects_inside_dependency_directories() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("target/package/node_modules/x")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"tool\"\n").unwrap();
+        fs::write(
+            root.join("target/package/node_modules/x/package.json"),
+            "{}",
+        )
+        .unwrap();
+
+        let detected = detect(root);
+
+        assert!(detected.only_cargo(), "{:?}", detected);
+        assert!(preset(&detected).deep);
+    }
+
+    #[test]
+    fn skips_system_scan_on_network_shares() {
+        let detected = Detected {
+            repositories: 1,
+            network_share: true,
+            complete: true,
+            ..Default::default()
+        };
+
+        let preset = preset(&detected);
+
+        assert_eq!(preset.scanners, vec![Scanner::Git]);
+        assert!(!preset.deep, "No projects, so no deep checks");
+        let lines = describe(&detected, Some(&preset), Path::new("/mnt/share"));
+        assert_eq!(lines.len(), 2, "{:?}", lines);
+        assert!(lines[1].contains("/mnt/share"), "{:?}", lines);
+    }
+
+    #[test]
+    fn skips_audit_without_advisories() {
+        let mut detected = Detected {
+            ecosystems: [Ecosystem::Python].into(),
+            complete: true,
+            ..Default::default()
+        };
+
+        let offline = preset(&detected);
+        assert_eq!(offline.scanners, vec![Scanner::Deps, Scanner::System]);
+        let lines = describe(&detected, Some(&offline), Path::new("."));
+        assert_eq!(lines.len(), 2, "{:?}", lines);
+
+        detected.advisories = true;
+        assert!(preset(&detected).scanners.contains(&Scanner::Audit));
+    }
+}
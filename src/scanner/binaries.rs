@@ -0,0 +1,206 @@
+//! Developer tools installed more than once on `PATH`
+//!
+//! A machine often ends up with two `python3`s, Homebrew's `git` next to the
+//! one from Xcode, or a manually installed `go` behind the distribution's.
+//! The shell runs whichever comes first in `PATH`, so a terminal with a
+//! different `PATH` (an IDE, a login shell, CI) silently runs another
+//! version. `scan --system` looks up every well-known developer tool on
+//! `PATH` and reports the tools found in several places with different
+//! versions, naming the one that wins.
+//!
+//! Only the tools listed here are run, with the arguments that print their
+//! version; other duplicates on `PATH` are never executed. Entries that
+//! resolve to the same file (such as `/bin` and `/usr/bin` on merged-`/usr`
+//! systems) count once.
+
+use crate::scanner::container;
+use crate::utils::fs as dh_fs;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Tools looked up on `PATH`, with the arguments that print their version
+const TOOLS: [(&str, &[&str]); 35] = [
+    ("git", &["--version"]),
+    ("python", &["--version"]),
+    ("python3", &["--version"]),
+    ("pip", &["--version"]),
+    ("pip3", &["--version"]),
+    ("node", &["--version"]),
+    ("npm", &["--version"]),
+    ("yarn", &["--version"]),
+    ("pnpm", &["--version"]),
+    ("deno", &["--version"]),
+    ("bun", &["--version"]),
+    ("ruby", &["--version"]),
+    ("gem", &["--version"]),
+    ("perl", &["--version"]),
+    ("php", &["--version"]),
+    ("go", &["version"]),
+    ("rustc", &["--version"]),
+    ("cargo", &["--version"]),
+    ("java", &["-version"]),
+    ("javac", &["-version"]),
+    ("mvn", &["--version"]),
+    ("gradle", &["--version"]),
+    ("dotnet", &["--version"]),
+    ("gcc", &["--version"]),
+    ("g++", &["--version"]),
+    ("clang", &["--version"]),
+    ("make", &["--version"]),
+    ("cmake", &["--version"]),
+    ("docker", &["--version"]),
+    ("kubectl", &["version", "--client"]),
+    ("terraform", &["version"]),
+    ("openssl", &["version"]),
+    ("curl", &["--version"]),
+    ("gpg", &["--version"]),
+    ("swift", &["--version"]),
+];
+
+/// One installation of a tool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Installation {
+    /// Path of the executable as found on `PATH`
+    pub path: PathBuf,
+    /// Version it reports, if it ran
+    pub version: Option<String>,
+}
+
+/// A tool found on `PATH` in several places with different versions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateBinary {
+    /// Command name, e.g. `git`
+    pub name: String,
+    /// Every installation in `PATH` order; the first one wins
+    pub installations: Vec<Installation>,
+}
+
+impl DuplicateBinary {
+    /// Returns the installation the shell runs
+    pub fn winner(&self) -> &Installation {
+        &self.installations[0]
+    }
+
+    /// Returns the installations the winner hides
+    pub fn shadowed(&self) -> &[Installation] {
+        &self.installations[1..]
+    }
+}
+
+/// Finds developer tools installed on `PATH` more than once with different versions
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::binaries;
+///
+/// for duplicate in binaries::find_duplicates() {
+///     println!("{} runs {}", duplicate.name, duplicate.winner().path.display());
+/// }
+/// ```
+pub fn find_duplicates() -> Vec<DuplicateBinary> {
+    TOOLS
+        .iter()
+        .filter_map(|(name, args)| {
+            let installations = distinct(dh_fs::find_on_path(name))
+                .into_iter()
+                .map(|path| Installation {
+                    version: container::run_command(&path.to_string_lossy(), args)
+                        .and_then(|output| container::extract_version(&output)),
+                    path,
+                })
+                .collect();
+            duplicate(name, installations)
+        })
+        .collect()
+}
+
+/// Drops paths that resolve to a file already listed
+fn distinct(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut seen: Vec<PathBuf> = Vec::new();
+    paths
+        .into_iter()
+        .filter(|path| {
+            let target = dh_fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+            let new = !seen.contains(&target);
+            seen.push(target);
+            new
+        })
+        .collect()
+}
+
+/// Returns a duplicate if the installations report more than one version
+///
+/// An installation whose version is unknown differs from one whose version
+/// is known; installations that all fail to report one are left out.
+fn duplicate(name: &str, installations: Vec<Installation>) -> Option<DuplicateBinary> {
+    let first = &installations.first()?.version;
+    let differs = installations
+        .iter()
+        .any(|installation| installation.version != *first);
+    differs.then(|| DuplicateBinary {
+        name: name.to_string(),
+        installations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installation(path: &str, version: Option<&str>) -> Installation {
+        Installation {
+            path: PathBuf::from(path),
+            version: version.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn reports_differing_versions_only() {
+        let same = vec![
+            installation("/usr/local/bin/git", Some("2.44.0")),
+            installation("/usr/bin/git", Some("2.44.0")),
+        ];
+        assert!(duplicate("git", same).is_none());
+        assert!(duplicate("git", vec![installation("/usr/bin/git", Some("2.39.3"))]).is_none());
+
+        let different = vec![
+            installation("/usr/bin/git", Some("2.39.3")),
+            installation("/opt/homebrew/bin/git", Some("2.44.0")),
+        ];
+        let duplicate = duplicate("git", different).unwrap();
+        assert_eq!(duplicate.winner().path, PathBuf::from("/usr/bin/git"));
+        assert_eq!(duplicate.shadowed()[0].version.as_deref(), Some("2.44.0"));
+    }
+
+    #[test]
+    fn unknown_versions_differ_from_known_ones() {
+        let unknown = vec![
+            installation("/usr/bin/python3", Some("3.11.4")),
+            installation("/opt/python/bin/python3", None),
+        ];
+        assert!(duplicate("python3", unknown).is_some());
+        let all_unknown = vec![
+            installation("/usr/bin/java", None),
+            installation("/opt/jdk/bin/java", None),
+        ];
+        assert!(duplicate("java", all_unknown).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn merges_links_to_the_same_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let real = dir.path().join("git");
+        std::fs::write(&real, "").unwrap();
+        let link = dir.path().join("git-link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        let other = dir.path().join("other");
+        std::fs::write(&other, "").unwrap();
+
+        assert_eq!(
+            distinct(vec![real.clone(), link, other.clone()]),
+            vec![real, other]
+        );
+    }
+}
@@ -0,0 +1,479 @@
+//! Bazel, Buck and Nix build files
+//!
+//! Polyglot monorepos often declare their external dependencies to a build
+//! system instead of a language package manager. The dependency scanner
+//! recognizes these build files and reads what they declare where the
+//! format allows it:
+//!
+//! - **Bazel**: `bazel_dep` calls in `MODULE.bazel` (`dev_dependency = True`
+//!   makes a development dependency), and repository rules such as
+//!   `http_archive`, `git_repository` and `go_repository` in `WORKSPACE` or
+//!   `WORKSPACE.bazel`, versioned by their `version`, `tag` or `commit`
+//! - **Buck**: `BUCK` files are detected; third-party dependencies live in
+//!   per-cell conventions and are not listed
+//! - **Nix**: the `inputs` of `flake.nix`, versioned by the branch, tag or
+//!   revision in their URL; `default.nix` is detected only
+//!
+//! Only the build file at the top of a project counts: `BUCK` and
+//! `default.nix` files below a directory with a build file of the same
+//! system belong to that project.
+
+use crate::scanner::deps::{self, Dependency, DependencyError, DependencyType, Ecosystem};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Build files of each build system, in the order they are read
+pub(crate) const BUILD_FILES: [(&str, Ecosystem); 6] = [
+    ("MODULE.bazel", Ecosystem::Bazel),
+    ("WORKSPACE", Ecosystem::Bazel),
+    ("WORKSPACE.bazel", Ecosystem::Bazel),
+    ("BUCK", Ecosystem::Buck),
+    ("flake.nix", Ecosystem::Nix),
+    ("default.nix", Ecosystem::Nix),
+];
+
+/// Bazel repository rules that fetch an external dependency
+const REPOSITORY_RULES: [&str; 7] = [
+    "http_archive",
+    "http_file",
+    "http_jar",
+    "git_repository",
+    "new_git_repository",
+    "go_repository",
+    "maven_jar",
+];
+
+/// Returns `true` if `dir` lies below a directory with a build file of
+/// `ecosystem`, up to and including `root`
+///
+/// Such a directory is a package of that project rather than a project of
+/// its own.
+pub(crate) fn is_nested(dir: &Path, root: &Path, ecosystem: &Ecosystem) -> bool {
+    dir.ancestors()
+        .skip(1)
+        .take_while(|ancestor| ancestor.starts_with(root))
+        .any(|ancestor| {
+            BUILD_FILES
+                .iter()
+                .any(|(file, owner)| owner == ecosystem && ancestor.join(file).is_file())
+        })
+}
+
+/// Parses the external dependencies a Bazel project declares
+///
+/// # Errors
+///
+/// Returns an error if a build file exists but cannot be read.
+pub(crate) fn parse_bazel(project_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
+    let mut dependencies = Vec::new();
+
+    let module_path = project_path.join("MODULE.bazel");
+    if module_path.is_file() {
+        let content = deps::read_manifest(&module_path)?;
+        for (_, args) in starlark_calls(&content, &["bazel_dep"]) {
+            let Some(name) = args.get("name") else {
+                continue;
+            };
+            let dependency_type = if args.get("dev_dependency").is_some_and(|dev| dev == "True") {
+                DependencyType::Development
+            } else {
+                DependencyType::Runtime
+            };
+            dependencies.push(Dependency {
+                name: name.clone(),
+                version: args
+                    .get("version")
+                    .cloned()
+                    .unwrap_or_else(|| "*".to_string()),
+                dependency_type,
+                ecosystem: Ecosystem::Bazel,
+                source_file: module_path.clone(),
+                extras: Vec::new(),
+                marker: None,
+            });
+        }
+    }
+
+    for file in ["WORKSPACE", "WORKSPACE.bazel"] {
+        let workspace_path = project_path.join(file);
+        if !workspace_path.is_file() {
+            continue;
+        }
+        let content = deps::read_manifest(&workspace_path)?;
+        for (_, args) in starlark_calls(&content, &REPOSITORY_RULES) {
+            let Some(name) = args.get("name") else {
+                continue;
+            };
+            let version = args
+                .get("version")
+                .or_else(|| args.get("tag"))
+                .cloned()
+                .or_else(|| {
+                    args.get("commit")
+                        .map(|commit| commit.chars().take(12).collect())
+                })
+                .unwrap_or_else(|| "*".to_string());
+            dependencies.push(Dependency {
+                name: name.clone(),
+                version,
+                dependency_type: DependencyType::Runtime,
+                ecosystem: Ecosystem::Bazel,
+                source_file: workspace_path.clone(),
+                extras: Vec::new(),
+                marker: None,
+            });
+        }
+    }
+
+    Ok(dependencies)
+}
+
+/// Parses the inputs of a Nix flake
+///
+/// # Errors
+///
+/// Returns an error if `flake.nix` exists but cannot be read.
+pub(crate) fn parse_nix(project_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
+    let flake_path = project_path.join("flake.nix");
+    if !flake_path.is_file() {
+        return Ok(Vec::new());
+    }
+    let content = deps::read_manifest(&flake_path)?;
+
+    Ok(flake_inputs(&content)
+        .into_iter()
+        .map(|(name, url)| Dependency {
+            name,
+            version: flake_url_version(&url),
+            dependency_type: DependencyType::Runtime,
+            ecosystem: Ecosystem::Nix,
+            source_file: flake_path.clone(),
+            extras: Vec::new(),
+            marker: None,
+        })
+        .collect())
+}
+
+/// Finds calls to the functions in `names` and their string and boolean
+/// keyword arguments
+///
+/// Comments are skipped and arguments may span lines. Arguments that are
+/// neither a string literal nor `True`/`False` are left out.
+fn starlark_calls(content: &str, names: &[&str]) -> Vec<(String, HashMap<String, String>)> {
+    let code = strip_comments(content);
+    let mut calls = Vec::new();
+    let mut rest = code.as_str();
+
+    while let Some(open) = rest.find('(') {
+        let callee = rest[..open]
+            .trim_end()
+            .rsplit(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or("");
+        let Some(length) = call_length(&rest[open..]) else {
+            break;
+        };
+        let args = &rest[open + 1..open + length - 1];
+        if names.contains(&callee) {
+            calls.push((callee.to_string(), keyword_arguments(args)));
+            rest = &rest[open + length..];
+        } else {
+            rest = &rest[open + 1..];
+        }
+    }
+    calls
+}
+
+/// Removes `#` comments outside string literals
+fn strip_comments(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            let mut quote = None;
+            for (index, c) in line.char_indices() {
+                match (c, quote) {
+                    ('"' | '\'', None) => quote = Some(c),
+                    (c, Some(open)) if c == open => quote = None,
+                    ('#', None) => return &line[..index],
+                    _ => {}
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the length of the parenthesized call arguments at the start of
+/// `text`, including both parentheses, or `None` if they are not closed
+fn call_length(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (index, c) in text.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            ('(' | '[' | '{', None) => depth += 1,
+            (')' | ']' | '}', None) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Reads the top-level `key = "value"` and `key = True` arguments of a call
+fn keyword_arguments(args: &str) -> HashMap<String, String> {
+    let mut arguments = HashMap::new();
+    for argument in split_top_level(args) {
+        let Some((key, value)) = argument.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let value = if let Some(text) = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        {
+            text
+        } else if value == "True" || value == "False" {
+            value
+        } else {
+            continue;
+        };
+        arguments.insert(key.trim().to_string(), value.to_string());
+    }
+    arguments
+}
+
+/// Splits call arguments at the commas outside nested brackets and strings
+fn split_top_level(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (index, c) in args.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            ('(' | '[' | '{', None) => depth += 1,
+            (')' | ']' | '}', None) => depth -= 1,
+            (',', None) if depth == 0 => {
+                parts.push(&args[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    parts
+}
+
+/// Finds the inputs of a flake and their URLs
+///
+/// Understands `inputs.name.url = "...";`, `name.url = "...";` inside an
+/// `inputs = { ... }` block, and `name = { url = "..."; ... };` blocks.
+/// Inputs without a URL, such as those that only `follows` another, are
+/// skipped.
+fn flake_inputs(content: &str) -> Vec<(String, String)> {
+    let mut inputs: Vec<(String, String)> = Vec::new();
+    let mut in_inputs = false;
+    let mut depth = 0usize;
+    let mut current: Option<(String, usize)> = None;
+
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let opens = line.matches('{').count();
+        let closes = line.matches('}').count();
+
+        if let Some((assignee, value)) = line.split_once('=') {
+            let assignee = assignee.trim();
+            let value = value.trim();
+            let path: Vec<&str> = assignee.split('.').collect();
+            let path = if path.first() == Some(&"inputs") {
+                &path[1..]
+            } else if in_inputs {
+                &path[..]
+            } else {
+                &[][..]
+            };
+
+            match path {
+                [] if assignee == "inputs" && value.starts_with('{') => in_inputs = true,
+                [name, "url"] => push_input(&mut inputs, name, value),
+                [name] if value.starts_with('{') => current = Some((name.to_string(), depth)),
+                ["url"] => {
+                    if let Some((name, _)) = &current {
+                        push_input(&mut inputs, name, value);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        depth = (depth + opens).saturating_sub(closes);
+        if current
+            .as_ref()
+            .is_some_and(|(_, opened_at)| depth <= *opened_at)
+        {
+            current = None;
+        }
+        if in_inputs && depth <= 1 && closes > 0 {
+            in_inputs = false;
+        }
+    }
+    inputs
+}
+
+/// Adds an input with the URL in a `"...";` value, once per name
+fn push_input(inputs: &mut Vec<(String, String)>, name: &str, value: &str) {
+    let url = value.trim_end_matches(';').trim().trim_matches('"');
+    if !url.is_empty() && !inputs.iter().any(|(known, _)| known == name) {
+        inputs.push((name.to_string(), url.to_string()));
+    }
+}
+
+/// Returns the branch, tag or revision a flake URL pins, or `*`
+///
+/// `github:owner/repo/ref`, `gitlab:` and `sourcehut:` URLs carry it as a
+/// third path segment; other URLs as a `rev` or `ref` query parameter.
+fn flake_url_version(url: &str) -> String {
+    if let Some((_, query)) = url.split_once('?') {
+        let params: HashMap<&str, &str> = query
+            .split('&')
+            .filter_map(|param| param.split_once('='))
+            .collect();
+        if let Some(version) = params.get("rev").or_else(|| params.get("ref")) {
+            return version.to_string();
+        }
+    }
+    let path = url.split('?').next().unwrap_or(url);
+    if let Some((_, location)) = ["github:", "gitlab:", "sourcehut:"]
+        .iter()
+        .find_map(|scheme| path.split_once(scheme))
+    {
+        if let Some(reference) = location.splitn(3, '/').nth(2).filter(|r| !r.is_empty()) {
+            return reference.to_string();
+        }
+    }
+    "*".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_bazel_modules_and_workspace_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("MODULE.bazel"),
+            r#"module(name = "monorepo", version = "1.0")
+
+# bazel_dep(name = "commented_out", version = "0.1")
+bazel_dep(name = "rules_go", version = "0.41.0")
+bazel_dep(
+    name = "gazelle",
+    version = "0.32.0",
+    dev_dependency = True,
+)
+go_deps = use_extension("@gazelle//:extensions.bzl", "go_deps")
+"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("WORKSPACE"),
+            r#"load("@bazel_tools//tools/build_defs/repo:http.bzl", "http_archive")
+
+http_archive(
+    name = "com_google_absl",
+    urls = ["https://github.com/abseil/abseil-cpp/archive/refs/tags/20230802.0.tar.gz"],
+    strip_prefix = "abseil-cpp-20230802.0",
+)
+git_repository(name = "rules_foo", remote = "https://example.com/rules_foo.git", commit = "0123456789abcdef0123")
+"#,
+        )
+        .unwrap();
+
+        let dependencies = parse_bazel(temp_dir.path()).unwrap();
+        let found: Vec<(&str, &str)> = dependencies
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("rules_go", "0.41.0"),
+                ("gazelle", "0.32.0"),
+                ("com_google_absl", "*"),
+                ("rules_foo", "0123456789ab")
+            ]
+        );
+        assert_eq!(dependencies[1].dependency_type, DependencyType::Development);
+        assert!(dependencies.iter().all(|d| d.ecosystem == Ecosystem::Bazel));
+    }
+
+    #[test]
+    fn parses_flake_inputs() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("flake.nix"),
+            r#"{
+  description = "dev shell";
+  inputs = {
+    nixpkgs.url = "github:NixOS/nixpkgs/nixos-23.11";
+    flake-utils.url = "github:numtide/flake-utils";
+    home-manager = {
+      url = "git+https://github.com/nix-community/home-manager?ref=release-23.11";
+      inputs.nixpkgs.follows = "nixpkgs";
+    };
+  };
+  inputs.rust-overlay.url = "github:oxalica/rust-overlay";
+
+  outputs = { self, nixpkgs, ... }: {
+    packages.x86_64-linux.default = nixpkgs.legacyPackages.x86_64-linux.hello;
+  };
+}
+"#,
+        )
+        .unwrap();
+
+        let dependencies = parse_nix(temp_dir.path()).unwrap();
+        let found: Vec<(&str, &str)> = dependencies
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("nixpkgs", "nixos-23.11"),
+                ("flake-utils", "*"),
+                ("home-manager", "release-23.11"),
+                ("rust-overlay", "*")
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_build_files_belong_to_the_top_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let package = temp_dir.path().join("services").join("api");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(temp_dir.path().join("BUCK"), "").unwrap();
+        fs::write(package.join("BUCK"), "").unwrap();
+
+        assert!(is_nested(&package, temp_dir.path(), &Ecosystem::Buck));
+        assert!(!is_nested(
+            temp_dir.path(),
+            temp_dir.path(),
+            &Ecosystem::Buck
+        ));
+        assert!(!is_nested(&package, temp_dir.path(), &Ecosystem::Nix));
+    }
+}
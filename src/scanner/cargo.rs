@@ -0,0 +1,1094 @@
+//! Cargo project hygiene
+//!
+//! Rust projects declare which compiler they support through the `edition`
+//! and `rust-version` (MSRV) fields of `Cargo.toml`. This scanner reports
+//! both for every package and flags:
+//!
+//! - packages without a `rust-version`
+//! - editions older than 2021 (a missing `edition` means 2015)
+//! - a `rust-version` lower than what the locked dependencies require
+//!
+//! Dependency requirements come from the `rust-version` of every registry
+//! package in the nearest `Cargo.lock`, read from the sources Cargo already
+//! downloaded to `$CARGO_HOME/registry/src`. Packages that were never
+//! downloaded are skipped, so the requirement is a lower bound. For
+//! workspaces the lockfile covers every member, so a member is held to the
+//! requirements of the whole workspace.
+//!
+//! Fields inherited with `edition.workspace = true` or
+//! `rust-version.workspace = true` are resolved from the workspace root.
+//!
+//! ## Features
+//!
+//! Every package's `[features]` are listed with the optional dependencies
+//! they enable, directly or through other features. The scanner flags:
+//!
+//! - features that enable no dependency and are never referenced, neither
+//!   by `feature = "..."` in the package's Rust sources, by
+//!   `CARGO_FEATURE_*` in its build script, nor by another feature
+//! - optional dependencies enabled by `default` that pull in many crates,
+//!   counted through `Cargo.lock`
+//!
+//! Features enabled only by other packages of a workspace count as unused.
+
+use crate::i18n::t;
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// The oldest edition that is not flagged as outdated
+const CURRENT_EDITION: u16 = 2021;
+
+/// Edition used by Cargo when a package declares none
+const DEFAULT_EDITION: &str = "2015";
+
+/// Transitive crates from which a default optional dependency counts as heavy
+const HEAVY_DEPENDENCY_CRATES: usize = 25;
+
+/// A dependency whose declared `rust-version` is the highest in the lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RustRequirement {
+    /// Name of the dependency
+    pub dependency: String,
+    /// Locked version of the dependency
+    pub version: String,
+    /// Rust version the dependency requires
+    pub rust_version: String,
+}
+
+/// Something worth fixing in a Cargo package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CargoFinding {
+    /// The package declares no `rust-version`
+    MissingRustVersion,
+    /// The package uses an edition older than 2021
+    OutdatedEdition {
+        /// The edition in effect
+        edition: String,
+    },
+    /// Locked dependencies need a newer Rust than the declared `rust-version`
+    RustVersionTooLow {
+        /// The declared `rust-version`
+        declared: String,
+        /// The highest `rust-version` among locked dependencies
+        required: String,
+        /// The dependency requiring it
+        dependency: String,
+    },
+    /// A feature enables no dependency and nothing refers to it
+    UnusedFeature {
+        /// Name of the feature
+        feature: String,
+    },
+    /// The `default` feature enables an optional dependency with many crates
+    HeavyDefaultDependency {
+        /// Name of the optional dependency
+        dependency: String,
+        /// Crates it pulls in, according to `Cargo.lock`
+        crates: usize,
+    },
+}
+
+impl CargoFinding {
+    /// Returns a localized, one-line description of the finding
+    pub fn describe(&self) -> String {
+        match self {
+            CargoFinding::MissingRustVersion => t("cargo-finding-no-msrv").to_string(),
+            CargoFinding::OutdatedEdition { edition } => {
+                tr!("cargo-finding-old-edition", edition = edition)
+            }
+            CargoFinding::RustVersionTooLow {
+                declared,
+                required,
+                dependency,
+            } => tr!(
+                "cargo-finding-msrv-too-low",
+                declared = declared,
+                required = required,
+                dependency = dependency
+            ),
+            CargoFinding::UnusedFeature { feature } => {
+                tr!("cargo-finding-unused-feature", feature = feature)
+            }
+            CargoFinding::HeavyDefaultDependency { dependency, crates } => {
+                tr!(
+                    "cargo-finding-heavy-default",
+                    dependency = dependency,
+                    crates = crates
+                )
+            }
+        }
+    }
+}
+
+/// A feature declared in `[features]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Feature {
+    /// Name of the feature
+    pub name: String,
+    /// Optional dependencies it enables, directly or through other features
+    pub dependencies: Vec<String>,
+    /// Whether the sources, build script or another feature refer to it
+    pub referenced: bool,
+}
+
+/// Result of checking one Cargo package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CargoProject {
+    /// Directory holding the package's `Cargo.toml`
+    pub path: PathBuf,
+    /// Package name
+    pub name: String,
+    /// Edition in effect (`2015` if not declared)
+    pub edition: String,
+    /// Declared minimum supported Rust version
+    pub rust_version: Option<String>,
+    /// Highest Rust version required by a locked dependency, if known
+    pub required_rust_version: Option<RustRequirement>,
+    /// Declared features, sorted by name
+    pub features: Vec<Feature>,
+    /// Problems found in the package
+    pub findings: Vec<CargoFinding>,
+}
+
+/// Checks the edition, MSRV and features of Cargo packages accepted by a filter
+///
+/// Virtual workspace manifests (a `Cargo.toml` without `[package]`) are not
+/// packages and are skipped.
+///
+/// # Arguments
+///
+/// * `path` - The directory to search for `Cargo.toml` files
+/// * `include` - Predicate deciding whether a package directory is included
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::cargo;
+/// use std::path::Path;
+///
+/// for project in cargo::scan_projects_matching(Path::new("."), |_| true) {
+///     println!("{}: edition {}", project.name, project.edition);
+/// }
+/// ```
+pub fn scan_projects_matching<F>(path: &Path, include: F) -> Vec<CargoProject>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut registry = Registry::new(cargo_home().map(|home| home.join("registry").join("src")));
+
+    dh_fs::walk(path)
+        .filter(|entry| entry.file_name() == "Cargo.toml")
+        .filter_map(|entry| {
+            let dir = dh_fs::strip_extended_length(entry.path().parent()?);
+            if !include(&dir) {
+                return None;
+            }
+            check_project(&dir, &mut registry)
+        })
+        .collect()
+}
+
+/// Displays Cargo project results at the given level of detail
+///
+/// - [`Detail::Summary`]: header and summary box only
+/// - [`Detail::Minimal`]: only packages with findings
+/// - [`Detail::Normal`]: every package with its edition, MSRV and features
+/// - [`Detail::Full`]: also the dependency setting the Rust requirement and
+///   the optional dependencies behind each feature
+///
+/// # Arguments
+///
+/// * `projects` - Slice of Cargo projects to display
+/// * `detail` - How much of the report to print
+pub fn display_results_with(projects: &[CargoProject], detail: Detail) {
+    if projects.is_empty() {
+        println!(
+            "{}",
+            display::header(&tr!("cargo-none"), "🦀", Role::Warning)
+        );
+        return;
+    }
+
+    let count = |matches: fn(&CargoFinding) -> bool| {
+        projects
+            .iter()
+            .filter(|project| project.findings.iter().any(matches))
+            .count()
+    };
+    let missing_msrv = count(|f| matches!(f, CargoFinding::MissingRustVersion));
+    let old_editions = count(|f| matches!(f, CargoFinding::OutdatedEdition { .. }));
+    let msrv_too_low = count(|f| matches!(f, CargoFinding::RustVersionTooLow { .. }));
+    let feature_issues = count(|f| {
+        matches!(
+            f,
+            CargoFinding::UnusedFeature { .. } | CargoFinding::HeavyDefaultDependency { .. }
+        )
+    });
+    let flagged = |count: usize| {
+        if count > 0 {
+            format!("{} ⚠️", count)
+        } else {
+            "0".to_string()
+        }
+    };
+    let role = if projects.iter().any(|p| !p.findings.is_empty()) {
+        Role::Warning
+    } else {
+        Role::Special
+    };
+
+    println!(
+        "{}",
+        display::header(&tr!("cargo-title", count = projects.len()), "🦀", role)
+    );
+
+    let summary_items = vec![
+        (t("cargo-projects"), projects.len().to_string()),
+        (t("cargo-missing-msrv"), flagged(missing_msrv)),
+        (t("cargo-old-editions"), flagged(old_editions)),
+        (t("cargo-msrv-too-low"), flagged(msrv_too_low)),
+        (t("cargo-feature-issues"), flagged(feature_issues)),
+    ];
+    print!("{}", display::summary_box(&summary_items));
+
+    if detail == Detail::Summary {
+        return;
+    }
+
+    let shown: Vec<&CargoProject> = projects
+        .iter()
+        .filter(|project| detail >= Detail::Normal || !project.findings.is_empty())
+        .collect();
+    if shown.is_empty() {
+        return;
+    }
+
+    println!("{}", display::section_divider(t("cargo-details")));
+    for (index, project) in shown.iter().enumerate() {
+        let (symbol, role) = if project.findings.is_empty() {
+            ("✓", Role::Success)
+        } else {
+            ("⚠", Role::Warning)
+        };
+        let msrv = project.rust_version.as_deref().unwrap_or("—");
+        let header = format!(
+            "{} {} {}",
+            symbol.paint(role),
+            project.name.paint(Role::Emphasis).bold(),
+            tr!("cargo-versions", edition = project.edition, msrv = msrv).paint(Role::Muted)
+        );
+        println!(
+            "{}",
+            display::tree_item(&header, index == shown.len() - 1, 0)
+        );
+
+        let mut lines: Vec<String> = project
+            .findings
+            .iter()
+            .map(|finding| {
+                format!(
+                    "{} {}",
+                    "⚠".paint(Role::Warning),
+                    finding.describe().paint(Role::Warning)
+                )
+            })
+            .collect();
+        if detail >= Detail::Normal && !project.features.is_empty() {
+            let names: Vec<&str> = project.features.iter().map(|f| f.name.as_str()).collect();
+            lines.push(
+                tr!("cargo-features", features = names.join(", "))
+                    .paint(Role::Muted)
+                    .to_string(),
+            );
+        }
+        if detail == Detail::Full {
+            lines.extend(
+                project
+                    .features
+                    .iter()
+                    .filter(|feature| !feature.dependencies.is_empty())
+                    .map(|feature| {
+                        tr!(
+                            "cargo-feature-deps",
+                            feature = feature.name,
+                            dependencies = feature.dependencies.join(", ")
+                        )
+                    }),
+            );
+            if let Some(requirement) = &project.required_rust_version {
+                lines.push(tr!(
+                    "cargo-requirement",
+                    required = requirement.rust_version,
+                    dependency = requirement.dependency,
+                    version = requirement.version
+                ));
+            }
+            lines.push(display::file_path(
+                &project.path.join("Cargo.toml").to_string_lossy(),
+            ));
+        }
+        for (line_index, line) in lines.iter().enumerate() {
+            println!(
+                "{}",
+                display::tree_item(line, line_index == lines.len() - 1, 1)
+            );
+        }
+    }
+}
+
+/// Checks one package directory; `None` if it holds no package manifest
+fn check_project(dir: &Path, registry: &mut Registry) -> Option<CargoProject> {
+    let manifest = read_toml(&dir.join("Cargo.toml"))?;
+    let package = manifest.get("package")?;
+    let name = package.get("name")?.as_str()?.to_string();
+
+    let edition =
+        package_field(dir, package, "edition").unwrap_or_else(|| DEFAULT_EDITION.to_string());
+    let rust_version = package_field(dir, package, "rust-version");
+    let lock = find_lockfile(dir).and_then(|lockfile| read_toml(&lockfile));
+    let required_rust_version = lock
+        .as_ref()
+        .and_then(|lock| highest_requirement(lock, registry));
+    let optional = optional_dependencies(&manifest);
+    let features = analyze_features(&manifest, &optional, &referenced_features(dir));
+
+    let mut findings = Vec::new();
+    if rust_version.is_none() {
+        findings.push(CargoFinding::MissingRustVersion);
+    }
+    if edition
+        .parse::<u16>()
+        .is_ok_and(|year| year < CURRENT_EDITION)
+    {
+        findings.push(CargoFinding::OutdatedEdition {
+            edition: edition.clone(),
+        });
+    }
+    if let (Some(declared), Some(required)) = (&rust_version, &required_rust_version) {
+        if rust_version_key(&required.rust_version) > rust_version_key(declared) {
+            findings.push(CargoFinding::RustVersionTooLow {
+                declared: declared.clone(),
+                required: required.rust_version.clone(),
+                dependency: required.dependency.clone(),
+            });
+        }
+    }
+    findings.extend(
+        features
+            .iter()
+            .filter(|f| f.name != "default" && f.dependencies.is_empty() && !f.referenced)
+            .map(|f| CargoFinding::UnusedFeature {
+                feature: f.name.clone(),
+            }),
+    );
+    let defaults = features.iter().find(|f| f.name == "default");
+    for dependency in defaults.into_iter().flat_map(|f| &f.dependencies) {
+        let crates = lock
+            .as_ref()
+            .zip(optional.get(dependency))
+            .and_then(|(lock, package)| transitive_crates(lock, package));
+        if let Some(crates) = crates.filter(|&crates| crates >= HEAVY_DEPENDENCY_CRATES) {
+            findings.push(CargoFinding::HeavyDefaultDependency {
+                dependency: dependency.clone(),
+                crates,
+            });
+        }
+    }
+
+    Some(CargoProject {
+        path: dir.to_path_buf(),
+        name,
+        edition,
+        rust_version,
+        required_rust_version,
+        features,
+        findings,
+    })
+}
+
+/// Returns optional dependencies by their name in the manifest, mapped to
+/// the package they refer to (which differs for renamed dependencies)
+fn optional_dependencies(manifest: &toml::Value) -> BTreeMap<String, String> {
+    let tables = ["dependencies", "build-dependencies"]
+        .into_iter()
+        .filter_map(|key| manifest.get(key)?.as_table());
+    let targets = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values())
+        .flat_map(|target| {
+            ["dependencies", "build-dependencies"]
+                .into_iter()
+                .filter_map(move |key| target.get(key)?.as_table())
+        });
+
+    tables
+        .chain(targets)
+        .flatten()
+        .filter(|(_, spec)| spec.get("optional").and_then(toml::Value::as_bool) == Some(true))
+        .map(|(name, spec)| {
+            let package = spec
+                .get("package")
+                .and_then(toml::Value::as_str)
+                .unwrap_or(name);
+            (name.clone(), package.to_string())
+        })
+        .collect()
+}
+
+/// Resolves every declared feature to the optional dependencies it enables
+fn analyze_features(
+    manifest: &toml::Value,
+    optional: &BTreeMap<String, String>,
+    in_code: &BTreeSet<String>,
+) -> Vec<Feature> {
+    let Some(table) = manifest.get("features").and_then(toml::Value::as_table) else {
+        return Vec::new();
+    };
+    let features: BTreeMap<&str, Vec<&str>> = table
+        .iter()
+        .map(|(name, items)| {
+            let items = items
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(toml::Value::as_str);
+            (name.as_str(), items.collect())
+        })
+        .collect();
+
+    // Features named in other features' lists, including `name/feature`
+    let enabled_by_features: BTreeSet<&str> = features
+        .iter()
+        .flat_map(|(owner, items)| items.iter().map(move |item| (*owner, *item)))
+        .filter_map(|(owner, item)| {
+            let name = item.split('/').next()?.trim_end_matches('?');
+            (name != owner).then_some(name)
+        })
+        .collect();
+
+    features
+        .keys()
+        .map(|&name| {
+            let mut dependencies = BTreeSet::new();
+            enabled_dependencies(
+                name,
+                &features,
+                optional,
+                &mut BTreeSet::new(),
+                &mut dependencies,
+            );
+            let env_name = name.to_uppercase().replace('-', "_");
+            Feature {
+                name: name.to_string(),
+                dependencies: dependencies.into_iter().collect(),
+                referenced: enabled_by_features.contains(name)
+                    || in_code.contains(name)
+                    || in_code.contains(&format!("CARGO_FEATURE_{}", env_name)),
+            }
+        })
+        .collect()
+}
+
+/// Collects the optional dependencies a feature enables into `enabled`
+///
+/// Follows Cargo's syntax: `dep:name` and the implicit feature `name` enable
+/// an optional dependency, `name/feature` enables it too, while the weak
+/// `name?/feature` does not.
+fn enabled_dependencies<'a>(
+    feature: &'a str,
+    features: &BTreeMap<&'a str, Vec<&'a str>>,
+    optional: &BTreeMap<String, String>,
+    visited: &mut BTreeSet<&'a str>,
+    enabled: &mut BTreeSet<String>,
+) {
+    if !visited.insert(feature) {
+        return;
+    }
+    for item in features.get(feature).into_iter().flatten() {
+        if let Some(dependency) = item.strip_prefix("dep:") {
+            enabled.insert(dependency.to_string());
+            continue;
+        }
+        let (name, weak) = match item.split_once('/') {
+            Some((name, _)) => (name.trim_end_matches('?'), name.ends_with('?')),
+            None => (*item, false),
+        };
+        if features.contains_key(name) && !item.contains('/') {
+            enabled_dependencies(name, features, optional, visited, enabled);
+        } else if optional.contains_key(name) && !weak {
+            enabled.insert(name.to_string());
+        }
+    }
+}
+
+/// Collects the features a package's Rust sources and build script refer to
+///
+/// Returns the names in `feature = "name"` expressions and every
+/// `CARGO_FEATURE_<NAME>` environment variable mentioned.
+fn referenced_features(dir: &Path) -> BTreeSet<String> {
+    let mut references = BTreeSet::new();
+    let root = dh_fs::extended_length(dir);
+    for entry in dh_fs::walk(dir) {
+        let path = entry.path();
+        if dh_fs::is_skipped(path.strip_prefix(&root).unwrap_or(path))
+            || path.extension().and_then(|e| e.to_str()) != Some("rs")
+        {
+            continue;
+        }
+        if let Ok(source) = std::fs::read_to_string(path) {
+            collect_feature_references(&source, &mut references);
+        }
+    }
+    references
+}
+
+/// Adds the feature names referenced in one source file to `references`
+fn collect_feature_references(source: &str, references: &mut BTreeSet<String>) {
+    for (index, _) in source.match_indices("feature") {
+        let rest = source[index + "feature".len()..].trim_start();
+        let Some(rest) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let Some(rest) = rest.trim_start().strip_prefix('"') else {
+            continue;
+        };
+        if let Some((name, _)) = rest.split_once('"') {
+            references.insert(name.to_string());
+        }
+    }
+
+    for (index, _) in source.match_indices("CARGO_FEATURE_") {
+        let name: String = source[index..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        references.insert(name);
+    }
+}
+
+/// Counts the crates a locked package pulls in, not counting itself
+///
+/// Dependencies listed as `name version` pick that version; a bare `name`
+/// means the lockfile holds a single version of it.
+fn transitive_crates(lock: &toml::Value, package: &str) -> Option<usize> {
+    let packages = lock.get("package")?.as_array()?;
+    let find = |reference: &str| {
+        let mut parts = reference.split_whitespace();
+        let (name, version) = (parts.next()?, parts.next());
+        packages.iter().find(|candidate| {
+            candidate.get("name").and_then(toml::Value::as_str) == Some(name)
+                && version.is_none_or(|v| {
+                    candidate.get("version").and_then(toml::Value::as_str) == Some(v)
+                })
+        })
+    };
+    let key = |package: &toml::Value| {
+        let field = |name| {
+            package
+                .get(name)
+                .and_then(toml::Value::as_str)
+                .unwrap_or_default()
+        };
+        format!("{} {}", field("name"), field("version"))
+    };
+
+    let root = find(package)?;
+    let mut seen = BTreeSet::from([key(root)]);
+    let mut queue = vec![root];
+    while let Some(current) = queue.pop() {
+        let dependencies = current.get("dependencies").and_then(toml::Value::as_array);
+        for dependency in dependencies
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_str)
+        {
+            if let Some(next) = find(dependency) {
+                if seen.insert(key(next)) {
+                    queue.push(next);
+                }
+            }
+        }
+    }
+    Some(seen.len() - 1)
+}
+
+/// Reads a `[package]` string field, following `field.workspace = true`
+fn package_field(dir: &Path, package: &toml::Value, field: &str) -> Option<String> {
+    let value = package.get(field)?;
+    if let Some(value) = value.as_str() {
+        return Some(value.to_string());
+    }
+    if value.get("workspace").and_then(toml::Value::as_bool) != Some(true) {
+        return None;
+    }
+
+    dir.ancestors().skip(1).find_map(|ancestor| {
+        let manifest = read_toml(&ancestor.join("Cargo.toml"))?;
+        let workspace_package = manifest.get("workspace")?.get("package")?;
+        Some(workspace_package.get(field)?.as_str()?.to_string())
+    })
+}
+
+/// Returns the `Cargo.lock` in `dir` or the closest ancestor holding one
+pub(crate) fn find_lockfile(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|ancestor| ancestor.join("Cargo.lock"))
+        .find(|lockfile| lockfile.is_file())
+}
+
+/// Returns the locked registry package with the highest `rust-version`
+fn highest_requirement(lock: &toml::Value, registry: &mut Registry) -> Option<RustRequirement> {
+    lock.get("package")?
+        .as_array()?
+        .iter()
+        .filter(|package| {
+            package
+                .get("source")
+                .and_then(toml::Value::as_str)
+                .is_some_and(|source| {
+                    source.starts_with("registry+") || source.starts_with("sparse+")
+                })
+        })
+        .filter_map(|package| {
+            let name = package.get("name")?.as_str()?;
+            let version = package.get("version")?.as_str()?;
+            let rust_version = registry.rust_version(name, version)?;
+            Some(RustRequirement {
+                dependency: name.to_string(),
+                version: version.to_string(),
+                rust_version,
+            })
+        })
+        .max_by(|a, b| {
+            rust_version_key(&a.rust_version)
+                .cmp(&rust_version_key(&b.rust_version))
+                .then_with(|| b.dependency.cmp(&a.dependency))
+        })
+}
+
+/// Orders Rust versions such as `1.70` and `1.74.1` numerically
+fn rust_version_key(version: &str) -> (u64, u64, u64) {
+    let mut parts = version
+        .trim()
+        .split('.')
+        .map(|part| part.parse::<u64>().unwrap_or(0));
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
+/// Returns Cargo's home directory (`$CARGO_HOME` or `~/.cargo`)
+pub(crate) fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(".cargo"))
+        })
+}
+
+/// Returns the per-registry source directories in `src`, usually
+/// `$CARGO_HOME/registry/src`, sorted
+pub(crate) fn registry_sources(src: Option<PathBuf>) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = src
+        .and_then(|src| std::fs::read_dir(src).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    roots.sort();
+    roots
+}
+
+/// Downloaded crate sources under `$CARGO_HOME/registry/src`
+///
+/// Each registry has its own directory (`index.crates.io-<hash>`), holding
+/// one `<name>-<version>` directory per crate. Lookups are cached because
+/// workspace members share most of their dependencies.
+struct Registry {
+    /// Per-registry source directories
+    roots: Vec<PathBuf>,
+    /// `rust-version` by `(name, version)`, `None` if unknown
+    cache: HashMap<(String, String), Option<String>>,
+}
+
+impl Registry {
+    fn new(src: Option<PathBuf>) -> Self {
+        Registry {
+            roots: registry_sources(src),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Returns the `rust-version` a downloaded crate declares
+    fn rust_version(&mut self, name: &str, version: &str) -> Option<String> {
+        let roots = &self.roots;
+        self.cache
+            .entry((name.to_string(), version.to_string()))
+            .or_insert_with(|| {
+                roots.iter().find_map(|root| {
+                    let manifest = read_toml(
+                        &root
+                            .join(format!("{}-{}", name, version))
+                            .join("Cargo.toml"),
+                    )?;
+                    Some(
+                        manifest
+                            .get("package")?
+                            .get("rust-version")?
+                            .as_str()?
+                            .to_string(),
+                    )
+                })
+            })
+            .clone()
+    }
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    toml::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Writes a downloaded crate into a fake registry source directory
+    fn downloaded(src: &Path, name: &str, version: &str, rust_version: Option<&str>) {
+        let dir = src
+            .join("index.crates.io-6f17d22bba15001f")
+            .join(format!("{}-{}", name, version));
+        fs::create_dir_all(&dir).unwrap();
+        let msrv = rust_version
+            .map(|v| format!("rust-version = \"{}\"\n", v))
+            .unwrap_or_default();
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"{}\"\n{}",
+                name, version, msrv
+            ),
+        )
+        .unwrap();
+    }
+
+    const LOCKFILE: &str = r#"version = 3
+
+[[package]]
+name = "api"
+version = "0.1.0"
+
+[[package]]
+name = "clap"
+version = "4.5.4"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "tokio"
+version = "1.37.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    mod manifests {
+        use super::*;
+
+        #[test]
+        fn reports_editions_and_missing_msrv() {
+            let temp_dir = TempDir::new().unwrap();
+            let mut registry = Registry::new(None);
+            let dir = temp_dir.path().join("legacy");
+            fs::create_dir(&dir).unwrap();
+            fs::write(
+                dir.join("Cargo.toml"),
+                "[package]\nname = \"legacy\"\nversion = \"0.1.0\"\n",
+            )
+            .unwrap();
+
+            let project = check_project(&dir, &mut registry).unwrap();
+
+            assert_eq!(project.edition, "2015");
+            assert_eq!(project.rust_version, None);
+            assert_eq!(
+                project.findings,
+                vec![
+                    CargoFinding::MissingRustVersion,
+                    CargoFinding::OutdatedEdition {
+                        edition: "2015".to_string()
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn inherits_fields_from_the_workspace() {
+            let temp_dir = TempDir::new().unwrap();
+            let mut registry = Registry::new(None);
+            fs::write(
+                temp_dir.path().join("Cargo.toml"),
+                "[workspace]\nmembers = [\"core\"]\n\n[workspace.package]\nedition = \"2021\"\nrust-version = \"1.74\"\n",
+            )
+            .unwrap();
+            let core = temp_dir.path().join("core");
+            fs::create_dir(&core).unwrap();
+            fs::write(
+                core.join("Cargo.toml"),
+                "[package]\nname = \"core\"\nedition.workspace = true\nrust-version.workspace = true\n",
+            )
+            .unwrap();
+
+            assert_eq!(
+                check_project(temp_dir.path(), &mut registry),
+                None,
+                "Virtual manifests are skipped"
+            );
+            let project = check_project(&core, &mut registry).unwrap();
+            assert_eq!(project.edition, "2021");
+            assert_eq!(project.rust_version.as_deref(), Some("1.74"));
+            assert!(project.findings.is_empty());
+        }
+    }
+
+    mod requirements {
+        use super::*;
+
+        #[test]
+        fn finds_the_dependency_needing_the_newest_rust() {
+            let temp_dir = TempDir::new().unwrap();
+            let src = temp_dir.path().join("registry/src");
+            downloaded(&src, "clap", "4.5.4", Some("1.74"));
+            downloaded(&src, "serde", "1.0.200", Some("1.31"));
+            downloaded(&src, "tokio", "1.37.0", Some("1.63"));
+            let mut registry = Registry::new(Some(src));
+            let lock: toml::Value = toml::from_str(LOCKFILE).unwrap();
+
+            let requirement = highest_requirement(&lock, &mut registry).unwrap();
+
+            assert_eq!(requirement.dependency, "clap");
+            assert_eq!(requirement.version, "4.5.4");
+            assert_eq!(requirement.rust_version, "1.74");
+        }
+
+        #[test]
+        fn flags_msrv_below_dependency_requirements() {
+            let temp_dir = TempDir::new().unwrap();
+            let src = temp_dir.path().join("registry/src");
+            downloaded(&src, "tokio", "1.37.0", Some("1.63"));
+            downloaded(&src, "clap", "4.5.4", None);
+            let mut registry = Registry::new(Some(src));
+            let dir = temp_dir.path().join("api");
+            fs::create_dir(&dir).unwrap();
+            fs::write(
+                dir.join("Cargo.toml"),
+                "[package]\nname = \"api\"\nedition = \"2021\"\nrust-version = \"1.60\"\n",
+            )
+            .unwrap();
+            fs::write(dir.join("Cargo.lock"), LOCKFILE).unwrap();
+
+            let project = check_project(&dir, &mut registry).unwrap();
+
+            assert_eq!(
+                project.findings,
+                vec![CargoFinding::RustVersionTooLow {
+                    declared: "1.60".to_string(),
+                    required: "1.63".to_string(),
+                    dependency: "tokio".to_string(),
+                }]
+            );
+        }
+
+        #[test]
+        fn compares_rust_versions_numerically() {
+            assert!(rust_version_key("1.74") > rust_version_key("1.8"));
+            assert!(rust_version_key("1.74.1") > rust_version_key("1.74"));
+            assert_eq!(rust_version_key("1.70"), rust_version_key("1.70.0"));
+        }
+    }
+
+    mod features {
+        use super::*;
+
+        const MANIFEST: &str = r#"[package]
+name = "api"
+
+[dependencies]
+serde = { version = "1", optional = true }
+tls = { package = "rustls", version = "0.23", optional = true }
+tokio = { version = "1", optional = true }
+
+[features]
+default = ["server"]
+server = ["dep:tokio", "json"]
+json = ["serde/derive"]
+metrics = ["tls?/logging"]
+nightly = []
+simd = []
+"#;
+
+        fn analyze(in_code: &[&str]) -> Vec<Feature> {
+            let manifest: toml::Value = toml::from_str(MANIFEST).unwrap();
+            let in_code = in_code.iter().map(|name| name.to_string()).collect();
+            analyze_features(&manifest, &optional_dependencies(&manifest), &in_code)
+        }
+
+        #[test]
+        fn resolves_dependencies_through_other_features() {
+            let features = analyze(&[]);
+            let dependencies = |name: &str| {
+                features
+                    .iter()
+                    .find(|f| f.name == name)
+                    .map(|f| f.dependencies.clone())
+                    .unwrap()
+            };
+
+            assert_eq!(dependencies("default"), vec!["serde", "tokio"]);
+            assert_eq!(dependencies("json"), vec!["serde"]);
+            assert!(
+                dependencies("metrics").is_empty(),
+                "Weak features enable nothing"
+            );
+        }
+
+        #[test]
+        fn finds_references_in_features_and_code() {
+            let features = analyze(&["simd"]);
+            let unused: Vec<&str> = features
+                .iter()
+                .filter(|f| f.name != "default" && f.dependencies.is_empty() && !f.referenced)
+                .map(|f| f.name.as_str())
+                .collect();
+            assert_eq!(unused, vec!["metrics", "nightly"]);
+
+            let referenced = analyze(&["CARGO_FEATURE_NIGHTLY"]);
+            assert!(referenced
+                .iter()
+                .any(|f| f.name == "nightly" && f.referenced));
+        }
+
+        #[test]
+        fn collects_feature_references_from_sources() {
+            let mut references = BTreeSet::new();
+            collect_feature_references(
+                "#[cfg(feature = \"simd\")]\nfn fast() {}\n#[cfg(all(unix, feature=\"tls\"))]\nmod tls;\n",
+                &mut references,
+            );
+            collect_feature_references(
+                "if env::var(\"CARGO_FEATURE_NIGHTLY\").is_ok() {}",
+                &mut references,
+            );
+            assert_eq!(
+                references.into_iter().collect::<Vec<_>>(),
+                vec!["CARGO_FEATURE_NIGHTLY", "simd", "tls"]
+            );
+        }
+
+        #[test]
+        fn maps_renamed_optional_dependencies_to_packages() {
+            let manifest: toml::Value = toml::from_str(MANIFEST).unwrap();
+            let optional = optional_dependencies(&manifest);
+            assert_eq!(optional.get("tls").map(String::as_str), Some("rustls"));
+            assert_eq!(optional.len(), 3);
+        }
+
+        #[test]
+        fn counts_transitive_crates_from_the_lockfile() {
+            let lock: toml::Value = toml::from_str(
+                r#"
+[[package]]
+name = "tokio"
+version = "1.37.0"
+dependencies = ["bytes", "mio 0.8.11"]
+
+[[package]]
+name = "mio"
+version = "0.8.11"
+dependencies = ["libc"]
+
+[[package]]
+name = "mio"
+version = "1.0.0"
+
+[[package]]
+name = "bytes"
+version = "1.6.0"
+
+[[package]]
+name = "libc"
+version = "0.2.155"
+dependencies = ["bytes"]
+"#,
+            )
+            .unwrap();
+
+            assert_eq!(transitive_crates(&lock, "tokio"), Some(3));
+            assert_eq!(transitive_crates(&lock, "bytes"), Some(0));
+            assert_eq!(transitive_crates(&lock, "serde"), None);
+        }
+
+        #[test]
+        fn flags_unused_features_and_heavy_defaults() {
+            let temp_dir = TempDir::new().unwrap();
+            let mut registry = Registry::new(None);
+            let dir = temp_dir.path();
+            fs::write(
+                dir.join("Cargo.toml"),
+                "[package]\nname = \"api\"\nedition = \"2021\"\nrust-version = \"1.70\"\n\n\
+                 [dependencies]\nheavy = { version = \"1\", optional = true }\n\n\
+                 [features]\ndefault = [\"heavy\"]\nunused = []\n",
+            )
+            .unwrap();
+            let mut lock = String::from(
+                "[[package]]\nname = \"heavy\"\nversion = \"1.0.0\"\ndependencies = [",
+            );
+            let crates: Vec<String> = (0..HEAVY_DEPENDENCY_CRATES)
+                .map(|i| format!("\"dep{}\"", i))
+                .collect();
+            lock.push_str(&crates.join(", "));
+            lock.push_str("]\n");
+            for i in 0..HEAVY_DEPENDENCY_CRATES {
+                lock.push_str(&format!(
+                    "\n[[package]]\nname = \"dep{}\"\nversion = \"1.0.0\"\n",
+                    i
+                ));
+            }
+            fs::write(dir.join("Cargo.lock"), lock).unwrap();
+
+            let project = check_project(dir, &mut registry).unwrap();
+
+            assert_eq!(
+                project.findings,
+                vec![
+                    CargoFinding::UnusedFeature {
+                        feature: "unused".to_string()
+                    },
+                    CargoFinding::HeavyDefaultDependency {
+                        dependency: "heavy".to_string(),
+                        crates: HEAVY_DEPENDENCY_CRATES,
+                    },
+                ]
+            );
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! CA certificate bundles
+//!
+//! Corporate proxy installers and hand-edited shell profiles regularly leave
+//! TLS clients with a broken trust store: `SSL_CERT_FILE` pointing at a file
+//! that was deleted, or at a file holding only the corporate root so every
+//! public site fails to verify. `scan --system` looks at:
+//!
+//! - the system bundle on Linux and macOS (`/etc/ssl/certs/ca-certificates.crt`,
+//!   `/etc/pki/tls/certs/ca-bundle.crt`, `/etc/ssl/cert.pem`, …); Windows keeps
+//!   its store in the registry and is not checked
+//! - the bundles named by `SSL_CERT_FILE`, `SSL_CERT_DIR`,
+//!   `REQUESTS_CA_BUNDLE`, `CURL_CA_BUNDLE`, `GIT_SSL_CAINFO` and
+//!   `NODE_EXTRA_CA_CERTS`
+//!
+//! Only obvious breakage is reported: a bundle that does not exist, holds no
+//! PEM certificate, ends in the middle of one, or (for variables that replace
+//! the trust store rather than add to it) holds too few certificates to
+//! include the public roots. Certificates are counted, not parsed or
+//! validated.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// System bundle locations, the first existing one is used
+const SYSTEM_BUNDLES: [&str; 5] = [
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/ca-bundle.pem",
+    "/etc/ssl/cert.pem",
+    "/etc/pki/ca-trust/extracted/pem/tls-ca-bundle.pem",
+];
+
+/// Environment variables naming a bundle, and whether it replaces the
+/// trust store (`true`) or adds to it (`false`)
+const BUNDLE_VARIABLES: [(&str, bool); 6] = [
+    ("SSL_CERT_FILE", true),
+    ("SSL_CERT_DIR", true),
+    ("REQUESTS_CA_BUNDLE", true),
+    ("CURL_CA_BUNDLE", true),
+    ("GIT_SSL_CAINFO", true),
+    ("NODE_EXTRA_CA_CERTS", false),
+];
+
+/// Fewest certificates a bundle replacing the trust store should hold
+///
+/// Public root programs ship well over a hundred roots; a bundle with fewer
+/// than this holds little more than a corporate root.
+pub const MIN_ROOT_CERTIFICATES: usize = 20;
+
+const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+const END: &str = "-----END CERTIFICATE-----";
+
+/// What is wrong with a bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleProblem {
+    /// The file or directory does not exist
+    Missing,
+    /// It holds no PEM certificate
+    Empty,
+    /// A certificate has no end marker
+    Truncated,
+    /// It replaces the trust store but holds too few certificates
+    Incomplete,
+}
+
+impl BundleProblem {
+    /// Returns the problem's name as used in findings
+    pub fn name(self) -> &'static str {
+        match self {
+            BundleProblem::Missing => "missing",
+            BundleProblem::Empty => "empty",
+            BundleProblem::Truncated => "truncated",
+            BundleProblem::Incomplete => "incomplete",
+        }
+    }
+}
+
+/// A CA bundle and what was found in it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaBundle {
+    /// File or directory of the bundle
+    pub path: PathBuf,
+    /// Environment variable naming it, `None` for the system bundle
+    pub variable: Option<String>,
+    /// Number of PEM certificates it holds
+    pub certificates: usize,
+    /// What is wrong with it, if anything
+    pub problem: Option<BundleProblem>,
+}
+
+/// Checks the system bundle and the bundles named in the environment
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::certs;
+///
+/// for bundle in certs::check_bundles() {
+///     println!("{}: {} certificates", bundle.path.display(), bundle.certificates);
+/// }
+/// ```
+pub fn check_bundles() -> Vec<CaBundle> {
+    let mut bundles = Vec::new();
+
+    if cfg!(unix) {
+        let system = SYSTEM_BUNDLES
+            .iter()
+            .map(Path::new)
+            .find(|path| path.is_file());
+        bundles.push(match system {
+            Some(path) => inspect(path, None, true),
+            None => CaBundle {
+                path: PathBuf::from(SYSTEM_BUNDLES[0]),
+                variable: None,
+                certificates: 0,
+                problem: Some(BundleProblem::Missing),
+            },
+        });
+    }
+
+    for (variable, replaces) in BUNDLE_VARIABLES {
+        if let Some(path) = std::env::var_os(variable).filter(|value| !value.is_empty()) {
+            bundles.push(inspect(Path::new(&path), Some(variable), replaces));
+        }
+    }
+    bundles
+}
+
+/// Counts the certificates of a bundle file or directory and judges them
+fn inspect(path: &Path, variable: Option<&str>, replaces: bool) -> CaBundle {
+    let counted = if path.is_dir() {
+        std::fs::read_dir(path).ok().map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_file())
+                .filter_map(|entry| std::fs::read(entry.path()).ok())
+                .map(|content| count_certificates(&String::from_utf8_lossy(&content)))
+                .fold((0, false), |(total, truncated), (count, cut)| {
+                    (total + count, truncated || cut)
+                })
+        })
+    } else {
+        std::fs::read(path)
+            .ok()
+            .map(|content| count_certificates(&String::from_utf8_lossy(&content)))
+    };
+
+    let (certificates, truncated) = counted.unwrap_or((0, false));
+    let problem = match counted {
+        None => Some(BundleProblem::Missing),
+        Some(_) if truncated => Some(BundleProblem::Truncated),
+        Some((0, _)) => Some(BundleProblem::Empty),
+        Some((count, _)) if replaces && count < MIN_ROOT_CERTIFICATES => {
+            Some(BundleProblem::Incomplete)
+        }
+        Some(_) => None,
+    };
+    CaBundle {
+        path: path.to_path_buf(),
+        variable: variable.map(str::to_string),
+        certificates,
+        problem,
+    }
+}
+
+/// Counts complete PEM certificates, noting whether one is cut off
+fn count_certificates(content: &str) -> (usize, bool) {
+    let mut complete = 0;
+    let mut open = false;
+    let mut truncated = false;
+    for line in content.lines().map(str::trim) {
+        if line == BEGIN {
+            truncated |= open;
+            open = true;
+        } else if line == END && open {
+            complete += 1;
+            open = false;
+        }
+    }
+    (complete, truncated || open)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn pem(count: usize) -> String {
+        format!("{}\nMIIB\n{}\n", BEGIN, END).repeat(count)
+    }
+
+    #[test]
+    fn counts_complete_certificates() {
+        assert_eq!(count_certificates(&pem(3)), (3, false));
+        assert_eq!(
+            count_certificates(&format!("{}{}\nMIIB\n", pem(2), BEGIN)),
+            (2, true)
+        );
+        assert_eq!(count_certificates("not a certificate"), (0, false));
+    }
+
+    #[test]
+    fn judges_bundles() {
+        let dir = TempDir::new().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+
+        let full = write("full.pem", &pem(MIN_ROOT_CERTIFICATES));
+        assert_eq!(inspect(&full, Some("SSL_CERT_FILE"), true).problem, None);
+
+        let corporate = write("corporate.pem", &pem(1));
+        let bundle = inspect(&corporate, Some("SSL_CERT_FILE"), true);
+        assert_eq!(
+            (bundle.certificates, bundle.problem),
+            (1, Some(BundleProblem::Incomplete))
+        );
+        assert_eq!(
+            inspect(&corporate, Some("NODE_EXTRA_CA_CERTS"), false).problem,
+            None
+        );
+
+        let empty = write("empty.pem", "");
+        assert_eq!(
+            inspect(&empty, None, true).problem,
+            Some(BundleProblem::Empty)
+        );
+        let cut = write("cut.pem", &format!("{}{}\n", pem(30), BEGIN));
+        assert_eq!(
+            inspect(&cut, None, true).problem,
+            Some(BundleProblem::Truncated)
+        );
+        assert_eq!(
+            inspect(&dir.path().join("gone.pem"), Some("CURL_CA_BUNDLE"), true).problem,
+            Some(BundleProblem::Missing)
+        );
+    }
+
+    #[test]
+    fn counts_certificate_directories() {
+        let dir = TempDir::new().unwrap();
+        for index in 0..MIN_ROOT_CERTIFICATES {
+            fs::write(dir.path().join(format!("{:08x}.0", index)), pem(1)).unwrap();
+        }
+        let bundle = inspect(dir.path(), Some("SSL_CERT_DIR"), true);
+        assert_eq!(
+            (bundle.certificates, bundle.problem),
+            (MIN_ROOT_CERTIFICATES, None)
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Clock synchronization
+//!
+//! A clock that is off by minutes breaks more than it seems: TLS handshakes
+//! fail on certificates "not yet valid", freshly issued JWTs are rejected,
+//! and build caches rebuild (or worse, skip) because file times lie.
+//! `scan --system` checks the clock two ways:
+//!
+//! - whether the operating system reports it as synchronized, on Linux via
+//!   `timedatectl` and on Windows via `w32tm /query /status`
+//! - its offset from the `Date` header of a plain HTTP request, measured at
+//!   the midpoint of the round trip; skipped in offline mode
+//!
+//! The reference is fetched over HTTP rather than HTTPS, so a skewed clock
+//! or a broken certificate store cannot stop the measurement. `Date` has a
+//! resolution of one second, so offsets below a few seconds are noise.
+
+use crate::acks;
+use crate::scanner::container;
+use crate::utils::net::NetworkGate;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Name of the clock offset check as recorded by `--offline`
+pub const CLOCK_CHECK: &str = "clock skew";
+
+/// Server whose `Date` header serves as the reference time
+const REFERENCE_URL: &str = "http://www.google.com/generate_204";
+
+/// Offset from which the clock counts as skewed, in seconds
+///
+/// Matches the leeway most JWT libraries allow.
+pub const SKEW_THRESHOLD_SECS: i64 = 60;
+
+/// Synchronization state and measured offset of the system clock
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockReport {
+    /// Whether the operating system reports the clock as synchronized
+    pub synchronized: Option<bool>,
+    /// Seconds the local clock is ahead of the reference (negative: behind)
+    pub offset_secs: Option<i64>,
+    /// Where the reference time came from
+    pub reference: Option<String>,
+}
+
+impl ClockReport {
+    /// Returns `true` if the measured offset reaches [`SKEW_THRESHOLD_SECS`]
+    pub fn is_skewed(&self) -> bool {
+        self.offset_secs
+            .is_some_and(|offset| offset.abs() >= SKEW_THRESHOLD_SECS)
+    }
+}
+
+/// Checks whether the clock is synchronized and measures its offset
+///
+/// The offset is only measured when `network` allows it.
+pub fn check_clock(network: &mut NetworkGate) -> ClockReport {
+    let offset_secs = if network.allow(CLOCK_CHECK) {
+        measure_offset()
+    } else {
+        None
+    };
+    ClockReport {
+        synchronized: synchronized(),
+        reference: offset_secs.map(|_| REFERENCE_URL.to_string()),
+        offset_secs,
+    }
+}
+
+/// Asks the operating system whether the clock is synchronized
+fn synchronized() -> Option<bool> {
+    if cfg!(target_os = "linux") {
+        let output = container::run_command(
+            "timedatectl",
+            &["show", "--property=NTPSynchronized", "--value"],
+        )?;
+        match output.as_str() {
+            "yes" => Some(true),
+            "no" => Some(false),
+            _ => None,
+        }
+    } else if cfg!(windows) {
+        let output = container::run_command("w32tm", &["/query", "/status"])?;
+        parse_w32tm_status(&output)
+    } else {
+        None
+    }
+}
+
+/// Reads the leap indicator of `w32tm /query /status`
+///
+/// `3` means the clock is not synchronized; anything else means it is.
+fn parse_w32tm_status(output: &str) -> Option<bool> {
+    let line = output
+        .lines()
+        .find(|line| line.trim_start().starts_with("Leap Indicator"))?;
+    let value = line.split(':').nth(1)?.trim();
+    Some(!value.starts_with('3'))
+}
+
+/// Measures the local clock against the reference server
+fn measure_offset() -> Option<i64> {
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(concat!("devhealth/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(5))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .ok()?;
+
+    let sent = SystemTime::now();
+    let response = client.head(REFERENCE_URL).send().ok()?;
+    let received = SystemTime::now();
+    let server = parse_http_date(response.headers().get("date")?.to_str().ok()?)?;
+
+    let round_trip = received.duration_since(sent).unwrap_or_default();
+    let local = (sent + round_trip / 2)
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_secs_f64();
+    // `Date` is truncated to the second, so the server time is on average
+    // half a second later than it says
+    Some((local - (server as f64 + 0.5)).round() as i64)
+}
+
+/// Parses an HTTP date such as `Sun, 06 Nov 1994 08:49:37 GMT`
+fn parse_http_date(date: &str) -> Option<u64> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let mut words = date.split_whitespace().skip(1);
+    let day: u32 = words.next()?.parse().ok()?;
+    let month_name = words.next()?;
+    let month = MONTHS.iter().position(|month| *month == month_name)? + 1;
+    let year: u32 = words.next()?.parse().ok()?;
+    let mut time = words
+        .next()?
+        .split(':')
+        .map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (time.next()??, time.next()??, time.next()??);
+
+    let midnight = acks::parse_date(&format!("{:04}-{:02}-{:02}", year, month, day)).ok()?;
+    Some(midnight + hours * 3600 + minutes * 60 + seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_http_dates() {
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(784_111_777)
+        );
+        assert_eq!(
+            parse_http_date("Sat, 01 Feb 2025 00:00:00 GMT"),
+            Some(1_738_368_000)
+        );
+        assert_eq!(parse_http_date("yesterday"), None);
+        assert_eq!(parse_http_date("Sun, 06 Foo 1994 08:49:37 GMT"), None);
+    }
+
+    #[test]
+    fn reads_w32tm_leap_indicator() {
+        let synced = "Leap Indicator: 0(no warning)\nStratum: 4 (secondary reference)\n";
+        assert_eq!(parse_w32tm_status(synced), Some(true));
+        let unsynced = "Leap Indicator: 3(not synchronized)\nStratum: 0 (unspecified)\n";
+        assert_eq!(parse_w32tm_status(unsynced), Some(false));
+        assert_eq!(
+            parse_w32tm_status("The service has not been started."),
+            None
+        );
+    }
+
+    #[test]
+    fn flags_offsets_from_the_threshold() {
+        let report = |offset| ClockReport {
+            offset_secs: offset,
+            ..ClockReport::default()
+        };
+        assert!(!report(Some(3)).is_skewed());
+        assert!(report(Some(-SKEW_THRESHOLD_SECS)).is_skewed());
+        assert!(report(Some(600)).is_skewed());
+        assert!(!report(None).is_skewed());
+    }
+}
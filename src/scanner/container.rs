@@ -0,0 +1,714 @@
+//! Dev container and Docker Compose development environments
+//!
+//! Projects that build inside a container often drift from the host: the
+//! host has Rust 1.79 while the container pins 1.75, or Node 22 instead of
+//! the container's Node 18. This scanner finds containerized development
+//! setups and, when their container is running, compares the toolchain on
+//! the host with the toolchain inside the container via `docker exec`.
+//!
+//! Recognized setups:
+//!
+//! - **Dev containers**: `.devcontainer/devcontainer.json`,
+//!   `.devcontainer/<name>/devcontainer.json` and `.devcontainer.json`
+//! - **Compose dev setups**: `docker-compose.dev.yml`, `compose.dev.yaml` and
+//!   the other `.dev` variants of the Compose file names
+//!
+//! Running containers are found by the labels the Dev Containers CLI
+//! (`devcontainer.local_folder`) and Docker Compose
+//! (`com.docker.compose.project.working_dir`) put on them. DevHealth never
+//! starts, stops or builds containers.
+
+use crate::i18n::t;
+use crate::scanner::deps::{self, Ecosystem};
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+/// Compose file names that describe a development setup
+const COMPOSE_DEV_FILES: [&str; 4] = [
+    "docker-compose.dev.yml",
+    "docker-compose.dev.yaml",
+    "compose.dev.yml",
+    "compose.dev.yaml",
+];
+
+/// Tools compared per ecosystem, with the arguments that print their version
+const TOOLS: [(Ecosystem, &str, &[&str]); 6] = [
+    (Ecosystem::Rust, "rustc", &["--version"]),
+    (Ecosystem::Rust, "cargo", &["--version"]),
+    (Ecosystem::NodeJs, "node", &["--version"]),
+    (Ecosystem::NodeJs, "npm", &["--version"]),
+    (Ecosystem::Python, "python3", &["--version"]),
+    (Ecosystem::Go, "go", &["version"]),
+];
+
+/// Errors that can occur while reading a development environment
+#[derive(Error, Debug)]
+pub enum ContainerError {
+    #[error("Failed to read file: {0}")]
+    FileRead(#[from] std::io::Error),
+    #[error("Failed to parse devcontainer.json: {0}")]
+    JsonParse(#[from] serde_json::Error),
+}
+
+/// How a development environment is containerized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EnvironmentKind {
+    /// A Dev Containers configuration (`devcontainer.json`)
+    DevContainer,
+    /// A Docker Compose development file
+    Compose,
+}
+
+/// A containerized development setup found in a project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DevEnvironment {
+    /// Project directory the container is defined for
+    pub project_path: PathBuf,
+    /// How the environment is defined
+    pub kind: EnvironmentKind,
+    /// The `devcontainer.json` or Compose file
+    pub config_file: PathBuf,
+    /// Display name from `devcontainer.json`
+    pub name: Option<String>,
+    /// Image the container is created from, if configured directly
+    pub image: Option<String>,
+    /// Compose service the dev container attaches to
+    pub service: Option<String>,
+}
+
+/// Whether the container of an environment could be inspected
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerState {
+    /// A container is running; its tools were compared
+    Running {
+        /// Short ID of the container
+        id: String,
+    },
+    /// No running container belongs to the environment
+    NotRunning,
+    /// The `docker` CLI is missing or cannot reach the daemon
+    DockerUnavailable,
+}
+
+/// Version of one tool on the host and inside the container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolVersion {
+    /// Command name, e.g. `rustc`
+    pub tool: String,
+    /// Version on the host, `None` if the tool is not installed there
+    pub host: Option<String>,
+    /// Version in the container, `None` if missing or not inspected
+    pub container: Option<String>,
+}
+
+impl ToolVersion {
+    /// Returns `true` if the tool exists in both places with different versions
+    pub fn differs(&self) -> bool {
+        matches!((&self.host, &self.container), (Some(host), Some(container)) if host != container)
+    }
+}
+
+/// Result of checking one development environment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContainerReport {
+    /// The environment that was checked
+    pub environment: DevEnvironment,
+    /// Whether its container could be inspected
+    pub state: ContainerState,
+    /// Toolchain versions for the ecosystems found in the project
+    pub tools: Vec<ToolVersion>,
+    /// Problems reading the environment's configuration
+    pub errors: Vec<String>,
+}
+
+impl ContainerReport {
+    /// Returns the number of tools whose host and container versions differ
+    pub fn mismatches(&self) -> usize {
+        self.tools.iter().filter(|tool| tool.differs()).count()
+    }
+}
+
+/// Runs a program and returns its trimmed output if it succeeded
+type Runner<'a> = &'a dyn Fn(&str, &[&str]) -> Option<String>;
+
+/// Finds dev container and Compose dev setups in projects accepted by a filter
+///
+/// Configuration files that cannot be parsed still produce an environment;
+/// the parse error is reported by [`check_environments`].
+///
+/// # Arguments
+///
+/// * `path` - The directory to search
+/// * `include` - Predicate deciding whether a project directory is included
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::container;
+/// use std::path::Path;
+///
+/// for environment in container::find_environments_matching(Path::new("."), |_| true) {
+///     println!("{}", environment.config_file.display());
+/// }
+/// ```
+pub fn find_environments_matching<F>(path: &Path, include: F) -> Vec<DevEnvironment>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut environments = Vec::new();
+
+    for entry in dh_fs::walk(path) {
+        let Some((project, kind)) = detect_environment_file(entry.path()) else {
+            continue;
+        };
+        let project_path = dh_fs::strip_extended_length(&project);
+        if !include(&project_path) {
+            continue;
+        }
+
+        let config = match kind {
+            EnvironmentKind::DevContainer => read_devcontainer(entry.path()).unwrap_or_default(),
+            EnvironmentKind::Compose => DevContainerConfig::default(),
+        };
+        environments.push(DevEnvironment {
+            project_path,
+            kind,
+            config_file: dh_fs::strip_extended_length(entry.path()),
+            name: config.name,
+            image: config.image,
+            service: config.service,
+        });
+    }
+
+    environments
+}
+
+/// Compares host and container toolchains of each environment
+///
+/// Tools are chosen from the ecosystems detected in each project. Stopped
+/// containers are reported without starting them, and a missing `docker`
+/// CLI is detected once for the whole scan.
+///
+/// # Arguments
+///
+/// * `environments` - Environments found by [`find_environments_matching`]
+pub fn check_environments(environments: Vec<DevEnvironment>) -> Vec<ContainerReport> {
+    check_environments_with(environments, &run_command)
+}
+
+/// Compares toolchains using `run` to execute `docker` and the host tools
+fn check_environments_with(environments: Vec<DevEnvironment>, run: Runner) -> Vec<ContainerReport> {
+    if environments.is_empty() {
+        return Vec::new();
+    }
+    let docker_available = run("docker", &["version", "--format", "{{.Server.Version}}"]).is_some();
+
+    environments
+        .into_iter()
+        .map(|environment| {
+            let errors = match environment.kind {
+                EnvironmentKind::DevContainer => read_devcontainer(&environment.config_file)
+                    .err()
+                    .map(|e| e.to_string())
+                    .into_iter()
+                    .collect(),
+                EnvironmentKind::Compose => Vec::new(),
+            };
+            let state = if docker_available {
+                find_container(&environment, run)
+                    .map(|id| ContainerState::Running { id })
+                    .unwrap_or(ContainerState::NotRunning)
+            } else {
+                ContainerState::DockerUnavailable
+            };
+
+            let ecosystems = deps::detect_all_ecosystems(&environment.project_path);
+            let tools = TOOLS
+                .iter()
+                .filter(|(ecosystem, _, _)| ecosystems.contains(ecosystem))
+                .map(|(_, tool, args)| ToolVersion {
+                    tool: tool.to_string(),
+                    host: run(tool, args).as_deref().and_then(extract_version),
+                    container: match &state {
+                        ContainerState::Running { id } => {
+                            let command = [&["exec", id.as_str(), *tool], *args].concat();
+                            run("docker", &command).as_deref().and_then(extract_version)
+                        }
+                        _ => None,
+                    },
+                })
+                .collect();
+
+            ContainerReport {
+                environment,
+                state,
+                tools,
+                errors,
+            }
+        })
+        .collect()
+}
+
+/// Displays container scan results at the given level of detail
+///
+/// - [`Detail::Summary`]: header and summary box only
+/// - [`Detail::Minimal`]: one line per environment plus its errors
+/// - [`Detail::Normal`]: every compared tool with differing versions
+///   highlighted
+/// - [`Detail::Full`]: also the configuration file of each environment
+///
+/// # Arguments
+///
+/// * `reports` - Slice of container reports to display
+/// * `detail` - How much of the report to print
+pub fn display_results_with(reports: &[ContainerReport], detail: Detail) {
+    if reports.is_empty() {
+        println!(
+            "{}",
+            display::header(&tr!("containers-none"), "🐳", Role::Warning)
+        );
+        return;
+    }
+
+    let running = reports
+        .iter()
+        .filter(|r| matches!(r.state, ContainerState::Running { .. }))
+        .count();
+    let mismatches: usize = reports.iter().map(ContainerReport::mismatches).sum();
+    let role = if mismatches > 0 {
+        Role::Warning
+    } else {
+        Role::Special
+    };
+
+    println!(
+        "{}",
+        display::header(&tr!("containers-title", count = reports.len()), "🐳", role)
+    );
+
+    let summary_items = vec![
+        (t("containers-environments"), reports.len().to_string()),
+        (t("containers-running"), running.to_string()),
+        (
+            t("containers-mismatches"),
+            if mismatches > 0 {
+                format!("{} ⚠️", mismatches)
+            } else {
+                "0".to_string()
+            },
+        ),
+    ];
+    print!("{}", display::summary_box(&summary_items));
+
+    if detail == Detail::Summary {
+        return;
+    }
+
+    println!("{}", display::section_divider(t("containers-details")));
+    for (index, report) in reports.iter().enumerate() {
+        let is_last = index == reports.len() - 1;
+        let environment = &report.environment;
+        let name = environment.name.clone().unwrap_or_else(|| {
+            environment
+                .project_path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "unknown".to_string())
+        });
+        let kind = match environment.kind {
+            EnvironmentKind::DevContainer => "devcontainer",
+            EnvironmentKind::Compose => "compose",
+        };
+        let state = match &report.state {
+            ContainerState::Running { id } => {
+                tr!("containers-state-running", id = id).paint(Role::Success)
+            }
+            ContainerState::NotRunning => t("containers-state-stopped").paint(Role::Muted),
+            ContainerState::DockerUnavailable => {
+                t("containers-state-no-docker").paint(Role::Warning)
+            }
+        };
+
+        let header = format!(
+            "{} {} {} {}",
+            "🐳",
+            name.paint(Role::Emphasis).bold(),
+            format!("[{}]", kind).paint(Role::Muted),
+            state
+        );
+        println!("{}", display::tree_item(&header, is_last, 0));
+
+        let mut lines: Vec<String> = report
+            .errors
+            .iter()
+            .map(|error| format!("{} {}", "✗".paint(Role::Error), error.paint(Role::Error)))
+            .collect();
+        if detail >= Detail::Normal {
+            lines.extend(report.tools.iter().map(tool_line));
+        }
+        if detail == Detail::Full {
+            lines.push(display::file_path(
+                &environment.config_file.to_string_lossy(),
+            ));
+        }
+
+        for (line_index, line) in lines.iter().enumerate() {
+            println!(
+                "{}",
+                display::tree_item(line, line_index == lines.len() - 1, 1)
+            );
+        }
+    }
+}
+
+/// Formats one tool comparison, highlighting differing versions
+fn tool_line(tool: &ToolVersion) -> String {
+    let version = |version: &Option<String>| version.clone().unwrap_or_else(|| "—".to_string());
+    let comparison = tr!(
+        "containers-tool",
+        host = version(&tool.host),
+        container = version(&tool.container)
+    );
+
+    if tool.differs() {
+        format!(
+            "{} {} {}",
+            "⚠️".paint(Role::Warning),
+            tool.tool.paint(Role::Highlight).bold(),
+            comparison.paint(Role::Warning)
+        )
+    } else {
+        format!(
+            "{} {}",
+            tool.tool.paint(Role::Highlight),
+            comparison.paint(Role::Muted)
+        )
+    }
+}
+
+/// Fields of `devcontainer.json` DevHealth reads
+#[derive(Debug, Default, Deserialize)]
+struct DevContainerConfig {
+    name: Option<String>,
+    image: Option<String>,
+    service: Option<String>,
+}
+
+/// Returns the project directory and kind of a development environment file
+pub(crate) fn detect_environment_file(path: &Path) -> Option<(PathBuf, EnvironmentKind)> {
+    let file_name = path.file_name()?.to_str()?;
+    let parent = path.parent()?;
+    let in_devcontainer_dir =
+        |dir: &Path| dir.file_name().is_some_and(|name| name == ".devcontainer");
+
+    match file_name {
+        ".devcontainer.json" => Some((parent.to_path_buf(), EnvironmentKind::DevContainer)),
+        "devcontainer.json" if in_devcontainer_dir(parent) => Some((
+            parent.parent()?.to_path_buf(),
+            EnvironmentKind::DevContainer,
+        )),
+        "devcontainer.json" if parent.parent().is_some_and(in_devcontainer_dir) => Some((
+            parent.parent()?.parent()?.to_path_buf(),
+            EnvironmentKind::DevContainer,
+        )),
+        name if COMPOSE_DEV_FILES.contains(&name) => {
+            Some((parent.to_path_buf(), EnvironmentKind::Compose))
+        }
+        _ => None,
+    }
+}
+
+/// Reads `devcontainer.json`, which allows comments and trailing commas
+fn read_devcontainer(path: &Path) -> Result<DevContainerConfig, ContainerError> {
+    let source = std::fs::read_to_string(dh_fs::extended_length(path))?;
+    Ok(serde_json::from_str(&strip_jsonc(&source))?)
+}
+
+/// Removes comments and trailing commas from JSON with comments (JSONC)
+fn strip_jsonc(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut output = String::with_capacity(source.len());
+    let mut in_string = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        i += 1;
+
+        if in_string {
+            output.push(c);
+            match c {
+                '\\' => {
+                    output.extend(next);
+                    i += 1;
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, next) {
+            ('"', _) => {
+                in_string = true;
+                output.push(c);
+            }
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                i += 1;
+                while i < chars.len() && !(chars[i - 1] == '*' && chars[i] == '/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            (',', _) => {
+                if !matches!(next_token(&chars[i..]), Some('}' | ']')) {
+                    output.push(c);
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+
+    output
+}
+
+/// Returns the next character that is neither whitespace nor in a comment
+fn next_token(chars: &[char]) -> Option<char> {
+    let mut i = 0;
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            (c, _) if c.is_whitespace() => i += 1,
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                while i < chars.len() && !(chars[i - 1] == '*' && chars[i] == '/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            (c, _) => return Some(c),
+        }
+    }
+    None
+}
+
+/// Finds the running container of an environment by its labels
+fn find_container(environment: &DevEnvironment, run: Runner) -> Option<String> {
+    // Both labels hold the absolute project path
+    let project = dh_fs::canonicalize(&environment.project_path)
+        .unwrap_or_else(|_| environment.project_path.clone());
+    let project = project.to_string_lossy();
+    let label = match environment.kind {
+        EnvironmentKind::DevContainer => format!("label=devcontainer.local_folder={}", project),
+        EnvironmentKind::Compose => {
+            format!("label=com.docker.compose.project.working_dir={}", project)
+        }
+    };
+
+    let output = run("docker", &["ps", "--quiet", "--filter", &label])?;
+    output.lines().next().map(str::to_string)
+}
+
+/// Extracts the version number from a tool's `--version` output
+///
+/// Handles `rustc 1.79.0 (129f3b996 2024-06-10)`, `v20.11.1`,
+/// `Python 3.12.3`, `go version go1.22.4 linux/amd64` and
+/// `Docker version 28.2.2, build e6534b4`.
+pub(crate) fn extract_version(output: &str) -> Option<String> {
+    output.split_whitespace().find_map(|word| {
+        let version = word
+            .trim_start_matches("go")
+            .trim_start_matches('v')
+            .trim_end_matches(',');
+        let starts_with_digit = version.chars().next().is_some_and(|c| c.is_ascii_digit());
+        (starts_with_digit && version.contains('.')).then(|| version.to_string())
+    })
+}
+
+/// Runs a program and returns its trimmed output if it exits successfully
+///
+/// Falls back to stderr for tools that print their version there.
+pub(crate) fn run_command(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if stdout.is_empty() {
+        Some(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    } else {
+        Some(stdout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn environment(project: &Path, kind: EnvironmentKind) -> DevEnvironment {
+        DevEnvironment {
+            project_path: project.to_path_buf(),
+            kind,
+            config_file: project.join("docker-compose.dev.yml"),
+            name: None,
+            image: None,
+            service: None,
+        }
+    }
+
+    mod discovery {
+        use super::*;
+
+        #[test]
+        fn finds_devcontainer_and_compose_setups() {
+            let temp_dir = TempDir::new().unwrap();
+            let api = temp_dir.path().join("api");
+            fs::create_dir_all(api.join(".devcontainer")).unwrap();
+            fs::write(
+                api.join(".devcontainer/devcontainer.json"),
+                "{\n  // Rust toolchain\n  \"name\": \"API\",\n  \"image\": \"rust:1.75\",\n}\n",
+            )
+            .unwrap();
+            let web = temp_dir.path().join("web");
+            fs::create_dir_all(&web).unwrap();
+            fs::write(web.join("compose.dev.yaml"), "services: {}\n").unwrap();
+            fs::write(web.join("docker-compose.yml"), "services: {}\n").unwrap();
+
+            let mut environments = find_environments_matching(temp_dir.path(), |_| true);
+            environments.sort_by(|a, b| a.project_path.cmp(&b.project_path));
+
+            assert_eq!(environments.len(), 2);
+            assert_eq!(environments[0].project_path, api);
+            assert_eq!(environments[0].kind, EnvironmentKind::DevContainer);
+            assert_eq!(environments[0].name.as_deref(), Some("API"));
+            assert_eq!(environments[0].image.as_deref(), Some("rust:1.75"));
+            assert_eq!(environments[1].project_path, web);
+            assert_eq!(environments[1].kind, EnvironmentKind::Compose);
+        }
+
+        #[test]
+        fn maps_configuration_files_to_projects() {
+            let detect = |path: &str| detect_environment_file(Path::new(path));
+            assert_eq!(
+                detect("/src/api/.devcontainer.json"),
+                Some((PathBuf::from("/src/api"), EnvironmentKind::DevContainer))
+            );
+            assert_eq!(
+                detect("/src/api/.devcontainer/gpu/devcontainer.json"),
+                Some((PathBuf::from("/src/api"), EnvironmentKind::DevContainer))
+            );
+            assert_eq!(detect("/src/api/config/devcontainer.json"), None);
+            assert_eq!(detect("/src/api/docker-compose.yml"), None);
+        }
+
+        #[test]
+        fn strips_comments_and_trailing_commas() {
+            let source = r#"{ "a": "http://x", /* note */ "b": [1, 2,], // end
+}"#;
+            let value: serde_json::Value = serde_json::from_str(&strip_jsonc(source)).unwrap();
+            assert_eq!(value["a"], "http://x");
+            assert_eq!(value["b"], serde_json::json!([1, 2]));
+        }
+    }
+
+    mod toolchains {
+        use super::*;
+
+        #[test]
+        fn extracts_versions_from_tool_output() {
+            assert_eq!(
+                extract_version("rustc 1.79.0 (129f3b996 2024-06-10)").as_deref(),
+                Some("1.79.0")
+            );
+            assert_eq!(extract_version("v20.11.1").as_deref(), Some("20.11.1"));
+            assert_eq!(extract_version("Python 3.12.3").as_deref(), Some("3.12.3"));
+            assert_eq!(
+                extract_version("go version go1.22.4 linux/amd64").as_deref(),
+                Some("1.22.4")
+            );
+            assert_eq!(
+                extract_version("Docker version 28.2.2, build e6534b4").as_deref(),
+                Some("28.2.2")
+            );
+            assert_eq!(extract_version("command not found"), None);
+        }
+
+        #[test]
+        fn compares_host_and_container_versions() {
+            let temp_dir = TempDir::new().unwrap();
+            fs::write(
+                temp_dir.path().join("Cargo.toml"),
+                "[package]\nname = \"api\"\n",
+            )
+            .unwrap();
+
+            let run = |program: &str, args: &[&str]| -> Option<String> {
+                match (program, args) {
+                    ("docker", ["version", ..]) => Some("27.0.3".to_string()),
+                    ("docker", ["ps", ..]) => Some("4f2a9c\n".to_string()),
+                    ("docker", ["exec", "4f2a9c", "rustc", ..]) => {
+                        Some("rustc 1.75.0 (abc 2023-12-21)".to_string())
+                    }
+                    ("docker", ["exec", "4f2a9c", "cargo", ..]) => {
+                        Some("cargo 1.79.0 (ffa9cf99a 2024-06-03)".to_string())
+                    }
+                    ("rustc", _) => Some("rustc 1.79.0 (129f3b996 2024-06-10)".to_string()),
+                    ("cargo", _) => Some("cargo 1.79.0 (ffa9cf99a 2024-06-03)".to_string()),
+                    _ => None,
+                }
+            };
+
+            let reports = check_environments_with(
+                vec![environment(temp_dir.path(), EnvironmentKind::DevContainer)],
+                &run,
+            );
+
+            assert_eq!(
+                reports[0].state,
+                ContainerState::Running {
+                    id: "4f2a9c".to_string()
+                }
+            );
+            assert_eq!(reports[0].tools.len(), 2);
+            assert!(reports[0].tools[0].differs());
+            assert!(!reports[0].tools[1].differs());
+            assert_eq!(reports[0].mismatches(), 1);
+        }
+
+        #[test]
+        fn reports_missing_docker_and_stopped_containers() {
+            let temp_dir = TempDir::new().unwrap();
+            let environments = vec![environment(temp_dir.path(), EnvironmentKind::Compose)];
+
+            let reports = check_environments_with(environments.clone(), &|_, _| None);
+            assert_eq!(reports[0].state, ContainerState::DockerUnavailable);
+
+            let docker_only = |program: &str, args: &[&str]| {
+                (program == "docker" && args[0] == "version").then(|| "27.0.3".to_string())
+            };
+            let reports = check_environments_with(environments, &docker_only);
+            assert_eq!(reports[0].state, ContainerState::NotRunning);
+        }
+    }
+}
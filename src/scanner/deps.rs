@@ -7,19 +7,29 @@
 //! - Node.js (`package.json`, `package-lock.json`)
 //! - Python (`requirements.txt`, `Pipfile`, `pyproject.toml`)
 //! - Go (`go.mod`)
+//! - Bazel (`MODULE.bazel`, `WORKSPACE`), Buck (`BUCK`) and Nix (`flake.nix`,
+//!   `default.nix`) build files, see [`buildfiles`]
 //!
 //! The scanner identifies dependency files, parses them, and provides
 //! health information including outdated packages and potential security issues.
 
-use crate::utils::display;
+use crate::i18n::t;
+use crate::scanner::buildfiles;
+use crate::scanner::gomod::{self, GoModule};
+use crate::scanner::lockfiles::{self, LockfileIssue, LockfileProblem};
+use crate::scanner::pep440::SpecifierSet;
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
 use colored::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use walkdir::WalkDir;
 
 /// Errors that can occur during dependency scanning
 #[derive(Error, Debug)]
@@ -34,6 +44,70 @@ pub enum DependencyError {
     SemverParse(#[from] semver::Error),
     #[error("Unsupported file format: {0}")]
     UnsupportedFormat(String),
+    #[error("Included requirements file not found: {0}")]
+    MissingInclude(PathBuf),
+    #[error("{}{}: {message}", path.display(), position.map(|(line, column)| format!(":{}:{}", line, column)).unwrap_or_default())]
+    Manifest {
+        /// The manifest that failed to read or parse
+        path: PathBuf,
+        /// Line and column of the syntax error, when known
+        position: Option<(usize, usize)>,
+        /// What went wrong
+        message: String,
+    },
+}
+
+impl DependencyError {
+    /// Attaches the manifest `path` to an error, with the line and column of
+    /// TOML and JSON syntax errors in `content`
+    fn in_manifest(self, path: &Path, content: &str) -> Self {
+        let (position, message) = match &self {
+            DependencyError::Manifest { .. } => return self,
+            DependencyError::TomlParse(error) => (
+                error.span().map(|span| line_column(content, span.start)),
+                format!("Failed to parse TOML: {}", error.message().trim_end()),
+            ),
+            DependencyError::JsonParse(error) if error.line() > 0 => {
+                let message = error.to_string();
+                let message = message
+                    .rsplit_once(" at line ")
+                    .map_or(message.as_str(), |(message, _)| message);
+                (
+                    Some((error.line(), error.column())),
+                    format!("Failed to parse JSON: {}", message),
+                )
+            }
+            _ => (None, self.to_string()),
+        };
+        DependencyError::Manifest {
+            path: path.to_path_buf(),
+            position,
+            message,
+        }
+    }
+}
+
+/// Returns the 1-based line and column of a byte offset in `content`
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Reads a manifest, naming it in the error if that fails
+pub(crate) fn read_manifest(path: &Path) -> Result<String, DependencyError> {
+    fs::read_to_string(path).map_err(|error| DependencyError::from(error).in_manifest(path, ""))
+}
+
+/// Parses a TOML manifest, naming it and the error position if that fails
+fn parse_toml_manifest<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    content: &str,
+) -> Result<T, DependencyError> {
+    toml::from_str(content).map_err(|error| DependencyError::from(error).in_manifest(path, content))
 }
 
 /// Represents a project dependency
@@ -49,6 +123,14 @@ pub struct Dependency {
     pub ecosystem: Ecosystem,
     /// File where this dependency was found
     pub source_file: PathBuf,
+    /// Optional features requested with the package, e.g. Python
+    /// `requests[socks]` or Cargo `features = ["derive"]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<String>,
+    /// Environment marker limiting where the dependency is installed, e.g.
+    /// Python `python_version < "3.11"` or Cargo `cfg(windows)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
 }
 
 /// Types of dependencies
@@ -64,8 +146,16 @@ pub enum DependencyType {
     Optional,
 }
 
+impl DependencyType {
+    /// Returns `true` for dependencies that ship with the application:
+    /// runtime and optional ones, as opposed to development and build ones
+    pub fn is_runtime(&self) -> bool {
+        matches!(self, DependencyType::Runtime | DependencyType::Optional)
+    }
+}
+
 /// Supported dependency ecosystems
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Ecosystem {
     /// Rust cargo ecosystem
     Rust,
@@ -75,6 +165,20 @@ pub enum Ecosystem {
     Python,
     /// Go modules ecosystem
     Go,
+    /// Bazel build system
+    Bazel,
+    /// Buck build system
+    Buck,
+    /// Nix flakes and derivations
+    Nix,
+}
+
+impl Ecosystem {
+    /// Returns `true` for build systems, whose files may declare external
+    /// dependencies for several languages at once
+    pub fn is_build_system(&self) -> bool {
+        matches!(self, Ecosystem::Bazel | Ecosystem::Buck | Ecosystem::Nix)
+    }
 }
 
 impl fmt::Display for Ecosystem {
@@ -84,12 +188,44 @@ impl fmt::Display for Ecosystem {
             Ecosystem::NodeJs => write!(f, "Node.js"),
             Ecosystem::Python => write!(f, "Python"),
             Ecosystem::Go => write!(f, "Go"),
+            Ecosystem::Bazel => write!(f, "Bazel"),
+            Ecosystem::Buck => write!(f, "Buck"),
+            Ecosystem::Nix => write!(f, "Nix"),
         }
     }
 }
 
+/// Narrowest space a dependency's source path is truncated to
+const MIN_PATH_WIDTH: usize = 16;
+
+/// Time [`summarize_manifests_matching`] may spend walking a directory tree
+/// during a quick `check`
+pub const MANIFEST_SCAN_BUDGET: Duration = Duration::from_secs(2);
+
+/// Manifest files counted without parsing them, for quick health checks
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManifestSummary {
+    /// Number of project directories holding at least one manifest
+    pub projects: usize,
+    /// Number of manifest files found
+    pub manifests: usize,
+    /// Manifest files per ecosystem, in [`Ecosystem`] declaration order
+    pub ecosystems: Vec<EcosystemCount>,
+    /// `false` if the time budget ran out before the walk finished
+    pub complete: bool,
+}
+
+/// Number of manifest files of one ecosystem
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EcosystemCount {
+    /// The ecosystem
+    pub ecosystem: Ecosystem,
+    /// Manifest files found for it
+    pub manifests: usize,
+}
+
 /// Result of dependency scanning for a project
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DependencyReport {
     /// Path to the project root
     pub project_path: PathBuf,
@@ -99,6 +235,30 @@ pub struct DependencyReport {
     pub ecosystems: Vec<Ecosystem>,
     /// Any errors encountered during scanning
     pub errors: Vec<String>,
+    /// Lockfiles missing or drifted from their manifests
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lockfiles: Vec<LockfileIssue>,
+    /// Go modules and workspaces: versions, replacements and exclusions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub go_modules: Vec<GoModule>,
+}
+
+impl DependencyReport {
+    /// Sorts ecosystems and dependencies so that scans of the same tree
+    /// produce the same report
+    ///
+    /// Dependencies are ordered by ecosystem, name, manifest and version.
+    pub fn sort(&mut self) {
+        self.ecosystems.sort();
+        self.dependencies.sort_by(|a, b| {
+            (&a.ecosystem, &a.name, &a.source_file, &a.version).cmp(&(
+                &b.ecosystem,
+                &b.name,
+                &b.source_file,
+                &b.version,
+            ))
+        });
+    }
 }
 
 /// Scans a directory for dependency files and analyzes them
@@ -131,14 +291,34 @@ pub struct DependencyReport {
 /// Returns an error if the directory cannot be accessed or if there are
 /// critical parsing errors in dependency files.
 pub fn scan_dependencies(path: &Path) -> Result<Vec<DependencyReport>, DependencyError> {
+    scan_dependencies_matching(path, |_| true)
+}
+
+/// Scans a directory for dependency files in projects accepted by a filter
+///
+/// Works like [`scan_dependencies`], but only parses projects whose root
+/// directory is accepted by `include`.
+///
+/// # Arguments
+///
+/// * `path` - The directory to scan for dependency files
+/// * `include` - Predicate deciding whether a project root is analyzed
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be accessed or if there are
+/// critical parsing errors in dependency files.
+pub fn scan_dependencies_matching<F>(
+    path: &Path,
+    include: F,
+) -> Result<Vec<DependencyReport>, DependencyError>
+where
+    F: Fn(&Path) -> bool,
+{
     let mut reports = Vec::new();
     let mut visited_projects = std::collections::HashSet::new();
 
-    for entry in WalkDir::new(path)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+    for entry in dh_fs::walk(path) {
         let file_path = entry.path();
 
         if let Some(ecosystem) = detect_dependency_file(file_path) {
@@ -150,66 +330,219 @@ pub fn scan_dependencies(path: &Path) -> Result<Vec<DependencyReport>, Dependenc
                 if visited_projects.contains(&project_root) {
                     continue;
                 }
+                let display_root = dh_fs::strip_extended_length(&project_root);
+                if ecosystem.is_build_system()
+                    && buildfiles::is_nested(&display_root, path, &ecosystem)
+                {
+                    continue;
+                }
                 visited_projects.insert(project_root.clone());
 
-                match scan_project(&project_root, ecosystem.clone()) {
-                    Ok(mut report) => {
-                        // Check for additional ecosystems in the same project
-                        for additional_ecosystem in detect_all_ecosystems(&project_root) {
-                            if additional_ecosystem != ecosystem {
-                                if let Ok(additional_deps) =
-                                    parse_dependencies(&project_root, additional_ecosystem.clone())
-                                {
-                                    report.dependencies.extend(additional_deps);
-                                    if !report.ecosystems.contains(&additional_ecosystem) {
-                                        report.ecosystems.push(additional_ecosystem);
-                                    }
-                                }
-                            }
-                        }
-                        reports.push(report);
-                    }
-                    Err(e) => {
-                        reports.push(DependencyReport {
-                            project_path: project_root,
-                            dependencies: Vec::new(),
-                            ecosystems: vec![ecosystem],
-                            errors: vec![e.to_string()],
-                        });
-                    }
+                if !include(&display_root) {
+                    continue;
                 }
+
+                reports.push(analyze_project(&project_root, display_root, ecosystem));
             }
         }
     }
 
+    // Go workspaces may use modules outside the scanned directory
+    let visited: std::collections::HashSet<PathBuf> = visited_projects
+        .iter()
+        .filter_map(|project| fs::canonicalize(project).ok())
+        .collect();
+    let mut members: Vec<PathBuf> = reports
+        .iter()
+        .filter(|report| report.ecosystems.contains(&Ecosystem::Go))
+        .flat_map(|report| gomod::workspace_members(&report.project_path))
+        .collect();
+    members.dedup();
+    for member in members {
+        let Ok(canonical) = fs::canonicalize(&member) else {
+            continue;
+        };
+        if visited.contains(&canonical) || !member.join("go.mod").is_file() || !include(&member) {
+            continue;
+        }
+        reports.push(analyze_project(&member, member.clone(), Ecosystem::Go));
+    }
+
+    for report in &mut reports {
+        report.sort();
+    }
+    reports.sort_by(|a, b| a.project_path.cmp(&b.project_path));
     Ok(reports)
 }
 
-/// Scans a single project directory for dependencies
-fn scan_project(
-    project_path: &Path,
-    primary_ecosystem: Ecosystem,
-) -> Result<DependencyReport, DependencyError> {
-    let dependencies = parse_dependencies(project_path, primary_ecosystem)?;
-    let ecosystems = detect_all_ecosystems(project_path);
-
-    Ok(DependencyReport {
-        project_path: project_path.to_path_buf(),
-        dependencies,
-        ecosystems,
+/// Analyzes the project in `project_root`, reported as `display_root`
+///
+/// Every manifest is parsed on its own: one that fails to parse is reported
+/// as an error of the project while the others still list their dependencies.
+fn analyze_project(
+    project_root: &Path,
+    display_root: PathBuf,
+    ecosystem: Ecosystem,
+) -> DependencyReport {
+    let mut ecosystems = detect_all_ecosystems(project_root);
+    if !ecosystems.contains(&ecosystem) {
+        ecosystems.insert(0, ecosystem);
+    }
+
+    let mut report = DependencyReport {
+        lockfiles: lockfiles::check_project(&display_root),
+        project_path: display_root,
+        dependencies: Vec::new(),
+        ecosystems: Vec::new(),
         errors: Vec::new(),
-    })
+        go_modules: Vec::new(),
+    };
+    for ecosystem in ecosystems {
+        for result in parse_manifests(project_root, &ecosystem) {
+            match result {
+                Ok(dependencies) => report.dependencies.extend(dependencies),
+                Err(e) => report.errors.push(e.to_string()),
+            }
+        }
+        report.ecosystems.push(ecosystem);
+    }
+    if report.ecosystems.contains(&Ecosystem::Go) {
+        report.go_modules = gomod::describe_project(&report.project_path);
+    }
+    report
+}
+
+/// Counts manifest files per ecosystem without parsing them
+///
+/// This is the offline, registry-free summary used by `devhealth check`. The
+/// walk stops once `budget` has elapsed, in which case the summary is marked
+/// incomplete.
+///
+/// # Arguments
+///
+/// * `path` - The directory to search for manifest files
+/// * `include` - Predicate deciding whether a project root is counted
+/// * `budget` - Maximum time to spend walking the directory tree
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::deps::{self, MANIFEST_SCAN_BUDGET};
+/// use std::path::Path;
+///
+/// let summary = deps::summarize_manifests_matching(Path::new("."), |_| true, MANIFEST_SCAN_BUDGET);
+/// assert!(summary.manifests >= summary.projects);
+/// ```
+pub fn summarize_manifests_matching<F>(path: &Path, include: F, budget: Duration) -> ManifestSummary
+where
+    F: Fn(&Path) -> bool,
+{
+    let started = Instant::now();
+    let order = [
+        Ecosystem::Rust,
+        Ecosystem::NodeJs,
+        Ecosystem::Python,
+        Ecosystem::Go,
+        Ecosystem::Bazel,
+        Ecosystem::Buck,
+        Ecosystem::Nix,
+    ];
+    let mut counts = [0usize; 7];
+    let mut projects = std::collections::HashSet::new();
+    let mut complete = true;
+
+    for entry in dh_fs::walk(path) {
+        if started.elapsed() >= budget {
+            complete = false;
+            break;
+        }
+
+        let Some(ecosystem) = detect_dependency_file(entry.path()) else {
+            continue;
+        };
+        let Some(project_root) = entry.path().parent() else {
+            continue;
+        };
+        let project_root = dh_fs::strip_extended_length(project_root);
+        if !include(&project_root)
+            || (ecosystem.is_build_system()
+                && buildfiles::is_nested(&project_root, path, &ecosystem))
+        {
+            continue;
+        }
+
+        projects.insert(project_root);
+        if let Some(index) = order.iter().position(|e| *e == ecosystem) {
+            counts[index] += 1;
+        }
+    }
+
+    ManifestSummary {
+        projects: projects.len(),
+        manifests: counts.iter().sum(),
+        ecosystems: order
+            .into_iter()
+            .zip(counts)
+            .filter(|(_, manifests)| *manifests > 0)
+            .map(|(ecosystem, manifests)| EcosystemCount {
+                ecosystem,
+                manifests,
+            })
+            .collect(),
+        complete,
+    }
+}
+
+/// Prints a manifest summary as a single line, truncated to the terminal width
+///
+/// # Arguments
+///
+/// * `summary` - The summary to display
+pub fn display_manifest_summary(summary: &ManifestSummary) {
+    let mut line = if summary.manifests == 0 {
+        tr!("check-no-manifests")
+    } else {
+        let ecosystems: Vec<String> = summary
+            .ecosystems
+            .iter()
+            .map(|count| {
+                format!(
+                    "{} {}",
+                    count.ecosystem.to_string().paint(Role::Highlight),
+                    count.manifests
+                )
+            })
+            .collect();
+        format!(
+            "{} ({})",
+            tr!(
+                "check-manifests",
+                manifests = summary.manifests.to_string().paint(Role::Emphasis).bold(),
+                projects = summary.projects.to_string().paint(Role::Emphasis).bold()
+            ),
+            ecosystems.join(", ")
+        )
+    };
+    if !summary.complete {
+        line = format!("{} {}", line, tr!("check-partial").paint(Role::Warning));
+    }
+
+    let line = display::symbols(&format!("📦 {}", line)).into_owned();
+    println!("{}", display::truncate(&line, display::terminal_width()));
 }
 
 /// Detects if a file is a dependency file and returns the ecosystem
-fn detect_dependency_file(path: &Path) -> Option<Ecosystem> {
+pub(crate) fn detect_dependency_file(path: &Path) -> Option<Ecosystem> {
     if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
         match filename {
             "Cargo.toml" => Some(Ecosystem::Rust),
             "package.json" => Some(Ecosystem::NodeJs),
             "requirements.txt" | "Pipfile" | "pyproject.toml" => Some(Ecosystem::Python),
-            "go.mod" => Some(Ecosystem::Go),
-            _ => None,
+            "go.mod" | "go.work" => Some(Ecosystem::Go),
+            _ => buildfiles::BUILD_FILES
+                .iter()
+                .find(|(file, _)| *file == filename)
+                .map(|(_, ecosystem)| ecosystem.clone()),
         }
     } else {
         None
@@ -217,7 +550,7 @@ fn detect_dependency_file(path: &Path) -> Option<Ecosystem> {
 }
 
 /// Detects all ecosystems present in a project directory
-fn detect_all_ecosystems(project_path: &Path) -> Vec<Ecosystem> {
+pub(crate) fn detect_all_ecosystems(project_path: &Path) -> Vec<Ecosystem> {
     let mut ecosystems = Vec::new();
 
     let files_to_check = [
@@ -227,9 +560,10 @@ fn detect_all_ecosystems(project_path: &Path) -> Vec<Ecosystem> {
         ("Pipfile", Ecosystem::Python),
         ("pyproject.toml", Ecosystem::Python),
         ("go.mod", Ecosystem::Go),
+        ("go.work", Ecosystem::Go),
     ];
 
-    for (filename, ecosystem) in &files_to_check {
+    for (filename, ecosystem) in files_to_check.iter().chain(&buildfiles::BUILD_FILES) {
         if project_path.join(filename).exists() && !ecosystems.contains(ecosystem) {
             ecosystems.push(ecosystem.clone());
         }
@@ -238,26 +572,62 @@ fn detect_all_ecosystems(project_path: &Path) -> Vec<Ecosystem> {
     ecosystems
 }
 
+/// Python manifests and their parsers, in the order they are read
+const PYTHON_MANIFESTS: [(&str, ManifestParser); 3] = [
+    ("requirements.txt", parse_requirements_txt),
+    ("pyproject.toml", parse_pyproject_toml),
+    ("Pipfile", parse_pipfile),
+];
+
+/// Parser of a single manifest file
+type ManifestParser = fn(&Path) -> Result<Vec<Dependency>, DependencyError>;
+
 /// Parses dependencies from a project for a specific ecosystem
-fn parse_dependencies(
+///
+/// Fails on the first manifest that cannot be parsed; see
+/// [`parse_manifests`] to keep the others.
+pub(crate) fn parse_dependencies(
     project_path: &Path,
     ecosystem: Ecosystem,
 ) -> Result<Vec<Dependency>, DependencyError> {
+    let mut dependencies = Vec::new();
+    for result in parse_manifests(project_path, &ecosystem) {
+        dependencies.extend(result?);
+    }
+    Ok(dependencies)
+}
+
+/// Parses each manifest of an ecosystem in a project independently
+fn parse_manifests(
+    project_path: &Path,
+    ecosystem: &Ecosystem,
+) -> Vec<Result<Vec<Dependency>, DependencyError>> {
     match ecosystem {
-        Ecosystem::Rust => parse_cargo_toml(project_path),
-        Ecosystem::NodeJs => parse_package_json(project_path),
-        Ecosystem::Python => parse_python_dependencies(project_path),
-        Ecosystem::Go => parse_go_mod(project_path),
+        Ecosystem::Rust => vec![parse_cargo_toml(project_path)],
+        Ecosystem::NodeJs => vec![parse_package_json(project_path)],
+        Ecosystem::Python => PYTHON_MANIFESTS
+            .iter()
+            .map(|(file, parse)| (project_path.join(file), parse))
+            .filter(|(path, _)| path.exists())
+            .map(|(path, parse)| parse(&path))
+            .collect(),
+        Ecosystem::Go => vec![gomod::parse_dependencies(project_path)],
+        Ecosystem::Bazel => vec![buildfiles::parse_bazel(project_path)],
+        Ecosystem::Buck => Vec::new(),
+        Ecosystem::Nix => vec![buildfiles::parse_nix(project_path)],
     }
 }
 
 /// Parses Rust dependencies from Cargo.toml
+///
+/// Dependencies of `[target.'cfg(...)'.*]` sections carry their target as
+/// [`Dependency::marker`].
 fn parse_cargo_toml(project_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
     let cargo_toml_path = project_path.join("Cargo.toml");
-    let content = fs::read_to_string(&cargo_toml_path)?;
+    let content = read_manifest(&cargo_toml_path)?;
 
     #[derive(Deserialize)]
-    struct CargoToml {
+    struct DependencyTables {
         dependencies: Option<HashMap<String, toml::Value>>,
         #[serde(rename = "dev-dependencies")]
         dev_dependencies: Option<HashMap<String, toml::Value>>,
@@ -265,33 +635,36 @@ fn parse_cargo_toml(project_path: &Path) -> Result<Vec<Dependency>, DependencyEr
         build_dependencies: Option<HashMap<String, toml::Value>>,
     }
 
-    let cargo_toml: CargoToml = toml::from_str(&content)?;
-    let mut dependencies = Vec::new();
-
-    // Parse runtime dependencies
-    if let Some(deps) = cargo_toml.dependencies {
-        for (name, value) in deps {
-            let dependency =
-                parse_cargo_dependency(name, value, DependencyType::Runtime, &cargo_toml_path)?;
-            dependencies.push(dependency);
-        }
+    #[derive(Deserialize)]
+    struct CargoToml {
+        #[serde(flatten)]
+        tables: DependencyTables,
+        #[serde(default)]
+        target: BTreeMap<String, DependencyTables>,
     }
 
-    // Parse dev dependencies
-    if let Some(deps) = cargo_toml.dev_dependencies {
-        for (name, value) in deps {
-            let dependency =
-                parse_cargo_dependency(name, value, DependencyType::Development, &cargo_toml_path)?;
-            dependencies.push(dependency);
-        }
-    }
+    let cargo_toml: CargoToml = parse_toml_manifest(&cargo_toml_path, &content)?;
+    let mut dependencies = Vec::new();
 
-    // Parse build dependencies
-    if let Some(deps) = cargo_toml.build_dependencies {
-        for (name, value) in deps {
-            let dependency =
-                parse_cargo_dependency(name, value, DependencyType::Build, &cargo_toml_path)?;
-            dependencies.push(dependency);
+    let sections = std::iter::once((None, cargo_toml.tables)).chain(
+        cargo_toml
+            .target
+            .into_iter()
+            .map(|(target, tables)| (Some(target), tables)),
+    );
+    for (target, tables) in sections {
+        let kinds = [
+            (tables.dependencies, DependencyType::Runtime),
+            (tables.dev_dependencies, DependencyType::Development),
+            (tables.build_dependencies, DependencyType::Build),
+        ];
+        for (deps, dep_type) in kinds {
+            for (name, value) in deps.into_iter().flatten() {
+                let mut dependency =
+                    parse_cargo_dependency(name, value, dep_type.clone(), &cargo_toml_path)?;
+                dependency.marker = target.clone();
+                dependencies.push(dependency);
+            }
         }
     }
 
@@ -299,35 +672,56 @@ fn parse_cargo_toml(project_path: &Path) -> Result<Vec<Dependency>, DependencyEr
 }
 
 /// Parses a single Cargo dependency entry
+///
+/// A renamed dependency (`package = "..."`) is reported under its package
+/// name, an `optional = true` one as [`DependencyType::Optional`], and its
+/// `features` as [`Dependency::extras`].
 fn parse_cargo_dependency(
     name: String,
     value: toml::Value,
     dep_type: DependencyType,
     source_file: &Path,
 ) -> Result<Dependency, DependencyError> {
-    let version = match value {
-        toml::Value::String(v) => v,
-        toml::Value::Table(table) => table
-            .get("version")
-            .and_then(|v| v.as_str())
-            .unwrap_or("*")
-            .to_string(),
-        _ => "*".to_string(),
-    };
-
-    Ok(Dependency {
+    let mut dependency = Dependency {
         name,
-        version,
+        version: "*".to_string(),
         dependency_type: dep_type,
         ecosystem: Ecosystem::Rust,
         source_file: source_file.to_path_buf(),
-    })
+        extras: Vec::new(),
+        marker: None,
+    };
+
+    match value {
+        toml::Value::String(version) => dependency.version = version,
+        toml::Value::Table(table) => {
+            if let Some(version) = table.get("version").and_then(|v| v.as_str()) {
+                dependency.version = version.to_string();
+            }
+            if let Some(package) = table.get("package").and_then(|v| v.as_str()) {
+                dependency.name = package.to_string();
+            }
+            if table.get("optional").and_then(|v| v.as_bool()) == Some(true) {
+                dependency.dependency_type = DependencyType::Optional;
+            }
+            dependency.extras = table
+                .get("features")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|feature| feature.as_str().map(str::to_string))
+                .collect();
+        }
+        _ => {}
+    }
+
+    Ok(dependency)
 }
 
 /// Parses Node.js dependencies from package.json
 fn parse_package_json(project_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
     let package_json_path = project_path.join("package.json");
-    let content = fs::read_to_string(&package_json_path)?;
+    let content = read_manifest(&package_json_path)?;
 
     #[derive(Deserialize)]
     struct PackageJson {
@@ -338,7 +732,8 @@ fn parse_package_json(project_path: &Path) -> Result<Vec<Dependency>, Dependency
         peer_dependencies: Option<HashMap<String, String>>,
     }
 
-    let package_json: PackageJson = serde_json::from_str(&content)?;
+    let package_json: PackageJson = serde_json::from_str(&content)
+        .map_err(|error| DependencyError::from(error).in_manifest(&package_json_path, &content))?;
     let mut dependencies = Vec::new();
 
     // Parse runtime dependencies
@@ -350,6 +745,8 @@ fn parse_package_json(project_path: &Path) -> Result<Vec<Dependency>, Dependency
                 dependency_type: DependencyType::Runtime,
                 ecosystem: Ecosystem::NodeJs,
                 source_file: package_json_path.clone(),
+                extras: Vec::new(),
+                marker: None,
             });
         }
     }
@@ -363,6 +760,8 @@ fn parse_package_json(project_path: &Path) -> Result<Vec<Dependency>, Dependency
                 dependency_type: DependencyType::Development,
                 ecosystem: Ecosystem::NodeJs,
                 source_file: package_json_path.clone(),
+                extras: Vec::new(),
+                marker: None,
             });
         }
     }
@@ -376,6 +775,8 @@ fn parse_package_json(project_path: &Path) -> Result<Vec<Dependency>, Dependency
                 dependency_type: DependencyType::Optional,
                 ecosystem: Ecosystem::NodeJs,
                 source_file: package_json_path.clone(),
+                extras: Vec::new(),
+                marker: None,
             });
         }
     }
@@ -383,67 +784,156 @@ fn parse_package_json(project_path: &Path) -> Result<Vec<Dependency>, Dependency
     Ok(dependencies)
 }
 
-/// Parses Python dependencies from various files
-fn parse_python_dependencies(project_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
+/// Parses a requirements.txt file and the files it includes
+///
+/// Follows `-r`/`--requirement` includes relative to the including file,
+/// each file once. Requirements keep their extras and environment markers;
+/// editable installs and URLs of VCS repositories count when they name
+/// their package (`#egg=name`), while local paths are the project itself or
+/// its own packages and are skipped. Constraint files (`-c`), index options
+/// and per-requirement options such as `--hash` are ignored.
+fn parse_requirements_txt(file_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
     let mut dependencies = Vec::new();
+    let mut visited = HashSet::new();
+    collect_requirements(file_path, &mut visited, &mut dependencies)?;
+    Ok(dependencies)
+}
 
-    // Try requirements.txt first
-    let requirements_path = project_path.join("requirements.txt");
-    if requirements_path.exists() {
-        dependencies.extend(parse_requirements_txt(&requirements_path)?);
+/// Adds the requirements of one requirements file, following its includes
+fn collect_requirements(
+    file_path: &Path,
+    visited: &mut HashSet<PathBuf>,
+    dependencies: &mut Vec<Dependency>,
+) -> Result<(), DependencyError> {
+    let canonical = fs::canonicalize(file_path).unwrap_or_else(|_| file_path.to_path_buf());
+    if !visited.insert(canonical) {
+        return Ok(());
     }
+    let content = read_manifest(file_path)?;
+    let dir = file_path.parent().unwrap_or(Path::new("."));
+
+    for line in requirement_lines(&content) {
+        if let Some(include) = option_value(&line, "-r", "--requirement") {
+            let include_path = dir.join(include);
+            if !include_path.is_file() {
+                return Err(DependencyError::MissingInclude(include_path));
+            }
+            collect_requirements(&include_path, visited, dependencies)?;
+            continue;
+        }
 
-    // Try pyproject.toml
-    let pyproject_path = project_path.join("pyproject.toml");
-    if pyproject_path.exists() {
-        dependencies.extend(parse_pyproject_toml(&pyproject_path)?);
-    }
+        let (spec, editable) = match option_value(&line, "-e", "--editable") {
+            Some(spec) => (spec, true),
+            None if line.starts_with('-') => continue,
+            None => (line.as_str(), false),
+        };
+        // Per-requirement options such as `--hash` follow the requirement
+        let spec = spec.split(" --").next().unwrap_or(spec).trim();
 
-    // Try Pipfile
-    let pipfile_path = project_path.join("Pipfile");
-    if pipfile_path.exists() {
-        dependencies.extend(parse_pipfile(&pipfile_path)?);
+        let dependency = if editable || is_url_or_path(spec) {
+            url_requirement(spec, file_path)
+        } else {
+            parse_python_dependency_string(spec, DependencyType::Runtime, file_path)
+        };
+        dependencies.extend(dependency);
     }
-
-    Ok(dependencies)
+    Ok(())
 }
 
-/// Parses requirements.txt file
-fn parse_requirements_txt(file_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
-    let content = fs::read_to_string(file_path)?;
-    let mut dependencies = Vec::new();
-
+/// Splits requirements file content into logical lines without comments
+///
+/// Lines ending in `\` continue on the next line; `#` starts a comment at
+/// the beginning of a line or after whitespace.
+fn requirement_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
     for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
+        let line = match line.find(" #").or_else(|| line.find("\t#")) {
+            Some(comment) => &line[..comment],
+            None if line.trim_start().starts_with('#') => "",
+            None => line,
+        };
+        if let Some(continued) = line.strip_suffix('\\') {
+            current.push_str(continued);
+            current.push(' ');
             continue;
         }
+        current.push_str(line);
+        let logical = current.trim().to_string();
+        current.clear();
+        if !logical.is_empty() {
+            lines.push(logical);
+        }
+    }
+    let logical = current.trim();
+    if !logical.is_empty() {
+        lines.push(logical.to_string());
+    }
+    lines
+}
 
-        // Parse "package==version" or "package>=version" format
-        let parts: Vec<&str> = line.split(&['=', '>', '<', '!', '~'][..]).collect();
-        if let Some(name) = parts.first() {
-            let version = if parts.len() > 1 {
-                parts[1..].join("")
-            } else {
-                "*".to_string()
-            };
+/// Returns the value of a short (`-r file`, `-rfile`) or long
+/// (`--requirement file`, `--requirement=file`) option at the start of a line
+fn option_value<'a>(line: &'a str, short: &str, long: &str) -> Option<&'a str> {
+    let value = if let Some(rest) = line.strip_prefix(long) {
+        rest.strip_prefix('=')
+            .or_else(|| rest.strip_prefix(char::is_whitespace))?
+    } else if line.starts_with("--") {
+        return None;
+    } else {
+        line.strip_prefix(short)?
+    };
+    Some(value.trim()).filter(|value| !value.is_empty())
+}
 
-            dependencies.push(Dependency {
-                name: name.trim().to_string(),
-                version,
-                dependency_type: DependencyType::Runtime,
-                ecosystem: Ecosystem::Python,
-                source_file: file_path.to_path_buf(),
-            });
-        }
+/// Returns `true` for requirements given as a URL or a local path
+fn is_url_or_path(spec: &str) -> bool {
+    spec.contains("://")
+        || spec.starts_with(['.', '/', '~'])
+        || spec.ends_with(".whl")
+        || spec.ends_with(".tar.gz")
+        || spec.ends_with(".zip")
+}
+
+/// Creates a dependency for a URL requirement that names its package
+///
+/// `name @ git+https://host/repo.git@v1.2` and
+/// `git+https://host/repo.git@v1.2#egg=name` become `name` at `v1.2`; URLs
+/// and local paths that do not name their package are skipped.
+fn url_requirement(spec: &str, source_file: &Path) -> Option<Dependency> {
+    if let Some((name, url)) = spec
+        .split_once('@')
+        .filter(|(name, _)| !name.contains(['/', ':']))
+    {
+        let mut dependency =
+            parse_python_dependency_string(name, DependencyType::Runtime, source_file)?;
+        dependency.version = url_reference(url).unwrap_or_else(|| "*".to_string());
+        return Some(dependency);
     }
 
-    Ok(dependencies)
+    let (url, fragment) = spec.split_once('#')?;
+    let egg = fragment
+        .split('&')
+        .find_map(|param| param.strip_prefix("egg="))?;
+    let mut dependency = parse_python_dependency_string(egg, DependencyType::Runtime, source_file)?;
+    dependency.version = url_reference(url).unwrap_or_else(|| "*".to_string());
+    Some(dependency)
+}
+
+/// Returns the branch, tag or commit a VCS URL pins with `@ref`
+fn url_reference(url: &str) -> Option<String> {
+    let url = url.trim();
+    let (_, path) = url.split_once("://")?;
+    let path = path.split('#').next().unwrap_or(path);
+    // Skip `user@host` credentials before the first slash
+    let (_, repository) = path.split_once('/')?;
+    let (_, reference) = repository.rsplit_once('@')?;
+    Some(reference.to_string()).filter(|reference| !reference.is_empty())
 }
 
 /// Parses pyproject.toml file
 fn parse_pyproject_toml(file_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
-    let content = fs::read_to_string(file_path)?;
+    let content = read_manifest(file_path)?;
 
     #[derive(Deserialize)]
     struct PyProjectToml {
@@ -457,7 +947,7 @@ fn parse_pyproject_toml(file_path: &Path) -> Result<Vec<Dependency>, DependencyE
         optional_dependencies: Option<HashMap<String, Vec<String>>>,
     }
 
-    let pyproject: PyProjectToml = toml::from_str(&content)?;
+    let pyproject: PyProjectToml = parse_toml_manifest(file_path, &content)?;
     let mut dependencies = Vec::new();
 
     if let Some(project) = pyproject.project {
@@ -493,7 +983,7 @@ fn parse_pyproject_toml(file_path: &Path) -> Result<Vec<Dependency>, DependencyE
 
 /// Parses Pipfile
 fn parse_pipfile(file_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
-    let content = fs::read_to_string(file_path)?;
+    let content = read_manifest(file_path)?;
 
     #[derive(Deserialize)]
     struct Pipfile {
@@ -502,7 +992,7 @@ fn parse_pipfile(file_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
         dev_packages: Option<HashMap<String, toml::Value>>,
     }
 
-    let pipfile: Pipfile = toml::from_str(&content)?;
+    let pipfile: Pipfile = parse_toml_manifest(file_path, &content)?;
     let mut dependencies = Vec::new();
 
     // Parse runtime dependencies
@@ -515,6 +1005,8 @@ fn parse_pipfile(file_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
                 dependency_type: DependencyType::Runtime,
                 ecosystem: Ecosystem::Python,
                 source_file: file_path.to_path_buf(),
+                extras: Vec::new(),
+                marker: None,
             });
         }
     }
@@ -529,6 +1021,8 @@ fn parse_pipfile(file_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
                 dependency_type: DependencyType::Development,
                 ecosystem: Ecosystem::Python,
                 source_file: file_path.to_path_buf(),
+                extras: Vec::new(),
+                marker: None,
             });
         }
     }
@@ -536,99 +1030,63 @@ fn parse_pipfile(file_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
     Ok(dependencies)
 }
 
-/// Parses Go dependencies from go.mod
-fn parse_go_mod(project_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
-    let go_mod_path = project_path.join("go.mod");
-    let content = fs::read_to_string(&go_mod_path)?;
-    let mut dependencies = Vec::new();
-    let mut in_require_block = false;
-
-    for line in content.lines() {
-        let line = line.trim();
-        
-        // Check if we're entering a require block
-        if line.starts_with("require (") {
-            in_require_block = true;
-            continue;
-        }
-        
-        // Check if we're exiting a require block
-        if in_require_block && line == ")" {
-            in_require_block = false;
-            continue;
-        }
-        
-        // Parse single-line require statements
-        if line.starts_with("require ") && !line.ends_with("(") {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 3 {
-                let name = parts[1].to_string();
-                let version = parts[2].to_string();
-                let dep_type = DependencyType::Runtime;
-
-                dependencies.push(Dependency {
-                    name,
-                    version,
-                    dependency_type: dep_type,
-                    ecosystem: Ecosystem::Go,
-                    source_file: go_mod_path.clone(),
-                });
-            }
-        }
-        
-        // Parse dependencies inside require blocks
-        if in_require_block && !line.is_empty() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 2 {
-                let name = parts[0].to_string();
-                let version = parts[1].to_string();
-                
-                // Determine dependency type based on comments
-                let dep_type = if line.contains("// indirect") {
-                    DependencyType::Development
-                } else {
-                    DependencyType::Runtime
-                };
-
-                dependencies.push(Dependency {
-                    name,
-                    version,
-                    dependency_type: dep_type,
-                    ecosystem: Ecosystem::Go,
-                    source_file: go_mod_path.clone(),
-                });
-            }
-        }
-    }
-
-    Ok(dependencies)
-}
-
-/// Helper function to parse Python dependency strings
+/// Parses a PEP 508 requirement such as
+/// `requests[socks]>=2.25.0; python_version < "3.11"`
+///
+/// The version is the specifier as a normalized PEP 440 specifier set
+/// (`>= 2.28, < 3` becomes `>=2.28,<3`), `*` when there is none, and the
+/// specifier as written if it is not valid PEP 440. Extras and the
+/// environment marker are kept on the dependency.
 fn parse_python_dependency_string(
     dep_str: &str,
     dep_type: DependencyType,
     source_file: &Path,
 ) -> Option<Dependency> {
-    // Parse formats like "requests>=2.25.0" or "django==3.2"
-    let parts: Vec<&str> = dep_str.split(&['=', '>', '<', '!', '~'][..]).collect();
-    if let Some(name) = parts.first() {
-        let version = if parts.len() > 1 {
-            parts[1..].join("")
-        } else {
-            "*".to_string()
-        };
+    let (requirement, marker) = match dep_str.split_once(';') {
+        Some((requirement, marker)) => (requirement, Some(marker.trim()).filter(|m| !m.is_empty())),
+        None => (dep_str, None),
+    };
+    let requirement = requirement.trim();
+    let name_end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    let name = &requirement[..name_end];
+    if name.is_empty() {
+        return None;
+    }
 
-        Some(Dependency {
-            name: name.trim().to_string(),
-            version,
-            dependency_type: dep_type,
-            ecosystem: Ecosystem::Python,
-            source_file: source_file.to_path_buf(),
-        })
-    } else {
-        None
+    let mut rest = requirement[name_end..].trim_start();
+    let mut extras = Vec::new();
+    if let Some(list) = rest.strip_prefix('[') {
+        let (list, after) = list.split_once(']')?;
+        extras = list
+            .split(',')
+            .map(str::trim)
+            .filter(|extra| !extra.is_empty())
+            .map(str::to_string)
+            .collect();
+        rest = after.trim_start();
     }
+
+    let specifier = rest.trim_start_matches('(').trim_end_matches(')').trim();
+    let version = if specifier.is_empty() || specifier.starts_with('@') {
+        "*".to_string()
+    } else {
+        match specifier.parse::<SpecifierSet>() {
+            Ok(specifiers) => specifiers.to_string(),
+            Err(_) => specifier.split_whitespace().collect(),
+        }
+    };
+
+    Some(Dependency {
+        name: name.to_string(),
+        version,
+        dependency_type: dep_type,
+        ecosystem: Ecosystem::Python,
+        source_file: source_file.to_path_buf(),
+        extras,
+        marker: marker.map(str::to_string),
+    })
 }
 
 /// Helper function to extract version from TOML value
@@ -663,8 +1121,109 @@ fn extract_version_from_toml_value(value: toml::Value) -> String {
 /// deps::display_results(&reports);
 /// ```
 pub fn display_results(reports: &[DependencyReport]) {
+    display_results_with(reports, Detail::Normal);
+}
+
+/// Formats a lockfile issue with the command that fixes it
+fn lockfile_line(issue: &LockfileIssue) -> String {
+    let manifest = issue
+        .manifest
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let action = &issue.action;
+    let text = match issue.problem {
+        LockfileProblem::Missing => tr!(
+            "deps-lockfile-missing",
+            manifest = manifest,
+            action = action
+        ),
+        LockfileProblem::OutOfSync => tr!(
+            "deps-lockfile-out-of-sync",
+            manifest = manifest,
+            dependencies = issue.dependencies.join(", "),
+            action = action
+        ),
+        LockfileProblem::Stale => tr!("deps-lockfile-stale", manifest = manifest, action = action),
+    };
+    format!("{} {}", "🔒", text.paint(Role::Warning))
+}
+
+/// Formats the version, replacements and exclusions of a Go module or workspace
+fn go_module_lines(module: &GoModule) -> Vec<String> {
+    let manifest = module
+        .manifest
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut lines = Vec::new();
+
+    if let Some(version) = module.go_version.as_ref().or(module.toolchain.as_ref()) {
+        let mut text = tr!(
+            "deps-go-version",
+            manifest = manifest.as_str(),
+            version = version
+        );
+        if let (Some(_), Some(toolchain)) = (&module.go_version, &module.toolchain) {
+            text = format!(
+                "{}, {}",
+                text,
+                tr!("deps-go-toolchain", toolchain = toolchain)
+            );
+        }
+        lines.push(format!(
+            "{} {}",
+            display::ecosystem_icon("Go"),
+            text.paint(Role::Muted)
+        ));
+    }
+    for replace in &module.replaces {
+        let text = if replace.is_local() {
+            tr!(
+                "deps-go-replace-local",
+                manifest = manifest.as_str(),
+                replace = replace
+            )
+        } else {
+            tr!(
+                "deps-go-replace",
+                manifest = manifest.as_str(),
+                replace = replace
+            )
+        };
+        lines.push(format!("{} {}", "🔀", text.paint(Role::Warning)));
+    }
+    for exclude in &module.excludes {
+        let text = tr!(
+            "deps-go-exclude",
+            manifest = manifest.as_str(),
+            module = exclude.module.as_str(),
+            version = exclude.version.as_str()
+        );
+        lines.push(format!("{} {}", "🚫", text.paint(Role::Muted)));
+    }
+    lines
+}
+
+/// Displays dependency scan results at the given level of detail
+///
+/// - [`Detail::Summary`]: header and summary box only
+/// - [`Detail::Minimal`]: one line per project plus its errors
+/// - [`Detail::Normal`]: ecosystem breakdown, up to
+///   [`NORMAL_DEPENDENCY_LIMIT`](display::NORMAL_DEPENDENCY_LIMIT) dependencies
+///   per ecosystem, and tips (same as [`display_results`])
+/// - [`Detail::Full`]: every dependency
+///
+/// # Arguments
+///
+/// * `reports` - Slice of dependency reports to display
+/// * `detail` - How much of the report to print
+pub fn display_results_with(reports: &[DependencyReport], detail: Detail) {
     if reports.is_empty() {
-        println!("{}", display::header("No dependency files found", "📦", colored::Color::Yellow));
+        println!(
+            "{}",
+            display::header(&tr!("deps-none"), "📦", Role::Warning)
+        );
         return;
     }
 
@@ -675,52 +1234,75 @@ pub fn display_results(reports: &[DependencyReport]) {
 
     // Calculate dependency health metrics
     let total_errors: usize = reports.iter().map(|r| r.errors.len()).sum();
-    
+    let total_lockfiles: usize = reports.iter().map(|r| r.lockfiles.len()).sum();
+
     // Display main header
-    println!("{}", display::header(
-        &format!("Dependency Analysis ({} ecosystems)", ecosystems.len()), 
-        "📦", 
-        colored::Color::BrightMagenta
-    ));
+    println!(
+        "{}",
+        display::header(
+            &tr!("deps-title", count = ecosystems.len()),
+            "📦",
+            Role::Special
+        )
+    );
 
     // Display summary box
     let summary_items = vec![
-        ("Total Projects", total_projects.to_string()),
-        ("Total Dependencies", total_dependencies.to_string()),
-        ("Ecosystems", ecosystems.len().to_string()),
-        ("Errors", if total_errors > 0 { 
-            format!("{} ❌", total_errors) 
-        } else { 
-            "0".to_string() 
-        }),
+        (t("deps-projects"), total_projects.to_string()),
+        (t("deps-total"), total_dependencies.to_string()),
+        (t("deps-ecosystems"), ecosystems.len().to_string()),
+        (
+            t("deps-lockfiles"),
+            if total_lockfiles > 0 {
+                format!("{} ⚠️", total_lockfiles)
+            } else {
+                "0".to_string()
+            },
+        ),
+        (
+            t("errors"),
+            if total_errors > 0 {
+                format!("{} ❌", total_errors)
+            } else {
+                "0".to_string()
+            },
+        ),
     ];
-    
+
     print!("{}", display::summary_box(&summary_items));
 
+    if detail == Detail::Summary {
+        return;
+    }
+
     // Display ecosystem breakdown
-    if !ecosystems.is_empty() {
-        println!("{}", display::section_divider("Ecosystem Breakdown"));
-        
+    if !ecosystems.is_empty() && detail >= Detail::Normal {
+        println!("{}", display::section_divider(t("deps-breakdown")));
+
         for ecosystem in &ecosystems {
             let count: usize = reports
                 .iter()
                 .flat_map(|r| &r.dependencies)
                 .filter(|d| d.ecosystem == **ecosystem)
                 .count();
-            
-            let ecosystem_display = format!("{} {} {} dependencies", 
+
+            let ecosystem_display = format!(
+                "{} {} {}",
                 display::ecosystem_icon(&ecosystem.to_string()),
-                ecosystem.to_string().bright_cyan().bold(),
-                count.to_string().bright_white().bold()
+                ecosystem.to_string().paint(Role::Highlight).bold(),
+                tr!(
+                    "deps-count",
+                    count = count.to_string().paint(Role::Emphasis).bold()
+                )
             );
-            
-            println!("  {}", ecosystem_display);
+
+            println!("  {}", display::symbols(&ecosystem_display));
         }
     }
 
     // Display detailed project breakdown
-    println!("{}", display::section_divider("Project Details"));
-    
+    println!("{}", display::section_divider(t("deps-details")));
+
     for (project_index, report) in reports.iter().enumerate() {
         let is_last_project = project_index == reports.len() - 1;
         let project_name = report
@@ -730,42 +1312,77 @@ pub fn display_results(reports: &[DependencyReport]) {
             .unwrap_or("unknown");
 
         // Project header with dependency count
-        let project_header = format!("{} {} {} dependencies", 
-            "📂".to_string(),
-            project_name.bright_white().bold(),
-            format!("({} deps)", report.dependencies.len()).bright_black()
+        let project_header = format!(
+            "{} {} {}",
+            "📂",
+            project_name.paint(Role::Emphasis).bold(),
+            tr!("deps-count-short", count = report.dependencies.len()).paint(Role::Muted)
+        );
+
+        println!(
+            "{}",
+            display::tree_item(&project_header, is_last_project, 0)
         );
-        
-        println!("{}", display::tree_item(&project_header, is_last_project, 0));
 
         // Group by ecosystem for cleaner display
-        let mut ecosystem_deps: HashMap<Ecosystem, Vec<&Dependency>> = HashMap::new();
-        for dep in &report.dependencies {
+        let mut ecosystem_deps: BTreeMap<Ecosystem, Vec<&Dependency>> = BTreeMap::new();
+        for dep in report
+            .dependencies
+            .iter()
+            .filter(|_| detail >= Detail::Normal)
+        {
             ecosystem_deps
                 .entry(dep.ecosystem.clone())
                 .or_default()
                 .push(dep);
         }
 
+        // Build systems detected without dependencies to list
+        let build_systems: Vec<&Ecosystem> = report
+            .ecosystems
+            .iter()
+            .filter(|ecosystem| {
+                ecosystem.is_build_system() && !ecosystem_deps.contains_key(*ecosystem)
+            })
+            .filter(|_| detail >= Detail::Normal)
+            .collect();
+        let go_lines: Vec<String> = report
+            .go_modules
+            .iter()
+            .filter(|_| detail >= Detail::Normal)
+            .flat_map(go_module_lines)
+            .collect();
+
         // Display dependencies by ecosystem
         for (ecosystem_index, (ecosystem, deps)) in ecosystem_deps.iter().enumerate() {
-            let is_last_ecosystem = ecosystem_index == ecosystem_deps.len() - 1 && report.errors.is_empty();
-            
-            let ecosystem_header = format!("{} {} {}", 
+            let is_last_ecosystem = ecosystem_index == ecosystem_deps.len() - 1
+                && build_systems.is_empty()
+                && go_lines.is_empty()
+                && report.lockfiles.is_empty()
+                && report.errors.is_empty();
+
+            let ecosystem_header = format!(
+                "{} {} {}",
                 display::ecosystem_icon(&ecosystem.to_string()),
-                ecosystem.to_string().bright_cyan(),
-                format!("({} deps)", deps.len()).bright_black()
+                ecosystem.to_string().paint(Role::Highlight),
+                tr!("deps-count-short", count = deps.len()).paint(Role::Muted)
+            );
+
+            println!(
+                "{}",
+                display::tree_item(&ecosystem_header, is_last_ecosystem, 1)
             );
-            
-            println!("{}", display::tree_item(&ecosystem_header, is_last_ecosystem, 1));
 
             // Show top dependencies (with limit for readability)
-            let deps_to_show = deps.iter().take(8);
-            let remaining = if deps.len() > 8 { deps.len() - 8 } else { 0 };
-            
-            for (dep_index, dep) in deps_to_show.enumerate() {
-                let is_last_dep = dep_index == 7.min(deps.len() - 1) && remaining == 0;
-                
+            let shown = detail
+                .dependency_limit()
+                .unwrap_or(deps.len())
+                .min(deps.len());
+            let remaining = deps.len() - shown;
+
+            for (dep_index, dep) in deps.iter().take(shown).enumerate() {
+                let is_last_dep = dep_index == shown - 1 && remaining == 0;
+
                 // Create dependency badge
                 let type_badge = match dep.dependency_type {
                     DependencyType::Runtime => display::badge("prod", display::BadgeType::Runtime),
@@ -774,45 +1391,98 @@ pub fn display_results(reports: &[DependencyReport]) {
                     DependencyType::Optional => display::badge("opt", display::BadgeType::Optional),
                 };
 
-                let dep_display = format!("{} {} {}", 
-                    display::version_display(&dep.name, &dep.version, None),
-                    type_badge,
-                    {
-                        let path = dep.source_file.to_string_lossy();
-                        let path_str = if path.len() > 35 {
-                            format!("...{}", &path[path.len()-32..])
-                        } else {
-                            path.to_string()
-                        };
-                        display::file_path(&path_str)
-                    }
+                // Fit the source path into the remaining terminal width
+                let dep_display = format!(
+                    "{} {}",
+                    display::version_display(&display::truncate(&dep.name, 40), &dep.version, None),
+                    type_badge
                 );
-                
+                let path_width = display::terminal_width()
+                    .saturating_sub(
+                        display::tree_prefix_width(2) + display::display_width(&dep_display) + 1,
+                    )
+                    .max(MIN_PATH_WIDTH);
+                let dep_display = format!(
+                    "{} {}",
+                    dep_display,
+                    display::file_path(&display::truncate_start(
+                        &dep.source_file.to_string_lossy(),
+                        path_width
+                    ))
+                );
+
                 println!("{}", display::tree_item(&dep_display, is_last_dep, 2));
             }
-            
+
             // Show "... and X more" if there are remaining dependencies
             if remaining > 0 {
-                let more_display = format!("{} {} more dependencies", 
-                    "...".bright_black(),
-                    remaining.to_string().bright_black()
+                let more_display = format!(
+                    "{} {}",
+                    "...".paint(Role::Muted),
+                    tr!("deps-more", count = remaining).paint(Role::Muted)
+                );
+                println!(
+                    "{}",
+                    display::tree_item(&more_display, is_last_ecosystem, 2)
                 );
-                println!("{}", display::tree_item(&more_display, is_last_ecosystem, 2));
             }
         }
 
+        for (index, ecosystem) in build_systems.iter().enumerate() {
+            let is_last = index == build_systems.len() - 1
+                && go_lines.is_empty()
+                && report.lockfiles.is_empty()
+                && report.errors.is_empty();
+            let line = format!(
+                "{} {} {}",
+                display::ecosystem_icon(&ecosystem.to_string()),
+                ecosystem.to_string().paint(Role::Highlight),
+                t("deps-build-system-detected").paint(Role::Muted)
+            );
+            println!(
+                "{}",
+                display::tree_item(&display::symbols(&line), is_last, 1)
+            );
+        }
+
+        // Display Go versions, replacements and exclusions
+        for (index, line) in go_lines.iter().enumerate() {
+            let is_last = index == go_lines.len() - 1
+                && report.lockfiles.is_empty()
+                && report.errors.is_empty();
+            println!(
+                "{}",
+                display::tree_item(&display::symbols(line), is_last, 1)
+            );
+        }
+
+        // Display lockfiles that drifted from their manifests
+        for (issue_index, issue) in report.lockfiles.iter().enumerate() {
+            let is_last_issue =
+                issue_index == report.lockfiles.len() - 1 && report.errors.is_empty();
+            println!(
+                "{}",
+                display::tree_item(&lockfile_line(issue), is_last_issue, 1)
+            );
+        }
+
         // Display any errors
         if !report.errors.is_empty() {
-            let error_header = format!("{} {} Errors", "⚠️".bright_red(), report.errors.len());
+            let error_header = format!(
+                "{} {} {}",
+                "⚠️".paint(Role::Error),
+                report.errors.len(),
+                t("errors")
+            );
             println!("{}", display::tree_item(&error_header, true, 1));
-            
+
             for (error_index, error) in report.errors.iter().enumerate() {
                 let is_last_error = error_index == report.errors.len() - 1;
-                let error_display = format!("{}", error.bright_red());
+                let error_display = format!("{}", error.paint(Role::Error));
                 println!("{}", display::tree_item(&error_display, is_last_error, 2));
             }
         }
-        
+
         // Add spacing between projects
         if !is_last_project {
             println!();
@@ -820,20 +1490,26 @@ pub fn display_results(reports: &[DependencyReport]) {
     }
 
     // Display helpful tips
-    if total_dependencies > 0 {
-        println!("\n{}", "💡 Tips:".bright_blue().bold());
-        
+    if total_dependencies > 0 && detail >= Detail::Normal {
+        println!(
+            "\n{}",
+            display::symbols(&format!("💡 {}", t("tips")))
+                .paint(Role::Accent)
+                .bold()
+        );
+
         let tips = vec![
-            ("Check for updates", "Run package manager update commands"),
-            ("Security scan", "Use tools like cargo audit, npm audit, or safety"),
-            ("Clean unused deps", "Remove dependencies you're not using"),
+            (t("deps-tip-updates"), t("deps-tip-updates-how")),
+            (t("deps-tip-security"), t("deps-tip-security-how")),
+            (t("deps-tip-unused"), t("deps-tip-unused-how")),
         ];
-        
+
         for tip in tips {
-            println!("  {} {}: {}", 
-                "•".bright_black(),
-                tip.0.bright_cyan(),
-                tip.1.bright_white()
+            println!(
+                "  {} {}: {}",
+                display::symbols("•").paint(Role::Muted),
+                tip.0.paint(Role::Highlight),
+                tip.1.paint(Role::Emphasis)
             );
         }
     }
@@ -975,6 +1651,52 @@ flask>=2.0.0
             let cc_dep = dependencies.iter().find(|d| d.name == "cc").unwrap();
             assert_eq!(cc_dep.dependency_type, DependencyType::Build);
         }
+
+        #[test]
+        fn parses_target_optional_and_renamed_dependencies() {
+            let temp_dir = TempDir::new().unwrap();
+            fs::write(
+                temp_dir.path().join("Cargo.toml"),
+                r#"
+[package]
+name = "test-project"
+version = "0.1.0"
+
+[dependencies]
+json = { package = "serde_json", version = "1.0" }
+rayon = { version = "1.8", optional = true, features = ["web_spin_lock"] }
+
+[target.'cfg(windows)'.dependencies]
+winapi = { version = "0.3", features = ["winuser"] }
+
+[target.'cfg(unix)'.dev-dependencies]
+nix = "0.27"
+"#,
+            )
+            .unwrap();
+
+            let dependencies = parse_cargo_toml(temp_dir.path()).unwrap();
+            assert_eq!(dependencies.len(), 4);
+
+            let json = dependencies
+                .iter()
+                .find(|d| d.name == "serde_json")
+                .unwrap();
+            assert_eq!(json.version, "1.0");
+            assert_eq!(json.marker, None);
+
+            let rayon = dependencies.iter().find(|d| d.name == "rayon").unwrap();
+            assert_eq!(rayon.dependency_type, DependencyType::Optional);
+            assert_eq!(rayon.extras, ["web_spin_lock"]);
+
+            let winapi = dependencies.iter().find(|d| d.name == "winapi").unwrap();
+            assert_eq!(winapi.dependency_type, DependencyType::Runtime);
+            assert_eq!(winapi.marker.as_deref(), Some("cfg(windows)"));
+
+            let nix = dependencies.iter().find(|d| d.name == "nix").unwrap();
+            assert_eq!(nix.dependency_type, DependencyType::Development);
+            assert_eq!(nix.marker.as_deref(), Some("cfg(unix)"));
+        }
     }
 
     mod package_json_parsing {
@@ -1015,11 +1737,14 @@ flask>=2.0.0
             assert_eq!(dependencies.len(), 4); // requests, django, numpy, flask
 
             let requests_dep = dependencies.iter().find(|d| d.name == "requests").unwrap();
-            assert_eq!(requests_dep.version, "2.28.0");
+            assert_eq!(requests_dep.version, ">=2.28.0");
             assert_eq!(requests_dep.ecosystem, Ecosystem::Python);
 
             let django_dep = dependencies.iter().find(|d| d.name == "django").unwrap();
-            assert_eq!(django_dep.version, "4.1.0");
+            assert_eq!(django_dep.version, "==4.1.0");
+
+            let numpy_dep = dependencies.iter().find(|d| d.name == "numpy").unwrap();
+            assert_eq!(numpy_dep.version, "~=1.24.0");
         }
 
         #[test]
@@ -1039,6 +1764,69 @@ flask>=2.0.0
             let dependencies = parse_requirements_txt(&requirements_path).unwrap();
             assert_eq!(dependencies.len(), 2); // Only requests and flask
         }
+
+        #[test]
+        fn follows_includes_and_keeps_extras_and_markers() {
+            let temp_dir = TempDir::new().unwrap();
+            let requirements_path = temp_dir.path().join("requirements.txt");
+            fs::create_dir(temp_dir.path().join("requirements")).unwrap();
+            fs::write(
+                &requirements_path,
+                r#"-r requirements/base.txt
+--requirement=requirements/base.txt  # included twice
+-c constraints.txt
+--index-url https://pypi.example.com/simple
+-e .
+requests[socks, security]>=2.28.0 ; python_version < "3.11"
+tomli>=1.1,<3 \
+    --hash=sha256:0123456789abcdef
+-e git+https://github.com/org/tool.git@v1.2.0#egg=tool
+internal @ git+https://git.example.com/org/internal.git@main
+./wheels/local-1.0-py3-none-any.whl
+"#,
+            )
+            .unwrap();
+            fs::write(
+                temp_dir.path().join("requirements").join("base.txt"),
+                "django==4.1.0\n",
+            )
+            .unwrap();
+
+            let dependencies = parse_requirements_txt(&requirements_path).unwrap();
+            let found: Vec<(&str, &str)> = dependencies
+                .iter()
+                .map(|d| (d.name.as_str(), d.version.as_str()))
+                .collect();
+            assert_eq!(
+                found,
+                [
+                    ("django", "==4.1.0"),
+                    ("requests", ">=2.28.0"),
+                    ("tomli", ">=1.1,<3"),
+                    ("tool", "v1.2.0"),
+                    ("internal", "main")
+                ]
+            );
+            assert!(dependencies[0].source_file.ends_with("base.txt"));
+            assert_eq!(dependencies[1].extras, ["socks", "security"]);
+            assert_eq!(
+                dependencies[1].marker.as_deref(),
+                Some(r#"python_version < "3.11""#)
+            );
+            assert!(dependencies[2].extras.is_empty() && dependencies[2].marker.is_none());
+        }
+
+        #[test]
+        fn reports_missing_includes() {
+            let temp_dir = TempDir::new().unwrap();
+            let requirements_path = temp_dir.path().join("requirements.txt");
+            fs::write(&requirements_path, "-r dev.txt\n").unwrap();
+
+            let error = parse_requirements_txt(&requirements_path).unwrap_err();
+            assert!(
+                matches!(error, DependencyError::MissingInclude(path) if path.ends_with("dev.txt"))
+            );
+        }
     }
 
     mod integration_tests {
@@ -1067,6 +1855,26 @@ flask>=2.0.0
             assert!(ecosystems.contains(&&Ecosystem::NodeJs));
         }
 
+        #[test]
+        fn skips_projects_rejected_by_filter() {
+            let temp_dir = TempDir::new().unwrap();
+
+            let rust_project = temp_dir.path().join("rust-project");
+            fs::create_dir_all(&rust_project).unwrap();
+            create_test_cargo_toml(&rust_project);
+
+            let node_project = temp_dir.path().join("node-project");
+            fs::create_dir_all(&node_project).unwrap();
+            create_test_package_json(&node_project);
+
+            let reports =
+                scan_dependencies_matching(temp_dir.path(), |p| p.ends_with("rust-project"))
+                    .unwrap();
+
+            assert_eq!(reports.len(), 1);
+            assert_eq!(reports[0].project_path, rust_project);
+        }
+
         #[test]
         fn handles_empty_directory() {
             let temp_dir = TempDir::new().unwrap();
@@ -1104,6 +1912,130 @@ flask>=2.0.0
             assert!(rust_deps > 0);
             assert!(node_deps > 0);
         }
+
+        #[test]
+        fn orders_projects_and_dependencies_deterministically() {
+            let temp_dir = TempDir::new().unwrap();
+            for project in ["web", "api"] {
+                let dir = temp_dir.path().join(project);
+                fs::create_dir_all(&dir).unwrap();
+                create_test_cargo_toml(&dir);
+                create_test_package_json(&dir);
+            }
+
+            let reports = scan_dependencies(temp_dir.path()).unwrap();
+
+            let projects: Vec<_> = reports
+                .iter()
+                .map(|r| r.project_path.file_name().unwrap())
+                .collect();
+            assert_eq!(projects, ["api", "web"]);
+            let names: Vec<_> = reports[0]
+                .dependencies
+                .iter()
+                .map(|d| d.name.as_str())
+                .collect();
+            assert_eq!(
+                names,
+                [
+                    "cc",
+                    "clap",
+                    "serde",
+                    "tempfile",
+                    "express",
+                    "jest",
+                    "lodash",
+                    "typescript"
+                ]
+            );
+        }
+
+        #[test]
+        fn keeps_valid_manifests_next_to_a_malformed_one() {
+            let temp_dir = TempDir::new().unwrap();
+            create_test_cargo_toml(temp_dir.path());
+            let package_json = temp_dir.path().join("package.json");
+            fs::write(
+                &package_json,
+                "{\n  \"dependencies\": {\n    \"express\": 4\n  }\n}\n",
+            )
+            .unwrap();
+            fs::write(
+                temp_dir.path().join("requirements.txt"),
+                "requests==2.31.0\n",
+            )
+            .unwrap();
+            fs::write(temp_dir.path().join("Pipfile"), "[packages]\nflask = \n").unwrap();
+
+            let reports = scan_dependencies(temp_dir.path()).unwrap();
+
+            assert_eq!(reports.len(), 1);
+            let report = &reports[0];
+            assert_eq!(
+                report.ecosystems,
+                [Ecosystem::Rust, Ecosystem::NodeJs, Ecosystem::Python]
+            );
+            assert!(report.dependencies.iter().any(|d| d.name == "serde"));
+            assert!(report.dependencies.iter().any(|d| d.name == "requests"));
+            assert_eq!(report.errors.len(), 2, "{:?}", report.errors);
+            assert!(
+                report.errors[0].starts_with(&format!(
+                    "{}:3:16: Failed to parse JSON",
+                    package_json.display()
+                )),
+                "{}",
+                report.errors[0]
+            );
+            assert!(
+                report.errors[1]
+                    .starts_with(&format!("{}:2:", temp_dir.path().join("Pipfile").display())),
+                "{}",
+                report.errors[1]
+            );
+        }
+    }
+
+    mod manifest_summary {
+        use super::*;
+
+        #[test]
+        fn counts_manifests_per_ecosystem() {
+            let temp_dir = TempDir::new().unwrap();
+            let mixed = temp_dir.path().join("mixed");
+            fs::create_dir_all(&mixed).unwrap();
+            create_test_cargo_toml(&mixed);
+            create_test_package_json(&mixed);
+            create_test_requirements_txt(temp_dir.path());
+
+            let summary =
+                summarize_manifests_matching(temp_dir.path(), |_| true, MANIFEST_SCAN_BUDGET);
+
+            assert!(summary.complete);
+            assert_eq!(summary.projects, 2);
+            assert_eq!(summary.manifests, 3);
+            let ecosystems: Vec<_> = summary
+                .ecosystems
+                .iter()
+                .map(|c| c.ecosystem.clone())
+                .collect();
+            assert_eq!(
+                ecosystems,
+                vec![Ecosystem::Rust, Ecosystem::NodeJs, Ecosystem::Python]
+            );
+        }
+
+        #[test]
+        fn respects_filter_and_budget() {
+            let temp_dir = TempDir::new().unwrap();
+            create_test_cargo_toml(temp_dir.path());
+
+            let filtered =
+                summarize_manifests_matching(temp_dir.path(), |_| false, MANIFEST_SCAN_BUDGET);
+            assert_eq!(filtered.manifests, 0);
+
+            let exhausted = summarize_manifests_matching(temp_dir.path(), |_| true, Duration::ZERO);
+            assert!(!exhausted.complete);
+        }
     }
 
     mod display_tests {
@@ -1125,6 +2057,8 @@ flask>=2.0.0
                 dependency_type: DependencyType::Runtime,
                 ecosystem: Ecosystem::Rust,
                 source_file: temp_dir.path().join("Cargo.toml"),
+                extras: Vec::new(),
+                marker: None,
             }];
 
             let report = DependencyReport {
@@ -1132,10 +2066,47 @@ flask>=2.0.0
                 dependencies,
                 ecosystems: vec![Ecosystem::Rust],
                 errors: Vec::new(),
+                lockfiles: Vec::new(),
+                go_modules: Vec::new(),
             };
 
             // Should not panic
             display_results(&[report]);
         }
+
+        #[test]
+        fn displays_every_detail_level() {
+            let temp_dir = TempDir::new().unwrap();
+            let dependencies = (0..12)
+                .map(|i| Dependency {
+                    name: format!("crate-{}", i),
+                    version: "1.0".to_string(),
+                    dependency_type: DependencyType::Runtime,
+                    ecosystem: Ecosystem::Rust,
+                    source_file: temp_dir.path().join("Cargo.toml"),
+                    extras: Vec::new(),
+                    marker: None,
+                })
+                .collect();
+
+            let report = DependencyReport {
+                project_path: temp_dir.path().to_path_buf(),
+                dependencies,
+                ecosystems: vec![Ecosystem::Rust],
+                errors: vec!["Failed to parse package.json".to_string()],
+                lockfiles: Vec::new(),
+                go_modules: Vec::new(),
+            };
+
+            // Should not panic at any level, including past the normal cap
+            for detail in [
+                Detail::Summary,
+                Detail::Minimal,
+                Detail::Normal,
+                Detail::Full,
+            ] {
+                display_results_with(std::slice::from_ref(&report), detail);
+            }
+        }
     }
 }
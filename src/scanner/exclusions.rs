@@ -0,0 +1,329 @@
+//! Antivirus and indexer exclusions of build directories
+//!
+//! Windows Defender scans every file a compiler or package manager writes,
+//! and Spotlight indexes it; on `target` and `node_modules` trees with tens
+//! of thousands of small files either can take a large share of build time.
+//! `scan --system` looks for the build directories of the projects below the
+//! scanned path and reports the ones that are still scanned or indexed, each
+//! with the command that excludes it:
+//!
+//! - Windows: only when Defender's real-time protection is on. Exclusions
+//!   are read with `Get-MpPreference`, which needs an administrator, and
+//!   otherwise checked one directory at a time with
+//!   `MpCmdRun.exe -CheckExclusion`. Directories neither can confirm are
+//!   counted as unverified rather than reported.
+//! - macOS: a directory is excluded from Spotlight when it or a parent holds
+//!   a `.metadata_never_index` file or has a `.noindex` suffix, or when
+//!   indexing is off for its volume (`mdutil -s`).
+//!
+//! Other platforms are not checked.
+
+use crate::scanner::container::run_command;
+use crate::utils::fs as dh_fs;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Project manifests and the build output directory next to them
+const BUILD_DIRS: [(&str, &str); 6] = [
+    ("Cargo.toml", "target"),
+    ("package.json", "node_modules"),
+    ("build.gradle", "build"),
+    ("build.gradle.kts", "build"),
+    ("CMakeLists.txt", "build"),
+    ("pom.xml", "target"),
+];
+
+/// File that keeps Spotlight out of a directory
+const NEVER_INDEX: &str = ".metadata_never_index";
+
+/// A scanner that slows builds down by looking at every file they write
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scanner {
+    /// Windows Defender real-time protection
+    Defender,
+    /// macOS Spotlight indexing
+    Spotlight,
+}
+
+impl Scanner {
+    /// Returns the scanner's product name
+    pub fn name(self) -> &'static str {
+        match self {
+            Scanner::Defender => "Windows Defender",
+            Scanner::Spotlight => "Spotlight",
+        }
+    }
+
+    /// Returns the command that excludes `dir` from the scanner
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::exclusions::Scanner;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(
+    ///     Scanner::Spotlight.exclude_command(Path::new("/src/api/target")),
+    ///     "touch '/src/api/target/.metadata_never_index'"
+    /// );
+    /// ```
+    pub fn exclude_command(self, dir: &Path) -> String {
+        match self {
+            Scanner::Defender => format!("Add-MpPreference -ExclusionPath \"{}\"", dir.display()),
+            Scanner::Spotlight => format!("touch '{}'", dir.join(NEVER_INDEX).display()),
+        }
+    }
+}
+
+/// A build directory the scanner still scans or indexes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildDirectory {
+    /// The build output directory
+    pub path: PathBuf,
+    /// Command that excludes it
+    pub command: String,
+}
+
+/// Build directories and whether the active scanner skips them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExclusionReport {
+    /// The scanner that was checked
+    pub scanner: Scanner,
+    /// Number of build directories found below the scanned path
+    pub checked: usize,
+    /// Build directories that are scanned or indexed
+    pub unexcluded: Vec<BuildDirectory>,
+    /// Build directories whose exclusion could not be determined
+    #[serde(default)]
+    pub unverified: usize,
+}
+
+/// Checks whether the build directories below `path` are excluded
+///
+/// Returns `None` on platforms without a supported scanner, when Defender's
+/// real-time protection is off, and when there is no build directory.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::exclusions;
+/// use std::path::Path;
+///
+/// if let Some(report) = exclusions::check_exclusions(Path::new(".")) {
+///     for dir in &report.unexcluded {
+///         println!("{} scans {}: {}", report.scanner.name(), dir.path.display(), dir.command);
+///     }
+/// }
+/// ```
+pub fn check_exclusions(path: &Path) -> Option<ExclusionReport> {
+    let scanner = if cfg!(windows) {
+        Scanner::Defender
+    } else if cfg!(target_os = "macos") {
+        Scanner::Spotlight
+    } else {
+        return None;
+    };
+    if scanner == Scanner::Defender && !defender_active() {
+        return None;
+    }
+
+    let dirs = build_directories(path);
+    if dirs.is_empty() {
+        return None;
+    }
+
+    let exclusions = match scanner {
+        Scanner::Defender => defender_exclusions(),
+        Scanner::Spotlight => None,
+    };
+    let mut report = ExclusionReport {
+        scanner,
+        checked: dirs.len(),
+        unexcluded: Vec::new(),
+        unverified: 0,
+    };
+    for dir in dirs {
+        let excluded = match (scanner, &exclusions) {
+            (Scanner::Defender, Some(exclusions)) => Some(is_excluded_by(&dir, exclusions)),
+            (Scanner::Defender, None) => defender_excludes(&dir),
+            (Scanner::Spotlight, _) => Some(never_indexed(&dir) || indexing_disabled(&dir)),
+        };
+        match excluded {
+            Some(true) => {}
+            Some(false) => report.unexcluded.push(BuildDirectory {
+                command: scanner.exclude_command(&dir),
+                path: dir,
+            }),
+            None => report.unverified += 1,
+        }
+    }
+    Some(report)
+}
+
+/// Finds the build output directories of the projects below `root`
+fn build_directories(root: &Path) -> Vec<PathBuf> {
+    let walk_root = dh_fs::extended_length(root);
+    let mut dirs: Vec<PathBuf> = dh_fs::walk(root)
+        .filter(|entry| {
+            !dh_fs::is_skipped(
+                entry
+                    .path()
+                    .strip_prefix(&walk_root)
+                    .unwrap_or(entry.path()),
+            )
+        })
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?;
+            let (_, output) = BUILD_DIRS.iter().find(|(manifest, _)| *manifest == name)?;
+            let dir = entry.path().parent()?.join(output);
+            dir.is_dir().then(|| dh_fs::strip_extended_length(&dir))
+        })
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+/// Returns `true` if Defender's real-time protection is on
+fn defender_active() -> bool {
+    powershell("(Get-MpComputerStatus).RealTimeProtectionEnabled")
+        .is_some_and(|output| output.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Reads Defender's excluded paths, `None` without administrator rights
+fn defender_exclusions() -> Option<Vec<String>> {
+    let output = powershell("(Get-MpPreference).ExclusionPath")?;
+    if output.starts_with("N/A") {
+        return None;
+    }
+    Some(
+        output
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// Asks `MpCmdRun.exe` whether Defender excludes `dir`
+fn defender_excludes(dir: &Path) -> Option<bool> {
+    let program = PathBuf::from(std::env::var_os("ProgramFiles")?)
+        .join("Windows Defender")
+        .join("MpCmdRun.exe");
+    let output = Command::new(program)
+        .args(["-CheckExclusion", "-path"])
+        .arg(dir)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.contains("is not excluded") {
+        Some(false)
+    } else if stdout.contains("is excluded") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Runs a PowerShell command and returns its output
+fn powershell(command: &str) -> Option<String> {
+    run_command(
+        "powershell",
+        &["-NoProfile", "-NonInteractive", "-Command", command],
+    )
+}
+
+/// Returns `true` if a Defender exclusion covers `dir`
+///
+/// Windows paths compare case-insensitively, with either separator.
+fn is_excluded_by(dir: &Path, exclusions: &[String]) -> bool {
+    let normalize = |path: &str| {
+        path.replace('/', "\\")
+            .trim_end_matches('\\')
+            .to_lowercase()
+    };
+    let dir = normalize(&dir.to_string_lossy());
+    exclusions
+        .iter()
+        .map(|exclusion| normalize(exclusion))
+        .any(|exclusion| {
+            !exclusion.is_empty()
+                && dir
+                    .strip_prefix(&exclusion)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('\\'))
+        })
+}
+
+/// Returns `true` if `dir` or a parent is marked to never be indexed
+fn never_indexed(dir: &Path) -> bool {
+    dir.ancestors().any(|dir| {
+        dir.join(NEVER_INDEX).exists()
+            || dir
+                .extension()
+                .is_some_and(|extension| extension == "noindex")
+    })
+}
+
+/// Returns `true` if Spotlight indexing is off for the volume holding `dir`
+fn indexing_disabled(dir: &Path) -> bool {
+    let dir = dir.to_string_lossy();
+    run_command("mdutil", &["-s", &dir]).is_some_and(|output| output.contains("Indexing disabled"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn finds_build_directories_of_projects() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        fs::create_dir_all(root.join("api/target/debug")).unwrap();
+        fs::write(root.join("api/Cargo.toml"), "[package]\nname = \"api\"\n").unwrap();
+        fs::create_dir_all(root.join("web/node_modules/dep/node_modules")).unwrap();
+        fs::write(root.join("web/package.json"), "{}").unwrap();
+        fs::write(root.join("web/node_modules/dep/package.json"), "{}").unwrap();
+        fs::create_dir(root.join("lib")).unwrap();
+        fs::write(root.join("lib/Cargo.toml"), "[package]\nname = \"lib\"\n").unwrap();
+
+        assert_eq!(
+            build_directories(root),
+            vec![root.join("api/target"), root.join("web/node_modules")]
+        );
+    }
+
+    #[test]
+    fn matches_defender_exclusions_by_prefix() {
+        let exclusions = vec![r"C:\src\api\".to_string(), r"d:\cache".to_string()];
+
+        assert!(is_excluded_by(Path::new(r"C:\src\api\target"), &exclusions));
+        assert!(is_excluded_by(Path::new(r"D:\Cache"), &exclusions));
+        assert!(!is_excluded_by(
+            Path::new(r"C:\src\api-v2\target"),
+            &exclusions
+        ));
+        assert!(!is_excluded_by(Path::new(r"C:\src"), &exclusions));
+        assert!(!is_excluded_by(Path::new(r"C:\src"), &[String::new()]));
+    }
+
+    #[test]
+    fn honors_spotlight_markers() {
+        let root = TempDir::new().unwrap();
+        let marked = root.path().join("api/target");
+        fs::create_dir_all(&marked).unwrap();
+        fs::write(marked.join(NEVER_INDEX), "").unwrap();
+        let suffixed = root.path().join("cache.noindex/web/node_modules");
+        fs::create_dir_all(&suffixed).unwrap();
+        let plain = root.path().join("web/node_modules");
+        fs::create_dir_all(&plain).unwrap();
+
+        assert!(never_indexed(&marked));
+        assert!(never_indexed(&suffixed));
+        assert!(!never_indexed(&plain));
+    }
+}
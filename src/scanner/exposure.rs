@@ -0,0 +1,762 @@
+//! Dependency exposure across projects (`deps exposure`)
+//!
+//! Combines the [`DependencyReport`]s of every project under a directory
+//! into a supply-chain view of what the projects share:
+//!
+//! - **Most used packages**: each package with the number of projects
+//!   depending on it
+//! - **Versions in use**: for each package, the versions in use and the
+//!   projects using each. Versions come from the lockfile where there is one
+//!   (`Cargo.lock`, `package-lock.json`, `poetry.lock`) and are the declared constraint
+//!   otherwise, so packages used in more than one version stand out.
+//! - **Vulnerable packages**: with an [`AuditReport`], each vulnerable
+//!   package with its advisories and the projects it affects, the package
+//!   affecting the most projects first
+//!
+//! [`crate::aggregate`] gives the same view across machines from saved JSON
+//! reports.
+//!
+//! [`find_package`] answers the opposite question for a single package
+//! (`deps find`): every project using it, with the version, dependency type
+//! and manifest.
+
+use crate::i18n::t;
+use crate::scanner::audit::AuditReport;
+use crate::scanner::cargo;
+use crate::scanner::deps::{Dependency, DependencyReport, DependencyType, Ecosystem};
+use crate::scanner::pins::{self, NpmLock};
+use crate::tr;
+use crate::utils::display;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Packages listed by default in text output
+pub const DEFAULT_TOP: usize = 20;
+
+/// A package and where each version of it is used
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageUsage {
+    /// Package name
+    pub name: String,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+    /// Version in use mapped to the projects using it
+    pub versions: BTreeMap<String, Vec<PathBuf>>,
+    /// Number of projects depending on the package in any version
+    pub projects: usize,
+}
+
+impl PackageUsage {
+    /// Returns `true` if projects use more than one version
+    pub fn has_drift(&self) -> bool {
+        self.versions.len() > 1
+    }
+}
+
+/// A vulnerable package and the projects it affects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VulnerablePackage {
+    /// Package name
+    pub name: String,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+    /// Identifiers of the advisories matching it
+    pub advisories: Vec<String>,
+    /// Affected versions in use
+    pub versions: Vec<String>,
+    /// First version fixing every advisory, if known
+    pub fixed_version: Option<String>,
+    /// Projects with an affected version
+    pub projects: Vec<PathBuf>,
+}
+
+/// Dependencies shared by the scanned projects
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExposureReport {
+    /// Number of projects scanned
+    pub projects: usize,
+    /// Every package, the most used first
+    pub packages: Vec<PackageUsage>,
+    /// Vulnerable packages, the one affecting the most projects first;
+    /// `None` without an audit
+    pub vulnerable: Option<Vec<VulnerablePackage>>,
+}
+
+impl ExposureReport {
+    /// Builds the report from the projects' dependency reports and, if the
+    /// dependencies were audited, the audit report
+    ///
+    /// A package counts once per project, however many of its manifests
+    /// declare it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::{deps, exposure::ExposureReport};
+    /// use std::path::Path;
+    ///
+    /// let reports = deps::scan_dependencies(Path::new(".")).unwrap();
+    /// let report = ExposureReport::build(&reports, None);
+    /// for package in report.packages.iter().take(5) {
+    ///     println!("{} is used by {} projects", package.name, package.projects);
+    /// }
+    /// ```
+    pub fn build(reports: &[DependencyReport], audit: Option<&AuditReport>) -> ExposureReport {
+        let mut resolver = Resolver::default();
+        let mut packages: BTreeMap<(String, Ecosystem), BTreeMap<String, BTreeSet<PathBuf>>> =
+            BTreeMap::new();
+        for report in reports {
+            for dependency in &report.dependencies {
+                let version = resolver.resolve(dependency);
+                packages
+                    .entry((dependency.name.clone(), dependency.ecosystem.clone()))
+                    .or_default()
+                    .entry(version)
+                    .or_default()
+                    .insert(report.project_path.clone());
+            }
+        }
+
+        let mut packages: Vec<PackageUsage> = packages
+            .into_iter()
+            .map(|((name, ecosystem), versions)| {
+                let projects = versions.values().flatten().collect::<BTreeSet<_>>().len();
+                PackageUsage {
+                    name,
+                    ecosystem,
+                    versions: versions
+                        .into_iter()
+                        .map(|(version, projects)| (version, projects.into_iter().collect()))
+                        .collect(),
+                    projects,
+                }
+            })
+            .collect();
+        packages.sort_by(|a, b| {
+            b.projects
+                .cmp(&a.projects)
+                .then_with(|| b.has_drift().cmp(&a.has_drift()))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        ExposureReport {
+            projects: reports.len(),
+            packages,
+            vulnerable: audit.map(|audit| vulnerable_packages(reports, audit)),
+        }
+    }
+
+    /// Returns the vulnerable package affecting the most projects, if any
+    pub fn most_exposed(&self) -> Option<&VulnerablePackage> {
+        self.vulnerable.as_ref()?.first()
+    }
+}
+
+/// A project depending on a searched package
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageMatch {
+    /// Project root
+    pub project: PathBuf,
+    /// Package name
+    pub name: String,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+    /// Declared version constraint
+    pub version: String,
+    /// Version the lockfile resolved the constraint to, if it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked: Option<String>,
+    /// Type of dependency
+    pub dependency_type: DependencyType,
+    /// Manifest declaring the dependency
+    pub source_file: PathBuf,
+}
+
+/// Lists every dependency on a package across projects
+///
+/// Package names match case-insensitively, treating `-`, `_` and `.` alike,
+/// and `query` may be any part of the name, so `log4j` finds `log4j-core`.
+/// Matches are ordered by package, then project.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::{deps, exposure};
+/// use std::path::Path;
+///
+/// let reports = deps::scan_dependencies(Path::new(".")).unwrap();
+/// for found in exposure::find_package(&reports, "serde") {
+///     println!("{} uses {} {}", found.project.display(), found.name, found.version);
+/// }
+/// ```
+pub fn find_package(reports: &[DependencyReport], query: &str) -> Vec<PackageMatch> {
+    let query = normalize_package_name(query);
+    let mut resolver = Resolver::default();
+    let mut matches: Vec<PackageMatch> = reports
+        .iter()
+        .flat_map(|report| {
+            report
+                .dependencies
+                .iter()
+                .map(move |dependency| (report, dependency))
+        })
+        .filter(|(_, dependency)| normalize_package_name(&dependency.name).contains(&query))
+        .map(|(report, dependency)| {
+            let locked = resolver.resolve(dependency);
+            PackageMatch {
+                project: report.project_path.clone(),
+                name: dependency.name.clone(),
+                ecosystem: dependency.ecosystem.clone(),
+                locked: (locked != dependency.version).then_some(locked),
+                version: dependency.version.clone(),
+                dependency_type: dependency.dependency_type.clone(),
+                source_file: dependency.source_file.clone(),
+            }
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        (&a.name, &a.ecosystem, &a.project, &a.source_file).cmp(&(
+            &b.name,
+            &b.ecosystem,
+            &b.project,
+            &b.source_file,
+        ))
+    });
+    matches
+}
+
+/// Lowercases a package name and unifies its separators
+pub(crate) fn normalize_package_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+/// Groups the audit's vulnerabilities by package
+fn vulnerable_packages(
+    reports: &[DependencyReport],
+    audit: &AuditReport,
+) -> Vec<VulnerablePackage> {
+    let project_of = |manifest: &Path| {
+        reports
+            .iter()
+            .filter(|report| manifest.starts_with(&report.project_path))
+            .max_by_key(|report| report.project_path.components().count())
+            .map(|report| report.project_path.clone())
+            .unwrap_or_else(|| manifest.parent().unwrap_or(manifest).to_path_buf())
+    };
+
+    let mut packages: BTreeMap<(String, Ecosystem), VulnerablePackage> = BTreeMap::new();
+    for vuln in &audit.vulnerabilities {
+        let dependency = &vuln.dependency;
+        let package = packages
+            .entry((dependency.name.clone(), dependency.ecosystem.clone()))
+            .or_insert_with(|| VulnerablePackage {
+                name: dependency.name.clone(),
+                ecosystem: dependency.ecosystem.clone(),
+                advisories: Vec::new(),
+                versions: Vec::new(),
+                fixed_version: None,
+                projects: Vec::new(),
+            });
+        insert_sorted(&mut package.advisories, &vuln.advisory_id);
+        insert_sorted(&mut package.versions, &vuln.version);
+        insert_sorted(&mut package.projects, &project_of(&dependency.source_file));
+        if let Some(fixed) = &vuln.fixed_version {
+            let newer = package.fixed_version.as_ref().is_none_or(|current| {
+                match (Version::parse(fixed), Version::parse(current)) {
+                    (Ok(fixed), Ok(current)) => fixed > current,
+                    _ => fixed > current,
+                }
+            });
+            if newer {
+                package.fixed_version = Some(fixed.clone());
+            }
+        }
+    }
+
+    let mut packages: Vec<_> = packages.into_values().collect();
+    packages.sort_by(|a, b| {
+        b.projects
+            .len()
+            .cmp(&a.projects.len())
+            .then_with(|| b.advisories.len().cmp(&a.advisories.len()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    packages
+}
+
+/// Inserts `value` into a sorted list unless already present
+fn insert_sorted<T: Ord + Clone>(list: &mut Vec<T>, value: &T) {
+    if let Err(position) = list.binary_search(value) {
+        list.insert(position, value.clone());
+    }
+}
+
+/// Resolves declared constraints to the versions their lockfiles locked,
+/// reading each lockfile once
+#[derive(Default)]
+pub(crate) struct Resolver {
+    cargo: HashMap<PathBuf, BTreeMap<String, Vec<Version>>>,
+    npm: HashMap<PathBuf, Option<NpmLock>>,
+    poetry: HashMap<PathBuf, BTreeMap<String, String>>,
+}
+
+impl Resolver {
+    /// Returns the locked version of a dependency, or its declared
+    /// constraint if the lockfile does not resolve it
+    pub(crate) fn resolve(&mut self, dependency: &Dependency) -> String {
+        self.locked(dependency)
+            .unwrap_or_else(|| dependency.version.clone())
+    }
+
+    /// Returns the version the lockfile next to a dependency's manifest, or
+    /// in the closest ancestor, locked it to
+    pub(crate) fn locked(&mut self, dependency: &Dependency) -> Option<String> {
+        let dir = dependency.source_file.parent().unwrap_or(Path::new("."));
+        match dependency.ecosystem {
+            Ecosystem::Rust => {
+                let locked = self.cargo.entry(dir.to_path_buf()).or_insert_with(|| {
+                    cargo::find_lockfile(dir)
+                        .and_then(|lockfile| fs::read_to_string(lockfile).ok())
+                        .map(|lock| pins::cargo_locked_versions(&lock))
+                        .unwrap_or_default()
+                });
+                let requirement = VersionReq::parse(&dependency.version).ok();
+                locked
+                    .get(&dependency.name)
+                    .into_iter()
+                    .flatten()
+                    .filter(|version| {
+                        requirement
+                            .as_ref()
+                            .is_none_or(|requirement| requirement.matches(version))
+                    })
+                    .max()
+                    .map(Version::to_string)
+            }
+            Ecosystem::NodeJs => self
+                .npm
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| NpmLock::find(dir))
+                .as_ref()
+                .and_then(|lock| lock.resolved(&dependency.name)),
+            Ecosystem::Python => self
+                .poetry
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| {
+                    dir.ancestors()
+                        .find_map(|ancestor| fs::read_to_string(ancestor.join("poetry.lock")).ok())
+                        .map(|lock| pins::poetry_locked_versions(&lock))
+                        .unwrap_or_default()
+                })
+                .get(&normalize_package_name(&dependency.name))
+                .cloned(),
+            _ => None,
+        }
+    }
+}
+
+/// Displays the exposure report, listing the `top` most used packages
+///
+/// # Arguments
+///
+/// * `report` - The report to display
+/// * `top` - How many packages to list, `None` for all of them
+pub fn display_report(report: &ExposureReport, top: Option<usize>) {
+    if report.packages.is_empty() {
+        println!("{}", display::header(t("deps-none"), "🕸️", Role::Warning));
+        return;
+    }
+    println!(
+        "{}",
+        display::header(
+            &tr!("exposure-title", count = report.projects),
+            "🕸️",
+            Role::Special
+        )
+    );
+
+    let drifting = report
+        .packages
+        .iter()
+        .filter(|package| package.has_drift())
+        .count();
+    let mut summary = vec![
+        (t("deps-projects"), report.projects.to_string()),
+        (t("exposure-packages"), report.packages.len().to_string()),
+        (t("exposure-drifting"), drifting.to_string()),
+    ];
+    if let Some(vulnerable) = &report.vulnerable {
+        summary.push((t("exposure-vulnerable"), vulnerable.len().to_string()));
+    }
+    print!("{}", display::summary_box(&summary));
+
+    if let Some(package) = report.most_exposed() {
+        let line = tr!(
+            "exposure-most-exposed",
+            package = package.name.as_str().paint(Role::Error).bold(),
+            count = package.projects.len()
+        );
+        println!("\n{} {}", display::symbols("🚨"), line);
+    }
+
+    let shown = top
+        .unwrap_or(report.packages.len())
+        .min(report.packages.len());
+    println!(
+        "{}",
+        display::section_divider(&tr!("exposure-most-used", count = shown))
+    );
+    for (index, package) in report.packages.iter().take(shown).enumerate() {
+        let header = format!(
+            "{} {} {}",
+            display::ecosystem_icon(&package.ecosystem.to_string()),
+            package.name.paint(Role::Highlight).bold(),
+            tr!("exposure-projects", count = package.projects).paint(Role::Muted)
+        );
+        println!("{}", display::tree_item(&header, index == shown - 1, 0));
+        let role = if package.has_drift() {
+            Role::Warning
+        } else {
+            Role::Emphasis
+        };
+        for (position, (version, projects)) in package.versions.iter().enumerate() {
+            let names: Vec<String> = projects
+                .iter()
+                .map(|project| project_name(project))
+                .collect();
+            let line = format!(
+                "{} {}",
+                version.paint(role),
+                names.join(", ").paint(Role::Muted)
+            );
+            println!(
+                "{}",
+                display::tree_item(&line, position == package.versions.len() - 1, 1)
+            );
+        }
+    }
+    if shown < report.packages.len() {
+        let more = tr!("exposure-more", count = report.packages.len() - shown);
+        println!("  {}", more.paint(Role::Muted));
+    }
+
+    let Some(vulnerable) = &report.vulnerable else {
+        println!(
+            "\n{} {}",
+            display::symbols("💡"),
+            t("exposure-tip-audit").paint(Role::Muted)
+        );
+        return;
+    };
+    if vulnerable.is_empty() {
+        println!(
+            "\n{} {}",
+            display::symbols("✅"),
+            t("exposure-no-vulnerable").paint(Role::Success)
+        );
+        return;
+    }
+    println!("{}", display::section_divider(t("exposure-vulnerable")));
+    for (index, package) in vulnerable.iter().enumerate() {
+        let fix = match &package.fixed_version {
+            Some(fixed) => tr!("exposure-fixed-in", version = fixed),
+            None => t("exposure-no-fix").to_string(),
+        };
+        let header = format!(
+            "{} {} {} {}",
+            package.name.paint(Role::Error).bold(),
+            package.versions.join(", ").paint(Role::Warning),
+            tr!("exposure-projects", count = package.projects.len()).paint(Role::Muted),
+            fix.paint(Role::Success)
+        );
+        println!(
+            "{}",
+            display::tree_item(&header, index == vulnerable.len() - 1, 0)
+        );
+        let names: Vec<String> = package
+            .projects
+            .iter()
+            .map(|project| project_name(project))
+            .collect();
+        println!(
+            "{}",
+            display::tree_item(
+                &package
+                    .advisories
+                    .join(", ")
+                    .paint(Role::Accent)
+                    .to_string(),
+                false,
+                1
+            )
+        );
+        println!("{}", display::tree_item(&names.join(", "), true, 1));
+    }
+}
+
+/// Displays the projects using a searched package
+///
+/// # Arguments
+///
+/// * `query` - The package searched for
+/// * `matches` - What [`find_package`] found
+pub fn display_matches(query: &str, matches: &[PackageMatch]) {
+    if matches.is_empty() {
+        println!(
+            "{} {}",
+            display::symbols("✅"),
+            tr!("find-none", package = query).paint(Role::Success)
+        );
+        return;
+    }
+    let projects = matches
+        .iter()
+        .map(|found| &found.project)
+        .collect::<BTreeSet<_>>()
+        .len();
+    println!(
+        "{}",
+        display::header(
+            &tr!(
+                "find-title",
+                package = query,
+                count = matches.len(),
+                projects = projects
+            ),
+            "🔎",
+            Role::Special
+        )
+    );
+
+    let mut by_project: BTreeMap<&Path, Vec<&PackageMatch>> = BTreeMap::new();
+    for found in matches {
+        by_project.entry(&found.project).or_default().push(found);
+    }
+    for (index, (project, dependencies)) in by_project.iter().enumerate() {
+        let header = format!(
+            "{} {}",
+            "📂",
+            project_name(project).paint(Role::Emphasis).bold()
+        );
+        println!(
+            "{}",
+            display::tree_item(&header, index == by_project.len() - 1, 0)
+        );
+        for (position, found) in dependencies.iter().enumerate() {
+            let badge = match found.dependency_type {
+                DependencyType::Runtime => display::badge("prod", display::BadgeType::Runtime),
+                DependencyType::Development => display::badge("dev", display::BadgeType::Dev),
+                DependencyType::Build => display::badge("build", display::BadgeType::Build),
+                DependencyType::Optional => display::badge("opt", display::BadgeType::Optional),
+            };
+            let version = match &found.locked {
+                Some(locked) => format!(
+                    "{} {}",
+                    found.version.paint(Role::Emphasis),
+                    tr!("find-locked", version = locked).paint(Role::Muted)
+                ),
+                None => found.version.paint(Role::Emphasis).to_string(),
+            };
+            let line = format!(
+                "{} {} {} {} {}",
+                display::ecosystem_icon(&found.ecosystem.to_string()),
+                found.name.paint(Role::Highlight),
+                version,
+                badge,
+                display::file_path(&found.source_file.display().to_string())
+            );
+            println!(
+                "{}",
+                display::tree_item(
+                    &display::symbols(&line),
+                    position == dependencies.len() - 1,
+                    1
+                )
+            );
+        }
+    }
+}
+
+/// Returns the directory name of a project
+pub(crate) fn project_name(project: &Path) -> String {
+    project
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| project.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::audit::Vulnerability;
+    use crate::scanner::deps::DependencyType;
+
+    fn dependency(project: &str, name: &str, version: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependency_type: DependencyType::Runtime,
+            ecosystem: Ecosystem::Python,
+            source_file: PathBuf::from(project).join("requirements.txt"),
+            extras: Vec::new(),
+            marker: None,
+        }
+    }
+
+    fn project(path: &str, dependencies: &[(&str, &str)]) -> DependencyReport {
+        DependencyReport {
+            project_path: PathBuf::from(path),
+            dependencies: dependencies
+                .iter()
+                .map(|(name, version)| dependency(path, name, version))
+                .collect(),
+            ecosystems: vec![Ecosystem::Python],
+            errors: Vec::new(),
+            lockfiles: Vec::new(),
+            go_modules: Vec::new(),
+        }
+    }
+
+    fn vulnerability(
+        project: &str,
+        name: &str,
+        version: &str,
+        advisory: &str,
+        fixed: &str,
+    ) -> Vulnerability {
+        Vulnerability {
+            advisory_id: advisory.to_string(),
+            summary: String::new(),
+            aliases: Vec::new(),
+            dependency: dependency(project, name, version),
+            version: version.to_string(),
+            fixed_version: Some(fixed.to_string()),
+        }
+    }
+
+    #[test]
+    fn ranks_packages_by_the_projects_using_them() {
+        let reports = [
+            project("/src/api", &[("requests", "2.31.0"), ("flask", "3.0.0")]),
+            project(
+                "/src/worker",
+                &[("requests", "2.28.0"), ("celery", "5.3.0")],
+            ),
+            project("/src/web", &[("requests", "2.31.0"), ("flask", "3.0.0")]),
+        ];
+        let report = ExposureReport::build(&reports, None);
+
+        assert_eq!(report.projects, 3);
+        let names: Vec<&str> = report
+            .packages
+            .iter()
+            .map(|package| package.name.as_str())
+            .collect();
+        assert_eq!(names, ["requests", "flask", "celery"]);
+        let requests = &report.packages[0];
+        assert_eq!(requests.projects, 3);
+        assert!(requests.has_drift());
+        assert_eq!(
+            requests.versions["2.31.0"],
+            [PathBuf::from("/src/api"), PathBuf::from("/src/web")]
+        );
+        assert_eq!(requests.versions["2.28.0"], [PathBuf::from("/src/worker")]);
+        assert!(!report.packages[1].has_drift());
+        assert_eq!(report.vulnerable, None);
+    }
+
+    #[test]
+    fn puts_the_vulnerable_package_affecting_the_most_projects_first() {
+        let reports = [
+            project("/src/api", &[("requests", "2.28.0"), ("jinja2", "3.1.2")]),
+            project("/src/worker", &[("requests", "2.28.0")]),
+        ];
+        let audit = AuditReport {
+            vulnerabilities: vec![
+                vulnerability("/src/api", "jinja2", "3.1.2", "GHSA-jinja", "3.1.3"),
+                vulnerability("/src/api", "requests", "2.28.0", "GHSA-one", "2.31.0"),
+                vulnerability("/src/api", "requests", "2.28.0", "GHSA-two", "2.32.0"),
+                vulnerability("/src/worker", "requests", "2.28.0", "GHSA-one", "2.31.0"),
+            ],
+            ..Default::default()
+        };
+        let report = ExposureReport::build(&reports, Some(&audit));
+
+        let most_exposed = report.most_exposed().unwrap();
+        assert_eq!(most_exposed.name, "requests");
+        assert_eq!(most_exposed.advisories, ["GHSA-one", "GHSA-two"]);
+        assert_eq!(
+            most_exposed.projects,
+            [PathBuf::from("/src/api"), PathBuf::from("/src/worker")]
+        );
+        assert_eq!(most_exposed.fixed_version.as_deref(), Some("2.32.0"));
+        assert_eq!(report.vulnerable.as_ref().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn finds_every_project_using_a_package() {
+        let reports = [
+            project("/src/api", &[("Log4J_Core", "2.14.1"), ("flask", "3.0.0")]),
+            project("/src/worker", &[("celery", "5.3.0")]),
+            project("/src/web", &[("log4j-api", "2.17.0")]),
+        ];
+
+        let matches = find_package(&reports, "log4j");
+        let found: Vec<(&str, &Path)> = matches
+            .iter()
+            .map(|found| (found.name.as_str(), found.project.as_path()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("Log4J_Core", Path::new("/src/api")),
+                ("log4j-api", Path::new("/src/web"))
+            ]
+        );
+        assert_eq!(matches[0].version, "2.14.1");
+        assert_eq!(matches[0].locked, None);
+        assert!(find_package(&reports, "log4j-core").len() == 1);
+        assert!(find_package(&reports, "openssl").is_empty());
+    }
+
+    #[test]
+    fn resolves_versions_from_the_lockfile() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.lock"),
+            "version = 3\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.197\"\n",
+        )
+        .unwrap();
+        let mut serde = dependency("/", "serde", "1.0");
+        serde.ecosystem = Ecosystem::Rust;
+        serde.source_file = temp_dir.path().join("Cargo.toml");
+        let mut resolver = Resolver::default();
+        assert_eq!(resolver.resolve(&serde), "1.0.197");
+
+        serde.version = "2".to_string();
+        assert_eq!(resolver.resolve(&serde), "2");
+    }
+
+    #[test]
+    fn resolves_python_versions_from_poetry_lock() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("poetry.lock"),
+            "[[package]]\nname = \"Flask_Cors\"\nversion = \"4.0.1\"\n",
+        )
+        .unwrap();
+        let mut flask = dependency("/", "flask-cors", ">=4.0");
+        flask.ecosystem = Ecosystem::Python;
+        flask.source_file = temp_dir.path().join("pyproject.toml");
+        let mut resolver = Resolver::default();
+        assert_eq!(resolver.locked(&flask).as_deref(), Some("4.0.1"));
+
+        flask.name = "requests".to_string();
+        assert_eq!(resolver.locked(&flask), None);
+    }
+}
@@ -0,0 +1,1406 @@
+//! Forge (GitHub, GitLab) enrichment of git results
+//!
+//! With an access token stored by `devhealth auth login github|gitlab`, set
+//! in `GITHUB_TOKEN`/`GH_TOKEN` and `GITLAB_TOKEN`, or configured under
+//! `[forge]` in `devhealth.toml` (see [`auth::resolve`]), repositories whose
+//! `origin` remote points at GitHub or GitLab are enriched with the CI status
+//! of their default branch, so a healthy local checkout with a red `main` is
+//! visible in the same report:
+//!
+//! - **GitHub**: check runs (GitHub Actions and other apps) and commit
+//!   statuses of the default branch head, combined
+//! - **GitLab**: the latest pipeline on the default branch of gitlab.com or
+//!   of the hosts listed in `gitlab_hosts`
+//!
+//! Local branches are also matched with the repository's open and merged
+//! pull requests (GitHub) or merge requests (GitLab), reporting branches
+//! with unmerged commits and no open PR, open PRs with unresolved review
+//! threads, and merged PRs whose local branch was never deleted.
+//!
+//! GitHub forks without an `upstream` remote also get their fork status
+//! (commits behind the parent's default branch) from the API, and can be
+//! synced on GitHub with [`sync_fork`]. Forks with an `upstream` remote are
+//! measured locally by the git scanner instead.
+//!
+//! Repositories archived on the forge are flagged as such: their findings
+//! are capped at `archived_level` under `[severity]` (`info` by default), and
+//! the clone is suggested for removal when it holds no local work.
+//!
+//! [`list_repositories`] lists every repository the tokens give access to,
+//! for `devhealth repos missing` (see [`crate::repos`]).
+//!
+//! Repositories on other hosts, or on a forge without a token, are left
+//! alone. The queries ask the [`NetworkGate`] first, so `--offline` skips
+//! them.
+
+use crate::auth::{self, Provider};
+use crate::config::ForgeConfig;
+use crate::scanner::git::{ForkSource, ForkStatus, GitRepo};
+use crate::utils::net::{NetworkGate, RateLimiter};
+use crate::utils::redact;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Base URL of the GitHub REST API
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Open and merged pull requests of a GitHub repository, with their review
+/// threads; merged ones are the most recently updated
+const GITHUB_PULL_REQUESTS_QUERY: &str = "query($owner: String!, $name: String!) {
+  repository(owner: $owner, name: $name) {
+    open: pullRequests(states: OPEN, first: 100) { nodes { ...pr } }
+    merged: pullRequests(states: MERGED, first: 100, orderBy: {field: UPDATED_AT, direction: DESC}) { nodes { ...pr } }
+  }
+}
+fragment pr on PullRequest {
+  number url headRefName headRepositoryOwner { login }
+  reviewThreads(first: 100) { nodes { isResolved } }
+}";
+
+/// Name of the enrichment in the list of checks skipped offline
+pub const FORGE_CHECK: &str = "forge enrichment (CI status, forks)";
+
+/// Repositories requested per page when listing an account's repositories
+const LIST_PAGE_SIZE: usize = 100;
+
+/// Pages listed per forge host at most, so a huge organization cannot make
+/// a listing run for minutes
+const MAX_LIST_PAGES: usize = 50;
+
+/// Errors that can occur while querying a forge
+#[derive(Error, Debug)]
+pub enum ForgeError {
+    #[error("Forge request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("No {0:?} token configured (see [forge] in devhealth.toml)")]
+    MissingToken(Forge),
+    #[error("Syncing forks through the API is only supported on GitHub")]
+    Unsupported,
+    #[error("No GitHub or GitLab token found; store one with `devhealth auth login github` or `devhealth auth login gitlab`")]
+    NoToken,
+    #[error("Forge API error: {0}")]
+    Api(String),
+}
+
+/// A supported code forge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Forge {
+    /// github.com
+    GitHub,
+    /// gitlab.com or a self-managed GitLab instance
+    GitLab,
+}
+
+/// A repository on a forge, as named by a git remote URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remote {
+    /// The forge hosting the repository
+    pub forge: Forge,
+    /// Host name, such as `github.com`
+    pub host: String,
+    /// Owner and repository (`owner/repo`); GitLab subgroups are included
+    pub project: String,
+}
+
+/// CI status of a repository's default branch
+///
+/// Ordered from best to worst, so combining several sources keeps the
+/// maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CiStatus {
+    /// No pipeline or check has run on the default branch
+    NoRuns,
+    /// Every pipeline or check succeeded
+    Passed,
+    /// A pipeline or check is still queued or running
+    Running,
+    /// A pipeline or check failed or was cancelled
+    Failed,
+    /// The forge could not be queried
+    Unknown,
+}
+
+/// A pull request (GitHub) or merge request (GitLab) of a local branch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PullRequest {
+    /// Number of the pull request, or IID of the merge request
+    pub number: u64,
+    /// Source branch
+    pub branch: String,
+    /// Web page of the pull request
+    pub url: String,
+    /// Review threads not yet marked as resolved
+    pub unresolved_threads: usize,
+}
+
+/// A repository the forge tokens give access to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForgeRepository {
+    /// The forge hosting the repository
+    pub forge: Forge,
+    /// Host name, such as `github.com`
+    pub host: String,
+    /// Owner and repository (`owner/repo`); GitLab subgroups are included
+    pub project: String,
+    /// HTTPS clone URL
+    pub clone_url: String,
+    /// SSH clone URL
+    pub ssh_url: String,
+    /// Web page of the repository
+    pub web_url: String,
+    /// Whether the repository is archived and read-only
+    pub archived: bool,
+    /// Whether the repository is a fork
+    pub fork: bool,
+}
+
+/// How a repository's local branches relate to its pull requests
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewStatus {
+    /// The forge hosting the pull requests
+    pub forge: Forge,
+    /// Local branches with commits missing from the default branch and no
+    /// open pull request
+    pub without_pr: Vec<String>,
+    /// Open pull requests of local branches with unresolved review threads
+    pub unresolved: Vec<PullRequest>,
+    /// Merged pull requests whose local branch still exists
+    pub merged: Vec<PullRequest>,
+}
+
+impl ReviewStatus {
+    /// Returns the number of branches and pull requests needing attention
+    pub fn issue_count(&self) -> usize {
+        self.without_pr.len() + self.unresolved.len() + self.merged.len()
+    }
+
+    /// Formats a pull request reference as the forge does, such as `#12`
+    /// on GitHub or `!12` on GitLab
+    pub fn reference(&self, pull_request: &PullRequest) -> String {
+        match self.forge {
+            Forge::GitHub => format!("#{}", pull_request.number),
+            Forge::GitLab => format!("!{}", pull_request.number),
+        }
+    }
+}
+
+/// Local branches of a repository
+#[derive(Debug, Default)]
+struct LocalBranches {
+    /// Every local branch
+    all: Vec<String>,
+    /// Branches with commits missing from the default branch
+    unmerged: Vec<String>,
+}
+
+/// Access tokens per forge, resolved from the keyring, the environment and
+/// the config
+#[derive(Clone, Default)]
+struct Tokens {
+    github: Option<String>,
+    gitlab: Option<String>,
+}
+
+impl Tokens {
+    /// Resolves tokens from the keyring first, then the environment, then
+    /// the config
+    fn resolve(
+        config: &ForgeConfig,
+        keyring: impl Fn(Provider) -> Option<String>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Tokens {
+        let token = |provider: Provider, configured: &Option<String>| {
+            auth::resolve_with(
+                provider,
+                provider.env_vars(),
+                configured.as_deref(),
+                &keyring,
+                &env,
+            )
+        };
+        Tokens {
+            github: token(Provider::GitHub, &config.github_token),
+            gitlab: token(Provider::GitLab, &config.gitlab_token),
+        }
+    }
+
+    fn for_forge(&self, forge: Forge) -> Option<&str> {
+        match forge {
+            Forge::GitHub => self.github.as_deref(),
+            Forge::GitLab => self.gitlab.as_deref(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.github.is_none() && self.gitlab.is_none()
+    }
+}
+
+/// Adds forge facts to every repository hosted on a forge
+///
+/// Sets the default-branch CI status, the review status of local branches
+/// and, for GitHub forks without a local `upstream` remote, the fork status.
+/// Does nothing when no forge token is
+/// configured. Repositories whose forge cannot be queried get
+/// [`CiStatus::Unknown`] and a warning on stderr.
+///
+/// # Arguments
+///
+/// * `repos` - Repositories from the git scanner, updated in place
+/// * `config` - The `[forge]` section with tokens and GitLab hosts
+/// * `network` - Gate consulted before any request is made
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use devhealth::config::ForgeConfig;
+/// use devhealth::scanner::{forge, git};
+/// use devhealth::utils::net::NetworkGate;
+/// use std::path::Path;
+///
+/// let mut repos = git::scan_directory(Path::new("."))?;
+/// forge::enrich_repositories(&mut repos, &ForgeConfig::default(), &mut NetworkGate::new(false));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn enrich_repositories(repos: &mut [GitRepo], config: &ForgeConfig, network: &mut NetworkGate) {
+    let tokens = Tokens::resolve(
+        config,
+        |provider| auth::stored(provider).ok().flatten(),
+        |name| std::env::var(name).ok(),
+    );
+    if tokens.is_empty() || repos.is_empty() || !network.allow(FORGE_CHECK) {
+        return;
+    }
+    let mut client = match ForgeClient::new(tokens) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!(
+                "Warning: could not query the forge: {}",
+                redact::text(&e.to_string())
+            );
+            return;
+        }
+    };
+
+    for repo in repos.iter_mut() {
+        let Some(remote) = remote_for(&repo.path, config) else {
+            continue;
+        };
+        if client.tokens.for_forge(remote.forge).is_none() {
+            continue;
+        }
+        match client.repository_facts(&remote, &repo.path) {
+            Ok(facts) => {
+                repo.ci = Some(facts.ci);
+                repo.archived = facts.archived;
+                repo.reviews = facts.reviews;
+                if repo.fork.is_none() {
+                    repo.fork = facts.fork;
+                }
+            }
+            Err(e) => {
+                eprintln!(
+                    "Warning: could not query {}: {}",
+                    remote.project,
+                    redact::text(&e.to_string())
+                );
+                repo.ci = Some(CiStatus::Unknown);
+            }
+        }
+    }
+}
+
+/// Parses a git remote URL naming a repository on GitHub or GitLab
+///
+/// Accepts HTTPS (`https://github.com/owner/repo.git`), SSH
+/// (`ssh://git@host:22/owner/repo`) and scp-like (`git@github.com:owner/repo`)
+/// URLs. Returns `None` for other hosts and for local paths.
+///
+/// # Arguments
+///
+/// * `url` - The remote URL
+/// * `gitlab_hosts` - Hosts of self-managed GitLab instances
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::forge::{self, Forge};
+///
+/// let remote = forge::parse_remote("git@github.com:rust-lang/rust.git", &[]).unwrap();
+/// assert_eq!(remote.forge, Forge::GitHub);
+/// assert_eq!(remote.project, "rust-lang/rust");
+/// ```
+pub fn parse_remote(url: &str, gitlab_hosts: &[String]) -> Option<Remote> {
+    let url = url.trim();
+    let (host, path) = match url.split_once("://") {
+        Some((_, rest)) => {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit('@').next()?;
+            (host.split(':').next()?, path)
+        }
+        None => {
+            let (authority, path) = url.split_once(':')?;
+            (authority.rsplit('@').next()?, path)
+        }
+    };
+
+    let host = host.to_lowercase();
+    let forge = if host == "github.com" {
+        Forge::GitHub
+    } else if host == "gitlab.com" || gitlab_hosts.iter().any(|h| h.eq_ignore_ascii_case(&host)) {
+        Forge::GitLab
+    } else {
+        return None;
+    };
+
+    let project = path.trim_matches('/');
+    let project = project.strip_suffix(".git").unwrap_or(project);
+    let segments: Vec<&str> = project.split('/').collect();
+    let valid = match forge {
+        Forge::GitHub => segments.len() == 2,
+        Forge::GitLab => segments.len() >= 2,
+    };
+    if !valid || segments.iter().any(|segment| segment.is_empty()) {
+        return None;
+    }
+
+    Some(Remote {
+        forge,
+        host,
+        project: project.to_string(),
+    })
+}
+
+/// Syncs a GitHub fork's branch with its parent on GitHub
+///
+/// Uses GitHub's "sync fork" operation, which fast-forwards the branch or,
+/// when it has commits of its own, merges the parent into it; conflicts are
+/// reported as errors.
+///
+/// # Arguments
+///
+/// * `remote` - The fork on GitHub
+/// * `branch` - The fork branch to update
+/// * `config` - The `[forge]` section providing the GitHub token
+///
+/// # Returns
+///
+/// GitHub's description of what happened.
+///
+/// # Errors
+///
+/// Returns an error if the remote is not on GitHub, no GitHub token is
+/// configured, or the request fails.
+pub fn sync_fork(
+    remote: &Remote,
+    branch: &str,
+    config: &ForgeConfig,
+) -> Result<String, ForgeError> {
+    if remote.forge != Forge::GitHub {
+        return Err(ForgeError::Unsupported);
+    }
+    let tokens = Tokens::resolve(
+        config,
+        |provider| auth::stored(provider).ok().flatten(),
+        |name| std::env::var(name).ok(),
+    );
+    let Some(token) = tokens.github.clone() else {
+        return Err(ForgeError::MissingToken(Forge::GitHub));
+    };
+    let client = ForgeClient::new(tokens)?;
+
+    #[derive(Deserialize)]
+    struct MergeUpstream {
+        message: String,
+    }
+
+    let response: MergeUpstream = client
+        .http
+        .post(format!(
+            "{}/repos/{}/merge-upstream",
+            GITHUB_API_URL, remote.project
+        ))
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&serde_json::json!({ "branch": branch }))
+        .send()?
+        .error_for_status()?
+        .json()?;
+    Ok(response.message)
+}
+
+/// Lists the repositories the forge tokens give access to
+///
+/// On GitHub, those the token's account owns, collaborates on or can see as
+/// an organization member; on gitlab.com and every host in `gitlab_hosts`,
+/// the projects the account is a member of. A GitLab host that cannot be
+/// queried is skipped with a warning on stderr, as long as another forge
+/// answers. At most [`MAX_LIST_PAGES`] pages of 100 repositories are listed
+/// per host.
+///
+/// # Errors
+///
+/// Returns [`ForgeError::NoToken`] if no forge token is configured, and the
+/// last request error if no forge could be queried.
+pub fn list_repositories(config: &ForgeConfig) -> Result<Vec<ForgeRepository>, ForgeError> {
+    let tokens = Tokens::resolve(
+        config,
+        |provider| auth::stored(provider).ok().flatten(),
+        |name| std::env::var(name).ok(),
+    );
+    if tokens.is_empty() {
+        return Err(ForgeError::NoToken);
+    }
+    let mut client = ForgeClient::new(tokens)?;
+
+    let mut results = Vec::new();
+    if client.tokens.github.is_some() {
+        results.push(client.github_repositories());
+    }
+    if client.tokens.gitlab.is_some() {
+        for host in
+            std::iter::once("gitlab.com").chain(config.gitlab_hosts.iter().map(String::as_str))
+        {
+            results.push(client.gitlab_projects(host));
+        }
+    }
+
+    let mut repositories = Vec::new();
+    let mut error = None;
+    for result in results {
+        match result {
+            Ok(listed) => repositories.extend(listed),
+            Err(e) => {
+                eprintln!(
+                    "Warning: could not list repositories: {}",
+                    redact::text(&e.to_string())
+                );
+                error = Some(e);
+            }
+        }
+    }
+    match error {
+        Some(e) if repositories.is_empty() => Err(e),
+        _ => Ok(repositories),
+    }
+}
+
+/// Returns the forge repository a local repository's `origin` points at
+///
+/// # Arguments
+///
+/// * `repo_path` - The local repository
+/// * `config` - The `[forge]` section listing self-managed GitLab hosts
+pub fn remote_for(repo_path: &Path, config: &ForgeConfig) -> Option<Remote> {
+    origin_url(repo_path).and_then(|url| parse_remote(&url, &config.gitlab_hosts))
+}
+
+/// Lists the local branches of a repository
+///
+/// Branches are compared with the local default branch, or with its
+/// `origin` copy when there is no local one; without either, every branch
+/// counts as unmerged.
+fn local_branches(repo_path: &Path, default_branch: &str) -> LocalBranches {
+    let list = |extra: Option<&str>| -> Option<Vec<String>> {
+        let mut command = Command::new("git");
+        command
+            .args(["for-each-ref", "--format=%(refname:short)"])
+            .current_dir(repo_path);
+        if let Some(extra) = extra {
+            command.arg(extra);
+        }
+        let output = command.arg("refs/heads").output().ok()?;
+        output.status.success().then(|| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|branch| *branch != default_branch)
+                .map(str::to_string)
+                .collect()
+        })
+    };
+
+    let all = list(None).unwrap_or_default();
+    let unmerged = [
+        format!("refs/heads/{}", default_branch),
+        format!("refs/remotes/origin/{}", default_branch),
+    ]
+    .iter()
+    .find_map(|base| list(Some(&format!("--no-merged={}", base))))
+    .unwrap_or_else(|| all.clone());
+    LocalBranches { all, unmerged }
+}
+
+/// Matches local branches with their open and merged pull requests
+///
+/// `merged` is expected most recent first; only the latest merged pull
+/// request of a branch is kept.
+fn review_status(
+    forge: Forge,
+    branches: &LocalBranches,
+    open: Vec<PullRequest>,
+    merged: Vec<PullRequest>,
+) -> ReviewStatus {
+    let is_local = |branch: &String| branches.all.contains(branch);
+    let has_open = |branch: &String| open.iter().any(|pr| &pr.branch == branch);
+
+    let mut merged_local: Vec<PullRequest> = Vec::new();
+    for pull_request in merged {
+        if is_local(&pull_request.branch)
+            && !has_open(&pull_request.branch)
+            && !merged_local
+                .iter()
+                .any(|pr| pr.branch == pull_request.branch)
+        {
+            merged_local.push(pull_request);
+        }
+    }
+
+    ReviewStatus {
+        forge,
+        without_pr: branches
+            .unmerged
+            .iter()
+            .filter(|branch| {
+                !has_open(branch) && !merged_local.iter().any(|pr| &pr.branch == *branch)
+            })
+            .cloned()
+            .collect(),
+        unresolved: open
+            .iter()
+            .filter(|pr| pr.unresolved_threads > 0 && is_local(&pr.branch))
+            .cloned()
+            .collect(),
+        merged: merged_local,
+    }
+}
+
+/// Returns the URL of the `origin` remote, or of the only remote
+fn origin_url(repo_path: &Path) -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(repo_path)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    git(&["remote", "get-url", "origin"]).or_else(|| {
+        let remotes = git(&["remote"])?;
+        let mut remotes = remotes.lines();
+        match (remotes.next(), remotes.next()) {
+            (Some(remote), None) => git(&["remote", "get-url", remote]),
+            _ => None,
+        }
+    })
+}
+
+/// HTTP client for the forge APIs
+struct ForgeClient {
+    tokens: Tokens,
+    http: reqwest::blocking::Client,
+    limiter: RateLimiter,
+}
+
+/// What one round of forge queries found about a repository
+struct RepositoryFacts {
+    ci: CiStatus,
+    archived: bool,
+    fork: Option<ForkStatus>,
+    reviews: Option<ReviewStatus>,
+}
+
+#[derive(Deserialize)]
+struct GitHubRepository {
+    default_branch: String,
+    #[serde(default)]
+    fork: bool,
+    #[serde(default)]
+    archived: bool,
+    parent: Option<GitHubParent>,
+}
+
+#[derive(Deserialize)]
+struct GitHubListedRepository {
+    full_name: String,
+    clone_url: String,
+    ssh_url: String,
+    html_url: String,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    fork: bool,
+}
+
+impl GitHubListedRepository {
+    fn into_repository(self) -> ForgeRepository {
+        ForgeRepository {
+            forge: Forge::GitHub,
+            host: "github.com".to_string(),
+            project: self.full_name,
+            clone_url: self.clone_url,
+            ssh_url: self.ssh_url,
+            web_url: self.html_url,
+            archived: self.archived,
+            fork: self.fork,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitHubParent {
+    full_name: String,
+    default_branch: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubComparison {
+    behind_by: usize,
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse {
+    data: Option<GraphQlData>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct GraphQlData {
+    repository: Option<GraphQlRepository>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlRepository {
+    open: GraphQlConnection<GraphQlPullRequest>,
+    merged: GraphQlConnection<GraphQlPullRequest>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlConnection<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPullRequest {
+    number: u64,
+    url: String,
+    head_ref_name: String,
+    head_repository_owner: Option<GraphQlOwner>,
+    review_threads: GraphQlConnection<GraphQlReviewThread>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlOwner {
+    login: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlReviewThread {
+    is_resolved: bool,
+}
+
+#[derive(Deserialize)]
+struct GitHubCheckRuns {
+    check_runs: Vec<GitHubCheckRun>,
+}
+
+#[derive(Deserialize)]
+struct GitHubCheckRun {
+    status: String,
+    conclusion: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GitHubCombinedStatus {
+    state: String,
+    total_count: usize,
+}
+
+#[derive(Deserialize)]
+struct GitLabProject {
+    id: u64,
+    default_branch: Option<String>,
+    #[serde(default)]
+    archived: bool,
+}
+
+#[derive(Deserialize)]
+struct GitLabListedProject {
+    path_with_namespace: String,
+    http_url_to_repo: String,
+    ssh_url_to_repo: String,
+    web_url: String,
+    #[serde(default)]
+    archived: bool,
+    forked_from_project: Option<serde::de::IgnoredAny>,
+}
+
+impl GitLabListedProject {
+    fn into_repository(self, host: &str) -> ForgeRepository {
+        ForgeRepository {
+            forge: Forge::GitLab,
+            host: host.to_lowercase(),
+            project: self.path_with_namespace,
+            clone_url: self.http_url_to_repo,
+            ssh_url: self.ssh_url_to_repo,
+            web_url: self.web_url,
+            archived: self.archived,
+            fork: self.forked_from_project.is_some(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitLabMergeRequest {
+    iid: u64,
+    source_branch: String,
+    source_project_id: u64,
+    web_url: String,
+    #[serde(default = "resolved_by_default")]
+    blocking_discussions_resolved: bool,
+}
+
+#[derive(Deserialize)]
+struct GitLabDiscussion {
+    #[serde(default)]
+    notes: Vec<GitLabNote>,
+}
+
+#[derive(Deserialize)]
+struct GitLabNote {
+    #[serde(default)]
+    resolvable: bool,
+    #[serde(default)]
+    resolved: bool,
+}
+
+fn resolved_by_default() -> bool {
+    true
+}
+
+impl GitLabMergeRequest {
+    fn into_pull_request(self, unresolved_threads: usize) -> PullRequest {
+        PullRequest {
+            number: self.iid,
+            branch: self.source_branch,
+            url: self.web_url,
+            unresolved_threads,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitLabPipeline {
+    status: String,
+}
+
+impl ForgeClient {
+    fn new(tokens: Tokens) -> Result<ForgeClient, ForgeError> {
+        let http = reqwest::blocking::Client::builder()
+            .user_agent(concat!("devhealth/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(15))
+            .build()?;
+        Ok(ForgeClient {
+            tokens,
+            http,
+            limiter: RateLimiter::default(),
+        })
+    }
+
+    /// Fetches the CI status, the review status of local branches and, on
+    /// GitHub, the fork status of a repository
+    fn repository_facts(
+        &mut self,
+        remote: &Remote,
+        repo_path: &Path,
+    ) -> Result<RepositoryFacts, ForgeError> {
+        match remote.forge {
+            Forge::GitHub => self.github_facts(&remote.project, repo_path),
+            Forge::GitLab => self.gitlab_facts(&remote.host, &remote.project, repo_path),
+        }
+    }
+
+    fn github_get<T: for<'de> Deserialize<'de>>(&mut self, path: &str) -> Result<T, ForgeError> {
+        self.limiter.wait();
+        let token = self.tokens.github.as_deref().unwrap_or_default();
+        Ok(self
+            .http
+            .get(format!("{}/{}", GITHUB_API_URL, path))
+            .bearer_auth(token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    fn github_facts(
+        &mut self,
+        project: &str,
+        repo_path: &Path,
+    ) -> Result<RepositoryFacts, ForgeError> {
+        let repository: GitHubRepository = self.github_get(&format!("repos/{}", project))?;
+        let branch = encode_component(&repository.default_branch);
+        let runs: GitHubCheckRuns = self.github_get(&format!(
+            "repos/{}/commits/{}/check-runs?per_page=100",
+            project, branch
+        ))?;
+        let statuses: GitHubCombinedStatus =
+            self.github_get(&format!("repos/{}/commits/{}/status", project, branch))?;
+        let ci = check_runs_status(&runs.check_runs).max(combined_status(&statuses));
+
+        // Pull requests of a fork's branches are opened against its parent
+        let owner = project.split('/').next().unwrap_or_default();
+        let base = match repository.parent.as_ref().filter(|_| repository.fork) {
+            Some(parent) => parent.full_name.as_str(),
+            None => project,
+        };
+        let branches = local_branches(repo_path, &repository.default_branch);
+        let (open, merged) = self.github_pull_requests(base, owner)?;
+        let reviews = review_status(Forge::GitHub, &branches, open, merged);
+
+        let fork = match repository.parent.filter(|_| repository.fork) {
+            Some(parent) => {
+                let comparison: GitHubComparison = self.github_get(&format!(
+                    "repos/{}/compare/{}...{}:{}",
+                    parent.full_name,
+                    encode_component(&parent.default_branch),
+                    owner,
+                    branch
+                ))?;
+                Some(ForkStatus {
+                    upstream: parent.full_name,
+                    branch: parent.default_branch,
+                    behind: comparison.behind_by,
+                    source: ForkSource::Forge,
+                })
+            }
+            None => None,
+        };
+        Ok(RepositoryFacts {
+            ci,
+            archived: repository.archived,
+            fork,
+            reviews: Some(reviews),
+        })
+    }
+
+    /// Fetches the open and merged pull requests of `base` opened from
+    /// branches of `owner`
+    fn github_pull_requests(
+        &mut self,
+        base: &str,
+        owner: &str,
+    ) -> Result<(Vec<PullRequest>, Vec<PullRequest>), ForgeError> {
+        let (base_owner, name) = base.split_once('/').unwrap_or((base, ""));
+        self.limiter.wait();
+        let response: GraphQlResponse = self
+            .http
+            .post(format!("{}/graphql", GITHUB_API_URL))
+            .bearer_auth(self.tokens.github.as_deref().unwrap_or_default())
+            .json(&serde_json::json!({
+                "query": GITHUB_PULL_REQUESTS_QUERY,
+                "variables": { "owner": base_owner, "name": name },
+            }))
+            .send()?
+            .error_for_status()?
+            .json()?;
+        if let Some(error) = response.errors.first() {
+            return Err(ForgeError::Api(error.message.clone()));
+        }
+        let Some(repository) = response.data.and_then(|data| data.repository) else {
+            return Err(ForgeError::Api(format!("repository {} not found", base)));
+        };
+
+        let convert = |nodes: Vec<GraphQlPullRequest>| -> Vec<PullRequest> {
+            nodes
+                .into_iter()
+                .filter(|pr| {
+                    pr.head_repository_owner
+                        .as_ref()
+                        .is_some_and(|head| head.login.eq_ignore_ascii_case(owner))
+                })
+                .map(|pr| PullRequest {
+                    number: pr.number,
+                    branch: pr.head_ref_name,
+                    url: pr.url,
+                    unresolved_threads: pr
+                        .review_threads
+                        .nodes
+                        .iter()
+                        .filter(|thread| !thread.is_resolved)
+                        .count(),
+                })
+                .collect()
+        };
+        Ok((
+            convert(repository.open.nodes),
+            convert(repository.merged.nodes),
+        ))
+    }
+
+    /// Lists the repositories of the GitHub account, page by page
+    fn github_repositories(&mut self) -> Result<Vec<ForgeRepository>, ForgeError> {
+        let mut repositories = Vec::new();
+        for page in 1..=MAX_LIST_PAGES {
+            let listed: Vec<GitHubListedRepository> = self.github_get(&format!(
+                "user/repos?affiliation=owner,collaborator,organization_member&sort=full_name&per_page={}&page={}",
+                LIST_PAGE_SIZE, page
+            ))?;
+            let last = listed.len() < LIST_PAGE_SIZE;
+            repositories.extend(
+                listed
+                    .into_iter()
+                    .map(GitHubListedRepository::into_repository),
+            );
+            if last {
+                break;
+            }
+        }
+        Ok(repositories)
+    }
+
+    /// Lists the projects the GitLab account is a member of on `host`
+    fn gitlab_projects(&mut self, host: &str) -> Result<Vec<ForgeRepository>, ForgeError> {
+        let url = format!("https://{}/api/v4/projects", host);
+        let per_page = LIST_PAGE_SIZE.to_string();
+        let mut repositories = Vec::new();
+        for page in 1..=MAX_LIST_PAGES {
+            let page = page.to_string();
+            let listed: Vec<GitLabListedProject> = self.gitlab_get(
+                &url,
+                &[
+                    ("membership", "true"),
+                    ("order_by", "path"),
+                    ("sort", "asc"),
+                    ("per_page", &per_page),
+                    ("page", &page),
+                ],
+            )?;
+            let last = listed.len() < LIST_PAGE_SIZE;
+            repositories.extend(
+                listed
+                    .into_iter()
+                    .map(|project| project.into_repository(host)),
+            );
+            if last {
+                break;
+            }
+        }
+        Ok(repositories)
+    }
+
+    fn gitlab_get<T: for<'de> Deserialize<'de>>(
+        &mut self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, ForgeError> {
+        self.limiter.wait();
+        let token = self.tokens.gitlab.as_deref().unwrap_or_default();
+        Ok(self
+            .http
+            .get(url)
+            .query(query)
+            .header("PRIVATE-TOKEN", token)
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    fn gitlab_facts(
+        &mut self,
+        host: &str,
+        project: &str,
+        repo_path: &Path,
+    ) -> Result<RepositoryFacts, ForgeError> {
+        let base = format!(
+            "https://{}/api/v4/projects/{}",
+            host,
+            encode_component(project)
+        );
+
+        let details: GitLabProject = self.gitlab_get(&base, &[])?;
+        let Some(branch) = details.default_branch else {
+            return Ok(RepositoryFacts {
+                ci: CiStatus::NoRuns,
+                archived: details.archived,
+                fork: None,
+                reviews: None,
+            });
+        };
+
+        let pipelines: Vec<GitLabPipeline> = self.gitlab_get(
+            &format!("{}/pipelines", base),
+            &[("ref", branch.as_str()), ("per_page", "1")],
+        )?;
+        let ci = pipelines.first().map_or(CiStatus::NoRuns, |pipeline| {
+            pipeline_status(&pipeline.status)
+        });
+
+        let merge_requests = format!("{}/merge_requests", base);
+        let opened: Vec<GitLabMergeRequest> =
+            self.gitlab_get(&merge_requests, &[("state", "opened"), ("per_page", "100")])?;
+        let merged: Vec<GitLabMergeRequest> = self.gitlab_get(
+            &merge_requests,
+            &[
+                ("state", "merged"),
+                ("order_by", "updated_at"),
+                ("per_page", "100"),
+            ],
+        )?;
+
+        let mut open = Vec::new();
+        for merge_request in opened
+            .into_iter()
+            .filter(|mr| mr.source_project_id == details.id)
+        {
+            // Discussions are only fetched for merge requests GitLab reports
+            // as having unresolved threads
+            let unresolved_threads = if merge_request.blocking_discussions_resolved {
+                0
+            } else {
+                let discussions: Vec<GitLabDiscussion> = self.gitlab_get(
+                    &format!("{}/{}/discussions", merge_requests, merge_request.iid),
+                    &[("per_page", "100")],
+                )?;
+                discussions
+                    .iter()
+                    .filter(|discussion| {
+                        discussion
+                            .notes
+                            .iter()
+                            .any(|note| note.resolvable && !note.resolved)
+                    })
+                    .count()
+            };
+            open.push(merge_request.into_pull_request(unresolved_threads));
+        }
+        let merged = merged
+            .into_iter()
+            .filter(|mr| mr.source_project_id == details.id)
+            .map(|mr| mr.into_pull_request(0))
+            .collect();
+
+        let branches = local_branches(repo_path, &branch);
+        Ok(RepositoryFacts {
+            ci,
+            archived: details.archived,
+            fork: None,
+            reviews: Some(review_status(Forge::GitLab, &branches, open, merged)),
+        })
+    }
+}
+
+/// Combines GitHub check runs into one status
+fn check_runs_status(runs: &[GitHubCheckRun]) -> CiStatus {
+    runs.iter()
+        .map(
+            |run| match (run.status.as_str(), run.conclusion.as_deref()) {
+                ("completed", Some("success" | "neutral" | "skipped")) => CiStatus::Passed,
+                ("completed", _) => CiStatus::Failed,
+                _ => CiStatus::Running,
+            },
+        )
+        .max()
+        .unwrap_or(CiStatus::NoRuns)
+}
+
+/// Maps GitHub's combined commit status, which is "pending" without statuses
+fn combined_status(status: &GitHubCombinedStatus) -> CiStatus {
+    match status.state.as_str() {
+        _ if status.total_count == 0 => CiStatus::NoRuns,
+        "success" => CiStatus::Passed,
+        "pending" => CiStatus::Running,
+        _ => CiStatus::Failed,
+    }
+}
+
+/// Maps a GitLab pipeline status
+fn pipeline_status(status: &str) -> CiStatus {
+    match status {
+        "success" => CiStatus::Passed,
+        "failed" | "canceled" => CiStatus::Failed,
+        "skipped" => CiStatus::NoRuns,
+        _ => CiStatus::Running,
+    }
+}
+
+/// Percent-encodes everything but unreserved URL characters
+fn encode_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod remotes {
+        use super::*;
+
+        #[test]
+        fn parses_https_ssh_and_scp_urls() {
+            for url in [
+                "https://github.com/rust-lang/cargo.git",
+                "https://token@github.com/rust-lang/cargo",
+                "ssh://git@github.com/rust-lang/cargo.git",
+                "git@github.com:rust-lang/cargo.git",
+                "git@GitHub.com:rust-lang/cargo/",
+            ] {
+                let remote =
+                    parse_remote(url, &[]).unwrap_or_else(|| panic!("{} should parse", url));
+                assert_eq!(remote.forge, Forge::GitHub, "{}", url);
+                assert_eq!(remote.host, "github.com", "{}", url);
+                assert_eq!(remote.project, "rust-lang/cargo", "{}", url);
+            }
+        }
+
+        #[test]
+        fn recognizes_gitlab_subgroups_and_self_managed_hosts() {
+            let remote = parse_remote("git@gitlab.com:group/sub/api.git", &[]).unwrap();
+            assert_eq!(remote.forge, Forge::GitLab);
+            assert_eq!(remote.project, "group/sub/api");
+
+            let hosts = vec!["git.example.com".to_string()];
+            let remote =
+                parse_remote("ssh://git@git.example.com:2222/team/api.git", &hosts).unwrap();
+            assert_eq!(remote.forge, Forge::GitLab);
+            assert_eq!(remote.host, "git.example.com");
+            assert_eq!(remote.project, "team/api");
+        }
+
+        #[test]
+        fn ignores_other_hosts_and_local_paths() {
+            assert_eq!(parse_remote("git@git.example.com:team/api.git", &[]), None);
+            assert_eq!(parse_remote("/srv/git/api.git", &[]), None);
+            assert_eq!(parse_remote("https://github.com/rust-lang", &[]), None);
+            assert_eq!(parse_remote("https://github.com/a/b/c", &[]), None);
+        }
+    }
+
+    mod statuses {
+        use super::*;
+
+        fn run(status: &str, conclusion: Option<&str>) -> GitHubCheckRun {
+            GitHubCheckRun {
+                status: status.to_string(),
+                conclusion: conclusion.map(str::to_string),
+            }
+        }
+
+        #[test]
+        fn combines_check_runs_worst_first() {
+            assert_eq!(check_runs_status(&[]), CiStatus::NoRuns);
+            assert_eq!(
+                check_runs_status(&[
+                    run("completed", Some("success")),
+                    run("completed", Some("skipped"))
+                ]),
+                CiStatus::Passed
+            );
+            assert_eq!(
+                check_runs_status(&[run("completed", Some("success")), run("in_progress", None)]),
+                CiStatus::Running
+            );
+            assert_eq!(
+                check_runs_status(&[run("queued", None), run("completed", Some("timed_out"))]),
+                CiStatus::Failed
+            );
+        }
+
+        #[test]
+        fn maps_commit_statuses_and_pipelines() {
+            let status = |state: &str, total_count| GitHubCombinedStatus {
+                state: state.to_string(),
+                total_count,
+            };
+            assert_eq!(combined_status(&status("pending", 0)), CiStatus::NoRuns);
+            assert_eq!(combined_status(&status("pending", 2)), CiStatus::Running);
+            assert_eq!(combined_status(&status("error", 1)), CiStatus::Failed);
+            assert_eq!(
+                CiStatus::Passed.max(combined_status(&status("failure", 1))),
+                CiStatus::Failed
+            );
+
+            assert_eq!(pipeline_status("success"), CiStatus::Passed);
+            assert_eq!(pipeline_status("canceled"), CiStatus::Failed);
+            assert_eq!(pipeline_status("waiting_for_resource"), CiStatus::Running);
+        }
+
+        #[test]
+        fn encodes_project_paths_and_branches() {
+            assert_eq!(encode_component("group/sub/api"), "group%2Fsub%2Fapi");
+            assert_eq!(encode_component("release/1.0"), "release%2F1.0");
+        }
+    }
+
+    mod reviews {
+        use super::*;
+
+        fn pr(number: u64, branch: &str, unresolved_threads: usize) -> PullRequest {
+            PullRequest {
+                number,
+                branch: branch.to_string(),
+                url: format!("https://github.com/example/api/pull/{}", number),
+                unresolved_threads,
+            }
+        }
+
+        fn branches(all: &[&str], unmerged: &[&str]) -> LocalBranches {
+            LocalBranches {
+                all: all.iter().map(|b| b.to_string()).collect(),
+                unmerged: unmerged.iter().map(|b| b.to_string()).collect(),
+            }
+        }
+
+        #[test]
+        fn reports_branches_without_open_pull_requests() {
+            let local = branches(&["feature", "spike", "done"], &["feature", "spike"]);
+            let status = review_status(Forge::GitHub, &local, vec![pr(4, "feature", 0)], vec![]);
+
+            assert_eq!(status.without_pr, vec!["spike".to_string()]);
+            assert!(status.unresolved.is_empty());
+            assert_eq!(status.issue_count(), 1);
+        }
+
+        #[test]
+        fn reports_unresolved_threads_of_local_branches_only() {
+            let local = branches(&["feature"], &["feature"]);
+            let open = vec![pr(4, "feature", 2), pr(5, "someone-else", 3)];
+            let status = review_status(Forge::GitLab, &local, open, vec![]);
+
+            assert_eq!(status.unresolved, vec![pr(4, "feature", 2)]);
+            assert_eq!(status.reference(&status.unresolved[0]), "!4");
+        }
+
+        #[test]
+        fn reports_the_latest_merged_pull_request_of_remaining_branches() {
+            let local = branches(&["old", "reopened"], &["old", "reopened"]);
+            let open = vec![pr(9, "reopened", 0)];
+            let merged = vec![
+                pr(8, "old", 0),
+                pr(3, "old", 0),
+                pr(2, "reopened", 0),
+                pr(1, "gone", 0),
+            ];
+            let status = review_status(Forge::GitHub, &local, open, merged);
+
+            assert_eq!(status.merged, vec![pr(8, "old", 0)]);
+            assert_eq!(status.reference(&status.merged[0]), "#8");
+            assert!(
+                status.without_pr.is_empty(),
+                "merged branches are not reported twice"
+            );
+        }
+
+        #[test]
+        fn lists_local_branches_against_the_default_branch() {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let git = |args: &[&str]| {
+                Command::new("git")
+                    .args(["-c", "user.name=Dev", "-c", "user.email=dev@example.com"])
+                    .args(args)
+                    .current_dir(temp_dir.path())
+                    .output()
+                    .unwrap()
+            };
+            git(&["init", "-q", "-b", "main"]);
+            git(&["commit", "-q", "--allow-empty", "-m", "initial"]);
+            git(&["branch", "merged"]);
+            git(&["checkout", "-q", "-b", "feature"]);
+            git(&["commit", "-q", "--allow-empty", "-m", "work"]);
+
+            let local = local_branches(temp_dir.path(), "main");
+            assert_eq!(local.all, vec!["feature".to_string(), "merged".to_string()]);
+            assert_eq!(local.unmerged, vec!["feature".to_string()]);
+        }
+    }
+
+    mod tokens {
+        use super::*;
+
+        #[test]
+        fn prefers_keyring_then_environment_over_config() {
+            let env = |name: &str| match name {
+                "GH_TOKEN" => Some("from-gh".to_string()),
+                "GITLAB_TOKEN" => Some(" ".to_string()),
+                _ => None,
+            };
+            let tokens = Tokens::resolve(&ForgeConfig::default(), |_| None, env);
+            assert_eq!(tokens.github.as_deref(), Some("from-gh"));
+            assert_eq!(tokens.gitlab, None, "Blank tokens are ignored");
+
+            let config = ForgeConfig {
+                github_token: Some("from-config".to_string()),
+                gitlab_token: Some("from-config".to_string()),
+                ..Default::default()
+            };
+            let tokens = Tokens::resolve(&config, |_| None, env);
+            assert_eq!(tokens.for_forge(Forge::GitHub), Some("from-gh"));
+            assert_eq!(tokens.for_forge(Forge::GitLab), Some("from-config"));
+
+            let keyring =
+                |provider| (provider == Provider::GitLab).then(|| "from-keyring".to_string());
+            let tokens = Tokens::resolve(&config, keyring, env);
+            assert_eq!(tokens.for_forge(Forge::GitLab), Some("from-keyring"));
+            assert!(Tokens::resolve(&ForgeConfig::default(), |_| None, |_| None).is_empty());
+        }
+    }
+
+    mod listing {
+        use super::*;
+
+        #[test]
+        fn reads_listed_github_repositories_and_gitlab_projects() {
+            let github: Vec<GitHubListedRepository> = serde_json::from_str(
+                r#"[{"full_name": "ada/api", "clone_url": "https://github.com/ada/api.git",
+                     "ssh_url": "git@github.com:ada/api.git", "html_url": "https://github.com/ada/api",
+                     "archived": true, "fork": false, "private": true}]"#,
+            )
+            .unwrap();
+            let repository = github.into_iter().next().unwrap().into_repository();
+            assert_eq!(repository.forge, Forge::GitHub);
+            assert_eq!(repository.project, "ada/api");
+            assert!(repository.archived && !repository.fork);
+
+            let gitlab: Vec<GitLabListedProject> = serde_json::from_str(
+                r#"[{"path_with_namespace": "team/tools/cli", "http_url_to_repo": "https://Git.Example.com/team/tools/cli.git",
+                     "ssh_url_to_repo": "git@git.example.com:team/tools/cli.git", "web_url": "https://git.example.com/team/tools/cli",
+                     "forked_from_project": {"id": 7}},
+                    {"path_with_namespace": "team/web", "http_url_to_repo": "https://git.example.com/team/web.git",
+                     "ssh_url_to_repo": "git@git.example.com:team/web.git", "web_url": "https://git.example.com/team/web"}]"#,
+            )
+            .unwrap();
+            let projects: Vec<ForgeRepository> = gitlab
+                .into_iter()
+                .map(|project| project.into_repository("Git.Example.com"))
+                .collect();
+            assert_eq!(projects[0].host, "git.example.com");
+            assert_eq!(projects[0].project, "team/tools/cli");
+            assert!(projects[0].fork, "forked_from_project marks a fork");
+            assert!(!projects[1].fork && !projects[1].archived);
+        }
+    }
+}
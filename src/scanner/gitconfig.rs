@@ -0,0 +1,225 @@
+//! Global git configuration audit
+//!
+//! A handful of global settings save every developer the same surprises:
+//!
+//! - `pull.rebase` (or `pull.ff`): without an explicit choice, `git pull`
+//!   warns on every divergent pull and newer versions refuse to pull at all
+//! - `init.defaultBranch`: new repositories otherwise start on `master` with
+//!   a hint on every `git init`
+//! - `core.autocrlf`: `true` on Windows so files are checked out with CRLF
+//!   and committed with LF; `input` or unset elsewhere, since `true` there
+//!   rewrites line endings of files written on Linux and macOS
+//! - `fetch.prune`: removes remote-tracking branches deleted on the remote,
+//!   which otherwise pile up and keep merged work looking unmerged
+//! - `rerere.enabled`: records conflict resolutions so a rebase or repeated
+//!   merge does not ask for the same resolution twice
+//!
+//! `scan --system` reads the global configuration with
+//! `git config --global --list` (honoring `GIT_CONFIG_GLOBAL`) and reports
+//! each deviation with the `git config` command that applies the
+//! recommendation.
+
+use crate::scanner::container;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// A recommended setting that is missing or set differently
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitSetting {
+    /// Configuration key, e.g. `fetch.prune`
+    pub key: String,
+    /// Current value, `None` if unset
+    pub current: Option<String>,
+    /// Recommended value
+    pub recommended: String,
+}
+
+impl GitSetting {
+    /// Returns the command that applies the recommended value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::gitconfig::GitSetting;
+    ///
+    /// let setting = GitSetting {
+    ///     key: "fetch.prune".to_string(),
+    ///     current: None,
+    ///     recommended: "true".to_string(),
+    /// };
+    /// assert_eq!(setting.command(), "git config --global fetch.prune true");
+    /// ```
+    pub fn command(&self) -> String {
+        format!("git config --global {} {}", self.key, self.recommended)
+    }
+}
+
+/// Deviations of the global git configuration from the recommendations
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitConfigReport {
+    /// The global configuration file
+    pub path: PathBuf,
+    /// Settings that differ from the recommendations
+    pub deviations: Vec<GitSetting>,
+}
+
+/// Audits the global git configuration
+///
+/// Returns `None` if git is not installed.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::gitconfig;
+///
+/// if let Some(report) = gitconfig::audit_global_config() {
+///     for setting in &report.deviations {
+///         println!("{}", setting.command());
+///     }
+/// }
+/// ```
+pub fn audit_global_config() -> Option<GitConfigReport> {
+    container::run_command("git", &["--version"])?;
+    let config = container::run_command("git", &["config", "--global", "--list"])
+        .map(|output| parse_config(&output))
+        .unwrap_or_default();
+
+    Some(GitConfigReport {
+        path: global_config_path(),
+        deviations: audit(&config, cfg!(windows)),
+    })
+}
+
+/// Returns the file `git config --global` writes to
+fn global_config_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("GIT_CONFIG_GLOBAL").filter(|path| !path.is_empty()) {
+        return PathBuf::from(path);
+    }
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(".gitconfig")
+}
+
+/// Parses `key=value` lines; later values of a key win, as in git
+fn parse_config(output: &str) -> BTreeMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=').unwrap_or((line, "true"));
+            Some((key.trim().to_lowercase(), value.trim().to_string()))
+                .filter(|(key, _)| !key.is_empty())
+        })
+        .collect()
+}
+
+/// Compares a configuration with the recommendations for the platform
+fn audit(config: &BTreeMap<String, String>, windows: bool) -> Vec<GitSetting> {
+    let get = |key: &str| config.get(key).cloned();
+    let is_true = |key: &str| {
+        get(key).is_some_and(|value| {
+            matches!(value.to_lowercase().as_str(), "true" | "yes" | "on" | "1")
+        })
+    };
+    let mut deviations = Vec::new();
+    let mut deviate = |key: &str, recommended: &str| {
+        deviations.push(GitSetting {
+            key: key.to_string(),
+            current: get(&key.to_lowercase()),
+            recommended: recommended.to_string(),
+        });
+    };
+
+    if get("pull.rebase").is_none() && get("pull.ff").is_none() {
+        deviate("pull.rebase", "false");
+    }
+    if get("init.defaultbranch").is_none() {
+        deviate("init.defaultBranch", "main");
+    }
+    let autocrlf = get("core.autocrlf").map(|value| value.to_lowercase());
+    match (windows, autocrlf.as_deref()) {
+        (true, Some("true")) | (false, None | Some("input" | "false")) => {}
+        (true, _) => deviate("core.autocrlf", "true"),
+        (false, _) => deviate("core.autocrlf", "input"),
+    }
+    if !is_true("fetch.prune") {
+        deviate("fetch.prune", "true");
+    }
+    if !is_true("rerere.enabled") {
+        deviate("rerere.enabled", "true");
+    }
+    deviations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_config_list() {
+        let config = parse_config(
+            "user.name=Ada\ncore.autocrlf=input\nrerere.enabled\ncore.autocrlf=false\n",
+        );
+        assert_eq!(
+            config.get("core.autocrlf").map(String::as_str),
+            Some("false")
+        );
+        assert_eq!(
+            config.get("rerere.enabled").map(String::as_str),
+            Some("true")
+        );
+    }
+
+    #[test]
+    fn reports_missing_recommendations() {
+        let keys = |deviations: Vec<GitSetting>| {
+            deviations
+                .into_iter()
+                .map(|setting| setting.key)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            keys(audit(&BTreeMap::new(), false)),
+            vec![
+                "pull.rebase",
+                "init.defaultBranch",
+                "fetch.prune",
+                "rerere.enabled"
+            ]
+        );
+        assert_eq!(
+            keys(audit(&BTreeMap::new(), true)),
+            vec![
+                "pull.rebase",
+                "init.defaultBranch",
+                "core.autocrlf",
+                "fetch.prune",
+                "rerere.enabled"
+            ]
+        );
+
+        let configured = parse_config("pull.ff=only\ninit.defaultbranch=main\ncore.autocrlf=input\nfetch.prune=true\nrerere.enabled=yes\n");
+        assert!(audit(&configured, false).is_empty());
+    }
+
+    #[test]
+    fn recommends_autocrlf_per_platform() {
+        let config = parse_config("core.autocrlf=true\n");
+        let unix = audit(&config, false);
+        let autocrlf = unix
+            .iter()
+            .find(|setting| setting.key == "core.autocrlf")
+            .unwrap();
+        assert_eq!(autocrlf.current.as_deref(), Some("true"));
+        assert_eq!(
+            autocrlf.command(),
+            "git config --global core.autocrlf input"
+        );
+        assert!(!audit(&config, true)
+            .iter()
+            .any(|setting| setting.key == "core.autocrlf"));
+    }
+}
@@ -0,0 +1,436 @@
+//! Go modules and workspaces (`go.mod`, `go.work`)
+//!
+//! Reads the directives of `go.mod` and `go.work` files beyond `require`:
+//!
+//! - `go` and `toolchain`: the language version and toolchain a module or
+//!   workspace asks for
+//! - `replace`: the dependency is built from another module or a local
+//!   directory. A replaced requirement is listed as its replacement so the
+//!   audit checks what is actually built, and every replacement is reported.
+//!   Replacements in a `go.work` take precedence over those of its modules.
+//! - `exclude`: the listed version is never used. A requirement of an
+//!   excluded version resolves to a later one, listed as `>version`.
+//! - `use` (`go.work` only): the modules of the workspace, which are scanned
+//!   even when they lie outside the scanned directory
+//!
+//! A module belongs to the nearest `go.work` in its ancestors that uses it.
+
+use crate::scanner::deps::{self, Dependency, DependencyError, DependencyType, Ecosystem};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A `replace` directive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoReplace {
+    /// Module path being replaced
+    pub module: String,
+    /// Version being replaced; every version when `None`
+    pub version: Option<String>,
+    /// Replacement module path or local directory
+    pub replacement: String,
+    /// Version of the replacement module; `None` for a local directory
+    pub replacement_version: Option<String>,
+}
+
+impl GoReplace {
+    /// Returns `true` if the replacement is a local directory
+    ///
+    /// Builds of a module with local replacements only work where that
+    /// directory exists.
+    pub fn is_local(&self) -> bool {
+        self.replacement_version.is_none()
+    }
+
+    /// Returns `true` if this directive replaces `module` at `version`
+    fn applies_to(&self, module: &str, version: &str) -> bool {
+        self.module == module
+            && self
+                .version
+                .as_deref()
+                .is_none_or(|replaced| replaced == version)
+    }
+}
+
+impl fmt::Display for GoReplace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.module)?;
+        if let Some(version) = &self.version {
+            write!(f, " {}", version)?;
+        }
+        write!(f, " => {}", self.replacement)?;
+        if let Some(version) = &self.replacement_version {
+            write!(f, " {}", version)?;
+        }
+        Ok(())
+    }
+}
+
+/// An `exclude` directive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoExclude {
+    /// Module path
+    pub module: String,
+    /// Excluded version
+    pub version: String,
+}
+
+/// What a `go.mod` or `go.work` file declares besides its requirements
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoModule {
+    /// The `go.mod` or `go.work` file
+    pub manifest: PathBuf,
+    /// Module path (`module` directive); `None` for a `go.work`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    /// Go language version (`go` directive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub go_version: Option<String>,
+    /// Toolchain (`toolchain` directive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
+    /// Replaced modules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replaces: Vec<GoReplace>,
+    /// Excluded module versions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excludes: Vec<GoExclude>,
+    /// Module directories of a workspace (`use` directives), relative to it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uses: Vec<String>,
+}
+
+/// A `require` directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GoRequire {
+    module: String,
+    version: String,
+    indirect: bool,
+}
+
+/// The directives of a `go.mod` or `go.work` file
+#[derive(Debug, Default)]
+struct GoFile {
+    info: GoModule,
+    requires: Vec<GoRequire>,
+}
+
+/// Parses the directives of a `go.mod` or `go.work` file
+///
+/// Directives may be single-line (`require example.com/a v1.0.0`) or
+/// blocks (`require ( ... )`); `// indirect` marks indirect requirements.
+fn parse(content: &str, manifest: &Path) -> GoFile {
+    let mut file = GoFile {
+        info: GoModule {
+            manifest: manifest.to_path_buf(),
+            ..GoModule::default()
+        },
+        requires: Vec::new(),
+    };
+    let mut block: Option<&str> = None;
+
+    for raw in content.lines() {
+        let (code, comment) = raw.split_once("//").unwrap_or((raw, ""));
+        let code = code.trim();
+        let indirect = comment.trim() == "indirect";
+
+        let (directive, args) = match block {
+            Some(_) if code == ")" => {
+                block = None;
+                continue;
+            }
+            Some(directive) => (directive, code),
+            None => {
+                let (directive, args) = code.split_once(char::is_whitespace).unwrap_or((code, ""));
+                let args = args.trim();
+                if args == "(" {
+                    block = ["require", "replace", "exclude", "use", "retract"]
+                        .into_iter()
+                        .find(|known| *known == directive);
+                    continue;
+                }
+                (directive, args)
+            }
+        };
+        if args.is_empty() {
+            continue;
+        }
+        let words: Vec<&str> = args
+            .split_whitespace()
+            .map(|word| word.trim_matches('"'))
+            .collect();
+
+        match (directive, words.as_slice()) {
+            ("module", [module, ..]) => file.info.module = Some(module.to_string()),
+            ("go", [version, ..]) => file.info.go_version = Some(version.to_string()),
+            ("toolchain", [toolchain, ..]) => file.info.toolchain = Some(toolchain.to_string()),
+            ("require", [module, version, ..]) => file.requires.push(GoRequire {
+                module: module.to_string(),
+                version: version.to_string(),
+                indirect,
+            }),
+            ("exclude", [module, version, ..]) => file.info.excludes.push(GoExclude {
+                module: module.to_string(),
+                version: version.to_string(),
+            }),
+            ("use", [dir, ..]) => file.info.uses.push(dir.to_string()),
+            ("replace", _) => {
+                let Some(arrow) = words.iter().position(|word| *word == "=>") else {
+                    continue;
+                };
+                let (old, new) = (&words[..arrow], &words[arrow + 1..]);
+                let (Some(module), Some(replacement)) = (old.first(), new.first()) else {
+                    continue;
+                };
+                file.info.replaces.push(GoReplace {
+                    module: module.to_string(),
+                    version: old.get(1).map(|v| v.to_string()),
+                    replacement: replacement.to_string(),
+                    replacement_version: new.get(1).map(|v| v.to_string()),
+                });
+            }
+            _ => {}
+        }
+    }
+    file
+}
+
+/// Reads and parses `dir/name` if it exists
+fn read(dir: &Path, name: &str) -> Result<Option<GoFile>, DependencyError> {
+    let path = dir.join(name);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    Ok(Some(parse(&deps::read_manifest(&path)?, &path)))
+}
+
+/// Finds the `go.work` in `module_dir` or its ancestors that uses the module
+fn find_workspace(module_dir: &Path) -> Option<GoFile> {
+    let module_dir = fs::canonicalize(module_dir).ok()?;
+    module_dir.ancestors().find_map(|dir| {
+        let workspace = read(dir, "go.work").ok()??;
+        let uses_module = workspace
+            .info
+            .uses
+            .iter()
+            .any(|used| fs::canonicalize(dir.join(used)).is_ok_and(|used| used == module_dir));
+        uses_module.then_some(workspace)
+    })
+}
+
+/// Parses the requirements of the Go module in `project_path`
+///
+/// Replacements of the module and of the workspace using it are applied,
+/// and requirements of excluded versions are listed as `>version`. Returns
+/// no dependencies for a directory with only a `go.work`.
+///
+/// # Errors
+///
+/// Returns an error if `go.mod` exists but cannot be read.
+pub(crate) fn parse_dependencies(project_path: &Path) -> Result<Vec<Dependency>, DependencyError> {
+    let Some(module) = read(project_path, "go.mod")? else {
+        return Ok(Vec::new());
+    };
+    let workspace_replaces = find_workspace(project_path)
+        .map(|workspace| workspace.info.replaces)
+        .unwrap_or_default();
+
+    let dependencies = module
+        .requires
+        .iter()
+        .map(|require| {
+            let replace = workspace_replaces
+                .iter()
+                .chain(&module.info.replaces)
+                .find(|replace| replace.applies_to(&require.module, &require.version));
+            let (name, version) = match replace {
+                Some(replace) => (
+                    replace.replacement.clone(),
+                    replace
+                        .replacement_version
+                        .clone()
+                        .unwrap_or_else(|| replace.replacement.clone()),
+                ),
+                None if module.info.excludes.iter().any(|exclude| {
+                    exclude.module == require.module && exclude.version == require.version
+                }) =>
+                {
+                    (require.module.clone(), format!(">{}", require.version))
+                }
+                None => (require.module.clone(), require.version.clone()),
+            };
+            Dependency {
+                name,
+                version,
+                dependency_type: if require.indirect {
+                    DependencyType::Development
+                } else {
+                    DependencyType::Runtime
+                },
+                ecosystem: Ecosystem::Go,
+                source_file: module.info.manifest.clone(),
+                extras: Vec::new(),
+                marker: None,
+            }
+        })
+        .collect();
+    Ok(dependencies)
+}
+
+/// Returns what the `go.mod` and `go.work` in `project_path` declare
+/// besides requirements
+///
+/// Unreadable files are skipped; [`parse_dependencies`] reports them.
+pub fn describe_project(project_path: &Path) -> Vec<GoModule> {
+    ["go.mod", "go.work"]
+        .into_iter()
+        .filter_map(|name| read(project_path, name).ok().flatten())
+        .map(|file| file.info)
+        .collect()
+}
+
+/// Returns the module directories the `go.work` in `project_path` uses
+pub(crate) fn workspace_members(project_path: &Path) -> Vec<PathBuf> {
+    read(project_path, "go.work")
+        .ok()
+        .flatten()
+        .map(|workspace| {
+            workspace
+                .info
+                .uses
+                .iter()
+                .map(|dir| join_lexically(project_path, dir))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Joins `relative` to `base`, resolving `.` and `..` without touching the
+/// file system
+fn join_lexically(base: &Path, relative: &str) -> PathBuf {
+    let mut path = base.to_path_buf();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(path.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                path.pop();
+            }
+            other => path.push(other),
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_directives_in_single_line_and_block_form() {
+        let file = parse(
+            r#"module example.com/api
+
+go 1.22.0
+
+toolchain go1.22.3
+
+require github.com/pkg/errors v0.9.1
+require (
+	golang.org/x/net v0.17.0 // indirect
+	"github.com/old/lib" v1.0.0
+)
+
+replace github.com/old/lib => github.com/fork/lib v1.0.1
+replace (
+	golang.org/x/net v0.17.0 => ../net
+)
+
+exclude github.com/pkg/errors v0.9.0
+
+retract v1.0.0 // published by mistake
+"#,
+            Path::new("go.mod"),
+        );
+
+        assert_eq!(file.info.module.as_deref(), Some("example.com/api"));
+        assert_eq!(file.info.go_version.as_deref(), Some("1.22.0"));
+        assert_eq!(file.info.toolchain.as_deref(), Some("go1.22.3"));
+        assert_eq!(file.requires.len(), 3);
+        assert!(file.requires[1].indirect);
+        assert_eq!(file.requires[2].module, "github.com/old/lib");
+        assert_eq!(file.info.replaces.len(), 2);
+        assert!(!file.info.replaces[0].is_local());
+        assert_eq!(
+            file.info.replaces[0].to_string(),
+            "github.com/old/lib => github.com/fork/lib v1.0.1"
+        );
+        assert_eq!(
+            file.info.replaces[1].to_string(),
+            "golang.org/x/net v0.17.0 => ../net"
+        );
+        assert!(file.info.replaces[1].is_local());
+        assert_eq!(
+            file.info.excludes,
+            [GoExclude {
+                module: "github.com/pkg/errors".to_string(),
+                version: "v0.9.0".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn applies_workspace_and_module_replacements_and_excludes() {
+        let temp_dir = TempDir::new().unwrap();
+        let api = temp_dir.path().join("api");
+        fs::create_dir(&api).unwrap();
+        fs::write(
+            temp_dir.path().join("go.work"),
+            "go 1.22\n\nuse (\n\t./api\n)\n\nreplace github.com/shared/log => github.com/fork/log v1.2.1\n",
+        )
+        .unwrap();
+        fs::write(
+            api.join("go.mod"),
+            r#"module example.com/api
+
+require (
+	github.com/shared/log v1.2.0
+	github.com/pkg/errors v0.9.0
+	example.com/internal v0.0.0
+)
+
+replace github.com/shared/log => ../log
+replace example.com/internal => ../internal
+exclude github.com/pkg/errors v0.9.0
+"#,
+        )
+        .unwrap();
+
+        let dependencies = parse_dependencies(&api).unwrap();
+        let found: Vec<(&str, &str)> = dependencies
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("github.com/fork/log", "v1.2.1"),
+                ("github.com/pkg/errors", ">v0.9.0"),
+                ("../internal", "../internal")
+            ]
+        );
+
+        assert_eq!(
+            workspace_members(temp_dir.path()),
+            [temp_dir.path().join("api")]
+        );
+        assert_eq!(join_lexically(&api, "../lib"), temp_dir.path().join("lib"));
+        let described = describe_project(temp_dir.path());
+        assert_eq!(described.len(), 1);
+        assert_eq!(described[0].go_version.as_deref(), Some("1.22"));
+        assert!(parse_dependencies(temp_dir.path()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,751 @@
+//! Upgrade impact across projects (`deps upgrade-impact`)
+//!
+//! Answers "what breaks if I upgrade X?" before anyone tries: every
+//! constraint on a package is checked against the version it would be
+//! upgraded to.
+//!
+//! - **Direct** constraints are the ones the scanned projects declare in
+//!   their manifests.
+//! - **Transitive** constraints are the ones of other packages in a
+//!   project's lockfile that depend on it. `package-lock.json` records them
+//!   for every installed package; for `Cargo.lock`, they are read from the
+//!   manifests of the dependent crates in `$CARGO_HOME/registry/src`.
+//!
+//! Constraints follow the rules of their ecosystem: Cargo and npm version
+//! ranges, PEP 440 specifiers, and Go minimum versions, which admit later
+//! versions of the same major version only. Constraints that cannot be
+//! checked, such as git dependencies or crates that were never downloaded,
+//! are reported as unknown rather than guessed.
+
+use crate::i18n::t;
+use crate::scanner::audit::parse_loose_version;
+use crate::scanner::cargo;
+use crate::scanner::deps::{DependencyReport, Ecosystem};
+use crate::scanner::exposure::normalize_package_name;
+use crate::scanner::pep440;
+use crate::tr;
+use crate::utils::display;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use semver::VersionReq;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Whether a constraint admits the new version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    /// The constraint rejects the new version
+    Blocking,
+    /// The constraint could not be checked
+    Unknown,
+    /// The constraint admits the new version
+    Compatible,
+}
+
+/// A constraint on the upgraded package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImpactConstraint {
+    /// Project the constraint applies to
+    pub project: PathBuf,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+    /// Manifest or lockfile the constraint was read from
+    pub source_file: PathBuf,
+    /// Package declaring the constraint, as `name version`; `None` for a
+    /// constraint the project declares itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+    /// The constraint; `None` if it could not be read
+    pub constraint: Option<String>,
+    /// Whether it admits the new version
+    pub verdict: Verdict,
+}
+
+/// Every constraint on a package and whether it admits a new version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeImpact {
+    /// The upgraded package
+    pub package: String,
+    /// The version it would be upgraded to
+    pub version: String,
+    /// Constraints on the package, blocking ones first
+    pub constraints: Vec<ImpactConstraint>,
+}
+
+impl UpgradeImpact {
+    /// Checks every constraint on `package` in the scanned projects and
+    /// their lockfiles against `version`
+    ///
+    /// Package names match case-insensitively, treating `-`, `_` and `.`
+    /// alike.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::deps;
+    /// use devhealth::scanner::impact::UpgradeImpact;
+    /// use std::path::Path;
+    ///
+    /// let reports = deps::scan_dependencies(Path::new(".")).unwrap();
+    /// let impact = UpgradeImpact::analyze(&reports, "serde", "2.0.0");
+    /// for constraint in impact.blocking() {
+    ///     println!("{} blocks the upgrade", constraint.project.display());
+    /// }
+    /// ```
+    pub fn analyze(reports: &[DependencyReport], package: &str, version: &str) -> UpgradeImpact {
+        let registries = cargo::registry_sources(
+            cargo::cargo_home().map(|home| home.join("registry").join("src")),
+        );
+        Self::analyze_with(reports, package, version, &registries)
+    }
+
+    fn analyze_with(
+        reports: &[DependencyReport],
+        package: &str,
+        version: &str,
+        registries: &[PathBuf],
+    ) -> UpgradeImpact {
+        let target = normalize_package_name(package);
+        let mut constraints = Vec::new();
+        let mut lockfiles = BTreeSet::new();
+
+        for report in reports {
+            for dependency in report
+                .dependencies
+                .iter()
+                .filter(|dependency| normalize_package_name(&dependency.name) == target)
+            {
+                constraints.push(ImpactConstraint {
+                    project: report.project_path.clone(),
+                    ecosystem: dependency.ecosystem.clone(),
+                    source_file: dependency.source_file.clone(),
+                    via: None,
+                    constraint: Some(dependency.version.clone()),
+                    verdict: verdict(&dependency.ecosystem, &dependency.version, version),
+                });
+            }
+
+            let dir = report.project_path.as_path();
+            if report.ecosystems.contains(&Ecosystem::Rust) {
+                if let Some(lockfile) =
+                    cargo::find_lockfile(dir).filter(|lockfile| lockfiles.insert(lockfile.clone()))
+                {
+                    constraints.extend(cargo_dependents(
+                        &lockfile, &target, version, registries, dir,
+                    ));
+                }
+            }
+            if report.ecosystems.contains(&Ecosystem::NodeJs) {
+                let lockfile = dir
+                    .ancestors()
+                    .map(|ancestor| ancestor.join("package-lock.json"))
+                    .find(|lockfile| lockfile.is_file());
+                if let Some(lockfile) =
+                    lockfile.filter(|lockfile| lockfiles.insert(lockfile.clone()))
+                {
+                    constraints.extend(npm_dependents(&lockfile, &target, version, dir));
+                }
+            }
+        }
+
+        // Within a project, the declared constraints before the transitive ones
+        constraints.sort_by(|a, b| {
+            (a.verdict, &a.project, &a.via, &a.source_file).cmp(&(
+                b.verdict,
+                &b.project,
+                &b.via,
+                &b.source_file,
+            ))
+        });
+        UpgradeImpact {
+            package: package.to_string(),
+            version: version.to_string(),
+            constraints,
+        }
+    }
+
+    /// Returns the constraints rejecting the new version
+    pub fn blocking(&self) -> impl Iterator<Item = &ImpactConstraint> {
+        self.constraints
+            .iter()
+            .filter(|constraint| constraint.verdict == Verdict::Blocking)
+    }
+
+    /// Returns how many constraints have the given verdict
+    pub fn count(&self, verdict: Verdict) -> usize {
+        self.constraints
+            .iter()
+            .filter(|constraint| constraint.verdict == verdict)
+            .count()
+    }
+
+    /// Returns the projects with at least one blocking constraint
+    pub fn blocked_projects(&self) -> BTreeSet<&Path> {
+        self.blocking()
+            .map(|constraint| constraint.project.as_path())
+            .collect()
+    }
+}
+
+/// Checks a constraint of an ecosystem against the new version
+pub(crate) fn verdict(ecosystem: &Ecosystem, constraint: &str, version: &str) -> Verdict {
+    let admitted = match ecosystem {
+        Ecosystem::Rust => cargo_admits(constraint, version),
+        Ecosystem::NodeJs => npm_admits(constraint, version),
+        Ecosystem::Python => python_admits(constraint, version),
+        Ecosystem::Go => go_admits(constraint, version),
+        Ecosystem::Bazel | Ecosystem::Buck | Ecosystem::Nix => None,
+    };
+    match admitted {
+        Some(true) => Verdict::Compatible,
+        Some(false) => Verdict::Blocking,
+        None => Verdict::Unknown,
+    }
+}
+
+/// Checks a Cargo version requirement, where `1.2` means `^1.2`
+fn cargo_admits(requirement: &str, version: &str) -> Option<bool> {
+    Some(
+        VersionReq::parse(requirement)
+            .ok()?
+            .matches(&parse_loose_version(version)?),
+    )
+}
+
+/// Checks an npm version range
+///
+/// Handles `||` alternatives, hyphen ranges (`1.2 - 2.3`), space-separated
+/// comparators and bare versions, which npm matches exactly. Tags, URLs and
+/// protocols such as `workspace:` cannot be checked.
+fn npm_admits(range: &str, version: &str) -> Option<bool> {
+    let version = parse_loose_version(version)?;
+    let mut admitted = false;
+    for alternative in range.split("||") {
+        let alternative = alternative.trim();
+        let comparators: Vec<String> = match alternative.split_once(" - ") {
+            Some((low, high)) => vec![
+                format!(">={}", low.trim().trim_start_matches('v')),
+                format!("<={}", high.trim().trim_start_matches('v')),
+            ],
+            None => npm_comparators(alternative),
+        };
+        let requirement = if comparators.is_empty() {
+            "*".to_string()
+        } else {
+            comparators.join(", ")
+        };
+        admitted |= VersionReq::parse(&requirement).ok()?.matches(&version);
+    }
+    Some(admitted)
+}
+
+/// Splits the comparators of an npm range, joining operators separated
+/// from their version (`>= 1.2`) and marking bare versions as exact
+fn npm_comparators(range: &str) -> Vec<String> {
+    let mut comparators: Vec<String> = Vec::new();
+    let mut operator = String::new();
+    for word in range.split_whitespace() {
+        let version_start = word
+            .find(|c: char| !"<>=~^".contains(c))
+            .unwrap_or(word.len());
+        let (op, version) = word.split_at(version_start);
+        operator.push_str(op);
+        if version.is_empty() {
+            continue;
+        }
+        let version = version.trim_start_matches('v');
+        let operator = match std::mem::take(&mut operator) {
+            op if op.is_empty() && version != "*" && !version.eq_ignore_ascii_case("x") => {
+                "=".to_string()
+            }
+            op => op,
+        };
+        comparators.push(format!("{}{}", operator, version));
+    }
+    comparators
+}
+
+/// Checks a PEP 440 specifier set
+fn python_admits(specifiers: &str, version: &str) -> Option<bool> {
+    if specifiers == "*" {
+        return Some(true);
+    }
+    let specifiers: pep440::SpecifierSet = specifiers.parse().ok()?;
+    Some(specifiers.contains(&version.parse().ok()?))
+}
+
+/// Checks a Go minimum version
+///
+/// Go selects the highest required version, so a requirement admits every
+/// later version with the same major version; a new major version is a
+/// different module path. `>v1.2.3` is an excluded version (see
+/// [`crate::scanner::gomod`]).
+fn go_admits(required: &str, version: &str) -> Option<bool> {
+    let (strict, required) = match required.strip_prefix('>') {
+        Some(required) => (true, required),
+        None => (false, required),
+    };
+    let required = parse_loose_version(required.trim_end_matches("+incompatible"))?;
+    let version = parse_loose_version(version.trim_end_matches("+incompatible"))?;
+    let major = |version: &semver::Version| version.major.max(1);
+    let later = if strict {
+        version > required
+    } else {
+        version >= required
+    };
+    Some(major(&required) == major(&version) && later)
+}
+
+/// Finds the packages in a `Cargo.lock` depending on `target` and checks
+/// their requirement on it
+///
+/// Workspace members are skipped, as the scan reports them directly. The
+/// requirements of registry crates are read from their downloaded manifest;
+/// crates from other sources, or never downloaded, are reported as unknown.
+fn cargo_dependents(
+    lockfile: &Path,
+    target: &str,
+    version: &str,
+    registries: &[PathBuf],
+    project: &Path,
+) -> Vec<ImpactConstraint> {
+    let Some(lock) = fs::read_to_string(lockfile)
+        .ok()
+        .and_then(|lock| toml::from_str::<toml::Value>(&lock).ok())
+    else {
+        return Vec::new();
+    };
+    let packages = lock
+        .get("package")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten();
+
+    let mut constraints = Vec::new();
+    for package in packages {
+        let (Some(name), Some(package_version)) = (
+            package.get("name").and_then(toml::Value::as_str),
+            package.get("version").and_then(toml::Value::as_str),
+        ) else {
+            continue;
+        };
+        let Some(source) = package.get("source").and_then(toml::Value::as_str) else {
+            continue;
+        };
+        let depends = package
+            .get("dependencies")
+            .and_then(toml::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_str)
+            .filter_map(|dependency| dependency.split_whitespace().next())
+            .find(|dependency| normalize_package_name(dependency) == target);
+        let Some(dependency) = depends else {
+            continue;
+        };
+
+        let requirement = (source.starts_with("registry+") || source.starts_with("sparse+"))
+            .then(|| downloaded_requirement(registries, name, package_version, dependency))
+            .flatten();
+        constraints.push(ImpactConstraint {
+            project: project.to_path_buf(),
+            ecosystem: Ecosystem::Rust,
+            source_file: lockfile.to_path_buf(),
+            via: Some(format!("{} {}", name, package_version)),
+            verdict: requirement
+                .as_deref()
+                .map_or(Verdict::Unknown, |requirement| {
+                    verdict(&Ecosystem::Rust, requirement, version)
+                }),
+            constraint: requirement,
+        });
+    }
+    constraints
+}
+
+/// Reads the requirement a downloaded crate's manifest declares on
+/// `dependency`, including target-specific and build dependencies
+fn downloaded_requirement(
+    registries: &[PathBuf],
+    name: &str,
+    version: &str,
+    dependency: &str,
+) -> Option<String> {
+    let manifest: toml::Value = registries.iter().find_map(|registry| {
+        let path = registry
+            .join(format!("{}-{}", name, version))
+            .join("Cargo.toml");
+        toml::from_str(&fs::read_to_string(path).ok()?).ok()
+    })?;
+
+    let targets = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+    std::iter::once(&manifest)
+        .chain(targets)
+        .flat_map(|tables| {
+            ["dependencies", "build-dependencies"]
+                .into_iter()
+                .filter_map(|table| tables.get(table)?.as_table())
+        })
+        .flat_map(|table| table.iter())
+        .find_map(|(key, value)| match value {
+            toml::Value::String(requirement) if key == dependency => Some(requirement.clone()),
+            toml::Value::Table(entry) => {
+                let package = entry
+                    .get("package")
+                    .and_then(toml::Value::as_str)
+                    .unwrap_or(key);
+                (package == dependency)
+                    .then(|| entry.get("version")?.as_str().map(str::to_string))
+                    .flatten()
+            }
+            _ => None,
+        })
+}
+
+/// Finds the packages in a `package-lock.json` (version 2 and later)
+/// depending on `target` and checks their range on it
+fn npm_dependents(
+    lockfile: &Path,
+    target: &str,
+    version: &str,
+    project: &Path,
+) -> Vec<ImpactConstraint> {
+    let Some(lock) = fs::read_to_string(lockfile)
+        .ok()
+        .and_then(|lock| serde_json::from_str::<serde_json::Value>(&lock).ok())
+    else {
+        return Vec::new();
+    };
+    let Some(packages) = lock.get("packages").and_then(serde_json::Value::as_object) else {
+        return Vec::new();
+    };
+
+    let mut constraints = Vec::new();
+    for (key, entry) in packages {
+        // The root and workspace members are scanned as projects
+        let Some((_, name)) = key.rsplit_once("node_modules/") else {
+            continue;
+        };
+        let range = ["dependencies", "optionalDependencies", "peerDependencies"]
+            .into_iter()
+            .filter_map(|section| entry.get(section)?.as_object())
+            .flat_map(|dependencies| dependencies.iter())
+            .find(|(dependency, _)| normalize_package_name(dependency) == target)
+            .and_then(|(_, range)| range.as_str());
+        let Some(range) = range else {
+            continue;
+        };
+        let package_version = entry
+            .get("version")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("?");
+        constraints.push(ImpactConstraint {
+            project: project.to_path_buf(),
+            ecosystem: Ecosystem::NodeJs,
+            source_file: lockfile.to_path_buf(),
+            via: Some(format!("{} {}", name, package_version)),
+            constraint: Some(range.to_string()),
+            verdict: verdict(&Ecosystem::NodeJs, range, version),
+        });
+    }
+    constraints
+}
+
+/// Displays which constraints block an upgrade
+pub fn display_impact(impact: &UpgradeImpact) {
+    let title = tr!(
+        "impact-title",
+        package = impact.package.as_str(),
+        version = impact.version.as_str()
+    );
+    if impact.constraints.is_empty() {
+        println!("{}", display::header(&title, "🔮", Role::Special));
+        println!(
+            "{} {}",
+            display::symbols("✅"),
+            tr!("impact-none", package = impact.package.as_str()).paint(Role::Muted)
+        );
+        return;
+    }
+    let blocking = impact.count(Verdict::Blocking);
+    println!(
+        "{}",
+        display::header(
+            &title,
+            "🔮",
+            if blocking > 0 {
+                Role::Error
+            } else {
+                Role::Special
+            }
+        )
+    );
+    let projects = impact
+        .constraints
+        .iter()
+        .map(|constraint| &constraint.project)
+        .collect::<BTreeSet<_>>();
+    print!(
+        "{}",
+        display::summary_box(&[
+            (t("deps-projects"), projects.len().to_string()),
+            (
+                t("impact-constraints"),
+                impact.constraints.len().to_string()
+            ),
+            (t("impact-blocking"), blocking.to_string()),
+            (
+                t("impact-unknown"),
+                impact.count(Verdict::Unknown).to_string()
+            ),
+            (
+                t("impact-compatible"),
+                impact.count(Verdict::Compatible).to_string()
+            ),
+        ])
+    );
+
+    if blocking == 0 {
+        println!(
+            "\n{} {}",
+            display::symbols("✅"),
+            tr!("impact-ready", version = impact.version.as_str()).paint(Role::Success)
+        );
+    } else {
+        let line = tr!(
+            "impact-blocked-projects",
+            count = impact.blocked_projects().len(),
+            version = impact.version.as_str()
+        );
+        println!(
+            "\n{} {}",
+            display::symbols("🚧"),
+            line.paint(Role::Error).bold()
+        );
+        println!("{}", display::section_divider(t("impact-blocking")));
+        display_constraints(impact.blocking());
+    }
+
+    let unknown: Vec<&ImpactConstraint> = impact
+        .constraints
+        .iter()
+        .filter(|constraint| constraint.verdict == Verdict::Unknown)
+        .collect();
+    if !unknown.is_empty() {
+        println!("{}", display::section_divider(t("impact-unknown")));
+        display_constraints(unknown.into_iter());
+        println!(
+            "\n{} {}",
+            display::symbols("💡"),
+            t("impact-tip-unknown").paint(Role::Muted)
+        );
+    }
+}
+
+/// Lists constraints grouped by project
+fn display_constraints<'a>(constraints: impl Iterator<Item = &'a ImpactConstraint>) {
+    let constraints: Vec<&ImpactConstraint> = constraints.collect();
+    let projects: Vec<&Path> = constraints
+        .iter()
+        .map(|constraint| constraint.project.as_path())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    for (index, project) in projects.iter().enumerate() {
+        let name = project
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_else(|| project.to_string_lossy());
+        println!(
+            "{}",
+            display::tree_item(
+                &format!("📂 {}", name.paint(Role::Emphasis).bold()),
+                index == projects.len() - 1,
+                0
+            )
+        );
+        let own: Vec<&&ImpactConstraint> = constraints
+            .iter()
+            .filter(|constraint| constraint.project == *project)
+            .collect();
+        for (position, constraint) in own.iter().enumerate() {
+            let origin = match &constraint.via {
+                Some(via) => tr!("impact-via", package = via.as_str()),
+                None => t("impact-direct").to_string(),
+            };
+            let line = format!(
+                "{} {} {} {}",
+                display::ecosystem_icon(&constraint.ecosystem.to_string()),
+                constraint
+                    .constraint
+                    .as_deref()
+                    .unwrap_or("?")
+                    .paint(Role::Warning),
+                origin.paint(Role::Highlight),
+                display::file_path(&constraint.source_file.display().to_string())
+            );
+            println!(
+                "{}",
+                display::tree_item(&display::symbols(&line), position == own.len() - 1, 1)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::deps::{Dependency, DependencyType};
+    use tempfile::TempDir;
+
+    fn report(dir: &Path, ecosystem: Ecosystem, dependencies: &[(&str, &str)]) -> DependencyReport {
+        DependencyReport {
+            project_path: dir.to_path_buf(),
+            dependencies: dependencies
+                .iter()
+                .map(|(name, version)| Dependency {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    dependency_type: DependencyType::Runtime,
+                    ecosystem: ecosystem.clone(),
+                    source_file: dir.join("manifest"),
+                    extras: Vec::new(),
+                    marker: None,
+                })
+                .collect(),
+            ecosystems: vec![ecosystem],
+            errors: Vec::new(),
+            lockfiles: Vec::new(),
+            go_modules: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn checks_constraints_by_ecosystem() {
+        assert_eq!(cargo_admits("1.0", "1.9.3"), Some(true));
+        assert_eq!(cargo_admits("1.0", "2.0.0"), Some(false));
+        assert_eq!(cargo_admits(">=1.2, <3", "2"), Some(true));
+
+        assert_eq!(npm_admits("^4.17.0", "5.0.0"), Some(false));
+        assert_eq!(npm_admits("~1.2.3 || >= 2.0.0", "2.1.0"), Some(true));
+        assert_eq!(npm_admits("1.2.3 - 2.3", "2.3.9"), Some(true));
+        assert_eq!(npm_admits("1.2.3", "1.2.4"), Some(false));
+        assert_eq!(npm_admits("1.x", "1.9.0"), Some(true));
+        assert_eq!(npm_admits("*", "9.0.0"), Some(true));
+        assert_eq!(npm_admits("workspace:^1", "1.0.0"), None);
+
+        assert_eq!(python_admits(">=2.28,<3", "3.0.0"), Some(false));
+        assert_eq!(python_admits("~=2.28.0", "2.28.5"), Some(true));
+
+        assert_eq!(go_admits("v1.2.0", "v1.9.0"), Some(true));
+        assert_eq!(go_admits("v1.2.0", "v1.1.0"), Some(false));
+        assert_eq!(go_admits("v1.2.0", "v2.0.0"), Some(false));
+        assert_eq!(go_admits(">v1.2.0", "v1.2.0"), Some(false));
+        assert_eq!(go_admits("../local", "v1.2.0"), None);
+    }
+
+    #[test]
+    fn finds_direct_and_transitive_cargo_constraints() {
+        let temp_dir = TempDir::new().unwrap();
+        let api = temp_dir.path().join("api");
+        fs::create_dir(&api).unwrap();
+        fs::write(
+            api.join("Cargo.lock"),
+            r#"version = 3
+
+[[package]]
+name = "api"
+version = "0.1.0"
+dependencies = ["hyper", "tokio"]
+
+[[package]]
+name = "hyper"
+version = "0.14.28"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["tokio"]
+
+[[package]]
+name = "tower"
+version = "0.4.13"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["tokio 1.37.0"]
+
+[[package]]
+name = "tokio"
+version = "1.37.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+        )
+        .unwrap();
+        let registry = temp_dir
+            .path()
+            .join("src")
+            .join("index.crates.io-6f17d22bba15001f");
+        let hyper = registry.join("hyper-0.14.28");
+        fs::create_dir_all(&hyper).unwrap();
+        fs::write(
+            hyper.join("Cargo.toml"),
+            "[package]\nname = \"hyper\"\nversion = \"0.14.28\"\n\n[dependencies.tokio]\nversion = \"1.27\"\nfeatures = [\"sync\"]\n",
+        )
+        .unwrap();
+
+        let reports = [report(
+            &api,
+            Ecosystem::Rust,
+            &[("tokio", ">=1.30, <2"), ("hyper", "0.14")],
+        )];
+        let impact = UpgradeImpact::analyze_with(&reports, "tokio", "2.0.0", &[registry]);
+
+        let found: Vec<(Option<&str>, Option<&str>, Verdict)> = impact
+            .constraints
+            .iter()
+            .map(|c| (c.via.as_deref(), c.constraint.as_deref(), c.verdict))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (None, Some(">=1.30, <2"), Verdict::Blocking),
+                (Some("hyper 0.14.28"), Some("1.27"), Verdict::Blocking),
+                (Some("tower 0.4.13"), None, Verdict::Unknown),
+            ]
+        );
+        assert_eq!(impact.blocked_projects().len(), 1);
+    }
+
+    #[test]
+    fn finds_transitive_npm_ranges() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("package-lock.json"),
+            r#"{"lockfileVersion": 3, "packages": {
+                "": {"dependencies": {"express": "^4.18.0"}},
+                "node_modules/express": {"version": "4.18.2", "dependencies": {"qs": "6.11.0"}},
+                "node_modules/body-parser": {"version": "1.20.1", "dependencies": {"qs": ">=6 <8"}},
+                "node_modules/qs": {"version": "6.11.0"}
+            }}"#,
+        )
+        .unwrap();
+
+        let reports = [report(
+            temp_dir.path(),
+            Ecosystem::NodeJs,
+            &[("express", "^4.18.0")],
+        )];
+        let impact = UpgradeImpact::analyze_with(&reports, "qs", "7.0.0", &[]);
+
+        assert_eq!(impact.count(Verdict::Blocking), 1);
+        assert_eq!(impact.constraints[0].via.as_deref(), Some("express 4.18.2"));
+        assert_eq!(
+            impact.constraints[1].via.as_deref(),
+            Some("body-parser 1.20.1")
+        );
+        assert_eq!(impact.constraints[1].verdict, Verdict::Compatible);
+    }
+}
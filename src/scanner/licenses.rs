@@ -0,0 +1,716 @@
+//! License inventory across projects (`licenses`)
+//!
+//! Rolls up the license of every dependency of every project under a
+//! directory into one table, license → packages → projects, for compliance
+//! reviews. Licenses are read offline from what package managers already
+//! downloaded:
+//!
+//! - **Rust**: the `license` of the crate's `Cargo.toml` in
+//!   `$CARGO_HOME/registry/src`, at the version locked in `Cargo.lock`
+//! - **Node.js**: the `license` of the package's `package.json` in
+//!   `node_modules`, in the project or a parent (hoisted) directory
+//! - **Python**: `License-Expression`, `License` or the license classifier in
+//!   the `METADATA` of the package in the project's virtual environment
+//!   (`.venv`, `venv` or `env`)
+//! - **Go**: the `LICENSE` file of the module in the module cache
+//!   (`$GOMODCACHE`, or `pkg/mod` under `$GOPATH` or `~/go`), recognized by
+//!   its text
+//!
+//! Packages that were never downloaded, or whose license cannot be told,
+//! are listed under [`UNKNOWN_LICENSE`]. The inventory prints as text, JSON
+//! or CSV (see [`LicenseFormat`]).
+
+use crate::i18n::t;
+use crate::scanner::cargo;
+use crate::scanner::deps::{Dependency, DependencyReport, Ecosystem};
+use crate::scanner::exposure::Resolver;
+use crate::tr;
+use crate::utils::display;
+use crate::utils::theme::{Paint, Role};
+use clap::ValueEnum;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// License of packages whose license could not be found
+pub const UNKNOWN_LICENSE: &str = "unknown";
+
+/// Virtual environment directories searched for Python packages
+const VENV_DIRS: [&str; 3] = [".venv", "venv", "env"];
+
+/// Output formats of the license inventory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LicenseFormat {
+    /// Tree of licenses, packages and projects
+    #[default]
+    Text,
+    /// Machine-readable JSON document
+    Json,
+    /// One `license,ecosystem,package,version,project` row per project
+    /// using a package, for spreadsheets
+    Csv,
+}
+
+/// A package version and the projects depending on it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LicensedPackage {
+    /// Package name
+    pub name: String,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+    /// Locked version, or the declared constraint when nothing is locked
+    pub version: String,
+    /// Projects depending on this version
+    pub projects: Vec<PathBuf>,
+}
+
+/// A license and the packages under it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LicenseGroup {
+    /// License as declared, usually an SPDX expression such as
+    /// `MIT OR Apache-2.0`, or [`UNKNOWN_LICENSE`]
+    pub license: String,
+    /// Packages under the license, by name
+    pub packages: Vec<LicensedPackage>,
+}
+
+/// Licenses of the dependencies of the scanned projects
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LicenseInventory {
+    /// Number of projects scanned
+    pub projects: usize,
+    /// Licenses, the one covering the most packages first and
+    /// [`UNKNOWN_LICENSE`] last
+    pub licenses: Vec<LicenseGroup>,
+}
+
+impl LicenseInventory {
+    /// Builds the inventory for the projects' dependency reports
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::{deps, licenses::LicenseInventory};
+    /// use std::path::Path;
+    ///
+    /// let reports = deps::scan_dependencies(Path::new(".")).unwrap();
+    /// let inventory = LicenseInventory::build(&reports);
+    /// for group in &inventory.licenses {
+    ///     println!("{}: {} packages", group.license, group.packages.len());
+    /// }
+    /// ```
+    pub fn build(reports: &[DependencyReport]) -> LicenseInventory {
+        LicenseInventory::build_with(reports, &LicenseFinder::from_env())
+    }
+
+    /// Builds the inventory, looking licenses up with `finder`
+    fn build_with(reports: &[DependencyReport], finder: &LicenseFinder) -> LicenseInventory {
+        let mut resolver = Resolver::default();
+        type Key = (String, Ecosystem, String);
+        let mut packages: BTreeMap<Key, (String, BTreeSet<PathBuf>)> = BTreeMap::new();
+        for report in reports {
+            for dependency in &report.dependencies {
+                let version = resolver.resolve(dependency);
+                let key = (
+                    dependency.name.clone(),
+                    dependency.ecosystem.clone(),
+                    version,
+                );
+                let (_, projects) = packages.entry(key).or_insert_with_key(|(_, _, version)| {
+                    let license = finder.license(dependency, version);
+                    (
+                        license.unwrap_or_else(|| UNKNOWN_LICENSE.to_string()),
+                        BTreeSet::new(),
+                    )
+                });
+                projects.insert(report.project_path.clone());
+            }
+        }
+
+        let mut groups: BTreeMap<String, Vec<LicensedPackage>> = BTreeMap::new();
+        for ((name, ecosystem, version), (license, projects)) in packages {
+            groups.entry(license).or_default().push(LicensedPackage {
+                name,
+                ecosystem,
+                version,
+                projects: projects.into_iter().collect(),
+            });
+        }
+        let mut licenses: Vec<LicenseGroup> = groups
+            .into_iter()
+            .map(|(license, packages)| LicenseGroup { license, packages })
+            .collect();
+        licenses.sort_by(|a, b| {
+            (a.license == UNKNOWN_LICENSE)
+                .cmp(&(b.license == UNKNOWN_LICENSE))
+                .then_with(|| b.packages.len().cmp(&a.packages.len()))
+                .then_with(|| a.license.cmp(&b.license))
+        });
+
+        LicenseInventory {
+            projects: reports.len(),
+            licenses,
+        }
+    }
+
+    /// Renders the inventory as CSV with a header row
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::licenses::LicenseInventory;
+    ///
+    /// let csv = LicenseInventory::default().to_csv();
+    /// assert_eq!(csv, "license,ecosystem,package,version,project\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("license,ecosystem,package,version,project\n");
+        for group in &self.licenses {
+            for package in &group.packages {
+                for project in &package.projects {
+                    let fields = [
+                        group.license.clone(),
+                        package.ecosystem.to_string(),
+                        package.name.clone(),
+                        package.version.clone(),
+                        project.display().to_string(),
+                    ];
+                    let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                    csv.push_str(&row.join(","));
+                    csv.push('\n');
+                }
+            }
+        }
+        csv
+    }
+}
+
+/// Quotes a CSV field if it holds a comma, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Looks up the licenses of downloaded packages
+struct LicenseFinder {
+    /// Per-registry crate source directories under `$CARGO_HOME/registry/src`
+    cargo_registries: Vec<PathBuf>,
+    /// The Go module cache
+    go_cache: Option<PathBuf>,
+}
+
+impl LicenseFinder {
+    /// Uses the Cargo home and Go module cache of the current user
+    fn from_env() -> LicenseFinder {
+        let go_cache = std::env::var_os("GOMODCACHE")
+            .filter(|cache| !cache.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                let gopath = std::env::var_os("GOPATH").filter(|gopath| !gopath.is_empty());
+                let first = gopath.and_then(|gopath| std::env::split_paths(&gopath).next());
+                first
+                    .or_else(|| {
+                        std::env::var_os("HOME")
+                            .or_else(|| std::env::var_os("USERPROFILE"))
+                            .map(|home| PathBuf::from(home).join("go"))
+                    })
+                    .map(|gopath| gopath.join("pkg").join("mod"))
+            });
+        LicenseFinder::new(
+            cargo::cargo_home().map(|home| home.join("registry").join("src")),
+            go_cache,
+        )
+    }
+
+    fn new(cargo_src: Option<PathBuf>, go_cache: Option<PathBuf>) -> LicenseFinder {
+        LicenseFinder {
+            cargo_registries: cargo::registry_sources(cargo_src),
+            go_cache,
+        }
+    }
+
+    /// Returns the license of a dependency at `version`, if it can be told
+    fn license(&self, dependency: &Dependency, version: &str) -> Option<String> {
+        let dir = dependency.source_file.parent().unwrap_or(Path::new("."));
+        match dependency.ecosystem {
+            Ecosystem::Rust => self.cargo_license(&dependency.name, version),
+            Ecosystem::NodeJs => npm_license(dir, &dependency.name),
+            Ecosystem::Python => python_license(dir, &dependency.name),
+            Ecosystem::Go => self.go_license(&dependency.name, version),
+            Ecosystem::Bazel | Ecosystem::Buck | Ecosystem::Nix => None,
+        }
+    }
+
+    /// Reads the `license` (or `license-file`) of a downloaded crate
+    fn cargo_license(&self, name: &str, version: &str) -> Option<String> {
+        self.cargo_registries.iter().find_map(|registry| {
+            let dir = registry.join(format!("{}-{}", name, version));
+            let manifest: toml::Value =
+                toml::from_str(&fs::read_to_string(dir.join("Cargo.toml")).ok()?).ok()?;
+            let package = manifest.get("package")?;
+            if let Some(license) = package.get("license").and_then(toml::Value::as_str) {
+                return Some(license.to_string());
+            }
+            let file = package.get("license-file")?.as_str()?;
+            identify_license_text(&fs::read_to_string(dir.join(file)).ok()?).map(str::to_string)
+        })
+    }
+
+    /// Recognizes the license file of a module in the Go module cache
+    fn go_license(&self, module: &str, version: &str) -> Option<String> {
+        let dir = self
+            .go_cache
+            .as_ref()?
+            .join(format!("{}@{}", escape_go_module(module), version));
+        let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let name = path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_uppercase();
+                ["LICENSE", "LICENCE", "COPYING"]
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix))
+            })
+            .collect();
+        files.sort();
+        files
+            .iter()
+            .find_map(|file| identify_license_text(&fs::read_to_string(file).ok()?))
+            .map(str::to_string)
+    }
+}
+
+/// Escapes a Go module path the way the module cache does: every uppercase
+/// letter becomes `!` followed by its lowercase form
+fn escape_go_module(module: &str) -> String {
+    let mut escaped = String::with_capacity(module.len());
+    for c in module.chars() {
+        if c.is_ascii_uppercase() {
+            escaped.push('!');
+            escaped.push(c.to_ascii_lowercase());
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Reads the license of an installed npm package, looking in the
+/// `node_modules` of `dir` and its parents
+fn npm_license(dir: &Path, name: &str) -> Option<String> {
+    dir.ancestors().find_map(|ancestor| {
+        let manifest = ancestor
+            .join("node_modules")
+            .join(name)
+            .join("package.json");
+        let document: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(manifest).ok()?).ok()?;
+        let license = |value: &serde_json::Value| match value {
+            serde_json::Value::String(license) => Some(license.clone()),
+            serde_json::Value::Object(object) => object.get("type")?.as_str().map(str::to_string),
+            _ => None,
+        };
+        document.get("license").and_then(license).or_else(|| {
+            let licenses: Vec<String> = document
+                .get("licenses")?
+                .as_array()?
+                .iter()
+                .filter_map(license)
+                .collect();
+            (!licenses.is_empty()).then(|| licenses.join(" OR "))
+        })
+    })
+}
+
+/// Reads the license of a package installed in the project's virtual
+/// environment
+fn python_license(dir: &Path, name: &str) -> Option<String> {
+    let normalized = normalize_python_name(name);
+    let site_packages = VENV_DIRS.iter().flat_map(|venv| {
+        let venv = dir.join(venv);
+        let unix = fs::read_dir(venv.join("lib"))
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path().join("site-packages"));
+        unix.chain(std::iter::once(venv.join("Lib").join("site-packages")))
+            .collect::<Vec<_>>()
+    });
+    for site_packages in site_packages {
+        let Ok(entries) = fs::read_dir(&site_packages) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(stem) = file_name.strip_suffix(".dist-info") else {
+                continue;
+            };
+            let package = stem.rsplit_once('-').map_or(stem, |(package, _)| package);
+            if normalize_python_name(package) == normalized {
+                let metadata = fs::read_to_string(entry.path().join("METADATA")).ok()?;
+                return python_metadata_license(&metadata);
+            }
+        }
+    }
+    None
+}
+
+/// Normalizes a Python distribution name for comparison (PEP 503)
+fn normalize_python_name(name: &str) -> String {
+    name.trim().to_lowercase().replace(['-', '.'], "_")
+}
+
+/// Returns the license declared in a Python package's `METADATA`
+///
+/// Prefers `License-Expression`, then a short `License` field, then the
+/// license classifiers.
+fn python_metadata_license(metadata: &str) -> Option<String> {
+    let headers = metadata.split("\n\n").next().unwrap_or_default();
+    let field = |name: &str| {
+        headers
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && *value != "UNKNOWN")
+    };
+    if let Some(expression) = field("License-Expression") {
+        return Some(expression.to_string());
+    }
+    if let Some(license) =
+        field("License").filter(|license| !license.contains('\n') && license.len() <= 64)
+    {
+        return Some(license.to_string());
+    }
+    let classifiers: Vec<&str> = headers
+        .lines()
+        .filter_map(|line| line.strip_prefix("Classifier: License :: "))
+        .filter_map(|classifier| classifier.rsplit(" :: ").next())
+        .collect();
+    (!classifiers.is_empty()).then(|| classifiers.join(" OR "))
+}
+
+/// Tells a license from the text of a license file
+///
+/// Recognizes the common permissive and copyleft licenses by their
+/// distinctive phrases and returns their SPDX identifiers.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::licenses::identify_license_text;
+///
+/// let text = "MIT License\n\nPermission is hereby granted, free of charge, ...";
+/// assert_eq!(identify_license_text(text), Some("MIT"));
+/// assert_eq!(identify_license_text("All rights reserved."), None);
+/// ```
+pub fn identify_license_text(text: &str) -> Option<&'static str> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let has = |phrase: &str| text.contains(phrase);
+    let license = if has("Apache License") && has("Version 2.0") {
+        "Apache-2.0"
+    } else if has("Mozilla Public License Version 2.0")
+        || has("Mozilla Public License, version 2.0")
+    {
+        "MPL-2.0"
+    } else if has("GNU AFFERO GENERAL PUBLIC LICENSE") {
+        "AGPL-3.0"
+    } else if has("GNU LESSER GENERAL PUBLIC LICENSE") {
+        if has("Version 2.1") {
+            "LGPL-2.1"
+        } else {
+            "LGPL-3.0"
+        }
+    } else if has("GNU GENERAL PUBLIC LICENSE") {
+        if has("Version 2") {
+            "GPL-2.0"
+        } else {
+            "GPL-3.0"
+        }
+    } else if has("Permission is hereby granted, free of charge") {
+        "MIT"
+    } else if has("Permission to use, copy, modify, and/or distribute this software") {
+        "ISC"
+    } else if has("Redistribution and use in source and binary forms") {
+        if has("Neither the name") || has("may be used to endorse or promote") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        }
+    } else if has("This is free and unencumbered software released into the public domain") {
+        "Unlicense"
+    } else {
+        return None;
+    };
+    Some(license)
+}
+
+/// Displays the inventory as a tree of licenses, packages and projects
+///
+/// # Arguments
+///
+/// * `inventory` - The inventory to display
+pub fn display_inventory(inventory: &LicenseInventory) {
+    if inventory.licenses.is_empty() {
+        println!("{}", display::header(t("deps-none"), "⚖️", Role::Warning));
+        return;
+    }
+    let packages: usize = inventory
+        .licenses
+        .iter()
+        .map(|group| group.packages.len())
+        .sum();
+    let unknown = inventory
+        .licenses
+        .iter()
+        .find(|group| group.license == UNKNOWN_LICENSE)
+        .map_or(0, |group| group.packages.len());
+    println!(
+        "{}",
+        display::header(
+            &tr!("licenses-title", count = inventory.licenses.len()),
+            "⚖️",
+            Role::Special
+        )
+    );
+    print!(
+        "{}",
+        display::summary_box(&[
+            (t("deps-projects"), inventory.projects.to_string()),
+            (t("exposure-packages"), packages.to_string()),
+            (t("licenses-unknown"), unknown.to_string()),
+        ])
+    );
+    println!();
+
+    for (index, group) in inventory.licenses.iter().enumerate() {
+        let role = if group.license == UNKNOWN_LICENSE {
+            Role::Warning
+        } else {
+            Role::Accent
+        };
+        let header = format!(
+            "{} {}",
+            group.license.paint(role).bold(),
+            tr!("licenses-packages", count = group.packages.len()).paint(Role::Muted)
+        );
+        println!(
+            "{}",
+            display::tree_item(&header, index == inventory.licenses.len() - 1, 0)
+        );
+        for (position, package) in group.packages.iter().enumerate() {
+            let projects: Vec<String> = package
+                .projects
+                .iter()
+                .map(|project| {
+                    project
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| project.display().to_string())
+                })
+                .collect();
+            let line = format!(
+                "{} {} {}",
+                display::version_display(&package.name, &package.version, None),
+                display::ecosystem_icon(&package.ecosystem.to_string()),
+                projects.join(", ").paint(Role::Muted)
+            );
+            println!(
+                "{}",
+                display::tree_item(&line, position == group.packages.len() - 1, 1)
+            );
+        }
+    }
+    if unknown > 0 {
+        println!(
+            "\n{} {}",
+            display::symbols("💡"),
+            t("licenses-tip-unknown").paint(Role::Muted)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::deps::DependencyType;
+    use tempfile::TempDir;
+
+    fn dependency(manifest: &Path, name: &str, version: &str, ecosystem: Ecosystem) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependency_type: DependencyType::Runtime,
+            ecosystem,
+            source_file: manifest.to_path_buf(),
+            extras: Vec::new(),
+            marker: None,
+        }
+    }
+
+    #[test]
+    fn reads_licenses_from_downloaded_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let crate_dir = root
+            .join("registry")
+            .join("index.crates.io-6f17d22bba15001f")
+            .join("serde-1.0.197");
+        fs::create_dir_all(&crate_dir).unwrap();
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"serde\"\nlicense = \"MIT OR Apache-2.0\"\n",
+        )
+        .unwrap();
+        let module = root
+            .join("gomod")
+            .join("github.com")
+            .join("!burnt!sushi")
+            .join("toml@v1.3.2");
+        fs::create_dir_all(&module).unwrap();
+        fs::write(
+            module.join("COPYING"),
+            "The MIT License (MIT)\n\nPermission is hereby granted, free of charge,\nto any person",
+        )
+        .unwrap();
+        let api = root.join("api");
+        fs::create_dir_all(api.join("node_modules").join("express")).unwrap();
+        fs::write(
+            api.join("node_modules")
+                .join("express")
+                .join("package.json"),
+            r#"{"license": "MIT"}"#,
+        )
+        .unwrap();
+        let dist_info = api
+            .join(".venv")
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages")
+            .join("Flask-3.0.0.dist-info");
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(dist_info.join("METADATA"), "Metadata-Version: 2.1\nName: Flask\nClassifier: License :: OSI Approved :: BSD License\n\nFlask is ...").unwrap();
+
+        let finder = LicenseFinder::new(Some(root.join("registry")), Some(root.join("gomod")));
+        let manifest = api.join("Cargo.toml");
+        let cases = [
+            (
+                dependency(&manifest, "serde", "1.0", Ecosystem::Rust),
+                "1.0.197",
+                Some("MIT OR Apache-2.0"),
+            ),
+            (
+                dependency(&manifest, "serde", "1.0", Ecosystem::Rust),
+                "1.0.0",
+                None,
+            ),
+            (
+                dependency(
+                    &manifest,
+                    "github.com/BurntSushi/toml",
+                    "v1.3.2",
+                    Ecosystem::Go,
+                ),
+                "v1.3.2",
+                Some("MIT"),
+            ),
+            (
+                dependency(&manifest, "express", "^4.18.0", Ecosystem::NodeJs),
+                "4.18.2",
+                Some("MIT"),
+            ),
+            (
+                dependency(&manifest, "flask", "3.0.0", Ecosystem::Python),
+                "3.0.0",
+                Some("BSD License"),
+            ),
+            (
+                dependency(&manifest, "requests", "2.31.0", Ecosystem::Python),
+                "2.31.0",
+                None,
+            ),
+        ];
+        for (dependency, version, license) in cases {
+            assert_eq!(
+                finder.license(&dependency, version).as_deref(),
+                license,
+                "{}",
+                dependency.name
+            );
+        }
+    }
+
+    #[test]
+    fn groups_packages_by_license() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut reports = Vec::new();
+        for project in ["api", "web"] {
+            let dir = temp_dir.path().join(project);
+            fs::create_dir_all(dir.join("node_modules").join("react")).unwrap();
+            fs::write(
+                dir.join("node_modules").join("react").join("package.json"),
+                r#"{"license": "MIT"}"#,
+            )
+            .unwrap();
+            let manifest = dir.join("package.json");
+            reports.push(DependencyReport {
+                project_path: dir.clone(),
+                dependencies: vec![
+                    dependency(&manifest, "react", "18.2.0", Ecosystem::NodeJs),
+                    dependency(&manifest, "left-pad", "1.3.0", Ecosystem::NodeJs),
+                ],
+                ecosystems: vec![Ecosystem::NodeJs],
+                errors: Vec::new(),
+                lockfiles: Vec::new(),
+                go_modules: Vec::new(),
+            });
+        }
+
+        let inventory = LicenseInventory::build_with(&reports, &LicenseFinder::new(None, None));
+        assert_eq!(inventory.projects, 2);
+        let licenses: Vec<&str> = inventory
+            .licenses
+            .iter()
+            .map(|group| group.license.as_str())
+            .collect();
+        assert_eq!(licenses, ["MIT", UNKNOWN_LICENSE]);
+        assert_eq!(inventory.licenses[0].packages[0].projects.len(), 2);
+
+        let csv = inventory.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 5);
+        assert!(rows[1].starts_with("MIT,Node.js,react,18.2.0,"));
+        assert!(rows[4].starts_with("unknown,Node.js,left-pad,1.3.0,"));
+    }
+
+    #[test]
+    fn quotes_csv_fields_when_needed() {
+        assert_eq!(csv_field("MIT"), "MIT");
+        assert_eq!(csv_field("MIT, Apache"), "\"MIT, Apache\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn identifies_license_texts() {
+        let apache =
+            "Apache License\n   Version 2.0, January 2004\n   http://www.apache.org/licenses/";
+        assert_eq!(identify_license_text(apache), Some("Apache-2.0"));
+        let bsd = "Redistribution and use in source and binary forms, with or without\nmodification, are permitted";
+        assert_eq!(identify_license_text(bsd), Some("BSD-2-Clause"));
+        let bsd3 = format!("{}\n3. Neither the name of the copyright holder", bsd);
+        assert_eq!(identify_license_text(&bsd3), Some("BSD-3-Clause"));
+        assert_eq!(
+            identify_license_text("GNU GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007"),
+            Some("GPL-3.0")
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Inode and open-file limits
+//!
+//! Two limits break large projects long before the disk is full or the
+//! machine is busy:
+//!
+//! - inodes: every file takes one, and `node_modules` and `target` trees
+//!   hold hundreds of thousands; once the file system runs out, creating a
+//!   file fails with "No space left on device" while `df -h` shows free space
+//! - open files: watchman, webpack's watcher, `cargo` and test runners keep
+//!   many files open at once and fail with `EMFILE` ("Too many open files")
+//!   under a soft limit of 256 (macOS) or 1024 (most Linux distributions)
+//!
+//! `scan --system` reads both on Unix with `statvfs` and `getrlimit`.
+//! File systems that allocate inodes dynamically (Btrfs, APFS on some
+//! versions) report no inode count and are skipped; Windows has neither
+//! limit.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Free inode share below which the volume counts as nearly exhausted, in percent
+pub const LOW_INODES_PERCENT: u64 = 10;
+
+/// Soft open-file limit below which file watchers and builds may fail
+pub const MIN_OPEN_FILES: u64 = 4096;
+
+/// Limit the remediation hints raise the open-file limit to
+pub const RECOMMENDED_OPEN_FILES: u64 = 65536;
+
+/// Inode usage of the file system holding the scanned directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InodeUsage {
+    /// Directory whose file system was measured
+    pub path: PathBuf,
+    /// Inodes on the file system
+    pub total: u64,
+    /// Inodes available to unprivileged users
+    pub available: u64,
+}
+
+impl InodeUsage {
+    /// Returns the share of inodes still available, in percent
+    pub fn available_percent(&self) -> u64 {
+        self.available
+            .saturating_mul(100)
+            .checked_div(self.total)
+            .unwrap_or(100)
+    }
+
+    /// Returns `true` if fewer than [`LOW_INODES_PERCENT`] are available
+    pub fn is_low(&self) -> bool {
+        self.available_percent() < LOW_INODES_PERCENT
+    }
+}
+
+/// The process's open-file limits, `RLIMIT_NOFILE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenFileLimit {
+    /// Limit in effect, inherited by every program started from the shell
+    pub soft: u64,
+    /// Ceiling the soft limit can be raised to without privileges,
+    /// `None` if unlimited
+    pub hard: Option<u64>,
+}
+
+impl OpenFileLimit {
+    /// Returns `true` if the soft limit is below [`MIN_OPEN_FILES`]
+    pub fn is_low(&self) -> bool {
+        self.soft < MIN_OPEN_FILES
+    }
+
+    /// Returns `true` if the hard limit stops `ulimit -n` from raising the
+    /// soft limit to [`MIN_OPEN_FILES`]
+    pub fn needs_privileges(&self) -> bool {
+        self.hard.is_some_and(|hard| hard < MIN_OPEN_FILES)
+    }
+
+    /// Returns the soft limit to raise to: [`RECOMMENDED_OPEN_FILES`], or
+    /// the hard limit if that is lower but still enough
+    pub fn target(&self) -> u64 {
+        match self.hard {
+            Some(hard) if hard >= MIN_OPEN_FILES => hard.min(RECOMMENDED_OPEN_FILES),
+            _ => RECOMMENDED_OPEN_FILES,
+        }
+    }
+}
+
+/// Inode usage and open-file limit
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LimitsReport {
+    /// Inodes of the scanned volume, `None` if not reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inodes: Option<InodeUsage>,
+    /// Open-file limit, `None` on platforms without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_files: Option<OpenFileLimit>,
+}
+
+/// Returns the command listing the directories below `path` with the most files
+///
+/// macOS `du` cannot count inodes, so files are counted with `find` there.
+pub fn busiest_directories_command(path: &Path) -> String {
+    if cfg!(target_os = "macos") {
+        format!(
+            "find '{}' -xdev | cut -d/ -f1-5 | sort | uniq -c | sort -n | tail",
+            path.display()
+        )
+    } else {
+        format!("du --inodes -x -d 3 '{}' | sort -n | tail", path.display())
+    }
+}
+
+/// Returns the command raising the open-file limit beyond the hard limit
+///
+/// `None` on platforms where this needs a configuration file rather than a
+/// command.
+pub fn raise_hard_limit_command() -> Option<String> {
+    cfg!(target_os = "macos").then(|| {
+        format!(
+            "sudo launchctl limit maxfiles {} 200000",
+            RECOMMENDED_OPEN_FILES
+        )
+    })
+}
+
+/// Reads the inode usage of the volume holding `path` and the open-file limit
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::limits;
+/// use std::path::Path;
+///
+/// let report = limits::check_limits(Path::new("."));
+/// if let Some(limit) = report.open_files.filter(|limit| limit.is_low()) {
+///     println!("ulimit -n is only {}", limit.soft);
+/// }
+/// ```
+pub fn check_limits(path: &Path) -> LimitsReport {
+    LimitsReport {
+        inodes: inode_usage(path),
+        open_files: open_file_limit(),
+    }
+}
+
+/// Returns the inode counts of the file system holding `path`
+#[cfg(unix)]
+fn inode_usage(path: &Path) -> Option<InodeUsage> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `statvfs` is plain data, so all-zero is a valid value.
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stats` points to
+    // a valid, properly aligned local that statvfs fills in.
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stats) } != 0 || stats.f_files == 0 {
+        return None;
+    }
+
+    Some(InodeUsage {
+        path: path.to_path_buf(),
+        total: stats.f_files as u64,
+        available: stats.f_favail as u64,
+    })
+}
+
+/// Returns the inode counts of the file system holding `path`
+#[cfg(not(unix))]
+fn inode_usage(_path: &Path) -> Option<InodeUsage> {
+    None
+}
+
+/// Returns the soft and hard `RLIMIT_NOFILE` of this process
+#[cfg(unix)]
+fn open_file_limit() -> Option<OpenFileLimit> {
+    // SAFETY: `rlimit` is plain data, so all-zero is a valid value.
+    let mut limit: libc::rlimit = unsafe { std::mem::zeroed() };
+    // SAFETY: `limit` points to a valid, properly aligned local that
+    // getrlimit fills in.
+    if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } != 0 {
+        return None;
+    }
+
+    // `rlim_t` is not `u64` on every Unix
+    #[allow(clippy::unnecessary_cast)]
+    let finite = |value: libc::rlim_t| (value != libc::RLIM_INFINITY).then_some(value as u64);
+    Some(OpenFileLimit {
+        soft: finite(limit.rlim_cur).unwrap_or(u64::MAX),
+        hard: finite(limit.rlim_max),
+    })
+}
+
+/// Returns the soft and hard `RLIMIT_NOFILE` of this process
+#[cfg(not(unix))]
+fn open_file_limit() -> Option<OpenFileLimit> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_inode_usage() {
+        let usage = |available| InodeUsage {
+            path: PathBuf::from("/"),
+            total: 1_000_000,
+            available,
+        };
+
+        assert!(usage(50_000).is_low());
+        assert!(!usage(100_000).is_low());
+        assert_eq!(
+            InodeUsage {
+                total: 0,
+                ..usage(0)
+            }
+            .available_percent(),
+            100
+        );
+    }
+
+    #[test]
+    fn rates_open_file_limits() {
+        let macos = OpenFileLimit {
+            soft: 256,
+            hard: None,
+        };
+        assert!(macos.is_low() && !macos.needs_privileges());
+
+        let linux = OpenFileLimit {
+            soft: 1024,
+            hard: Some(1024),
+        };
+        assert!(linux.is_low() && linux.needs_privileges());
+
+        let capped = OpenFileLimit {
+            soft: 1024,
+            hard: Some(20_000),
+        };
+        assert!(!capped.needs_privileges());
+        assert_eq!(capped.target(), 20_000);
+        assert_eq!(macos.target(), RECOMMENDED_OPEN_FILES);
+
+        assert!(!OpenFileLimit {
+            soft: 65536,
+            hard: Some(1_048_576)
+        }
+        .is_low());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reads_limits_of_existing_paths() {
+        let report = check_limits(Path::new("."));
+        assert!(report.open_files.is_some());
+        if let Some(inodes) = report.inodes {
+            assert!(inodes.available <= inodes.total);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Locale and UTF-8 configuration
+//!
+//! Without a UTF-8 locale, compilers print `?` for every non-ASCII character
+//! in their diagnostics, Python and Perl fail to decode file names, git
+//! quotes paths as octal escapes and DevHealth falls back to its plain ASCII
+//! output. `scan --system` checks:
+//!
+//! - on Unix, the locale selected by `LC_ALL`, `LC_CTYPE` or `LANG` (in that
+//!   order, like the C library), and whether the C library can load it:
+//!   `locale charmap` prints `ANSI_X3.4-1968` rather than `UTF-8` when the
+//!   named locale is not installed
+//! - on Windows, the console code page (`chcp`), which is UTF-8 only as
+//!   code page 65001
+//!
+//! A problem comes with the commands that fix it for the user's shell.
+
+use crate::scanner::container;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Variables selecting the character encoding, highest precedence first
+const VARIABLES: [&str; 3] = ["LC_ALL", "LC_CTYPE", "LANG"];
+
+/// Locale suggested when the current one names no language
+///
+/// Linux distributions ship `C.UTF-8` even when no other locale is
+/// generated; macOS has no `C.UTF-8`.
+const DEFAULT_LOCALE: &str = if cfg!(target_os = "linux") {
+    "C.UTF-8"
+} else {
+    "en_US.UTF-8"
+};
+
+/// The UTF-8 console code page on Windows
+const UTF8_CODE_PAGE: &str = "65001";
+
+/// What is wrong with the locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocaleProblem {
+    /// No locale variable is set, so programs use the ASCII `C` locale
+    Unset,
+    /// The locale selects another encoding
+    NotUtf8,
+    /// The locale names UTF-8 but is not installed
+    NotInstalled,
+    /// The Windows console does not use the UTF-8 code page
+    ConsoleCodePage,
+}
+
+impl LocaleProblem {
+    /// Returns the problem's name as used in findings
+    pub fn name(self) -> &'static str {
+        match self {
+            LocaleProblem::Unset => "unset",
+            LocaleProblem::NotUtf8 => "not UTF-8",
+            LocaleProblem::NotInstalled => "not installed",
+            LocaleProblem::ConsoleCodePage => "console code page",
+        }
+    }
+}
+
+/// The active locale and whether it is UTF-8
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocaleReport {
+    /// Variable the locale comes from, `None` if none is set
+    pub variable: Option<String>,
+    /// The locale, e.g. `de_DE.UTF-8`
+    pub locale: Option<String>,
+    /// Encoding the C library or console reports, e.g. `UTF-8`,
+    /// `ANSI_X3.4-1968` or `code page 850`
+    pub encoding: Option<String>,
+    /// What is wrong, if anything
+    pub problem: Option<LocaleProblem>,
+    /// Commands that fix the problem for the user's shell
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fix: Vec<String>,
+}
+
+/// Checks the locale variables and the encoding they result in
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::locale;
+///
+/// let report = locale::check_locale();
+/// if report.problem.is_some() {
+///     for command in &report.fix {
+///         println!("{}", command);
+///     }
+/// }
+/// ```
+pub fn check_locale() -> LocaleReport {
+    if cfg!(windows) {
+        let code_page =
+            container::run_command("cmd", &["/c", "chcp"]).and_then(|output| parse_chcp(&output));
+        let problem = code_page
+            .as_deref()
+            .filter(|code_page| *code_page != UTF8_CODE_PAGE)
+            .map(|_| LocaleProblem::ConsoleCodePage);
+        return LocaleReport {
+            variable: None,
+            locale: None,
+            encoding: code_page.map(|code_page| format!("code page {}", code_page)),
+            fix: problem.map(|_| windows_fix()).unwrap_or_default(),
+            problem,
+        };
+    }
+
+    let variables: Vec<(&str, Option<String>)> = VARIABLES
+        .iter()
+        .map(|name| (*name, std::env::var(name).ok()))
+        .collect();
+    let charmap = container::run_command("locale", &["charmap"]);
+    let mut report = assess(&variables, charmap.as_deref());
+    if let Some(problem) = report.problem {
+        let shell = std::env::var("SHELL").ok();
+        report.fix = unix_fix(
+            problem,
+            report.variable.as_deref(),
+            report.locale.as_deref(),
+            shell.as_deref(),
+        );
+    }
+    report
+}
+
+/// Rates the locale selected by `variables`, given the C library's charmap
+fn assess(variables: &[(&str, Option<String>)], charmap: Option<&str>) -> LocaleReport {
+    let selected = variables.iter().find_map(|(name, value)| {
+        value
+            .as_deref()
+            .filter(|value| !value.is_empty())
+            .map(|value| (*name, value))
+    });
+    let charmap = charmap
+        .map(|charmap| charmap.lines().last().unwrap_or(charmap).trim())
+        .filter(|charmap| !charmap.is_empty());
+
+    let problem = match selected {
+        None => Some(LocaleProblem::Unset),
+        Some((_, locale)) if !is_utf8(locale) => Some(LocaleProblem::NotUtf8),
+        Some(_) if charmap.is_some_and(|charmap| !is_utf8(charmap)) => {
+            Some(LocaleProblem::NotInstalled)
+        }
+        Some(_) => None,
+    };
+    LocaleReport {
+        variable: selected.map(|(name, _)| name.to_string()),
+        locale: selected.map(|(_, locale)| locale.to_string()),
+        encoding: charmap.map(String::from),
+        problem,
+        fix: Vec::new(),
+    }
+}
+
+/// Returns `true` if a locale or charmap name denotes UTF-8
+fn is_utf8(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.contains("utf-8") || name.contains("utf8")
+}
+
+/// Returns the UTF-8 variant of `locale`, keeping its language and territory
+///
+/// `de_DE.ISO-8859-1@euro` becomes `de_DE.UTF-8`; `C`, `POSIX` and unset
+/// locales become [`DEFAULT_LOCALE`].
+fn utf8_locale(locale: Option<&str>) -> String {
+    let language = locale
+        .map(|locale| locale.split(['.', '@']).next().unwrap_or(locale))
+        .filter(|language| !language.is_empty() && *language != "C" && *language != "POSIX");
+    match language {
+        Some(language) => format!("{}.UTF-8", language),
+        None => DEFAULT_LOCALE.to_string(),
+    }
+}
+
+/// Returns the commands that switch a Unix shell to a UTF-8 locale
+///
+/// `LANG` is set in the shell's profile; an overriding `LC_ALL` or
+/// `LC_CTYPE` has to go.
+fn unix_fix(
+    problem: LocaleProblem,
+    variable: Option<&str>,
+    locale: Option<&str>,
+    shell: Option<&str>,
+) -> Vec<String> {
+    let target = utf8_locale(locale);
+    let mut fix = Vec::new();
+    if problem == LocaleProblem::NotInstalled && cfg!(target_os = "linux") {
+        fix.push(format!("sudo locale-gen {}", target));
+    }
+
+    let shell = shell
+        .and_then(|shell| Path::new(shell).file_name())
+        .and_then(|name| name.to_str())
+        .unwrap_or("sh");
+    fix.push(match shell {
+        "fish" => format!("set -Ux LANG {}", target),
+        "zsh" => format!("echo 'export LANG={}' >> ~/.zshrc", target),
+        "bash" => format!("echo 'export LANG={}' >> ~/.bashrc", target),
+        _ => format!("echo 'export LANG={}' >> ~/.profile", target),
+    });
+    if let Some(variable) = variable.filter(|variable| *variable != "LANG") {
+        fix.push(format!("unset {}", variable));
+    }
+    fix
+}
+
+/// Returns the commands that switch PowerShell and the console to UTF-8
+fn windows_fix() -> Vec<String> {
+    vec![
+        "chcp 65001".to_string(),
+        "Add-Content $PROFILE '[Console]::OutputEncoding = [Text.UTF8Encoding]::new()'".to_string(),
+    ]
+}
+
+/// Extracts the number from `chcp` output such as `Active code page: 850`
+fn parse_chcp(output: &str) -> Option<String> {
+    output
+        .rsplit(|c: char| !c.is_ascii_digit())
+        .find(|part| !part.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(lc_all: Option<&str>, lang: Option<&str>) -> Vec<(&'static str, Option<String>)> {
+        vec![
+            ("LC_ALL", lc_all.map(String::from)),
+            ("LC_CTYPE", None),
+            ("LANG", lang.map(String::from)),
+        ]
+    }
+
+    #[test]
+    fn rates_locale_variables_and_charmap() {
+        let ok = assess(&vars(None, Some("en_US.UTF-8")), Some("UTF-8"));
+        assert_eq!(ok.problem, None);
+        assert_eq!(ok.variable.as_deref(), Some("LANG"));
+
+        let overridden = assess(
+            &vars(Some("C"), Some("en_US.UTF-8")),
+            Some("ANSI_X3.4-1968"),
+        );
+        assert_eq!(overridden.problem, Some(LocaleProblem::NotUtf8));
+        assert_eq!(overridden.variable.as_deref(), Some("LC_ALL"));
+
+        let missing = assess(&vars(None, Some("de_DE.utf8")), Some("ANSI_X3.4-1968"));
+        assert_eq!(missing.problem, Some(LocaleProblem::NotInstalled));
+
+        assert_eq!(
+            assess(&vars(Some(""), None), None).problem,
+            Some(LocaleProblem::Unset)
+        );
+    }
+
+    #[test]
+    fn suggests_fixes_per_shell() {
+        assert_eq!(utf8_locale(Some("de_DE.ISO-8859-1@euro")), "de_DE.UTF-8");
+        assert_eq!(utf8_locale(Some("POSIX")), DEFAULT_LOCALE);
+
+        let fix = unix_fix(
+            LocaleProblem::NotUtf8,
+            Some("LC_ALL"),
+            Some("C"),
+            Some("/usr/bin/zsh"),
+        );
+        assert_eq!(
+            fix,
+            vec![
+                format!("echo 'export LANG={}' >> ~/.zshrc", DEFAULT_LOCALE),
+                "unset LC_ALL".to_string()
+            ]
+        );
+        let fix = unix_fix(
+            LocaleProblem::Unset,
+            None,
+            None,
+            Some("/usr/local/bin/fish"),
+        );
+        assert_eq!(fix, vec![format!("set -Ux LANG {}", DEFAULT_LOCALE)]);
+    }
+
+    #[test]
+    fn parses_console_code_page() {
+        assert_eq!(parse_chcp("Active code page: 850").as_deref(), Some("850"));
+        assert_eq!(
+            parse_chcp("Aktive Codepage: 65001.").as_deref(),
+            Some("65001")
+        );
+        assert_eq!(parse_chcp("nothing"), None);
+    }
+}
@@ -0,0 +1,439 @@
+//! Lockfile drift
+//!
+//! A lockfile that no longer matches its manifest means the next install
+//! resolves something other than what was last built and tested. For every
+//! Cargo and npm project the dependency scanner finds, this module checks
+//! for:
+//!
+//! - a missing lockfile: no `Cargo.lock` for a crate with a binary target
+//!   (libraries leave locking to the applications using them), or no
+//!   `package-lock.json`, `yarn.lock`, `pnpm-lock.yaml` or `bun.lock` for a
+//!   `package.json` with dependencies
+//! - a lockfile out of sync: a registry dependency in `Cargo.toml` without a
+//!   locked version satisfying its constraint, or `package.json`
+//!   dependencies that differ from those `package-lock.json` recorded
+//! - a stale lockfile: the manifest was changed more than
+//!   [`STALE_LOCKFILE_SECS`] seconds after the lockfile was last written
+//!
+//! Lockfiles are looked up in the project directory and its ancestors up to
+//! the repository root, so workspace members share the workspace lockfile.
+//! Each problem comes with the command that brings the lockfile up to date.
+
+use crate::scanner::{cargo, pins};
+use semver::VersionReq;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A manifest changed this many seconds after its lockfile makes the
+/// lockfile stale
+///
+/// The slack keeps files written by the same checkout or install apart from
+/// real edits.
+pub const STALE_LOCKFILE_SECS: u64 = 60;
+
+/// npm-compatible lockfiles with the command that updates each
+const NODE_LOCKFILES: [(&str, &str); 6] = [
+    ("package-lock.json", "npm install"),
+    ("npm-shrinkwrap.json", "npm install"),
+    ("yarn.lock", "yarn install"),
+    ("pnpm-lock.yaml", "pnpm install"),
+    ("bun.lock", "bun install"),
+    ("bun.lockb", "bun install"),
+];
+
+/// `package.json` objects `package-lock.json` records for the root package
+const NPM_SECTIONS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "optionalDependencies",
+    "peerDependencies",
+];
+
+/// What is wrong with a project's lockfile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LockfileProblem {
+    /// There is no lockfile
+    Missing,
+    /// The lockfile does not satisfy the manifest
+    OutOfSync,
+    /// The manifest changed after the lockfile was written
+    Stale,
+}
+
+/// A lockfile that drifted from its manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockfileIssue {
+    /// The `Cargo.toml` or `package.json`
+    pub manifest: PathBuf,
+    /// The lockfile, `None` if missing
+    pub lockfile: Option<PathBuf>,
+    /// What is wrong
+    pub problem: LockfileProblem,
+    /// Dependencies the lockfile does not satisfy, for
+    /// [`LockfileProblem::OutOfSync`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+    /// Command that brings the lockfile up to date, e.g. `npm install`
+    pub action: String,
+}
+
+/// Checks the lockfiles of the Cargo and npm manifests in a project
+/// directory
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::lockfiles;
+/// use std::path::Path;
+///
+/// for issue in lockfiles::check_project(Path::new(".")) {
+///     println!("{:?}: run {}", issue.problem, issue.action);
+/// }
+/// ```
+pub fn check_project(dir: &Path) -> Vec<LockfileIssue> {
+    let cargo = Some(dir.join("Cargo.toml")).filter(|manifest| manifest.is_file());
+    let npm = Some(dir.join("package.json")).filter(|manifest| manifest.is_file());
+    cargo
+        .and_then(|manifest| check_cargo(&manifest))
+        .into_iter()
+        .chain(npm.and_then(|manifest| check_npm(&manifest)))
+        .collect()
+}
+
+/// Checks the `Cargo.lock` of a package
+///
+/// Virtual workspace manifests are skipped; their members are checked on
+/// their own.
+fn check_cargo(manifest: &Path) -> Option<LockfileIssue> {
+    let document: toml::Value = toml::from_str(&fs::read_to_string(manifest).ok()?).ok()?;
+    document.get("package")?;
+    let dir = manifest.parent()?;
+    let issue = |lockfile: Option<PathBuf>, problem, dependencies, action: &str| LockfileIssue {
+        manifest: manifest.to_path_buf(),
+        lockfile,
+        problem,
+        dependencies,
+        action: action.to_string(),
+    };
+
+    let Some(lockfile) =
+        cargo::find_lockfile(dir).filter(|lockfile| within_repository(dir, lockfile))
+    else {
+        return has_binary_target(&document, dir).then(|| {
+            issue(
+                None,
+                LockfileProblem::Missing,
+                Vec::new(),
+                "cargo generate-lockfile",
+            )
+        });
+    };
+    let locked = pins::cargo_locked_versions(&fs::read_to_string(&lockfile).ok()?);
+    let unsatisfied: Vec<String> = pins::cargo_registry_dependencies(&document)
+        .into_iter()
+        .filter(|dependency| {
+            let Ok(requirement) = VersionReq::parse(&dependency.constraint) else {
+                return false;
+            };
+            !locked
+                .get(&dependency.package)
+                .is_some_and(|versions| versions.iter().any(|version| requirement.matches(version)))
+        })
+        .map(|dependency| dependency.name)
+        .collect();
+    if !unsatisfied.is_empty() {
+        return Some(issue(
+            Some(lockfile),
+            LockfileProblem::OutOfSync,
+            unsatisfied,
+            "cargo update --workspace",
+        ));
+    }
+    is_stale(manifest, &lockfile).then(|| {
+        issue(
+            Some(lockfile),
+            LockfileProblem::Stale,
+            Vec::new(),
+            "cargo update --workspace",
+        )
+    })
+}
+
+/// Returns `true` if a package builds a binary: `src/main.rs`, `src/bin`
+/// or a `[[bin]]` target
+fn has_binary_target(document: &toml::Value, dir: &Path) -> bool {
+    document
+        .get("bin")
+        .and_then(toml::Value::as_array)
+        .is_some_and(|bins| !bins.is_empty())
+        || dir.join("src").join("main.rs").is_file()
+        || dir.join("src").join("bin").is_dir()
+}
+
+/// Checks the lockfile of a `package.json`
+fn check_npm(manifest: &Path) -> Option<LockfileIssue> {
+    let document: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(manifest).ok()?).ok()?;
+    let dir = manifest.parent()?;
+    let declares = |section: &str| {
+        document
+            .get(section)
+            .and_then(serde_json::Value::as_object)
+            .is_some_and(|dependencies| !dependencies.is_empty())
+    };
+    if !NPM_SECTIONS.iter().any(|section| declares(section)) {
+        return None;
+    }
+
+    let root = dir
+        .ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+        .unwrap_or(dir);
+    let found = dir
+        .ancestors()
+        .take_while(|ancestor| ancestor.starts_with(root))
+        .find_map(|ancestor| {
+            NODE_LOCKFILES
+                .iter()
+                .map(|(name, action)| (ancestor.join(name), *action))
+                .find(|(lockfile, _)| lockfile.is_file())
+        });
+    let issue = |lockfile: Option<PathBuf>, problem, dependencies, action: &str| LockfileIssue {
+        manifest: manifest.to_path_buf(),
+        lockfile,
+        problem,
+        dependencies,
+        action: action.to_string(),
+    };
+    let Some((lockfile, action)) = found else {
+        return Some(issue(
+            None,
+            LockfileProblem::Missing,
+            Vec::new(),
+            "npm install",
+        ));
+    };
+
+    if lockfile
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        let lock: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&lockfile).ok()?).ok()?;
+        let project = lockfile
+            .parent()
+            .and_then(|root| dir.strip_prefix(root).ok())
+            .map(|relative| {
+                relative
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .unwrap_or_default();
+        let differing = npm_differences(&document, &lock, &project);
+        if !differing.is_empty() {
+            return Some(issue(
+                Some(lockfile),
+                LockfileProblem::OutOfSync,
+                differing,
+                action,
+            ));
+        }
+    }
+    is_stale(manifest, &lockfile)
+        .then(|| issue(Some(lockfile), LockfileProblem::Stale, Vec::new(), action))
+}
+
+/// Returns the dependencies `package.json` declares differently than the
+/// lockfile recorded for the package at `project`
+///
+/// Lockfile version 2 and later record the declared dependencies of every
+/// workspace package under `packages`; version 1 lockfiles only list what is
+/// installed, so only dependencies missing from them are found.
+fn npm_differences(
+    manifest: &serde_json::Value,
+    lock: &serde_json::Value,
+    project: &str,
+) -> Vec<String> {
+    let mut differing = Vec::new();
+    if let Some(recorded) = lock
+        .get("packages")
+        .and_then(|packages| packages.get(project))
+    {
+        for section in NPM_SECTIONS {
+            let declared = manifest.get(section).and_then(serde_json::Value::as_object);
+            let locked = recorded.get(section).and_then(serde_json::Value::as_object);
+            let names = declared
+                .into_iter()
+                .chain(locked)
+                .flat_map(|object| object.keys());
+            for name in names {
+                let changed = declared.and_then(|object| object.get(name))
+                    != locked.and_then(|object| object.get(name));
+                if changed && !differing.contains(name) {
+                    differing.push(name.clone());
+                }
+            }
+        }
+    } else if let Some(installed) = lock
+        .get("dependencies")
+        .and_then(serde_json::Value::as_object)
+    {
+        for section in ["dependencies", "devDependencies"] {
+            let declared = manifest.get(section).and_then(serde_json::Value::as_object);
+            for name in declared.into_iter().flat_map(|object| object.keys()) {
+                if !installed.contains_key(name) && !differing.contains(name) {
+                    differing.push(name.clone());
+                }
+            }
+        }
+    }
+    differing.sort();
+    differing
+}
+
+/// Returns `true` if the manifest changed more than
+/// [`STALE_LOCKFILE_SECS`] after the lockfile
+fn is_stale(manifest: &Path, lockfile: &Path) -> bool {
+    let modified = |path: &Path| {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    };
+    match (modified(manifest), modified(lockfile)) {
+        (Some(manifest), Some(lockfile)) => manifest
+            .duration_since(lockfile)
+            .is_ok_and(|newer| newer.as_secs() > STALE_LOCKFILE_SECS),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `path` lies in the git repository holding `dir`, or
+/// `dir` is in no repository
+fn within_repository(dir: &Path, path: &Path) -> bool {
+    match dir
+        .ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+    {
+        Some(root) => path.starts_with(root),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    const CARGO_LOCK: &str =
+        "version = 3\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.150\"\n";
+
+    fn binary_crate(dependencies: &str) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        fs::create_dir(temp_dir.path().join("src")).unwrap();
+        fs::write(
+            temp_dir.path().join("src").join("main.rs"),
+            "fn main() {}\n",
+        )
+        .unwrap();
+        let manifest = format!(
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\n{}",
+            dependencies
+        );
+        fs::write(temp_dir.path().join("Cargo.toml"), manifest).unwrap();
+        temp_dir
+    }
+
+    fn backdate(path: &Path, seconds: u64) {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(seconds))
+            .unwrap();
+    }
+
+    #[test]
+    fn expects_a_cargo_lock_only_for_binaries() {
+        let app = binary_crate("serde = \"1\"\n");
+        let issues = check_project(app.path());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].problem, LockfileProblem::Missing);
+        assert_eq!(issues[0].lockfile, None);
+        assert_eq!(issues[0].action, "cargo generate-lockfile");
+
+        fs::remove_file(app.path().join("src").join("main.rs")).unwrap();
+        assert!(check_project(app.path()).is_empty());
+    }
+
+    #[test]
+    fn finds_requirements_the_cargo_lock_does_not_satisfy() {
+        let app = binary_crate("serde = \"1.0.150\"\nlocal = { path = \"../local\" }\n");
+        fs::write(app.path().join("Cargo.lock"), CARGO_LOCK).unwrap();
+        assert!(check_project(app.path()).is_empty());
+
+        let app = binary_crate("serde = \"1.0.190\"\n");
+        fs::write(app.path().join("Cargo.lock"), CARGO_LOCK).unwrap();
+        let issues = check_project(app.path());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].problem, LockfileProblem::OutOfSync);
+        assert_eq!(issues[0].dependencies, vec!["serde"]);
+        assert_eq!(issues[0].action, "cargo update --workspace");
+    }
+
+    #[test]
+    fn reports_lockfiles_older_than_their_manifest() {
+        let app = binary_crate("serde = \"1\"\n");
+        let lockfile = app.path().join("Cargo.lock");
+        fs::write(&lockfile, CARGO_LOCK).unwrap();
+        backdate(&lockfile, STALE_LOCKFILE_SECS / 2);
+        assert!(check_project(app.path()).is_empty());
+
+        backdate(&lockfile, STALE_LOCKFILE_SECS * 10);
+        let issues = check_project(app.path());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].problem, LockfileProblem::Stale);
+        assert_eq!(issues[0].lockfile.as_deref(), Some(lockfile.as_path()));
+    }
+
+    #[test]
+    fn checks_npm_lockfiles_up_to_the_repository_root() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        let web = temp_dir.path().join("packages").join("web");
+        fs::create_dir_all(&web).unwrap();
+        fs::write(web.join("package.json"), r#"{"name": "web"}"#).unwrap();
+        assert!(check_project(&web).is_empty());
+
+        fs::write(
+            web.join("package.json"),
+            r#"{"dependencies": {"react": "^18.2.0", "vite": "^5.0.0"}}"#,
+        )
+        .unwrap();
+        let issues = check_project(&web);
+        assert_eq!(issues[0].problem, LockfileProblem::Missing);
+        assert_eq!(issues[0].action, "npm install");
+
+        let lock = r#"{
+            "lockfileVersion": 3,
+            "packages": {
+                "": {"workspaces": ["packages/*"]},
+                "packages/web": {"dependencies": {"react": "^18.2.0", "lodash": "^4.17.0"}}
+            }
+        }"#;
+        fs::write(temp_dir.path().join("package-lock.json"), lock).unwrap();
+        let issues = check_project(&web);
+        assert_eq!(issues[0].problem, LockfileProblem::OutOfSync);
+        assert_eq!(issues[0].dependencies, vec!["lodash", "vite"]);
+
+        fs::remove_file(temp_dir.path().join("package-lock.json")).unwrap();
+        fs::write(temp_dir.path().join("yarn.lock"), "").unwrap();
+        assert!(check_project(&web).is_empty());
+        backdate(&temp_dir.path().join("yarn.lock"), STALE_LOCKFILE_SECS * 10);
+        let issues = check_project(&web);
+        assert_eq!(issues[0].problem, LockfileProblem::Stale);
+        assert_eq!(issues[0].action, "yarn install");
+    }
+}
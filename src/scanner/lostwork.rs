@@ -0,0 +1,304 @@
+//! Lost work detection (`scan --lost-work`)
+//!
+//! A bad `git reset --hard`, an aborted rebase or a dropped stash leaves
+//! commits that no branch, tag or remote-tracking ref reaches any more. Git
+//! keeps them until the reflog expires and `git gc` prunes them, so they can
+//! still be recovered for a while. For each repository this module looks at:
+//!
+//! - the `HEAD` reflog, which records every commit that was checked out,
+//!   created, amended or rebased
+//! - dangling commits reported by `git fsck`, such as dropped stashes
+//!
+//! Commits from the last [`LOST_WORK_DAYS`] days that are unreachable from
+//! every ref are reported, keeping only the newest commit of each lost line
+//! of work, with a command that recovers it as a branch.
+
+use crate::scanner::git::{GitRepo, GitStatus};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Commits older than this many days are not reported
+///
+/// Matches git's default `gc.reflogExpireUnreachable`, after which
+/// unreachable reflog entries are dropped anyway.
+pub const LOST_WORK_DAYS: u64 = 30;
+
+/// Where a lost commit was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LostSource {
+    /// An entry of the `HEAD` reflog
+    Reflog,
+    /// A dangling commit found by `git fsck`, e.g. a dropped stash
+    Dangling,
+}
+
+/// A recent commit no branch, tag or remote-tracking ref reaches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LostCommit {
+    /// Full commit hash
+    pub hash: String,
+    /// First line of the commit message
+    pub subject: String,
+    /// Commit time as a Unix timestamp
+    pub committed_at: u64,
+    /// Where the commit was found
+    pub source: LostSource,
+}
+
+impl LostCommit {
+    /// Returns the abbreviated commit hash
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    /// Returns the command that recovers the commit as a new branch
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::lostwork::{LostCommit, LostSource};
+    ///
+    /// let commit = LostCommit {
+    ///     hash: "3f2a9c1d0e5b7a8f".to_string(),
+    ///     subject: "Add retry logic".to_string(),
+    ///     committed_at: 0,
+    ///     source: LostSource::Reflog,
+    /// };
+    /// assert_eq!(commit.recovery(), "git branch recover-3f2a9c1 3f2a9c1d0e5b7a8f");
+    /// ```
+    pub fn recovery(&self) -> String {
+        format!("git branch recover-{} {}", self.short_hash(), self.hash)
+    }
+}
+
+/// Looks for lost work in every repository, updating them in place
+///
+/// Repositories that could not be analyzed are skipped.
+///
+/// # Arguments
+///
+/// * `repos` - Repositories from the git scanner
+/// * `now` - Current Unix timestamp, the end of the [`LOST_WORK_DAYS`] window
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use devhealth::scanner::{git, lostwork};
+/// use devhealth::utils::display;
+/// use std::path::Path;
+///
+/// let mut repos = git::scan_directory(Path::new("."))?;
+/// lostwork::enrich_repositories(&mut repos, display::unix_now());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn enrich_repositories(repos: &mut [GitRepo], now: u64) {
+    for repo in repos
+        .iter_mut()
+        .filter(|repo| !matches!(repo.status, GitStatus::Error(_)))
+    {
+        repo.lost_commits =
+            find_lost_commits(&repo.path, now.saturating_sub(LOST_WORK_DAYS * 86_400));
+    }
+}
+
+/// Returns the commits since `since` that only the reflog or the object
+/// database still know about, newest first
+pub fn find_lost_commits(repo_path: &Path, since: u64) -> Vec<LostCommit> {
+    // Reflogs hold thousands of entries, so revisions go through stdin
+    let git = |args: &[&str], input: &str| {
+        let mut child = Command::new("git")
+            .args(args)
+            .current_dir(repo_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        // Dropping stdin after writing closes it, so git sees the end of input
+        child.stdin.take()?.write_all(input.as_bytes()).ok()?;
+        let output = child.wait_with_output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    let mut candidates: BTreeMap<String, LostSource> = BTreeMap::new();
+    for hash in
+        parse_dangling(&git(&["fsck", "--dangling", "--no-progress"], "").unwrap_or_default())
+    {
+        candidates.insert(hash, LostSource::Dangling);
+    }
+    for line in git(&["log", "-g", "--format=%H", "HEAD"], "")
+        .unwrap_or_default()
+        .lines()
+    {
+        candidates.insert(line.trim().to_string(), LostSource::Reflog);
+    }
+    candidates.retain(|hash, _| !hash.is_empty());
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+
+    // Commits reachable from a candidate but from no ref, with their parents
+    let input: String = candidates
+        .keys()
+        .map(|hash| format!("{}\n", hash))
+        .collect();
+    let unreachable = git(
+        &["rev-list", "--parents", "--stdin", "--not", "--all"],
+        &input,
+    )
+    .unwrap_or_default();
+    let tips = lost_tips(&unreachable, &candidates);
+    if tips.is_empty() {
+        return Vec::new();
+    }
+
+    let input: String = tips.iter().map(|hash| format!("{}\n", hash)).collect();
+    let mut commits: Vec<LostCommit> = git(
+        &[
+            "log",
+            "--no-walk=unsorted",
+            "--stdin",
+            "--format=%H%x1f%ct%x1f%s",
+        ],
+        &input,
+    )
+    .unwrap_or_default()
+    .lines()
+    .filter_map(|line| parse_commit(line, &candidates))
+    .filter(|commit| commit.committed_at >= since)
+    .collect();
+    commits.sort_by_key(|commit| Reverse(commit.committed_at));
+    commits
+}
+
+/// Returns the lost candidates no other unreachable commit builds on
+///
+/// `rev_list` is `git rev-list --parents` output of the unreachable commits.
+/// A candidate that is the parent of one of them is an ancestor of newer
+/// lost work and is left out.
+fn lost_tips<'a>(rev_list: &'a str, candidates: &BTreeMap<String, LostSource>) -> Vec<&'a str> {
+    let mut parents = BTreeSet::new();
+    let mut unreachable = Vec::new();
+    for line in rev_list.lines() {
+        let mut hashes = line.split_whitespace();
+        if let Some(hash) = hashes.next() {
+            unreachable.push(hash);
+            parents.extend(hashes);
+        }
+    }
+    unreachable
+        .into_iter()
+        .filter(|hash| candidates.contains_key(*hash) && !parents.contains(hash))
+        .collect()
+}
+
+/// Extracts the hashes of `dangling commit <hash>` lines from `git fsck`
+fn parse_dangling(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("dangling commit "))
+        .map(|hash| hash.trim().to_string())
+        .collect()
+}
+
+/// Parses a `%H%x1f%ct%x1f%s` line of a candidate commit
+fn parse_commit(line: &str, candidates: &BTreeMap<String, LostSource>) -> Option<LostCommit> {
+    let mut fields = line.splitn(3, '\u{1f}');
+    let hash = fields.next()?.trim().to_string();
+    let committed_at = fields.next()?.trim().parse().ok()?;
+    let subject = fields.next().unwrap_or_default().trim().to_string();
+    let source = *candidates.get(&hash)?;
+    Some(LostCommit {
+        hash,
+        subject,
+        committed_at,
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args([
+                "-c",
+                "user.name=Dev",
+                "-c",
+                "user.email=dev@example.com",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn parses_fsck_output() {
+        let output =
+            "Checking object directories\ndangling blob 1111\ndangling commit abcdef0123\n";
+        assert_eq!(parse_dangling(output), vec!["abcdef0123".to_string()]);
+    }
+
+    #[test]
+    fn keeps_only_the_tips_of_lost_work() {
+        let candidates = BTreeMap::from([
+            ("c3".to_string(), LostSource::Reflog),
+            ("c2".to_string(), LostSource::Reflog),
+            ("d1".to_string(), LostSource::Dangling),
+        ]);
+        let rev_list = "c3 c2\nd1 b0 s1\nc2 b0\ns1 b0\n";
+        assert_eq!(lost_tips(rev_list, &candidates), vec!["c3", "d1"]);
+    }
+
+    #[test]
+    fn finds_commits_lost_by_a_hard_reset() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        git(repo, &["init", "-q"]);
+        git(
+            repo,
+            &["commit", "-q", "--allow-empty", "-m", "Initial commit"],
+        );
+        git(
+            repo,
+            &["commit", "-q", "--allow-empty", "-m", "Draft parser"],
+        );
+        git(
+            repo,
+            &["commit", "-q", "--allow-empty", "-m", "Add retry logic"],
+        );
+        git(repo, &["reset", "-q", "--hard", "HEAD~2"]);
+
+        let lost = find_lost_commits(repo, 0);
+
+        assert_eq!(
+            lost.len(),
+            1,
+            "Only the tip of the lost line is reported: {:?}",
+            lost
+        );
+        assert_eq!(lost[0].subject, "Add retry logic");
+        assert_eq!(lost[0].source, LostSource::Reflog);
+        assert!(
+            find_lost_commits(repo, u64::MAX).is_empty(),
+            "Old commits are ignored"
+        );
+
+        git(repo, &["branch", "rescued", &lost[0].hash]);
+        assert!(find_lost_commits(repo, 0).is_empty());
+    }
+}
@@ -5,10 +5,70 @@
 //!
 //! - [`git`]: Git repository health and status analysis
 //! - [`deps`]: Dependency health checking across multiple ecosystems
-//! - [`system`]: System resource monitoring (planned)
-//! - [`analytics`]: Project analytics and metrics (planned)
+//! - [`buildfiles`]: Bazel, Buck and Nix build files and the dependencies they declare
+//! - [`gomod`]: Go module replacements, exclusions and `go.work` workspaces
+//! - [`audit`]: Vulnerability audit of dependencies against an advisory database
+//! - [`container`]: Host versus dev container toolchain comparison
+//! - [`workspace`]: Monorepo workspaces, per-package health and version conflicts
+//! - [`cargo`]: Rust edition and MSRV audit of Cargo packages
+//! - [`runtime`]: Node version managers, `PATH` shadowing and pinned runtime versions
+//! - [`lostwork`]: Recent commits only the reflog still reaches (`--lost-work`)
+//! - [`permissions`]: Readable `.git` directories and committed or readable secrets
+//! - [`sizes`]: Working tree, `.git` and build artifact sizes per repository (`--sizes`)
+//! - [`forge`]: CI status of the default branch on GitHub and GitLab
+//! - [`release`]: Manifest versions compared with the latest git tag
+//! - [`exposure`]: Packages, versions and vulnerabilities shared across projects (`deps exposure`)
+//! - [`alignment`]: Packages used at versions far apart and the version to converge on (`deps align`)
+//! - [`impact`]: Constraints blocking an upgrade across projects (`deps upgrade-impact`)
+//! - [`licenses`]: License inventory of every dependency across projects (`licenses`)
+//! - [`lockfiles`]: Missing, stale and out-of-sync Cargo and npm lockfiles
+//! - [`pep440`]: Python versions and version specifiers
+//! - [`pins`]: Exact pins for loose Cargo and npm constraints (`deps pin-suggestions`)
+//! - [`system`]: Memory and disk snapshots (full monitoring planned)
+//! - [`packages`]: Outdated developer tools from Homebrew, apt and winget
+//! - [`binaries`]: Developer tools on `PATH` more than once with different versions
+//! - [`clock`]: Clock synchronization and offset from a reference server
+//! - [`certs`]: Missing and broken CA certificate bundles
+//! - [`proxy`]: Proxy settings per tool and TLS interception
+//! - [`exclusions`]: Build directories scanned by Windows Defender or indexed by Spotlight
+//! - [`power`]: Power source, battery level, power mode and CPU speed limits
+//! - [`limits`]: Inode usage and the open-file limit
+//! - [`locale`]: UTF-8 locale and console code page
+//! - [`gitconfig`]: Recommended settings in the global git configuration
+//! - [`analytics`]: Deep lint, format and build checks (`--deep`)
+//! - [`auto`]: Scanners picked from what a path holds (`--auto`)
 
+pub mod alignment;
 pub mod analytics;
+pub mod audit;
+pub mod auto;
+pub mod binaries;
+pub mod buildfiles;
+pub mod cargo;
+pub mod certs;
+pub mod clock;
+pub mod container;
 pub mod deps;
+pub mod exclusions;
+pub mod exposure;
+pub mod forge;
 pub mod git;
+pub mod gitconfig;
+pub mod gomod;
+pub mod impact;
+pub mod licenses;
+pub mod limits;
+pub mod locale;
+pub mod lockfiles;
+pub mod lostwork;
+pub mod packages;
+pub mod pep440;
+pub mod permissions;
+pub mod pins;
+pub mod power;
+pub mod proxy;
+pub mod release;
+pub mod runtime;
+pub mod sizes;
 pub mod system;
+pub mod workspace;
@@ -0,0 +1,380 @@
+//! Outdated developer tools from the system package manager
+//!
+//! `scan --system` asks the package managers present on the machine which
+//! installed packages have updates:
+//!
+//! - **Homebrew** (macOS and Linux): `brew outdated --json=v2`, formulae and
+//!   casks alike
+//! - **apt** (Debian and Ubuntu): `apt list --upgradable`, filtered to
+//!   compilers, runtimes, build tools and version control
+//! - **winget** (Windows): `winget upgrade`, which queries its sources and is
+//!   skipped in offline mode
+//!
+//! None of them refreshes its package index, so results are as fresh as the
+//! last `brew update` or `apt update`. Each report carries the commands that
+//! install the updates.
+
+use crate::scanner::container;
+use crate::utils::net::NetworkGate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Name of the winget check as recorded by `--offline`
+pub const WINGET_CHECK: &str = "winget upgrade";
+
+/// apt packages counted as developer tools: exact names and name prefixes
+/// (ending in `-`)
+const APT_DEV_PACKAGES: &[&str] = &[
+    "git",
+    "git-",
+    "gcc",
+    "gcc-",
+    "g++",
+    "g++-",
+    "clang",
+    "clang-",
+    "llvm",
+    "llvm-",
+    "lld",
+    "gdb",
+    "make",
+    "cmake",
+    "ninja-build",
+    "meson",
+    "build-essential",
+    "pkg-config",
+    "autoconf",
+    "automake",
+    "libtool",
+    "python3",
+    "python3-",
+    "python-is-python3",
+    "nodejs",
+    "npm",
+    "golang",
+    "golang-",
+    "rustc",
+    "cargo",
+    "openjdk-",
+    "maven",
+    "gradle",
+    "ruby",
+    "ruby-",
+    "php",
+    "php-",
+    "docker",
+    "docker-",
+    "docker.io",
+    "containerd",
+    "podman",
+    "curl",
+    "openssh-client",
+    "openssl",
+    "sqlite3",
+    "shellcheck",
+    "jq",
+];
+
+/// A system package manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    /// Homebrew on macOS and Linux
+    Homebrew,
+    /// apt on Debian-based Linux distributions
+    Apt,
+    /// The Windows Package Manager
+    Winget,
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PackageManager::Homebrew => "brew",
+            PackageManager::Apt => "apt",
+            PackageManager::Winget => "winget",
+        })
+    }
+}
+
+/// An installed package with a newer version available
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutdatedTool {
+    /// Package name, or the winget package ID
+    pub name: String,
+    /// Installed version
+    pub installed: String,
+    /// Version the package manager would install
+    pub available: String,
+}
+
+/// The outdated packages of one package manager
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageReport {
+    /// The package manager that was asked
+    pub manager: PackageManager,
+    /// Outdated developer tools
+    pub outdated: Vec<OutdatedTool>,
+    /// Commands installing the updates
+    pub update_commands: Vec<String>,
+}
+
+impl PackageReport {
+    /// Creates a report, deriving the update commands from the tools
+    pub fn new(manager: PackageManager, outdated: Vec<OutdatedTool>) -> PackageReport {
+        let names: Vec<&str> = outdated.iter().map(|tool| tool.name.as_str()).collect();
+        let update_commands = match manager {
+            _ if names.is_empty() => Vec::new(),
+            PackageManager::Homebrew => vec![format!("brew upgrade {}", names.join(" "))],
+            PackageManager::Apt => vec![format!(
+                "sudo apt install --only-upgrade {}",
+                names.join(" ")
+            )],
+            PackageManager::Winget => names
+                .iter()
+                .map(|id| format!("winget upgrade --id {} --exact", id))
+                .collect(),
+        };
+        PackageReport {
+            manager,
+            outdated,
+            update_commands,
+        }
+    }
+}
+
+/// Asks every available package manager for outdated developer tools
+///
+/// Package managers that are not installed, or whose output cannot be read,
+/// are left out. winget is only asked when `network` allows it.
+pub fn check_outdated(network: &mut NetworkGate) -> Vec<PackageReport> {
+    let mut reports = Vec::new();
+
+    if cfg!(any(target_os = "macos", target_os = "linux")) {
+        if let Some(outdated) = container::run_command("brew", &["outdated", "--json=v2"])
+            .and_then(|output| parse_brew_outdated(&output))
+        {
+            reports.push(PackageReport::new(PackageManager::Homebrew, outdated));
+        }
+    }
+
+    if cfg!(target_os = "linux") {
+        if let Some(output) = container::run_command("apt", &["list", "--upgradable"]) {
+            reports.push(PackageReport::new(
+                PackageManager::Apt,
+                parse_apt_upgradable(&output),
+            ));
+        }
+    }
+
+    if cfg!(windows) && network.allow(WINGET_CHECK) {
+        let args = [
+            "upgrade",
+            "--accept-source-agreements",
+            "--disable-interactivity",
+        ];
+        if let Some(outdated) =
+            container::run_command("winget", &args).and_then(|output| parse_winget_upgrade(&output))
+        {
+            reports.push(PackageReport::new(PackageManager::Winget, outdated));
+        }
+    }
+
+    reports
+}
+
+/// Parses `brew outdated --json=v2`
+///
+/// Returns `None` if the output is not Homebrew's JSON.
+fn parse_brew_outdated(output: &str) -> Option<Vec<OutdatedTool>> {
+    #[derive(Deserialize)]
+    struct Outdated {
+        #[serde(default)]
+        formulae: Vec<Package>,
+        #[serde(default)]
+        casks: Vec<Package>,
+    }
+    #[derive(Deserialize)]
+    struct Package {
+        name: String,
+        #[serde(default)]
+        installed_versions: Vec<String>,
+        current_version: String,
+    }
+
+    let outdated: Outdated = serde_json::from_str(output).ok()?;
+    Some(
+        outdated
+            .formulae
+            .into_iter()
+            .chain(outdated.casks)
+            .map(|package| OutdatedTool {
+                installed: package
+                    .installed_versions
+                    .last()
+                    .cloned()
+                    .unwrap_or_default(),
+                name: package.name,
+                available: package.current_version,
+            })
+            .collect(),
+    )
+}
+
+/// Parses `apt list --upgradable`, keeping developer tools only
+///
+/// Lines read `git/jammy-updates 1:2.34.1-1ubuntu1.11 amd64 [upgradable from: 1:2.34.1-1ubuntu1.10]`.
+fn parse_apt_upgradable(output: &str) -> Vec<OutdatedTool> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, rest) = line.split_once('/')?;
+            let available = rest.split_whitespace().nth(1)?;
+            let installed = rest
+                .split("upgradable from:")
+                .nth(1)?
+                .trim()
+                .trim_end_matches(']');
+            Some(OutdatedTool {
+                name: name.to_string(),
+                installed: installed.to_string(),
+                available: available.to_string(),
+            })
+        })
+        .filter(|tool| is_dev_package(&tool.name))
+        .collect()
+}
+
+/// Returns `true` if an apt package is a developer tool
+fn is_dev_package(name: &str) -> bool {
+    APT_DEV_PACKAGES
+        .iter()
+        .any(|package| match package.strip_suffix('-') {
+            Some(_) => name.starts_with(package),
+            None => name == *package,
+        })
+}
+
+/// Parses the table printed by `winget upgrade`
+///
+/// Columns are found by their header (`Name`, `Id`, `Version`, `Available`),
+/// whose labels winget localizes, so the header is recognized by the dashed
+/// line below it. Returns `None` if no table was printed.
+fn parse_winget_upgrade(output: &str) -> Option<Vec<OutdatedTool>> {
+    let lines: Vec<&str> = output
+        .lines()
+        .map(|line| line.rsplit('\r').next().unwrap_or(line))
+        .collect();
+    let divider = lines
+        .iter()
+        .position(|line| line.len() > 10 && line.trim().chars().all(|c| c == '-'))?;
+    let header: Vec<char> = lines.get(divider.checked_sub(1)?)?.chars().collect();
+
+    // Column starts: every non-space character preceded by a space
+    let starts: Vec<usize> = (0..header.len())
+        .filter(|&i| header[i] != ' ' && (i == 0 || header[i - 1] == ' '))
+        .collect();
+    if starts.len() < 4 {
+        return None;
+    }
+
+    let column = |row: &[char], index: usize| -> String {
+        let start = starts[index];
+        let end = starts
+            .get(index + 1)
+            .copied()
+            .unwrap_or(row.len())
+            .min(row.len());
+        row.get(start..end)
+            .map(|cells| cells.iter().collect::<String>().trim().to_string())
+            .unwrap_or_default()
+    };
+    Some(
+        lines[divider + 1..]
+            .iter()
+            .map(|line| line.chars().collect::<Vec<char>>())
+            .filter(|row| row.len() >= starts[3])
+            .map(|row| OutdatedTool {
+                name: column(&row, 1),
+                installed: column(&row, 2),
+                available: column(&row, 3),
+            })
+            .filter(|tool| !tool.name.is_empty() && !tool.available.is_empty())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_brew_json() {
+        let output = r#"{"formulae": [{"name": "git", "installed_versions": ["2.43.0"], "current_version": "2.44.0", "pinned": false}],
+            "casks": [{"name": "docker", "installed_versions": ["4.27.0"], "current_version": "4.28.0"}]}"#;
+        let tools = parse_brew_outdated(output).unwrap();
+        assert_eq!(
+            tools[0],
+            OutdatedTool {
+                name: "git".to_string(),
+                installed: "2.43.0".to_string(),
+                available: "2.44.0".to_string(),
+            }
+        );
+        assert_eq!(tools[1].name, "docker");
+        assert!(parse_brew_outdated("Error: not json").is_none());
+    }
+
+    #[test]
+    fn keeps_apt_developer_tools_only() {
+        let output = "Listing...\n\
+            git/jammy-updates 1:2.34.1-1ubuntu1.11 amd64 [upgradable from: 1:2.34.1-1ubuntu1.10]\n\
+            libreoffice-core/jammy-updates 1:7.3.7 amd64 [upgradable from: 1:7.3.6]\n\
+            python3-pip/jammy-updates 22.0.2+dfsg-1ubuntu0.4 all [upgradable from: 22.0.2+dfsg-1ubuntu0.3]\n";
+        let tools = parse_apt_upgradable(output);
+        let names: Vec<&str> = tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, vec!["git", "python3-pip"]);
+        assert_eq!(tools[0].installed, "1:2.34.1-1ubuntu1.10");
+        assert_eq!(tools[0].available, "1:2.34.1-1ubuntu1.11");
+        assert!(!is_dev_package("gitk-extra") && is_dev_package("gcc-12"));
+    }
+
+    #[test]
+    fn parses_winget_table() {
+        let output = "\r   - \r\
+            Name               Id                  Version   Available Source\n\
+            -------------------------------------------------------------------\n\
+            Git                Git.Git             2.43.0    2.44.0    winget\n\
+            Node.js LTS        OpenJS.NodeJS.LTS   20.11.0   20.11.1   winget\n\
+            2 upgrades available.\n";
+        let tools = parse_winget_upgrade(output).unwrap();
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[1].name, "OpenJS.NodeJS.LTS");
+        assert_eq!(
+            (tools[1].installed.as_str(), tools[1].available.as_str()),
+            ("20.11.0", "20.11.1")
+        );
+        assert!(
+            parse_winget_upgrade("No installed package found matching input criteria.").is_none()
+        );
+    }
+
+    #[test]
+    fn derives_update_commands() {
+        let tool = |name: &str| OutdatedTool {
+            name: name.to_string(),
+            installed: "1".to_string(),
+            available: "2".to_string(),
+        };
+        let brew = PackageReport::new(PackageManager::Homebrew, vec![tool("git"), tool("node")]);
+        assert_eq!(brew.update_commands, vec!["brew upgrade git node"]);
+        let winget = PackageReport::new(
+            PackageManager::Winget,
+            vec![tool("Git.Git"), tool("Rustlang.Rustup")],
+        );
+        assert_eq!(winget.update_commands.len(), 2);
+        assert!(PackageReport::new(PackageManager::Apt, Vec::new())
+            .update_commands
+            .is_empty());
+    }
+}
@@ -0,0 +1,530 @@
+//! Python versions and version specifiers (PEP 440)
+//!
+//! Python packages do not follow semver: versions such as `2.0rc1`,
+//! `1.0.post2`, `2024.1` or `1!2.0` have an ordering of their own, and
+//! requirements combine several clauses such as `>=2.28,<3` or `~=1.24.0`.
+//! This module parses both so that the dependency scanner keeps Python
+//! constraints as written and the audit compares PyPI versions the way pip
+//! does.
+//!
+//! Local version labels (`+ubuntu1`) are accepted but ignored when
+//! comparing.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Errors that can occur while parsing versions and specifiers
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Pep440Error {
+    #[error("Invalid Python version: {0}")]
+    InvalidVersion(String),
+    #[error("Invalid Python version specifier: {0}")]
+    InvalidSpecifier(String),
+}
+
+/// Pre-release phases, in ascending order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PreRelease {
+    /// Alpha release (`a`, `alpha`)
+    Alpha,
+    /// Beta release (`b`, `beta`)
+    Beta,
+    /// Release candidate (`rc`, `c`, `pre`, `preview`)
+    Candidate,
+}
+
+/// A parsed PEP 440 version
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::pep440::Version;
+///
+/// let rc: Version = "2.0rc1".parse().unwrap();
+/// let release: Version = "2.0".parse().unwrap();
+/// let post: Version = "2.0.post1".parse().unwrap();
+/// assert!(rc < release && release < post);
+/// assert_eq!("1.24".parse::<Version>().unwrap(), "1.24.0".parse().unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Version {
+    /// Version epoch (`1!`), 0 when absent
+    pub epoch: u64,
+    /// Release segments (`1.24.0`)
+    pub release: Vec<u64>,
+    /// Pre-release phase and number
+    pub pre: Option<(PreRelease, u64)>,
+    /// Post-release number
+    pub post: Option<u64>,
+    /// Development release number
+    pub dev: Option<u64>,
+}
+
+impl Version {
+    /// Returns `true` for pre-releases and development releases
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    /// Returns the release segments without trailing zeros
+    fn trimmed_release(&self) -> &[u64] {
+        let end = self
+            .release
+            .iter()
+            .rposition(|&n| n != 0)
+            .map_or(1, |i| i + 1);
+        &self.release[..end.min(self.release.len())]
+    }
+
+    /// Returns the key versions are ordered by
+    ///
+    /// A development release without a pre-release sorts before the
+    /// pre-releases of its version, a release without post-release before
+    /// its post-releases, and a development release before the release it
+    /// leads to.
+    #[allow(clippy::type_complexity)]
+    fn key(&self) -> (u64, &[u64], (i8, u64), Option<u64>, (bool, u64)) {
+        let pre = match (self.pre, self.post, self.dev) {
+            (Some((phase, n)), _, _) => (phase as i8, n),
+            (None, None, Some(_)) => (-1, 0),
+            (None, _, _) => (i8::MAX, 0),
+        };
+        let dev = (self.dev.is_none(), self.dev.unwrap_or(0));
+        (self.epoch, self.trimmed_release(), pre, self.post, dev)
+    }
+}
+
+impl FromStr for Version {
+    type Err = Pep440Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || Pep440Error::InvalidVersion(text.to_string());
+        let lower = text.trim().to_lowercase();
+        let mut rest = lower.strip_prefix('v').unwrap_or(&lower);
+        rest = rest.split('+').next().unwrap_or(rest);
+
+        let epoch = match rest.split_once('!') {
+            Some((epoch, after)) => {
+                rest = after;
+                epoch.parse().map_err(|_| invalid())?
+            }
+            None => 0,
+        };
+
+        let release_end = rest
+            .char_indices()
+            .find(|&(i, c)| {
+                !(c.is_ascii_digit()
+                    || (c == '.' && rest[i + 1..].starts_with(|n: char| n.is_ascii_digit())))
+            })
+            .map_or(rest.len(), |(i, _)| i);
+        let release = rest[..release_end]
+            .split('.')
+            .map(|segment| segment.parse().map_err(|_| invalid()))
+            .collect::<Result<Vec<u64>, _>>()?;
+        rest = &rest[release_end..];
+
+        let mut version = Version {
+            epoch,
+            release,
+            pre: None,
+            post: None,
+            dev: None,
+        };
+
+        for (labels, slot) in [
+            (
+                &["alpha", "a", "beta", "b", "preview", "pre", "rc", "c"][..],
+                0,
+            ),
+            (&["post", "rev", "r"][..], 1),
+            (&["dev"][..], 2),
+        ] {
+            let trimmed = rest.trim_start_matches(['.', '-', '_']);
+            let Some(label) = labels.iter().find(|label| trimmed.starts_with(*label)) else {
+                // `1.0-1` is an implicit post-release
+                if slot == 1
+                    && rest.starts_with('-')
+                    && trimmed.starts_with(|c: char| c.is_ascii_digit())
+                {
+                    let (number, after) = leading_number(trimmed);
+                    version.post = Some(number);
+                    rest = after;
+                }
+                continue;
+            };
+            let (number, after) =
+                leading_number(trimmed[label.len()..].trim_start_matches(['.', '-', '_']));
+            rest = after;
+            match slot {
+                0 => {
+                    let phase = match *label {
+                        "alpha" | "a" => PreRelease::Alpha,
+                        "beta" | "b" => PreRelease::Beta,
+                        _ => PreRelease::Candidate,
+                    };
+                    version.pre = Some((phase, number));
+                }
+                1 => version.post = Some(number),
+                _ => version.dev = Some(number),
+            }
+        }
+
+        if rest.is_empty() {
+            Ok(version)
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+/// Splits a leading number off `text`, 0 when there is none
+fn leading_number(text: &str) -> (u64, &str) {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    (text[..end].parse().unwrap_or(0), &text[end..])
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+        let release: Vec<String> = self.release.iter().map(u64::to_string).collect();
+        write!(f, "{}", release.join("."))?;
+        if let Some((phase, n)) = self.pre {
+            let label = match phase {
+                PreRelease::Alpha => "a",
+                PreRelease::Beta => "b",
+                PreRelease::Candidate => "rc",
+            };
+            write!(f, "{}{}", label, n)?;
+        }
+        if let Some(n) = self.post {
+            write!(f, ".post{}", n)?;
+        }
+        if let Some(n) = self.dev {
+            write!(f, ".dev{}", n)?;
+        }
+        Ok(())
+    }
+}
+
+/// Comparison operators of a version specifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// `~=`, compatible release
+    Compatible,
+    /// `==`, also with a trailing `.*`
+    Equal,
+    /// `!=`, also with a trailing `.*`
+    NotEqual,
+    /// `<=`
+    LessEqual,
+    /// `>=`
+    GreaterEqual,
+    /// `<`
+    Less,
+    /// `>`
+    Greater,
+    /// `===`, exact string match
+    Arbitrary,
+}
+
+impl Operator {
+    /// Returns the operator as written in a specifier
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Compatible => "~=",
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+            Operator::LessEqual => "<=",
+            Operator::GreaterEqual => ">=",
+            Operator::Less => "<",
+            Operator::Greater => ">",
+            Operator::Arbitrary => "===",
+        }
+    }
+}
+
+/// One clause of a version specifier, such as `>=2.28`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Specifier {
+    /// Comparison operator
+    pub operator: Operator,
+    /// Version as written, without a trailing `.*`
+    pub version: String,
+    /// `true` for `==1.2.*` and `!=1.2.*` prefix matches
+    pub wildcard: bool,
+}
+
+impl Specifier {
+    /// Returns `true` if `version` satisfies this clause
+    ///
+    /// Exclusive comparisons follow PEP 440: `<2.0` does not admit `2.0rc1`
+    /// and `>1.0` does not admit `1.0.post1`.
+    pub fn contains(&self, version: &Version) -> bool {
+        if self.operator == Operator::Arbitrary {
+            return version.to_string() == self.version;
+        }
+        let Ok(bound) = self.version.parse::<Version>() else {
+            return false;
+        };
+        let same_release =
+            || version.epoch == bound.epoch && version.trimmed_release() == bound.trimmed_release();
+        match self.operator {
+            Operator::Equal if self.wildcard => {
+                prefix_matches(version, &bound.release, bound.epoch)
+            }
+            Operator::NotEqual if self.wildcard => {
+                !prefix_matches(version, &bound.release, bound.epoch)
+            }
+            Operator::Equal => *version == bound,
+            Operator::NotEqual => *version != bound,
+            Operator::LessEqual => *version <= bound,
+            Operator::GreaterEqual => *version >= bound,
+            Operator::Less => {
+                *version < bound
+                    && !(version.is_prerelease() && !bound.is_prerelease() && same_release())
+            }
+            Operator::Greater => {
+                *version > bound
+                    && !(version.post.is_some() && bound.post.is_none() && same_release())
+            }
+            Operator::Compatible => {
+                let prefix = &bound.release[..bound.release.len().saturating_sub(1).max(1)];
+                *version >= bound && prefix_matches(version, prefix, bound.epoch)
+            }
+            Operator::Arbitrary => unreachable!(),
+        }
+    }
+}
+
+/// Returns `true` if the release of `version` starts with `prefix`
+fn prefix_matches(version: &Version, prefix: &[u64], epoch: u64) -> bool {
+    version.epoch == epoch
+        && prefix
+            .iter()
+            .enumerate()
+            .all(|(i, segment)| version.release.get(i).copied().unwrap_or(0) == *segment)
+}
+
+impl fmt::Display for Specifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.operator.as_str(), self.version)?;
+        if self.wildcard {
+            write!(f, ".*")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Specifier {
+    type Err = Pep440Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || Pep440Error::InvalidSpecifier(text.to_string());
+        let text = text.trim();
+        let operators = [
+            ("===", Operator::Arbitrary),
+            ("~=", Operator::Compatible),
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("<=", Operator::LessEqual),
+            (">=", Operator::GreaterEqual),
+            ("<", Operator::Less),
+            (">", Operator::Greater),
+        ];
+        // A bare version is an exact pin, as pip accepts in some files
+        let (operator, version) = operators
+            .iter()
+            .find_map(|(symbol, operator)| {
+                text.strip_prefix(symbol)
+                    .map(|rest| (*operator, rest.trim()))
+            })
+            .unwrap_or((Operator::Equal, text));
+        if version.is_empty() {
+            return Err(invalid());
+        }
+
+        let (version, wildcard) = match version.strip_suffix(".*") {
+            Some(prefix) if matches!(operator, Operator::Equal | Operator::NotEqual) => {
+                (prefix, true)
+            }
+            Some(_) => return Err(invalid()),
+            None => (version, false),
+        };
+        if operator != Operator::Arbitrary {
+            let parsed: Version = version.parse().map_err(|_| invalid())?;
+            if operator == Operator::Compatible && parsed.release.len() < 2 {
+                return Err(invalid());
+            }
+        }
+        Ok(Specifier {
+            operator,
+            version: version.to_string(),
+            wildcard,
+        })
+    }
+}
+
+/// A comma-separated set of specifier clauses, such as `>=2.28,<3`
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::pep440::SpecifierSet;
+///
+/// let specifiers: SpecifierSet = ">= 2.28, < 3".parse().unwrap();
+/// assert_eq!(specifiers.to_string(), ">=2.28,<3");
+/// assert!(specifiers.contains(&"2.31.0".parse().unwrap()));
+/// assert!(!specifiers.contains(&"3.0".parse().unwrap()));
+/// assert_eq!(specifiers.lowest_version().unwrap().to_string(), "2.28");
+///
+/// let compatible: SpecifierSet = "~=1.24.0".parse().unwrap();
+/// assert!(compatible.contains(&"1.24.3".parse().unwrap()));
+/// assert!(!compatible.contains(&"1.25.0".parse().unwrap()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecifierSet {
+    /// The clauses, all of which must hold
+    pub specifiers: Vec<Specifier>,
+}
+
+impl SpecifierSet {
+    /// Returns `true` if `version` satisfies every clause
+    pub fn contains(&self, version: &Version) -> bool {
+        self.specifiers
+            .iter()
+            .all(|specifier| specifier.contains(version))
+    }
+
+    /// Returns the lowest version the set names that it also admits
+    ///
+    /// This is the version of an exact pin, or the lower bound of `>=` and
+    /// `~=` constraints. Returns `None` if the set has no lower bound, as in
+    /// `<3` or `!=1.5`, or if it excludes its own lower bound.
+    pub fn lowest_version(&self) -> Option<Version> {
+        self.specifiers
+            .iter()
+            .filter(|specifier| {
+                !specifier.wildcard
+                    && matches!(
+                        specifier.operator,
+                        Operator::Equal
+                            | Operator::GreaterEqual
+                            | Operator::Compatible
+                            | Operator::Arbitrary
+                    )
+            })
+            .filter_map(|specifier| specifier.version.parse::<Version>().ok())
+            .max()
+            .filter(|version| self.contains(version))
+    }
+}
+
+impl FromStr for SpecifierSet {
+    type Err = Pep440Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let specifiers = text
+            .split(',')
+            .map(str::trim)
+            .filter(|clause| !clause.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<Specifier>, _>>()?;
+        Ok(SpecifierSet { specifiers })
+    }
+}
+
+impl fmt::Display for SpecifierSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let clauses: Vec<String> = self.specifiers.iter().map(Specifier::to_string).collect();
+        write!(f, "{}", clauses.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(text: &str) -> Version {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn orders_versions_like_pip() {
+        let ordered = [
+            "1.0.dev1",
+            "1.0a1",
+            "1.0a2.dev1",
+            "1.0a2",
+            "1.0b1",
+            "1.0rc1",
+            "1.0",
+            "1.0.post1.dev1",
+            "1.0.post1",
+            "1.1",
+            "2024.1",
+            "1!0.1",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(
+                version(pair[0]) < version(pair[1]),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert_eq!(version("1.0-1"), version("1.0.post1"));
+        assert_eq!(version("v1.0.0-RC.1"), version("1.0rc1"));
+        assert_eq!(version("2.0+ubuntu1"), version("2.0"));
+        assert_eq!(version("1.0alpha"), version("1.0a0"));
+        assert!("1.0-beta-final".parse::<Version>().is_err());
+        assert!("latest".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn matches_specifiers() {
+        let set = |text: &str| text.parse::<SpecifierSet>().unwrap();
+        assert!(set("==1.2.*").contains(&version("1.2.9")));
+        assert!(!set("==1.2.*").contains(&version("1.3")));
+        assert!(set("!=1.5").contains(&version("1.6")));
+        assert!(!set("<2.0").contains(&version("2.0rc1")));
+        assert!(!set(">1.0").contains(&version("1.0.post1")));
+        assert!(set(">1.0").contains(&version("1.1")));
+        assert!(set("~=2.2").contains(&version("2.9")));
+        assert!(!set("~=2.2").contains(&version("3.0")));
+        assert!("~=2".parse::<SpecifierSet>().is_err());
+        assert!(">=1.*".parse::<SpecifierSet>().is_err());
+
+        assert_eq!(set("2.28.0").to_string(), "==2.28.0");
+        assert_eq!(set(">=1.0,!=1.5,<2").lowest_version(), Some(version("1.0")));
+        assert_eq!(set(">=1.5,!=1.5").lowest_version(), None);
+        assert_eq!(set("<3").lowest_version(), None);
+        assert_eq!(set("").lowest_version(), None);
+    }
+}
@@ -0,0 +1,779 @@
+//! Pin suggestions for loose dependency constraints (`deps pin-suggestions`)
+//!
+//! A constraint is loose when it has no upper bound or names nothing but a
+//! major version: `*`, `>=1.2`, `1`, `^1`, `1.x`, or an npm tag such as
+//! `latest`. Any version published under it may be picked up on the next
+//! resolve. For each loose constraint in a `Cargo.toml` or `package.json`,
+//! the version the lockfile resolved it to is suggested as an exact pin:
+//! `=1.0.197` for Cargo, `4.17.21` for npm.
+//!
+//! Path, git and workspace dependencies and npm peer dependencies, which are
+//! meant to be ranges, are left alone. A loose constraint the lockfile does
+//! not resolve, because there is no `Cargo.lock` or `package-lock.json` or
+//! the package is missing from it, is listed without a pin.
+//!
+//! [`patch`] turns the suggestions into a unified diff that `git apply` or
+//! `patch -p1` applies from the scanned directory.
+
+use crate::i18n::t;
+use crate::scanner::cargo;
+use crate::scanner::deps::{DependencyReport, Ecosystem};
+use crate::scanner::exposure::normalize_package_name;
+use crate::tr;
+use crate::utils::display;
+use crate::utils::theme::{Paint, Role};
+use semver::{Op, Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Cargo manifest tables holding dependencies
+const CARGO_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// `package.json` objects holding dependencies that are worth pinning
+const NPM_SECTIONS: [&str; 3] = ["dependencies", "devDependencies", "optionalDependencies"];
+
+/// Lines of context around each change in a patch
+const CONTEXT_LINES: usize = 3;
+
+/// A loose constraint and the exact version to pin it to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinSuggestion {
+    /// The `Cargo.toml` or `package.json` declaring the dependency
+    pub manifest: PathBuf,
+    /// Ecosystem of the manifest
+    pub ecosystem: Ecosystem,
+    /// Table or object the dependency is declared in, e.g. `dev-dependencies`
+    pub section: String,
+    /// Name of the dependency as declared
+    pub name: String,
+    /// The loose constraint
+    pub constraint: String,
+    /// Version the lockfile resolved the constraint to, `None` if not locked
+    pub resolved: Option<String>,
+    /// The exact constraint to use instead, `None` if not locked
+    pub pin: Option<String>,
+}
+
+/// Finds the loose constraints of every Cargo and npm project
+///
+/// Manifests under `node_modules` are skipped. Suggestions are ordered by
+/// manifest, section and name.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::{deps, pins};
+/// use std::path::Path;
+///
+/// let reports = deps::scan_dependencies(Path::new(".")).unwrap();
+/// for suggestion in pins::suggest_pins(&reports) {
+///     println!("{} {} -> {:?}", suggestion.name, suggestion.constraint, suggestion.pin);
+/// }
+/// ```
+pub fn suggest_pins(reports: &[DependencyReport]) -> Vec<PinSuggestion> {
+    let manifests: BTreeSet<&Path> = reports
+        .iter()
+        .flat_map(|report| &report.dependencies)
+        .map(|dependency| dependency.source_file.as_path())
+        .filter(|manifest| {
+            !manifest
+                .components()
+                .any(|part| part.as_os_str() == "node_modules")
+        })
+        .collect();
+    manifests
+        .into_iter()
+        .flat_map(
+            |manifest| match manifest.file_name().and_then(|name| name.to_str()) {
+                Some("Cargo.toml") => cargo_suggestions(manifest),
+                Some("package.json") => npm_suggestions(manifest),
+                _ => Vec::new(),
+            },
+        )
+        .collect()
+}
+
+/// Returns the suggestions for a `Cargo.toml`
+fn cargo_suggestions(manifest: &Path) -> Vec<PinSuggestion> {
+    let Some(content) = fs::read_to_string(manifest).ok() else {
+        return Vec::new();
+    };
+    let Ok(document) = toml::from_str::<toml::Value>(&content) else {
+        return Vec::new();
+    };
+    let locked = manifest
+        .parent()
+        .and_then(cargo::find_lockfile)
+        .and_then(|lockfile| fs::read_to_string(lockfile).ok())
+        .map(|lock| cargo_locked_versions(&lock))
+        .unwrap_or_default();
+
+    let mut suggestions = Vec::new();
+    for dependency in cargo_registry_dependencies(&document) {
+        let Some(requirement) = loose_cargo_requirement(&dependency.constraint) else {
+            continue;
+        };
+        let resolved = locked
+            .get(&dependency.package)
+            .into_iter()
+            .flatten()
+            .filter(|version| requirement.matches(version))
+            .max()
+            .map(Version::to_string);
+        suggestions.push(PinSuggestion {
+            manifest: manifest.to_path_buf(),
+            ecosystem: Ecosystem::Rust,
+            section: dependency.section.to_string(),
+            name: dependency.name,
+            constraint: dependency.constraint,
+            pin: resolved.as_ref().map(|version| format!("={}", version)),
+            resolved,
+        });
+    }
+    suggestions
+}
+
+/// A dependency of a `Cargo.toml` resolved from a registry
+pub(crate) struct RegistryDependency {
+    /// Table it is declared in, e.g. `dev-dependencies`
+    pub section: &'static str,
+    /// Name it is declared under
+    pub name: String,
+    /// Package name in the registry, which differs from `name` for renamed
+    /// dependencies
+    pub package: String,
+    /// Version requirement, `*` if none is given
+    pub constraint: String,
+}
+
+/// Returns the registry dependencies of a parsed `Cargo.toml`, skipping
+/// path, git and workspace-inherited dependencies
+pub(crate) fn cargo_registry_dependencies(document: &toml::Value) -> Vec<RegistryDependency> {
+    let mut dependencies = Vec::new();
+    for section in CARGO_SECTIONS {
+        let Some(table) = document.get(section).and_then(toml::Value::as_table) else {
+            continue;
+        };
+        for (name, value) in table {
+            let (constraint, package) = match value {
+                toml::Value::String(constraint) => (constraint.as_str(), name.as_str()),
+                toml::Value::Table(entry) => {
+                    if ["path", "git", "workspace"]
+                        .iter()
+                        .any(|key| entry.contains_key(*key))
+                    {
+                        continue;
+                    }
+                    let constraint = entry
+                        .get("version")
+                        .and_then(toml::Value::as_str)
+                        .unwrap_or("*");
+                    let package = entry
+                        .get("package")
+                        .and_then(toml::Value::as_str)
+                        .unwrap_or(name);
+                    (constraint, package)
+                }
+                _ => continue,
+            };
+            dependencies.push(RegistryDependency {
+                section,
+                name: name.clone(),
+                package: package.to_string(),
+                constraint: constraint.to_string(),
+            });
+        }
+    }
+    dependencies
+}
+
+/// Returns `true` if a Cargo constraint is loose
+///
+/// Constraints that are not valid version requirements are never loose.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::pins::is_loose_cargo;
+///
+/// assert!(is_loose_cargo("*"));
+/// assert!(is_loose_cargo("1"));
+/// assert!(is_loose_cargo(">=0.4"));
+/// assert!(!is_loose_cargo("1.0.197"));
+/// assert!(!is_loose_cargo(">=1.2, <1.5"));
+/// ```
+pub fn is_loose_cargo(constraint: &str) -> bool {
+    loose_cargo_requirement(constraint).is_some()
+}
+
+/// Returns the parsed requirement if a Cargo constraint is loose
+fn loose_cargo_requirement(constraint: &str) -> Option<VersionReq> {
+    let requirement = VersionReq::parse(constraint).ok()?;
+    let comparators = &requirement.comparators;
+    let unbounded = comparators
+        .iter()
+        .any(|c| matches!(c.op, Op::Greater | Op::GreaterEq))
+        && !comparators
+            .iter()
+            .any(|c| matches!(c.op, Op::Less | Op::LessEq));
+    let major_only = comparators
+        .iter()
+        .any(|c| matches!(c.op, Op::Caret | Op::Tilde | Op::Wildcard) && c.minor.is_none());
+    (comparators.is_empty() || unbounded || major_only).then_some(requirement)
+}
+
+/// Returns every version of every package in a `Cargo.lock`
+pub(crate) fn cargo_locked_versions(lock: &str) -> BTreeMap<String, Vec<Version>> {
+    let mut locked: BTreeMap<String, Vec<Version>> = BTreeMap::new();
+    let Ok(lock) = toml::from_str::<toml::Value>(lock) else {
+        return locked;
+    };
+    for package in lock
+        .get("package")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let name = package.get("name").and_then(toml::Value::as_str);
+        let version = package
+            .get("version")
+            .and_then(toml::Value::as_str)
+            .and_then(|version| Version::parse(version).ok());
+        if let (Some(name), Some(version)) = (name, version) {
+            locked.entry(name.to_string()).or_default().push(version);
+        }
+    }
+    locked
+}
+
+/// Returns the version of every package in a `poetry.lock`, keyed by the
+/// normalized package name
+pub(crate) fn poetry_locked_versions(lock: &str) -> BTreeMap<String, String> {
+    let Ok(lock) = toml::from_str::<toml::Value>(lock) else {
+        return BTreeMap::new();
+    };
+    lock.get("package")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|package| {
+            let name = package.get("name")?.as_str()?;
+            let version = package.get("version")?.as_str()?;
+            Some((normalize_package_name(name), version.to_string()))
+        })
+        .collect()
+}
+
+/// Returns the suggestions for a `package.json`
+fn npm_suggestions(manifest: &Path) -> Vec<PinSuggestion> {
+    let Some(document) = fs::read_to_string(manifest)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    else {
+        return Vec::new();
+    };
+    let lock = manifest.parent().and_then(NpmLock::find);
+
+    let mut suggestions = Vec::new();
+    for section in NPM_SECTIONS {
+        let Some(object) = document.get(section).and_then(serde_json::Value::as_object) else {
+            continue;
+        };
+        for (name, constraint) in object {
+            let Some(constraint) = constraint
+                .as_str()
+                .filter(|constraint| is_loose_npm(constraint))
+            else {
+                continue;
+            };
+            let resolved = lock.as_ref().and_then(|lock| lock.resolved(name));
+            suggestions.push(PinSuggestion {
+                manifest: manifest.to_path_buf(),
+                ecosystem: Ecosystem::NodeJs,
+                section: section.to_string(),
+                name: name.clone(),
+                constraint: constraint.to_string(),
+                pin: resolved.clone(),
+                resolved,
+            });
+        }
+    }
+    suggestions
+}
+
+/// Returns `true` if an npm constraint is loose
+///
+/// Specifiers that do not name a registry version, such as `file:`,
+/// `workspace:`, git URLs or `user/repo`, are never loose.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::pins::is_loose_npm;
+///
+/// assert!(is_loose_npm("latest"));
+/// assert!(is_loose_npm("^4"));
+/// assert!(is_loose_npm("1.x"));
+/// assert!(is_loose_npm(">=16"));
+/// assert!(!is_loose_npm("^4.17.21"));
+/// assert!(!is_loose_npm("file:../shared"));
+/// ```
+pub fn is_loose_npm(constraint: &str) -> bool {
+    let constraint = constraint.trim();
+    if constraint.contains(':') || constraint.contains('/') {
+        return false;
+    }
+    if constraint.is_empty()
+        || constraint.contains("||")
+        || constraint.starts_with(|c: char| c.is_ascii_alphabetic() && c != 'x' && c != 'X')
+    {
+        return true;
+    }
+    if constraint.starts_with(['>', '<']) {
+        return !constraint.contains('<');
+    }
+    let version = constraint.trim_start_matches(['^', '~', '=', 'v', ' ']);
+    let mut parts = version.split('.');
+    let major = parts.next().unwrap_or_default();
+    let minor = parts.next();
+    ["*", "x", "X"].contains(&major) || minor.is_none_or(|minor| ["*", "x", "X"].contains(&minor))
+}
+
+/// A `package-lock.json` and the directory the project sits in relative to it
+pub(crate) struct NpmLock {
+    lock: serde_json::Value,
+    project: String,
+}
+
+impl NpmLock {
+    /// Reads the `package-lock.json` in `dir` or the closest ancestor
+    /// holding one, as for npm workspaces
+    pub(crate) fn find(dir: &Path) -> Option<NpmLock> {
+        dir.ancestors().find_map(|ancestor| {
+            let content = fs::read_to_string(ancestor.join("package-lock.json")).ok()?;
+            let project = dir
+                .strip_prefix(ancestor)
+                .ok()?
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            Some(NpmLock {
+                lock: serde_json::from_str(&content).ok()?,
+                project,
+            })
+        })
+    }
+
+    /// Returns the version installed for the project's dependency `name`
+    ///
+    /// Reads `packages` (lockfile version 2 and later), preferring a copy
+    /// nested in the project over the hoisted one, then `dependencies`
+    /// (version 1).
+    pub(crate) fn resolved(&self, name: &str) -> Option<String> {
+        let version =
+            |entry: &serde_json::Value| entry.get("version")?.as_str().map(str::to_string);
+        let packages = self.lock.get("packages");
+        let nested = (!self.project.is_empty())
+            .then(|| format!("{}/node_modules/{}", self.project, name))
+            .and_then(|key| packages?.get(key))
+            .and_then(version);
+        nested
+            .or_else(|| {
+                packages?
+                    .get(format!("node_modules/{}", name))
+                    .and_then(version)
+            })
+            .or_else(|| self.lock.get("dependencies")?.get(name).and_then(version))
+    }
+}
+
+/// Renders the pinned suggestions as a unified diff
+///
+/// Paths in the diff are relative to `root`, so the patch applies with
+/// `git apply` or `patch -p1` from there. Only the constraint in each line
+/// changes; the formatting of the manifests is kept. Returns an empty string
+/// if nothing can be pinned.
+///
+/// # Errors
+///
+/// Returns an error if a manifest cannot be read.
+pub fn patch(suggestions: &[PinSuggestion], root: &Path) -> std::io::Result<String> {
+    let mut manifests: BTreeMap<&Path, Vec<&PinSuggestion>> = BTreeMap::new();
+    for suggestion in suggestions
+        .iter()
+        .filter(|suggestion| suggestion.pin.is_some())
+    {
+        manifests
+            .entry(&suggestion.manifest)
+            .or_default()
+            .push(suggestion);
+    }
+
+    let mut patch = String::new();
+    for (manifest, suggestions) in manifests {
+        let content = fs::read_to_string(manifest)?;
+        let changes: BTreeMap<usize, String> = suggestions
+            .into_iter()
+            .filter_map(|suggestion| match suggestion.ecosystem {
+                Ecosystem::Rust => rewrite_cargo(&content, suggestion),
+                _ => rewrite_npm(&content, suggestion),
+            })
+            .collect();
+        if changes.is_empty() {
+            continue;
+        }
+        let relative = manifest.strip_prefix(root).unwrap_or(manifest);
+        let label = relative
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        patch.push_str(&unified_diff(&label, &content, &changes));
+    }
+    Ok(patch)
+}
+
+/// Returns the index and new text of the `Cargo.toml` line declaring the
+/// suggestion's dependency
+fn rewrite_cargo(content: &str, suggestion: &PinSuggestion) -> Option<(usize, String)> {
+    let pin = suggestion.pin.as_deref()?;
+    let dotted = format!("{}.{}", suggestion.section, suggestion.name);
+    let mut section = String::new();
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            section = trimmed.trim_matches(['[', ']']).trim().to_string();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().trim_matches(['"', '\'']);
+        let value_start = line.len() - value.len();
+        if section == suggestion.section && key == suggestion.name {
+            let value = value.trim_start();
+            if value.starts_with('{') {
+                let table_start = line.len() - value.len();
+                return Some((
+                    index,
+                    match line[table_start..].find("version") {
+                        Some(version) => replace_quoted(line, table_start + version, pin)?,
+                        None => format!(
+                            "{}{{ version = \"{}\",{}",
+                            &line[..table_start],
+                            pin,
+                            &line[table_start + 1..]
+                        ),
+                    },
+                ));
+            }
+            return Some((index, replace_quoted(line, value_start, pin)?));
+        }
+        if section == dotted && key == "version" {
+            return Some((index, replace_quoted(line, value_start, pin)?));
+        }
+    }
+    None
+}
+
+/// Returns the index and new text of the `package.json` line declaring the
+/// suggestion's dependency
+fn rewrite_npm(content: &str, suggestion: &PinSuggestion) -> Option<(usize, String)> {
+    let pin = suggestion.pin.as_deref()?;
+    let section_key = format!("\"{}\"", suggestion.section);
+    let name_key = format!("\"{}\"", suggestion.name);
+    let mut in_section = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with(&section_key) && trimmed.contains('{') {
+            in_section = true;
+        } else if in_section && trimmed.starts_with('}') {
+            in_section = false;
+        } else if in_section && trimmed.starts_with(&name_key) {
+            let colon = line.find(&name_key)? + name_key.len();
+            let colon = colon + line[colon..].find(':')?;
+            return Some((index, replace_quoted(line, colon, pin)?));
+        }
+    }
+    None
+}
+
+/// Replaces the contents of the first quoted string at or after `from`
+fn replace_quoted(line: &str, from: usize, replacement: &str) -> Option<String> {
+    let open = from + line[from..].find(['"', '\''])?;
+    let quote = line[open..].chars().next()?;
+    let close = open + 1 + line[open + 1..].find(quote)?;
+    Some(format!(
+        "{}{}{}",
+        &line[..=open],
+        replacement,
+        &line[close..]
+    ))
+}
+
+/// Formats changed lines of a file as a unified diff
+fn unified_diff(label: &str, content: &str, changes: &BTreeMap<usize, String>) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let last = lines.len().saturating_sub(1);
+    let missing_newline = !content.ends_with('\n');
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", label, label);
+
+    let indices: Vec<usize> = changes.keys().copied().collect();
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &index in &indices {
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + CONTEXT_LINES).min(last);
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 + 1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        let count = end - start + 1;
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            start + 1,
+            count,
+            start + 1,
+            count
+        ));
+        for (index, line) in lines.iter().enumerate().take(end + 1).skip(start) {
+            let no_newline = if missing_newline && index == last {
+                "\\ No newline at end of file\n"
+            } else {
+                ""
+            };
+            match changes.get(&index) {
+                Some(new) => diff.push_str(&format!(
+                    "-{}\n{}+{}\n{}",
+                    line, no_newline, new, no_newline
+                )),
+                None => diff.push_str(&format!(" {}\n{}", line, no_newline)),
+            }
+        }
+    }
+    diff
+}
+
+/// Prints the suggestions grouped by manifest
+pub fn display_suggestions(suggestions: &[PinSuggestion]) {
+    if suggestions.is_empty() {
+        println!("{}", display::header(t("pins-none"), "📌", Role::Success));
+        return;
+    }
+    println!(
+        "{}",
+        display::header(
+            &tr!("pins-title", count = suggestions.len()),
+            "📌",
+            Role::Accent
+        )
+    );
+
+    let mut manifests: BTreeMap<&Path, Vec<&PinSuggestion>> = BTreeMap::new();
+    for suggestion in suggestions {
+        manifests
+            .entry(&suggestion.manifest)
+            .or_default()
+            .push(suggestion);
+    }
+    let count = manifests.len();
+    for (index, (manifest, suggestions)) in manifests.into_iter().enumerate() {
+        println!(
+            "{}",
+            display::tree_item(
+                &display::file_path(&manifest.display().to_string()),
+                index == count - 1,
+                0
+            )
+        );
+        let width = suggestions.iter().map(|s| s.name.len()).max().unwrap_or(0);
+        for (position, suggestion) in suggestions.iter().enumerate() {
+            let target = match &suggestion.pin {
+                Some(pin) => pin.paint(Role::Success).to_string(),
+                None => t("pins-not-locked").paint(Role::Warning).to_string(),
+            };
+            let line = format!(
+                "{:<width$} {} → {} {}",
+                suggestion.name.paint(Role::Highlight),
+                suggestion.constraint.paint(Role::Warning),
+                target,
+                format!("({})", suggestion.section).paint(Role::Muted),
+                width = width
+            );
+            println!(
+                "{}",
+                display::tree_item(&line, position == suggestions.len() - 1, 1)
+            );
+        }
+    }
+    if suggestions
+        .iter()
+        .any(|suggestion| suggestion.pin.is_some())
+    {
+        println!(
+            "\n{} {}",
+            display::symbols("💡"),
+            t("pins-tip").paint(Role::Muted)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::deps::{Dependency, DependencyType};
+    use tempfile::TempDir;
+
+    fn report(manifest: &Path) -> DependencyReport {
+        DependencyReport {
+            project_path: manifest.parent().unwrap().to_path_buf(),
+            dependencies: vec![Dependency {
+                name: "any".to_string(),
+                version: "*".to_string(),
+                dependency_type: DependencyType::Runtime,
+                ecosystem: Ecosystem::Rust,
+                source_file: manifest.to_path_buf(),
+                extras: Vec::new(),
+                marker: None,
+            }],
+            ecosystems: Vec::new(),
+            errors: Vec::new(),
+            lockfiles: Vec::new(),
+            go_modules: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn detects_loose_constraints() {
+        for loose in ["*", "1", "^0", "~1", "1.*", ">=1.2", "> 0.3"] {
+            assert!(is_loose_cargo(loose), "{} should be loose", loose);
+        }
+        for tight in [
+            "1.2",
+            "^1.2.3",
+            "=1.0.0",
+            "~1.2",
+            ">=1, <2",
+            "not a version",
+        ] {
+            assert!(!is_loose_cargo(tight), "{} should not be loose", tight);
+        }
+        for loose in [
+            "", "*", "latest", "x", "1", "^4", "~2", "4.x", ">=16", "1 || 2",
+        ] {
+            assert!(is_loose_npm(loose), "{} should be loose", loose);
+        }
+        for tight in [
+            "4.17.21",
+            "^4.17",
+            "~1.2.x",
+            ">=1 <2",
+            "workspace:*",
+            "github:user/repo",
+            "user/repo",
+        ] {
+            assert!(!is_loose_npm(tight), "{} should not be loose", tight);
+        }
+    }
+
+    #[test]
+    fn pins_cargo_dependencies_to_the_locked_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &manifest,
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1\"\nlog = { version = \"*\", features = [\"std\"] }\nregex = \"1.10.2\"\nlocal = { path = \"../local\" }\n\n[dev-dependencies.tempfile]\nversion = \">=3\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.lock"),
+            "version = 3\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.197\"\n\n[[package]]\nname = \"serde\"\nversion = \"0.9.15\"\n\n[[package]]\nname = \"log\"\nversion = \"0.4.21\"\n",
+        )
+        .unwrap();
+
+        let suggestions = suggest_pins(&[report(&manifest)]);
+        let pins: Vec<(&str, &str, Option<&str>)> = suggestions
+            .iter()
+            .map(|s| (s.name.as_str(), s.constraint.as_str(), s.pin.as_deref()))
+            .collect();
+        assert_eq!(
+            pins,
+            vec![
+                ("log", "*", Some("=0.4.21")),
+                ("serde", "1", Some("=1.0.197")),
+                ("tempfile", ">=3", None),
+            ]
+        );
+
+        let patch = patch(&suggestions, temp_dir.path()).unwrap();
+        assert!(
+            patch.starts_with("--- a/Cargo.toml\n+++ b/Cargo.toml\n@@ -3,8 +3,8 @@\n"),
+            "{}",
+            patch
+        );
+        assert!(
+            patch.contains("-serde = \"1\"\n+serde = \"=1.0.197\"\n"),
+            "{}",
+            patch
+        );
+        assert!(
+            patch.contains("+log = { version = \"=0.4.21\", features = [\"std\"] }\n"),
+            "{}",
+            patch
+        );
+        assert!(
+            !patch.contains("+version"),
+            "Unlocked dependencies are not patched: {}",
+            patch
+        );
+    }
+
+    #[test]
+    fn pins_npm_dependencies_from_the_lockfile() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("packages").join("web");
+        fs::create_dir_all(&project).unwrap();
+        let manifest = project.join("package.json");
+        fs::write(
+            &manifest,
+            "{\n  \"dependencies\": {\n    \"lodash\": \"latest\",\n    \"react\": \"^18\",\n    \"shared\": \"workspace:*\"\n  },\n  \"peerDependencies\": {\n    \"react-dom\": \"*\"\n  }\n}",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("package-lock.json"),
+            r#"{"lockfileVersion": 3, "packages": {
+                "node_modules/lodash": {"version": "4.17.21"},
+                "node_modules/react": {"version": "17.0.2"},
+                "packages/web/node_modules/react": {"version": "18.2.0"}
+            }}"#,
+        )
+        .unwrap();
+
+        let suggestions = suggest_pins(&[report(&manifest)]);
+        let pins: Vec<(&str, Option<&str>)> = suggestions
+            .iter()
+            .map(|s| (s.name.as_str(), s.pin.as_deref()))
+            .collect();
+        assert_eq!(
+            pins,
+            vec![("lodash", Some("4.17.21")), ("react", Some("18.2.0"))]
+        );
+
+        let patch = patch(&suggestions, temp_dir.path()).unwrap();
+        assert!(
+            patch.starts_with("--- a/packages/web/package.json\n"),
+            "{}",
+            patch
+        );
+        assert!(
+            patch.contains("-    \"react\": \"^18\",\n+    \"react\": \"18.2.0\",\n"),
+            "{}",
+            patch
+        );
+    }
+}
@@ -0,0 +1,381 @@
+//! Power source, power mode and CPU speed limits
+//!
+//! A laptop on battery, in a low-power mode or holding its CPU back to keep
+//! cool builds noticeably slower, and a long scan or build can drain what is
+//! left of the battery. `scan --system` reports:
+//!
+//! - the power source and battery level, on Linux from
+//!   `/sys/class/power_supply`, on macOS from `pmset -g batt` and on Windows
+//!   from `Win32_Battery`
+//! - the OS power mode: the ACPI platform profile or `powerprofilesctl` on
+//!   Linux, Low Power Mode (`pmset -g`) on macOS and the active power plan
+//!   (`powercfg /getactivescheme`) on Windows
+//! - how far the CPU is held below its full speed: the cpufreq limit on
+//!   Linux and `CPU_Speed_Limit` (`pmset -g therm`) on macOS
+//!
+//! Machines without a battery get no report unless their power mode or CPU
+//! limit slows them down. `devhealth bench` warns before measuring on such
+//! a machine, since its results are not comparable with normal runs.
+
+use crate::scanner::container;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Battery level below which a long scan or build may drain the battery
+pub const LOW_BATTERY_PERCENT: u8 = 30;
+
+/// Windows power plan GUIDs and the mode they stand for
+const WINDOWS_SCHEMES: [(&str, PowerMode); 3] = [
+    ("a1841308-3541-4fab-bc81-f71556f20b4a", PowerMode::LowPower),
+    ("381b4222-f694-41f0-9685-ff5bb260df2e", PowerMode::Balanced),
+    (
+        "8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c",
+        PowerMode::Performance,
+    ),
+];
+
+/// Where the machine draws its power from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerSource {
+    /// Plugged in
+    Ac,
+    /// Running on battery
+    Battery,
+}
+
+/// The operating system's power mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerMode {
+    /// Power saver or Low Power Mode, throttling the CPU
+    LowPower,
+    /// The default trade-off
+    Balanced,
+    /// Full speed
+    Performance,
+}
+
+/// Power source, battery and throttling state
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerReport {
+    /// Power source, `None` if unknown
+    pub source: Option<PowerSource>,
+    /// Battery charge in percent, `None` without a battery
+    pub battery_percent: Option<u8>,
+    /// Whether the battery is charging
+    pub charging: Option<bool>,
+    /// Operating system power mode
+    pub mode: Option<PowerMode>,
+    /// CPU speed limit in percent of full speed, below 100 when throttled
+    pub cpu_speed_limit: Option<u8>,
+}
+
+impl PowerReport {
+    /// Returns `true` if the machine has a battery
+    pub fn has_battery(&self) -> bool {
+        self.battery_percent.is_some()
+    }
+
+    /// Returns `true` if the machine runs on battery
+    pub fn on_battery(&self) -> bool {
+        self.source == Some(PowerSource::Battery)
+    }
+
+    /// Returns `true` if a power mode or speed limit holds the CPU back
+    pub fn is_throttled(&self) -> bool {
+        self.mode == Some(PowerMode::LowPower)
+            || self.cpu_speed_limit.is_some_and(|limit| limit < 100)
+    }
+
+    /// Returns `true` if a long scan or build may drain the battery
+    pub fn is_draining(&self) -> bool {
+        self.on_battery()
+            && self
+                .battery_percent
+                .is_some_and(|percent| percent < LOW_BATTERY_PERCENT)
+    }
+
+    /// Returns `true` if builds and benchmarks run slower than usual
+    pub fn slows_builds(&self) -> bool {
+        self.on_battery() || self.is_throttled()
+    }
+}
+
+/// Reads the power source, battery level, power mode and CPU speed limit
+///
+/// Returns `None` on machines without a battery that are not throttled.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::power;
+///
+/// if let Some(report) = power::check_power() {
+///     if report.slows_builds() {
+///         println!("Builds will be slower than usual");
+///     }
+/// }
+/// ```
+pub fn check_power() -> Option<PowerReport> {
+    let report = if cfg!(target_os = "linux") {
+        read_linux(Path::new("/sys"))
+    } else if cfg!(target_os = "macos") {
+        read_macos()
+    } else if cfg!(windows) {
+        read_windows()
+    } else {
+        return None;
+    };
+    (report.has_battery() || report.is_throttled()).then_some(report)
+}
+
+/// Reads the power state from a Linux sysfs tree
+fn read_linux(sys: &Path) -> PowerReport {
+    let mut report = PowerReport::default();
+    let read = |path: &Path| {
+        fs::read_to_string(path)
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
+
+    let supplies = fs::read_dir(sys.join("class/power_supply"))
+        .into_iter()
+        .flatten()
+        .flatten();
+    for supply in supplies.map(|entry| entry.path()) {
+        match read(&supply.join("type")).as_deref() {
+            Some("Battery") if report.battery_percent.is_none() => {
+                report.battery_percent =
+                    read(&supply.join("capacity")).and_then(|value| value.parse().ok());
+                let status = read(&supply.join("status"));
+                report.charging = status.as_deref().map(|status| status == "Charging");
+                if status.as_deref() == Some("Discharging") {
+                    report.source = Some(PowerSource::Battery);
+                }
+            }
+            Some("Mains") if read(&supply.join("online")).as_deref() == Some("1") => {
+                report.source = Some(PowerSource::Ac);
+            }
+            _ => {}
+        }
+    }
+    if report.source.is_none() && report.charging.is_some() {
+        report.source = Some(PowerSource::Ac);
+    }
+
+    report.mode = read(&sys.join("firmware/acpi/platform_profile"))
+        .or_else(|| container::run_command("powerprofilesctl", &["get"]))
+        .and_then(|profile| parse_linux_profile(&profile));
+
+    let cpufreq = sys.join("devices/system/cpu/cpu0/cpufreq");
+    let frequency =
+        |name: &str| read(&cpufreq.join(name)).and_then(|value| value.parse::<u64>().ok());
+    if let (Some(limit), Some(max)) = (frequency("scaling_max_freq"), frequency("cpuinfo_max_freq"))
+    {
+        report.cpu_speed_limit = speed_limit(limit, max);
+    }
+    report
+}
+
+/// Maps an ACPI platform profile or power-profiles-daemon profile to a mode
+fn parse_linux_profile(profile: &str) -> Option<PowerMode> {
+    match profile.trim() {
+        "low-power" | "quiet" | "cool" | "power-saver" => Some(PowerMode::LowPower),
+        "balanced" | "balanced-performance" => Some(PowerMode::Balanced),
+        "performance" => Some(PowerMode::Performance),
+        _ => None,
+    }
+}
+
+/// Returns the CPU frequency limit in percent of the maximum
+fn speed_limit(limit: u64, max: u64) -> Option<u8> {
+    (max > 0).then(|| (limit.min(max) * 100 / max) as u8)
+}
+
+/// Reads the power state with `pmset`
+fn read_macos() -> PowerReport {
+    let mut report = container::run_command("pmset", &["-g", "batt"])
+        .map(|output| parse_pmset_batt(&output))
+        .unwrap_or_default();
+    report.mode =
+        container::run_command("pmset", &["-g"]).and_then(|output| parse_pmset_mode(&output));
+    report.cpu_speed_limit = container::run_command("pmset", &["-g", "therm"])
+        .and_then(|output| parse_pmset_therm(&output));
+    report
+}
+
+/// Parses the power source and battery state from `pmset -g batt`
+fn parse_pmset_batt(output: &str) -> PowerReport {
+    let source = if output.contains("'Battery Power'") {
+        Some(PowerSource::Battery)
+    } else if output.contains("'AC Power'") {
+        Some(PowerSource::Ac)
+    } else {
+        None
+    };
+    let battery = output.lines().find(|line| line.contains("InternalBattery"));
+    let battery_percent = battery.and_then(|line| {
+        let (before, _) = line.split_once('%')?;
+        before
+            .rsplit(|c: char| !c.is_ascii_digit())
+            .next()?
+            .parse()
+            .ok()
+    });
+    let charging = battery.map(|line| line.contains("; charging;"));
+    PowerReport {
+        source,
+        battery_percent,
+        charging,
+        ..PowerReport::default()
+    }
+}
+
+/// Parses Low Power Mode from the current `pmset -g` settings
+fn parse_pmset_mode(output: &str) -> Option<PowerMode> {
+    output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        match (fields.next()?, fields.next()?) {
+            ("lowpowermode" | "powermode", "1") => Some(PowerMode::LowPower),
+            ("lowpowermode" | "powermode", "0") => Some(PowerMode::Balanced),
+            ("powermode", "2") => Some(PowerMode::Performance),
+            _ => None,
+        }
+    })
+}
+
+/// Parses `CPU_Speed_Limit` from `pmset -g therm`
+fn parse_pmset_therm(output: &str) -> Option<u8> {
+    output.lines().find_map(|line| {
+        let (name, value) = line.split_once('=')?;
+        (name.trim() == "CPU_Speed_Limit").then(|| value.trim().parse().ok())?
+    })
+}
+
+/// Reads the power state from `Win32_Battery` and the active power plan
+fn read_windows() -> PowerReport {
+    let battery = container::run_command(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Get-CimInstance Win32_Battery | ForEach-Object { \"$($_.EstimatedChargeRemaining) $($_.BatteryStatus)\" }",
+        ],
+    );
+    let mut report = battery
+        .map(|output| parse_win32_battery(&output))
+        .unwrap_or_default();
+    report.mode = container::run_command("powercfg", &["/getactivescheme"])
+        .and_then(|output| parse_powercfg_scheme(&output));
+    report
+}
+
+/// Parses `<charge> <BatteryStatus>` as printed for `Win32_Battery`
+///
+/// Status 1 means discharging, 2 on AC power and 6 to 9 charging.
+fn parse_win32_battery(output: &str) -> PowerReport {
+    let mut fields = output.lines().next().unwrap_or_default().split_whitespace();
+    let battery_percent = fields.next().and_then(|value| value.parse().ok());
+    let status = fields.next().and_then(|value| value.parse::<u16>().ok());
+    PowerReport {
+        source: status.map(|status| {
+            if status == 1 {
+                PowerSource::Battery
+            } else {
+                PowerSource::Ac
+            }
+        }),
+        battery_percent,
+        charging: status.map(|status| (6..=9).contains(&status)),
+        ..PowerReport::default()
+    }
+}
+
+/// Maps the GUID printed by `powercfg /getactivescheme` to a mode
+fn parse_powercfg_scheme(output: &str) -> Option<PowerMode> {
+    let output = output.to_lowercase();
+    WINDOWS_SCHEMES
+        .iter()
+        .find(|(guid, _)| output.contains(guid))
+        .map(|(_, mode)| *mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn reads_linux_battery_profile_and_cpu_limit() {
+        let sys = TempDir::new().unwrap();
+        let sys = sys.path();
+        write(sys, "class/power_supply/AC/type", "Mains\n");
+        write(sys, "class/power_supply/AC/online", "0\n");
+        write(sys, "class/power_supply/BAT0/type", "Battery\n");
+        write(sys, "class/power_supply/BAT0/capacity", "18\n");
+        write(sys, "class/power_supply/BAT0/status", "Discharging\n");
+        write(sys, "firmware/acpi/platform_profile", "low-power\n");
+        write(
+            sys,
+            "devices/system/cpu/cpu0/cpufreq/scaling_max_freq",
+            "1600000\n",
+        );
+        write(
+            sys,
+            "devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq",
+            "3200000\n",
+        );
+
+        let report = read_linux(sys);
+        assert_eq!(report.source, Some(PowerSource::Battery));
+        assert_eq!(report.battery_percent, Some(18));
+        assert_eq!(report.charging, Some(false));
+        assert_eq!(report.mode, Some(PowerMode::LowPower));
+        assert_eq!(report.cpu_speed_limit, Some(50));
+        assert!(report.is_throttled() && report.is_draining());
+    }
+
+    #[test]
+    fn parses_pmset_output() {
+        let batt = "Now drawing from 'AC Power'\n -InternalBattery-0 (id=4653155)\t85%; charging; 0:47 remaining present: true\n";
+        let report = parse_pmset_batt(batt);
+        assert_eq!(report.source, Some(PowerSource::Ac));
+        assert_eq!(report.battery_percent, Some(85));
+        assert_eq!(report.charging, Some(true));
+        assert!(!report.slows_builds());
+
+        assert_eq!(
+            parse_pmset_mode(
+                "System-wide power settings:\nCurrently in use:\n lowpowermode         1\n"
+            ),
+            Some(PowerMode::LowPower)
+        );
+        assert_eq!(parse_pmset_therm("CPU Power notify\n\tCPU_Scheduler_Limit \t= 100\n\tCPU_Available_CPUs \t= 8\n\tCPU_Speed_Limit \t= 70\n"), Some(70));
+    }
+
+    #[test]
+    fn parses_windows_battery_and_power_plan() {
+        let report = parse_win32_battery("64 1\r\n");
+        assert_eq!(report.source, Some(PowerSource::Battery));
+        assert_eq!(report.battery_percent, Some(64));
+        assert_eq!(
+            parse_powercfg_scheme(
+                "Power Scheme GUID: a1841308-3541-4fab-bc81-f71556f20b4a  (Power saver)"
+            ),
+            Some(PowerMode::LowPower)
+        );
+        assert_eq!(
+            parse_powercfg_scheme("Power Scheme GUID: 0000  (Custom)"),
+            None
+        );
+    }
+}
@@ -0,0 +1,755 @@
+//! Release version consistency
+//!
+//! A release usually means two steps: bumping the version in the manifest
+//! and tagging the commit. This scanner compares the version declared in a
+//! repository's root manifest with the latest git tag reachable from `HEAD`
+//! and flags repositories where the two disagree, or where commits were
+//! added since the tag without bumping the version.
+//!
+//! The version is read from the first root manifest that declares one:
+//!
+//! - **Rust**: `Cargo.toml` (`[package] version`, or
+//!   `[workspace.package] version` for workspaces)
+//! - **Node.js**: `package.json` (`version`)
+//! - **Python**: `pyproject.toml` (`[project] version` or
+//!   `[tool.poetry] version`)
+//!
+//! Tags are matched by the version they contain, so `v1.2.0`, `1.2.0`,
+//! `release-1.2.0` and `my-crate@1.2.0` all name version 1.2.0.
+//!
+//! ## Changelogs
+//!
+//! The scanner also looks for a root changelog (`CHANGELOG.md`, `CHANGES.md`,
+//! `HISTORY.md` and their variants), recognizes the
+//! [Keep a Changelog](https://keepachangelog.com) format, and flags
+//! changelogs that fell behind: many commits or several tags since the file
+//! was last changed, or no entry for the latest tag. Repositories with
+//! neither a versioned manifest nor a changelog are skipped.
+
+use crate::i18n::t;
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Root manifests checked for a version, in order of preference
+const MANIFESTS: [&str; 3] = ["Cargo.toml", "package.json", "pyproject.toml"];
+
+/// Root changelog file names, matched case-insensitively
+const CHANGELOGS: [&str; 6] = [
+    "CHANGELOG.md",
+    "CHANGELOG",
+    "CHANGELOG.rst",
+    "CHANGES.md",
+    "CHANGES",
+    "HISTORY.md",
+];
+
+/// Commits after the last changelog change that make it stale
+const STALE_CHANGELOG_COMMITS: usize = 50;
+
+/// Tags after the last changelog change that make it stale
+const STALE_CHANGELOG_TAGS: usize = 2;
+
+/// How a manifest version relates to the latest tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionStatus {
+    /// The manifest version differs from the tag and is not newer than it
+    Mismatch,
+    /// Commits were added since the tag, but the version was not bumped
+    BumpMissing,
+    /// The manifest version is newer than the tag; a release is pending
+    Unreleased,
+    /// No tag is reachable from `HEAD`
+    Untagged,
+    /// No root manifest declares a version
+    Unversioned,
+    /// The manifest version matches the tag and nothing was added since
+    Released,
+}
+
+/// Result of comparing one repository's manifest version with its tags
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseReport {
+    /// Root directory of the repository
+    pub repo_path: PathBuf,
+    /// Manifest the version was read from
+    pub manifest: Option<PathBuf>,
+    /// Version declared in the manifest
+    pub version: Option<String>,
+    /// Latest tag reachable from `HEAD`
+    pub tag: Option<String>,
+    /// Number of commits between the tag and `HEAD`
+    pub commits_since_tag: usize,
+    /// How the manifest version relates to the tag
+    pub status: VersionStatus,
+    /// The root changelog, `None` if the repository has none
+    pub changelog: Option<Changelog>,
+}
+
+impl ReleaseReport {
+    /// Returns `true` if the version and tag disagree, a bump is missing, or
+    /// the changelog is stale
+    pub fn needs_attention(&self) -> bool {
+        matches!(
+            self.status,
+            VersionStatus::Mismatch | VersionStatus::BumpMissing
+        ) || self.changelog.as_ref().is_some_and(Changelog::is_stale)
+    }
+}
+
+/// A repository's changelog and how far it lags behind the history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Changelog {
+    /// The changelog file
+    pub path: PathBuf,
+    /// Whether it follows the Keep a Changelog format
+    pub keep_a_changelog: bool,
+    /// Commits since the changelog was last changed
+    pub commits_since_update: usize,
+    /// Tags created on commits after the last changelog change
+    pub tags_since_update: usize,
+    /// Whether the version of the latest tag has an entry (`true` without tags)
+    pub covers_latest_tag: bool,
+}
+
+impl Changelog {
+    /// Returns `true` if the changelog fell behind the repository's history
+    ///
+    /// A changelog is stale when the latest tag has no entry, when
+    /// [`STALE_CHANGELOG_TAGS`] or more tags were created since it last
+    /// changed, or when [`STALE_CHANGELOG_COMMITS`] or more commits were.
+    pub fn is_stale(&self) -> bool {
+        !self.covers_latest_tag
+            || self.tags_since_update >= STALE_CHANGELOG_TAGS
+            || self.commits_since_update >= STALE_CHANGELOG_COMMITS
+    }
+}
+
+/// Compares manifest versions and tags of repositories accepted by a filter
+///
+/// # Arguments
+///
+/// * `path` - The directory to search for git repositories
+/// * `include` - Predicate deciding whether a repository is checked
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::release;
+/// use std::path::Path;
+///
+/// let reports = release::scan_repositories_matching(Path::new("."), |_| true).unwrap();
+/// release::display_results_with(&reports, devhealth::utils::display::Detail::Normal);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be accessed or traversed.
+pub fn scan_repositories_matching<F>(
+    path: &Path,
+    include: F,
+) -> Result<Vec<ReleaseReport>, Box<dyn std::error::Error>>
+where
+    F: Fn(&Path) -> bool,
+{
+    let reports = dh_fs::find_git_repositories(path)?
+        .into_iter()
+        .filter(|repo| include(repo))
+        .filter_map(|repo| check_repository(&repo))
+        .collect();
+    Ok(reports)
+}
+
+/// Compares the manifest version and changelog of one repository with its
+/// latest tag
+///
+/// Returns `None` if the repository has neither a root manifest with a
+/// version nor a changelog.
+///
+/// # Arguments
+///
+/// * `repo_path` - Path to the git repository root directory
+pub fn check_repository(repo_path: &Path) -> Option<ReleaseReport> {
+    let (manifest, version) = MANIFESTS
+        .iter()
+        .find_map(|name| {
+            let manifest = repo_path.join(name);
+            manifest_version(&manifest).map(|version| (manifest, version))
+        })
+        .unzip();
+    let changelog_path = find_changelog(repo_path);
+    if version.is_none() && changelog_path.is_none() {
+        return None;
+    }
+
+    let tag = git(repo_path, &["describe", "--tags", "--abbrev=0"]);
+    let commits_since_tag = tag
+        .as_deref()
+        .and_then(|tag| {
+            git(
+                repo_path,
+                &["rev-list", "--count", &format!("{}..HEAD", tag)],
+            )
+        })
+        .and_then(|count| count.parse().ok())
+        .unwrap_or(0);
+    let status = match &version {
+        Some(version) => classify(version, tag.as_deref(), commits_since_tag),
+        None => VersionStatus::Unversioned,
+    };
+    let changelog = changelog_path.map(|path| check_changelog(repo_path, path, tag.as_deref()));
+
+    Some(ReleaseReport {
+        repo_path: repo_path.to_path_buf(),
+        manifest,
+        version,
+        tag,
+        commits_since_tag,
+        status,
+        changelog,
+    })
+}
+
+/// Displays release version and changelog results at the given level of detail
+///
+/// - [`Detail::Summary`]: header and summary box only
+/// - [`Detail::Minimal`]: only repositories that need attention
+/// - [`Detail::Normal`]: every repository with its version, tag and changelog
+/// - [`Detail::Full`]: also the manifest and changelog files
+///
+/// # Arguments
+///
+/// * `reports` - Slice of release reports to display
+/// * `detail` - How much of the report to print
+pub fn display_results_with(reports: &[ReleaseReport], detail: Detail) {
+    if reports.is_empty() {
+        println!(
+            "{}",
+            display::header(&tr!("release-none"), "🏷️", Role::Warning)
+        );
+        return;
+    }
+
+    let count = |status| reports.iter().filter(|r| r.status == status).count();
+    let mismatches = count(VersionStatus::Mismatch);
+    let bumps_missing = count(VersionStatus::BumpMissing);
+    let stale_changelogs = reports
+        .iter()
+        .filter(|r| r.changelog.as_ref().is_some_and(Changelog::is_stale))
+        .count();
+    let flagged = |count: usize| {
+        if count > 0 {
+            format!("{} ⚠️", count)
+        } else {
+            "0".to_string()
+        }
+    };
+    let role = if mismatches + bumps_missing + stale_changelogs > 0 {
+        Role::Warning
+    } else {
+        Role::Special
+    };
+
+    println!(
+        "{}",
+        display::header(&tr!("release-title", count = reports.len()), "🏷️", role)
+    );
+
+    let summary_items = vec![
+        (t("release-repos"), reports.len().to_string()),
+        (
+            t("release-released"),
+            count(VersionStatus::Released).to_string(),
+        ),
+        (t("release-bumps-missing"), flagged(bumps_missing)),
+        (t("release-mismatches"), flagged(mismatches)),
+        (t("release-stale-changelogs"), flagged(stale_changelogs)),
+    ];
+    print!("{}", display::summary_box(&summary_items));
+
+    if detail == Detail::Summary {
+        return;
+    }
+
+    let shown: Vec<&ReleaseReport> = reports
+        .iter()
+        .filter(|report| detail >= Detail::Normal || report.needs_attention())
+        .collect();
+    if shown.is_empty() {
+        return;
+    }
+
+    println!("{}", display::section_divider(t("release-details")));
+    for (index, report) in shown.iter().enumerate() {
+        let name = report
+            .repo_path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "unknown".to_string());
+        let (symbol, role) = match report.status {
+            VersionStatus::Mismatch => ("✗", Role::Error),
+            VersionStatus::BumpMissing => ("⚠", Role::Warning),
+            VersionStatus::Unreleased | VersionStatus::Untagged | VersionStatus::Unversioned => {
+                ("○", Role::Muted)
+            }
+            VersionStatus::Released => ("✓", Role::Success),
+        };
+        let version = report.version.as_deref().unwrap_or_default();
+        let tag = report.tag.as_deref().unwrap_or_default();
+        let message = match report.status {
+            VersionStatus::Mismatch => tr!("release-status-mismatch", version = version, tag = tag),
+            VersionStatus::BumpMissing => tr!(
+                "release-status-bump-missing",
+                version = version,
+                tag = tag,
+                commits = report.commits_since_tag
+            ),
+            VersionStatus::Unreleased => {
+                tr!("release-status-unreleased", version = version, tag = tag)
+            }
+            VersionStatus::Untagged => tr!("release-status-untagged", version = version),
+            VersionStatus::Unversioned => t("release-status-unversioned").to_string(),
+            VersionStatus::Released => tr!("release-status-released", version = version, tag = tag),
+        };
+
+        let line = format!(
+            "{} {} {}",
+            symbol.paint(role),
+            name.paint(Role::Emphasis).bold(),
+            message.paint(role)
+        );
+        println!("{}", display::tree_item(&line, index == shown.len() - 1, 0));
+
+        let mut lines = vec![changelog_line(report.changelog.as_ref(), tag)];
+        if detail == Detail::Full {
+            let files = report
+                .manifest
+                .iter()
+                .chain(report.changelog.iter().map(|c| &c.path));
+            lines.extend(files.map(|file| display::file_path(&file.to_string_lossy())));
+        }
+        for (line_index, line) in lines.iter().enumerate() {
+            println!(
+                "{}",
+                display::tree_item(line, line_index == lines.len() - 1, 1)
+            );
+        }
+    }
+}
+
+/// Formats the changelog state of a repository
+fn changelog_line(changelog: Option<&Changelog>, tag: &str) -> String {
+    let Some(changelog) = changelog else {
+        return format!(
+            "{} {}",
+            "○".paint(Role::Muted),
+            t("release-changelog-missing").paint(Role::Muted)
+        );
+    };
+
+    let name = changelog
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let format = if changelog.keep_a_changelog {
+        format!(" {}", t("release-changelog-keep").paint(Role::Muted))
+    } else {
+        String::new()
+    };
+    if !changelog.is_stale() {
+        let message = tr!("release-changelog-current", file = name);
+        return format!(
+            "{} {}{}",
+            "✓".paint(Role::Success),
+            message.paint(Role::Success),
+            format
+        );
+    }
+
+    let message = if changelog.covers_latest_tag {
+        tr!(
+            "release-changelog-stale",
+            file = name,
+            commits = changelog.commits_since_update,
+            tags = changelog.tags_since_update
+        )
+    } else {
+        tr!("release-changelog-no-entry", file = name, tag = tag)
+    };
+    format!(
+        "{} {}{}",
+        "⚠".paint(Role::Warning),
+        message.paint(Role::Warning),
+        format
+    )
+}
+
+/// Reads the declared version from a root manifest, if it has one
+fn manifest_version(manifest: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(manifest).ok()?;
+    let version = match manifest.file_name()?.to_str()? {
+        "Cargo.toml" => {
+            let manifest: toml::Value = toml::from_str(&content).ok()?;
+            let package = manifest.get("package").and_then(|p| p.get("version"));
+            // `version.workspace = true` inherits from `[workspace.package]`
+            package
+                .and_then(toml::Value::as_str)
+                .or_else(|| {
+                    manifest
+                        .get("workspace")?
+                        .get("package")?
+                        .get("version")?
+                        .as_str()
+                })?
+                .to_string()
+        }
+        "package.json" => {
+            let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+            manifest.get("version")?.as_str()?.to_string()
+        }
+        "pyproject.toml" => {
+            let manifest: toml::Value = toml::from_str(&content).ok()?;
+            manifest
+                .get("project")
+                .and_then(|project| project.get("version"))
+                .or_else(|| manifest.get("tool")?.get("poetry")?.get("version"))?
+                .as_str()?
+                .to_string()
+        }
+        _ => return None,
+    };
+    Some(version.trim().to_string()).filter(|version| !version.is_empty())
+}
+
+/// Returns the root changelog of a repository, if it has one
+fn find_changelog(repo_path: &Path) -> Option<PathBuf> {
+    let entries = std::fs::read_dir(repo_path).ok()?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+
+    CHANGELOGS.iter().find_map(|name| {
+        files
+            .iter()
+            .find(|file| {
+                file.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+            })
+            .cloned()
+    })
+}
+
+/// Measures how far a changelog lags behind the repository's history
+fn check_changelog(repo_path: &Path, path: PathBuf, tag: Option<&str>) -> Changelog {
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    let file = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+
+    // Commit that last changed the changelog; uncommitted files count as current
+    let last_change = git(repo_path, &["log", "-1", "--format=%H", "--", file]);
+    let (commits_since_update, tags_since_update) = match &last_change {
+        Some(commit) => {
+            let commits = git(
+                repo_path,
+                &["rev-list", "--count", &format!("{}..HEAD", commit)],
+            )
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0);
+            let tags = git(repo_path, &["tag", "--contains", commit]).unwrap_or_default();
+            let on_commit = git(repo_path, &["tag", "--points-at", commit]).unwrap_or_default();
+            let later = tags
+                .lines()
+                .filter(|t| !on_commit.lines().any(|o| o == *t))
+                .count();
+            (commits, later)
+        }
+        None => (0, 0),
+    };
+
+    Changelog {
+        keep_a_changelog: is_keep_a_changelog(&content),
+        commits_since_update,
+        tags_since_update,
+        covers_latest_tag: tag.is_none_or(|tag| has_entry_for(&content, tag_version(tag))),
+        path,
+    }
+}
+
+/// Returns `true` if a changelog follows the Keep a Changelog format
+///
+/// The format is recognized by its link to keepachangelog.com or by
+/// version headings such as `## [Unreleased]` and `## [1.2.0] - 2024-05-01`.
+fn is_keep_a_changelog(content: &str) -> bool {
+    content.contains("keepachangelog.com")
+        || content.lines().any(|line| {
+            line.strip_prefix("## [")
+                .and_then(|rest| rest.split_once(']'))
+                .is_some_and(|(version, _)| {
+                    version.eq_ignore_ascii_case("unreleased") || Version::parse(version).is_ok()
+                })
+        })
+}
+
+/// Returns `true` if a changelog mentions `version` on its own
+///
+/// `1.2.0` matches in `## [1.2.0]` and `v1.2.0`, but not in `11.2.0` or
+/// `1.2.0.1`.
+fn has_entry_for(content: &str, version: &str) -> bool {
+    content.match_indices(version).any(|(index, _)| {
+        let before = content[..index].chars().next_back();
+        let mut after = content[index + version.len()..].chars();
+        let continues = match after.next() {
+            Some(c) => {
+                c.is_ascii_digit() || (c == '.' && after.next().is_some_and(|c| c.is_ascii_digit()))
+            }
+            None => false,
+        };
+        !before.is_some_and(|c| c.is_ascii_digit() || c == '.') && !continues
+    })
+}
+
+/// Returns the version part of a tag such as `v1.2.0` or `pkg@1.2.0`
+///
+/// The version starts at the first digit that follows the start of the tag,
+/// a separator, or a standalone `v`, so prefixes like `release-` and
+/// `api2-v` are skipped.
+fn tag_version(tag: &str) -> &str {
+    let tag = tag.rsplit_once('@').map_or(tag, |(_, version)| version);
+    let starts_version = |index: usize| {
+        let mut before = tag[..index].chars().rev();
+        match before.next() {
+            None => true,
+            Some('v' | 'V') => !before.next().is_some_and(|c| c.is_ascii_alphanumeric()),
+            Some(previous) => !previous.is_ascii_alphanumeric() && previous != '.',
+        }
+    };
+    match tag
+        .char_indices()
+        .find(|&(index, c)| c.is_ascii_digit() && starts_version(index))
+    {
+        Some((index, _)) => &tag[index..],
+        None => tag,
+    }
+}
+
+/// Compares two versions, by semver where both parse and textually otherwise
+///
+/// Returns `None` for unequal versions that cannot be ordered.
+fn compare_versions(manifest: &str, tag: &str) -> Option<Ordering> {
+    match (Version::parse(manifest), Version::parse(tag)) {
+        (Ok(manifest), Ok(tag)) => Some(manifest.cmp(&tag)),
+        _ => (manifest == tag).then_some(Ordering::Equal),
+    }
+}
+
+/// Classifies a manifest version against the latest tag
+fn classify(version: &str, tag: Option<&str>, commits_since_tag: usize) -> VersionStatus {
+    let Some(tag) = tag else {
+        return VersionStatus::Untagged;
+    };
+    match compare_versions(version, tag_version(tag)) {
+        Some(Ordering::Equal) if commits_since_tag > 0 => VersionStatus::BumpMissing,
+        Some(Ordering::Equal) => VersionStatus::Released,
+        Some(Ordering::Greater) => VersionStatus::Unreleased,
+        Some(Ordering::Less) | None => VersionStatus::Mismatch,
+    }
+}
+
+/// Runs a git command in a repository and returns its trimmed output
+fn git(repo_path: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    mod manifests {
+        use super::*;
+
+        #[test]
+        fn reads_versions_from_each_ecosystem() {
+            let temp_dir = TempDir::new().unwrap();
+            let write = |name: &str, content: &str| {
+                let path = temp_dir.path().join(name);
+                fs::write(&path, content).unwrap();
+                manifest_version(&path)
+            };
+
+            assert_eq!(
+                write(
+                    "Cargo.toml",
+                    "[package]\nname = \"api\"\nversion = \"1.4.0\"\n"
+                ),
+                Some("1.4.0".to_string())
+            );
+            assert_eq!(
+                write("package.json", r#"{"name": "web", "version": "2.0.1"}"#),
+                Some("2.0.1".to_string())
+            );
+            assert_eq!(
+                write(
+                    "pyproject.toml",
+                    "[tool.poetry]\nname = \"cli\"\nversion = \"0.3.0\"\n"
+                ),
+                Some("0.3.0".to_string())
+            );
+        }
+
+        #[test]
+        fn falls_back_to_workspace_package_version() {
+            let temp_dir = TempDir::new().unwrap();
+            let path = temp_dir.path().join("Cargo.toml");
+            fs::write(
+                &path,
+                "[package]\nname = \"api\"\nversion.workspace = true\n\n[workspace.package]\nversion = \"3.1.0\"\n",
+            )
+            .unwrap();
+            assert_eq!(manifest_version(&path), Some("3.1.0".to_string()));
+
+            fs::write(&path, "[workspace]\nmembers = [\"crates/*\"]\n").unwrap();
+            assert_eq!(manifest_version(&path), None);
+        }
+    }
+
+    mod classification {
+        use super::*;
+
+        #[test]
+        fn extracts_versions_from_tags() {
+            assert_eq!(tag_version("v1.2.0"), "1.2.0");
+            assert_eq!(tag_version("1.2.0"), "1.2.0");
+            assert_eq!(tag_version("release-1.2.0-rc.1"), "1.2.0-rc.1");
+            assert_eq!(tag_version("@scope/web@2.0.0"), "2.0.0");
+            assert_eq!(tag_version("api2-v0.4.1"), "0.4.1");
+            assert_eq!(tag_version("nightly"), "nightly");
+        }
+
+        #[test]
+        fn classifies_versions_against_tags() {
+            assert_eq!(
+                classify("1.2.0", Some("v1.2.0"), 0),
+                VersionStatus::Released
+            );
+            assert_eq!(
+                classify("1.2.0", Some("v1.2.0"), 4),
+                VersionStatus::BumpMissing
+            );
+            assert_eq!(
+                classify("1.3.0", Some("v1.2.0"), 4),
+                VersionStatus::Unreleased
+            );
+            assert_eq!(
+                classify("1.1.0", Some("v1.2.0"), 0),
+                VersionStatus::Mismatch
+            );
+            assert_eq!(classify("1.2", Some("v1.3"), 0), VersionStatus::Mismatch);
+            assert_eq!(classify("1.2", Some("v1.2"), 0), VersionStatus::Released);
+            assert_eq!(classify("1.2.0", None, 12), VersionStatus::Untagged);
+        }
+
+        #[test]
+        fn flags_mismatches_and_missing_bumps() {
+            let report = |status| ReleaseReport {
+                repo_path: PathBuf::from("/src/api"),
+                manifest: Some(PathBuf::from("/src/api/Cargo.toml")),
+                version: Some("1.2.0".to_string()),
+                tag: Some("v1.2.0".to_string()),
+                commits_since_tag: 0,
+                status,
+                changelog: None,
+            };
+            assert!(report(VersionStatus::Mismatch).needs_attention());
+            assert!(report(VersionStatus::BumpMissing).needs_attention());
+            assert!(!report(VersionStatus::Unreleased).needs_attention());
+            assert!(!report(VersionStatus::Released).needs_attention());
+
+            let mut stale = report(VersionStatus::Released);
+            stale.changelog = Some(changelog(0, 0, false));
+            assert!(stale.needs_attention(), "A stale changelog needs attention");
+        }
+    }
+
+    mod changelogs {
+        use super::*;
+
+        #[test]
+        fn finds_root_changelogs_case_insensitively() {
+            let temp_dir = TempDir::new().unwrap();
+            assert_eq!(find_changelog(temp_dir.path()), None);
+
+            fs::write(temp_dir.path().join("History.md"), "# History\n").unwrap();
+            fs::write(temp_dir.path().join("changelog.md"), "# Changelog\n").unwrap();
+            fs::create_dir(temp_dir.path().join("CHANGES")).unwrap();
+            assert_eq!(
+                find_changelog(temp_dir.path()),
+                Some(temp_dir.path().join("changelog.md"))
+            );
+        }
+
+        #[test]
+        fn recognizes_keep_a_changelog_format() {
+            let keep = "# Changelog\n\n## [Unreleased]\n\n## [1.2.0] - 2024-05-01\n### Added\n";
+            assert!(is_keep_a_changelog(keep));
+            assert!(is_keep_a_changelog(
+                "Format based on https://keepachangelog.com/en/1.1.0/\n"
+            ));
+            assert!(!is_keep_a_changelog("# History\n\n## 1.2.0\n- Fixes\n"));
+        }
+
+        #[test]
+        fn matches_versions_on_their_own() {
+            let content = "## [1.2.0] - 2024-05-01\n\nSee v0.9.1 for older notes.\n";
+            assert!(has_entry_for(content, "1.2.0"));
+            assert!(has_entry_for(content, "0.9.1"));
+            assert!(!has_entry_for(content, "2.0"));
+            assert!(!has_entry_for("## 11.2.0\n", "1.2.0"));
+            assert!(!has_entry_for("## 1.2.0.1\n", "1.2.0"));
+            assert!(has_entry_for("Released 1.2.0.\n", "1.2.0"));
+        }
+
+        #[test]
+        fn flags_changelogs_that_fell_behind() {
+            assert!(!changelog(3, 1, true).is_stale());
+            assert!(changelog(STALE_CHANGELOG_COMMITS, 0, true).is_stale());
+            assert!(changelog(3, STALE_CHANGELOG_TAGS, true).is_stale());
+            assert!(
+                changelog(0, 0, false).is_stale(),
+                "Latest tag without an entry"
+            );
+        }
+    }
+
+    fn changelog(commits: usize, tags: usize, covers_latest_tag: bool) -> Changelog {
+        Changelog {
+            path: PathBuf::from("/src/api/CHANGELOG.md"),
+            keep_a_changelog: true,
+            commits_since_update: commits,
+            tags_since_update: tags,
+            covers_latest_tag,
+        }
+    }
+}
@@ -0,0 +1,803 @@
+//! Node version managers and pinned runtime versions
+//!
+//! Projects pin the runtimes they expect in `.nvmrc`, `.node-version`,
+//! `.tool-versions` (asdf) or the `volta` field of `package.json`. When the
+//! shell runs something else, builds fail in ways that are hard to trace
+//! back to the version. This scanner reports:
+//!
+//! - the installed version managers: nvm, fnm, Volta and asdf
+//! - every `node` on `PATH`, in the order the shell searches them, and
+//!   which version manager (if any) installed it
+//! - a `node` shadowing another one from a different source, such as a
+//!   system `/usr/bin/node` earlier in `PATH` than the nvm one
+//! - for every pinned runtime, the version active in the project directory
+//!   and whether it satisfies the pin
+//!
+//! Active versions come from running the runtime in the project directory,
+//! so per-directory shims (Volta, asdf) resolve the way they would in a
+//! shell. A pin such as `20` or `20.11` is satisfied by any version it is a
+//! prefix of; aliases like `lts/*` or `system` are listed but not compared.
+
+use crate::i18n::t;
+use crate::scanner::container;
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files that pin a Node version on their own
+pub(crate) const NODE_PIN_FILES: [&str; 2] = [".nvmrc", ".node-version"];
+
+/// `.tool-versions` plugins that are compared, with the program and
+/// arguments printing the active version
+const TOOLS: [(&str, &str, &[&str]); 11] = [
+    ("nodejs", "node", &["--version"]),
+    ("node", "node", &["--version"]),
+    ("python", "python3", &["--version"]),
+    ("ruby", "ruby", &["--version"]),
+    ("golang", "go", &["version"]),
+    ("rust", "rustc", &["--version"]),
+    ("deno", "deno", &["--version"]),
+    ("bun", "bun", &["--version"]),
+    ("pnpm", "pnpm", &["--version"]),
+    ("yarn", "yarn", &["--version"]),
+    ("terraform", "terraform", &["version"]),
+];
+
+/// A tool that installs and switches runtime versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionManager {
+    /// Node Version Manager (or nvm-windows)
+    Nvm,
+    /// Fast Node Manager
+    Fnm,
+    /// Volta
+    Volta,
+    /// asdf, which manages any runtime through plugins
+    Asdf,
+}
+
+impl fmt::Display for VersionManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VersionManager::Nvm => "nvm",
+            VersionManager::Fnm => "fnm",
+            VersionManager::Volta => "volta",
+            VersionManager::Asdf => "asdf",
+        })
+    }
+}
+
+/// An installed version manager
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManagerInstall {
+    /// Which version manager
+    pub manager: VersionManager,
+    /// Directory holding its installed runtimes
+    pub root: PathBuf,
+}
+
+/// A `node` executable found on `PATH`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeBinary {
+    /// Path of the executable
+    pub path: PathBuf,
+    /// Version manager that installed it, `None` for a system install
+    pub manager: Option<VersionManager>,
+    /// Version it reports, if it ran
+    pub version: Option<String>,
+}
+
+impl NodeBinary {
+    /// Names where the binary comes from: a version manager or `system`
+    pub fn source(&self) -> String {
+        self.manager
+            .map(|manager| manager.to_string())
+            .unwrap_or_else(|| t("runtime-system").to_string())
+    }
+}
+
+/// A `node` that is never run because one from another source comes first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Shadowing {
+    /// The `node` the shell runs
+    pub active: NodeBinary,
+    /// The `node` it hides
+    pub shadowed: NodeBinary,
+}
+
+/// Whether the active version of a runtime satisfies its pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PinStatus {
+    /// The active version satisfies the pin
+    Matches,
+    /// A different version is active
+    Mismatch,
+    /// The runtime is not installed or did not report a version
+    NotInstalled,
+    /// The pin is an alias such as `lts/*` that cannot be compared
+    Unresolved,
+}
+
+/// A runtime version pinned by a project file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimePin {
+    /// File declaring the pin
+    pub file: PathBuf,
+    /// Runtime name as written in `.tool-versions` (`nodejs` for Node pins)
+    pub runtime: String,
+    /// Requested versions; `.tool-versions` may list fallbacks
+    pub requested: Vec<String>,
+    /// Version active in the project directory
+    pub active: Option<String>,
+    /// Whether `active` satisfies `requested`
+    pub status: PinStatus,
+}
+
+/// The pinned runtimes of one project directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectRuntimes {
+    /// Directory holding the pin files
+    pub path: PathBuf,
+    /// Pins in file order
+    pub pins: Vec<RuntimePin>,
+}
+
+impl ProjectRuntimes {
+    /// Returns the pins whose active version differs or is missing
+    pub fn mismatches(&self) -> impl Iterator<Item = &RuntimePin> {
+        self.pins
+            .iter()
+            .filter(|pin| matches!(pin.status, PinStatus::Mismatch | PinStatus::NotInstalled))
+    }
+}
+
+/// Version managers, `node` binaries and pinned versions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeReport {
+    /// Installed version managers
+    pub managers: Vec<ManagerInstall>,
+    /// Every `node` on `PATH`, the active one first
+    pub node: Vec<NodeBinary>,
+    /// `node` binaries hidden by one from another source
+    pub shadowing: Vec<Shadowing>,
+    /// Projects pinning runtime versions
+    pub projects: Vec<ProjectRuntimes>,
+}
+
+/// Detects version managers and checks the pins of projects accepted by a filter
+///
+/// # Arguments
+///
+/// * `path` - The directory to search for pin files
+/// * `include` - Predicate deciding whether a project directory is included
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::runtime;
+/// use std::path::Path;
+///
+/// let report = runtime::scan_runtimes_matching(Path::new("."), |_| true);
+/// for shadowing in &report.shadowing {
+///     println!("{} hides {}", shadowing.active.path.display(), shadowing.shadowed.path.display());
+/// }
+/// ```
+pub fn scan_runtimes_matching<F>(path: &Path, include: F) -> RuntimeReport
+where
+    F: Fn(&Path) -> bool,
+{
+    let managers = detect_managers();
+    let node: Vec<NodeBinary> = dh_fs::find_on_path("node")
+        .into_iter()
+        .map(|path| NodeBinary {
+            manager: owner(&path, &managers),
+            version: container::run_command(&path.to_string_lossy(), &["--version"])
+                .and_then(|output| container::extract_version(&output)),
+            path,
+        })
+        .collect();
+    let shadowing = find_shadowing(&node);
+
+    let mut pins: BTreeMap<PathBuf, Vec<(PathBuf, String, Vec<String>)>> = BTreeMap::new();
+    let walk_root = dh_fs::extended_length(path);
+    for entry in dh_fs::walk(path).filter(|entry| entry.file_type().is_file()) {
+        let Some(parent) = entry.path().parent() else {
+            continue;
+        };
+        let dir = dh_fs::strip_extended_length(parent);
+        if dh_fs::is_skipped(parent.strip_prefix(&walk_root).unwrap_or(parent)) || !include(&dir) {
+            continue;
+        }
+        let file = dh_fs::strip_extended_length(entry.path());
+        let found = read_pins(&file);
+        if !found.is_empty() {
+            let project = pins.entry(dir).or_default();
+            project.extend(
+                found
+                    .into_iter()
+                    .map(|(runtime, requested)| (file.clone(), runtime, requested)),
+            );
+        }
+    }
+
+    let projects = pins
+        .into_iter()
+        .map(|(dir, pins)| ProjectRuntimes {
+            pins: pins
+                .into_iter()
+                .map(|(file, runtime, requested)| {
+                    let active = active_version(&runtime, &dir);
+                    let status = pin_status(&requested, active.as_deref());
+                    RuntimePin {
+                        file,
+                        runtime,
+                        requested,
+                        active,
+                        status,
+                    }
+                })
+                .collect(),
+            path: dir,
+        })
+        .collect();
+
+    RuntimeReport {
+        managers,
+        node,
+        shadowing,
+        projects,
+    }
+}
+
+/// Displays runtime results at the given level of detail
+///
+/// - [`Detail::Summary`]: header and summary box only
+/// - [`Detail::Minimal`]: only shadowed binaries and pins that do not match
+/// - [`Detail::Normal`]: every project with its pins
+/// - [`Detail::Full`]: also every `node` on `PATH` and the pin files
+///
+/// # Arguments
+///
+/// * `report` - The runtime report to display
+/// * `detail` - How much of the report to print
+pub fn display_results_with(report: &RuntimeReport, detail: Detail) {
+    let mismatched = report
+        .projects
+        .iter()
+        .filter(|project| project.mismatches().next().is_some())
+        .count();
+    let role = if mismatched > 0 || !report.shadowing.is_empty() {
+        Role::Warning
+    } else {
+        Role::Special
+    };
+    println!(
+        "{}",
+        display::header(
+            &tr!("runtime-title", count = report.projects.len()),
+            "🧰",
+            role
+        )
+    );
+
+    let flagged = |count: usize| {
+        if count > 0 {
+            format!("{} ⚠️", count)
+        } else {
+            "0".to_string()
+        }
+    };
+    let managers: Vec<String> = report
+        .managers
+        .iter()
+        .map(|install| install.manager.to_string())
+        .collect();
+    let active = match report.node.first() {
+        Some(node) => format!(
+            "{} ({})",
+            node.version.as_deref().unwrap_or(t("runtime-unknown")),
+            node.source()
+        ),
+        None => t("runtime-none").to_string(),
+    };
+    let summary_items = vec![
+        (
+            t("runtime-managers"),
+            if managers.is_empty() {
+                t("runtime-none").to_string()
+            } else {
+                managers.join(", ")
+            },
+        ),
+        (t("runtime-active-node"), active),
+        (t("runtime-shadowed"), flagged(report.shadowing.len())),
+        (t("runtime-mismatched"), flagged(mismatched)),
+    ];
+    print!("{}", display::summary_box(&summary_items));
+
+    if detail == Detail::Summary {
+        return;
+    }
+
+    let shown: Vec<&ProjectRuntimes> = report
+        .projects
+        .iter()
+        .filter(|project| detail >= Detail::Normal || project.mismatches().next().is_some())
+        .collect();
+    let binaries = if detail == Detail::Full {
+        report.node.as_slice()
+    } else {
+        &[]
+    };
+    if shown.is_empty() && report.shadowing.is_empty() && binaries.is_empty() {
+        return;
+    }
+
+    println!("{}", display::section_divider(t("runtime-details")));
+    let mut items: Vec<(String, Vec<String>)> = Vec::new();
+    for shadowing in &report.shadowing {
+        let line = tr!(
+            "runtime-shadows",
+            active = describe_binary(&shadowing.active),
+            shadowed = describe_binary(&shadowing.shadowed)
+        );
+        items.push((
+            format!("{} {}", "⚠".paint(Role::Warning), line.paint(Role::Warning)),
+            Vec::new(),
+        ));
+    }
+    for (index, binary) in binaries.iter().enumerate() {
+        let label = if index == 0 {
+            t("runtime-on-path-active")
+        } else {
+            t("runtime-on-path")
+        };
+        let line = format!("{} {}", label, describe_binary(binary));
+        items.push((line.paint(Role::Muted).to_string(), Vec::new()));
+    }
+    for project in shown {
+        let (symbol, role) = if project.mismatches().next().is_some() {
+            ("⚠", Role::Warning)
+        } else {
+            ("✓", Role::Success)
+        };
+        let name = project
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| project.path.display().to_string());
+        let header = format!(
+            "{} {}",
+            symbol.paint(role),
+            name.paint(Role::Emphasis).bold()
+        );
+        let lines = project
+            .pins
+            .iter()
+            .filter(|pin| {
+                detail >= Detail::Normal
+                    || matches!(pin.status, PinStatus::Mismatch | PinStatus::NotInstalled)
+            })
+            .map(|pin| describe_pin(pin, detail))
+            .collect();
+        items.push((header, lines));
+    }
+
+    for (index, (header, lines)) in items.iter().enumerate() {
+        println!(
+            "{}",
+            display::tree_item(header, index == items.len() - 1, 0)
+        );
+        for (line_index, line) in lines.iter().enumerate() {
+            println!(
+                "{}",
+                display::tree_item(line, line_index == lines.len() - 1, 1)
+            );
+        }
+    }
+}
+
+/// Formats a binary as `nvm node 20.11.1 (/home/ada/.nvm/…/bin/node)`
+fn describe_binary(binary: &NodeBinary) -> String {
+    tr!(
+        "runtime-binary",
+        source = binary.source(),
+        version = binary.version.as_deref().unwrap_or(t("runtime-unknown")),
+        path = binary.path.display().to_string()
+    )
+}
+
+/// Formats one pin with its active version, colored by status
+fn describe_pin(pin: &RuntimePin, detail: Detail) -> String {
+    let file = pin
+        .file
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let active = pin.active.as_deref().unwrap_or(t("runtime-not-installed"));
+    let line = tr!(
+        "runtime-pin",
+        file = file,
+        runtime = pin.runtime,
+        requested = pin.requested.join(" "),
+        active = active
+    );
+    let line = match pin.status {
+        PinStatus::Matches => format!("{} {}", "✓".paint(Role::Success), line),
+        PinStatus::Mismatch | PinStatus::NotInstalled => {
+            format!("{} {}", "⚠".paint(Role::Warning), line.paint(Role::Warning))
+        }
+        PinStatus::Unresolved => format!("{} {}", "?".paint(Role::Muted), line.paint(Role::Muted)),
+    };
+    if detail == Detail::Full {
+        format!(
+            "{} {}",
+            line,
+            display::file_path(&pin.file.to_string_lossy())
+        )
+    } else {
+        line
+    }
+}
+
+/// Finds the installed version managers
+///
+/// Each is looked up at its configured location (`NVM_DIR`, `FNM_DIR`,
+/// `VOLTA_HOME`, `ASDF_DATA_DIR`) or its default one in the home directory.
+fn detect_managers() -> Vec<ManagerInstall> {
+    let env = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    let home = env("HOME").or_else(|| env("USERPROFILE"));
+    let in_home = |relative: &str| home.as_ref().map(|home| home.join(relative));
+
+    // Locations in order of preference, with a file marking an install
+    let candidates = [
+        (
+            VersionManager::Nvm,
+            vec![env("NVM_DIR"), in_home(".nvm")],
+            Some("nvm.sh"),
+        ),
+        (VersionManager::Nvm, vec![env("NVM_HOME")], None),
+        (
+            VersionManager::Fnm,
+            vec![
+                env("FNM_DIR"),
+                in_home(".local/share/fnm"),
+                in_home(".fnm"),
+                in_home("Library/Application Support/fnm"),
+                env("APPDATA").map(|dir| dir.join("fnm")),
+            ],
+            None,
+        ),
+        (
+            VersionManager::Volta,
+            vec![
+                env("VOLTA_HOME"),
+                in_home(".volta"),
+                env("LOCALAPPDATA").map(|dir| dir.join("Volta")),
+            ],
+            None,
+        ),
+        (
+            VersionManager::Asdf,
+            vec![env("ASDF_DATA_DIR"), env("ASDF_DIR"), in_home(".asdf")],
+            None,
+        ),
+    ];
+
+    let mut managers: Vec<ManagerInstall> = Vec::new();
+    for (manager, roots, marker) in candidates {
+        if managers.iter().any(|install| install.manager == manager) {
+            continue;
+        }
+        let root = roots.into_iter().flatten().find(|root| match marker {
+            Some(marker) => root.join(marker).is_file(),
+            None => root.is_dir(),
+        });
+        if let Some(root) = root {
+            managers.push(ManagerInstall { manager, root });
+        }
+    }
+    managers
+}
+
+/// Returns the version manager a `node` executable belongs to
+///
+/// fnm links the active version into a per-shell `fnm_multishells`
+/// directory outside its root, so that directory counts as fnm's too.
+fn owner(binary: &Path, managers: &[ManagerInstall]) -> Option<VersionManager> {
+    if binary
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().contains("fnm_multishells"))
+    {
+        return Some(VersionManager::Fnm);
+    }
+    managers
+        .iter()
+        .find(|install| binary.starts_with(&install.root))
+        .map(|install| install.manager)
+}
+
+/// Pairs the active `node` with every later one from another source
+fn find_shadowing(node: &[NodeBinary]) -> Vec<Shadowing> {
+    let Some((active, rest)) = node.split_first() else {
+        return Vec::new();
+    };
+    let mut shadowing: Vec<Shadowing> = Vec::new();
+    for binary in rest
+        .iter()
+        .filter(|binary| binary.manager != active.manager)
+    {
+        // One entry per source: the first binary of each is the one it would run
+        if shadowing
+            .iter()
+            .all(|entry| entry.shadowed.manager != binary.manager)
+        {
+            shadowing.push(Shadowing {
+                active: active.clone(),
+                shadowed: binary.clone(),
+            });
+        }
+    }
+    shadowing
+}
+
+/// Reads the runtime pins of a file, if it is a pin file
+///
+/// Returns `(runtime, requested versions)` pairs.
+fn read_pins(file: &Path) -> Vec<(String, Vec<String>)> {
+    let Some(name) = file.file_name().and_then(|name| name.to_str()) else {
+        return Vec::new();
+    };
+    if !NODE_PIN_FILES.contains(&name) && name != ".tool-versions" && name != "package.json" {
+        return Vec::new();
+    }
+    let Ok(content) = std::fs::read_to_string(file) else {
+        return Vec::new();
+    };
+
+    match name {
+        ".tool-versions" => parse_tool_versions(&content),
+        "package.json" => parse_volta_pin(&content)
+            .map(|version| vec![("nodejs".to_string(), vec![version])])
+            .unwrap_or_default(),
+        _ => parse_node_pin(&content)
+            .map(|version| vec![("nodejs".to_string(), vec![version])])
+            .unwrap_or_default(),
+    }
+}
+
+/// Parses `.nvmrc` or `.node-version`: the first line that is not a comment
+fn parse_node_pin(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Parses the `volta.node` field of a `package.json`
+fn parse_volta_pin(content: &str) -> Option<String> {
+    let manifest: serde_json::Value = serde_json::from_str(content).ok()?;
+    Some(manifest.get("volta")?.get("node")?.as_str()?.to_string())
+}
+
+/// Parses `.tool-versions`, keeping the runtimes DevHealth can compare
+///
+/// Lines read `nodejs 20.11.1 18.19.0`: a plugin name and one or more
+/// versions, the first preferred.
+fn parse_tool_versions(content: &str) -> Vec<(String, Vec<String>)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut words = line
+                .split('#')
+                .next()
+                .unwrap_or_default()
+                .split_whitespace();
+            let runtime = words.next()?;
+            let versions: Vec<String> = words.map(str::to_string).collect();
+            let known = TOOLS.iter().any(|(plugin, ..)| *plugin == runtime);
+            (known && !versions.is_empty()).then(|| (runtime.to_string(), versions))
+        })
+        .collect()
+}
+
+/// Runs a runtime in `dir` and returns the version it reports
+fn active_version(runtime: &str, dir: &Path) -> Option<String> {
+    let (_, program, args) = TOOLS.iter().find(|(plugin, ..)| *plugin == runtime)?;
+    let output = Command::new(program)
+        .args(*args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // Older runtimes print their version to stderr
+    container::extract_version(&String::from_utf8_lossy(&output.stdout))
+        .or_else(|| container::extract_version(&String::from_utf8_lossy(&output.stderr)))
+}
+
+/// Compares the active version with the requested ones
+fn pin_status(requested: &[String], active: Option<&str>) -> PinStatus {
+    let versions: Vec<&str> = requested
+        .iter()
+        .map(|version| version.trim().trim_start_matches('v'))
+        .filter(|version| version.chars().next().is_some_and(|c| c.is_ascii_digit()))
+        .collect();
+    if versions.is_empty() {
+        return PinStatus::Unresolved;
+    }
+    let Some(active) = active else {
+        return PinStatus::NotInstalled;
+    };
+    if versions.iter().any(|version| satisfies(version, active)) {
+        PinStatus::Matches
+    } else {
+        PinStatus::Mismatch
+    }
+}
+
+/// Returns `true` if `version` is `requested` or starts with its components
+///
+/// `20` and `20.11` are satisfied by `20.11.1`; `20.1` is not.
+fn satisfies(requested: &str, version: &str) -> bool {
+    let numeric = |text: &str| -> Vec<String> {
+        text.split('.')
+            .map(|part| part.chars().take_while(char::is_ascii_digit).collect())
+            .collect()
+    };
+    let requested = numeric(requested);
+    let version = numeric(version.trim_start_matches('v'));
+    requested.len() <= version.len() && requested.iter().zip(&version).all(|(a, b)| a == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn binary(path: &str, manager: Option<VersionManager>, version: &str) -> NodeBinary {
+        NodeBinary {
+            path: PathBuf::from(path),
+            manager,
+            version: Some(version.to_string()),
+        }
+    }
+
+    #[test]
+    fn parses_pin_files() {
+        assert_eq!(
+            parse_node_pin("# team default\nv20.11.1\n"),
+            Some("v20.11.1".to_string())
+        );
+        assert_eq!(parse_node_pin("\n\n"), None);
+        assert_eq!(
+            parse_volta_pin(r#"{"name": "web", "volta": {"node": "20.11.1", "npm": "10.2.4"}}"#),
+            Some("20.11.1".to_string())
+        );
+        assert_eq!(parse_volta_pin(r#"{"name": "web"}"#), None);
+        assert_eq!(
+            parse_tool_versions("nodejs 20.11.1 18.19.0\nelixir 1.16.0\npython 3.12.1 # latest\n"),
+            vec![
+                (
+                    "nodejs".to_string(),
+                    vec!["20.11.1".to_string(), "18.19.0".to_string()]
+                ),
+                ("python".to_string(), vec!["3.12.1".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn compares_versions_by_components() {
+        assert!(satisfies("20", "20.11.1"));
+        assert!(satisfies("20.11", "v20.11.1"));
+        assert!(satisfies("3.3.0", "3.3.0p0"));
+        assert!(!satisfies("20.1", "20.11.1"));
+        assert!(!satisfies("20.11.1.4", "20.11.1"));
+
+        let pins = |versions: &[&str]| versions.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            pin_status(&pins(&["v20"]), Some("20.11.1")),
+            PinStatus::Matches
+        );
+        assert_eq!(
+            pin_status(&pins(&["22", "20"]), Some("20.11.1")),
+            PinStatus::Matches
+        );
+        assert_eq!(
+            pin_status(&pins(&["18"]), Some("20.11.1")),
+            PinStatus::Mismatch
+        );
+        assert_eq!(pin_status(&pins(&["18"]), None), PinStatus::NotInstalled);
+        assert_eq!(
+            pin_status(&pins(&["lts/iron"]), Some("20.11.1")),
+            PinStatus::Unresolved
+        );
+    }
+
+    #[test]
+    fn flags_binaries_from_other_sources() {
+        let system = binary("/usr/bin/node", None, "18.19.0");
+        let nvm = binary(
+            "/home/ada/.nvm/versions/node/v20.11.1/bin/node",
+            Some(VersionManager::Nvm),
+            "20.11.1",
+        );
+        let nvm_old = binary(
+            "/home/ada/.nvm/versions/node/v18.0.0/bin/node",
+            Some(VersionManager::Nvm),
+            "18.0.0",
+        );
+
+        let shadowing = find_shadowing(&[system.clone(), nvm.clone(), nvm_old]);
+        assert_eq!(
+            shadowing,
+            vec![Shadowing {
+                active: system.clone(),
+                shadowed: nvm.clone(),
+            }]
+        );
+        assert!(find_shadowing(&[nvm, system.clone()]).len() == 1);
+        assert!(find_shadowing(&[system]).is_empty());
+    }
+
+    #[test]
+    fn attributes_binaries_to_managers() {
+        let managers = vec![ManagerInstall {
+            manager: VersionManager::Volta,
+            root: PathBuf::from("/home/ada/.volta"),
+        }];
+        assert_eq!(
+            owner(Path::new("/home/ada/.volta/bin/node"), &managers),
+            Some(VersionManager::Volta)
+        );
+        assert_eq!(
+            owner(
+                Path::new("/run/user/1000/fnm_multishells/123_456/bin/node"),
+                &managers
+            ),
+            Some(VersionManager::Fnm)
+        );
+        assert_eq!(owner(Path::new("/usr/local/bin/node"), &managers), None);
+    }
+
+    #[test]
+    fn collects_pins_per_project() {
+        let dir = TempDir::new().unwrap();
+        let web = dir.path().join("web");
+        fs::create_dir_all(web.join("node_modules/dep")).unwrap();
+        fs::write(web.join(".nvmrc"), "lts/iron\n").unwrap();
+        fs::write(web.join(".tool-versions"), "elixir 1.16.0\n").unwrap();
+        fs::write(web.join("node_modules/dep/.nvmrc"), "14\n").unwrap();
+        fs::write(dir.path().join("package.json"), r#"{"name": "root"}"#).unwrap();
+
+        let report = scan_runtimes_matching(dir.path(), |_| true);
+        assert_eq!(
+            report.projects.len(),
+            1,
+            "Dependencies and files without pins are skipped"
+        );
+        let project = &report.projects[0];
+        assert_eq!(project.path, web);
+        assert_eq!(project.pins.len(), 1);
+        assert_eq!(project.pins[0].runtime, "nodejs");
+        assert_eq!(project.pins[0].status, PinStatus::Unresolved);
+        assert!(project.mismatches().next().is_none());
+    }
+}
@@ -0,0 +1,563 @@
+//! Disk usage per repository (`scan --sizes`)
+//!
+//! Each repository's size is split into three parts:
+//!
+//! - the `.git` directory: history, packs and the reflog
+//! - build artifacts: `node_modules`, `__pycache__` and `.venv` anywhere in
+//!   the working tree, plus `target`, `build` and `dist` next to the manifest
+//!   of the build tool that writes them
+//! - the rest of the working tree
+//!
+//! Repositories nested in the working tree are left out, since they are
+//! measured on their own. With `history = true` under `[scan]` in
+//! `devhealth.toml`, every measurement is recorded in `size-history.json` in
+//! the DevHealth data directory and the next scan shows how much each
+//! repository grew since, highlighting build artifacts that grew by
+//! [`ARTIFACT_GROWTH_BYTES`] or more.
+//!
+//! `target`, `build` and `dist` directories holding at least
+//! [`STALE_ARTIFACT_BYTES`] that nothing was written to for
+//! [`STALE_ARTIFACT_DAYS`] days are stale: they are reported along with
+//! whether the sources next to them changed since, and are what
+//! `devhealth clean` removes.
+
+use crate::scanner::git::{GitRepo, GitStatus};
+use crate::utils::paths;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the size history inside the data directory
+pub const HISTORY_FILE: &str = "size-history.json";
+
+/// Growth of the build artifacts since the previous scan that is reported
+pub const ARTIFACT_GROWTH_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Build output untouched for this many days is stale
+pub const STALE_ARTIFACT_DAYS: u64 = 90;
+
+/// Stale build output smaller than this is not worth reporting
+pub const STALE_ARTIFACT_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Build output directories, with the manifest that has to sit next to them
+/// (`None` if the name alone identifies them)
+const ARTIFACT_DIRS: [(&str, Option<&str>); 9] = [
+    ("node_modules", None),
+    ("__pycache__", None),
+    (".venv", None),
+    ("target", Some("Cargo.toml")),
+    ("target", Some("pom.xml")),
+    ("build", Some("build.gradle")),
+    ("build", Some("build.gradle.kts")),
+    ("build", Some("CMakeLists.txt")),
+    ("dist", Some("package.json")),
+];
+
+/// Errors that can occur while reading or writing the size history
+#[derive(Error, Debug)]
+pub enum SizeHistoryError {
+    #[error("Failed to access size history: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse size history: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error("Could not determine the DevHealth state directory")]
+    NoStateDirectory,
+}
+
+/// Bytes used by a repository, by part
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoSize {
+    /// Working tree files outside build artifacts
+    pub working_tree: u64,
+    /// The `.git` directory
+    pub git: u64,
+    /// Build artifacts and installed dependencies
+    pub artifacts: u64,
+}
+
+impl RepoSize {
+    /// Returns the size of all parts together
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::sizes::RepoSize;
+    ///
+    /// let size = RepoSize { working_tree: 10, git: 20, artifacts: 300 };
+    /// assert_eq!(size.total(), 330);
+    /// ```
+    pub fn total(&self) -> u64 {
+        self.working_tree + self.git + self.artifacts
+    }
+}
+
+/// A size measured by an earlier scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeRecord {
+    /// Unix timestamp of the scan
+    pub at: u64,
+    /// The size then
+    pub size: RepoSize,
+}
+
+/// A repository's size and, with history enabled, its previous size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskUsage {
+    /// The size now
+    pub size: RepoSize,
+    /// The size at the previous scan, if one was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous: Option<SizeRecord>,
+}
+
+impl DiskUsage {
+    /// Returns how many bytes the repository grew since the previous scan,
+    /// negative if it shrank
+    pub fn growth(&self) -> Option<i64> {
+        self.previous
+            .map(|previous| self.size.total() as i64 - previous.size.total() as i64)
+    }
+
+    /// Returns how many bytes the build artifacts grew since the previous
+    /// scan, negative if they shrank
+    pub fn artifact_growth(&self) -> Option<i64> {
+        self.previous
+            .map(|previous| self.size.artifacts as i64 - previous.size.artifacts as i64)
+    }
+
+    /// Returns `true` if the build artifacts grew by at least
+    /// [`ARTIFACT_GROWTH_BYTES`] since the previous scan
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::sizes::{DiskUsage, RepoSize, SizeRecord, ARTIFACT_GROWTH_BYTES};
+    ///
+    /// let before = RepoSize { working_tree: 0, git: 0, artifacts: 0 };
+    /// let usage = DiskUsage {
+    ///     size: RepoSize { artifacts: 2 * ARTIFACT_GROWTH_BYTES, ..before },
+    ///     previous: Some(SizeRecord { at: 0, size: before }),
+    /// };
+    /// assert!(usage.artifacts_ballooning());
+    /// ```
+    pub fn artifacts_ballooning(&self) -> bool {
+        self.artifact_growth()
+            .is_some_and(|growth| growth >= ARTIFACT_GROWTH_BYTES as i64)
+    }
+}
+
+/// A build output directory nothing was written to for months
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaleArtifacts {
+    /// The directory, e.g. `<repo>/target`
+    pub path: PathBuf,
+    /// Bytes it holds
+    pub bytes: u64,
+    /// Unix timestamp of the newest file in it
+    pub last_modified: u64,
+    /// Whether source files next to it changed after it was last written,
+    /// so a build would rewrite it anyway
+    pub sources_changed: bool,
+}
+
+/// Sizes recorded by the last scan of each repository
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeHistory {
+    /// Repository root to its last recorded size
+    #[serde(default)]
+    pub repositories: BTreeMap<PathBuf, SizeRecord>,
+}
+
+impl SizeHistory {
+    /// Returns the default location of the size history
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SizeHistoryError::NoStateDirectory`] if no home directory is known.
+    pub fn default_path() -> Result<PathBuf, SizeHistoryError> {
+        paths::data_dir()
+            .map(|dir| dir.join(HISTORY_FILE))
+            .ok_or(SizeHistoryError::NoStateDirectory)
+    }
+
+    /// Loads the history from `path`, returning an empty history if missing
+    pub fn load(path: &Path) -> Result<SizeHistory, SizeHistoryError> {
+        if !path.exists() {
+            return Ok(SizeHistory::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the history to `path`, creating parent directories if needed
+    pub fn save(&self, path: &Path) -> Result<(), SizeHistoryError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Attaches each repository's previous size and records its current one
+    ///
+    /// Repositories that were not measured are left alone, so their earlier
+    /// records survive scans of other directories.
+    pub fn record(&mut self, repos: &mut [GitRepo], now: u64) {
+        for repo in repos.iter_mut() {
+            if let Some(usage) = repo.disk.as_mut() {
+                usage.previous = self.repositories.get(&repo.path).copied();
+                self.repositories.insert(
+                    repo.path.clone(),
+                    SizeRecord {
+                        at: now,
+                        size: usage.size,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Drops the sizes recorded before `oldest` and returns how many were
+    /// dropped
+    ///
+    /// These belong to repositories no scan has measured since, which were
+    /// often moved or deleted.
+    pub fn prune(&mut self, oldest: u64) -> usize {
+        let before = self.repositories.len();
+        self.repositories.retain(|_, record| record.at >= oldest);
+        before - self.repositories.len()
+    }
+}
+
+/// Measures every repository and finds its stale build output, updating
+/// them in place
+///
+/// Repositories that could not be analyzed are skipped.
+///
+/// # Arguments
+///
+/// * `repos` - Repositories from the git scanner
+/// * `now` - Current Unix timestamp, the end of the [`STALE_ARTIFACT_DAYS`] window
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use devhealth::scanner::{git, sizes};
+/// use devhealth::utils::display;
+/// use std::path::Path;
+///
+/// let mut repos = git::scan_directory(Path::new("."))?;
+/// sizes::enrich_repositories(&mut repos, display::unix_now());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn enrich_repositories(repos: &mut [GitRepo], now: u64) {
+    for repo in repos
+        .iter_mut()
+        .filter(|repo| !matches!(repo.status, GitStatus::Error(_)))
+    {
+        let measurement = walk_repository(&repo.path);
+        let cutoff = now.saturating_sub(STALE_ARTIFACT_DAYS * 86_400);
+        repo.stale_artifacts = measurement
+            .artifacts
+            .into_iter()
+            .filter(|artifacts| {
+                artifacts.last_modified <= cutoff && artifacts.bytes >= STALE_ARTIFACT_BYTES
+            })
+            .collect();
+        repo.disk = Some(DiskUsage {
+            size: measurement.size,
+            previous: None,
+        });
+    }
+}
+
+/// Returns whether git ignores `dir` in the repository at `repo_path` and
+/// tracks no files below it, so removing it loses nothing committed
+///
+/// Anything git cannot answer for counts as not disposable.
+pub fn is_disposable(repo_path: &Path, dir: &Path) -> bool {
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo_path)
+            .args(args)
+            .arg(dir)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+    };
+    let ignored = git(&["check-ignore", "-q", "--"]).is_some();
+    ignored && git(&["ls-files", "--"]).is_some_and(|output| output.stdout.is_empty())
+}
+
+/// Measures the working tree, `.git` directory and build artifacts of a
+/// repository
+pub fn measure(repo_path: &Path) -> RepoSize {
+    walk_repository(repo_path).size
+}
+
+/// What walking a working tree found
+#[derive(Default)]
+struct Measurement {
+    size: RepoSize,
+    /// Every `target`, `build` and `dist` directory, stale or not
+    artifacts: Vec<StaleArtifacts>,
+}
+
+/// Measures a repository and lists its `target`, `build` and `dist`
+/// directories
+fn walk_repository(repo_path: &Path) -> Measurement {
+    let mut measurement = Measurement::default();
+    measurement.size.git = directory_size(&repo_path.join(".git")).0;
+    measure_tree(repo_path, &mut measurement);
+    measurement
+}
+
+/// Adds the files below `dir` to the working tree or artifact size
+///
+/// Returns the newest modification time of the source files below `dir`,
+/// which `target`, `build` and `dist` directories in it are compared with.
+fn measure_tree(dir: &Path, measurement: &mut Measurement) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut newest_source = 0;
+    let mut build_dirs = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            if entry.file_name() == ".git" || path.join(".git").exists() {
+                continue;
+            }
+            match artifact_kind(&path) {
+                Some(manifest) => {
+                    let (bytes, last_modified) = directory_size(&path);
+                    measurement.size.artifacts += bytes;
+                    if manifest.is_some() {
+                        build_dirs.push((path, bytes, last_modified));
+                    }
+                }
+                None => newest_source = newest_source.max(measure_tree(&path, measurement)),
+            }
+        } else if file_type.is_file() {
+            let metadata = entry.metadata().ok();
+            measurement.size.working_tree += metadata
+                .as_ref()
+                .map(|metadata| metadata.len())
+                .unwrap_or(0);
+            newest_source = newest_source.max(metadata.as_ref().map(modified).unwrap_or(0));
+        }
+    }
+    measurement
+        .artifacts
+        .extend(
+            build_dirs
+                .into_iter()
+                .map(|(path, bytes, last_modified)| StaleArtifacts {
+                    path,
+                    bytes,
+                    last_modified,
+                    sources_changed: newest_source > last_modified,
+                }),
+        );
+    newest_source
+}
+
+/// Returns the manifest a build output directory belongs to: `Some(None)`
+/// for directories the name alone identifies, `None` for other directories
+fn artifact_kind(dir: &Path) -> Option<Option<&'static str>> {
+    let name = dir.file_name().and_then(|name| name.to_str())?;
+    let parent = dir.parent().unwrap_or(dir);
+    ARTIFACT_DIRS
+        .iter()
+        .find(|(artifact, manifest)| {
+            *artifact == name && manifest.is_none_or(|manifest| parent.join(manifest).is_file())
+        })
+        .map(|(_, manifest)| *manifest)
+}
+
+/// Returns the size of all files below `dir` and the newest modification
+/// time among them, without following links
+fn directory_size(dir: &Path) -> (u64, u64) {
+    crate::utils::fs::walk(dir)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .fold((0, 0), |(bytes, newest), metadata| {
+            (bytes + metadata.len(), newest.max(modified(&metadata)))
+        })
+}
+
+/// Returns a file's modification time as a Unix timestamp, 0 if unknown
+fn modified(metadata: &std::fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |age| age.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, bytes: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![b'x'; bytes]).unwrap();
+    }
+
+    #[test]
+    fn splits_working_tree_git_and_artifacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        write(&repo.join(".git").join("HEAD"), 20);
+        write(&repo.join("Cargo.toml"), 10);
+        write(&repo.join("target").join("debug").join("app"), 1000);
+        write(
+            &repo
+                .join("web")
+                .join("node_modules")
+                .join("react")
+                .join("index.js"),
+            300,
+        );
+        write(&repo.join("docs").join("build").join("index.html"), 5);
+        write(
+            &repo.join("vendor").join("lib").join(".git").join("HEAD"),
+            7000,
+        );
+
+        assert_eq!(
+            measure(repo),
+            RepoSize {
+                working_tree: 15,
+                git: 20,
+                artifacts: 1300,
+            },
+            "docs/build has no manifest next to it and nested repositories are left out"
+        );
+    }
+
+    #[test]
+    fn finds_build_output_untouched_for_months() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(repo)
+            .status()
+            .unwrap();
+        write(&repo.join("Cargo.toml"), 10);
+        write(&repo.join("src").join("main.rs"), 10);
+        write(&repo.join("web").join("package.json"), 2);
+        write(&repo.join("web").join("dist").join("app.js"), 10);
+        let binary = repo.join("target").join("debug").join("app");
+        write(&binary, 0);
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(&binary)
+            .unwrap();
+        file.set_len(STALE_ARTIFACT_BYTES).unwrap();
+        let now = modified(&std::fs::metadata(repo.join("Cargo.toml")).unwrap());
+        let built = std::time::UNIX_EPOCH
+            + std::time::Duration::from_secs(now - (STALE_ARTIFACT_DAYS + 10) * 86_400);
+        file.set_modified(built).unwrap();
+
+        let mut repos = vec![crate::scanner::git::analyze_repository(repo)];
+        enrich_repositories(&mut repos, now);
+
+        assert_eq!(
+            repos[0].stale_artifacts.len(),
+            1,
+            "Small or recent build output is fine: {:?}",
+            repos[0].stale_artifacts
+        );
+        let stale = &repos[0].stale_artifacts[0];
+        assert_eq!(stale.path, repo.join("target"));
+        assert_eq!(stale.bytes, STALE_ARTIFACT_BYTES);
+        assert!(stale.sources_changed, "main.rs is newer than the build");
+
+        enrich_repositories(&mut repos, now - 20 * 86_400);
+        assert!(
+            repos[0].stale_artifacts.is_empty(),
+            "Not stale before 90 days"
+        );
+    }
+
+    #[test]
+    fn only_ignored_untracked_output_is_disposable() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo)
+                .status()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        std::fs::write(repo.join(".gitignore"), "target/\nbuild/\n").unwrap();
+        write(&repo.join("target").join("debug").join("app"), 10);
+        write(&repo.join("build").join("generated.h"), 10);
+        write(&repo.join("dist").join("app.js"), 10);
+        git(&["add", "-f", "build/generated.h"]);
+
+        assert!(is_disposable(repo, &repo.join("target")));
+        assert!(
+            !is_disposable(repo, &repo.join("build")),
+            "build/ holds a tracked file"
+        );
+        assert!(
+            !is_disposable(repo, &repo.join("dist")),
+            "dist/ is not ignored"
+        );
+        assert!(
+            !is_disposable(&repo.join("missing"), &repo.join("target")),
+            "git fails outside a repository"
+        );
+    }
+
+    #[test]
+    fn records_sizes_between_scans() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state").join(HISTORY_FILE);
+        let mut repos = vec![crate::scanner::git::analyze_repository(temp_dir.path())];
+        let size = |artifacts| RepoSize {
+            working_tree: 100,
+            git: 50,
+            artifacts,
+        };
+
+        repos[0].disk = Some(DiskUsage {
+            size: size(0),
+            previous: None,
+        });
+        let mut history = SizeHistory::load(&path).unwrap();
+        history.record(&mut repos, 1_000);
+        history.save(&path).unwrap();
+        assert_eq!(
+            repos[0].disk.unwrap().growth(),
+            None,
+            "Nothing to compare on the first scan"
+        );
+
+        repos[0].disk = Some(DiskUsage {
+            size: size(ARTIFACT_GROWTH_BYTES),
+            previous: None,
+        });
+        let mut history = SizeHistory::load(&path).unwrap();
+        history.record(&mut repos, 2_000);
+        let usage = repos[0].disk.unwrap();
+        assert_eq!(usage.previous.map(|previous| previous.at), Some(1_000));
+        assert_eq!(usage.growth(), Some(ARTIFACT_GROWTH_BYTES as i64));
+        assert!(usage.artifacts_ballooning());
+        assert_eq!(history.repositories[&repos[0].path].at, 2_000);
+
+        assert_eq!(history.prune(1_500), 0);
+        assert_eq!(history.prune(2_500), 1);
+        assert!(history.repositories.is_empty());
+    }
+}
@@ -1,7 +1,20 @@
-//! System resource monitoring (planned feature)
+//! System resource monitoring
 //!
-//! This module will provide functionality for monitoring system resources
-//! and their impact on development productivity, including:
+//! [`snapshot`] takes a quick, read-only look at memory and disk space for
+//! `devhealth check`, and notes whether DevHealth runs inside WSL. `scan --system`
+//! adds the developer tools the system package manager can update (see
+//! [`packages`](crate::scanner::packages)) and the tools installed more than
+//! once on `PATH` (see [`binaries`](crate::scanner::binaries)), checks the
+//! clock (see [`clock`](crate::scanner::clock)), the CA bundles (see
+//! [`certs`](crate::scanner::certs)), the proxy settings (see
+//! [`proxy`](crate::scanner::proxy)), whether build directories are
+//! excluded from antivirus scanning and indexing (see
+//! [`exclusions`](crate::scanner::exclusions)), the power source and mode
+//! (see [`power`](crate::scanner::power)), the inode and open-file limits
+//! (see [`limits`](crate::scanner::limits)), the locale (see
+//! [`locale`](crate::scanner::locale)) and the global git configuration (see
+//! [`gitconfig`](crate::scanner::gitconfig)). Full monitoring is still a planned
+//! feature and will include:
 //!
 //! - CPU usage and load averages
 //! - Memory consumption and availability
@@ -9,30 +22,637 @@
 //! - Network connectivity and bandwidth
 //! - Development tool performance metrics
 
-/// Monitors system resources and performance metrics
+use crate::i18n::t;
+use crate::scanner::binaries::DuplicateBinary;
+use crate::scanner::certs::{BundleProblem, CaBundle};
+use crate::scanner::clock::ClockReport;
+use crate::scanner::exclusions::{ExclusionReport, Scanner};
+use crate::scanner::gitconfig::GitConfigReport;
+use crate::scanner::limits::{self, LimitsReport};
+use crate::scanner::locale::{LocaleProblem, LocaleReport};
+use crate::scanner::packages::PackageReport;
+use crate::scanner::power::{PowerMode, PowerReport, PowerSource};
+use crate::scanner::proxy::{ProxyReport, TlsStatus};
+use crate::tr;
+use crate::utils::display;
+use crate::utils::theme::{Paint, Role};
+use crate::utils::wsl::{self, WslInfo};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Memory and disk space at one point in time
+///
+/// Values the platform cannot report are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    /// Total physical memory in bytes
+    pub memory_total: Option<u64>,
+    /// Memory available to new processes in bytes
+    pub memory_available: Option<u64>,
+    /// Directory whose file system was measured
+    pub disk_path: PathBuf,
+    /// Size of the file system holding `disk_path` in bytes
+    pub disk_total: Option<u64>,
+    /// Space available to unprivileged users on that file system in bytes
+    pub disk_available: Option<u64>,
+    /// WSL version and distribution, when running inside WSL
+    pub wsl: Option<WslInfo>,
+    /// Outdated developer tools per package manager (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<PackageReport>,
+    /// Developer tools on `PATH` more than once with different versions
+    /// (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DuplicateBinary>,
+    /// Clock synchronization and offset (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock: Option<ClockReport>,
+    /// System and configured CA bundles (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub certificates: Vec<CaBundle>,
+    /// Proxy settings and TLS interception (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyReport>,
+    /// Build directories scanned by antivirus or indexed by Spotlight
+    /// (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclusions: Option<ExclusionReport>,
+    /// Power source, battery level and throttling on laptops
+    /// (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<PowerReport>,
+    /// Inode usage and open-file limit (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<LimitsReport>,
+    /// Locale and whether it is UTF-8 (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<LocaleReport>,
+    /// Recommended global git settings that are missing (`scan --system` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_config: Option<GitConfigReport>,
+}
+
+/// Takes a memory and disk space snapshot
 ///
-/// This is a placeholder function for future system monitoring functionality.
-/// When implemented, it will analyze system resources to provide insights into:
-/// - Current resource utilization
-/// - Performance bottlenecks
-/// - Resource recommendations for development
-/// - System health warnings
+/// Reads `/proc/meminfo` for memory (Linux only) and queries the file system
+/// holding `path` for disk space (Unix only). Never fails; unavailable values
+/// are left as `None`. Inside WSL the distribution and WSL version are
+/// recorded as well.
 ///
-/// # Note
+/// # Arguments
 ///
-/// This function is currently not implemented and serves as a placeholder
-/// for future development.
+/// * `path` - A directory on the file system to measure
 ///
 /// # Examples
 ///
 /// ```rust
 /// use devhealth::scanner::system;
+/// use std::path::Path;
 ///
-/// // Future usage (not yet implemented)
-/// system::monitor_system();
+/// let snapshot = system::snapshot(Path::new("."));
+/// system::display_snapshot(&snapshot);
 /// ```
-pub fn monitor_system() {
-    println!("System monitoring not implemented yet!");
+pub fn snapshot(path: &Path) -> SystemSnapshot {
+    let (memory_total, memory_available) = std::fs::read_to_string("/proc/meminfo")
+        .map(|meminfo| parse_meminfo(&meminfo))
+        .unwrap_or((None, None));
+    let (disk_total, disk_available) = disk_space(path);
+
+    SystemSnapshot {
+        memory_total,
+        memory_available,
+        disk_path: path.to_path_buf(),
+        disk_total,
+        disk_available,
+        wsl: wsl::detect(),
+        packages: Vec::new(),
+        duplicates: Vec::new(),
+        clock: None,
+        certificates: Vec::new(),
+        proxy: None,
+        exclusions: None,
+        power: None,
+        limits: None,
+        locale: None,
+        git_config: None,
+    }
+}
+
+/// Prints a snapshot as a single line, followed by any outdated tools
+///
+/// Free space is colored as a warning below 25% and as an error below 10%.
+/// The line is truncated to the terminal width. Outdated tools are listed per
+/// package manager with the commands that update them, and tools found on
+/// `PATH` more than once with every installation, the one that runs first.
+/// The clock and every CA bundle get a line each, colored as a warning or
+/// error when they are off or broken. Proxy settings are listed per tool
+/// when a proxy is configured or a connection is intercepted. Build
+/// directories still scanned by antivirus or indexed are listed with the
+/// command that excludes each. On laptops the power source, battery level and
+/// power mode get a line, with a warning when builds will be slow or a long
+/// scan may drain the battery. Inode usage and the open-file limit share a
+/// line, followed by how to fix either when it is low. A locale that is not
+/// UTF-8 is shown with the commands that fix it for the user's shell, and
+/// missing recommended git settings with the `git config` commands that set
+/// them.
+///
+/// # Arguments
+///
+/// * `snapshot` - The snapshot to display
+pub fn display_snapshot(snapshot: &SystemSnapshot) {
+    let line = tr!(
+        "system-snapshot",
+        memory = free_of(snapshot.memory_available, snapshot.memory_total),
+        disk = free_of(snapshot.disk_available, snapshot.disk_total)
+    );
+    let line = match &snapshot.wsl {
+        Some(wsl) => format!("{}, {}", line, wsl),
+        None => line,
+    };
+    let line = display::symbols(&format!("💻 {}", line)).into_owned();
+    println!("{}", display::truncate(&line, display::terminal_width()));
+
+    if let Some(clock) = snapshot
+        .clock
+        .as_ref()
+        .filter(|c| c.synchronized.is_some() || c.offset_secs.is_some())
+    {
+        let mut parts = Vec::new();
+        match clock.synchronized {
+            Some(true) => parts.push(t("system-clock-synced").to_string()),
+            Some(false) => parts.push(t("system-clock-unsynced").paint(Role::Warning).to_string()),
+            None => {}
+        }
+        if let Some(offset) = clock.offset_secs {
+            let text = tr!("system-clock-offset", offset = format!("{:+}", offset));
+            let role = if clock.is_skewed() {
+                Role::Error
+            } else {
+                Role::Success
+            };
+            parts.push(text.paint(role).to_string());
+        }
+        let line = tr!("system-clock", state = parts.join(", "));
+        println!("{}", display::symbols(&format!("🕒 {}", line)));
+    }
+
+    if let Some(report) = snapshot
+        .limits
+        .as_ref()
+        .filter(|report| report.inodes.is_some() || report.open_files.is_some())
+    {
+        let mut parts = Vec::new();
+        let mut hints = Vec::new();
+        if let Some(inodes) = &report.inodes {
+            let text = tr!("system-limits-inodes", percent = inodes.available_percent());
+            parts.push(if inodes.is_low() {
+                text.paint(Role::Error).to_string()
+            } else {
+                text
+            });
+            if inodes.is_low() {
+                let command = limits::busiest_directories_command(&inodes.path);
+                hints.push(tr!(
+                    "system-limits-inodes-hint",
+                    command = command.as_str().paint(Role::Accent)
+                ));
+            }
+        }
+        if let Some(open_files) = &report.open_files {
+            let hard = open_files.hard.map_or_else(
+                || t("system-limits-unlimited").to_string(),
+                |hard| hard.to_string(),
+            );
+            let text = tr!("system-limits-files", soft = open_files.soft, hard = hard);
+            parts.push(if open_files.is_low() {
+                text.paint(Role::Warning).to_string()
+            } else {
+                text
+            });
+            if open_files.is_low() {
+                let command = format!("ulimit -n {}", open_files.target());
+                hints.push(tr!(
+                    "system-limits-files-hint",
+                    command = command.as_str().paint(Role::Accent)
+                ));
+                if open_files.needs_privileges() {
+                    hints.push(match limits::raise_hard_limit_command() {
+                        Some(command) => tr!(
+                            "system-limits-hard-hint-command",
+                            command = command.as_str().paint(Role::Accent)
+                        ),
+                        None => t("system-limits-hard-hint-linux").to_string(),
+                    });
+                }
+            }
+        }
+        println!(
+            "{}",
+            display::symbols(&format!(
+                "🗃️  {}",
+                tr!("system-limits", limits = parts.join(", "))
+            ))
+        );
+        for (i, hint) in hints.iter().enumerate() {
+            println!("{}", display::tree_item(hint, i + 1 == hints.len(), 0));
+        }
+    }
+
+    if let Some(locale) = &snapshot.locale {
+        let name = match (&locale.variable, &locale.locale) {
+            (Some(variable), Some(value)) => format!("{}={}", variable, value),
+            _ => t("system-locale-unset").to_string(),
+        };
+        let line = match &locale.encoding {
+            Some(encoding) => tr!("system-locale", locale = name, encoding = encoding.as_str()),
+            None => tr!("system-locale-no-encoding", locale = name),
+        };
+        let line = match locale.problem {
+            None => line,
+            Some(problem) => {
+                let reason = match problem {
+                    LocaleProblem::Unset => t("system-locale-problem-unset"),
+                    LocaleProblem::NotUtf8 => t("system-locale-problem-not-utf8"),
+                    LocaleProblem::NotInstalled => t("system-locale-problem-not-installed"),
+                    LocaleProblem::ConsoleCodePage => t("system-locale-problem-code-page"),
+                };
+                format!("{} — {}", line, reason)
+                    .paint(Role::Warning)
+                    .to_string()
+            }
+        };
+        println!("{}", display::symbols(&format!("🔤 {}", line)));
+        for (i, command) in locale.fix.iter().enumerate() {
+            let line = tr!(
+                "system-locale-fix",
+                command = command.as_str().paint(Role::Accent)
+            );
+            println!(
+                "{}",
+                display::tree_item(&line, i + 1 == locale.fix.len(), 0)
+            );
+        }
+    }
+
+    if let Some(git_config) = snapshot
+        .git_config
+        .as_ref()
+        .filter(|report| !report.deviations.is_empty())
+    {
+        let line = tr!(
+            "system-git-config",
+            path = git_config.path.display().to_string(),
+            count = git_config.deviations.len()
+        );
+        println!("{}", display::symbols(&format!("🔧 {}", line)));
+        for (i, setting) in git_config.deviations.iter().enumerate() {
+            let current = setting
+                .current
+                .as_deref()
+                .unwrap_or(t("system-git-config-unset"));
+            let line = tr!(
+                "system-git-config-setting",
+                key = setting.key.as_str().paint(Role::Emphasis),
+                current = current,
+                command = setting.command().paint(Role::Accent)
+            );
+            println!(
+                "{}",
+                display::tree_item(&line, i + 1 == git_config.deviations.len(), 0)
+            );
+        }
+    }
+
+    if let Some(power) = &snapshot.power {
+        let mut parts = Vec::new();
+        match power.source {
+            Some(PowerSource::Ac) => parts.push(t("system-power-ac").to_string()),
+            Some(PowerSource::Battery) => parts.push(t("system-power-battery").to_string()),
+            None => {}
+        }
+        if let Some(percent) = power.battery_percent {
+            let level = if power.charging == Some(true) {
+                tr!("system-power-charging", percent = percent)
+            } else {
+                tr!("system-power-level", percent = percent)
+            };
+            parts.push(if power.is_draining() {
+                level.paint(Role::Error).to_string()
+            } else {
+                level
+            });
+        }
+        match power.mode {
+            Some(PowerMode::LowPower) => {
+                parts.push(t("system-power-low-power").paint(Role::Warning).to_string())
+            }
+            Some(PowerMode::Balanced) => parts.push(t("system-power-balanced").to_string()),
+            Some(PowerMode::Performance) => parts.push(t("system-power-performance").to_string()),
+            None => {}
+        }
+        if let Some(limit) = power.cpu_speed_limit.filter(|limit| *limit < 100) {
+            parts.push(
+                tr!("system-power-cpu-limit", percent = limit)
+                    .paint(Role::Warning)
+                    .to_string(),
+            );
+        }
+        println!(
+            "{}",
+            display::symbols(&format!(
+                "🔋 {}",
+                tr!("system-power", state = parts.join(", "))
+            ))
+        );
+
+        let mut warnings = Vec::new();
+        if power.slows_builds() {
+            warnings.push(t("system-power-slow").paint(Role::Warning).to_string());
+        }
+        if power.is_draining() {
+            warnings.push(t("system-power-drain").paint(Role::Error).to_string());
+        }
+        for (i, warning) in warnings.iter().enumerate() {
+            println!(
+                "{}",
+                display::tree_item(warning, i + 1 == warnings.len(), 0)
+            );
+        }
+    }
+
+    for bundle in &snapshot.certificates {
+        let source = bundle.variable.as_deref().unwrap_or(t("system-ca-system"));
+        let line = tr!(
+            "system-ca-bundle",
+            path = bundle.path.display().to_string(),
+            source = source,
+            count = bundle.certificates
+        );
+        let line = match bundle.problem {
+            None => line,
+            Some(problem) => {
+                let reason = match problem {
+                    BundleProblem::Missing => t("system-ca-missing"),
+                    BundleProblem::Empty => t("system-ca-empty"),
+                    BundleProblem::Truncated => t("system-ca-truncated"),
+                    BundleProblem::Incomplete => t("system-ca-incomplete"),
+                };
+                format!("{} — {}", line, reason)
+                    .paint(Role::Error)
+                    .to_string()
+            }
+        };
+        println!("{}", display::symbols(&format!("🔐 {}", line)));
+    }
+
+    if let Some(proxy) = snapshot.proxy.as_ref().filter(|proxy| proxy.is_relevant()) {
+        let variables: Vec<String> = proxy
+            .variables
+            .iter()
+            .map(|variable| format!("{}={}", variable.name, variable.value))
+            .collect();
+        let variables = if variables.is_empty() {
+            t("system-proxy-no-variables").to_string()
+        } else {
+            variables.join(", ")
+        };
+        let line = tr!("system-proxy", variables = variables);
+        let line = if proxy.is_mismatched() {
+            line.paint(Role::Warning).to_string()
+        } else {
+            line
+        };
+        println!("{}", display::symbols(&format!("🌐 {}", line)));
+
+        let mut lines: Vec<String> = proxy
+            .tools
+            .iter()
+            .map(|tool| match (&tool.proxy, &tool.source) {
+                (Some(url), Some(source)) => tr!(
+                    "system-proxy-tool",
+                    tool = tool.tool.as_str(),
+                    proxy = url.as_str(),
+                    source = source.as_str()
+                ),
+                _ => tr!("system-proxy-direct", tool = tool.tool.as_str()),
+            })
+            .collect();
+        lines.extend(proxy.endpoints.iter().map(|endpoint| {
+            let issuer = endpoint.issuer.as_deref().unwrap_or(t("system-unknown"));
+            let name = endpoint.name.as_str();
+            match endpoint.status {
+                TlsStatus::Genuine => tr!("system-tls-genuine", endpoint = name, issuer = issuer)
+                    .paint(Role::Muted)
+                    .to_string(),
+                TlsStatus::Intercepted => {
+                    tr!("system-tls-intercepted", endpoint = name, issuer = issuer)
+                        .paint(Role::Warning)
+                        .to_string()
+                }
+                TlsStatus::Untrusted => tr!("system-tls-untrusted", endpoint = name)
+                    .paint(Role::Error)
+                    .to_string(),
+                TlsStatus::Unreachable => tr!("system-tls-unreachable", endpoint = name)
+                    .paint(Role::Muted)
+                    .to_string(),
+            }
+        }));
+        for (i, line) in lines.iter().enumerate() {
+            println!("{}", display::tree_item(line, i + 1 == lines.len(), 0));
+        }
+    }
+
+    if let Some(exclusions) = &snapshot.exclusions {
+        let scanner = exclusions.scanner.name();
+        let line = if exclusions.unexcluded.is_empty() {
+            tr!(
+                "system-exclusions-ok",
+                scanner = scanner,
+                count = exclusions.checked
+            )
+        } else {
+            let impact = match exclusions.scanner {
+                Scanner::Defender => t("system-exclusions-defender-impact"),
+                Scanner::Spotlight => t("system-exclusions-spotlight-impact"),
+            };
+            let line = tr!(
+                "system-exclusions",
+                scanner = scanner,
+                count = exclusions.unexcluded.len(),
+                total = exclusions.checked
+            );
+            format!("{} {}", line, impact)
+                .paint(Role::Warning)
+                .to_string()
+        };
+        println!("{}", display::symbols(&format!("🛡️  {}", line)));
+        for (i, dir) in exclusions.unexcluded.iter().enumerate() {
+            let is_last = i + 1 == exclusions.unexcluded.len() && exclusions.unverified == 0;
+            println!(
+                "{}",
+                display::tree_item(&dir.path.display().to_string(), is_last, 0)
+            );
+            println!(
+                "{}",
+                display::tree_continuation(
+                    &dir.command.as_str().paint(Role::Accent).to_string(),
+                    is_last,
+                    0
+                )
+            );
+        }
+        if exclusions.unverified > 0 {
+            let line = tr!(
+                "system-exclusions-unverified",
+                count = exclusions.unverified
+            );
+            println!(
+                "{}",
+                display::tree_item(&line.paint(Role::Muted).to_string(), true, 0)
+            );
+        }
+    }
+
+    for report in snapshot
+        .packages
+        .iter()
+        .filter(|report| !report.outdated.is_empty())
+    {
+        println!(
+            "{}",
+            display::symbols(&format!(
+                "📦 {}",
+                tr!(
+                    "system-outdated-tools",
+                    manager = report.manager.to_string(),
+                    count = report.outdated.len()
+                )
+            ))
+        );
+        for tool in &report.outdated {
+            let line = tr!(
+                "system-outdated-tool",
+                name = tool.name.as_str().paint(Role::Emphasis),
+                installed = tool.installed.as_str(),
+                available = tool.available.as_str().paint(Role::Success)
+            );
+            println!("{}", display::tree_item(&line, false, 0));
+        }
+        for (i, command) in report.update_commands.iter().enumerate() {
+            let line = tr!(
+                "system-update-with",
+                command = command.as_str().paint(Role::Accent)
+            );
+            println!(
+                "{}",
+                display::tree_item(&line, i + 1 == report.update_commands.len(), 0)
+            );
+        }
+    }
+
+    for duplicate in &snapshot.duplicates {
+        let winner = duplicate.winner();
+        let line = tr!(
+            "system-duplicate-binary",
+            name = duplicate.name.as_str(),
+            count = duplicate.installations.len(),
+            path = winner.path.display().to_string()
+        );
+        println!(
+            "{}",
+            display::symbols(&format!("🔀 {}", line.paint(Role::Warning)))
+        );
+        for (i, installation) in duplicate.installations.iter().enumerate() {
+            let version = installation
+                .version
+                .as_deref()
+                .unwrap_or(t("system-unknown"));
+            let line = if i == 0 {
+                tr!(
+                    "system-binary-wins",
+                    path = installation.path.display().to_string(),
+                    version = version.paint(Role::Emphasis)
+                )
+            } else {
+                tr!(
+                    "system-binary-shadowed",
+                    path = installation.path.display().to_string(),
+                    version = version
+                )
+                .paint(Role::Muted)
+                .to_string()
+            };
+            println!(
+                "{}",
+                display::tree_item(&line, i + 1 == duplicate.installations.len(), 0)
+            );
+        }
+    }
+}
+
+/// Formats "<free> free of <total>", colored by the share still free
+fn free_of(available: Option<u64>, total: Option<u64>) -> String {
+    let (Some(available), Some(total)) = (available, total) else {
+        return t("system-unknown").to_string();
+    };
+
+    let role = match available
+        .saturating_mul(100)
+        .checked_div(total)
+        .unwrap_or(0)
+    {
+        0..=9 => Role::Error,
+        10..=24 => Role::Warning,
+        _ => Role::Success,
+    };
+    tr!(
+        "system-free-of",
+        free = display::bytes(available).paint(role).bold(),
+        total = display::bytes(total)
+    )
+}
+
+/// Extracts `MemTotal` and `MemAvailable` from `/proc/meminfo`, in bytes
+fn parse_meminfo(meminfo: &str) -> (Option<u64>, Option<u64>) {
+    let field = |name: &str| {
+        meminfo.lines().find_map(|line| {
+            let value = line.strip_prefix(name)?.strip_prefix(':')?;
+            let kib: u64 = value.trim().trim_end_matches("kB").trim().parse().ok()?;
+            Some(kib * 1024)
+        })
+    };
+    (field("MemTotal"), field("MemAvailable"))
+}
+
+/// Returns the total and available bytes of the file system holding `path`
+#[cfg(unix)]
+fn disk_space(path: &Path) -> (Option<u64>, Option<u64>) {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return (None, None);
+    };
+    // SAFETY: `statvfs` is plain data, so all-zero is a valid value.
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stats` points to
+    // a valid, properly aligned local that statvfs fills in.
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stats) } != 0 {
+        return (None, None);
+    }
+
+    let fragment = stats.f_frsize as u64;
+    (
+        Some(stats.f_blocks as u64 * fragment),
+        Some(stats.f_bavail as u64 * fragment),
+    )
+}
+
+/// Returns the total and available bytes of the file system holding `path`
+#[cfg(not(unix))]
+fn disk_space(_path: &Path) -> (Option<u64>, Option<u64>) {
+    (None, None)
 }
 
 #[cfg(test)]
@@ -40,8 +660,27 @@ mod tests {
     use super::*;
 
     #[test]
-    fn monitor_system_does_not_panic() {
-        // Ensure the placeholder function can be called without issues
-        monitor_system();
+    fn parses_meminfo_fields() {
+        let meminfo =
+            "MemTotal:       16384000 kB\nMemFree:         1024 kB\nMemAvailable:    8192000 kB\n";
+        assert_eq!(
+            parse_meminfo(meminfo),
+            (Some(16_384_000 * 1024), Some(8_192_000 * 1024))
+        );
+        assert_eq!(parse_meminfo("garbage"), (None, None));
+    }
+
+    #[test]
+    fn formats_unknown_values() {
+        assert_eq!(free_of(None, Some(1024)), "unknown");
+        assert!(free_of(Some(512), Some(1024)).contains("free of 1.0 KiB"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn measures_disk_space_of_existing_paths() {
+        let snapshot = snapshot(Path::new("."));
+        assert!(snapshot.disk_total.is_some());
+        assert!(snapshot.disk_available <= snapshot.disk_total);
     }
 }
@@ -0,0 +1,1191 @@
+//! Monorepo workspace analysis
+//!
+//! A monorepo holds many internal packages under one workspace root. This
+//! scanner finds workspace roots, reports the health of every internal
+//! package, and flags external dependencies that different packages pin to
+//! different versions.
+//!
+//! Recognized workspace roots:
+//!
+//! - **Cargo**: `Cargo.toml` with a `[workspace]` table (`members`, `exclude`)
+//! - **pnpm**: `pnpm-workspace.yaml` (`packages`, with `!` exclusions)
+//! - **npm and Yarn**: `package.json` with a `workspaces` field
+//! - **Nx**: `nx.json`; packages are the directories holding `project.json`
+//!   when no package manager workspaces are configured
+//! - **Turborepo**: `turbo.json`, whose packages come from the package
+//!   manager workspaces
+//! - **Go**: `go.work` (`use` directives)
+//!
+//! ## Internal dependency graph
+//!
+//! Packages depending on other packages of the same workspace form a
+//! directed graph. The scanner reports dependency cycles (which most build
+//! tools reject or handle poorly), the packages most depended on (fan-in)
+//! and those with the most internal dependencies (fan-out), and can export
+//! the graph in Graphviz DOT format with [`to_dot`].
+
+use crate::i18n::t;
+use crate::scanner::deps::{self, Dependency, Ecosystem};
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::fs as dh_fs;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write as _};
+use std::path::{Path, PathBuf};
+
+/// Number of packages listed for the largest fan-in and fan-out
+const FAN_LEADERS: usize = 3;
+
+/// Version requirements that do not pin an external version
+const UNPINNED_PREFIXES: [&str; 5] = ["workspace:", "file:", "link:", "portal:", "path:"];
+
+/// Tools that define a workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WorkspaceKind {
+    /// Cargo workspace (`[workspace]` in `Cargo.toml`)
+    Cargo,
+    /// pnpm workspace (`pnpm-workspace.yaml`)
+    Pnpm,
+    /// npm or Yarn workspaces (`workspaces` in `package.json`)
+    NpmWorkspaces,
+    /// Nx (`nx.json`)
+    Nx,
+    /// Turborepo (`turbo.json`)
+    Turborepo,
+    /// Go workspace (`go.work`)
+    GoWork,
+}
+
+impl fmt::Display for WorkspaceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkspaceKind::Cargo => write!(f, "Cargo"),
+            WorkspaceKind::Pnpm => write!(f, "pnpm"),
+            WorkspaceKind::NpmWorkspaces => write!(f, "npm/Yarn"),
+            WorkspaceKind::Nx => write!(f, "Nx"),
+            WorkspaceKind::Turborepo => write!(f, "Turborepo"),
+            WorkspaceKind::GoWork => write!(f, "go.work"),
+        }
+    }
+}
+
+/// Health of one internal package of a workspace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageReport {
+    /// Package name from its manifest, or its directory name
+    pub name: String,
+    /// Directory of the package
+    pub path: PathBuf,
+    /// Ecosystem of the package manifest
+    pub ecosystem: Ecosystem,
+    /// Number of declared dependencies, internal ones included
+    pub dependencies: usize,
+    /// Other packages of the same workspace this package depends on
+    pub internal_dependencies: Vec<String>,
+    /// External dependencies pinned differently elsewhere in the workspace
+    pub conflicts: usize,
+    /// Problems reading the package manifest
+    pub errors: Vec<String>,
+}
+
+impl PackageReport {
+    /// Calculates a health score from 0 to 100
+    ///
+    /// Each manifest error costs 25 points and each conflicting external
+    /// dependency 10 points.
+    pub fn health_score(&self) -> u8 {
+        let penalty = 25 * self.errors.len() + 10 * self.conflicts;
+        100usize.saturating_sub(penalty) as u8
+    }
+}
+
+/// One package's requirement in a [`VersionConflict`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedVersion {
+    /// Name of the internal package
+    pub package: String,
+    /// Version requirement declared by that package
+    pub version: String,
+}
+
+/// An external dependency required at different versions within a workspace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionConflict {
+    /// Ecosystem of the dependency
+    pub ecosystem: Ecosystem,
+    /// Name of the external dependency
+    pub name: String,
+    /// Every package's requirement, ordered by package name
+    pub versions: Vec<PinnedVersion>,
+}
+
+/// Result of analyzing one workspace root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceReport {
+    /// Directory holding the workspace definition
+    pub root: PathBuf,
+    /// Tools defining the workspace, e.g. pnpm together with Turborepo
+    pub kinds: Vec<WorkspaceKind>,
+    /// Internal packages, ordered by name
+    pub packages: Vec<PackageReport>,
+    /// External dependencies pinned inconsistently, ordered by name
+    pub conflicts: Vec<VersionConflict>,
+    /// Groups of packages that depend on each other in a cycle, each sorted
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl WorkspaceReport {
+    /// Returns the number of packages whose manifests could not be read
+    pub fn errors(&self) -> usize {
+        self.packages
+            .iter()
+            .filter(|p| !p.errors.is_empty())
+            .count()
+    }
+
+    /// Returns how many internal packages depend on each package
+    ///
+    /// Packages nothing depends on are omitted. Sorted by count, highest
+    /// first, then by name.
+    pub fn fan_in(&self) -> Vec<(&str, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for dependency in self.packages.iter().flat_map(|p| &p.internal_dependencies) {
+            *counts.entry(dependency.as_str()).or_default() += 1;
+        }
+        rank(counts.into_iter().collect())
+    }
+
+    /// Returns how many internal packages each package depends on
+    ///
+    /// Packages without internal dependencies are omitted. Sorted by count,
+    /// highest first, then by name.
+    pub fn fan_out(&self) -> Vec<(&str, usize)> {
+        rank(
+            self.packages
+                .iter()
+                .map(|p| (p.name.as_str(), p.internal_dependencies.len()))
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+        )
+    }
+}
+
+/// Sorts `(name, count)` pairs by count, highest first, then by name
+fn rank(mut counts: Vec<(&str, usize)>) -> Vec<(&str, usize)> {
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    counts
+}
+
+/// Renders the internal dependency graphs of workspaces as Graphviz DOT
+///
+/// Each workspace becomes a cluster; an edge `a -> b` means package `a`
+/// depends on package `b`. Edges within a dependency cycle are drawn red.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::workspace;
+///
+/// let dot = workspace::to_dot(&[]);
+/// assert!(dot.starts_with("digraph workspaces {"));
+/// ```
+pub fn to_dot(reports: &[WorkspaceReport]) -> String {
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut dot = String::from("digraph workspaces {\n    rankdir=LR;\n    node [shape=box];\n");
+
+    for (index, report) in reports.iter().enumerate() {
+        let id = |name: &str| quote(&format!("{}/{}", index, name));
+        let in_cycle = |a: &str, b: &str| {
+            report
+                .cycles
+                .iter()
+                .any(|cycle| cycle.iter().any(|p| p == a) && cycle.iter().any(|p| p == b))
+        };
+
+        let _ = writeln!(dot, "    subgraph cluster_{} {{", index);
+        let _ = writeln!(
+            dot,
+            "        label={};",
+            quote(&report.root.display().to_string())
+        );
+        for package in &report.packages {
+            let _ = writeln!(
+                dot,
+                "        {} [label={}];",
+                id(&package.name),
+                quote(&package.name)
+            );
+        }
+        for package in &report.packages {
+            for dependency in &package.internal_dependencies {
+                let style = if in_cycle(&package.name, dependency) {
+                    " [color=red]"
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    dot,
+                    "        {} -> {}{};",
+                    id(&package.name),
+                    id(dependency),
+                    style
+                );
+            }
+        }
+        dot.push_str("    }\n");
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Finds workspace roots accepted by a filter and analyzes their packages
+///
+/// # Arguments
+///
+/// * `path` - The directory to search for workspace roots
+/// * `include` - Predicate deciding whether a workspace root is analyzed
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::scanner::workspace;
+/// use std::path::Path;
+///
+/// for report in workspace::scan_workspaces_matching(Path::new("."), |_| true) {
+///     println!("{}: {} packages", report.root.display(), report.packages.len());
+/// }
+/// ```
+pub fn scan_workspaces_matching<F>(path: &Path, include: F) -> Vec<WorkspaceReport>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut roots: BTreeMap<PathBuf, BTreeSet<WorkspaceKind>> = BTreeMap::new();
+
+    let walk_root = dh_fs::extended_length(path);
+    for entry in dh_fs::walk(path) {
+        if dh_fs::is_skipped(
+            entry
+                .path()
+                .strip_prefix(&walk_root)
+                .unwrap_or(entry.path()),
+        ) {
+            continue;
+        }
+        let Some(kind) = detect_workspace_file(entry.path()) else {
+            continue;
+        };
+        if let Some(root) = entry.path().parent() {
+            roots
+                .entry(dh_fs::strip_extended_length(root))
+                .or_default()
+                .insert(kind);
+        }
+    }
+
+    roots
+        .into_iter()
+        .filter(|(root, _)| include(root))
+        .map(|(root, kinds)| analyze_workspace(&root, kinds.into_iter().collect()))
+        .collect()
+}
+
+/// Displays workspace analysis results at the given level of detail
+///
+/// - [`Detail::Summary`]: header and summary box only
+/// - [`Detail::Minimal`]: one line per workspace plus its version conflicts
+/// - [`Detail::Normal`]: also one line per package
+/// - [`Detail::Full`]: also each package's internal dependencies
+///
+/// # Arguments
+///
+/// * `reports` - Slice of workspace reports to display
+/// * `detail` - How much of the report to print
+pub fn display_results_with(reports: &[WorkspaceReport], detail: Detail) {
+    if reports.is_empty() {
+        println!(
+            "{}",
+            display::header(&tr!("workspace-none"), "🗂️", Role::Warning)
+        );
+        return;
+    }
+
+    let packages: usize = reports.iter().map(|r| r.packages.len()).sum();
+    let conflicts: usize = reports.iter().map(|r| r.conflicts.len()).sum();
+    let errors: usize = reports.iter().map(WorkspaceReport::errors).sum();
+    let cycles: usize = reports.iter().map(|r| r.cycles.len()).sum();
+    let role = if conflicts + errors + cycles > 0 {
+        Role::Warning
+    } else {
+        Role::Special
+    };
+
+    println!(
+        "{}",
+        display::header(&tr!("workspace-title", count = reports.len()), "🗂️", role)
+    );
+
+    let count_with = |count: usize, symbol: &str| {
+        if count > 0 {
+            format!("{} {}", count, symbol)
+        } else {
+            "0".to_string()
+        }
+    };
+    let summary_items = vec![
+        (t("workspace-roots"), reports.len().to_string()),
+        (t("workspace-packages"), packages.to_string()),
+        (t("workspace-conflicts"), count_with(conflicts, "⚠️")),
+        (t("workspace-cycles"), count_with(cycles, "❌")),
+        (t("errors"), count_with(errors, "❌")),
+    ];
+    print!("{}", display::summary_box(&summary_items));
+
+    if detail == Detail::Summary {
+        return;
+    }
+
+    println!("{}", display::section_divider(t("workspace-details")));
+    for (index, report) in reports.iter().enumerate() {
+        let is_last = index == reports.len() - 1;
+        let name = report
+            .root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| report.root.display().to_string());
+        let kinds: Vec<String> = report.kinds.iter().map(ToString::to_string).collect();
+
+        let header = format!(
+            "{} {} {} {}",
+            "🗂️",
+            name.paint(Role::Emphasis).bold(),
+            format!("[{}]", kinds.join(", ")).paint(Role::Muted),
+            tr!("workspace-package-count", count = report.packages.len()).paint(Role::Muted)
+        );
+        println!("{}", display::tree_item(&header, is_last, 0));
+
+        let mut lines = Vec::new();
+        if detail >= Detail::Normal {
+            for package in &report.packages {
+                lines.push(package_line(package));
+                if detail == Detail::Full && !package.internal_dependencies.is_empty() {
+                    lines.push(format!(
+                        "  {} {}",
+                        "→".paint(Role::Muted),
+                        package.internal_dependencies.join(", ").paint(Role::Muted)
+                    ));
+                }
+                for error in &package.errors {
+                    lines.push(format!(
+                        "  {} {}",
+                        "✗".paint(Role::Error),
+                        error.paint(Role::Error)
+                    ));
+                }
+            }
+        }
+        lines.extend(report.cycles.iter().map(|cycle| {
+            format!(
+                "{} {}",
+                "❌".paint(Role::Error),
+                tr!("workspace-cycle", packages = cycle.join(", ")).paint(Role::Error)
+            )
+        }));
+        if detail >= Detail::Normal {
+            let leaders = [
+                (t("workspace-fan-in"), report.fan_in()),
+                (t("workspace-fan-out"), report.fan_out()),
+            ];
+            for (label, ranking) in leaders {
+                let top: Vec<String> = ranking
+                    .iter()
+                    .take(FAN_LEADERS)
+                    .map(|(name, count)| format!("{} ({})", name, count))
+                    .collect();
+                if !top.is_empty() {
+                    lines.push(format!("{} {}", label.paint(Role::Muted), top.join(", ")));
+                }
+            }
+        }
+        lines.extend(report.conflicts.iter().map(conflict_line));
+
+        for (line_index, line) in lines.iter().enumerate() {
+            println!(
+                "{}",
+                display::tree_item(line, line_index == lines.len() - 1, 1)
+            );
+        }
+    }
+}
+
+/// Formats one package with its health score
+fn package_line(package: &PackageReport) -> String {
+    let score = package.health_score();
+    let role = match score {
+        80..=100 => Role::Success,
+        50..=79 => Role::Warning,
+        _ => Role::Error,
+    };
+    format!(
+        "{} {} {} {}",
+        display::ecosystem_icon(&package.ecosystem.to_string()),
+        package.name.paint(Role::Highlight).bold(),
+        tr!(
+            "workspace-package-deps",
+            count = package.dependencies,
+            internal = package.internal_dependencies.len()
+        )
+        .paint(Role::Muted),
+        format!("{}%", score).paint(role)
+    )
+}
+
+/// Formats one version conflict as `name: pkg version, pkg version`
+fn conflict_line(conflict: &VersionConflict) -> String {
+    let versions: Vec<String> = conflict
+        .versions
+        .iter()
+        .map(|pinned| format!("{} {}", pinned.package, pinned.version))
+        .collect();
+    format!(
+        "{} {} {}",
+        "⚠️".paint(Role::Warning),
+        tr!("workspace-conflict", name = conflict.name)
+            .paint(Role::Warning)
+            .bold(),
+        versions.join(", ")
+    )
+}
+
+/// Analyzes the packages of one workspace root
+fn analyze_workspace(root: &Path, kinds: Vec<WorkspaceKind>) -> WorkspaceReport {
+    let mut members: BTreeSet<(PathBuf, Ecosystem)> = BTreeSet::new();
+    let mut node_workspaces = false;
+
+    for kind in &kinds {
+        let (patterns, ecosystem) = match kind {
+            WorkspaceKind::Cargo => (cargo_members(root), Ecosystem::Rust),
+            WorkspaceKind::Pnpm => (pnpm_packages(root), Ecosystem::NodeJs),
+            WorkspaceKind::NpmWorkspaces => (npm_workspaces(root), Ecosystem::NodeJs),
+            WorkspaceKind::GoWork => (go_work_uses(root), Ecosystem::Go),
+            WorkspaceKind::Nx | WorkspaceKind::Turborepo => continue,
+        };
+        node_workspaces |= ecosystem == Ecosystem::NodeJs;
+        for dir in expand_members(root, &patterns, manifest_name(&ecosystem)) {
+            members.insert((dir, ecosystem.clone()));
+        }
+    }
+
+    // Nx without package manager workspaces: every project.json is a package
+    if kinds.contains(&WorkspaceKind::Nx) && !node_workspaces {
+        for dir in expand_members(root, &["**".to_string()], "project.json") {
+            members.insert((dir, Ecosystem::NodeJs));
+        }
+    }
+
+    // The root itself is a package when its manifest names one
+    for ecosystem in [Ecosystem::Rust, Ecosystem::NodeJs] {
+        let has_workspace = match ecosystem {
+            Ecosystem::Rust => kinds.contains(&WorkspaceKind::Cargo),
+            _ => node_workspaces,
+        };
+        if has_workspace && package_name(root, &ecosystem).is_some() {
+            members.insert((root.to_path_buf(), ecosystem));
+        }
+    }
+
+    let packages: Vec<(PackageReport, Vec<Dependency>)> = members
+        .into_iter()
+        .map(|(path, ecosystem)| read_package(path, ecosystem))
+        .collect();
+    let internal: BTreeSet<(String, Ecosystem)> = packages
+        .iter()
+        .map(|(package, _)| (package.name.clone(), package.ecosystem.clone()))
+        .collect();
+
+    let conflicts = find_conflicts(&packages, &internal);
+    let mut packages: Vec<PackageReport> = packages
+        .into_iter()
+        .map(|(mut package, dependencies)| {
+            let is_internal =
+                |dep: &Dependency| internal.contains(&(dep.name.clone(), dep.ecosystem.clone()));
+            package.internal_dependencies = dependencies
+                .iter()
+                .filter(|dep| is_internal(dep) && dep.name != package.name)
+                .map(|dep| dep.name.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            package.conflicts = conflicts
+                .iter()
+                .filter(|conflict| {
+                    conflict
+                        .versions
+                        .iter()
+                        .any(|pinned| pinned.package == package.name)
+                })
+                .count();
+            package
+        })
+        .collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.path.cmp(&b.path)));
+    let cycles = find_cycles(&packages);
+
+    WorkspaceReport {
+        root: root.to_path_buf(),
+        kinds,
+        packages,
+        conflicts,
+        cycles,
+    }
+}
+
+/// Finds dependency cycles with Tarjan's strongly connected components
+///
+/// Returns every group of two or more packages that can reach each other,
+/// each sorted by name.
+fn find_cycles(packages: &[PackageReport]) -> Vec<Vec<String>> {
+    struct Tarjan<'a> {
+        edges: BTreeMap<&'a str, &'a [String]>,
+        index: BTreeMap<&'a str, usize>,
+        low: BTreeMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: BTreeSet<&'a str>,
+        components: Vec<Vec<String>>,
+    }
+
+    impl<'a> Tarjan<'a> {
+        fn visit(&mut self, node: &'a str) {
+            let next = self.index.len();
+            self.index.insert(node, next);
+            self.low.insert(node, next);
+            self.stack.push(node);
+            self.on_stack.insert(node);
+
+            let edges = self.edges.get(node).copied().unwrap_or_default();
+            for target in edges.iter().map(String::as_str) {
+                if !self.edges.contains_key(target) {
+                    continue;
+                }
+                if !self.index.contains_key(target) {
+                    self.visit(target);
+                    let low = self.low[node].min(self.low[target]);
+                    self.low.insert(node, low);
+                } else if self.on_stack.contains(target) {
+                    let low = self.low[node].min(self.index[target]);
+                    self.low.insert(node, low);
+                }
+            }
+
+            if self.low[node] == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member.to_string());
+                    if member == node {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    component.sort();
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges: packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.internal_dependencies.as_slice()))
+            .collect(),
+        index: BTreeMap::new(),
+        low: BTreeMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        components: Vec::new(),
+    };
+    for package in packages {
+        if !tarjan.index.contains_key(package.name.as_str()) {
+            tarjan.visit(&package.name);
+        }
+    }
+
+    tarjan.components.sort();
+    tarjan.components
+}
+
+/// Reads a package's name and dependencies
+fn read_package(path: PathBuf, ecosystem: Ecosystem) -> (PackageReport, Vec<Dependency>) {
+    let name = package_name(&path, &ecosystem).unwrap_or_else(|| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+    let has_manifest = path.join(manifest_name(&ecosystem)).exists();
+    let (dependencies, errors) = if has_manifest {
+        match deps::parse_dependencies(&path, ecosystem.clone()) {
+            Ok(dependencies) => (dependencies, Vec::new()),
+            Err(e) => (Vec::new(), vec![e.to_string()]),
+        }
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let report = PackageReport {
+        name,
+        path,
+        ecosystem,
+        dependencies: dependencies.len(),
+        internal_dependencies: Vec::new(),
+        conflicts: 0,
+        errors,
+    };
+    (report, dependencies)
+}
+
+/// Groups external dependencies by name and keeps those with several versions
+fn find_conflicts(
+    packages: &[(PackageReport, Vec<Dependency>)],
+    internal: &BTreeSet<(String, Ecosystem)>,
+) -> Vec<VersionConflict> {
+    let mut pins: BTreeMap<(String, String), BTreeSet<(String, String)>> = BTreeMap::new();
+    let mut ecosystems = BTreeMap::new();
+
+    for (package, dependencies) in packages {
+        for dep in dependencies {
+            if internal.contains(&(dep.name.clone(), dep.ecosystem.clone()))
+                || !is_pinned(&dep.version)
+            {
+                continue;
+            }
+            let key = (dep.ecosystem.to_string(), dep.name.clone());
+            ecosystems.insert(key.clone(), dep.ecosystem.clone());
+            pins.entry(key)
+                .or_default()
+                .insert((package.name.clone(), dep.version.clone()));
+        }
+    }
+
+    pins.into_iter()
+        .filter(|(_, pinned)| {
+            pinned
+                .iter()
+                .map(|(_, version)| version)
+                .collect::<BTreeSet<_>>()
+                .len()
+                > 1
+        })
+        .map(|(key, pinned)| VersionConflict {
+            ecosystem: ecosystems[&key].clone(),
+            name: key.1,
+            versions: pinned
+                .into_iter()
+                .map(|(package, version)| PinnedVersion { package, version })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Returns `true` if a version requirement pins an external version
+fn is_pinned(version: &str) -> bool {
+    let version = version.trim();
+    !version.is_empty()
+        && version != "*"
+        && !UNPINNED_PREFIXES
+            .iter()
+            .any(|prefix| version.starts_with(prefix))
+}
+
+/// Returns the workspace kind defined by a file, if any
+pub(crate) fn detect_workspace_file(path: &Path) -> Option<WorkspaceKind> {
+    match path.file_name()?.to_str()? {
+        "Cargo.toml" => {
+            let manifest: toml::Value = read_toml(path)?;
+            manifest.get("workspace").map(|_| WorkspaceKind::Cargo)
+        }
+        "package.json" => {
+            let manifest: serde_json::Value = read_json(path)?;
+            manifest
+                .get("workspaces")
+                .map(|_| WorkspaceKind::NpmWorkspaces)
+        }
+        "pnpm-workspace.yaml" | "pnpm-workspace.yml" => Some(WorkspaceKind::Pnpm),
+        "nx.json" => Some(WorkspaceKind::Nx),
+        "turbo.json" => Some(WorkspaceKind::Turborepo),
+        "go.work" => Some(WorkspaceKind::GoWork),
+        _ => None,
+    }
+}
+
+/// Returns the manifest file that marks a package of an ecosystem
+fn manifest_name(ecosystem: &Ecosystem) -> &'static str {
+    match ecosystem {
+        Ecosystem::Rust => "Cargo.toml",
+        Ecosystem::NodeJs => "package.json",
+        Ecosystem::Go => "go.mod",
+        Ecosystem::Python => "pyproject.toml",
+        Ecosystem::Bazel => "MODULE.bazel",
+        Ecosystem::Buck => "BUCK",
+        Ecosystem::Nix => "flake.nix",
+    }
+}
+
+/// Reads the package name from a package directory's manifest
+fn package_name(dir: &Path, ecosystem: &Ecosystem) -> Option<String> {
+    match ecosystem {
+        Ecosystem::Rust => {
+            let manifest: toml::Value = read_toml(&dir.join("Cargo.toml"))?;
+            Some(manifest.get("package")?.get("name")?.as_str()?.to_string())
+        }
+        Ecosystem::NodeJs => ["package.json", "project.json"].iter().find_map(|file| {
+            let manifest: serde_json::Value = read_json(&dir.join(file))?;
+            Some(manifest.get("name")?.as_str()?.to_string())
+        }),
+        Ecosystem::Go => {
+            let go_mod = std::fs::read_to_string(dir.join("go.mod")).ok()?;
+            go_mod
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("module "))
+                .map(|module| module.trim().trim_matches('"').to_string())
+        }
+        Ecosystem::Python | Ecosystem::Bazel | Ecosystem::Buck | Ecosystem::Nix => None,
+    }
+}
+
+/// Returns `members` and `!`-prefixed `exclude` patterns of a Cargo workspace
+fn cargo_members(root: &Path) -> Vec<String> {
+    let Some(manifest) = read_toml::<toml::Value>(&root.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let list = |key: &str| -> Vec<String> {
+        manifest
+            .get("workspace")
+            .and_then(|workspace| workspace.get(key))
+            .and_then(|value| value.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let mut patterns = list("members");
+    patterns.extend(
+        list("exclude")
+            .into_iter()
+            .map(|pattern| format!("!{}", pattern)),
+    );
+    patterns
+}
+
+/// Returns the `packages` patterns of `pnpm-workspace.yaml`
+fn pnpm_packages(root: &Path) -> Vec<String> {
+    ["pnpm-workspace.yaml", "pnpm-workspace.yml"]
+        .iter()
+        .find_map(|file| std::fs::read_to_string(root.join(file)).ok())
+        .map(|yaml| parse_pnpm_packages(&yaml))
+        .unwrap_or_default()
+}
+
+/// Extracts the `packages:` list from a pnpm workspace file
+fn parse_pnpm_packages(yaml: &str) -> Vec<String> {
+    let mut in_packages = false;
+    let mut patterns = Vec::new();
+
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed.starts_with("packages:");
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            let item = item.split(" #").next().unwrap_or_default().trim();
+            patterns.push(item.trim_matches(['\'', '"']).to_string());
+        }
+    }
+    patterns
+}
+
+/// Returns the `workspaces` patterns of a root `package.json`
+///
+/// Accepts both the array form and Yarn's `{ "packages": [...] }` form.
+fn npm_workspaces(root: &Path) -> Vec<String> {
+    let Some(manifest) = read_json::<serde_json::Value>(&root.join("package.json")) else {
+        return Vec::new();
+    };
+    let workspaces = manifest.get("workspaces");
+    workspaces
+        .and_then(|w| w.as_array())
+        .or_else(|| {
+            workspaces
+                .and_then(|w| w.get("packages"))
+                .and_then(|p| p.as_array())
+        })
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the directories listed by `use` in `go.work`
+fn go_work_uses(root: &Path) -> Vec<String> {
+    std::fs::read_to_string(root.join("go.work"))
+        .map(|go_work| parse_go_work(&go_work))
+        .unwrap_or_default()
+}
+
+/// Extracts `use` directives, in single-line and block form
+fn parse_go_work(go_work: &str) -> Vec<String> {
+    let mut in_block = false;
+    let mut dirs = Vec::new();
+
+    for line in go_work.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() {
+                dirs.push(line.to_string());
+            }
+        } else if let Some(rest) = line
+            .strip_prefix("use")
+            .filter(|rest| rest.starts_with([' ', '\t', '(']))
+        {
+            match rest.trim() {
+                "(" => in_block = true,
+                "" => {}
+                dir => dirs.push(dir.to_string()),
+            }
+        }
+    }
+    dirs
+}
+
+/// Finds directories below `root` matching member patterns and holding `manifest`
+///
+/// Patterns are relative to `root`; patterns starting with `!` exclude.
+fn expand_members(root: &Path, patterns: &[String], manifest: &str) -> Vec<PathBuf> {
+    let normalize = |pattern: &str| {
+        pattern
+            .trim_start_matches("./")
+            .trim_end_matches('/')
+            .to_string()
+    };
+    let (excludes, includes): (Vec<&String>, Vec<&String>) = patterns
+        .iter()
+        .partition(|pattern| pattern.starts_with('!'));
+    let includes: Vec<String> = includes.iter().map(|p| normalize(p)).collect();
+    let excludes: Vec<String> = excludes.iter().map(|p| normalize(&p[1..])).collect();
+    if includes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut members = Vec::new();
+    for entry in dh_fs::walk(root) {
+        let path = dh_fs::strip_extended_length(entry.path());
+        if entry.depth() == 0 || !entry.file_type().is_dir() {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        if dh_fs::is_skipped(relative) {
+            continue;
+        }
+        let matches = |patterns: &[String]| {
+            patterns
+                .iter()
+                .any(|pattern| dh_fs::glob_matches(pattern, relative))
+        };
+        if matches(&includes) && !matches(&excludes) && path.join(manifest).is_file() {
+            members.push(path);
+        }
+    }
+    members
+}
+
+fn read_toml<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    toml::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, file: &str, content: &str) {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    mod detection {
+        use super::*;
+
+        #[test]
+        fn parses_pnpm_workspace_file() {
+            let yaml = "packages:\n  - 'packages/*'\n  - \"apps/**\" # apps\n  - '!**/test/**'\ncatalog:\n  - nope\n";
+            assert_eq!(
+                parse_pnpm_packages(yaml),
+                vec!["packages/*", "apps/**", "!**/test/**"]
+            );
+        }
+
+        #[test]
+        fn parses_go_work_use_directives() {
+            let go_work = "go 1.22\n\nuse ./cmd // tools\nuse (\n    ./api\n    ./lib\n)\n";
+            assert_eq!(parse_go_work(go_work), vec!["./cmd", "./api", "./lib"]);
+        }
+
+        #[test]
+        fn ignores_unpinned_requirements() {
+            assert!(is_pinned("^1.2.0"));
+            assert!(!is_pinned("*"));
+            assert!(!is_pinned("workspace:*"));
+            assert!(!is_pinned("file:../lib"));
+        }
+    }
+
+    mod graph {
+        use super::*;
+
+        fn package(name: &str, internal: &[&str]) -> PackageReport {
+            PackageReport {
+                name: name.to_string(),
+                path: PathBuf::from(format!("/mono/{}", name)),
+                ecosystem: Ecosystem::NodeJs,
+                dependencies: internal.len(),
+                internal_dependencies: internal.iter().map(|d| d.to_string()).collect(),
+                conflicts: 0,
+                errors: Vec::new(),
+            }
+        }
+
+        fn workspace(packages: Vec<PackageReport>) -> WorkspaceReport {
+            let cycles = find_cycles(&packages);
+            WorkspaceReport {
+                root: PathBuf::from("/mono"),
+                kinds: vec![WorkspaceKind::Pnpm],
+                packages,
+                conflicts: Vec::new(),
+                cycles,
+            }
+        }
+
+        #[test]
+        fn detects_cycles() {
+            let report = workspace(vec![
+                package("api", &["core", "auth"]),
+                package("auth", &["core"]),
+                package("core", &["utils"]),
+                package("ui", &["web"]),
+                package("utils", &["core"]),
+                package("web", &["ui"]),
+            ]);
+            assert_eq!(
+                report.cycles,
+                vec![
+                    vec!["core".to_string(), "utils".to_string()],
+                    vec!["ui".to_string(), "web".to_string()]
+                ]
+            );
+            assert!(workspace(vec![package("a", &["b"]), package("b", &[])])
+                .cycles
+                .is_empty());
+        }
+
+        #[test]
+        fn ranks_fan_in_and_fan_out() {
+            let report = workspace(vec![
+                package("api", &["auth", "core"]),
+                package("auth", &["core"]),
+                package("cli", &["core"]),
+                package("core", &[]),
+            ]);
+            assert_eq!(report.fan_in(), vec![("core", 3), ("auth", 1)]);
+            assert_eq!(report.fan_out(), vec![("api", 2), ("auth", 1), ("cli", 1)]);
+        }
+
+        #[test]
+        fn exports_dot_with_cycle_edges_highlighted() {
+            let report = workspace(vec![
+                package("a", &["b"]),
+                package("b", &["a", "c"]),
+                package("c", &[]),
+            ]);
+            let dot = to_dot(&[report]);
+
+            assert!(dot.starts_with("digraph workspaces {"));
+            assert!(dot.contains("subgraph cluster_0 {"));
+            assert!(dot.contains("\"0/a\" [label=\"a\"];"));
+            assert!(dot.contains("\"0/a\" -> \"0/b\" [color=red];"));
+            assert!(dot.contains("\"0/b\" -> \"0/c\";"));
+            assert!(dot.trim_end().ends_with('}'));
+        }
+    }
+
+    mod analysis {
+        use super::*;
+
+        #[test]
+        fn reports_cargo_workspace_packages_and_conflicts() {
+            let temp_dir = TempDir::new().unwrap();
+            let root = temp_dir.path();
+            write(
+                root,
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n",
+            );
+            write(
+                root,
+                "crates/core/Cargo.toml",
+                "[package]\nname = \"core\"\n\n[dependencies]\nserde = \"1.0.190\"\n",
+            );
+            write(
+                root,
+                "crates/cli/Cargo.toml",
+                "[package]\nname = \"cli\"\n\n[dependencies]\ncore = { path = \"../core\" }\nserde = \"1.0\"\nclap = \"4\"\n",
+            );
+            write(root, "crates/old/Cargo.toml", "[package]\nname = \"old\"\n");
+
+            let reports = scan_workspaces_matching(root, |_| true);
+
+            assert_eq!(reports.len(), 1);
+            let report = &reports[0];
+            assert_eq!(report.kinds, vec![WorkspaceKind::Cargo]);
+            let names: Vec<&str> = report.packages.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(names, vec!["cli", "core"]);
+            assert_eq!(report.packages[0].internal_dependencies, vec!["core"]);
+            assert_eq!(report.conflicts.len(), 1);
+            assert_eq!(report.conflicts[0].name, "serde");
+            assert_eq!(
+                report.conflicts[0].versions,
+                vec![
+                    PinnedVersion {
+                        package: "cli".to_string(),
+                        version: "1.0".to_string()
+                    },
+                    PinnedVersion {
+                        package: "core".to_string(),
+                        version: "1.0.190".to_string()
+                    },
+                ]
+            );
+            assert_eq!(report.packages[1].health_score(), 90);
+        }
+
+        #[test]
+        fn combines_package_manager_and_build_tool_markers() {
+            let temp_dir = TempDir::new().unwrap();
+            let root = temp_dir.path();
+            write(
+                root,
+                "package.json",
+                r#"{"name": "mono", "private": true, "workspaces": ["packages/*"]}"#,
+            );
+            write(root, "turbo.json", "{}");
+            write(
+                root,
+                "packages/ui/package.json",
+                r#"{"name": "@mono/ui", "dependencies": {"react": "^18.2.0"}}"#,
+            );
+            write(
+                root,
+                "packages/web/package.json",
+                r#"{"name": "@mono/web", "dependencies": {"@mono/ui": "workspace:*", "react": "^18.2.0"}}"#,
+            );
+            write(
+                root,
+                "node_modules/dep/package.json",
+                r#"{"name": "dep", "workspaces": []}"#,
+            );
+
+            let reports = scan_workspaces_matching(root, |_| true);
+
+            assert_eq!(reports.len(), 1);
+            assert_eq!(
+                reports[0].kinds,
+                vec![WorkspaceKind::NpmWorkspaces, WorkspaceKind::Turborepo]
+            );
+            let names: Vec<&str> = reports[0]
+                .packages
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect();
+            assert_eq!(names, vec!["@mono/ui", "@mono/web", "mono"]);
+            assert_eq!(
+                reports[0].packages[1].internal_dependencies,
+                vec!["@mono/ui"]
+            );
+            assert!(reports[0].conflicts.is_empty());
+        }
+
+        #[test]
+        fn finds_go_work_modules() {
+            let temp_dir = TempDir::new().unwrap();
+            let root = temp_dir.path();
+            write(root, "go.work", "go 1.22\nuse (\n  ./api\n  ./lib\n)\n");
+            write(
+                root,
+                "api/go.mod",
+                "module example.com/api\n\nrequire example.com/lib v0.0.0\n",
+            );
+            write(root, "lib/go.mod", "module example.com/lib\n");
+
+            let reports = scan_workspaces_matching(root, |_| true);
+
+            let names: Vec<&str> = reports[0]
+                .packages
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect();
+            assert_eq!(names, vec!["example.com/api", "example.com/lib"]);
+            assert_eq!(
+                reports[0].packages[0].internal_dependencies,
+                vec!["example.com/lib"]
+            );
+        }
+
+        #[test]
+        fn scans_workspaces_below_a_skipped_directory_name() {
+            let temp_dir = TempDir::new().unwrap();
+            let root = temp_dir.path().join("vendor/mono");
+            write(
+                &root,
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\n",
+            );
+            write(
+                &root,
+                "crates/core/Cargo.toml",
+                "[package]\nname = \"core\"\n",
+            );
+
+            let reports = scan_workspaces_matching(&root, |_| true);
+
+            assert_eq!(
+                reports.len(),
+                1,
+                "Only directories below the scanned path are skipped"
+            );
+            assert_eq!(reports[0].packages.len(), 1);
+        }
+    }
+}
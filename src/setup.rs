@@ -0,0 +1,774 @@
+//! First-run setup (`devhealth init`)
+//!
+//! Running `devhealth init` without an editor asks a few questions and
+//! writes a commented `devhealth.toml`:
+//!
+//! - **code roots**: the directories holding the user's repositories, each
+//!   written as a `[tags]` entry so reports can be filtered with `--tag`
+//! - **scanners**: what `devhealth scan` runs without scanner flags
+//! - **thresholds**: the `--fail-level` default and the days after which
+//!   uncommitted changes count as stale
+//!
+//! The defaults come from the environment: code roots from the usual
+//! directories (`~/src`, `~/code`, …) that exist, and scanners from the
+//! package managers on `PATH`. Optionally, a daily cron entry scans the
+//! dependencies under each root and runs `watch-advisories`, and a
+//! `pre-push` hook running `devhealth check` is added to every repository
+//! under the roots that does not have one yet.
+//!
+//! The questions and answers go through any reader and writer, so the same
+//! code serves the terminal and tests; [`Answers::defaults`] answers every
+//! question with its default.
+
+use crate::config::{Config, Scanner};
+use crate::findings::{Severity, STALE_UNCOMMITTED_DAYS};
+use crate::tr;
+use crate::utils::fs as fs_utils;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+/// Directories looked for as code roots, in order
+const ROOT_CANDIDATES: [&str; 7] = [
+    "~/src",
+    "~/code",
+    "~/projects",
+    "~/dev",
+    "~/work",
+    "~/repos",
+    "~/git",
+];
+
+/// Comment line marking the crontab entry written by `init`
+pub const SCHEDULE_MARKER: &str = "# devhealth init: daily dependency scan and advisory watch";
+
+/// Comment line marking `pre-push` hooks written by `init`
+const HOOK_MARKER: &str = "# Installed by devhealth init";
+
+/// Errors that can occur while writing the configuration, schedule or hooks
+#[derive(Error, Debug)]
+pub enum SetupError {
+    #[error("{} already exists (use --force to replace it)", .0.display())]
+    ConfigExists(PathBuf),
+    #[error("Could not determine the DevHealth config directory")]
+    NoConfigDirectory,
+    #[error("Failed to write {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to update the crontab: {0}")]
+    Crontab(String),
+}
+
+/// What `init` found on this machine
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Environment {
+    /// Whether git is on `PATH`
+    pub git: bool,
+    /// Package managers found on `PATH`, such as `cargo` and `npm`
+    pub tools: Vec<&'static str>,
+    /// Whether a GitHub or GitLab token is set in the environment
+    pub forge_token: bool,
+    /// Whether `crontab` is available for the schedule
+    pub cron: bool,
+    /// Usual code directories that exist
+    pub roots: Vec<PathBuf>,
+    /// The user's home directory, used to write paths as `~/…`
+    pub home: Option<PathBuf>,
+}
+
+impl Environment {
+    /// Looks at `PATH`, the environment and the home directory
+    pub fn detect() -> Environment {
+        let on_path = |name: &str| !fs_utils::find_on_path(name).is_empty();
+        let tools = ["cargo", "npm", "pip", "go"]
+            .into_iter()
+            .filter(|tool| on_path(tool))
+            .collect();
+        let forge_token = ["GITHUB_TOKEN", "GH_TOKEN", "GITLAB_TOKEN"]
+            .iter()
+            .any(|name| std::env::var_os(name).is_some_and(|value| !value.is_empty()));
+        let home = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(PathBuf::from);
+        let roots = ROOT_CANDIDATES
+            .iter()
+            .map(|candidate| PathBuf::from(fs_utils::expand_home(candidate)))
+            .filter(|dir| home.is_some() && dir.is_dir())
+            .collect();
+
+        Environment {
+            git: on_path("git"),
+            tools,
+            forge_token,
+            cron: cfg!(unix) && on_path("crontab"),
+            roots,
+            home,
+        }
+    }
+
+    /// Writes `path` relative to the home directory as `~/…`
+    pub fn display_path(&self, path: &Path) -> String {
+        match self
+            .home
+            .as_deref()
+            .and_then(|home| path.strip_prefix(home).ok())
+        {
+            Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+            Some(rest) => format!("~/{}", rest.to_string_lossy().replace('\\', "/")),
+            None => path.to_string_lossy().into_owned(),
+        }
+    }
+}
+
+/// The choices `init` writes into `devhealth.toml`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    /// Directories holding the user's repositories
+    pub roots: Vec<PathBuf>,
+    /// Scanners `devhealth scan` runs without scanner flags
+    pub scanners: Vec<Scanner>,
+    /// Whether to keep report history
+    pub history: bool,
+    /// Severity at which `check` and `scan` fail, if any
+    pub fail_level: Option<Severity>,
+    /// Days after which uncommitted changes count as stale
+    pub stale_uncommitted_days: u64,
+    /// Whether to install the daily cron entry
+    pub schedule: bool,
+    /// Whether to install `pre-push` hooks under the roots
+    pub hooks: bool,
+}
+
+impl Answers {
+    /// Returns the answer to every question suggested for `env`
+    ///
+    /// The code roots default to the usual directories that exist, or the
+    /// current directory. Audits are suggested when a package manager is
+    /// installed, the Rust and Node checks when cargo or npm is. Nothing is
+    /// installed by default.
+    pub fn defaults(env: &Environment, current_dir: &Path) -> Answers {
+        let roots = if env.roots.is_empty() {
+            vec![current_dir.to_path_buf()]
+        } else {
+            env.roots.clone()
+        };
+        let mut scanners = vec![Scanner::Git, Scanner::Deps];
+        if !env.tools.is_empty() {
+            scanners.push(Scanner::Audit);
+        }
+        if env.tools.contains(&"cargo") {
+            scanners.push(Scanner::Cargo);
+        }
+        if env.tools.contains(&"npm") {
+            scanners.push(Scanner::Runtimes);
+        }
+
+        Answers {
+            roots,
+            scanners,
+            history: true,
+            fail_level: Some(Severity::Error),
+            stale_uncommitted_days: STALE_UNCOMMITTED_DAYS,
+            schedule: false,
+            hooks: false,
+        }
+    }
+}
+
+/// Asks every question on `output`, reading the answers from `input`
+///
+/// An empty answer takes the default shown in brackets, and an invalid one
+/// is asked again. Once `input` ends, the remaining questions take their
+/// defaults. The schedule is only offered where cron is available.
+///
+/// # Errors
+///
+/// Returns an error if reading or writing fails.
+pub fn ask<R: BufRead, W: Write>(
+    env: &Environment,
+    current_dir: &Path,
+    input: &mut R,
+    output: &mut W,
+) -> io::Result<Answers> {
+    let defaults = Answers::defaults(env, current_dir);
+    let mut prompt = Prompt { input, output };
+
+    let shown: Vec<String> = defaults
+        .roots
+        .iter()
+        .map(|root| env.display_path(root))
+        .collect();
+    let roots = loop {
+        let Some(answer) = prompt.ask(&tr!("setup-ask-roots"), &shown.join(", "))? else {
+            break defaults.roots.clone();
+        };
+        let roots: Vec<PathBuf> = answer
+            .split(',')
+            .map(str::trim)
+            .filter(|root| !root.is_empty())
+            .map(|root| PathBuf::from(fs_utils::expand_home(root)))
+            .collect();
+        match roots.iter().find(|root| !root.is_dir()) {
+            Some(missing) => prompt.again(&tr!("setup-no-directory", path = missing.display()))?,
+            None if !roots.is_empty() => break roots,
+            None => prompt.again(&tr!("setup-invalid"))?,
+        }
+    };
+
+    let names: Vec<&str> = Scanner::ALL.iter().map(|scanner| scanner.name()).collect();
+    let shown: Vec<&str> = defaults
+        .scanners
+        .iter()
+        .map(|scanner| scanner.name())
+        .collect();
+    let scanners = loop {
+        let question = tr!("setup-ask-scanners", available = names.join(", "));
+        let Some(answer) = prompt.ask(&question, &shown.join(", "))? else {
+            break defaults.scanners.clone();
+        };
+        let scanners: Option<Vec<Scanner>> = split_list(&answer)
+            .map(|name| {
+                Scanner::ALL
+                    .into_iter()
+                    .find(|scanner| scanner.name() == name)
+            })
+            .collect();
+        match scanners {
+            Some(scanners) if !scanners.is_empty() => break scanners,
+            _ => prompt.again(&tr!("setup-invalid"))?,
+        }
+    };
+
+    let history = prompt.yes_no(&tr!("setup-ask-history"), defaults.history)?;
+
+    let fail_level = loop {
+        let shown = defaults.fail_level.map_or("none", Severity::name);
+        match prompt.ask(&tr!("setup-ask-fail-level"), shown)?.as_deref() {
+            None => break defaults.fail_level,
+            Some("none") => break None,
+            Some(name) => match Severity::ALL
+                .into_iter()
+                .find(|severity| severity.name() == name)
+            {
+                Some(severity) => break Some(severity),
+                None => prompt.again(&tr!("setup-invalid"))?,
+            },
+        }
+    };
+
+    let stale_uncommitted_days = loop {
+        let shown = defaults.stale_uncommitted_days.to_string();
+        match prompt
+            .ask(&tr!("setup-ask-stale-days"), &shown)?
+            .map(|answer| answer.parse())
+        {
+            None => break defaults.stale_uncommitted_days,
+            Some(Ok(days)) if days > 0 => break days,
+            Some(_) => prompt.again(&tr!("setup-invalid"))?,
+        }
+    };
+
+    let schedule = env.cron && prompt.yes_no(&tr!("setup-ask-schedule"), defaults.schedule)?;
+    let hooks = env.git && prompt.yes_no(&tr!("setup-ask-hooks"), defaults.hooks)?;
+
+    Ok(Answers {
+        roots,
+        scanners,
+        history,
+        fail_level,
+        stale_uncommitted_days,
+        schedule,
+        hooks,
+    })
+}
+
+/// Questions asked on a writer and answered on a reader
+struct Prompt<'a, R, W> {
+    input: &'a mut R,
+    output: &'a mut W,
+}
+
+impl<R: BufRead, W: Write> Prompt<'_, R, W> {
+    /// Asks one question, returning the trimmed answer, or `None` for the
+    /// default
+    fn ask(&mut self, text: &str, default: &str) -> io::Result<Option<String>> {
+        write!(self.output, "{} [{}]: ", text, default)?;
+        self.output.flush()?;
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            writeln!(self.output)?;
+        }
+        let answer = line.trim();
+        Ok((!answer.is_empty()).then(|| answer.to_string()))
+    }
+
+    /// Asks a yes/no question until the answer is one
+    fn yes_no(&mut self, text: &str, default: bool) -> io::Result<bool> {
+        loop {
+            let answer = self.ask(text, if default { "Y/n" } else { "y/N" })?;
+            match answer.map(|answer| answer.to_lowercase()).as_deref() {
+                None => return Ok(default),
+                Some("y" | "yes") => return Ok(true),
+                Some("n" | "no") => return Ok(false),
+                Some(_) => self.again(&tr!("setup-invalid"))?,
+            }
+        }
+    }
+
+    /// Explains why a question is asked again
+    fn again(&mut self, reason: &str) -> io::Result<()> {
+        writeln!(self.output, "  {}", reason)
+    }
+}
+
+/// Splits a comma- or space-separated list of names
+fn split_list(answer: &str) -> impl Iterator<Item = &str> {
+    answer
+        .split([',', ' '])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Renders the commented `devhealth.toml` for `answers`
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::config::Config;
+/// use devhealth::setup::{self, Answers, Environment};
+/// use std::path::Path;
+///
+/// let env = Environment::default();
+/// let toml = setup::render(&Answers::defaults(&env, Path::new("/src")), &env);
+/// let config: Config = toml::from_str(&toml).unwrap();
+/// assert!(config.scan.history);
+/// ```
+pub fn render(answers: &Answers, env: &Environment) -> String {
+    let list = |items: Vec<String>| items.join(", ");
+    let scanners = list(
+        answers
+            .scanners
+            .iter()
+            .map(|scanner| quote(scanner.name()))
+            .collect(),
+    );
+    let available = list(
+        Scanner::ALL
+            .iter()
+            .map(|scanner| scanner.name().to_string())
+            .collect(),
+    );
+    let fail_level = match answers.fail_level {
+        Some(severity) => format!("fail_level = {}", quote(severity.name())),
+        None => "# fail_level = \"error\"".to_string(),
+    };
+    let github_token = if env.forge_token {
+        "# Tokens are taken from GITHUB_TOKEN, GH_TOKEN and GITLAB_TOKEN"
+    } else {
+        "# Store tokens in the system keyring with `devhealth auth login github`\n# github_token = \"ghp_...\""
+    };
+
+    let mut tags = BTreeMap::new();
+    for root in &answers.roots {
+        let name = root.file_name().map_or("code".to_string(), |name| {
+            name.to_string_lossy().to_lowercase()
+        });
+        let mut unique = name.clone();
+        let mut suffix = 2;
+        while tags.contains_key(&unique) {
+            unique = format!("{}-{}", name, suffix);
+            suffix += 1;
+        }
+        let pattern = format!("{}/**", env.display_path(root).trim_end_matches('/'));
+        tags.insert(unique, pattern);
+    }
+    let tags: Vec<String> = tags
+        .iter()
+        .map(|(name, pattern)| format!("{} = [{}]", key(name), quote(pattern)))
+        .collect();
+
+    format!(
+        r#"# DevHealth configuration, written by `devhealth init`
+#
+# Every setting is optional; remove a line to get its default back.
+
+# Plain-text output without emoji or box drawing
+# accessible = true
+
+# Report language: "en", "de" or "es" (defaults to the locale)
+# lang = "en"
+
+[scan]
+# Scanners `devhealth scan` runs when no scanner flag is given
+# (available: {available})
+default = [{scanners}]
+# Record each report and show what changed since the previous run
+history = {history}
+
+[severity]
+# `check` and `scan` exit with status 1 once a finding reaches this level:
+# "info", "warn", "error" or "critical"
+{fail_level}
+# Days after which uncommitted changes count as stale
+stale_uncommitted_days = {stale_days}
+
+# Checks switched off everywhere (see `devhealth rules`)
+[checks]
+# disabled = ["stale-changelog"]
+
+# Forge access for CI status
+[forge]
+{github_token}
+# gitlab_hosts = ["gitlab.example.com"]
+
+# Code roots; filter reports with `--tag <name>`
+[tags]
+{tags}
+"#,
+        history = answers.history,
+        stale_days = answers.stale_uncommitted_days,
+        tags = tags.join("\n"),
+    )
+}
+
+/// Quotes `value` as a TOML string
+fn quote(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+/// Writes `name` as a TOML key, quoting it unless it is a bare key
+fn key(name: &str) -> String {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        name.to_string()
+    } else {
+        quote(name)
+    }
+}
+
+/// Writes the configuration to `path`, validating it first
+///
+/// # Errors
+///
+/// Returns an error if `path` exists and `force` is not set, or if the file
+/// cannot be written.
+pub fn write_config(path: &Path, contents: &str, force: bool) -> Result<(), SetupError> {
+    debug_assert!(
+        Config::check(contents).is_ok_and(|(_, issues)| issues.is_empty()),
+        "init wrote an invalid config"
+    );
+    if path.exists() && !force {
+        return Err(SetupError::ConfigExists(path.to_path_buf()));
+    }
+    let io_error = |source| SetupError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    fs::write(path, contents).map_err(io_error)
+}
+
+/// Returns the crontab lines for the daily scan and advisory watch
+///
+/// Each root's dependencies are scanned, which refreshes the inventory
+/// `watch-advisories` checks, at 9:00 every day.
+pub fn schedule_entry(exe: &Path, roots: &[PathBuf]) -> String {
+    let exe = shell_quote(&exe.to_string_lossy());
+    let mut commands: Vec<String> = roots
+        .iter()
+        .map(|root| {
+            format!(
+                "{} scan --deps --path {} >/dev/null 2>&1",
+                exe,
+                shell_quote(&root.to_string_lossy())
+            )
+        })
+        .collect();
+    commands.push(format!("{} watch-advisories >/dev/null 2>&1", exe));
+    format!("{}\n0 9 * * * {}\n", SCHEDULE_MARKER, commands.join("; "))
+}
+
+/// Adds `entry` to the user's crontab
+///
+/// The first line of `entry` is a marker comment. Returns `false` without
+/// changing anything if the crontab already holds that marker, such as an
+/// entry written by an earlier `init`.
+///
+/// # Errors
+///
+/// Returns an error if `crontab` cannot be run or rejects the new table.
+pub fn install_schedule(entry: &str) -> Result<bool, SetupError> {
+    let crontab_error = |e: io::Error| SetupError::Crontab(e.to_string());
+    // `crontab -l` fails when the user has no crontab yet
+    let current = Command::new("crontab")
+        .arg("-l")
+        .stderr(Stdio::null())
+        .output()
+        .map_err(crontab_error)?;
+    let mut table = if current.status.success() {
+        String::from_utf8_lossy(&current.stdout).into_owned()
+    } else {
+        String::new()
+    };
+    let marker = entry.lines().next().unwrap_or_default();
+    if table.lines().any(|line| line == marker) {
+        return Ok(false);
+    }
+    if !table.is_empty() && !table.ends_with('\n') {
+        table.push('\n');
+    }
+    table.push_str(entry);
+
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(crontab_error)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(table.as_bytes()).map_err(crontab_error)?;
+    }
+    let output = child.wait_with_output().map_err(crontab_error)?;
+    if !output.status.success() {
+        return Err(SetupError::Crontab(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(true)
+}
+
+/// Hooks written and left alone by [`install_hooks`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookOutcome {
+    /// Repositories that got a `pre-push` hook
+    pub installed: Vec<PathBuf>,
+    /// Repositories that already had one, which is kept
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Adds a `pre-push` hook running `devhealth check` to the repositories
+/// under `roots`
+///
+/// The hook checks the repository being pushed, so the push is refused
+/// when a finding reaches `fail_level`. Existing hooks are never replaced,
+/// and repositories whose `.git` is a file (worktrees, submodules) are left
+/// out.
+///
+/// # Errors
+///
+/// Returns an error if a hook cannot be written.
+pub fn install_hooks(roots: &[PathBuf], exe: &Path) -> Result<HookOutcome, SetupError> {
+    let hook = format!(
+        "#!/bin/sh\n{}\nexec {} check --path \"$(git rev-parse --show-toplevel)\"\n",
+        HOOK_MARKER,
+        shell_quote(&exe.to_string_lossy())
+    );
+    let mut outcome = HookOutcome::default();
+    for root in roots {
+        let repos = fs_utils::find_git_repositories(root).unwrap_or_default();
+        for repo in repos.into_iter().filter(|repo| repo.join(".git").is_dir()) {
+            let path = repo.join(".git").join("hooks").join("pre-push");
+            if path.exists() {
+                outcome.skipped.push(repo);
+                continue;
+            }
+            write_hook(&path, &hook).map_err(|source| SetupError::Io { path, source })?;
+            outcome.installed.push(repo);
+        }
+    }
+    Ok(outcome)
+}
+
+/// Writes an executable hook script
+fn write_hook(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+/// Quotes `value` for a POSIX shell
+pub(crate) fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn environment(tools: Vec<&'static str>) -> Environment {
+        Environment {
+            git: true,
+            tools,
+            forge_token: false,
+            cron: true,
+            roots: vec![
+                PathBuf::from("/home/ada/src"),
+                PathBuf::from("/home/ada/work"),
+            ],
+            home: Some(PathBuf::from("/home/ada")),
+        }
+    }
+
+    mod defaults {
+        use super::*;
+
+        #[test]
+        fn suggest_scanners_for_installed_tools() {
+            let answers = Answers::defaults(&environment(vec!["cargo"]), Path::new("/tmp"));
+            assert_eq!(
+                answers.scanners,
+                vec![Scanner::Git, Scanner::Deps, Scanner::Audit, Scanner::Cargo]
+            );
+            assert_eq!(answers.roots.len(), 2);
+            assert!(!answers.schedule && !answers.hooks);
+        }
+
+        #[test]
+        fn fall_back_to_the_current_directory() {
+            let env = Environment::default();
+            let answers = Answers::defaults(&env, Path::new("/tmp/here"));
+            assert_eq!(answers.roots, vec![PathBuf::from("/tmp/here")]);
+            assert_eq!(answers.scanners, vec![Scanner::Git, Scanner::Deps]);
+        }
+    }
+
+    mod questions {
+        use super::*;
+
+        #[test]
+        fn empty_answers_take_the_defaults() {
+            let env = environment(vec![]);
+            let mut output = Vec::new();
+            let answers = ask(&env, Path::new("/tmp"), &mut "".as_bytes(), &mut output).unwrap();
+            assert_eq!(answers, Answers::defaults(&env, Path::new("/tmp")));
+        }
+
+        #[test]
+        fn invalid_answers_are_asked_again() {
+            let temp_dir = TempDir::new().unwrap();
+            let mut env = environment(vec![]);
+            env.cron = false;
+            let input = format!(
+                "{}\ngit, nope\ngit audit\nn\nsevere\nnone\n0\n30\nyes\n",
+                temp_dir.path().display()
+            );
+            let mut output = Vec::new();
+            let answers = ask(&env, Path::new("/tmp"), &mut input.as_bytes(), &mut output).unwrap();
+
+            assert_eq!(answers.roots, vec![temp_dir.path().to_path_buf()]);
+            assert_eq!(answers.scanners, vec![Scanner::Git, Scanner::Audit]);
+            assert!(!answers.history);
+            assert_eq!(answers.fail_level, None);
+            assert_eq!(answers.stale_uncommitted_days, 30);
+            assert!(!answers.schedule, "cron is not available");
+            assert!(answers.hooks);
+        }
+    }
+
+    mod rendering {
+        use super::*;
+
+        #[test]
+        fn writes_a_config_that_loads() {
+            let env = environment(vec!["npm"]);
+            let mut answers = Answers::defaults(&env, Path::new("/tmp"));
+            answers.roots.push(PathBuf::from("/mnt/other/src"));
+            let toml = render(&answers, &env);
+
+            let config: Config = toml::from_str(&toml).unwrap();
+            assert_eq!(config.scan.default, answers.scanners);
+            assert!(config.scan.history);
+            assert_eq!(config.severity.fail_level, Some(Severity::Error));
+            assert_eq!(config.tags["src"], vec!["~/src/**".to_string()]);
+            assert_eq!(config.tags["work"], vec!["~/work/**".to_string()]);
+            assert_eq!(config.tags["src-2"], vec!["/mnt/other/src/**".to_string()]);
+            assert!(toml.contains("# accessible = true"));
+            assert!(toml.contains("\nsrc = [\"~/src/**\"]\n"));
+        }
+
+        #[test]
+        fn comments_out_a_missing_fail_level() {
+            let env = environment(vec![]);
+            let mut answers = Answers::defaults(&env, Path::new("/tmp"));
+            answers.fail_level = None;
+            let config: Config = toml::from_str(&render(&answers, &env)).unwrap();
+            assert_eq!(config.severity.fail_level, None);
+        }
+    }
+
+    mod installation {
+        use super::*;
+
+        #[test]
+        fn refuses_to_overwrite_without_force() {
+            let temp_dir = TempDir::new().unwrap();
+            let path = temp_dir.path().join("devhealth.toml");
+            fs::write(&path, "lang = \"de\"\n").unwrap();
+
+            assert!(matches!(
+                write_config(&path, "", false),
+                Err(SetupError::ConfigExists(_))
+            ));
+            write_config(&path, "", true).unwrap();
+            assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        }
+
+        #[test]
+        fn schedule_scans_each_root_then_watches() {
+            let entry = schedule_entry(
+                Path::new("/bin/devhealth"),
+                &[PathBuf::from("/home/ada/it's")],
+            );
+            let mut lines = entry.lines();
+            assert_eq!(lines.next(), Some(SCHEDULE_MARKER));
+            assert_eq!(
+                lines.next(),
+                Some(
+                    "0 9 * * * '/bin/devhealth' scan --deps --path '/home/ada/it'\\''s' >/dev/null 2>&1; \
+                     '/bin/devhealth' watch-advisories >/dev/null 2>&1"
+                )
+            );
+        }
+
+        #[test]
+        fn hooks_keep_existing_ones() {
+            let temp_dir = TempDir::new().unwrap();
+            for name in ["fresh", "hooked"] {
+                fs::create_dir_all(temp_dir.path().join(name).join(".git").join("hooks")).unwrap();
+            }
+            let existing = temp_dir.path().join("hooked/.git/hooks/pre-push");
+            fs::write(&existing, "#!/bin/sh\n").unwrap();
+
+            let outcome = install_hooks(
+                &[temp_dir.path().to_path_buf()],
+                Path::new("/bin/devhealth"),
+            )
+            .unwrap();
+            assert_eq!(outcome.installed, vec![temp_dir.path().join("fresh")]);
+            assert_eq!(outcome.skipped, vec![temp_dir.path().join("hooked")]);
+            let hook =
+                fs::read_to_string(temp_dir.path().join("fresh/.git/hooks/pre-push")).unwrap();
+            assert!(hook.contains("'/bin/devhealth' check --path"));
+            assert_eq!(fs::read_to_string(&existing).unwrap(), "#!/bin/sh\n");
+        }
+    }
+}
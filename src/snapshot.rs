@@ -0,0 +1,821 @@
+//! Scan snapshots for bug reports
+//!
+//! `devhealth export --out snapshot.tar.zst` bundles everything needed to
+//! reproduce what DevHealth saw on a machine into one archive:
+//!
+//! - `manifest.json`: DevHealth version, platform, scanned path, creation
+//!   time and the versions of common toolchain commands
+//! - `results.json`: the structured results, as printed by `--format json`
+//! - `devhealth.toml`: the configuration in use, with tokens redacted
+//!
+//! `devhealth import` prints a snapshot, or compares two with `--diff`.
+//!
+//! Archives are written and read by the system `tar` command, which ships
+//! with Linux, macOS and Windows 10 and later. The compression follows the
+//! file extension: `.tar.zst` (needs `zstd`), `.tar.gz` or plain `.tar`.
+
+use crate::i18n::t;
+use crate::report::ScanResults;
+use crate::scanner::container;
+use crate::scanner::git::{GitRepo, GitStatus};
+use crate::tr;
+use crate::utils::display::{self, Detail};
+use crate::utils::redact::{self, REDACTED};
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+/// Version of the snapshot layout written by this build
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+/// File names inside a snapshot archive
+const MANIFEST_FILE: &str = "manifest.json";
+const RESULTS_FILE: &str = "results.json";
+const CONFIG_FILE: &str = "devhealth.toml";
+
+/// Commands whose versions are recorded, with the arguments that print them
+const TOOLS: [(&str, &[&str]); 8] = [
+    ("git", &["--version"]),
+    ("rustc", &["--version"]),
+    ("cargo", &["--version"]),
+    ("node", &["--version"]),
+    ("npm", &["--version"]),
+    ("python3", &["--version"]),
+    ("go", &["version"]),
+    ("docker", &["--version"]),
+];
+
+/// Errors that can occur while writing or reading a snapshot
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("Failed to access snapshot: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse snapshot: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Failed to parse config file: {0}")]
+    TomlParse(#[from] toml::de::Error),
+    #[error("Failed to write config file: {0}")]
+    TomlWrite(#[from] toml::ser::Error),
+    #[error("Unsupported snapshot file {}: use .tar.zst, .tar.gz or .tar", .0.display())]
+    UnsupportedArchive(PathBuf),
+    #[error("Cannot run tar to handle the snapshot archive ({0})")]
+    ArchiverUnavailable(std::io::Error),
+    #[error("tar failed: {0}")]
+    Archive(String),
+    #[error("{} is not a DevHealth snapshot", .0.display())]
+    NotASnapshot(PathBuf),
+    #[error("Snapshot format {0} is newer than this DevHealth supports; please upgrade")]
+    NewerFormat(u32),
+}
+
+/// Where and with what a snapshot was taken
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Snapshot layout version, see [`SNAPSHOT_FORMAT`]
+    pub format: u32,
+    /// Version of DevHealth that took the snapshot
+    pub devhealth_version: String,
+    /// When the snapshot was taken, as a Unix timestamp
+    pub created_at: u64,
+    /// Operating system, e.g. `linux`
+    pub os: String,
+    /// CPU architecture, e.g. `x86_64`
+    pub arch: String,
+    /// The scanned directory
+    pub path: PathBuf,
+    /// Versions of common toolchain commands
+    pub tools: Vec<ToolInfo>,
+}
+
+/// Version of one toolchain command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolInfo {
+    /// Command name, e.g. `rustc`
+    pub tool: String,
+    /// Its version, `None` if it is not installed
+    pub version: Option<String>,
+}
+
+/// A scan snapshot: results, configuration and environment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Where and with what the snapshot was taken
+    pub manifest: Manifest,
+    /// The structured scan results
+    pub results: ScanResults,
+    /// The configuration file in use, with secrets redacted
+    pub config: Option<String>,
+}
+
+/// Differences between two snapshots
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SnapshotDiff {
+    /// DevHealth versions of both snapshots, if they differ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub devhealth_version: Option<(String, String)>,
+    /// Toolchain commands whose version changed
+    pub tools: Vec<Change>,
+    /// Whether the configuration changed
+    pub config_changed: bool,
+    /// Repositories whose branch or state changed, by path
+    pub repositories: Vec<Change>,
+    /// Advisories matched by the newer snapshot only
+    pub new_vulnerabilities: Vec<String>,
+    /// Advisories matched by the older snapshot only
+    pub resolved_vulnerabilities: Vec<String>,
+    /// Dependencies added, removed or changed, by name and ecosystem
+    pub dependencies: Vec<Change>,
+}
+
+/// One value that differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change {
+    /// What changed, e.g. a tool name or repository path
+    pub name: String,
+    /// Value in the older snapshot, `None` if absent
+    pub before: Option<String>,
+    /// Value in the newer snapshot, `None` if absent
+    pub after: Option<String>,
+}
+
+impl SnapshotDiff {
+    /// Returns `true` if the snapshots differ in anything compared
+    pub fn is_empty(&self) -> bool {
+        *self == SnapshotDiff::default()
+    }
+}
+
+/// Compression applied to a snapshot archive, chosen by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Returns the compression matching the archive's file name
+    fn of(path: &Path) -> Result<Compression, SnapshotError> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+            Ok(Compression::Zstd)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Ok(Compression::Gzip)
+        } else if name.ends_with(".tar") {
+            Ok(Compression::None)
+        } else {
+            Err(SnapshotError::UnsupportedArchive(path.to_path_buf()))
+        }
+    }
+
+    /// The `tar` flag selecting this compression
+    fn flag(self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("-z"),
+            Compression::Zstd => Some("--zstd"),
+        }
+    }
+}
+
+/// A temporary directory removed when dropped
+struct Staging(PathBuf);
+
+impl Staging {
+    fn new() -> std::io::Result<Staging> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_nanos())
+            .unwrap_or_default();
+        let dir = std::env::temp_dir().join(format!(
+            "devhealth-snapshot-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        fs::create_dir_all(&dir)?;
+        Ok(Staging(dir))
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+impl Manifest {
+    /// Describes the current machine, recording the versions of [`TOOLS`]
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The directory the snapshot's results are for
+    pub fn capture(path: &Path) -> Manifest {
+        let tools = TOOLS
+            .iter()
+            .map(|(tool, args)| ToolInfo {
+                tool: tool.to_string(),
+                version: container::run_command(tool, args)
+                    .and_then(|output| container::extract_version(&output)),
+            })
+            .collect();
+        Manifest {
+            format: SNAPSHOT_FORMAT,
+            devhealth_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: display::unix_now(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            path: path.to_path_buf(),
+            tools,
+        }
+    }
+}
+
+/// Checks that a snapshot file name has a supported archive extension
+///
+/// Lets `export` fail before running a check whose results could not be
+/// written.
+///
+/// # Errors
+///
+/// Returns [`SnapshotError::UnsupportedArchive`] for other extensions.
+pub fn ensure_supported(path: &Path) -> Result<(), SnapshotError> {
+    Compression::of(path).map(|_| ())
+}
+
+/// Replaces tokens, passwords and secrets in a configuration file
+///
+/// Every string value whose key ends in one of [`redact::SECRET_KEYS`]
+/// (`token`, `password`, `secret`, `apikey` or `api_key`) is replaced, in
+/// any table.
+///
+/// # Errors
+///
+/// Returns an error if the configuration is not valid TOML.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::snapshot::redact_config;
+///
+/// let redacted = redact_config("[forge]\ngithub_token = \"ghp_secret\"\n").unwrap();
+/// assert!(!redacted.contains("ghp_secret"));
+/// ```
+pub fn redact_config(content: &str) -> Result<String, SnapshotError> {
+    fn redact(value: &mut toml::Value) {
+        match value {
+            toml::Value::Table(table) => {
+                for (key, value) in table.iter_mut() {
+                    let key = key.to_lowercase();
+                    let secret = redact::SECRET_KEYS
+                        .iter()
+                        .any(|suffix| key.ends_with(suffix));
+                    if secret && value.is_str() {
+                        *value = toml::Value::String(REDACTED.to_string());
+                    } else {
+                        redact(value);
+                    }
+                }
+            }
+            toml::Value::Array(values) => values.iter_mut().for_each(redact),
+            _ => {}
+        }
+    }
+
+    let mut value: toml::Value = toml::from_str(content)?;
+    redact(&mut value);
+    Ok(toml::to_string(&value)?)
+}
+
+impl Snapshot {
+    /// Writes the snapshot as a `tar` archive
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extension is not a supported archive type,
+    /// `tar` cannot be run or fails, or the files cannot be written.
+    pub fn write(&self, out: &Path) -> Result<(), SnapshotError> {
+        let compression = Compression::of(out)?;
+        let out = std::path::absolute(out)?;
+        let staging = Staging::new()?;
+
+        fs::write(
+            staging.0.join(MANIFEST_FILE),
+            serde_json::to_string_pretty(&self.manifest)?,
+        )?;
+        fs::write(staging.0.join(RESULTS_FILE), self.results.to_json()?)?;
+        let mut files = vec![MANIFEST_FILE, RESULTS_FILE];
+        if let Some(config) = &self.config {
+            fs::write(staging.0.join(CONFIG_FILE), config)?;
+            files.push(CONFIG_FILE);
+        }
+
+        let mut command = Command::new("tar");
+        command
+            .arg("-c")
+            .args(compression.flag())
+            .arg("-f")
+            .arg(&out);
+        command.arg("-C").arg(&staging.0).args(files);
+        run_tar(command)
+    }
+
+    /// Reads a snapshot archive written by [`Snapshot::write`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive cannot be extracted, lacks a
+    /// manifest, or was written by a newer snapshot format.
+    pub fn read(path: &Path) -> Result<Snapshot, SnapshotError> {
+        let compression = Compression::of(path)?;
+        let archive = std::path::absolute(path)?;
+        let staging = Staging::new()?;
+
+        let mut command = Command::new("tar");
+        command
+            .arg("-x")
+            .args(compression.flag())
+            .arg("-f")
+            .arg(&archive);
+        command.arg("-C").arg(&staging.0);
+        run_tar(command)?;
+
+        let manifest = match fs::read_to_string(staging.0.join(MANIFEST_FILE)) {
+            Ok(content) => serde_json::from_str::<Manifest>(&content)?,
+            Err(_) => return Err(SnapshotError::NotASnapshot(path.to_path_buf())),
+        };
+        if manifest.format > SNAPSHOT_FORMAT {
+            return Err(SnapshotError::NewerFormat(manifest.format));
+        }
+        let results = serde_json::from_str(&fs::read_to_string(staging.0.join(RESULTS_FILE))?)?;
+        let config = fs::read_to_string(staging.0.join(CONFIG_FILE)).ok();
+
+        Ok(Snapshot {
+            manifest,
+            results,
+            config,
+        })
+    }
+
+    /// Compares this snapshot with a newer one
+    pub fn diff(&self, newer: &Snapshot) -> SnapshotDiff {
+        let (before, after) = (&self.manifest, &newer.manifest);
+        let devhealth_version = (before.devhealth_version != after.devhealth_version).then(|| {
+            (
+                before.devhealth_version.clone(),
+                after.devhealth_version.clone(),
+            )
+        });
+
+        let tools = |manifest: &Manifest| -> BTreeMap<String, String> {
+            manifest
+                .tools
+                .iter()
+                .filter_map(|tool| Some((tool.tool.clone(), tool.version.clone()?)))
+                .collect()
+        };
+        let repositories = |results: &ScanResults| -> BTreeMap<String, String> {
+            results
+                .git
+                .iter()
+                .flatten()
+                .map(|repo| (repo.path.display().to_string(), repository_state(repo)))
+                .collect()
+        };
+        let vulnerabilities = |results: &ScanResults| -> BTreeSet<String> {
+            results
+                .audit
+                .iter()
+                .flat_map(|audit| &audit.vulnerabilities)
+                .map(|vuln| {
+                    format!(
+                        "{} ({} {})",
+                        vuln.advisory_id, vuln.dependency.name, vuln.version
+                    )
+                })
+                .collect()
+        };
+        let dependencies = |results: &ScanResults| -> BTreeMap<String, String> {
+            let mut versions: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+            let declared = results
+                .dependencies
+                .iter()
+                .flatten()
+                .flat_map(|report| &report.dependencies);
+            for dependency in declared {
+                versions
+                    .entry(format!("{} ({})", dependency.name, dependency.ecosystem))
+                    .or_default()
+                    .insert(dependency.version.clone());
+            }
+            versions
+                .into_iter()
+                .map(|(name, versions)| (name, versions.into_iter().collect::<Vec<_>>().join(", ")))
+                .collect()
+        };
+
+        let (old_vulns, new_vulns) = (
+            vulnerabilities(&self.results),
+            vulnerabilities(&newer.results),
+        );
+        SnapshotDiff {
+            devhealth_version,
+            tools: changes(tools(before), tools(after)),
+            config_changed: self.config != newer.config,
+            repositories: changes(repositories(&self.results), repositories(&newer.results)),
+            new_vulnerabilities: new_vulns.difference(&old_vulns).cloned().collect(),
+            resolved_vulnerabilities: old_vulns.difference(&new_vulns).cloned().collect(),
+            dependencies: changes(dependencies(&self.results), dependencies(&newer.results)),
+        }
+    }
+
+    /// Displays the snapshot's environment followed by its results
+    ///
+    /// The bundled configuration is printed at [`Detail::Full`].
+    pub fn display(&self, detail: Detail) {
+        let manifest = &self.manifest;
+        println!(
+            "{}",
+            display::header(t("snapshot-title"), "📸", Role::Accent)
+        );
+        let config = if self.config.is_some() {
+            t("snapshot-config-included")
+        } else {
+            t("snapshot-config-none")
+        };
+        let summary_items = vec![
+            (t("snapshot-version"), manifest.devhealth_version.clone()),
+            (
+                t("snapshot-created"),
+                display::age(manifest.created_at, display::unix_now()),
+            ),
+            (
+                t("snapshot-platform"),
+                format!("{}/{}", manifest.os, manifest.arch),
+            ),
+            (t("snapshot-path"), manifest.path.display().to_string()),
+            (t("snapshot-config"), config.to_string()),
+        ];
+        print!("{}", display::summary_box(&summary_items));
+
+        if detail > Detail::Summary {
+            println!("{}", display::section_divider(t("snapshot-tools")));
+            for (index, tool) in manifest.tools.iter().enumerate() {
+                let is_last = index == manifest.tools.len() - 1;
+                let version = match &tool.version {
+                    Some(version) => version.paint(Role::Success),
+                    None => t("snapshot-tool-missing").paint(Role::Muted),
+                };
+                let line = format!("{} {}", tool.tool.paint(Role::Emphasis).bold(), version);
+                println!("{}", display::tree_item(&line, is_last, 0));
+            }
+        }
+
+        if detail == Detail::Full {
+            if let Some(config) = &self.config {
+                println!("{}", display::section_divider(t("snapshot-configuration")));
+                println!("{}", config.trim_end());
+            }
+        }
+
+        println!();
+        self.results.display(detail);
+    }
+}
+
+impl SnapshotDiff {
+    /// Displays the differences between two snapshots
+    ///
+    /// # Arguments
+    ///
+    /// * `before` - Manifest of the older snapshot
+    /// * `after` - Manifest of the newer snapshot
+    pub fn display(&self, before: &Manifest, after: &Manifest) {
+        println!(
+            "{}",
+            display::header(t("snapshot-diff-title"), "🔀", Role::Accent)
+        );
+        let now = display::unix_now();
+        println!(
+            "{}",
+            tr!(
+                "snapshot-diff-range",
+                before = display::age(before.created_at, now),
+                after = display::age(after.created_at, now)
+            )
+            .paint(Role::Muted)
+        );
+
+        if self.is_empty() {
+            println!(
+                "{} {}",
+                display::symbols("✅"),
+                t("snapshot-diff-none").paint(Role::Success)
+            );
+            return;
+        }
+
+        let mut tools = self.tools.clone();
+        if let Some((old, new)) = &self.devhealth_version {
+            tools.insert(
+                0,
+                Change {
+                    name: "devhealth".to_string(),
+                    before: Some(old.clone()),
+                    after: Some(new.clone()),
+                },
+            );
+        }
+        print_changes(t("snapshot-tools"), &tools);
+
+        if self.config_changed {
+            println!("{}", display::section_divider(t("snapshot-configuration")));
+            println!(
+                "{}",
+                display::tree_item(
+                    &t("snapshot-diff-config-changed")
+                        .paint(Role::Warning)
+                        .to_string(),
+                    true,
+                    0
+                )
+            );
+        }
+
+        print_changes(t("snapshot-diff-repositories"), &self.repositories);
+
+        let vulnerabilities: Vec<String> = self
+            .new_vulnerabilities
+            .iter()
+            .map(|vuln| format!("{} {}", "+".paint(Role::Error).bold(), vuln))
+            .chain(
+                self.resolved_vulnerabilities
+                    .iter()
+                    .map(|vuln| format!("{} {}", "-".paint(Role::Success).bold(), vuln)),
+            )
+            .collect();
+        if !vulnerabilities.is_empty() {
+            println!(
+                "{}",
+                display::section_divider(t("snapshot-diff-vulnerabilities"))
+            );
+            for (index, line) in vulnerabilities.iter().enumerate() {
+                let is_last = index == vulnerabilities.len() - 1;
+                println!("{}", display::tree_item(line, is_last, 0));
+            }
+        }
+
+        print_changes(t("snapshot-diff-dependencies"), &self.dependencies);
+    }
+}
+
+/// Runs a prepared `tar` command, turning failures into errors
+fn run_tar(mut command: Command) -> Result<(), SnapshotError> {
+    let output = command
+        .output()
+        .map_err(SnapshotError::ArchiverUnavailable)?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(SnapshotError::Archive(message))
+    }
+}
+
+/// Prints a section of changes, nothing if there are none
+fn print_changes(title: &str, changes: &[Change]) {
+    if changes.is_empty() {
+        return;
+    }
+    println!("{}", display::section_divider(title));
+    let missing = || t("snapshot-diff-absent").paint(Role::Muted).to_string();
+    for (index, change) in changes.iter().enumerate() {
+        let is_last = index == changes.len() - 1;
+        let line = format!(
+            "{}: {} → {}",
+            change.name.paint(Role::Emphasis).bold(),
+            change.before.clone().unwrap_or_else(missing),
+            change
+                .after
+                .as_deref()
+                .map(|after| after.paint(Role::Warning).to_string())
+                .unwrap_or_else(missing)
+        );
+        println!("{}", display::tree_item(&line, is_last, 0));
+    }
+}
+
+/// Summarizes a repository's state for comparison, e.g. `main, dirty`
+fn repository_state(repo: &GitRepo) -> String {
+    let status = match repo.status {
+        GitStatus::Clean => t("git-clean"),
+        GitStatus::Dirty => t("git-dirty"),
+        GitStatus::Error(_) => t("git-error"),
+    };
+    let mut state = format!("{}, {}", repo.branch, status.to_lowercase());
+    if repo.unpushed_commits {
+        state.push_str(", ↑");
+    }
+    state
+}
+
+/// Lists the keys whose values differ between two maps
+fn changes(before: BTreeMap<String, String>, mut after: BTreeMap<String, String>) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for (name, old) in before {
+        match after.remove(&name) {
+            Some(new) if new == old => {}
+            new => changes.push(Change {
+                name,
+                before: Some(old),
+                after: new,
+            }),
+        }
+    }
+    changes.extend(after.into_iter().map(|(name, new)| Change {
+        name,
+        before: None,
+        after: Some(new),
+    }));
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn manifest(tools: &[(&str, Option<&str>)]) -> Manifest {
+        Manifest {
+            format: SNAPSHOT_FORMAT,
+            devhealth_version: "0.2.0".to_string(),
+            created_at: 1_700_000_000,
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            path: PathBuf::from("/src"),
+            tools: tools
+                .iter()
+                .map(|(tool, version)| ToolInfo {
+                    tool: tool.to_string(),
+                    version: version.map(str::to_string),
+                })
+                .collect(),
+        }
+    }
+
+    fn repo(path: &str, status: GitStatus) -> GitRepo {
+        GitRepo {
+            status,
+            ..GitRepo::test(path)
+        }
+    }
+
+    mod archives {
+        use super::*;
+
+        #[test]
+        fn chooses_compression_by_extension() {
+            assert_eq!(
+                Compression::of(Path::new("out/snapshot.tar.zst")).unwrap(),
+                Compression::Zstd
+            );
+            assert_eq!(
+                Compression::of(Path::new("snapshot.TGZ")).unwrap(),
+                Compression::Gzip
+            );
+            assert_eq!(
+                Compression::of(Path::new("snapshot.tar")).unwrap(),
+                Compression::None
+            );
+            assert!(matches!(
+                Compression::of(Path::new("snapshot.zip")),
+                Err(SnapshotError::UnsupportedArchive(_))
+            ));
+        }
+
+        #[test]
+        fn round_trips_through_tar() {
+            let temp_dir = TempDir::new().unwrap();
+            let out = temp_dir.path().join("snapshot.tar.gz");
+            let snapshot = Snapshot {
+                manifest: manifest(&[("rustc", Some("1.80.0")), ("go", None)]),
+                results: ScanResults {
+                    git: Some(vec![repo("/src/api", GitStatus::Clean)]),
+                    ..Default::default()
+                },
+                config: Some("lang = \"de\"\n".to_string()),
+            };
+
+            snapshot.write(&out).unwrap();
+            let read = Snapshot::read(&out).unwrap();
+
+            assert_eq!(read.manifest, snapshot.manifest);
+            assert_eq!(read.config, snapshot.config);
+            assert_eq!(read.results.git.map(|repos| repos.len()), Some(1));
+        }
+
+        #[test]
+        fn rejects_archives_without_a_manifest() {
+            let temp_dir = TempDir::new().unwrap();
+            fs::write(temp_dir.path().join("notes.txt"), "hello").unwrap();
+            let out = temp_dir.path().join("other.tar");
+            let status = Command::new("tar")
+                .arg("-cf")
+                .arg(&out)
+                .arg("-C")
+                .arg(temp_dir.path())
+                .arg("notes.txt")
+                .status()
+                .unwrap();
+            assert!(status.success());
+
+            assert!(matches!(
+                Snapshot::read(&out),
+                Err(SnapshotError::NotASnapshot(_))
+            ));
+        }
+    }
+
+    mod redaction {
+        use super::*;
+
+        #[test]
+        fn redacts_tokens_in_every_table() {
+            let config = "lang = \"en\"\n\n[forge]\ngithub_token = \"ghp_abc\"\ngitlab_hosts = [\"git.example.com\"]\n\n[proxy]\npassword = \"hunter2\"\n\n[registry]\napi_key = \"k3y-value\"\n";
+
+            let redacted = redact_config(config).unwrap();
+
+            assert!(!redacted.contains("ghp_abc"));
+            assert!(!redacted.contains("hunter2"));
+            assert!(!redacted.contains("k3y-value"));
+            assert!(redacted.contains("git.example.com"));
+            assert!(redacted.contains(REDACTED));
+        }
+    }
+
+    mod diffing {
+        use super::*;
+
+        fn snapshot(tools: &[(&str, Option<&str>)], repos: Vec<GitRepo>) -> Snapshot {
+            Snapshot {
+                manifest: manifest(tools),
+                results: ScanResults {
+                    git: Some(repos),
+                    ..Default::default()
+                },
+                config: None,
+            }
+        }
+
+        #[test]
+        fn reports_tool_and_repository_changes() {
+            let before = snapshot(
+                &[("rustc", Some("1.79.0")), ("node", Some("20.1.0"))],
+                vec![
+                    repo("/src/api", GitStatus::Clean),
+                    repo("/src/old", GitStatus::Clean),
+                ],
+            );
+            let after = snapshot(
+                &[("rustc", Some("1.80.0")), ("node", Some("20.1.0"))],
+                vec![repo("/src/api", GitStatus::Dirty)],
+            );
+
+            let diff = before.diff(&after);
+
+            assert_eq!(
+                diff.tools,
+                vec![Change {
+                    name: "rustc".to_string(),
+                    before: Some("1.79.0".to_string()),
+                    after: Some("1.80.0".to_string()),
+                }]
+            );
+            assert_eq!(diff.repositories.len(), 2);
+            assert_eq!(diff.repositories[0].after.as_deref(), Some("main, dirty"));
+            assert_eq!(
+                diff.repositories[1].after, None,
+                "removed repositories have no new state"
+            );
+            assert!(!diff.config_changed);
+        }
+
+        #[test]
+        fn finds_nothing_between_identical_snapshots() {
+            let snapshot = snapshot(
+                &[("git", Some("2.43.0"))],
+                vec![repo("/src/api", GitStatus::Clean)],
+            );
+
+            assert!(snapshot.diff(&snapshot.clone()).is_empty());
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Anonymous aggregate statistics for platform teams
+//!
+//! `devhealth stats export` condenses a report into counts a platform team
+//! can collect from many developers without learning what anyone works on:
+//!
+//! - **Repositories**: how many there are, how many are dirty, have unpushed
+//!   commits or failed to scan, and how their health scores are distributed
+//! - **Ecosystems**: manifest files per ecosystem
+//! - **Dependencies**: projects, dependencies and vulnerable dependencies
+//! - **Findings**: counts per rule ID and per severity
+//!
+//! Nothing identifying is included: no repository or project names, paths,
+//! branches, remotes, package names or advisory IDs. The export is only
+//! written when asked for and never sent anywhere by DevHealth.
+
+use crate::findings::Severity;
+use crate::report::ScanResults;
+use crate::scanner::deps::Ecosystem;
+use crate::scanner::git::GitStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Version of the statistics format, raised on incompatible changes
+pub const STATS_SCHEMA: u32 = 1;
+
+/// Seconds in a day
+const DAY: u64 = 24 * 60 * 60;
+
+/// Aggregate statistics of one report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeamStats {
+    /// Format version, see [`STATS_SCHEMA`]
+    pub schema: u32,
+    /// DevHealth version that produced the statistics
+    pub devhealth_version: String,
+    /// Day the statistics were produced, as the Unix timestamp of its start
+    /// (the time of day is left out)
+    pub day: u64,
+    /// Operating system, e.g. `linux`
+    pub os: String,
+    /// Repository counts, if the git scanner ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repositories: Option<RepositoryStats>,
+    /// Manifest files per ecosystem
+    pub ecosystems: BTreeMap<Ecosystem, usize>,
+    /// Dependency counts, if the dependency scanner ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<DependencyStats>,
+    /// Findings per rule ID
+    pub findings_by_rule: BTreeMap<String, usize>,
+    /// Findings per severity
+    pub findings_by_severity: BTreeMap<Severity, usize>,
+}
+
+/// Repository counts and the distribution of their health scores
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepositoryStats {
+    /// Repositories found
+    pub total: usize,
+    /// Repositories with uncommitted changes
+    pub dirty: usize,
+    /// Repositories with unpushed commits
+    pub unpushed: usize,
+    /// Repositories that could not be analyzed
+    pub errors: usize,
+    /// Number of repositories per health score (0–100)
+    pub scores: BTreeMap<u8, usize>,
+    /// Mean health score, `None` without repositories
+    pub mean_score: Option<f64>,
+}
+
+/// Dependency counts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DependencyStats {
+    /// Projects with at least one manifest
+    pub projects: usize,
+    /// Dependencies declared across all projects, if they were listed
+    /// (a quick check only counts manifests)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<usize>,
+    /// Dependencies matched by an advisory, if an audit ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vulnerable: Option<usize>,
+}
+
+impl TeamStats {
+    /// Aggregates `results` at `now`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::report::ScanResults;
+    /// use devhealth::stats::TeamStats;
+    ///
+    /// let stats = TeamStats::from_results(&ScanResults::default(), 100_000);
+    /// assert_eq!(stats.day, 86_400);
+    /// assert!(stats.repositories.is_none());
+    /// ```
+    pub fn from_results(results: &ScanResults, now: u64) -> TeamStats {
+        let repositories = results.git.as_ref().map(|repos| {
+            let mut stats = RepositoryStats {
+                total: repos.len(),
+                ..Default::default()
+            };
+            for repo in repos {
+                stats.dirty += usize::from(repo.uncommitted_changes);
+                stats.unpushed += usize::from(repo.unpushed_commits);
+                stats.errors += usize::from(matches!(repo.status, GitStatus::Error(_)));
+                *stats.scores.entry(repo.health_score()).or_default() += 1;
+            }
+            if !repos.is_empty() {
+                let sum: usize = repos
+                    .iter()
+                    .map(|repo| usize::from(repo.health_score()))
+                    .sum();
+                stats.mean_score = Some(sum as f64 / repos.len() as f64);
+            }
+            stats
+        });
+
+        let mut ecosystems = BTreeMap::new();
+        if let Some(manifests) = &results.manifests {
+            for count in &manifests.ecosystems {
+                *ecosystems.entry(count.ecosystem.clone()).or_default() += count.manifests;
+            }
+        } else if let Some(reports) = &results.dependencies {
+            for ecosystem in reports.iter().flat_map(|report| &report.ecosystems) {
+                *ecosystems.entry(ecosystem.clone()).or_default() += 1;
+            }
+        }
+
+        let vulnerable = results
+            .audit
+            .as_ref()
+            .map(|audit| audit.vulnerabilities.len());
+        let dependencies = match (&results.dependencies, &results.manifests) {
+            (Some(reports), _) => Some(DependencyStats {
+                projects: reports.len(),
+                dependencies: Some(reports.iter().map(|report| report.dependencies.len()).sum()),
+                vulnerable,
+            }),
+            (None, Some(manifests)) => Some(DependencyStats {
+                projects: manifests.projects,
+                dependencies: None,
+                vulnerable,
+            }),
+            (None, None) => None,
+        };
+
+        let mut findings_by_rule = BTreeMap::new();
+        let mut findings_by_severity = BTreeMap::new();
+        for finding in &results.findings {
+            *findings_by_rule.entry(finding.rule.clone()).or_default() += 1;
+            *findings_by_severity.entry(finding.severity).or_default() += 1;
+        }
+
+        TeamStats {
+            schema: STATS_SCHEMA,
+            devhealth_version: env!("CARGO_PKG_VERSION").to_string(),
+            day: now - now % DAY,
+            os: std::env::consts::OS.to_string(),
+            repositories,
+            ecosystems,
+            dependencies,
+            findings_by_rule,
+            findings_by_severity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::findings::{Check, Finding};
+    use crate::scanner::deps::{EcosystemCount, ManifestSummary};
+    use crate::scanner::git::GitRepo;
+    use std::path::PathBuf;
+
+    fn repo(path: &str, dirty: bool, unpushed: bool) -> GitRepo {
+        GitRepo {
+            status: if dirty {
+                GitStatus::Dirty
+            } else {
+                GitStatus::Clean
+            },
+            branch: "secret-feature".to_string(),
+            uncommitted_changes: dirty,
+            unpushed_commits: unpushed,
+            ..GitRepo::test(path)
+        }
+    }
+
+    #[test]
+    fn counts_without_names_or_paths() {
+        let results = ScanResults {
+            git: Some(vec![
+                repo("/home/me/acme-payments", true, false),
+                repo("/home/me/acme-ledger", false, true),
+                repo("/home/me/dotfiles", false, false),
+            ]),
+            manifests: Some(ManifestSummary {
+                projects: 2,
+                manifests: 3,
+                ecosystems: vec![
+                    EcosystemCount {
+                        ecosystem: Ecosystem::Rust,
+                        manifests: 2,
+                    },
+                    EcosystemCount {
+                        ecosystem: Ecosystem::NodeJs,
+                        manifests: 1,
+                    },
+                ],
+                complete: true,
+            }),
+            findings: vec![Finding {
+                rule: "GIT002".to_string(),
+                check: Check::UncommittedChanges,
+                severity: Severity::Warn,
+                path: PathBuf::from("/home/me/acme-payments"),
+                detail: Some("acme".to_string()),
+            }],
+            ..Default::default()
+        };
+
+        let stats = TeamStats::from_results(&results, 1_000);
+        let repositories = stats.repositories.as_ref().unwrap();
+        assert_eq!(
+            (
+                repositories.total,
+                repositories.dirty,
+                repositories.unpushed
+            ),
+            (3, 1, 1)
+        );
+        assert_eq!(
+            repositories.scores,
+            BTreeMap::from([(60, 1), (80, 1), (100, 1)])
+        );
+        assert_eq!(repositories.mean_score, Some(80.0));
+        assert_eq!(stats.ecosystems[&Ecosystem::Rust], 2);
+        assert_eq!(stats.dependencies.as_ref().unwrap().projects, 2);
+        assert_eq!(stats.findings_by_rule["GIT002"], 1);
+        assert_eq!(stats.findings_by_severity[&Severity::Warn], 1);
+
+        let json = serde_json::to_string(&stats).unwrap();
+        for secret in ["acme", "dotfiles", "/home", "secret-feature"] {
+            assert!(!json.contains(secret), "{} leaked into {}", secret, json);
+        }
+    }
+}
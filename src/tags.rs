@@ -0,0 +1,294 @@
+//! Repository grouping and tagging
+//!
+//! Repositories can be tagged in two ways:
+//!
+//! - declaratively, with path patterns in the `[tags]` section of `devhealth.toml`
+//! - with `devhealth tag add <repo> <tag>...`, which stores tags per repository
+//!   in `tags.json` inside the DevHealth data directory
+//!
+//! A [`TagFilter`] combines both sources and decides which repositories and
+//! projects a `--tag` filtered scan includes. Tags apply to the tagged path
+//! and everything beneath it, so dependency projects inside a tagged
+//! repository are included too.
+
+use crate::config::{Config, ConfigError};
+use crate::utils::fs as dh_fs;
+use crate::utils::paths;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the tag store inside the state directory
+pub const TAGS_FILE: &str = "tags.json";
+
+/// Errors that can occur while reading or writing tags
+#[derive(Error, Debug)]
+pub enum TagError {
+    #[error("Failed to access tag store: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse tag store: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Could not determine the DevHealth state directory")]
+    NoStateDirectory,
+}
+
+/// Tags assigned to repositories with the `devhealth tag` command
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TagStore {
+    /// Canonical repository path mapped to its tags
+    pub repositories: BTreeMap<PathBuf, BTreeSet<String>>,
+}
+
+impl TagStore {
+    /// Returns the default location of the tag store
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TagError::NoStateDirectory`] if no home directory is known.
+    pub fn default_path() -> Result<PathBuf, TagError> {
+        paths::data_dir()
+            .map(|dir| dir.join(TAGS_FILE))
+            .ok_or(TagError::NoStateDirectory)
+    }
+
+    /// Loads the tag store from `path`, returning an empty store if missing
+    pub fn load(path: &Path) -> Result<TagStore, TagError> {
+        if !path.exists() {
+            return Ok(TagStore::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the tag store to `path`, creating parent directories if needed
+    pub fn save(&self, path: &Path) -> Result<(), TagError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Adds tags to a repository
+    pub fn add(&mut self, repo: &Path, tags: &[String]) {
+        self.repositories
+            .entry(repo.to_path_buf())
+            .or_default()
+            .extend(tags.iter().cloned());
+    }
+
+    /// Removes tags from a repository
+    ///
+    /// Returns `true` if any tag was removed. Repositories left without tags
+    /// are dropped from the store.
+    pub fn remove(&mut self, repo: &Path, tags: &[String]) -> bool {
+        let Some(existing) = self.repositories.get_mut(repo) else {
+            return false;
+        };
+
+        let before = existing.len();
+        existing.retain(|tag| !tags.contains(tag));
+        let removed = existing.len() != before;
+
+        if existing.is_empty() {
+            self.repositories.remove(repo);
+        }
+        removed
+    }
+}
+
+/// Selects repositories and projects by tag
+#[derive(Debug, Clone, Default)]
+pub struct TagFilter {
+    /// Tags requested on the command line; empty means "no filtering"
+    wanted: Vec<String>,
+    /// Tag patterns from the configuration file
+    patterns: BTreeMap<String, Vec<String>>,
+    /// Tags assigned with the `tag` command
+    store: TagStore,
+}
+
+impl TagFilter {
+    /// Creates a filter from explicit configuration and tag store
+    pub fn new(wanted: &[String], config: &Config, store: TagStore) -> Self {
+        TagFilter {
+            wanted: wanted.to_vec(),
+            patterns: config.tags.clone(),
+            store,
+        }
+    }
+
+    /// Creates a filter for `wanted`, loading the configuration and tag store
+    ///
+    /// Nothing is loaded when `wanted` is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration or tag store cannot be read.
+    pub fn load(wanted: &[String]) -> Result<Self, TagError> {
+        if wanted.is_empty() {
+            return Ok(TagFilter::default());
+        }
+
+        let config = Config::load()?;
+        let store = TagStore::load(&TagStore::default_path()?)?;
+        Ok(TagFilter::new(wanted, &config, store))
+    }
+
+    /// Returns `true` if the filter restricts results to specific tags
+    pub fn is_active(&self) -> bool {
+        !self.wanted.is_empty()
+    }
+
+    /// Returns every tag that applies to `path`
+    ///
+    /// Tags on any ancestor of `path` apply as well.
+    pub fn tags_for(&self, path: &Path) -> BTreeSet<String> {
+        let path = canonical(path);
+        let mut tags = BTreeSet::new();
+
+        for ancestor in path.ancestors() {
+            if let Some(stored) = self.store.repositories.get(ancestor) {
+                tags.extend(stored.iter().cloned());
+            }
+            for (tag, patterns) in &self.patterns {
+                if patterns.iter().any(|p| dh_fs::glob_matches(p, ancestor)) {
+                    tags.insert(tag.clone());
+                }
+            }
+        }
+
+        tags
+    }
+
+    /// Returns `true` if `path` should be included in the scan
+    pub fn matches(&self, path: &Path) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+        let tags = self.tags_for(path);
+        self.wanted.iter().any(|tag| tags.contains(tag))
+    }
+}
+
+/// Resolves a path to its canonical absolute form, falling back to the input
+pub fn canonical(path: &Path) -> PathBuf {
+    dh_fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    mod tag_store {
+        use super::*;
+
+        #[test]
+        fn adds_and_removes_tags() {
+            let mut store = TagStore::default();
+            let repo = Path::new("/src/api");
+
+            store.add(repo, &tags(&["work", "backend"]));
+            assert_eq!(store.repositories[repo].len(), 2);
+
+            assert!(store.remove(repo, &tags(&["backend"])));
+            assert!(!store.remove(repo, &tags(&["missing"])));
+            assert!(store.remove(repo, &tags(&["work"])));
+            assert!(
+                store.repositories.is_empty(),
+                "Repositories without tags should be dropped"
+            );
+        }
+
+        #[test]
+        fn round_trips_through_file() {
+            let temp_dir = TempDir::new().unwrap();
+            let path = temp_dir.path().join("nested").join(TAGS_FILE);
+
+            let mut store = TagStore::default();
+            store.add(Path::new("/src/api"), &tags(&["work"]));
+            store.save(&path).unwrap();
+
+            assert_eq!(TagStore::load(&path).unwrap(), store);
+        }
+
+        #[test]
+        fn missing_file_yields_empty_store() {
+            let temp_dir = TempDir::new().unwrap();
+            let store = TagStore::load(&temp_dir.path().join(TAGS_FILE)).unwrap();
+            assert!(store.repositories.is_empty());
+        }
+    }
+
+    mod tag_filter {
+        use super::*;
+
+        #[test]
+        fn inactive_filter_matches_everything() {
+            let filter = TagFilter::default();
+            assert!(filter.matches(Path::new("/anything")));
+        }
+
+        #[test]
+        fn matches_stored_tags_on_path_and_descendants() {
+            let temp_dir = TempDir::new().unwrap();
+            let repo = canonical(temp_dir.path());
+            let mut store = TagStore::default();
+            store.add(&repo, &tags(&["work"]));
+
+            let filter = TagFilter::new(&tags(&["work"]), &Config::default(), store);
+
+            assert!(filter.matches(&repo));
+            assert!(filter.matches(&repo.join("crates").join("core")));
+            assert!(!filter.matches(Path::new("/elsewhere")));
+        }
+
+        #[test]
+        fn matches_config_patterns() {
+            let mut config = Config::default();
+            config
+                .tags
+                .insert("personal".to_string(), tags(&["/home/me/personal/*"]));
+
+            let filter = TagFilter::new(&tags(&["personal"]), &config, TagStore::default());
+
+            assert!(filter.matches(Path::new("/home/me/personal/blog")));
+            assert!(filter.matches(Path::new("/home/me/personal/blog/frontend")));
+            assert!(!filter.matches(Path::new("/home/me/work/api")));
+        }
+
+        #[test]
+        fn any_requested_tag_is_enough() {
+            let mut config = Config::default();
+            config.tags.insert("a".to_string(), tags(&["/x/**"]));
+            config.tags.insert("b".to_string(), tags(&["/y/**"]));
+
+            let filter = TagFilter::new(&tags(&["a", "b"]), &config, TagStore::default());
+
+            assert!(filter.matches(Path::new("/x/one")));
+            assert!(filter.matches(Path::new("/y/two")));
+            assert!(!filter.matches(Path::new("/z/three")));
+        }
+
+        #[test]
+        fn collects_tags_from_all_sources() {
+            let mut config = Config::default();
+            config.tags.insert("work".to_string(), tags(&["/src/**"]));
+            let mut store = TagStore::default();
+            store.add(Path::new("/src/api"), &tags(&["backend"]));
+
+            let filter = TagFilter::new(&[], &config, store);
+            let found: Vec<_> = filter.tags_for(Path::new("/src/api")).into_iter().collect();
+
+            assert_eq!(found, vec!["backend", "work"]);
+        }
+    }
+}
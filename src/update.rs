@@ -0,0 +1,689 @@
+//! Release updates (`devhealth self-update`) and the daily version check
+//!
+//! Releases are published on GitHub with one binary per platform, named
+//! `devhealth-<arch>-<os>` (with `.exe` on Windows, e.g.
+//! `devhealth-x86_64-linux`), a `SHA256SUMS` file listing their checksums,
+//! and `SHA256SUMS.sig`, an SSH signature of that file:
+//!
+//! ```bash
+//! sha256sum devhealth-* > SHA256SUMS
+//! ssh-keygen -Y sign -f release_key -n devhealth-release SHA256SUMS
+//! ```
+//!
+//! `self-update` downloads the binary for the running platform, the
+//! checksums and their signature, and only installs a binary whose checksum
+//! matches checksums signed by the `public_key` from `[update]` or the key
+//! built into official releases (`DEVHEALTH_RELEASE_KEY` at build time).
+//! Without a key, or for unsigned releases, nothing is installed. The new
+//! binary is written next to the executable and renamed over it, so an
+//! interrupted update leaves the old one working.
+//!
+//! Once a day, commands run in a terminal look for a newer release and
+//! mention it after their output. The check never delays a command: it runs
+//! as a detached `self-update --check`, whose result the next run reads from
+//! `update-check.json` in the cache directory. `check = false` in
+//! `[update]`, `DEVHEALTH_NO_UPDATE_CHECK=1` or `--offline` turn it off.
+
+use crate::config::UpdateConfig;
+use crate::utils::paths;
+use crate::utils::signature::{self, SignatureError};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use thiserror::Error;
+
+/// GitHub API endpoint describing the latest release
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/Art-Jashari/DevHealth/releases/latest";
+
+/// Name of the release asset listing the binaries' SHA-256 checksums
+pub const CHECKSUMS_FILE: &str = "SHA256SUMS";
+
+/// Name of the release asset holding the signature of the checksums
+pub const SIGNATURE_FILE: &str = "SHA256SUMS.sig";
+
+/// Namespace release signatures are made for (`ssh-keygen -Y sign -n`)
+pub const SIGNATURE_NAMESPACE: &str = "devhealth-release";
+
+/// Public key of official releases, set with `DEVHEALTH_RELEASE_KEY` when
+/// building them
+pub const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("DEVHEALTH_RELEASE_KEY");
+
+/// File name of the last version check inside the cache directory
+pub const CHECK_FILE: &str = "update-check.json";
+
+/// Seconds between two background version checks
+pub const CHECK_INTERVAL: u64 = 24 * 60 * 60;
+
+/// Name of the release lookup in the list of checks skipped offline
+pub const UPDATE_CHECK: &str = "release lookup";
+
+/// Environment variable turning the background version check off
+pub const NO_CHECK_VAR: &str = "DEVHEALTH_NO_UPDATE_CHECK";
+
+/// Errors that can occur while checking for or installing a release
+#[derive(Error, Debug)]
+pub enum UpdateError {
+    #[error("Failed to download the release: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Failed to access the version check: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse the version check: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error("Release {tag} has an invalid version: {source}")]
+    InvalidVersion {
+        tag: String,
+        #[source]
+        source: semver::Error,
+    },
+    #[error("Release {version} has no binary for this platform ({asset})")]
+    NoAsset { version: Version, asset: String },
+    #[error("SHA256SUMS of release {version} does not list {asset}")]
+    NoChecksum { version: Version, asset: String },
+    #[error("Checksum of {asset} does not match SHA256SUMS (expected {expected}, got {actual})")]
+    ChecksumMismatch {
+        asset: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Release {0} is not signed (SHA256SUMS.sig is missing)")]
+    Unsigned(Version),
+    #[error("No release key to verify updates with: set public_key under [update] or install an official build")]
+    NoPublicKey,
+    #[error("Release signature is not valid: {0}")]
+    Signature(#[from] SignatureError),
+    #[error("Failed to replace {}: {source}", path.display())]
+    Replace {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Could not determine the DevHealth cache directory")]
+    NoCacheDirectory,
+}
+
+/// A published release, as described by the GitHub API
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    /// Git tag of the release, such as `v0.3.0`
+    pub tag_name: String,
+    /// Files attached to the release
+    pub assets: Vec<Asset>,
+}
+
+/// A file attached to a release
+#[derive(Debug, Clone, Deserialize)]
+pub struct Asset {
+    /// File name, such as `devhealth-x86_64-linux`
+    pub name: String,
+    /// URL the file is downloaded from
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// Returns the version the release's tag names
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tag (without a leading `v`) is not a semantic
+    /// version.
+    pub fn version(&self) -> Result<Version, UpdateError> {
+        Version::parse(self.tag_name.trim_start_matches('v')).map_err(|source| {
+            UpdateError::InvalidVersion {
+                tag: self.tag_name.clone(),
+                source,
+            }
+        })
+    }
+
+    /// Returns the asset called `name`
+    pub fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// What [`self_update`] did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    /// The running version is the latest release
+    UpToDate(Version),
+    /// A newer release exists; only reported with `check_only`
+    Available(Version),
+    /// The executable was replaced
+    Updated { from: Version, to: Version },
+}
+
+/// The result of the last version check
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionCheck {
+    /// Unix time of the last check
+    pub checked_at: u64,
+    /// Version of the latest release at that time
+    pub latest: Option<String>,
+}
+
+impl VersionCheck {
+    /// Returns the location of the version check in the cache directory
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UpdateError::NoCacheDirectory`] if no home directory is
+    /// known.
+    pub fn default_path() -> Result<PathBuf, UpdateError> {
+        paths::cache_dir()
+            .map(|dir| dir.join(CHECK_FILE))
+            .ok_or(UpdateError::NoCacheDirectory)
+    }
+
+    /// Loads the last check; a missing or unreadable file yields none
+    pub fn load(path: &Path) -> VersionCheck {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves the check, replacing the file atomically
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), UpdateError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let partial = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&partial, serde_json::to_string(self)?)?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    /// Returns `true` once a day has passed since the last check
+    pub fn is_due(&self, now: u64) -> bool {
+        now.saturating_sub(self.checked_at) >= CHECK_INTERVAL
+    }
+
+    /// Returns the latest release if it is newer than `current`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::update::VersionCheck;
+    /// use semver::Version;
+    ///
+    /// let check = VersionCheck { checked_at: 0, latest: Some("1.2.0".to_string()) };
+    /// assert_eq!(check.newer_than(&Version::new(1, 1, 0)), Some(Version::new(1, 2, 0)));
+    /// assert_eq!(check.newer_than(&Version::new(1, 2, 0)), None);
+    /// ```
+    pub fn newer_than(&self, current: &Version) -> Option<Version> {
+        self.latest
+            .as_deref()
+            .and_then(|latest| Version::parse(latest).ok())
+            .filter(|latest| latest > current)
+    }
+}
+
+/// Returns the version of the running executable
+pub fn current_version() -> Version {
+    Version::parse(env!("CARGO_PKG_VERSION")).expect("the package version is a semantic version")
+}
+
+/// Returns the name of the release binary for the running platform
+pub fn asset_name() -> String {
+    format!(
+        "devhealth-{}-{}{}",
+        std::env::consts::ARCH,
+        std::env::consts::OS,
+        std::env::consts::EXE_SUFFIX
+    )
+}
+
+/// Returns the SHA-256 checksum of `data` as lowercase hex
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Finds the checksum of `asset` in a `SHA256SUMS` file
+///
+/// Lines are `sha256sum` output: the hex checksum, whitespace, and the file
+/// name, which binary mode prefixes with `*`.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::update;
+///
+/// let sums = "ab12  devhealth-x86_64-linux\ncd34 *devhealth-x86_64-windows.exe\n";
+/// assert_eq!(update::checksum_for(sums, "devhealth-x86_64-windows.exe"), Some("cd34"));
+/// assert_eq!(update::checksum_for(sums, "devhealth-aarch64-macos"), None);
+/// ```
+pub fn checksum_for<'a>(sums: &'a str, asset: &str) -> Option<&'a str> {
+    sums.lines().find_map(|line| {
+        let (checksum, name) = line.trim().split_once(char::is_whitespace)?;
+        (name.trim_start().trim_start_matches('*') == asset).then_some(checksum)
+    })
+}
+
+/// Returns the key releases must be signed with: the `public_key` from
+/// `[update]`, or the one built into official releases
+///
+/// # Errors
+///
+/// Returns [`UpdateError::NoPublicKey`] if neither is set.
+pub fn release_key(config: &UpdateConfig) -> Result<&str, UpdateError> {
+    let not_blank = |key: &&str| !key.trim().is_empty();
+    config
+        .public_key
+        .as_deref()
+        .filter(not_blank)
+        .or(RELEASE_PUBLIC_KEY.filter(not_blank))
+        .ok_or(UpdateError::NoPublicKey)
+}
+
+/// Checks a downloaded binary against the release's checksums and signature
+///
+/// The checksums must carry a valid signature by `public_key` made for
+/// [`SIGNATURE_NAMESPACE`], and list the checksum of `binary`.
+///
+/// # Errors
+///
+/// Returns an error if the signature is missing or invalid, or if the
+/// checksum of `binary` is not listed or does not match.
+pub fn verify_release(
+    version: &Version,
+    asset: &str,
+    binary: &[u8],
+    sums: &[u8],
+    signature: Option<&[u8]>,
+    public_key: &str,
+    work_dir: &Path,
+) -> Result<(), UpdateError> {
+    let signature = signature.ok_or_else(|| UpdateError::Unsigned(version.clone()))?;
+    signature::verify(sums, signature, public_key, SIGNATURE_NAMESPACE, work_dir)?;
+    verify_checksum(version, asset, binary, sums)
+}
+
+/// Compares the checksum of `binary` with the one `sums` lists for `asset`
+fn verify_checksum(
+    version: &Version,
+    asset: &str,
+    binary: &[u8],
+    sums: &[u8],
+) -> Result<(), UpdateError> {
+    let sums = String::from_utf8_lossy(sums);
+    let expected = checksum_for(&sums, asset).ok_or_else(|| UpdateError::NoChecksum {
+        version: version.clone(),
+        asset: asset.to_string(),
+    })?;
+    let actual = sha256_hex(binary);
+    if !expected.eq_ignore_ascii_case(&actual) {
+        return Err(UpdateError::ChecksumMismatch {
+            asset: asset.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Replaces the executable at `exe` with `binary`
+///
+/// The binary is written to a hidden file next to `exe` and renamed over
+/// it. Windows cannot replace a running executable, so there the old one is
+/// moved aside to `<name>.old` first, and moved back if the rename fails.
+///
+/// # Errors
+///
+/// Returns an error if the directory of `exe` is not writable.
+pub fn replace_executable(exe: &Path, binary: &[u8]) -> Result<(), UpdateError> {
+    let replace_error = |source| UpdateError::Replace {
+        path: exe.to_path_buf(),
+        source,
+    };
+    let name = exe
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let staged = exe.with_file_name(format!(".{}.new", name));
+
+    let result = (|| {
+        fs::write(&staged, binary)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+        }
+        #[cfg(windows)]
+        let old = {
+            let old = exe.with_extension("old");
+            let _ = fs::remove_file(&old);
+            fs::rename(exe, &old)?;
+            old
+        };
+        let installed = fs::rename(&staged, exe);
+        #[cfg(windows)]
+        if installed.is_err() {
+            let _ = fs::rename(&old, exe);
+        }
+        installed
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+    result.map_err(replace_error)
+}
+
+/// Looks for a newer release and installs it unless `check_only` is set
+///
+/// Every successful lookup is recorded as the day's version check, so the
+/// background check does not repeat it.
+///
+/// # Errors
+///
+/// Returns an error if the release cannot be downloaded or verified, or if
+/// the executable cannot be replaced.
+pub fn self_update(
+    config: &UpdateConfig,
+    check_only: bool,
+    now: u64,
+) -> Result<UpdateOutcome, UpdateError> {
+    let http = reqwest::blocking::Client::builder()
+        .user_agent(concat!("devhealth/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(120))
+        .build()?;
+    let release: Release = http
+        .get(LATEST_RELEASE_URL)
+        .send()?
+        .error_for_status()?
+        .json()?;
+    let latest = release.version()?;
+    if let Ok(path) = VersionCheck::default_path() {
+        let check = VersionCheck {
+            checked_at: now,
+            latest: Some(latest.to_string()),
+        };
+        let _ = check.save(&path);
+    }
+
+    let current = current_version();
+    if latest <= current {
+        return Ok(UpdateOutcome::UpToDate(current));
+    }
+    if check_only {
+        return Ok(UpdateOutcome::Available(latest));
+    }
+    let public_key = release_key(config)?;
+
+    let name = asset_name();
+    let download = |asset: &Asset| -> Result<Vec<u8>, UpdateError> {
+        Ok(http
+            .get(&asset.browser_download_url)
+            .send()?
+            .error_for_status()?
+            .bytes()?
+            .to_vec())
+    };
+    let binary = release.asset(&name).ok_or_else(|| UpdateError::NoAsset {
+        version: latest.clone(),
+        asset: name.clone(),
+    })?;
+    let sums = release
+        .asset(CHECKSUMS_FILE)
+        .ok_or_else(|| UpdateError::NoChecksum {
+            version: latest.clone(),
+            asset: name.clone(),
+        })?;
+    let binary = download(binary)?;
+    let sums = download(sums)?;
+    let signature = release.asset(SIGNATURE_FILE).map(download).transpose()?;
+
+    let work_dir = paths::cache_dir().unwrap_or_else(std::env::temp_dir);
+    verify_release(
+        &latest,
+        &name,
+        &binary,
+        &sums,
+        signature.as_deref(),
+        public_key,
+        &work_dir,
+    )?;
+
+    replace_executable(&std::env::current_exe()?, &binary)?;
+    Ok(UpdateOutcome::Updated {
+        from: current,
+        to: latest,
+    })
+}
+
+/// Returns a newer release found by an earlier check, starting the next
+/// check in the background when one is due
+///
+/// Returns `None` when the check is switched off in `[update]` or with
+/// [`NO_CHECK_VAR`]. The check time is recorded before the background check
+/// starts, so a failing check is not retried until the next day.
+pub fn pending_update(config: &UpdateConfig, now: u64) -> Option<Version> {
+    if !config.check || std::env::var_os(NO_CHECK_VAR).is_some_and(|value| !value.is_empty()) {
+        return None;
+    }
+    let path = VersionCheck::default_path().ok()?;
+    let mut check = VersionCheck::load(&path);
+    if check.is_due(now) {
+        check.checked_at = now;
+        if check.save(&path).is_ok() {
+            spawn_check();
+        }
+    }
+    check.newer_than(&current_version())
+}
+
+/// Starts `devhealth self-update --check` without waiting for it
+fn spawn_check() {
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = Command::new(exe)
+            .args(["self-update", "--check"])
+            .env(NO_CHECK_VAR, "1")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// `sha256sum` of an empty file
+    const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    mod releases {
+        use super::*;
+
+        #[test]
+        fn parses_the_github_release_response() {
+            let release: Release = serde_json::from_str(
+                r#"{"tag_name": "v0.3.1", "name": "0.3.1", "assets": [
+                    {"name": "SHA256SUMS", "browser_download_url": "https://example.com/SHA256SUMS", "size": 120}
+                ]}"#,
+            )
+            .unwrap();
+            assert_eq!(release.version().unwrap(), Version::new(0, 3, 1));
+            assert!(release.asset(CHECKSUMS_FILE).is_some());
+            assert!(release.asset(&asset_name()).is_none());
+        }
+
+        #[test]
+        fn rejects_tags_that_are_not_versions() {
+            let release = Release {
+                tag_name: "nightly".to_string(),
+                assets: Vec::new(),
+            };
+            assert!(matches!(
+                release.version(),
+                Err(UpdateError::InvalidVersion { .. })
+            ));
+        }
+
+        #[test]
+        fn names_the_binary_after_the_platform() {
+            let name = asset_name();
+            assert!(name.starts_with("devhealth-"));
+            assert!(name.contains(std::env::consts::OS));
+        }
+    }
+
+    mod verification {
+        use super::*;
+
+        fn verify(binary: &[u8], sums: &str) -> Result<(), UpdateError> {
+            verify_checksum(
+                &Version::new(1, 0, 0),
+                "devhealth-x86_64-linux",
+                binary,
+                sums.as_bytes(),
+            )
+        }
+
+        #[test]
+        fn computes_sha256() {
+            assert_eq!(sha256_hex(b""), EMPTY_SHA256);
+        }
+
+        #[test]
+        fn accepts_a_matching_checksum() {
+            let sums = format!("{}  devhealth-x86_64-linux\n", EMPTY_SHA256.to_uppercase());
+            verify(b"", &sums).unwrap();
+        }
+
+        #[test]
+        fn rejects_a_tampered_binary() {
+            let sums = format!("{}  devhealth-x86_64-linux\n", EMPTY_SHA256);
+            assert!(matches!(
+                verify(b"evil", &sums),
+                Err(UpdateError::ChecksumMismatch { .. })
+            ));
+        }
+
+        #[test]
+        fn rejects_a_binary_missing_from_the_checksums() {
+            let sums = format!("{}  devhealth-aarch64-macos\n", EMPTY_SHA256);
+            assert!(matches!(
+                verify(b"", &sums),
+                Err(UpdateError::NoChecksum { .. })
+            ));
+        }
+
+        #[test]
+        fn requires_a_signature() {
+            let temp_dir = TempDir::new().unwrap();
+            let sums = format!("{}  devhealth-x86_64-linux\n", EMPTY_SHA256);
+            let verified = verify_release(
+                &Version::new(1, 0, 0),
+                "devhealth-x86_64-linux",
+                b"",
+                sums.as_bytes(),
+                None,
+                "ssh-ed25519 AAAA release",
+                temp_dir.path(),
+            );
+            assert!(matches!(verified, Err(UpdateError::Unsigned(_))));
+        }
+
+        #[test]
+        fn refuses_to_install_without_a_key() {
+            let mut config = UpdateConfig {
+                public_key: Some("ssh-ed25519 AAAA release".to_string()),
+                ..UpdateConfig::default()
+            };
+            assert_eq!(release_key(&config).unwrap(), "ssh-ed25519 AAAA release");
+
+            config.public_key = Some("  ".to_string());
+            match RELEASE_PUBLIC_KEY {
+                Some(built_in) => assert_eq!(
+                    release_key(&config).unwrap(),
+                    built_in,
+                    "A blank key falls back to the built-in one"
+                ),
+                None => assert!(matches!(
+                    release_key(&config),
+                    Err(UpdateError::NoPublicKey)
+                )),
+            }
+        }
+    }
+
+    mod installation {
+        use super::*;
+
+        #[test]
+        fn replaces_the_executable_in_place() {
+            let temp_dir = TempDir::new().unwrap();
+            let exe = temp_dir.path().join("devhealth");
+            fs::write(&exe, "old").unwrap();
+
+            replace_executable(&exe, b"new").unwrap();
+            assert_eq!(fs::read(&exe).unwrap(), b"new");
+            let names: Vec<_> = fs::read_dir(temp_dir.path())
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name())
+                .collect();
+            assert_eq!(
+                names,
+                vec![std::ffi::OsString::from("devhealth")],
+                "no staged file is left behind"
+            );
+        }
+
+        #[test]
+        fn reports_an_unwritable_location() {
+            let temp_dir = TempDir::new().unwrap();
+            let exe = temp_dir.path().join("missing").join("devhealth");
+            assert!(matches!(
+                replace_executable(&exe, b"new"),
+                Err(UpdateError::Replace { .. })
+            ));
+        }
+    }
+
+    mod version_check {
+        use super::*;
+
+        #[test]
+        fn is_due_once_a_day() {
+            let check = VersionCheck {
+                checked_at: 1_000,
+                latest: None,
+            };
+            assert!(!check.is_due(1_000 + CHECK_INTERVAL - 1));
+            assert!(check.is_due(1_000 + CHECK_INTERVAL));
+            assert!(VersionCheck::default().is_due(1_000 + CHECK_INTERVAL));
+        }
+
+        #[test]
+        fn survives_a_round_trip_and_corrupt_files() {
+            let temp_dir = TempDir::new().unwrap();
+            let path = temp_dir.path().join("cache").join(CHECK_FILE);
+            let check = VersionCheck {
+                checked_at: 42,
+                latest: Some("9.9.9".to_string()),
+            };
+            check.save(&path).unwrap();
+            assert_eq!(VersionCheck::load(&path), check);
+            assert_eq!(
+                check.newer_than(&current_version()),
+                Some(Version::new(9, 9, 9))
+            );
+
+            fs::write(&path, "{").unwrap();
+            assert_eq!(VersionCheck::load(&path), VersionCheck::default());
+        }
+    }
+}
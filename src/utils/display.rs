@@ -2,39 +2,173 @@
 //!
 //! This module provides utilities for creating beautiful, colorized terminal output
 //! with consistent formatting, progress indicators, and visual hierarchy.
+//!
+//! Colors come from the active [`theme`](crate::utils::theme) rather than
+//! being hard-coded, so every scanner's output follows the user's palette.
+//!
+//! Layouts adapt to the terminal width (see [`terminal_width`]): boxes and
+//! dividers shrink on narrow terminals, long names are truncated with an
+//! ellipsis, and long paths wrap onto continuation lines.
+//!
+//! In accessible mode (see [`set_accessible`]) emoji and box-drawing
+//! characters are replaced by plain labels such as `[OK]` and `[WARN]`, and
+//! trees and boxes become indented plain text that screen readers and
+//! non-UTF-8 terminals handle well.
 
+use crate::i18n;
+use crate::tr;
+use crate::utils::theme::{theme, Paint, Role};
+use clap::ValueEnum;
 use colored::*;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Whether output uses plain labels instead of emoji and box drawing
+static ACCESSIBLE: AtomicBool = AtomicBool::new(false);
+
+/// Plain replacements for symbols that carry meaning
+const SYMBOL_LABELS: [(&str, &str); 17] = [
+    ("✅", "[OK]"),
+    ("✓", "[OK]"),
+    ("🟢", "[OK]"),
+    ("⚠️", "[WARN]"),
+    ("⚠", "[WARN]"),
+    ("🟡", "[WARN]"),
+    ("❌", "[ERROR]"),
+    ("✗", "[ERROR]"),
+    ("🔴", "[ERROR]"),
+    ("🚨", "[ALERT]"),
+    ("ℹ️", "[INFO]"),
+    ("↑", "[UNPUSHED]"),
+    ("↓", "[BEHIND]"),
+    ("→", "->"),
+    ("•", "-"),
+    ("…", "..."),
+    ("█", "#"),
+];
+
+/// Width assumed when the terminal size cannot be determined
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Narrowest width layouts are computed for
+pub const MIN_TERMINAL_WIDTH: usize = 40;
 
-/// Creates a styled header with optional emoji and color
-pub fn header(title: &str, emoji: &str, color: Color) -> String {
-    format!("{} {}", emoji, title.color(color).bold())
+/// Width of the label column in summary boxes
+const SUMMARY_LABEL_WIDTH: usize = 20;
+
+/// Widest a summary box or section divider grows
+const MAX_BOX_WIDTH: usize = 53;
+
+/// Number of dependencies listed per ecosystem at [`Detail::Normal`]
+pub const NORMAL_DEPENDENCY_LIMIT: usize = 8;
+
+/// How much of each report section is printed
+///
+/// Levels are ordered, so renderers can test e.g. `detail >= Detail::Normal`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Detail {
+    /// Headers and summary boxes only (selected with `--summary`)
+    #[value(skip)]
+    Summary,
+    /// One line per entry, without nested items or tips
+    Minimal,
+    /// Entries with a capped number of nested items, plus tips
+    #[default]
+    Normal,
+    /// Everything, including every dependency and last-commit times
+    Full,
+}
+
+impl Detail {
+    /// Maximum number of dependencies listed per ecosystem, `None` meaning all
+    pub fn dependency_limit(self) -> Option<usize> {
+        match self {
+            Detail::Summary | Detail::Minimal => Some(0),
+            Detail::Normal => Some(NORMAL_DEPENDENCY_LIMIT),
+            Detail::Full => None,
+        }
+    }
+}
+
+/// How entries are laid out in text output, selected with `--view`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum View {
+    /// Entries as a tree, with nested details and tips
+    #[default]
+    Tree,
+    /// One row per repository, in columns fitted to the terminal width
+    Table,
+}
+
+/// Creates a styled header with optional emoji, colored for `role`
+pub fn header(title: &str, emoji: &str, role: Role) -> String {
+    if is_accessible() {
+        return title.paint(role).bold().to_string();
+    }
+    format!("{} {}", emoji, title.paint(role).bold())
 }
 
 /// Creates a styled section divider
 pub fn section_divider(title: &str) -> String {
-    let divider = "─".repeat(50);
-    format!("\n{}\n{} {}\n{}", 
-        divider.bright_black(),
-        "▶".bright_blue().bold(), 
-        title.bright_white().bold(),
-        divider.bright_black()
+    if is_accessible() {
+        return format!("\n{}", title.paint(Role::Emphasis).bold());
+    }
+    let divider = "─".repeat(terminal_width().min(MAX_BOX_WIDTH - 3));
+    format!(
+        "\n{}\n{} {}\n{}",
+        divider.paint(Role::Muted),
+        "▶".paint(Role::Accent).bold(),
+        title.paint(Role::Emphasis).bold(),
+        divider.paint(Role::Muted)
     )
 }
 
 /// Creates a summary box with statistics
 pub fn summary_box(items: &[(&str, String)]) -> String {
+    summary_box_with_width(items, terminal_width())
+}
+
+/// Creates a summary box fitting within `width` columns
+///
+/// Values too wide for the remaining space are truncated with an ellipsis.
+pub fn summary_box_with_width(items: &[(&str, String)], width: usize) -> String {
+    if is_accessible() {
+        let mut result = format!("{}:\n", i18n::t("summary"));
+        for (label, value) in items {
+            result.push_str(&format!(
+                "  {}: {}\n",
+                label.paint(Role::Accent),
+                symbols(value.trim()).paint(Role::Emphasis).bold()
+            ));
+        }
+        return result;
+    }
+
+    let box_width = width.clamp(MIN_TERMINAL_WIDTH, MAX_BOX_WIDTH);
+    let value_width = width.saturating_sub(SUMMARY_LABEL_WIDTH + 5).max(10);
+
+    let title = format!("─ {} ", i18n::t("summary"));
+    let top = format!(
+        "┌{}{}┐\n",
+        title,
+        "─".repeat(box_width - 2 - title.chars().count())
+    );
+    let bottom = format!("└{}┘\n", "─".repeat(box_width - 2));
+
     let mut result = String::new();
-    result.push_str(&"┌─ Summary ─────────────────────────────────────────┐\n".bright_black().to_string());
-    
+    result.push_str(&top.paint(Role::Muted).to_string());
+
     for (label, value) in items {
-        result.push_str(&format!("│ {:<20} {} {}\n", 
-            label.bright_blue(),
-            "│".bright_black(),
-            value.bright_white().bold()
+        result.push_str(&format!(
+            "│ {:<20} {} {}\n",
+            truncate(label, SUMMARY_LABEL_WIDTH).paint(Role::Accent),
+            "│".paint(Role::Muted),
+            truncate(value, value_width).paint(Role::Emphasis).bold()
         ));
     }
-    
-    result.push_str(&"└───────────────────────────────────────────────────┘\n".bright_black().to_string());
+
+    result.push_str(&bottom.paint(Role::Muted).to_string());
     result
 }
 
@@ -43,53 +177,121 @@ pub fn progress_bar(current: usize, total: usize, width: usize) -> String {
     if total == 0 {
         return "".to_string();
     }
-    
+    if is_accessible() {
+        return format!("{}/{}", current, total);
+    }
+
     let filled = (current * width) / total;
     let empty = width - filled;
-    
-    format!("[{}{}] {}/{}", 
-        "█".repeat(filled).bright_green(),
-        "░".repeat(empty).bright_black(),
-        current.to_string().bright_white().bold(),
-        total.to_string().bright_black()
+
+    format!(
+        "[{}{}] {}/{}",
+        "█".repeat(filled).paint(Role::Success),
+        "░".repeat(empty).paint(Role::Muted),
+        current.to_string().paint(Role::Emphasis).bold(),
+        total.to_string().paint(Role::Muted)
     )
 }
 
 /// Creates a status indicator with appropriate colors
 pub fn status_indicator(status: &str, is_good: bool) -> String {
-    let (symbol, color) = match (status, is_good) {
-        (_, true) => ("✓", Color::BrightGreen),
-        (_, false) => ("✗", Color::BrightRed),
+    let (symbol, role) = match (status, is_good) {
+        (_, true) => ("✓", Role::Success),
+        (_, false) => ("✗", Role::Error),
     };
-    
-    format!("{} {}", symbol.color(color).bold(), status.color(color))
+
+    format!(
+        "{} {}",
+        symbols(symbol).paint(role).bold(),
+        status.paint(role)
+    )
 }
 
 /// Creates a tree-like structure indicator
 pub fn tree_item(content: &str, is_last: bool, level: usize) -> String {
+    if is_accessible() {
+        return format!("{}{}", "  ".repeat(level + 1), symbols(content));
+    }
     let indent = "  ".repeat(level);
     let connector = if is_last { "└─" } else { "├─" };
-    
-    format!("{}{} {}", 
-        indent.bright_black(),
-        connector.bright_black(),
+
+    format!(
+        "{}{} {}",
+        indent.paint(Role::Muted),
+        connector.paint(Role::Muted),
+        content
+    )
+}
+
+/// Creates the continuation line of a tree item, aligned under its content
+///
+/// Used to wrap long items (such as paths) produced with [`tree_item`].
+pub fn tree_continuation(content: &str, is_last: bool, level: usize) -> String {
+    if is_accessible() {
+        return format!("{}{}", "  ".repeat(level + 2), symbols(content));
+    }
+    let indent = "  ".repeat(level);
+    let connector = if is_last { "  " } else { "│ " };
+
+    format!(
+        "{}{} {}",
+        indent.paint(Role::Muted),
+        connector.paint(Role::Muted),
         content
     )
 }
 
+/// Width taken by the indentation and connector of a tree item at `level`
+pub fn tree_prefix_width(level: usize) -> usize {
+    if is_accessible() {
+        2 * level + 2
+    } else {
+        2 * level + 3
+    }
+}
+
 /// Creates a badge for dependency types or categories
 pub fn badge(text: &str, badge_type: BadgeType) -> String {
-    let (bg_color, text_color) = match badge_type {
-        BadgeType::Runtime => (Color::BrightGreen, Color::Black),
-        BadgeType::Dev => (Color::BrightYellow, Color::Black),
-        BadgeType::Build => (Color::BrightBlue, Color::White),
-        BadgeType::Optional => (Color::BrightMagenta, Color::White),
-        BadgeType::Error => (Color::BrightRed, Color::White),
-        BadgeType::Warning => (Color::Yellow, Color::Black),
-        BadgeType::Info => (Color::Cyan, Color::Black),
+    let bg_role = badge_type.role();
+    let text_color = match badge_type {
+        BadgeType::Build | BadgeType::Optional | BadgeType::Error => Color::White,
+        BadgeType::Runtime | BadgeType::Dev | BadgeType::Warning | BadgeType::Info => Color::Black,
     };
-    
-    format!(" {} ", text.color(text_color).on_color(bg_color).bold())
+
+    if is_accessible() {
+        return format!("({})", text.paint(bg_role));
+    }
+    format!(
+        " {} ",
+        text.color(text_color)
+            .on_color(theme().color(bg_role))
+            .bold()
+    )
+}
+
+/// Creates a compact status badge: a symbol followed by what it counts
+///
+/// Unlike [`badge`], status badges carry no background, so several fit on
+/// one line after a repository or project (`🛡 2 vulns ⬆ unpushed ✗ CI`).
+/// Accessible output drops the symbol and brackets the text.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::display::{self, BadgeType};
+///
+/// let badge = display::status_badge("🛡", "2 vulns", BadgeType::Error);
+/// assert!(badge.contains("2 vulns"));
+/// ```
+pub fn status_badge(symbol: &str, text: &str, badge_type: BadgeType) -> String {
+    let role = badge_type.role();
+    if is_accessible() {
+        return format!("[{}]", text.paint(role));
+    }
+    format!("{} {}", symbol, text)
+        .paint(role)
+        .bold()
+        .to_string()
 }
 
 /// Badge types for different categories
@@ -103,9 +305,23 @@ pub enum BadgeType {
     Info,
 }
 
+impl BadgeType {
+    /// Returns the role a badge of this type is colored with
+    fn role(&self) -> Role {
+        match self {
+            BadgeType::Runtime => Role::Success,
+            BadgeType::Dev | BadgeType::Warning => Role::Warning,
+            BadgeType::Build => Role::Accent,
+            BadgeType::Optional => Role::Special,
+            BadgeType::Error => Role::Error,
+            BadgeType::Info => Role::Highlight,
+        }
+    }
+}
+
 /// Creates a file path display with proper highlighting
 pub fn file_path(path: &str) -> String {
-    path.bright_black().italic().to_string()
+    path.paint(Role::Muted).italic().to_string()
 }
 
 /// Creates an ecosystem icon with color
@@ -115,57 +331,622 @@ pub fn ecosystem_icon(ecosystem: &str) -> String {
         "node.js" | "nodejs" => "📦".to_string(),
         "python" => "🐍".to_string(),
         "go" => "🐹".to_string(),
+        "bazel" => "🌿".to_string(),
+        "buck" => "🦌".to_string(),
+        "nix" => "❄️".to_string(),
         _ => "📄".to_string(),
     }
 }
 
 /// Creates a version display with proper formatting
 pub fn version_display(name: &str, version: &str, is_latest: Option<bool>) -> String {
-    let name_colored = name.bright_white().bold();
-    let version_colored = version.bright_green();
-    
+    let name_colored = name.paint(Role::Emphasis).bold();
+    let version_colored = version.paint(Role::Success);
+
     match is_latest {
-        Some(true) => format!("{} {} {}", name_colored, version_colored, "✓".bright_green()),
-        Some(false) => format!("{} {} {}", name_colored, version_colored, "⚠".yellow()),
+        Some(true) => format!(
+            "{} {} {}",
+            name_colored,
+            version_colored,
+            "✓".paint(Role::Success)
+        ),
+        Some(false) => format!(
+            "{} {} {}",
+            name_colored,
+            version_colored,
+            "⚠".paint(Role::Warning)
+        ),
         None => format!("{} {}", name_colored, version_colored),
     }
 }
 
+/// Describes how long ago a Unix timestamp was, in whole days
+pub fn age(timestamp: u64, now: u64) -> String {
+    match now.saturating_sub(timestamp) / 86_400 {
+        0 => tr!("age-today"),
+        1 => tr!("age-one-day"),
+        days => tr!("age-days", days = days),
+    }
+}
+
+/// Formats a byte count with binary units, e.g. `1.5 GiB`
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::display;
+///
+/// assert_eq!(display::bytes(512), "512 B");
+/// assert_eq!(display::bytes(3 * 1024 * 1024 / 2), "1.5 MiB");
+/// ```
+pub fn bytes(count: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if count < 1024 {
+        return format!("{} B", count);
+    }
+
+    let mut value = count as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Returns the current time in seconds since the Unix epoch
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Computes the widths of the dependency table columns for `width`
+///
+/// The source column takes whatever space is left; on narrow terminals the
+/// package column shrinks too.
+fn table_columns(width: usize) -> [usize; 4] {
+    // Borders and padding: "│ " + 3 × " │ " + " │"
+    let available = width.saturating_sub(13);
+    let (version, kind) = (12, 8);
+    let name = available.saturating_sub(version + kind + 10).clamp(10, 25);
+    let source = available.saturating_sub(name + version + kind).max(10);
+    [name, version, kind, source]
+}
+
 /// Creates a table-like layout for dependency information
 pub fn dependency_table_row(name: &str, version: &str, dep_type: &str, source: &str) -> String {
-    format!("│ {:<25} │ {:<12} │ {:<8} │ {:<20} │",
-        name.bright_white().bold(),
-        version.bright_green(),
-        dep_type.color(match dep_type {
-            "runtime" => Color::BrightGreen,
-            "dev" => Color::BrightYellow,
-            "build" => Color::BrightBlue,
-            _ => Color::BrightMagenta,
+    let [name_w, version_w, type_w, source_w] = table_columns(terminal_width());
+    let row = format!(
+        "│ {:<name_w$} │ {:<version_w$} │ {:<type_w$} │ {:<source_w$} │",
+        truncate(name, name_w).paint(Role::Emphasis).bold(),
+        truncate(version, version_w).paint(Role::Success),
+        truncate(dep_type, type_w).paint(match dep_type {
+            "runtime" => Role::Success,
+            "dev" => Role::Warning,
+            "build" => Role::Accent,
+            _ => Role::Special,
         }),
-        source.bright_black().italic()
-    )
+        truncate_start(source, source_w).paint(Role::Muted).italic()
+    );
+    symbols(&row).into_owned()
 }
 
 /// Creates table header
 pub fn dependency_table_header() -> String {
-    let header = format!("┌─{:─<25}─┬─{:─<12}─┬─{:─<8}─┬─{:─<20}─┐",
-        "─", "─", "─", "─");
-    let titles = format!("│ {:<25} │ {:<12} │ {:<8} │ {:<20} │",
-        "Package".bright_blue().bold(),
-        "Version".bright_blue().bold(),
-        "Type".bright_blue().bold(),
-        "Source".bright_blue().bold()
+    let [name_w, version_w, type_w, source_w] = table_columns(terminal_width());
+    let header = format!(
+        "┌─{}─┬─{}─┬─{}─┬─{}─┐",
+        "─".repeat(name_w),
+        "─".repeat(version_w),
+        "─".repeat(type_w),
+        "─".repeat(source_w)
+    );
+    let titles = format!(
+        "│ {:<name_w$} │ {:<version_w$} │ {:<type_w$} │ {:<source_w$} │",
+        "Package".paint(Role::Accent).bold(),
+        "Version".paint(Role::Accent).bold(),
+        "Type".paint(Role::Accent).bold(),
+        "Source".paint(Role::Accent).bold()
     );
-    let separator = format!("├─{:─<25}─┼─{:─<12}─┼─{:─<8}─┼─{:─<20}─┤",
-        "─", "─", "─", "─");
-    
-    format!("{}\n{}\n{}", header.bright_black(), titles, separator.bright_black())
+    let separator = format!(
+        "├─{}─┼─{}─┼─{}─┼─{}─┤",
+        "─".repeat(name_w),
+        "─".repeat(version_w),
+        "─".repeat(type_w),
+        "─".repeat(source_w)
+    );
+
+    symbols(&format!(
+        "{}\n{}\n{}",
+        header.paint(Role::Muted),
+        titles,
+        separator.paint(Role::Muted)
+    ))
+    .into_owned()
 }
 
 /// Creates table footer
 pub fn dependency_table_footer() -> String {
-    format!("└─{:─<25}─┴─{:─<12}─┴─{:─<8}─┴─{:─<20}─┘",
-        "─", "─", "─", "─").bright_black().to_string()
+    let [name_w, version_w, type_w, source_w] = table_columns(terminal_width());
+    let footer = format!(
+        "└─{}─┴─{}─┴─{}─┴─{}─┘",
+        "─".repeat(name_w),
+        "─".repeat(version_w),
+        "─".repeat(type_w),
+        "─".repeat(source_w)
+    );
+    symbols(&footer).paint(Role::Muted).to_string()
+}
+
+/// Which side of a [`Column`] its cells are aligned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// A column of a [`table`]
+#[derive(Debug, Clone, Copy)]
+pub struct Column<'a> {
+    /// Title shown in the header row
+    pub title: &'a str,
+    /// Side the title and cells are aligned to
+    pub align: Align,
+    /// Narrowest the column is shrunk to before columns are dropped
+    pub min_width: usize,
+}
+
+impl<'a> Column<'a> {
+    /// Creates a left-aligned column
+    pub fn left(title: &'a str, min_width: usize) -> Self {
+        Column {
+            title,
+            align: Align::Left,
+            min_width,
+        }
+    }
+
+    /// Creates a right-aligned column, for numbers and sizes
+    pub fn right(title: &'a str, min_width: usize) -> Self {
+        Column {
+            title,
+            align: Align::Right,
+            min_width,
+        }
+    }
+}
+
+/// Lays out `rows` in bordered columns fitting the terminal width
+///
+/// See [`table_with_width`].
+pub fn table(columns: &[Column], rows: &[Vec<String>]) -> String {
+    table_with_width(columns, rows, terminal_width())
+}
+
+/// Lays out `rows` in bordered columns fitting within `width` columns
+///
+/// Each column is as wide as its widest cell. When the table does not fit,
+/// the widest columns are shrunk towards their minimum width and their cells
+/// truncated with an ellipsis; if that is not enough, columns are dropped
+/// from the right. Cells may contain color sequences.
+///
+/// In accessible mode each row becomes one line of `title: value` pairs.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::display::{self, Column};
+///
+/// let columns = [Column::left("Name", 4), Column::right("Score", 5)];
+/// let rows = vec![vec!["api".to_string(), "100".to_string()]];
+/// let table = display::table_with_width(&columns, &rows, 80);
+/// assert!(table.lines().any(|line| line == "│ api  │   100 │"));
+///
+/// let narrow = display::table_with_width(&columns, &rows, 10);
+/// assert!(!narrow.contains("Score"));
+/// ```
+pub fn table_with_width(columns: &[Column], rows: &[Vec<String>], width: usize) -> String {
+    fn cell(row: &[String], index: usize) -> &str {
+        row.get(index).map(String::as_str).unwrap_or("")
+    }
+    if is_accessible() {
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let cells: Vec<String> = columns
+                    .iter()
+                    .enumerate()
+                    .map(|(index, column)| format!("{}: {}", column.title, cell(row, index)))
+                    .collect();
+                plain_symbols(&cells.join(", "))
+            })
+            .collect();
+        return lines.join("\n");
+    }
+    let mut widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            rows.iter()
+                .map(|row| display_width(cell(row, index)))
+                .chain([display_width(column.title)])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    // Borders and padding: "│ " + " │ " between columns + " │"
+    let total = |widths: &[usize]| widths.iter().sum::<usize>() + 3 * widths.len() + 1;
+    while total(&widths) > width {
+        let widest = (0..widths.len())
+            .filter(|&index| widths[index] > columns[index].min_width)
+            .max_by_key(|&index| widths[index]);
+        match widest {
+            Some(index) => widths[index] -= 1,
+            None if widths.len() > 1 => {
+                widths.pop();
+            }
+            None => break,
+        }
+    }
+
+    let pad = |text: &str, width: usize, align: Align| {
+        let text = truncate(text, width);
+        let padding = " ".repeat(width.saturating_sub(display_width(&text)));
+        match align {
+            Align::Left => format!("{}{}", text, padding),
+            Align::Right => format!("{}{}", padding, text),
+        }
+    };
+    let rule = |left: &str, middle: &str, right: &str| {
+        let lines: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+        format!("{}{}{}", left, lines.join(middle), right)
+            .paint(Role::Muted)
+            .to_string()
+    };
+    let line = |cells: Vec<String>| format!("│ {} │", cells.join(" │ "));
+
+    let titles = widths
+        .iter()
+        .zip(columns)
+        .map(|(&width, column)| {
+            pad(
+                &column.title.paint(Role::Accent).bold().to_string(),
+                width,
+                column.align,
+            )
+        })
+        .collect();
+    let mut lines = vec![rule("┌", "┬", "┐"), line(titles), rule("├", "┼", "┤")];
+    for row in rows {
+        let cells = widths
+            .iter()
+            .zip(columns)
+            .enumerate()
+            .map(|(index, (&width, column))| pad(cell(row, index), width, column.align))
+            .collect();
+        lines.push(line(cells));
+    }
+    lines.push(rule("└", "┴", "┘"));
+    lines.join("\n")
+}
+
+/// Switches accessible output on or off for the rest of the process
+pub fn set_accessible(enabled: bool) {
+    ACCESSIBLE.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if output uses plain labels instead of emoji and boxes
+pub fn is_accessible() -> bool {
+    ACCESSIBLE.load(Ordering::Relaxed)
+}
+
+/// Returns `false` if the locale explicitly selects a non-UTF-8 encoding
+///
+/// Checks `LC_ALL`, `LC_CTYPE` and `LANG` in that order, like the C library
+/// does. An unset locale is assumed to be UTF-8.
+pub fn locale_is_utf8() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+        .is_none_or(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+/// Adapts symbols in `text` to the current output mode
+///
+/// Returns `text` unchanged unless accessible mode is on, in which case it
+/// is converted with [`plain_symbols`].
+pub fn symbols(text: &str) -> Cow<'_, str> {
+    if is_accessible() {
+        Cow::Owned(plain_symbols(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Replaces emoji and box-drawing characters with plain ASCII
+///
+/// Symbols that carry meaning become labels (`✓` → `[OK]`, `⚠️` → `[WARN]`,
+/// `❌` → `[ERROR]`); decorative emoji are dropped together with the space
+/// that follows them; box-drawing lines become `-`, `|` and `+`.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::display;
+///
+/// assert_eq!(display::plain_symbols("✅ Clean"), "[OK] Clean");
+/// assert_eq!(display::plain_symbols("📂 api (3 deps)"), "api (3 deps)");
+/// ```
+pub fn plain_symbols(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut skip_space = false;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some((symbol, label)) = SYMBOL_LABELS
+            .iter()
+            .find(|(symbol, _)| rest.starts_with(symbol))
+        {
+            result.push_str(label);
+            rest = &rest[symbol.len()..];
+            skip_space = false;
+            continue;
+        }
+        rest = &rest[c.len_utf8()..];
+
+        match c {
+            ' ' if skip_space => skip_space = false,
+            '─' | '━' | '═' => result.push('-'),
+            '│' | '┃' | '║' => result.push('|'),
+            '\u{2500}'..='\u{257F}' => result.push('+'),
+            '░' => result.push('.'),
+            '\u{FE0F}' | '\u{200D}' => {}
+            c if is_pictograph(c) => skip_space = true,
+            c => {
+                skip_space = false;
+                result.push(c);
+            }
+        }
+    }
+    result
+}
+
+/// Returns `true` for emoji and pictographic symbols
+fn is_pictograph(c: char) -> bool {
+    matches!(c as u32, 0x2190..=0x21FF | 0x2300..=0x23FF | 0x25A0..=0x27BF | 0x2B00..=0x2BFF | 0x1F000..=0x1FAFF)
+}
+
+/// Returns the width of the terminal output is rendered for
+///
+/// Uses the `COLUMNS` environment variable when set, then the size of the
+/// terminal attached to stdout, and falls back to
+/// [`DEFAULT_TERMINAL_WIDTH`] (e.g. when output is piped). The result is
+/// never narrower than [`MIN_TERMINAL_WIDTH`].
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .filter(|&columns| columns > 0)
+        .or_else(detect_terminal_width)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+        .max(MIN_TERMINAL_WIDTH)
+}
+
+/// Asks the terminal attached to stdout for its width
+#[cfg(unix)]
+fn detect_terminal_width() -> Option<usize> {
+    use std::io::IsTerminal;
+    use std::os::fd::AsRawFd;
+
+    let stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return None;
+    }
+
+    let mut size = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: TIOCGWINSZ only writes a `winsize` into the pointer it is given,
+    // which points to a valid, properly aligned local.
+    let result = unsafe { libc::ioctl(stdout.as_raw_fd(), libc::TIOCGWINSZ, &mut size) };
+
+    (result == 0 && size.ws_col > 0).then_some(usize::from(size.ws_col))
+}
+
+/// Asks the terminal attached to stdout for its width
+#[cfg(not(unix))]
+fn detect_terminal_width() -> Option<usize> {
+    None
+}
+
+/// Returns the number of terminal columns `text` occupies
+///
+/// ANSI color sequences take no space, wide characters (CJK and most emoji)
+/// take two columns, and combining marks and variation selectors take none.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::display;
+/// use colored::*;
+///
+/// assert_eq!(display::display_width("serde"), 5);
+/// assert_eq!(display::display_width(&"serde".red().to_string()), 5);
+/// assert_eq!(display::display_width("🦀 rust"), 7);
+/// ```
+pub fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(len) = escape_len(rest) {
+            rest = &rest[len..];
+            continue;
+        }
+        width += char_width(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    width
+}
+
+/// Shortens `text` to at most `max_width` columns, ending with an ellipsis
+///
+/// Color sequences are preserved; a reset is appended when the text was cut
+/// inside a colored span.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::display;
+///
+/// assert_eq!(display::truncate("tokio-util", 8), "tokio-u…");
+/// assert_eq!(display::truncate("serde", 8), "serde");
+/// ```
+pub fn truncate(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+
+    let mut result = String::new();
+    let mut width = 0;
+    let mut colored = false;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(len) = escape_len(rest) {
+            result.push_str(&rest[..len]);
+            colored = true;
+            rest = &rest[len..];
+            continue;
+        }
+
+        let char_width = char_width(c);
+        if width + char_width + 1 > max_width {
+            break;
+        }
+        width += char_width;
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    if max_width > 0 {
+        result.push('…');
+    }
+    if colored {
+        result.push_str("\u{1b}[0m");
+    }
+    result
+}
+
+/// Shortens `text` to at most `max_width` columns, keeping its end
+///
+/// Useful for paths, where the file name matters most.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::display;
+///
+/// assert_eq!(display::truncate_start("/home/me/src/api/Cargo.toml", 16), "…/api/Cargo.toml");
+/// ```
+pub fn truncate_start(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let mut kept = Vec::new();
+    let mut width = 0;
+    for c in text.chars().rev() {
+        let char_width = char_width(c);
+        if width + char_width + 1 > max_width {
+            break;
+        }
+        width += char_width;
+        kept.push(c);
+    }
+
+    std::iter::once('…').chain(kept.into_iter().rev()).collect()
+}
+
+/// Splits a path into lines of at most `width` columns
+///
+/// Lines break after path separators where possible; components longer than
+/// a whole line are split wherever needed.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::display;
+///
+/// let lines = display::wrap_path("/home/me/projects/api", 12);
+/// assert_eq!(lines, vec!["/home/me/", "projects/api"]);
+/// ```
+pub fn wrap_path(path: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for segment in path.split_inclusive(['/', '\\']) {
+        if !line.is_empty() && display_width(&line) + display_width(segment) > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        for c in segment.chars() {
+            if !line.is_empty() && display_width(&line) + char_width(c) > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push(c);
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Returns the byte length of the ANSI escape sequence `text` starts with
+fn escape_len(text: &str) -> Option<usize> {
+    let body = text.strip_prefix("\u{1b}[")?;
+    let end = body.find(|c: char| ('@'..='~').contains(&c))?;
+    Some(2 + end + 1)
+}
+
+/// Returns the number of terminal columns a single character occupies
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x00..=0x1F | 0x7F => 0,
+        // Combining marks, zero-width spaces, variation selectors
+        0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        // East Asian wide characters
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD => 2,
+        // Emoji with default emoji presentation
+        0x2705 | 0x270A..=0x270B | 0x2728 | 0x274C | 0x274E | 0x2753..=0x2755 | 0x2757 => 2,
+        0x1F300..=0x1F64F | 0x1F680..=0x1F6FF | 0x1F7E0..=0x1F7EB | 0x1F900..=0x1F9FF => 2,
+        0x1FA70..=0x1FAFF => 2,
+        _ => 1,
+    }
 }
 
 #[cfg(test)]
@@ -174,7 +955,7 @@ mod tests {
 
     #[test]
     fn creates_header_with_emoji_and_color() {
-        let result = header("Test Header", "🔍", Color::Blue);
+        let result = header("Test Header", "🔍", Role::Accent);
         assert!(result.contains("Test Header"));
         assert!(result.contains("🔍"));
     }
@@ -201,4 +982,137 @@ mod tests {
         assert_eq!(ecosystem_icon("go"), "🐹");
         assert_eq!(ecosystem_icon("unknown"), "📄");
     }
+
+    #[test]
+    fn formats_age_in_days() {
+        assert_eq!(age(1_000, 1_000), "today");
+        assert_eq!(age(0, 86_400), "1 day ago");
+        assert_eq!(age(0, 3 * 86_400 + 5), "3 days ago");
+    }
+
+    #[test]
+    fn formats_byte_counts_with_binary_units() {
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(1023), "1023 B");
+        assert_eq!(bytes(1024), "1.0 KiB");
+        assert_eq!(bytes(16 * 1024 * 1024 * 1024), "16.0 GiB");
+    }
+
+    #[test]
+    fn measures_width_ignoring_colors_and_counting_wide_chars() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("\u{1b}[1;31mabc\u{1b}[0m"), 3);
+        assert_eq!(display_width("📦"), 2);
+        assert_eq!(display_width("⚠️"), 1);
+        assert_eq!(display_width("日本"), 4);
+    }
+
+    #[test]
+    fn truncates_with_ellipsis() {
+        assert_eq!(truncate("abcdef", 4), "abc…");
+        assert_eq!(truncate("abcdef", 6), "abcdef");
+        assert_eq!(truncate("日本語", 4), "日…");
+        assert_eq!(truncate_start("abcdef", 4), "…def");
+
+        let colored = "\u{1b}[31mabcdef\u{1b}[0m";
+        let shortened = truncate(colored, 4);
+        assert_eq!(display_width(&shortened), 4);
+        assert!(
+            shortened.ends_with("\u{1b}[0m"),
+            "Cut colors should be reset"
+        );
+    }
+
+    #[test]
+    fn wraps_paths_at_separators() {
+        assert_eq!(wrap_path("/a/b/c", 80), vec!["/a/b/c"]);
+        assert_eq!(wrap_path("/src/api/crates", 9), vec!["/src/api/", "crates"]);
+        assert_eq!(
+            wrap_path("/averyverylongname", 8),
+            vec!["/", "averyver", "ylongnam", "e"]
+        );
+        assert_eq!(wrap_path("", 8), vec![""]);
+    }
+
+    #[test]
+    fn summary_box_fits_narrow_terminals() {
+        let items = vec![("Path", "/very/long/path/".repeat(10))];
+        let rendered = summary_box_with_width(&items, 40);
+        for line in rendered.lines() {
+            assert!(display_width(line) <= 53, "Line too wide: {}", line);
+        }
+        assert!(rendered.contains('…'));
+    }
+
+    #[test]
+    fn table_columns_use_available_width() {
+        let wide = table_columns(120);
+        assert_eq!(wide[0], 25);
+        assert_eq!(wide.iter().sum::<usize>() + 13, 120);
+
+        let narrow = table_columns(50);
+        assert!(narrow.iter().sum::<usize>() + 13 <= 60);
+    }
+
+    #[test]
+    fn table_shrinks_then_drops_columns_to_fit() {
+        let columns = [
+            Column::left("Name", 4),
+            Column::left("Branch", 6),
+            Column::right("Score", 5),
+        ];
+        let rows = vec![vec![
+            "very-long-repository".to_string(),
+            "main".to_string(),
+            "80".to_string(),
+        ]];
+
+        let wide = table_with_width(&columns, &rows, 80);
+        assert!(wide.contains("very-long-repository"));
+        assert!(wide
+            .lines()
+            .all(|line| display_width(line) == display_width(wide.lines().next().unwrap())));
+
+        let shrunk = table_with_width(&columns, &rows, 30);
+        assert!(shrunk.lines().all(|line| display_width(line) <= 30));
+        assert!(shrunk.contains("…"));
+        assert!(shrunk.contains("Score"));
+
+        let dropped = table_with_width(&columns, &rows, 20);
+        assert!(dropped.lines().all(|line| display_width(line) <= 20));
+        assert!(!dropped.contains("Score"));
+    }
+
+    #[test]
+    fn converts_symbols_to_plain_labels() {
+        assert_eq!(plain_symbols("⚠️  Dirty"), "[WARN]  Dirty");
+        assert_eq!(plain_symbols("❌ Error: boom"), "[ERROR] Error: boom");
+        assert_eq!(
+            plain_symbols("✓ Clean api on main ↑"),
+            "[OK] Clean api on main [UNPUSHED]"
+        );
+        assert_eq!(plain_symbols("🦀 Rust (3 deps)"), "Rust (3 deps)");
+        assert_eq!(plain_symbols("💡 Tip:"), "Tip:");
+        assert_eq!(plain_symbols("┌─ Summary ─┐"), "+- Summary -+");
+        assert_eq!(plain_symbols("[██░░]"), "[##..]");
+        assert_eq!(plain_symbols("plain text"), "plain text");
+    }
+
+    #[test]
+    fn symbols_are_unchanged_outside_accessible_mode() {
+        assert!(!is_accessible(), "Tests run with the default output mode");
+        assert_eq!(symbols("✅ Clean"), "✅ Clean");
+    }
+
+    #[test]
+    fn detail_levels_are_ordered_and_cap_dependencies() {
+        assert!(Detail::Summary < Detail::Minimal);
+        assert!(Detail::Normal < Detail::Full);
+        assert_eq!(Detail::default(), Detail::Normal);
+        assert_eq!(
+            Detail::Normal.dependency_limit(),
+            Some(NORMAL_DEPENDENCY_LIMIT)
+        );
+        assert_eq!(Detail::Full.dependency_limit(), None);
+    }
 }
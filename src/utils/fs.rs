@@ -1,11 +1,40 @@
 //! File system utilities for DevHealth
 //!
 //! This module provides file system operations specifically tailored for
-//! development environment analysis, including git repository discovery
-//! and directory traversal functionality.
+//! development environment analysis, including git repository discovery,
+//! directory traversal, and path pattern matching.
+//!
+//! ## Windows paths
+//!
+//! Directory walks never descend into symbolic links, junctions or other
+//! reparse points, which on Windows can point back at an ancestor and make a
+//! scan of `C:\dev` loop forever.
+//!
+//! Win32 APIs reject paths longer than [`MAX_PATH`] characters unless they
+//! use the extended-length (`\\?\`) prefix. Walks therefore start from the
+//! extended form of their root (see [`extended_length`]), and paths shown to
+//! the user or matched against patterns drop it again (see
+//! [`strip_extended_length`]). Both are no-ops on other platforms.
 
+use std::io;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use walkdir::{DirEntry, WalkDir};
+
+/// Longest path most Win32 APIs accept without the extended-length prefix
+pub const MAX_PATH: usize = 260;
+
+/// Dependency and build output directories scanners never search
+pub const SKIPPED_DIRS: [&str; 5] = ["node_modules", "target", ".git", "vendor", "dist"];
+
+/// Prefix of extended-length (verbatim) Windows paths
+const VERBATIM_PREFIX: &str = r"\\?\";
+
+/// Prefix of extended-length UNC paths (`\\server\share`)
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+/// `FILE_ATTRIBUTE_REPARSE_POINT`, set on junctions and symbolic links
+#[cfg(windows)]
+const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
 
 /// Finds all git repositories within a directory tree
 ///
@@ -42,17 +71,13 @@ use walkdir::WalkDir;
 pub fn find_git_repositories(root: &Path) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
     let mut git_repos = Vec::new();
 
-    for entry in WalkDir::new(root)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+    for entry in walk(root) {
         let path = entry.path();
 
         // Check if this directory contains a .git folder
         if path.file_name().and_then(|name| name.to_str()) == Some(".git") {
             if let Some(parent) = path.parent() {
-                git_repos.push(parent.to_path_buf());
+                git_repos.push(strip_extended_length(parent));
             }
         }
     }
@@ -60,6 +85,361 @@ pub fn find_git_repositories(root: &Path) -> Result<Vec<PathBuf>, Box<dyn std::e
     Ok(git_repos)
 }
 
+/// Walks a directory tree without following links or junctions
+///
+/// Yields every readable entry below `root`, including `root` itself.
+/// Symbolic links, junctions and other reparse points that lead to
+/// directories are skipped rather than descended into, so link cycles cannot
+/// make the walk loop. Links to files are yielded; unreadable entries are
+/// skipped. On Windows the yielded
+/// paths carry the extended-length prefix so that deep trees stay readable;
+/// pass them through [`strip_extended_length`] before showing them.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::fs;
+/// use std::path::Path;
+///
+/// let manifests = fs::walk(Path::new("."))
+///     .filter(|entry| entry.file_name() == "Cargo.toml")
+///     .count();
+/// assert!(manifests >= 1);
+/// ```
+pub fn walk(root: &Path) -> impl Iterator<Item = DirEntry> {
+    WalkDir::new(extended_length(root))
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !(is_link(entry) && entry.path().is_dir()))
+        .filter_map(|e| e.ok())
+}
+
+/// Returns `true` if an entry is a symbolic link, junction or reparse point
+pub fn is_link(entry: &DirEntry) -> bool {
+    if entry.path_is_symlink() {
+        return true;
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+
+        if let Ok(metadata) = entry.metadata() {
+            return metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0;
+        }
+    }
+
+    false
+}
+
+/// Returns `true` for paths inside dependency and build output directories
+/// (see [`SKIPPED_DIRS`])
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::fs;
+/// use std::path::Path;
+///
+/// assert!(fs::is_skipped(Path::new("web/node_modules/react/package.json")));
+/// assert!(!fs::is_skipped(Path::new("web/src/targets.ts")));
+/// ```
+pub fn is_skipped(path: &Path) -> bool {
+    path.components()
+        .any(|c| SKIPPED_DIRS.iter().any(|skipped| c.as_os_str() == *skipped))
+}
+
+/// Returns the extended-length form of a path on Windows
+///
+/// Relative paths are made absolute first, since the prefix only applies to
+/// absolute paths. Paths that cannot be expressed verbatim (e.g. ones with
+/// `..` components) are returned unchanged. On other platforms the path is
+/// always returned unchanged.
+pub fn extended_length(path: &Path) -> PathBuf {
+    if !cfg!(windows) {
+        return path.to_path_buf();
+    }
+
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    absolute
+        .to_str()
+        .and_then(to_verbatim)
+        .map(PathBuf::from)
+        .unwrap_or(absolute)
+}
+
+/// Removes the extended-length prefix from a path
+///
+/// `\\?\C:\dev` becomes `C:\dev` and `\\?\UNC\server\share` becomes
+/// `\\server\share`. Other paths, including device paths such as
+/// `\\?\Volume{…}`, are returned unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::fs;
+/// use std::path::{Path, PathBuf};
+///
+/// assert_eq!(
+///     fs::strip_extended_length(Path::new(r"\\?\UNC\build\src")),
+///     PathBuf::from(r"\\build\src")
+/// );
+/// assert_eq!(fs::strip_extended_length(Path::new("/src/api")), PathBuf::from("/src/api"));
+/// ```
+pub fn strip_extended_length(path: &Path) -> PathBuf {
+    path.to_str()
+        .and_then(from_verbatim)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Canonicalizes a path without the extended-length prefix
+///
+/// On Windows [`std::fs::canonicalize`] always returns extended-length
+/// paths, which would never match the paths produced by a scan.
+///
+/// # Errors
+///
+/// Returns an error if the path does not exist or cannot be resolved.
+pub fn canonicalize(path: &Path) -> io::Result<PathBuf> {
+    std::fs::canonicalize(path).map(|path| strip_extended_length(&path))
+}
+
+/// Adds the extended-length prefix to an absolute Windows path
+///
+/// Returns `None` for paths that are already verbatim, relative, or contain
+/// `.` or `..` components, which the prefix would stop Windows from resolving.
+fn to_verbatim(path: &str) -> Option<String> {
+    if path.starts_with(VERBATIM_PREFIX) || path.starts_with(r"\\.\") {
+        return None;
+    }
+
+    let path = path.replace('/', "\\");
+    let bytes = path.as_bytes();
+    let (prefix, rest) = if let Some(unc) = path.strip_prefix(r"\\") {
+        (VERBATIM_UNC_PREFIX, unc)
+    } else if bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && &bytes[1..3] == b":\\" {
+        (VERBATIM_PREFIX, path.as_str())
+    } else {
+        return None;
+    };
+
+    if rest
+        .split('\\')
+        .any(|component| component == "." || component == "..")
+    {
+        return None;
+    }
+    Some(format!("{}{}", prefix, rest))
+}
+
+/// Removes the extended-length prefix from a drive or UNC path
+fn from_verbatim(path: &str) -> Option<String> {
+    if let Some(unc) = path.strip_prefix(VERBATIM_UNC_PREFIX) {
+        return Some(format!(r"\\{}", unc));
+    }
+
+    let rest = path.strip_prefix(VERBATIM_PREFIX)?;
+    let bytes = rest.as_bytes();
+    let is_drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    is_drive.then(|| rest.to_string())
+}
+
+/// Expands a leading `~` in a path pattern to the user's home directory
+pub fn expand_home(pattern: &str) -> String {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+
+    match (pattern.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            format!("{}{}", home.to_string_lossy(), rest)
+        }
+        _ => pattern.to_string(),
+    }
+}
+
+/// Finds every executable named `name` on `PATH`, in `PATH` order
+///
+/// The first entry is the one a shell runs. Directories listed more than
+/// once are only searched once. On Windows each extension in `PATHEXT`
+/// (`.exe`, `.cmd`, …) is tried as well.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::fs;
+///
+/// for git in fs::find_on_path("git") {
+///     println!("{}", git.display());
+/// }
+/// ```
+pub fn find_on_path(name: &str) -> Vec<PathBuf> {
+    let Some(path) = std::env::var_os("PATH") else {
+        return Vec::new();
+    };
+    let mut names = vec![name.to_string()];
+    if cfg!(windows) {
+        let extensions =
+            std::env::var("PATHEXT").unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string());
+        names.extend(
+            extensions
+                .split(';')
+                .filter(|ext| !ext.is_empty())
+                .map(|ext| format!("{}{}", name, ext.to_lowercase())),
+        );
+    }
+
+    let mut searched: Vec<PathBuf> = Vec::new();
+    let mut found = Vec::new();
+    for dir in std::env::split_paths(&path).filter(|dir| !dir.as_os_str().is_empty()) {
+        if searched.contains(&dir) {
+            continue;
+        }
+        found.extend(
+            names
+                .iter()
+                .map(|name| dir.join(name))
+                .filter(|candidate| is_executable(candidate)),
+        );
+        searched.push(dir);
+    }
+    found
+}
+
+/// Returns `true` if `path` is a file the current user may execute
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+/// Returns `true` if `path` is a file the current user may execute
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Checks whether a path matches a glob pattern
+///
+/// Supported syntax:
+/// - `*` matches any characters within a single path component
+/// - `?` matches exactly one character
+/// - `**` matches any number of path components (including none)
+/// - a leading `~` expands to the home directory
+///
+/// Both `/` and `\\` are accepted as separators.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::fs;
+/// use std::path::Path;
+///
+/// assert!(fs::glob_matches("/src/work/**", Path::new("/src/work/api/server")));
+/// assert!(fs::glob_matches("/src/*-api", Path::new("/src/billing-api")));
+/// assert!(!fs::glob_matches("/src/work/*", Path::new("/src/personal/blog")));
+/// ```
+pub fn glob_matches(pattern: &str, path: &Path) -> bool {
+    let pattern = expand_home(pattern).replace('\\', "/");
+    let path = path.to_string_lossy().replace('\\', "/");
+
+    let pattern: Vec<&str> = pattern.split('/').filter(|c| !c.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+
+    match_components(&pattern, &path)
+}
+
+/// Checks that a path pattern of `devhealth.toml` can match anything
+///
+/// Patterns are matched against absolute paths (see [`glob_matches`]), so
+/// they must start with `/`, `~`, `**` or a drive letter. Character classes
+/// and braces are not supported, and `**` must be a whole component.
+///
+/// # Errors
+///
+/// Returns why the pattern is invalid.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::fs;
+///
+/// assert!(fs::check_glob("~/work/**").is_ok());
+/// assert!(fs::check_glob("**/*-api").is_ok());
+/// assert!(fs::check_glob("work/*").is_err());
+/// assert!(fs::check_glob("/src/{api,cli}").is_err());
+/// ```
+pub fn check_glob(pattern: &str) -> Result<(), &'static str> {
+    let normalized = pattern.replace('\\', "/");
+    let drive = normalized.as_bytes().get(1) == Some(&b':')
+        && normalized.as_bytes()[0].is_ascii_alphabetic();
+    if normalized.trim().is_empty() {
+        Err("the pattern is empty")
+    } else if !(normalized.starts_with('/')
+        || normalized.starts_with('~')
+        || normalized.starts_with("**")
+        || drive)
+    {
+        Err("patterns are matched against absolute paths, so they must start with `/`, `~` or `**`")
+    } else if normalized.contains(['[', ']', '{', '}']) {
+        Err("character classes and braces are not supported; use `*` and `?`")
+    } else if normalized
+        .split('/')
+        .any(|component| component.contains("**") && component != "**")
+    {
+        Err("`**` must be a whole path component, as in `~/work/**`")
+    } else {
+        Ok(())
+    }
+}
+
+/// Matches path components against pattern components, handling `**`
+fn match_components(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_components(rest, &path[skip..])),
+        Some((first, rest)) => match path.split_first() {
+            Some((component, remaining)) => {
+                match_component(first, component) && match_components(rest, remaining)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Matches a single path component against a pattern with `*` and `?`
+fn match_component(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    // matches[j] is true when pattern[..i] matches text[..j]
+    let mut matches = vec![false; text.len() + 1];
+    matches[0] = true;
+
+    for p in &pattern {
+        let mut next = vec![false; text.len() + 1];
+        match p {
+            '*' => {
+                let mut seen = false;
+                for j in 0..=text.len() {
+                    seen |= matches[j];
+                    next[j] = seen;
+                }
+            }
+            '?' => next[1..].copy_from_slice(&matches[..text.len()]),
+            c => {
+                for j in 1..=text.len() {
+                    next[j] = matches[j - 1] && text[j - 1] == *c;
+                }
+            }
+        }
+        matches = next;
+    }
+
+    matches[text.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,4 +549,146 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn does_not_descend_into_directory_links() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let project = create_git_repo_in(temp_dir.path(), "project");
+
+        // A link back to an ancestor, like a junction loop on Windows
+        std::os::unix::fs::symlink(temp_dir.path(), project.join("loop")).unwrap();
+
+        let entries: Vec<_> = walk(temp_dir.path()).collect();
+        assert!(entries.iter().all(|entry| entry.file_name() != "loop"));
+        assert!(entries.iter().all(|entry| entry.depth() <= 2));
+        assert_eq!(
+            find_git_repositories(temp_dir.path()).unwrap(),
+            vec![project]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn walks_from_a_linked_root() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let project = create_git_repo_in(temp_dir.path(), "project");
+
+        let link = temp_dir.path().join("link");
+        std::os::unix::fs::symlink(&project, &link).unwrap();
+        assert_eq!(find_git_repositories(&link).unwrap(), vec![link]);
+    }
+
+    mod windows_paths {
+        use super::*;
+
+        #[test]
+        fn adds_prefix_to_drive_and_unc_paths() {
+            assert_eq!(
+                to_verbatim(r"C:\dev\api").as_deref(),
+                Some(r"\\?\C:\dev\api")
+            );
+            assert_eq!(
+                to_verbatim("C:/dev/api").as_deref(),
+                Some(r"\\?\C:\dev\api")
+            );
+            assert_eq!(
+                to_verbatim(r"\\build\src\api").as_deref(),
+                Some(r"\\?\UNC\build\src\api")
+            );
+        }
+
+        #[test]
+        fn leaves_paths_the_prefix_cannot_express() {
+            assert_eq!(to_verbatim(r"\\?\C:\dev"), None);
+            assert_eq!(to_verbatim(r"\\.\pipe\docker"), None);
+            assert_eq!(to_verbatim(r"dev\api"), None);
+            assert_eq!(to_verbatim(r"C:dev"), None);
+            assert_eq!(to_verbatim(r"C:\dev\..\api"), None);
+            assert_eq!(to_verbatim("/home/ada/dev"), None);
+        }
+
+        #[test]
+        fn strips_prefix_from_drive_and_unc_paths() {
+            assert_eq!(from_verbatim(r"\\?\C:\dev").as_deref(), Some(r"C:\dev"));
+            assert_eq!(
+                from_verbatim(r"\\?\UNC\build\src").as_deref(),
+                Some(r"\\build\src")
+            );
+            assert_eq!(from_verbatim(r"\\?\Volume{1234}\dev"), None);
+            assert_eq!(from_verbatim(r"C:\dev"), None);
+        }
+
+        #[test]
+        fn prefix_round_trips_long_paths() {
+            let long = format!(r"C:\dev\{}", "nested\\".repeat(40));
+            assert!(long.len() > MAX_PATH);
+            let verbatim = to_verbatim(&long).unwrap();
+            assert_eq!(from_verbatim(&verbatim), Some(long));
+        }
+
+        #[test]
+        fn extended_length_is_a_no_op_elsewhere() {
+            let path = Path::new("/src/api");
+            if !cfg!(windows) {
+                assert_eq!(extended_length(path), path);
+            }
+            assert_eq!(strip_extended_length(path), path);
+        }
+    }
+
+    mod glob_matching {
+        use super::*;
+
+        #[test]
+        fn matches_literal_paths() {
+            assert!(glob_matches("/src/project", Path::new("/src/project")));
+            assert!(!glob_matches("/src/project", Path::new("/src/project/sub")));
+        }
+
+        #[test]
+        fn single_star_stays_within_a_component() {
+            assert!(glob_matches("/src/*", Path::new("/src/project")));
+            assert!(!glob_matches("/src/*", Path::new("/src/a/b")));
+            assert!(glob_matches("/src/*-api", Path::new("/src/billing-api")));
+            assert!(!glob_matches("/src/*-api", Path::new("/src/billing-web")));
+        }
+
+        #[test]
+        fn double_star_spans_components() {
+            assert!(glob_matches("/src/**", Path::new("/src")));
+            assert!(glob_matches("/src/**", Path::new("/src/a/b/c")));
+            assert!(glob_matches("/src/**/repo", Path::new("/src/a/b/repo")));
+            assert!(!glob_matches("/src/**/repo", Path::new("/other/repo")));
+        }
+
+        #[test]
+        fn question_mark_matches_one_character() {
+            assert!(glob_matches("/src/v?", Path::new("/src/v1")));
+            assert!(!glob_matches("/src/v?", Path::new("/src/v10")));
+        }
+
+        #[test]
+        fn accepts_windows_separators() {
+            assert!(glob_matches("C:\\dev\\**", Path::new("C:\\dev\\tool")));
+        }
+
+        #[test]
+        fn rejects_patterns_that_never_match() {
+            for valid in ["/src/*", "~/work/**", "**/repo", "C:\\dev\\**", "/src/v?"] {
+                assert_eq!(check_glob(valid), Ok(()), "{}", valid);
+            }
+            for invalid in [
+                "",
+                "work/**",
+                "./api",
+                "/src/[ab]",
+                "/src/{api,cli}",
+                "/src/**api",
+                "~/a**/b",
+            ] {
+                assert!(check_glob(invalid).is_err(), "{}", invalid);
+            }
+        }
+    }
 }
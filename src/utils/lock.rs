@@ -0,0 +1,237 @@
+//! Single-instance locking of DevHealth state
+//!
+//! Scheduled scans, `watch-advisories` from cron and manual runs all update
+//! the same files in the data directory: the scan history, the dependency
+//! inventory, tags, acknowledgements and the advisory database. Each of
+//! those is read, changed and written back whole, so two runs interleaving
+//! would lose one run's changes or leave a half-written file.
+//!
+//! Commands that write state therefore hold an exclusive advisory lock on
+//! `devhealth.lock` in the data directory for as long as they run. The lock
+//! is taken with the operating system's file locking (`flock` on Unix,
+//! `LockFileEx` on Windows), so it is released when the process exits, even
+//! after a crash, and a leftover lock file never blocks anyone. The file
+//! holds the ID of the process owning the lock, to tell users who they are
+//! waiting for.
+
+use crate::utils::paths;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the lock file inside the data directory
+pub const LOCK_FILE: &str = "devhealth.lock";
+
+/// Errors that can occur while taking the state lock
+#[derive(Error, Debug)]
+pub enum LockError {
+    #[error("Failed to lock DevHealth state: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Another DevHealth run{} is updating the same state; retry later or drop --no-wait", holder_suffix(*.0))]
+    Busy(Option<u32>),
+    #[error("Could not determine the DevHealth data directory")]
+    NoStateDirectory,
+}
+
+fn holder_suffix(pid: Option<u32>) -> String {
+    pid.map(|pid| format!(" (process {})", pid))
+        .unwrap_or_default()
+}
+
+/// What to do when another process holds the lock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Block until the other process is done
+    Wait,
+    /// Fail right away with [`LockError::Busy`]
+    NoWait,
+}
+
+/// An exclusive lock on DevHealth state, released when dropped
+#[derive(Debug)]
+pub struct StateLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl StateLock {
+    /// Returns the location of the lock file inside the data directory
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LockError::NoStateDirectory`] if no data directory can be
+    /// determined.
+    pub fn default_path() -> Result<PathBuf, LockError> {
+        paths::data_dir()
+            .map(|dir| dir.join(LOCK_FILE))
+            .ok_or(LockError::NoStateDirectory)
+    }
+
+    /// Takes the lock at `path` without blocking
+    ///
+    /// Creates the file and its parent directories if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LockError::Busy`] with the holder's process ID, if known,
+    /// when another process holds the lock.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::utils::lock::{LockError, StateLock};
+    ///
+    /// let dir = tempfile::TempDir::new().unwrap();
+    /// let path = dir.path().join("devhealth.lock");
+    /// let lock = StateLock::try_acquire(&path).unwrap();
+    /// assert!(matches!(StateLock::try_acquire(&path), Err(LockError::Busy(_))));
+    /// drop(lock);
+    /// assert!(StateLock::try_acquire(&path).is_ok());
+    /// ```
+    pub fn try_acquire(path: &Path) -> Result<StateLock, LockError> {
+        let mut file = open(path)?;
+        match file.try_lock() {
+            Ok(()) => StateLock::claim(file, path),
+            Err(TryLockError::WouldBlock) => Err(LockError::Busy(holder(&mut file))),
+            Err(TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    /// Takes the lock at `path`, blocking until it is free
+    pub fn acquire(path: &Path) -> Result<StateLock, LockError> {
+        let file = open(path)?;
+        file.lock()?;
+        StateLock::claim(file, path)
+    }
+
+    /// Takes the lock at `path` according to `mode`
+    ///
+    /// With [`LockMode::Wait`], `waiting` is called with the holder's
+    /// process ID before blocking, so callers can say what they wait for.
+    pub fn acquire_with(
+        path: &Path,
+        mode: LockMode,
+        waiting: impl FnOnce(Option<u32>),
+    ) -> Result<StateLock, LockError> {
+        match (StateLock::try_acquire(path), mode) {
+            (Err(LockError::Busy(pid)), LockMode::Wait) => {
+                waiting(pid);
+                StateLock::acquire(path)
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Returns the location of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records this process as the holder of a freshly locked file
+    fn claim(mut file: File, path: &Path) -> Result<StateLock, LockError> {
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        write!(file, "{}", std::process::id())?;
+        file.flush()?;
+        Ok(StateLock {
+            file,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+impl Drop for StateLock {
+    fn drop(&mut self) {
+        // Clear the holder first so a process reading it in between does not
+        // name a process that no longer holds the lock
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+/// Opens the lock file for reading and writing without truncating it
+fn open(path: &Path) -> std::io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// Reads the process ID recorded by the holder of the lock
+fn holder(file: &mut File) -> Option<u32> {
+    let mut contents = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut contents).ok()?;
+    contents.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn busy_lock_names_its_holder() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state").join(LOCK_FILE);
+        let lock = StateLock::try_acquire(&path).unwrap();
+        assert_eq!(lock.path(), path);
+
+        match StateLock::try_acquire(&path) {
+            Err(LockError::Busy(pid)) => assert_eq!(pid, Some(std::process::id())),
+            other => panic!("Expected a busy lock, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn released_lock_clears_the_holder() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        drop(StateLock::try_acquire(&path).unwrap());
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        assert!(StateLock::try_acquire(&path).is_ok());
+    }
+
+    #[test]
+    fn no_wait_fails_without_calling_back() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        let _lock = StateLock::try_acquire(&path).unwrap();
+
+        let mut waited = false;
+        let result = StateLock::acquire_with(&path, LockMode::NoWait, |_| waited = true);
+        assert!(matches!(result, Err(LockError::Busy(_))));
+        assert!(!waited);
+    }
+
+    #[test]
+    fn wait_blocks_until_released() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        let lock = StateLock::try_acquire(&path).unwrap();
+
+        let (waiting, waited) = std::sync::mpsc::channel();
+        let waiter = {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                let lock = StateLock::acquire_with(&path, LockMode::Wait, |pid| {
+                    waiting.send(pid).unwrap()
+                })
+                .unwrap();
+                drop(lock);
+            })
+        };
+        let holder = waited.recv().unwrap();
+        drop(lock);
+        waiter.join().unwrap();
+
+        assert_eq!(holder, Some(std::process::id()));
+    }
+}
@@ -2,7 +2,16 @@
 //!
 //! This module contains various utility functions and helpers used throughout
 //! the DevHealth application, including file system operations, display
-//! formatting, and common helper functions.
+//! formatting, color themes, network rate limiting, platform directories, state locking, credential redaction, signature verification, SMTP delivery, WSL detection, and common
+//! helper functions.
 
 pub mod display;
 pub mod fs;
+pub mod lock;
+pub mod net;
+pub mod paths;
+pub mod redact;
+pub mod signature;
+pub mod smtp;
+pub mod theme;
+pub mod wsl;
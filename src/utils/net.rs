@@ -0,0 +1,263 @@
+//! Network utilities for registry and advisory queries
+//!
+//! This module provides the shared plumbing used by every scanner that talks to
+//! a remote package registry or vulnerability database: a simple rate limiter
+//! so large scans don't get a CI machine throttled, and batching helpers for
+//! APIs that accept many packages per request (such as the OSV `querybatch`
+//! endpoint). It also provides the [`NetworkGate`] consulted before any
+//! network-dependent check runs, which implements the global `--offline`
+//! mode.
+//!
+//! The rate defaults to [`DEFAULT_REQUESTS_PER_SECOND`] and can be changed
+//! with the `DEVHEALTH_REQUESTS_PER_SECOND` environment variable, for example
+//! to slow down scans on a shared CI runner (`0` turns the limit off). Every
+//! client builds its limiter with [`RateLimiter::default`], which reads it.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default number of registry requests allowed per second
+pub const DEFAULT_REQUESTS_PER_SECOND: f64 = 5.0;
+
+/// Maximum number of queries the OSV `querybatch` endpoint accepts per request
+pub const OSV_BATCH_LIMIT: usize = 1000;
+
+/// Environment variable overriding [`DEFAULT_REQUESTS_PER_SECOND`]
+pub const REQUESTS_PER_SECOND_VAR: &str = "DEVHEALTH_REQUESTS_PER_SECOND";
+
+/// Limits how often outgoing registry requests are made
+///
+/// The limiter enforces a minimum interval between consecutive requests
+/// derived from a requests-per-second budget. Callers invoke [`RateLimiter::wait`]
+/// before each request; the call blocks only as long as needed to stay within
+/// the configured rate.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    /// Minimum time between two consecutive requests
+    min_interval: Duration,
+    /// When the previous request was allowed through
+    last_request: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter allowing `requests_per_second` requests
+    ///
+    /// A value of zero (or any non-finite or negative value) disables limiting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::utils::net::RateLimiter;
+    ///
+    /// let mut limiter = RateLimiter::new(10.0);
+    /// limiter.wait(); // first request goes through immediately
+    /// ```
+    pub fn new(requests_per_second: f64) -> Self {
+        let min_interval = if requests_per_second.is_finite() && requests_per_second > 0.0 {
+            Duration::from_secs_f64(1.0 / requests_per_second)
+        } else {
+            Duration::ZERO
+        };
+
+        RateLimiter {
+            min_interval,
+            last_request: None,
+        }
+    }
+
+    /// Creates a rate limiter that never delays requests
+    pub fn unlimited() -> Self {
+        RateLimiter::new(0.0)
+    }
+
+    /// Returns the minimum interval enforced between requests
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    /// Blocks until the next request is allowed and records it
+    pub fn wait(&mut self) {
+        let delay = self.delay_at(Instant::now());
+        if !delay.is_zero() {
+            thread::sleep(delay);
+        }
+        self.last_request = Some(Instant::now());
+    }
+
+    /// Computes how long a request made at `now` would have to wait
+    fn delay_at(&self, now: Instant) -> Duration {
+        match self.last_request {
+            Some(last) => (last + self.min_interval).saturating_duration_since(now),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    /// Creates a rate limiter allowing the configured rate: the value of
+    /// [`REQUESTS_PER_SECOND_VAR`], or [`DEFAULT_REQUESTS_PER_SECOND`]
+    fn default() -> Self {
+        RateLimiter::new(configured_rate(
+            std::env::var(REQUESTS_PER_SECOND_VAR).ok().as_deref(),
+        ))
+    }
+}
+
+/// Parses a requests-per-second override, falling back to
+/// [`DEFAULT_REQUESTS_PER_SECOND`] for missing, negative or unparsable values
+fn configured_rate(value: Option<&str>) -> f64 {
+    value
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|rate| rate.is_finite() && *rate >= 0.0)
+        .unwrap_or(DEFAULT_REQUESTS_PER_SECOND)
+}
+
+/// Splits a list of queries into batches of at most `batch_size` items
+///
+/// A `batch_size` of zero is treated as one so callers always make progress.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::net;
+///
+/// let packages = ["serde", "clap", "tokio"];
+/// let batches: Vec<_> = net::batches(&packages, 2).collect();
+/// assert_eq!(batches, vec![&["serde", "clap"][..], &["tokio"][..]]);
+/// ```
+pub fn batches<T>(items: &[T], batch_size: usize) -> std::slice::Chunks<'_, T> {
+    items.chunks(batch_size.max(1))
+}
+
+/// Decides whether network-dependent checks may run during a scan
+///
+/// Every check that needs the network (registry freshness, vulnerability
+/// audits, forge enrichment) asks the gate for permission first. In offline
+/// mode the gate refuses and remembers the check, so the report can state
+/// exactly what was skipped.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkGate {
+    /// Whether network access is disabled for this run
+    offline: bool,
+    /// Names of the checks that were skipped because of offline mode
+    skipped: Vec<String>,
+}
+
+impl NetworkGate {
+    /// Creates a gate for a run that is offline or online
+    pub fn new(offline: bool) -> Self {
+        NetworkGate {
+            offline,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if network access is disabled
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Asks whether the named check may use the network
+    ///
+    /// Returns `true` when online. When offline, records `check` as skipped
+    /// (once) and returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::utils::net::NetworkGate;
+    ///
+    /// let mut gate = NetworkGate::new(true);
+    /// assert!(!gate.allow("registry freshness"));
+    /// assert_eq!(gate.skipped(), ["registry freshness"]);
+    /// ```
+    pub fn allow(&mut self, check: &str) -> bool {
+        if !self.offline {
+            return true;
+        }
+        if !self.skipped.iter().any(|s| s == check) {
+            self.skipped.push(check.to_string());
+        }
+        false
+    }
+
+    /// Returns the checks skipped so far because of offline mode
+    pub fn skipped(&self) -> &[String] {
+        &self.skipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_request_is_not_delayed() {
+        let limiter = RateLimiter::new(1.0);
+        assert_eq!(limiter.delay_at(Instant::now()), Duration::ZERO);
+    }
+
+    #[test]
+    fn delays_requests_inside_the_interval() {
+        let mut limiter = RateLimiter::new(2.0);
+        let start = Instant::now();
+        limiter.last_request = Some(start);
+
+        let delay = limiter.delay_at(start + Duration::from_millis(100));
+        assert_eq!(delay, Duration::from_millis(400));
+
+        let delay = limiter.delay_at(start + Duration::from_secs(1));
+        assert_eq!(
+            delay,
+            Duration::ZERO,
+            "No delay once the interval has passed"
+        );
+    }
+
+    #[test]
+    fn zero_or_invalid_rate_disables_limiting() {
+        assert_eq!(RateLimiter::new(0.0).min_interval(), Duration::ZERO);
+        assert_eq!(RateLimiter::new(-3.0).min_interval(), Duration::ZERO);
+        assert_eq!(RateLimiter::new(f64::NAN).min_interval(), Duration::ZERO);
+        assert_eq!(RateLimiter::unlimited().min_interval(), Duration::ZERO);
+    }
+
+    #[test]
+    fn reads_the_rate_override() {
+        assert_eq!(configured_rate(Some("2")), 2.0);
+        assert_eq!(configured_rate(Some(" 0.5 ")), 0.5);
+        assert_eq!(configured_rate(Some("0")), 0.0, "0 turns the limit off");
+        assert_eq!(configured_rate(None), DEFAULT_REQUESTS_PER_SECOND);
+        assert_eq!(configured_rate(Some("fast")), DEFAULT_REQUESTS_PER_SECOND);
+        assert_eq!(configured_rate(Some("-1")), DEFAULT_REQUESTS_PER_SECOND);
+    }
+
+    #[test]
+    fn splits_items_into_batches() {
+        let items: Vec<usize> = (0..2500).collect();
+        let sizes: Vec<usize> = batches(&items, OSV_BATCH_LIMIT).map(|b| b.len()).collect();
+        assert_eq!(sizes, vec![1000, 1000, 500]);
+    }
+
+    #[test]
+    fn zero_batch_size_still_makes_progress() {
+        let items = [1, 2, 3];
+        assert_eq!(batches(&items, 0).count(), 3);
+    }
+
+    #[test]
+    fn online_gate_allows_every_check() {
+        let mut gate = NetworkGate::new(false);
+        assert!(gate.allow("audit"));
+        assert!(gate.skipped().is_empty());
+    }
+
+    #[test]
+    fn offline_gate_records_each_skipped_check_once() {
+        let mut gate = NetworkGate::new(true);
+        assert!(!gate.allow("audit"));
+        assert!(!gate.allow("audit"));
+        assert!(!gate.allow("registry freshness"));
+        assert_eq!(gate.skipped(), ["audit", "registry freshness"]);
+    }
+}
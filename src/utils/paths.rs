@@ -0,0 +1,293 @@
+//! Platform directories for configuration, cache and data
+//!
+//! DevHealth stores three kinds of files:
+//!
+//! - **config**: `devhealth.toml`
+//! - **data**: the tag store and the advisory database mirror
+//! - **cache**: anything that can be re-fetched (reserved for registry data)
+//!
+//! Each kind lives in the platform's conventional location:
+//!
+//! - **Linux and other Unix** (XDG): `$XDG_CONFIG_HOME/devhealth`,
+//!   `$XDG_DATA_HOME/devhealth` and `$XDG_CACHE_HOME/devhealth`, defaulting
+//!   to `~/.config`, `~/.local/share` and `~/.cache`
+//! - **macOS**: `~/Library/Application Support/devhealth` for config and
+//!   data, `~/Library/Caches/devhealth` for cache
+//! - **Windows**: `%APPDATA%\devhealth` for config, `%LOCALAPPDATA%\devhealth`
+//!   for data, and its `cache` subdirectory for cache
+//!
+//! Explicitly set `XDG_*` variables are honored on macOS too.
+//!
+//! Two overrides take precedence, in this order:
+//!
+//! 1. `DEVHEALTH_HOME` puts everything in one directory (config and data at
+//!    its top level, cache in `cache/`), which is useful for tests and
+//!    shared machines
+//! 2. An existing legacy `~/.devhealth` directory keeps being used the same
+//!    way, so upgrading does not lose configuration or tags
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Name of the per-application directory inside each platform directory
+const APP_DIR: &str = "devhealth";
+
+/// Directory used by DevHealth releases before platform directories
+const LEGACY_DIR: &str = ".devhealth";
+
+/// The kinds of files DevHealth stores
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirKind {
+    /// User configuration (`devhealth.toml`)
+    Config,
+    /// Persistent state such as tags and the advisory database
+    Data,
+    /// Re-creatable downloads
+    Cache,
+}
+
+/// Platform conventions used to resolve directories
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    /// XDG base directories (Linux, BSDs)
+    Xdg,
+    /// Apple's `~/Library` layout
+    MacOs,
+    /// Windows Known Folders, read from their environment variables
+    Windows,
+}
+
+impl Platform {
+    /// Returns the platform DevHealth was compiled for
+    pub fn current() -> Platform {
+        if cfg!(windows) {
+            Platform::Windows
+        } else if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else {
+            Platform::Xdg
+        }
+    }
+}
+
+/// Returns the directory holding `devhealth.toml`
+pub fn config_dir() -> Option<PathBuf> {
+    dir(DirKind::Config)
+}
+
+/// Returns the directory holding tags and the advisory database
+pub fn data_dir() -> Option<PathBuf> {
+    dir(DirKind::Data)
+}
+
+/// Returns the directory for re-creatable downloads
+pub fn cache_dir() -> Option<PathBuf> {
+    dir(DirKind::Cache)
+}
+
+/// Resolves a directory for the current platform and environment
+///
+/// Returns `None` if neither an override nor a home directory is known.
+pub fn dir(kind: DirKind) -> Option<PathBuf> {
+    resolve(
+        kind,
+        Platform::current(),
+        &|name| std::env::var_os(name),
+        &|path| path.is_dir(),
+    )
+}
+
+/// Resolves a directory from an explicit platform and environment
+///
+/// `env` looks up environment variables and `is_dir` checks whether the
+/// legacy directory exists, so every platform can be tested anywhere.
+fn resolve(
+    kind: DirKind,
+    platform: Platform,
+    env: &dyn Fn(&str) -> Option<OsString>,
+    is_dir: &dyn Fn(&PathBuf) -> bool,
+) -> Option<PathBuf> {
+    let var = |name: &str| {
+        env(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    let single = |root: PathBuf| match kind {
+        DirKind::Cache => root.join("cache"),
+        DirKind::Config | DirKind::Data => root,
+    };
+
+    if let Some(root) = var("DEVHEALTH_HOME") {
+        return Some(single(root));
+    }
+
+    let home = match platform {
+        Platform::Windows => var("USERPROFILE").or_else(|| var("HOME")),
+        Platform::Xdg | Platform::MacOs => var("HOME"),
+    };
+    if let Some(legacy) = home.as_ref().map(|home| home.join(LEGACY_DIR)) {
+        if is_dir(&legacy) {
+            return Some(single(legacy));
+        }
+    }
+
+    let base = match platform {
+        Platform::Windows => match kind {
+            DirKind::Config => var("APPDATA"),
+            DirKind::Data | DirKind::Cache => var("LOCALAPPDATA"),
+        }
+        .or_else(|| home.clone().map(|home| home.join("AppData").join("Local"))),
+        Platform::Xdg | Platform::MacOs => {
+            let (xdg, fallback) = match (kind, platform) {
+                (DirKind::Config, Platform::MacOs) => {
+                    ("XDG_CONFIG_HOME", "Library/Application Support")
+                }
+                (DirKind::Data, Platform::MacOs) => {
+                    ("XDG_DATA_HOME", "Library/Application Support")
+                }
+                (DirKind::Cache, Platform::MacOs) => ("XDG_CACHE_HOME", "Library/Caches"),
+                (DirKind::Config, _) => ("XDG_CONFIG_HOME", ".config"),
+                (DirKind::Data, _) => ("XDG_DATA_HOME", ".local/share"),
+                (DirKind::Cache, _) => ("XDG_CACHE_HOME", ".cache"),
+            };
+            // The XDG spec ignores relative paths
+            var(xdg)
+                .filter(|dir| dir.is_absolute())
+                .or_else(|| home.clone().map(|home| home.join(fallback)))
+        }
+    }?;
+
+    let dir = base.join(APP_DIR);
+    Some(match (platform, kind) {
+        (Platform::Windows, DirKind::Cache) => dir.join("cache"),
+        _ => dir,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn resolve_with(
+        kind: DirKind,
+        platform: Platform,
+        vars: &[(&str, &str)],
+        legacy: bool,
+    ) -> PathBuf {
+        let vars: HashMap<String, OsString> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), OsString::from(value)))
+            .collect();
+        resolve(kind, platform, &|name| vars.get(name).cloned(), &|_| legacy).unwrap()
+    }
+
+    mod overrides {
+        use super::*;
+
+        #[test]
+        fn devhealth_home_holds_everything() {
+            let vars = [("DEVHEALTH_HOME", "/state"), ("HOME", "/home/ada")];
+            for platform in [Platform::Xdg, Platform::MacOs, Platform::Windows] {
+                assert_eq!(
+                    resolve_with(DirKind::Config, platform, &vars, true),
+                    PathBuf::from("/state")
+                );
+                assert_eq!(
+                    resolve_with(DirKind::Data, platform, &vars, false),
+                    PathBuf::from("/state")
+                );
+                assert_eq!(
+                    resolve_with(DirKind::Cache, platform, &vars, false),
+                    PathBuf::from("/state/cache")
+                );
+            }
+        }
+
+        #[test]
+        fn existing_legacy_directory_is_kept() {
+            let vars = [("HOME", "/home/ada"), ("XDG_CONFIG_HOME", "/xdg")];
+            assert_eq!(
+                resolve_with(DirKind::Config, Platform::Xdg, &vars, true),
+                PathBuf::from("/home/ada/.devhealth")
+            );
+            assert_eq!(
+                resolve_with(DirKind::Cache, Platform::Xdg, &vars, true),
+                PathBuf::from("/home/ada/.devhealth/cache")
+            );
+        }
+
+        #[test]
+        fn unknown_home_resolves_to_nothing() {
+            assert_eq!(
+                resolve(DirKind::Data, Platform::Xdg, &|_| None, &|_| false),
+                None
+            );
+        }
+    }
+
+    mod platforms {
+        use super::*;
+
+        #[test]
+        fn xdg_uses_base_directory_variables_or_defaults() {
+            let vars = [("HOME", "/home/ada"), ("XDG_CACHE_HOME", "/var/cache/ada")];
+            assert_eq!(
+                resolve_with(DirKind::Config, Platform::Xdg, &vars, false),
+                PathBuf::from("/home/ada/.config/devhealth")
+            );
+            assert_eq!(
+                resolve_with(DirKind::Data, Platform::Xdg, &vars, false),
+                PathBuf::from("/home/ada/.local/share/devhealth")
+            );
+            assert_eq!(
+                resolve_with(DirKind::Cache, Platform::Xdg, &vars, false),
+                PathBuf::from("/var/cache/ada/devhealth")
+            );
+        }
+
+        #[test]
+        fn xdg_ignores_relative_variables() {
+            let vars = [
+                ("HOME", "/home/ada"),
+                ("XDG_CONFIG_HOME", "relative/config"),
+            ];
+            assert_eq!(
+                resolve_with(DirKind::Config, Platform::Xdg, &vars, false),
+                PathBuf::from("/home/ada/.config/devhealth")
+            );
+        }
+
+        #[test]
+        fn macos_uses_library_directories() {
+            let vars = [("HOME", "/Users/ada")];
+            assert_eq!(
+                resolve_with(DirKind::Config, Platform::MacOs, &vars, false),
+                PathBuf::from("/Users/ada/Library/Application Support/devhealth")
+            );
+            assert_eq!(
+                resolve_with(DirKind::Cache, Platform::MacOs, &vars, false),
+                PathBuf::from("/Users/ada/Library/Caches/devhealth")
+            );
+        }
+
+        #[test]
+        fn windows_uses_known_folders() {
+            let vars = [
+                ("USERPROFILE", r"C:\Users\ada"),
+                ("APPDATA", r"C:\Users\ada\AppData\Roaming"),
+                ("LOCALAPPDATA", r"C:\Users\ada\AppData\Local"),
+            ];
+            assert_eq!(
+                resolve_with(DirKind::Config, Platform::Windows, &vars, false),
+                PathBuf::from(r"C:\Users\ada\AppData\Roaming").join("devhealth")
+            );
+            assert_eq!(
+                resolve_with(DirKind::Cache, Platform::Windows, &vars, false),
+                PathBuf::from(r"C:\Users\ada\AppData\Local")
+                    .join("devhealth")
+                    .join("cache")
+            );
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! SSH signature verification
+//!
+//! Documents DevHealth downloads (the organization policy, release
+//! checksums) are signed with an SSH key, the same way git signs commits:
+//!
+//! ```bash
+//! ssh-keygen -Y sign -f key -n <namespace> file
+//! ```
+//!
+//! and verified with `ssh-keygen -Y verify` against a single configured
+//! public key. The namespace keeps a signature made for one kind of document
+//! from being accepted for another.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+/// Errors that can occur while verifying a signature
+#[derive(Error, Debug)]
+pub enum SignatureError {
+    #[error("Failed to prepare signature verification: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Signature is not valid: {0}")]
+    BadSignature(String),
+    #[error("Cannot verify the signature: ssh-keygen is not available ({0})")]
+    VerifierUnavailable(std::io::Error),
+}
+
+/// Verifies an SSH signature of `data` made by `public_key` for `namespace`
+///
+/// Runs `ssh-keygen -Y verify` with a one-entry allowed-signers file written
+/// to `work_dir`, which is created if needed. The temporary files are
+/// removed again.
+///
+/// # Errors
+///
+/// Returns an error if ssh-keygen cannot be run or rejects the signature.
+pub fn verify(
+    data: &[u8],
+    signature: &[u8],
+    public_key: &str,
+    namespace: &str,
+    work_dir: &Path,
+) -> Result<(), SignatureError> {
+    fs::create_dir_all(work_dir)?;
+    let allowed_signers = work_dir.join(format!("allowed_signers.{}", std::process::id()));
+    let signature_path = work_dir.join(format!("incoming.{}.sig", std::process::id()));
+    fs::write(
+        &allowed_signers,
+        format!("{} {}\n", namespace, public_key.trim()),
+    )?;
+    fs::write(&signature_path, signature)?;
+
+    let result = (|| {
+        let mut child = Command::new("ssh-keygen")
+            .arg("-Y")
+            .arg("verify")
+            .arg("-f")
+            .arg(&allowed_signers)
+            .args(["-I", namespace, "-n", namespace, "-s"])
+            .arg(&signature_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(SignatureError::VerifierUnavailable)?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(data)?;
+        }
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            Err(SignatureError::BadSignature(message))
+        }
+    })();
+
+    let _ = fs::remove_file(&allowed_signers);
+    let _ = fs::remove_file(&signature_path);
+    result
+}
@@ -0,0 +1,439 @@
+//! Minimal SMTP client
+//!
+//! Sends one message per connection, which is all the digest needs:
+//!
+//! ```text
+//! EHLO → [STARTTLS → EHLO] → [AUTH PLAIN] → MAIL FROM → RCPT TO… → DATA → QUIT
+//! ```
+//!
+//! Connections are secured with STARTTLS on the submission port (587) by
+//! default, or with TLS from the start (465). Plain connections are only
+//! meant for relays on the local machine or network. Credentials are never
+//! sent over an unencrypted connection.
+
+use base64::Engine;
+use native_tls::{TlsConnector, TlsStream};
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Seconds to wait for the server before giving up
+const TIMEOUT_SECS: u64 = 30;
+
+/// Errors that can occur while sending a message
+#[derive(Error, Debug)]
+pub enum SmtpError {
+    #[error("Failed to connect to {server}: {source}")]
+    Connect {
+        server: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Connection to the SMTP server failed: {0}")]
+    Io(#[from] io::Error),
+    #[error("TLS with the SMTP server failed: {0}")]
+    Tls(String),
+    #[error("SMTP server rejected {command}: {reply}")]
+    Rejected { command: String, reply: String },
+    #[error("Refusing to send credentials over an unencrypted connection; use starttls or tls")]
+    InsecureAuth,
+}
+
+/// How the connection to the server is secured
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Security {
+    /// Start in plain text and upgrade with `STARTTLS`
+    #[default]
+    StartTls,
+    /// TLS from the start
+    Tls,
+    /// No encryption
+    None,
+}
+
+impl Security {
+    /// Returns the usual port for this kind of connection
+    pub fn default_port(self) -> u16 {
+        match self {
+            Security::StartTls => 587,
+            Security::Tls => 465,
+            Security::None => 25,
+        }
+    }
+}
+
+/// Where and as whom to send
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Server {
+    /// Host name of the server
+    pub host: String,
+    /// TCP port
+    pub port: u16,
+    /// How the connection is secured
+    pub security: Security,
+    /// User name and password for `AUTH PLAIN`, if the server needs them
+    pub credentials: Option<(String, String)>,
+}
+
+/// A message ready to send
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    /// Sender address for `MAIL FROM`
+    pub from: String,
+    /// Recipient addresses for `RCPT TO`
+    pub to: Vec<String>,
+    /// The full message with headers, lines ending in `\r\n`
+    pub data: String,
+}
+
+/// A plain or TLS connection to the server
+enum Connection {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Connection::Plain(stream) => stream.read(buf),
+            Connection::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Connection::Plain(stream) => stream.write(buf),
+            Connection::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Connection::Plain(stream) => stream.flush(),
+            Connection::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+/// A connection to the server, reading replies line by line
+struct Session {
+    stream: BufReader<Connection>,
+}
+
+impl Session {
+    /// Reads a possibly multi-line reply, returning its code and text
+    fn reply(&mut self) -> Result<(u16, String), SmtpError> {
+        let mut text = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line)? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "server closed the connection",
+                )
+                .into());
+            }
+            let line = line.trim_end();
+            let code = line
+                .get(..3)
+                .and_then(|code| code.parse().ok())
+                .unwrap_or(0);
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(line.get(4..).unwrap_or(""));
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok((code, text));
+            }
+        }
+    }
+
+    /// Sends one command line
+    fn send(&mut self, command: &str) -> Result<(), SmtpError> {
+        let stream = self.stream.get_mut();
+        stream.write_all(command.as_bytes())?;
+        stream.write_all(b"\r\n")?;
+        stream.flush()?;
+        Ok(())
+    }
+
+    /// Sends `command` and checks that the reply code is `expected`
+    ///
+    /// `shown` replaces the command in errors, so credentials stay out of
+    /// them.
+    fn command(&mut self, command: &str, shown: &str, expected: u16) -> Result<String, SmtpError> {
+        self.send(command)?;
+        self.expect(shown, expected)
+    }
+
+    /// Reads a reply and checks that its code is `expected`
+    fn expect(&mut self, shown: &str, expected: u16) -> Result<String, SmtpError> {
+        let (code, text) = self.reply()?;
+        if code != expected {
+            return Err(SmtpError::Rejected {
+                command: shown.to_string(),
+                reply: format!("{} {}", code, text),
+            });
+        }
+        Ok(text)
+    }
+}
+
+/// Sends `envelope` through `server`
+///
+/// # Errors
+///
+/// Returns an error if the server cannot be reached, TLS fails, or the
+/// server rejects a command.
+pub fn send(server: &Server, envelope: &Envelope) -> Result<(), SmtpError> {
+    if server.credentials.is_some() && server.security == Security::None {
+        return Err(SmtpError::InsecureAuth);
+    }
+    let connect_error = |source| SmtpError::Connect {
+        server: format!("{}:{}", server.host, server.port),
+        source,
+    };
+    let address = (server.host.as_str(), server.port)
+        .to_socket_addrs()
+        .map_err(connect_error)?
+        .next()
+        .ok_or_else(|| connect_error(io::Error::new(io::ErrorKind::NotFound, "no address")))?;
+    let timeout = Duration::from_secs(TIMEOUT_SECS);
+    let tcp = TcpStream::connect_timeout(&address, timeout).map_err(connect_error)?;
+    tcp.set_read_timeout(Some(timeout))?;
+    tcp.set_write_timeout(Some(timeout))?;
+
+    let mut session = Session {
+        stream: BufReader::new(match server.security {
+            Security::Tls => tls(&server.host, tcp)?,
+            Security::StartTls | Security::None => Connection::Plain(tcp),
+        }),
+    };
+    session.expect("connection", 220)?;
+    let hello = format!("EHLO {}", local_name());
+    let mut extensions = session.command(&hello, "EHLO", 250)?;
+
+    if server.security == Security::StartTls {
+        session.command("STARTTLS", "STARTTLS", 220)?;
+        // Anything buffered past the STARTTLS reply would have been sent
+        // unencrypted, so it is refused rather than trusted
+        if !session.stream.buffer().is_empty() {
+            return Err(SmtpError::Tls(
+                "server sent data before the TLS handshake".to_string(),
+            ));
+        }
+        let Connection::Plain(tcp) = session.stream.into_inner() else {
+            unreachable!("STARTTLS only runs on plain connections");
+        };
+        session = Session {
+            stream: BufReader::new(tls(&server.host, tcp)?),
+        };
+        extensions = session.command(&hello, "EHLO", 250)?;
+    }
+
+    if let Some((user, password)) = &server.credentials {
+        if !extensions
+            .lines()
+            .any(|line| line.to_ascii_uppercase().starts_with("AUTH"))
+        {
+            return Err(SmtpError::Rejected {
+                command: "AUTH".to_string(),
+                reply: "server does not offer authentication".to_string(),
+            });
+        }
+        let token =
+            base64::engine::general_purpose::STANDARD.encode(format!("\0{}\0{}", user, password));
+        session.command(&format!("AUTH PLAIN {}", token), "AUTH PLAIN", 235)?;
+    }
+
+    session.command(&format!("MAIL FROM:<{}>", envelope.from), "MAIL FROM", 250)?;
+    for recipient in &envelope.to {
+        let command = format!("RCPT TO:<{}>", recipient);
+        session.send(&command)?;
+        let (code, text) = session.reply()?;
+        if code != 250 && code != 251 {
+            return Err(SmtpError::Rejected {
+                command,
+                reply: format!("{} {}", code, text),
+            });
+        }
+    }
+    session.command("DATA", "DATA", 354)?;
+    session
+        .stream
+        .get_mut()
+        .write_all(dot_stuff(&envelope.data).as_bytes())?;
+    session.command(".", "message", 250)?;
+    // The message is accepted; a failing QUIT does not matter
+    let _ = session.command("QUIT", "QUIT", 221);
+    Ok(())
+}
+
+/// Wraps `tcp` in TLS, verifying the certificate for `host`
+fn tls(host: &str, tcp: TcpStream) -> Result<Connection, SmtpError> {
+    let connector = TlsConnector::new().map_err(|e| SmtpError::Tls(e.to_string()))?;
+    let stream = connector
+        .connect(host, tcp)
+        .map_err(|e| SmtpError::Tls(e.to_string()))?;
+    Ok(Connection::Tls(Box::new(stream)))
+}
+
+/// Returns the name to greet the server with
+fn local_name() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .filter(|name| !name.is_empty() && !name.contains(char::is_whitespace))
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+/// Prepares message data for `DATA`: ends every line in `\r\n` and doubles
+/// dots at the start of lines, so no line ends the data early
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::smtp::dot_stuff;
+///
+/// assert_eq!(dot_stuff("Hi\n.hidden\n"), "Hi\r\n..hidden\r\n");
+/// ```
+pub fn dot_stuff(data: &str) -> String {
+    let mut stuffed = String::with_capacity(data.len() + 16);
+    for line in data.lines() {
+        if line.starts_with('.') {
+            stuffed.push('.');
+        }
+        stuffed.push_str(line);
+        stuffed.push_str("\r\n");
+    }
+    stuffed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serves one session on a local port, answering each command with the
+    /// reply of the first matching prefix, and returns what was received
+    fn fake_server(
+        replies: &'static [(&'static str, &'static str)],
+    ) -> (u16, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut received = Vec::new();
+            let mut in_data = false;
+            writer.write_all(b"220 test ready\r\n").unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end_matches(['\r', '\n']).to_string();
+                received.push(line.clone());
+                if in_data {
+                    if line == "." {
+                        in_data = false;
+                        writer.write_all(b"250 queued\r\n").unwrap();
+                    }
+                    continue;
+                }
+                let reply = replies
+                    .iter()
+                    .find(|(prefix, _)| line.starts_with(prefix))
+                    .map_or("500 unknown\r\n", |(_, reply)| reply);
+                in_data = line == "DATA" && reply.starts_with("354");
+                writer.write_all(reply.as_bytes()).unwrap();
+                if line == "QUIT" {
+                    break;
+                }
+            }
+            received
+        });
+        (port, handle)
+    }
+
+    fn server(port: u16) -> Server {
+        Server {
+            host: "127.0.0.1".to_string(),
+            port,
+            security: Security::None,
+            credentials: None,
+        }
+    }
+
+    fn envelope() -> Envelope {
+        Envelope {
+            from: "dh@example.com".to_string(),
+            to: vec!["a@example.com".to_string(), "b@example.com".to_string()],
+            data: "Subject: Digest\r\n\r\nHello\r\n.dot\r\n".to_string(),
+        }
+    }
+
+    #[test]
+    fn sends_a_message_through_multi_line_replies() {
+        let (port, handle) = fake_server(&[
+            ("EHLO", "250-test\r\n250-SIZE 1000\r\n250 8BITMIME\r\n"),
+            ("MAIL FROM", "250 ok\r\n"),
+            ("RCPT TO", "250 ok\r\n"),
+            ("DATA", "354 go ahead\r\n"),
+            ("QUIT", "221 bye\r\n"),
+        ]);
+        send(&server(port), &envelope()).unwrap();
+
+        let received = handle.join().unwrap();
+        assert!(received[0].starts_with("EHLO "));
+        assert_eq!(received[1], "MAIL FROM:<dh@example.com>");
+        assert_eq!(
+            &received[2..4],
+            ["RCPT TO:<a@example.com>", "RCPT TO:<b@example.com>"]
+        );
+        assert!(received.contains(&"..dot".to_string()));
+        assert_eq!(received.last().map(String::as_str), Some("QUIT"));
+    }
+
+    #[test]
+    fn reports_rejected_recipients() {
+        let (port, handle) = fake_server(&[
+            ("EHLO", "250 test\r\n"),
+            ("MAIL FROM", "250 ok\r\n"),
+            ("RCPT TO", "550 no such user\r\n"),
+        ]);
+        let error = send(&server(port), &envelope()).unwrap_err();
+        drop(handle);
+
+        match error {
+            SmtpError::Rejected { command, reply } => {
+                assert_eq!(command, "RCPT TO:<a@example.com>");
+                assert_eq!(reply, "550 no such user");
+            }
+            other => panic!("Expected a rejection, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn never_sends_credentials_unencrypted() {
+        let server = Server {
+            credentials: Some(("me".to_string(), "secret".to_string())),
+            ..server(1)
+        };
+        assert!(matches!(
+            send(&server, &envelope()),
+            Err(SmtpError::InsecureAuth)
+        ));
+    }
+
+    #[test]
+    fn doubles_leading_dots() {
+        assert_eq!(dot_stuff(".\r\n..\r\na.b"), "..\r\n...\r\na.b\r\n");
+    }
+}
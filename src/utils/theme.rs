@@ -0,0 +1,384 @@
+//! Color themes for terminal output
+//!
+//! Renderers never name colors directly; they paint text with a semantic
+//! [`Role`] (success, warning, error, ...) and the active [`Theme`] decides
+//! which color each role gets. Themes are configured in the `[theme]` section
+//! of `devhealth.toml`:
+//!
+//! ```toml
+//! [theme]
+//! palette = "colorblind"   # "default", "colorblind" or "light"
+//! success = "bright blue"  # optional per-role overrides
+//! muted = "#808080"
+//! ```
+//!
+//! Colors are ANSI names as understood by the `colored` crate (`"red"`,
+//! `"bright red"`, `"bright_red"`) or `#rrggbb` hex values.
+
+use colored::{Color, ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// The theme used by every renderer, set once at startup
+static ACTIVE_THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Errors that can occur while building a theme from configuration
+#[derive(Error, Debug, PartialEq)]
+pub enum ThemeError {
+    #[error("Unknown theme role '{0}' (expected one of: {roles})", roles = Role::NAMES.join(", "))]
+    UnknownRole(String),
+    #[error("Invalid color '{value}' for theme role '{role}'")]
+    InvalidColor { role: String, value: String },
+}
+
+/// What a piece of output means, independent of its color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Healthy state: clean repositories, versions, available fixes
+    Success,
+    /// Needs attention: dirty repositories, dev dependencies
+    Warning,
+    /// Failures and vulnerabilities
+    Error,
+    /// Section markers, labels and headings
+    Accent,
+    /// Branch names, ecosystems and other identifiers
+    Highlight,
+    /// Names and values that should stand out
+    Emphasis,
+    /// Borders, paths and secondary text
+    Muted,
+    /// Optional dependencies and miscellaneous categories
+    Special,
+}
+
+impl Role {
+    /// Configuration names of every role, in declaration order
+    pub const NAMES: [&'static str; 8] = [
+        "success",
+        "warning",
+        "error",
+        "accent",
+        "highlight",
+        "emphasis",
+        "muted",
+        "special",
+    ];
+
+    const ALL: [Role; 8] = [
+        Role::Success,
+        Role::Warning,
+        Role::Error,
+        Role::Accent,
+        Role::Highlight,
+        Role::Emphasis,
+        Role::Muted,
+        Role::Special,
+    ];
+
+    /// Returns the name used for this role in the configuration file
+    pub fn name(self) -> &'static str {
+        Role::NAMES[self as usize]
+    }
+}
+
+impl FromStr for Role {
+    type Err = ThemeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Role::ALL
+            .into_iter()
+            .find(|role| role.name() == s)
+            .ok_or_else(|| ThemeError::UnknownRole(s.to_string()))
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Built-in color palettes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Palette {
+    /// Bright colors for dark terminal backgrounds
+    #[default]
+    Default,
+    /// Blue/orange palette distinguishable with red-green color blindness
+    Colorblind,
+    /// Darker colors readable on light terminal backgrounds
+    Light,
+}
+
+/// The `[theme]` section of `devhealth.toml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Palette the theme starts from
+    pub palette: Palette,
+    /// Per-role color overrides, keyed by role name
+    #[serde(flatten)]
+    pub colors: BTreeMap<String, String>,
+}
+
+/// Colors assigned to every [`Role`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    colors: [Color; 8],
+}
+
+impl Theme {
+    /// Creates the theme of a built-in palette
+    pub fn palette(palette: Palette) -> Self {
+        let colors = match palette {
+            Palette::Default => [
+                Color::BrightGreen,
+                Color::BrightYellow,
+                Color::BrightRed,
+                Color::BrightBlue,
+                Color::BrightCyan,
+                Color::BrightWhite,
+                Color::BrightBlack,
+                Color::BrightMagenta,
+            ],
+            // Okabe-Ito colors: blue for good, orange/vermillion for bad
+            Palette::Colorblind => [
+                Color::TrueColor {
+                    r: 0,
+                    g: 114,
+                    b: 178,
+                },
+                Color::TrueColor {
+                    r: 230,
+                    g: 159,
+                    b: 0,
+                },
+                Color::TrueColor {
+                    r: 213,
+                    g: 94,
+                    b: 0,
+                },
+                Color::TrueColor {
+                    r: 86,
+                    g: 180,
+                    b: 233,
+                },
+                Color::TrueColor {
+                    r: 240,
+                    g: 228,
+                    b: 66,
+                },
+                Color::BrightWhite,
+                Color::BrightBlack,
+                Color::TrueColor {
+                    r: 204,
+                    g: 121,
+                    b: 167,
+                },
+            ],
+            Palette::Light => [
+                Color::Green,
+                Color::TrueColor {
+                    r: 175,
+                    g: 95,
+                    b: 0,
+                },
+                Color::Red,
+                Color::Blue,
+                Color::Cyan,
+                Color::Black,
+                Color::BrightBlack,
+                Color::Magenta,
+            ],
+        };
+        Theme { colors }
+    }
+
+    /// Builds a theme from the `[theme]` configuration section
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown role names or unparsable colors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::utils::theme::{Palette, Role, Theme, ThemeConfig};
+    /// use colored::Color;
+    ///
+    /// let mut config = ThemeConfig { palette: Palette::Light, ..Default::default() };
+    /// config.colors.insert("success".to_string(), "bright blue".to_string());
+    ///
+    /// let theme = Theme::from_config(&config).unwrap();
+    /// assert_eq!(theme.color(Role::Success), Color::BrightBlue);
+    /// assert_eq!(theme.color(Role::Error), Color::Red);
+    /// ```
+    pub fn from_config(config: &ThemeConfig) -> Result<Self, ThemeError> {
+        let mut theme = Theme::palette(config.palette);
+        for (name, value) in &config.colors {
+            let role: Role = name.parse()?;
+            let color = parse_color(value).ok_or_else(|| ThemeError::InvalidColor {
+                role: name.clone(),
+                value: value.clone(),
+            })?;
+            theme.colors[role as usize] = color;
+        }
+        Ok(theme)
+    }
+
+    /// Returns the color assigned to `role`
+    pub fn color(&self, role: Role) -> Color {
+        self.colors[role as usize]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::palette(Palette::Default)
+    }
+}
+
+/// Installs the theme used for the rest of the process
+///
+/// Returns `false` if a theme was already active (the first one wins).
+pub fn set_theme(theme: Theme) -> bool {
+    ACTIVE_THEME.set(theme).is_ok()
+}
+
+/// Returns the active theme, or the default theme if none was installed
+pub fn theme() -> &'static Theme {
+    ACTIVE_THEME.get_or_init(Theme::default)
+}
+
+/// Paints text with the color the active theme assigns to a role
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::theme::{Paint, Role};
+/// use colored::Colorize;
+///
+/// println!("{} Clean", "✓".paint(Role::Success).bold());
+/// ```
+pub trait Paint {
+    /// Colors `self` for `role`
+    fn paint(&self, role: Role) -> ColoredString;
+}
+
+impl Paint for str {
+    fn paint(&self, role: Role) -> ColoredString {
+        self.color(theme().color(role))
+    }
+}
+
+/// Parses an ANSI color name or `#rrggbb` hex value
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::TrueColor {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        });
+    }
+
+    value.replace(['_', '-'], " ").parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_palette_keeps_the_original_colors() {
+        let theme = Theme::default();
+        assert_eq!(theme.color(Role::Success), Color::BrightGreen);
+        assert_eq!(theme.color(Role::Error), Color::BrightRed);
+        assert_eq!(theme.color(Role::Muted), Color::BrightBlack);
+    }
+
+    #[test]
+    fn colorblind_palette_avoids_red_green_pairs() {
+        let theme = Theme::palette(Palette::Colorblind);
+        assert_ne!(theme.color(Role::Success), Color::BrightGreen);
+        assert_ne!(theme.color(Role::Error), Color::BrightRed);
+    }
+
+    #[test]
+    fn parses_color_names_and_hex_values() {
+        assert_eq!(parse_color("red"), Some(Color::Red));
+        assert_eq!(parse_color("bright_cyan"), Some(Color::BrightCyan));
+        assert_eq!(parse_color("Bright-Blue"), Some(Color::BrightBlue));
+        assert_eq!(
+            parse_color("#0072b2"),
+            Some(Color::TrueColor {
+                r: 0,
+                g: 114,
+                b: 178
+            })
+        );
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("#gg0000"), None);
+        assert_eq!(parse_color("mauve"), None);
+    }
+
+    #[test]
+    fn applies_overrides_on_top_of_palette() {
+        let mut config = ThemeConfig {
+            palette: Palette::Light,
+            ..Default::default()
+        };
+        config
+            .colors
+            .insert("muted".to_string(), "#808080".to_string());
+
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(
+            theme.color(Role::Muted),
+            Color::TrueColor {
+                r: 128,
+                g: 128,
+                b: 128
+            }
+        );
+        assert_eq!(theme.color(Role::Emphasis), Color::Black);
+    }
+
+    #[test]
+    fn rejects_unknown_roles_and_colors() {
+        let mut config = ThemeConfig::default();
+        config
+            .colors
+            .insert("sparkle".to_string(), "red".to_string());
+        assert_eq!(
+            Theme::from_config(&config),
+            Err(ThemeError::UnknownRole("sparkle".to_string()))
+        );
+
+        let mut config = ThemeConfig::default();
+        config
+            .colors
+            .insert("error".to_string(), "mauve".to_string());
+        assert!(matches!(
+            Theme::from_config(&config),
+            Err(ThemeError::InvalidColor { .. })
+        ));
+    }
+
+    #[test]
+    fn role_names_round_trip() {
+        for name in Role::NAMES {
+            assert_eq!(name.parse::<Role>().unwrap().name(), name);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Windows Subsystem for Linux (WSL) support
+//!
+//! Inside WSL, Windows drives are mounted under `/mnt/<drive>` through a
+//! file system bridge that is many times slower than the Linux file system.
+//! Scanning `/mnt/c/dev` works, but walking thousands of files and running
+//! `git status` on each repository can take minutes instead of seconds.
+//!
+//! This module detects WSL, recognizes paths on Windows drives so DevHealth
+//! can warn about them, and translates Windows paths such as `C:\dev` that
+//! are passed on the command line into their WSL mount points.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Default directory Windows drives are mounted under
+const DEFAULT_MOUNT_ROOT: &str = "/mnt";
+
+/// The WSL environment DevHealth is running in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WslInfo {
+    /// WSL major version (1 or 2)
+    pub version: u8,
+    /// Name of the distribution, from `WSL_DISTRO_NAME`
+    pub distro: Option<String>,
+}
+
+impl fmt::Display for WslInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.distro {
+            Some(distro) => write!(f, "WSL {} ({})", self.version, distro),
+            None => write!(f, "WSL {}", self.version),
+        }
+    }
+}
+
+/// Detects whether DevHealth is running inside WSL
+///
+/// Reads the kernel release from `/proc/sys/kernel/osrelease`, which WSL
+/// kernels mark with `microsoft`. Returns `None` everywhere else.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::wsl;
+///
+/// if let Some(info) = wsl::detect() {
+///     println!("Running in {}", info);
+/// }
+/// ```
+pub fn detect() -> Option<WslInfo> {
+    let osrelease = std::fs::read_to_string("/proc/sys/kernel/osrelease").ok()?;
+    let version = parse_version(&osrelease)?;
+    let distro = std::env::var("WSL_DISTRO_NAME")
+        .ok()
+        .filter(|name| !name.is_empty());
+
+    Some(WslInfo { version, distro })
+}
+
+/// Returns the Windows drive letter `path` lives on, if it is a WSL mount
+///
+/// Only paths below the automount root (`/mnt` unless changed in
+/// `/etc/wsl.conf`) with a single-letter directory count as Windows drives.
+///
+/// # Examples
+///
+/// ```rust
+/// use devhealth::utils::wsl;
+/// use std::path::Path;
+///
+/// assert_eq!(wsl::windows_drive(Path::new("/mnt/c/dev/api")), Some('C'));
+/// assert_eq!(wsl::windows_drive(Path::new("/home/ada/dev")), None);
+/// ```
+pub fn windows_drive(path: &Path) -> Option<char> {
+    drive_under(path, &mount_root())
+}
+
+/// Translates a Windows path passed on the command line into a WSL path
+///
+/// Inside WSL, drive paths such as `C:\dev\api` become `/mnt/c/dev/api`, and
+/// paths into the distribution itself such as `\\wsl$\Ubuntu\home\ada`
+/// become `/home/ada`. Paths that already exist, and every path outside WSL,
+/// are returned unchanged.
+pub fn resolve_path(path: &Path) -> PathBuf {
+    if path.exists() || detect().is_none() {
+        return path.to_path_buf();
+    }
+
+    path.to_str()
+        .and_then(|windows| translate(windows, &mount_root()))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Returns the WSL major version if a kernel release belongs to WSL
+///
+/// WSL 2 kernels end in `-microsoft-standard-WSL2`; WSL 1 reports the
+/// Windows build with a `-Microsoft` suffix.
+fn parse_version(osrelease: &str) -> Option<u8> {
+    let release = osrelease.trim().to_lowercase();
+    if !release.contains("microsoft") {
+        return None;
+    }
+    Some(
+        if release.contains("wsl2") || release.contains("microsoft-standard") {
+            2
+        } else {
+            1
+        },
+    )
+}
+
+/// Returns the directory Windows drives are mounted under
+fn mount_root() -> PathBuf {
+    std::fs::read_to_string("/etc/wsl.conf")
+        .ok()
+        .and_then(|conf| parse_mount_root(&conf))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_MOUNT_ROOT))
+}
+
+/// Extracts `root` from the `[automount]` section of `wsl.conf`
+fn parse_mount_root(conf: &str) -> Option<PathBuf> {
+    let mut in_automount = false;
+    for line in conf.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_automount = line.eq_ignore_ascii_case("[automount]");
+        } else if let Some((key, value)) = line.split_once('=') {
+            if in_automount && key.trim() == "root" {
+                let root = value.trim().trim_matches('"').trim_end_matches('/');
+                return (!root.is_empty()).then(|| PathBuf::from(root));
+            }
+        }
+    }
+    None
+}
+
+/// Returns the drive letter of a path directly below `root`
+fn drive_under(path: &Path, root: &Path) -> Option<char> {
+    let drive = path.strip_prefix(root).ok()?.components().next()?;
+    let drive = drive.as_os_str().to_str()?;
+
+    let mut chars = drive.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) if letter.is_ascii_alphabetic() => Some(letter.to_ascii_uppercase()),
+        _ => None,
+    }
+}
+
+/// Translates a Windows drive or `\\wsl$` path to its WSL equivalent
+fn translate(path: &str, root: &Path) -> Option<PathBuf> {
+    let path = path.replace('\\', "/");
+
+    for share in ["//wsl$/", "//wsl.localhost/"] {
+        if let Some(rest) = path
+            .get(..share.len())
+            .filter(|p| p.eq_ignore_ascii_case(share))
+        {
+            let inside = &path[rest.len()..];
+            let (_distro, inside) = inside.split_once('/').unwrap_or((inside, ""));
+            return Some(Path::new("/").join(inside));
+        }
+    }
+
+    let bytes = path.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        let drive = (bytes[0] as char).to_ascii_lowercase().to_string();
+        let rest = path[2..].trim_start_matches('/');
+        let mut translated = root.join(drive);
+        if !rest.is_empty() {
+            translated.push(rest);
+        }
+        return Some(translated);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod detection {
+        use super::*;
+
+        #[test]
+        fn recognizes_wsl_kernels() {
+            assert_eq!(
+                parse_version("5.15.153.1-microsoft-standard-WSL2\n"),
+                Some(2)
+            );
+            assert_eq!(parse_version("4.4.0-19041-Microsoft"), Some(1));
+            assert_eq!(parse_version("6.8.0-45-generic"), None);
+        }
+
+        #[test]
+        fn reads_automount_root_from_wsl_conf() {
+            let conf = "[boot]\nsystemd=true\n\n[automount]\nenabled = true\nroot = /win/\n";
+            assert_eq!(parse_mount_root(conf), Some(PathBuf::from("/win")));
+            assert_eq!(parse_mount_root("[network]\nroot = /nope\n"), None);
+        }
+
+        #[test]
+        fn displays_version_and_distro() {
+            let info = WslInfo {
+                version: 2,
+                distro: Some("Ubuntu".to_string()),
+            };
+            assert_eq!(info.to_string(), "WSL 2 (Ubuntu)");
+            assert_eq!(
+                WslInfo {
+                    version: 1,
+                    distro: None
+                }
+                .to_string(),
+                "WSL 1"
+            );
+        }
+    }
+
+    mod paths {
+        use super::*;
+
+        #[test]
+        fn finds_drives_below_the_mount_root() {
+            let root = Path::new("/mnt");
+            assert_eq!(drive_under(Path::new("/mnt/c"), root), Some('C'));
+            assert_eq!(drive_under(Path::new("/mnt/d/src/api"), root), Some('D'));
+            assert_eq!(drive_under(Path::new("/mnt/wsl/share"), root), None);
+            assert_eq!(drive_under(Path::new("/mnt"), root), None);
+            assert_eq!(drive_under(Path::new("/home/ada"), root), None);
+        }
+
+        #[test]
+        fn translates_drive_paths() {
+            let root = Path::new("/mnt");
+            assert_eq!(
+                translate(r"C:\dev\api", root),
+                Some(PathBuf::from("/mnt/c/dev/api"))
+            );
+            assert_eq!(translate("D:/src", root), Some(PathBuf::from("/mnt/d/src")));
+            assert_eq!(translate(r"C:\", root), Some(PathBuf::from("/mnt/c")));
+            assert_eq!(
+                translate(r"C:\dev", Path::new("/win")),
+                Some(PathBuf::from("/win/c/dev"))
+            );
+        }
+
+        #[test]
+        fn translates_distribution_shares() {
+            let root = Path::new("/mnt");
+            assert_eq!(
+                translate(r"\\wsl$\Ubuntu\home\ada", root),
+                Some(PathBuf::from("/home/ada"))
+            );
+            assert_eq!(
+                translate(r"\\wsl.localhost\Debian\srv", root),
+                Some(PathBuf::from("/srv"))
+            );
+        }
+
+        #[test]
+        fn leaves_other_paths_alone() {
+            let root = Path::new("/mnt");
+            assert_eq!(translate("/home/ada", root), None);
+            assert_eq!(translate(r"\\server\share", root), None);
+            assert_eq!(translate("dev/api", root), None);
+        }
+    }
+}
@@ -0,0 +1,564 @@
+//! Advisory watch (`watch-advisories`)
+//!
+//! Every `scan --deps` or `scan --audit` stores the dependencies it found in
+//! `dependency-inventory.json` inside the DevHealth data directory, per
+//! project. `devhealth watch-advisories` audits that inventory against the
+//! advisory database, refreshed from OSV unless offline, and alerts about
+//! advisories that were not there at the previous check, without scanning
+//! the filesystem again.
+//!
+//! The advisories already known are kept in `advisory-watch.json`. The first
+//! check only records them, so later checks alert about newly published
+//! advisories alone. Alerts are printed and, unless `--no-notify` is given,
+//! sent as a desktop notification (`notify-send` on Linux, `osascript` on
+//! macOS, a PowerShell balloon tip on Windows).
+//!
+//! Run it from cron or a scheduled task, or give `--interval` to keep
+//! checking in the foreground.
+
+use crate::i18n::t;
+use crate::scanner::audit::Vulnerability;
+use crate::scanner::deps::{Dependency, DependencyReport, Ecosystem};
+use crate::tr;
+use crate::utils::display;
+use crate::utils::paths;
+use crate::utils::theme::{Paint, Role};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+/// File name of the dependency inventory inside the data directory
+pub const INVENTORY_FILE: &str = "dependency-inventory.json";
+
+/// File name of the advisories already alerted about
+pub const WATCH_FILE: &str = "advisory-watch.json";
+
+/// Errors that can occur while reading or writing the inventory or the
+/// watch state
+#[derive(Error, Debug)]
+pub enum WatchError {
+    #[error("Failed to access the advisory watch state: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse the advisory watch state: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    #[error("Could not determine the DevHealth data directory")]
+    NoStateDirectory,
+    #[error("No dependency inventory yet; run `devhealth scan --deps` on your projects first")]
+    NoInventory,
+}
+
+/// Returns the location of a watch file inside the data directory
+fn data_file(name: &str) -> Result<PathBuf, WatchError> {
+    paths::data_dir()
+        .map(|dir| dir.join(name))
+        .ok_or(WatchError::NoStateDirectory)
+}
+
+/// Reads a JSON file, returning the default value if it is missing
+fn load_json<T: Default + for<'de> Deserialize<'de>>(path: &Path) -> Result<T, WatchError> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Writes a JSON file, creating parent directories if needed
+fn save_json<T: Serialize>(value: &T, path: &Path) -> Result<(), WatchError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+/// The dependencies of every project scanned so far
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependencyInventory {
+    /// When a project was last added or updated, as a Unix timestamp
+    pub updated_at: u64,
+    /// Dependencies by project directory
+    pub projects: BTreeMap<PathBuf, Vec<Dependency>>,
+}
+
+impl DependencyInventory {
+    /// Returns the location of the inventory inside the data directory
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WatchError::NoStateDirectory`] if no data directory can be
+    /// determined.
+    pub fn default_path() -> Result<PathBuf, WatchError> {
+        data_file(INVENTORY_FILE)
+    }
+
+    /// Loads the inventory from `path`, returning an empty inventory if missing
+    pub fn load(path: &Path) -> Result<DependencyInventory, WatchError> {
+        load_json(path)
+    }
+
+    /// Writes the inventory to `path`, creating parent directories if needed
+    pub fn save(&self, path: &Path) -> Result<(), WatchError> {
+        save_json(self, path)
+    }
+
+    /// Replaces the projects under `root` with those of a new scan of it
+    ///
+    /// Projects under `root` missing from `reports` were removed since the
+    /// previous scan and are dropped; projects elsewhere are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use devhealth::scanner::deps::DependencyReport;
+    /// use devhealth::watch::DependencyInventory;
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let mut inventory = DependencyInventory::default();
+    /// let report = DependencyReport {
+    ///     project_path: PathBuf::from("/src/api"),
+    ///     dependencies: Vec::new(),
+    ///     ecosystems: Vec::new(),
+    ///     errors: Vec::new(),
+    ///     lockfiles: Vec::new(),
+    ///     go_modules: Vec::new(),
+    /// };
+    /// inventory.update(Path::new("/src"), &[report], 1_000);
+    /// assert!(inventory.projects.contains_key(Path::new("/src/api")));
+    ///
+    /// inventory.update(Path::new("/src"), &[], 2_000);
+    /// assert!(inventory.projects.is_empty());
+    /// ```
+    pub fn update(&mut self, root: &Path, reports: &[DependencyReport], now: u64) {
+        self.projects
+            .retain(|project, _| !project.starts_with(root));
+        self.merge(reports, now);
+    }
+
+    /// Adds or replaces the projects of `reports`, keeping all others
+    ///
+    /// Used for scans filtered by tag, which do not see every project under
+    /// their root.
+    pub fn merge(&mut self, reports: &[DependencyReport], now: u64) {
+        for report in reports {
+            self.projects
+                .insert(report.project_path.clone(), report.dependencies.clone());
+        }
+        self.updated_at = now;
+    }
+
+    /// Returns the inventory as dependency reports, ready to audit
+    pub fn reports(&self) -> Vec<DependencyReport> {
+        self.projects
+            .iter()
+            .map(|(project, dependencies)| {
+                let mut ecosystems: Vec<Ecosystem> = Vec::new();
+                for dependency in dependencies {
+                    if !ecosystems.contains(&dependency.ecosystem) {
+                        ecosystems.push(dependency.ecosystem.clone());
+                    }
+                }
+                DependencyReport {
+                    project_path: project.clone(),
+                    dependencies: dependencies.clone(),
+                    ecosystems,
+                    errors: Vec::new(),
+                    lockfiles: Vec::new(),
+                    go_modules: Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the number of dependencies across all projects
+    pub fn dependency_count(&self) -> usize {
+        self.projects.values().map(Vec::len).sum()
+    }
+}
+
+/// Stores the dependencies found by a scan of `root` in the inventory
+///
+/// Paths are made absolute so that `watch-advisories` can name the affected
+/// projects from any directory. With `filtered`, the scan only saw some of
+/// the projects under `root` and the others are kept.
+///
+/// # Errors
+///
+/// Returns an error if the inventory cannot be read or written.
+pub fn record_scan(
+    root: &Path,
+    reports: &[DependencyReport],
+    filtered: bool,
+    now: u64,
+) -> Result<(), WatchError> {
+    let absolute = |path: &Path| std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let reports: Vec<DependencyReport> = reports
+        .iter()
+        .map(|report| {
+            let mut report = report.clone();
+            report.project_path = absolute(&report.project_path);
+            for dependency in &mut report.dependencies {
+                dependency.source_file = absolute(&dependency.source_file);
+            }
+            report
+        })
+        .collect();
+
+    let path = DependencyInventory::default_path()?;
+    let mut inventory = DependencyInventory::load(&path)?;
+    if filtered {
+        inventory.merge(&reports, now);
+    } else {
+        inventory.update(&absolute(root), &reports, now);
+    }
+    inventory.save(&path)
+}
+
+/// A newly published advisory and what it affects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdvisoryAlert {
+    /// Identifier of the advisory
+    pub advisory_id: String,
+    /// Summary of the advisory
+    pub summary: String,
+    /// Affected package
+    pub package: String,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+    /// Affected versions in use
+    pub versions: Vec<String>,
+    /// First version containing a fix, if known
+    pub fixed_version: Option<String>,
+    /// Projects with an affected version
+    pub projects: Vec<PathBuf>,
+}
+
+/// The advisories already known at the previous check
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchState {
+    /// When the previous check ran, as a Unix timestamp; `None` before the
+    /// first check
+    pub checked_at: Option<u64>,
+    /// `<advisory> <package>` for every advisory matching the inventory
+    pub known: BTreeSet<String>,
+}
+
+impl WatchState {
+    /// Returns the location of the watch state inside the data directory
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WatchError::NoStateDirectory`] if no data directory can be
+    /// determined.
+    pub fn default_path() -> Result<PathBuf, WatchError> {
+        data_file(WATCH_FILE)
+    }
+
+    /// Loads the watch state from `path`, returning an empty state if missing
+    pub fn load(path: &Path) -> Result<WatchState, WatchError> {
+        load_json(path)
+    }
+
+    /// Writes the watch state to `path`, creating parent directories if needed
+    pub fn save(&self, path: &Path) -> Result<(), WatchError> {
+        save_json(self, path)
+    }
+
+    /// Records the vulnerabilities found by a check and returns the
+    /// advisories that were not known before, one alert per advisory and
+    /// package
+    ///
+    /// The first check only records what it finds and returns no alerts.
+    /// Advisories that no longer match, because the dependency was updated
+    /// or the project removed, are forgotten.
+    pub fn check(&mut self, vulnerabilities: &[Vulnerability], now: u64) -> Vec<AdvisoryAlert> {
+        let first_check = self.checked_at.is_none();
+        let mut alerts: BTreeMap<String, AdvisoryAlert> = BTreeMap::new();
+        let mut known = BTreeSet::new();
+        for vuln in vulnerabilities {
+            let key = format!("{} {}", vuln.advisory_id, vuln.dependency.name);
+            known.insert(key.clone());
+            if first_check || self.known.contains(&key) {
+                continue;
+            }
+            let alert = alerts.entry(key).or_insert_with(|| AdvisoryAlert {
+                advisory_id: vuln.advisory_id.clone(),
+                summary: vuln.summary.clone(),
+                package: vuln.dependency.name.clone(),
+                ecosystem: vuln.dependency.ecosystem.clone(),
+                versions: Vec::new(),
+                fixed_version: vuln.fixed_version.clone(),
+                projects: Vec::new(),
+            });
+            if !alert.versions.contains(&vuln.version) {
+                alert.versions.push(vuln.version.clone());
+            }
+            if let Some(project) = vuln.dependency.source_file.parent() {
+                if !alert.projects.iter().any(|known| known == project) {
+                    alert.projects.push(project.to_path_buf());
+                }
+            }
+        }
+        self.known = known;
+        self.checked_at = Some(now);
+        alerts.into_values().collect()
+    }
+}
+
+/// Sends a desktop notification, returning `true` if one was shown
+///
+/// Uses `notify-send` on Linux and other Unix systems, `osascript` on macOS
+/// and a PowerShell balloon tip on Windows. Fails quietly where none of them
+/// is available, e.g. on a headless server.
+///
+/// The balloon tip disappears with the PowerShell process that shows it, so
+/// that process is left running in the background for the ten seconds the
+/// tip is shown rather than waited for.
+pub fn notify(title: &str, body: &str) -> bool {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(title)
+        );
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else if cfg!(windows) {
+        let quote = |text: &str| format!("'{}'", text.replace('\'', "''"));
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             $icon = New-Object System.Windows.Forms.NotifyIcon; \
+             $icon.Icon = [System.Drawing.SystemIcons]::Warning; $icon.Visible = $true; \
+             $icon.ShowBalloonTip(10000, {}, {}, 'Warning'); Start-Sleep -Seconds 10; $icon.Dispose()",
+            quote(title),
+            quote(body)
+        );
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", &script]);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args(["--urgency=critical", "--app-name=DevHealth", title, body]);
+        command
+    };
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    if cfg!(windows) {
+        return command.spawn().is_ok();
+    }
+    command.status().is_ok_and(|status| status.success())
+}
+
+/// Quotes text as an AppleScript string literal
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Returns the title and body of the desktop notification for `alerts`
+pub fn notification(alerts: &[AdvisoryAlert]) -> (String, String) {
+    let title = tr!("watch-notification-title", count = alerts.len());
+    let lines: Vec<String> = alerts
+        .iter()
+        .map(|alert| {
+            format!(
+                "{} {}: {}",
+                alert.package,
+                alert.versions.join(", "),
+                alert.advisory_id
+            )
+        })
+        .collect();
+    (title, lines.join("\n"))
+}
+
+/// Displays the outcome of a check
+///
+/// # Arguments
+///
+/// * `inventory` - The inventory that was audited
+/// * `alerts` - Advisories that are new since the previous check
+/// * `first_check` - Whether this check only recorded the known advisories
+/// * `known` - Number of advisories matching the inventory
+pub fn display_check(
+    inventory: &DependencyInventory,
+    alerts: &[AdvisoryAlert],
+    first_check: bool,
+    known: usize,
+) {
+    let watching = tr!(
+        "watch-watching",
+        dependencies = inventory.dependency_count(),
+        projects = inventory.projects.len()
+    );
+    println!("{} {}", display::symbols("👀"), watching);
+
+    if first_check {
+        println!(
+            "{} {}",
+            display::symbols("📋"),
+            tr!("watch-baseline", count = known).paint(Role::Muted)
+        );
+        return;
+    }
+    if alerts.is_empty() {
+        println!(
+            "{} {}",
+            display::symbols("✅"),
+            t("watch-no-new").paint(Role::Success)
+        );
+        return;
+    }
+
+    println!(
+        "{}",
+        display::header(
+            &tr!("watch-new-title", count = alerts.len()),
+            "🚨",
+            Role::Error
+        )
+    );
+    for (index, alert) in alerts.iter().enumerate() {
+        let fix = match &alert.fixed_version {
+            Some(fixed) => tr!("exposure-fixed-in", version = fixed),
+            None => t("exposure-no-fix").to_string(),
+        };
+        let header = format!(
+            "{} {} {} {}",
+            alert.advisory_id.paint(Role::Error).bold(),
+            alert.package.paint(Role::Highlight),
+            alert.versions.join(", ").paint(Role::Warning),
+            fix.paint(Role::Success)
+        );
+        let is_last = index == alerts.len() - 1;
+        println!("{}", display::tree_item(&header, is_last, 0));
+        if !alert.summary.is_empty() {
+            println!("{}", display::tree_item(&alert.summary, false, 1));
+        }
+        let projects: Vec<String> = alert
+            .projects
+            .iter()
+            .map(|project| project.display().to_string())
+            .collect();
+        println!(
+            "{}",
+            display::tree_item(&display::file_path(&projects.join(", ")), true, 1)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::deps::DependencyType;
+    use tempfile::TempDir;
+
+    fn dependency(project: &str, name: &str, version: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependency_type: DependencyType::Runtime,
+            ecosystem: Ecosystem::Rust,
+            source_file: PathBuf::from(project).join("Cargo.toml"),
+            extras: Vec::new(),
+            marker: None,
+        }
+    }
+
+    fn vulnerability(project: &str, name: &str, advisory: &str) -> Vulnerability {
+        Vulnerability {
+            advisory_id: advisory.to_string(),
+            summary: format!("{} is broken", name),
+            aliases: Vec::new(),
+            dependency: dependency(project, name, "1.0.0"),
+            version: "1.0.0".to_string(),
+            fixed_version: Some("1.0.1".to_string()),
+        }
+    }
+
+    #[test]
+    fn alerts_only_about_advisories_published_since_the_previous_check() {
+        let mut state = WatchState::default();
+        let known = [vulnerability("/src/api", "time", "RUSTSEC-1")];
+        assert!(
+            state.check(&known, 100).is_empty(),
+            "The first check records a baseline"
+        );
+
+        let published = [
+            vulnerability("/src/api", "time", "RUSTSEC-1"),
+            vulnerability("/src/api", "smallvec", "RUSTSEC-2"),
+            vulnerability("/src/web", "smallvec", "RUSTSEC-2"),
+        ];
+        let alerts = state.check(&published, 200);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].advisory_id, "RUSTSEC-2");
+        assert_eq!(
+            alerts[0].projects,
+            [PathBuf::from("/src/api"), PathBuf::from("/src/web")]
+        );
+        assert_eq!(state.checked_at, Some(200));
+
+        assert!(state.check(&published, 300).is_empty());
+        assert_eq!(state.known.len(), 2);
+    }
+
+    #[test]
+    fn inventory_round_trips_and_audits_as_reports() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state").join(INVENTORY_FILE);
+        assert!(DependencyInventory::load(&path)
+            .unwrap()
+            .projects
+            .is_empty());
+
+        let mut inventory = DependencyInventory::default();
+        let report = |project: &str| DependencyReport {
+            project_path: PathBuf::from(project),
+            dependencies: vec![dependency(project, "serde", "1.0.197")],
+            ecosystems: vec![Ecosystem::Rust],
+            errors: Vec::new(),
+            lockfiles: Vec::new(),
+            go_modules: Vec::new(),
+        };
+        inventory.update(
+            Path::new("/src"),
+            &[report("/src/api"), report("/src/web")],
+            100,
+        );
+        inventory.update(Path::new("/work"), &[report("/work/tool")], 200);
+        inventory.update(Path::new("/src"), &[report("/src/api")], 300);
+        inventory.save(&path).unwrap();
+
+        let loaded = DependencyInventory::load(&path).unwrap();
+        assert_eq!(loaded.updated_at, 300);
+        let projects: Vec<PathBuf> = loaded
+            .reports()
+            .into_iter()
+            .map(|report| report.project_path)
+            .collect();
+        assert_eq!(
+            projects,
+            [PathBuf::from("/src/api"), PathBuf::from("/work/tool")]
+        );
+        assert_eq!(loaded.dependency_count(), 2);
+    }
+
+    #[test]
+    fn summarizes_alerts_for_notifications() {
+        let mut state = WatchState {
+            checked_at: Some(0),
+            ..Default::default()
+        };
+        let alerts = state.check(&[vulnerability("/src/api", "time", "RUSTSEC-1")], 100);
+        let (title, body) = notification(&alerts);
+        assert!(title.contains('1'));
+        assert_eq!(body, "time 1.0.0: RUSTSEC-1");
+        assert_eq!(applescript_string("say \"hi\""), "\"say \\\"hi\\\"\"");
+    }
+}